@@ -28,17 +28,21 @@
 //!       text: Text::from("My Heading"),
 //!     },
 //!     source_range: Range { start: 0, end: 13 },
+//!     depth: 0,
 //!   },
 //!   Node {
 //!     markdown_node: MarkdownNode::Paragraph {
 //!       text: Text::from("Some text."),
 //!     },
 //!     source_range: Range { start: 14, end: 24 },
+//!     depth: 0,
 //!   },
 //! ])
 //! ```
 
 #![deny(missing_docs)]
+// Text and nodes can be iterated by reference, so clones that are only read are unnecessary
+#![warn(clippy::redundant_clone)]
 #![doc(html_root_url = "https://docs.rs/basalt-core")]
 
 /// Provides Markdown parser that supports Obsidian flavor.
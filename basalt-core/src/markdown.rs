@@ -25,12 +25,14 @@
 //!       text: Text::from("My Heading"),
 //!     },
 //!     source_range: Range { start: 0, end: 13 },
+//!     depth: 0,
 //!   },
 //!   Node {
 //!     markdown_node: MarkdownNode::Paragraph {
 //!       text: Text::from("Some text."),
 //!     },
-//!     source_range: Range { start: 14, end: 24 }
+//!     source_range: Range { start: 14, end: 24 },
+//!     depth: 0,
 //!   },
 //! ])
 //! ```
@@ -43,28 +45,46 @@
 //! with a new one. When an event indicates the end of that structure, the node is finalized
 //! and pushed into [`Parser::output`].
 //!
-//! Unrecognized events (such as [`InlineHtml`](pulldown_cmark::Event::InlineHtml)) are simply
-//! ignored for the time being.
+//! HTML events (such as [`InlineHtml`](pulldown_cmark::Event::InlineHtml)) are reduced to their
+//! text content with [`Text::from_html`]. Unrecognized events (such as
+//! [`InlineMath`](pulldown_cmark::Event::InlineMath)) are simply ignored for the time being.
 //!
 //! ## Not yet implemented
 //!
-//! - Handling of inline HTML, math blocks, etc.
-//! - Tracking code block language (`lang`) properly (currently set to [`None`]).
-use std::vec::IntoIter;
+//! - Rendering of HTML elements, math blocks, etc.
+use std::{fmt, slice::Iter, vec::IntoIter};
 
-use pulldown_cmark::{Event, Options, Tag, TagEnd};
+use pulldown_cmark::{CodeBlockKind, Event, Tag, TagEnd};
+use unicode_width::UnicodeWidthStr;
 
-/// A style that can be applied to [`TextNode`] (code, emphasis, strikethrough, strong).
+mod export;
+
+pub use export::{to_html, to_html_document, to_plain_text};
+pub use pulldown_cmark::Options;
+
+/// A style that can be applied to [`TextNode`] (code, emphasis, strikethrough, strong, links).
+///
+/// The delimiter of emphasis and strong emphasis is kept, so that `*italic*` and `_emphasis_`,
+/// and `**strong**` and `__bold__`, can be told apart.
 #[derive(Clone, Debug, PartialEq)]
 pub enum Style {
     /// Inline code style (e.g. `code`).
     Code,
-    /// Italic/emphasis style (e.g. `*emphasis*`).
+    /// Italic/emphasis style delimited with underscores (e.g. `_emphasis_`).
     Emphasis,
+    /// Italic style delimited with asterisks (e.g. `*italic*`).
+    Italic,
     /// Strikethrough style (e.g. `~~strikethrough~~`).
     Strikethrough,
-    /// Bold/strong style (e.g. `**strong**`).
+    /// Bold/strong style delimited with asterisks (e.g. `**strong**`).
     Strong,
+    /// Bold style delimited with underscores (e.g. `__bold__`).
+    Bold,
+    /// The text of an inline link (e.g. `[text](https://example.com)`).
+    Link {
+        /// The destination of the link.
+        url: String,
+    },
 }
 
 /// Represents the variant of a list or task item (checked, unchecked, etc.).
@@ -120,6 +140,65 @@ pub enum BlockQuoteKind {
     Important,
     Warning,
     Caution,
+    /// A callout type that is not one of the GitHub alert types, e.g. `[!bug]` or `[!example]`.
+    /// Stores the type as written in the marker.
+    Custom(String),
+}
+
+impl BlockQuoteKind {
+    /// Parses a callout marker like `[!bug]` at the start of the text. Returns the kind and the
+    /// text after the marker, or `None` if the text does not start with a marker.
+    ///
+    /// The type is matched case-insensitively against the GitHub alert types, other types become
+    /// [`BlockQuoteKind::Custom`]. The `+` or `-` that makes a callout foldable in Obsidian is
+    /// skipped.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use basalt_core::markdown::BlockQuoteKind;
+    ///
+    /// assert_eq!(
+    ///     BlockQuoteKind::from_marker("[!bug]- Crash on start"),
+    ///     Some((BlockQuoteKind::Custom("bug".into()), "Crash on start"))
+    /// );
+    /// assert_eq!(BlockQuoteKind::from_marker("[!Tip]"), Some((BlockQuoteKind::Tip, "")));
+    /// assert_eq!(BlockQuoteKind::from_marker("[link]"), None);
+    /// ```
+    pub fn from_marker(text: &str) -> Option<(Self, &str)> {
+        let (label, rest) = text.strip_prefix("[!")?.split_once(']')?;
+
+        if label.is_empty()
+            || !label
+                .chars()
+                .all(|c| c.is_alphanumeric() || c == '-' || c == '_')
+        {
+            return None;
+        }
+
+        let kind = match label.to_lowercase().as_str() {
+            "note" => Self::Note,
+            "tip" => Self::Tip,
+            "important" => Self::Important,
+            "warning" => Self::Warning,
+            "caution" => Self::Caution,
+            _ => Self::Custom(label.to_string()),
+        };
+
+        Some((kind, rest.trim_start_matches(['+', '-']).trim_start()))
+    }
+
+    /// Returns the type of the callout as written in a marker, e.g. `NOTE` or `bug`.
+    pub fn label(&self) -> &str {
+        match self {
+            Self::Note => "NOTE",
+            Self::Tip => "TIP",
+            Self::Important => "IMPORTANT",
+            Self::Warning => "WARNING",
+            Self::Caution => "CAUTION",
+            Self::Custom(label) => label,
+        }
+    }
 }
 
 impl From<pulldown_cmark::BlockQuoteKind> for BlockQuoteKind {
@@ -172,6 +251,12 @@ impl From<String> for TextNode {
     }
 }
 
+impl AsMut<String> for TextNode {
+    fn as_mut(&mut self) -> &mut String {
+        &mut self.content
+    }
+}
+
 impl TextNode {
     /// Creates a new [`TextNode`] from `content` and optional [`Style`].
     pub fn new(content: String, style: Option<Style>) -> Self {
@@ -221,11 +306,126 @@ impl IntoIterator for Text {
     }
 }
 
+impl<'a> IntoIterator for &'a Text {
+    type Item = &'a TextNode;
+    type IntoIter = Iter<'a, TextNode>;
+    fn into_iter(self) -> Self::IntoIter {
+        self.0.iter()
+    }
+}
+
 impl Text {
+    /// Returns an iterator over the [`TextNode`]s without consuming the text.
+    pub fn iter(&self) -> Iter<'_, TextNode> {
+        self.0.iter()
+    }
+
+    /// Returns the [`TextNode`] at the index, or [`None`] if the index is out of bounds.
+    pub fn get(&self, index: usize) -> Option<&TextNode> {
+        self.0.get(index)
+    }
+
+    /// Returns the first [`TextNode`], or [`None`] if the text is empty.
+    pub fn first(&self) -> Option<&TextNode> {
+        self.0.first()
+    }
+
     /// Appends a [`TextNode`] to the inner text list.
     fn push(&mut self, node: TextNode) {
         self.0.push(node);
     }
+
+    /// Extracts the text content of an HTML snippet.
+    ///
+    /// Tags are removed, `<br>` tags become line breaks and character references, e.g. `&amp;`,
+    /// are decoded. The text is not otherwise interpreted, so e.g. the text of an `<em>` element is
+    /// returned without [`Style`]. Returns an empty [`Text`] if the snippet has no text content.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use basalt_core::markdown::Text;
+    ///
+    /// assert_eq!(Text::from_html("<em>Fish</em> &amp; chips<br>"), Text::from("Fish & chips\n"));
+    /// assert_eq!(Text::from_html("<div class=\"note\">"), Text::default());
+    /// ```
+    pub fn from_html(html: &str) -> Self {
+        let mut content = String::new();
+        // The contents of the tag that is being read, if any
+        let mut tag: Option<String> = None;
+
+        html.chars().for_each(|c| match (&mut tag, c) {
+            (None, '<') => tag = Some(String::new()),
+            (None, c) => content.push(c),
+            (Some(name), '>') => {
+                if name.trim_end_matches('/').trim().eq_ignore_ascii_case("br") {
+                    content.push('\n');
+                }
+                tag = None;
+            }
+            (Some(name), c) => name.push(c),
+        });
+
+        // An unclosed tag is not a tag after all
+        if let Some(name) = tag {
+            content.push('<');
+            content.push_str(&name);
+        }
+
+        let content = decode_html_entities(&content);
+
+        if content.is_empty() {
+            Self::default()
+        } else {
+            content.into()
+        }
+    }
+}
+
+/// Decodes the named character references that are common in Markdown documents, e.g. `&amp;`,
+/// and all numeric character references, e.g. `&#38;` and `&#x26;`. Unknown references are kept
+/// as is.
+fn decode_html_entities(text: &str) -> String {
+    let mut decoded = String::new();
+    let mut rest = text;
+
+    while let Some(start) = rest.find('&') {
+        decoded.push_str(&rest[..start]);
+        rest = &rest[start..];
+
+        let entity = rest.find(';').map(|end| &rest[1..end]).and_then(|name| {
+            match name {
+                "amp" => Some('&'),
+                "lt" => Some('<'),
+                "gt" => Some('>'),
+                "quot" => Some('"'),
+                "apos" => Some('\''),
+                "nbsp" => Some('\u{a0}'),
+                _ => name
+                    .strip_prefix("#x")
+                    .or_else(|| name.strip_prefix("#X"))
+                    .map(|hex| u32::from_str_radix(hex, 16))
+                    .or_else(|| name.strip_prefix('#').map(str::parse))
+                    .and_then(Result::ok)
+                    .and_then(char::from_u32),
+            }
+            .map(|c| (c, name.len() + 2))
+        });
+
+        match entity {
+            Some((c, len)) => {
+                decoded.push(c);
+                rest = &rest[len..];
+            }
+            None => {
+                decoded.push('&');
+                rest = &rest[1..];
+            }
+        }
+    }
+
+    decoded.push_str(rest);
+    decoded
 }
 
 /// A [`std::ops::Range`] type for depicting range in [`crate::markdown`].
@@ -240,6 +440,7 @@ impl Text {
 ///     text: Text::default(),
 ///   },
 ///   source_range: Range::default(),
+///   depth: 0,
 /// };
 /// ```
 pub type Range<Idx> = std::ops::Range<Idx>;
@@ -272,14 +473,217 @@ pub struct Node {
 
     /// The range in the original source text that this node covers.
     pub source_range: Range<usize>,
+
+    /// The number of list and block quote ancestors of this node. For example, the items of a
+    /// top-level list have a depth of `1` and the items of a list nested inside them `2`.
+    pub depth: usize,
 }
 
 impl Node {
     /// Creates a new `Node` from the provided [`MarkdownNode`] and source range.
+    ///
+    /// The depth of the node is `0`, see [`Node::with_depth`].
     pub fn new(markdown_node: MarkdownNode, source_range: Range<usize>) -> Self {
         Self {
             markdown_node,
             source_range,
+            depth: 0,
+        }
+    }
+
+    /// Sets the number of list and block quote ancestors of the node.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use basalt_core::markdown::{Node, MarkdownNode, Text};
+    ///
+    /// let node = Node::new(MarkdownNode::Paragraph { text: Text::default() }, 0..10).with_depth(2);
+    ///
+    /// assert_eq!(node.depth(), 2);
+    /// ```
+    pub fn with_depth(self, depth: usize) -> Self {
+        Self { depth, ..self }
+    }
+
+    /// Returns the number of list and block quote ancestors of the node.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use basalt_core::markdown::from_str;
+    ///
+    /// let nodes = from_str("- Item\n  - Nested item\n\n> Quote");
+    ///
+    /// assert_eq!(nodes.iter().map(|node| node.depth()).collect::<Vec<_>>(), [1, 2, 0]);
+    /// ```
+    pub fn depth(&self) -> usize {
+        self.depth
+    }
+
+    /// Returns the source text that the node covers.
+    ///
+    /// Returns [`None`] if the source range is out of bounds or does not fall on UTF-8
+    /// character boundaries, e.g. when the node was parsed from a different source.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use basalt_core::markdown::from_str;
+    ///
+    /// let source = "# Heading\n\nParagraph";
+    /// let nodes = from_str(source);
+    ///
+    /// assert_eq!(nodes[0].source_slice(source), Some("# Heading\n"));
+    /// assert_eq!(nodes[1].source_slice("Short"), None);
+    /// ```
+    pub fn source_slice<'a>(&self, source: &'a str) -> Option<&'a str> {
+        source.get(self.source_range.clone())
+    }
+
+    /// Returns the source text that the node covers, for sources the node was parsed from.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the source range is out of bounds or does not fall on UTF-8 character
+    /// boundaries. Use [`Node::source_slice`] when the source may not match the node.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use basalt_core::markdown::from_str;
+    ///
+    /// let source = "# Heading\n\nParagraph";
+    /// let nodes = from_str(source);
+    ///
+    /// assert_eq!(nodes[1].source_slice_unchecked(source), "Paragraph");
+    /// ```
+    pub fn source_slice_unchecked<'a>(&self, source: &'a str) -> &'a str {
+        let Range { start, end } = self.source_range;
+
+        debug_assert!(
+            source.is_char_boundary(start) && source.is_char_boundary(end),
+            "source range {start}..{end} is not on UTF-8 character boundaries of the source"
+        );
+
+        &source[start..end]
+    }
+
+    /// Returns the index of the node whose source range contains the byte offset, or [`None`]
+    /// if the offset falls between nodes or outside of them.
+    ///
+    /// The nodes must be sorted by the start of their source ranges, as returned by
+    /// [`from_str`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use basalt_core::markdown::{from_str, Node};
+    ///
+    /// let nodes = from_str("# Heading\n\nParagraph");
+    ///
+    /// assert_eq!(Node::find_by_range(&nodes, 3), Some(0));
+    /// assert_eq!(Node::find_by_range(&nodes, 11), Some(1));
+    /// assert_eq!(Node::find_by_range(&nodes, 20), None);
+    /// ```
+    pub fn find_by_range(nodes: &[Node], byte_offset: usize) -> Option<usize> {
+        let index = nodes
+            .partition_point(|node| node.source_range.start <= byte_offset)
+            .checked_sub(1)?;
+
+        nodes[index]
+            .source_range
+            .contains(&byte_offset)
+            .then_some(index)
+    }
+
+    /// Returns `true` if the node is a heading, see [`MarkdownNode::is_heading`].
+    pub fn is_heading(&self) -> bool {
+        self.markdown_node.is_heading()
+    }
+
+    /// Returns `true` if the node is a paragraph, see [`MarkdownNode::is_paragraph`].
+    pub fn is_paragraph(&self) -> bool {
+        self.markdown_node.is_paragraph()
+    }
+
+    /// Returns `true` if the node is a code block, see [`MarkdownNode::is_code_block`].
+    pub fn is_code_block(&self) -> bool {
+        self.markdown_node.is_code_block()
+    }
+
+    /// Returns `true` if the node is a list item, see [`MarkdownNode::is_list`].
+    pub fn is_list(&self) -> bool {
+        self.markdown_node.is_list()
+    }
+
+    /// Returns `true` if the node is a block quote, see [`MarkdownNode::is_block_quote`].
+    pub fn is_block_quote(&self) -> bool {
+        self.markdown_node.is_block_quote()
+    }
+
+    /// Returns `true` if the node is a task item, see [`MarkdownNode::is_task`].
+    pub fn is_task(&self) -> bool {
+        self.markdown_node.is_task()
+    }
+
+    /// Returns the level of a heading node, see [`MarkdownNode::heading_level`].
+    pub fn heading_level(&self) -> Option<HeadingLevel> {
+        self.markdown_node.heading_level()
+    }
+
+    /// Returns the text of the node, see [`MarkdownNode::as_text`].
+    pub fn as_text(&self) -> Option<&Text> {
+        self.markdown_node.as_text()
+    }
+
+    /// Estimates the number of terminal lines the node takes up when rendered at the width,
+    /// without rendering it.
+    ///
+    /// The text is measured in display columns, so wide characters such as CJK count twice.
+    /// Paragraphs are assumed to fill every line, so text that wraps early at word boundaries
+    /// can take more lines. Each block is counted with the empty line that follows it. A list
+    /// item counts as a single line, without the empty line after the list.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use basalt_core::markdown::from_str;
+    ///
+    /// let nodes = from_str("# Notes\n\nA paragraph that takes two lines.\n\n```\nlet x = 1;\n```");
+    /// let heights: Vec<_> = nodes.iter().map(|node| node.estimated_render_height(20)).collect();
+    ///
+    /// assert_eq!(heights, [4, 3, 4]);
+    /// ```
+    pub fn estimated_render_height(&self, width: usize) -> usize {
+        let text_width = |text: &Text| text.iter().map(|node| node.content.width()).sum::<usize>();
+
+        match &self.markdown_node {
+            MarkdownNode::Heading {
+                level: HeadingLevel::H1,
+                ..
+            } => 4,
+            MarkdownNode::Heading { .. } => 2,
+            MarkdownNode::Paragraph { text } => text_width(text).div_ceil(width.max(1)).max(1) + 1,
+            MarkdownNode::CodeBlock { text, .. } => {
+                let line_count = text
+                    .iter()
+                    .map(|node| node.content.matches('\n').count())
+                    .sum::<usize>()
+                    + 1;
+
+                line_count + 2
+            }
+            MarkdownNode::Item { .. } => 1,
+            // The children are rendered behind the `┃ ` bar. The lines that separate them take
+            // the place of the empty lines that follow top-level blocks.
+            MarkdownNode::BlockQuote { kind, nodes } => {
+                usize::from(kind.is_some())
+                    + nodes
+                        .iter()
+                        .map(|node| node.estimated_render_height(width.saturating_sub(2)))
+                        .sum::<usize>()
+            }
         }
     }
 
@@ -287,15 +691,25 @@ impl Node {
     ///
     /// If the markdown node is a [`MarkdownNode::BlockQuote`], the [`TextNode`] will be pushed
     /// into the last child [`Node`], if any.
+    ///
+    /// A list item whose text starts with a loose task marker, e.g. `[?] `, becomes a
+    /// [`ItemKind::Checked`] task. pulldown-cmark only recognizes `[ ]` and `[x]` as task markers.
     /// ```
     pub(crate) fn push_text_node(&mut self, node: TextNode) {
         match &mut self.markdown_node {
+            MarkdownNode::Item {
+                kind: kind @ None,
+                text,
+            } if text.first().is_none() && is_loose_task_marker(&node.content) => {
+                *kind = Some(ItemKind::Checked);
+                text.push(TextNode::new(node.content[4..].to_string(), node.style));
+            }
             MarkdownNode::Paragraph { text, .. }
             | MarkdownNode::Heading { text, .. }
             | MarkdownNode::CodeBlock { text, .. }
             | MarkdownNode::Item { text, .. } => text.push(node),
-            MarkdownNode::BlockQuote { nodes, .. } => {
-                if let Some(last_node) = nodes.last_mut() {
+            markdown_node => {
+                if let Some(last_node) = markdown_node.children_mut().last_mut() {
                     last_node.push_text_node(node);
                 }
             }
@@ -303,6 +717,11 @@ impl Node {
     }
 }
 
+/// Returns `true` if the text starts with a task marker checked with any character, e.g. `[?] `.
+fn is_loose_task_marker(text: &str) -> bool {
+    matches!(text.as_bytes(), [b'[', marker, b']', b' ', ..] if marker.is_ascii() && *marker != b' ')
+}
+
 /// The Markdown AST node enumeration.
 #[derive(Clone, Debug, PartialEq)]
 #[allow(missing_docs)]
@@ -341,6 +760,236 @@ pub enum MarkdownNode {
     },
 }
 
+impl MarkdownNode {
+    /// Returns `true` if the node is a [`MarkdownNode::Heading`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use basalt_core::markdown::{HeadingLevel, MarkdownNode, Text};
+    ///
+    /// let heading = MarkdownNode::Heading { level: HeadingLevel::H1, text: Text::default() };
+    ///
+    /// assert!(heading.is_heading());
+    /// assert!(!MarkdownNode::Paragraph { text: Text::default() }.is_heading());
+    /// ```
+    pub fn is_heading(&self) -> bool {
+        matches!(self, MarkdownNode::Heading { .. })
+    }
+
+    /// Returns `true` if the node is a [`MarkdownNode::Paragraph`].
+    pub fn is_paragraph(&self) -> bool {
+        matches!(self, MarkdownNode::Paragraph { .. })
+    }
+
+    /// Returns `true` if the node is a [`MarkdownNode::CodeBlock`].
+    pub fn is_code_block(&self) -> bool {
+        matches!(self, MarkdownNode::CodeBlock { .. })
+    }
+
+    /// Returns `true` if the node is a list item, including task items.
+    ///
+    /// Lists are represented by their [`MarkdownNode::Item`] nodes, there is no separate node for
+    /// the list itself.
+    pub fn is_list(&self) -> bool {
+        matches!(self, MarkdownNode::Item { .. })
+    }
+
+    /// Returns `true` if the node is a [`MarkdownNode::BlockQuote`], including callout blocks.
+    pub fn is_block_quote(&self) -> bool {
+        matches!(self, MarkdownNode::BlockQuote { .. })
+    }
+
+    /// Returns `true` if the node is a task item with a checkbox, checked or not.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use basalt_core::markdown::{from_str, MarkdownNode};
+    ///
+    /// let nodes = from_str("- [ ] Task\n- Item");
+    ///
+    /// assert!(nodes[0].markdown_node.is_task());
+    /// assert!(!nodes[1].markdown_node.is_task());
+    /// ```
+    pub fn is_task(&self) -> bool {
+        matches!(
+            self,
+            MarkdownNode::Item {
+                kind: Some(ItemKind::HardChecked | ItemKind::Checked | ItemKind::Unchecked),
+                ..
+            }
+        )
+    }
+
+    /// Returns the [`HeadingLevel`] of a heading, or [`None`] for other nodes.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use basalt_core::markdown::{from_str, HeadingLevel};
+    ///
+    /// let nodes = from_str("## Heading\n\nParagraph");
+    ///
+    /// assert_eq!(nodes[0].markdown_node.heading_level(), Some(HeadingLevel::H2));
+    /// assert_eq!(nodes[1].markdown_node.heading_level(), None);
+    /// ```
+    pub fn heading_level(&self) -> Option<HeadingLevel> {
+        match self {
+            MarkdownNode::Heading { level, .. } => Some(level.clone()),
+            _ => None,
+        }
+    }
+
+    /// Returns the [`Text`] of the nodes that carry a single text, or [`None`] for block quotes.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use basalt_core::markdown::{from_str, Text};
+    ///
+    /// let nodes = from_str("# Heading\n\n> Quote");
+    ///
+    /// assert_eq!(nodes[0].markdown_node.as_text(), Some(&Text::from("Heading")));
+    /// assert_eq!(nodes[1].markdown_node.as_text(), None);
+    /// ```
+    pub fn as_text(&self) -> Option<&Text> {
+        match self {
+            MarkdownNode::Heading { text, .. }
+            | MarkdownNode::Paragraph { text }
+            | MarkdownNode::CodeBlock { text, .. }
+            | MarkdownNode::Item { text, .. } => Some(text),
+            MarkdownNode::BlockQuote { .. } => None,
+        }
+    }
+
+    /// Returns `true` if the node contains child nodes, i.e. it is a
+    /// [`MarkdownNode::BlockQuote`].
+    pub fn is_container(&self) -> bool {
+        matches!(self, MarkdownNode::BlockQuote { .. })
+    }
+
+    /// Returns the child nodes of a container node, or an empty slice for the nodes that carry a
+    /// text.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use basalt_core::markdown::{from_str, Text};
+    ///
+    /// let nodes = from_str("> Quote\n>\n> - Item\n\nParagraph");
+    ///
+    /// assert!(nodes[0].markdown_node.is_container());
+    /// assert_eq!(nodes[0].markdown_node.children().len(), 2);
+    /// assert_eq!(nodes[0].markdown_node.children()[0].as_text(), Some(&Text::from("Quote")));
+    ///
+    /// assert!(!nodes[1].markdown_node.is_container());
+    /// assert!(nodes[1].markdown_node.children().is_empty());
+    /// ```
+    pub fn children(&self) -> &[Node] {
+        match self {
+            MarkdownNode::BlockQuote { nodes, .. } => nodes,
+            _ => &[],
+        }
+    }
+
+    /// Returns the child nodes of a container node mutably, see [`MarkdownNode::children`].
+    pub fn children_mut(&mut self) -> &mut [Node] {
+        match self {
+            MarkdownNode::BlockQuote { nodes, .. } => nodes,
+            _ => &mut [],
+        }
+    }
+}
+
+/// Returns the [`Options`] for the extensions supported by Obsidian Flavored Markdown.
+///
+/// The options enable tables, footnotes, strikethrough, task lists, math, callouts, and YAML
+/// front matter. Smart punctuation, heading attributes, definition lists, and `+++` metadata
+/// blocks are not part of Obsidian Flavored Markdown and are disabled.
+///
+/// # Examples
+///
+/// ```
+/// use basalt_core::markdown::{from_str, obsidian_options, Parser};
+///
+/// let markdown = "# Heading {#id}";
+///
+/// assert_ne!(Parser::new_with_options(markdown, obsidian_options()).parse(), from_str(markdown));
+/// ```
+pub fn obsidian_options() -> Options {
+    Options::ENABLE_TABLES
+        | Options::ENABLE_FOOTNOTES
+        | Options::ENABLE_STRIKETHROUGH
+        | Options::ENABLE_TASKLISTS
+        | Options::ENABLE_MATH
+        | Options::ENABLE_GFM
+        | Options::ENABLE_YAML_STYLE_METADATA_BLOCKS
+}
+
+/// Detects a callout marker that [`pulldown_cmark`] does not recognize, e.g. `[!bug]`, at the
+/// start of the first paragraph of a block quote. The marker is removed from the text and stored
+/// as the kind of the block quote.
+fn with_callout(mut node: Node) -> Node {
+    let MarkdownNode::BlockQuote {
+        kind: kind @ None,
+        nodes,
+    } = &mut node.markdown_node
+    else {
+        return node;
+    };
+
+    let Some(Node {
+        markdown_node: MarkdownNode::Paragraph { text },
+        ..
+    }) = nodes.first_mut()
+    else {
+        return node;
+    };
+
+    if let Some(callout) = take_callout_marker(&mut text.0) {
+        *kind = Some(callout);
+
+        if text.0.is_empty() {
+            nodes.remove(0);
+        }
+    }
+
+    node
+}
+
+/// Removes a callout marker, e.g. `[!bug]`, from the start of the text nodes of a paragraph and
+/// returns its kind, see [`BlockQuoteKind::from_marker`]. The first text node is removed if it
+/// only contained the marker.
+///
+/// The text nodes are generic, so that parsers with their own node types detect callouts the
+/// same way as [`from_str`].
+///
+/// # Examples
+///
+/// ```
+/// use basalt_core::markdown::{take_callout_marker, BlockQuoteKind, TextNode};
+///
+/// let mut text = vec![TextNode::from("[!bug] Crash on start")];
+/// assert_eq!(take_callout_marker(&mut text), Some(BlockQuoteKind::Custom("bug".into())));
+/// assert_eq!(text, [TextNode::from("Crash on start")]);
+///
+/// let mut text = vec![TextNode::from("[!tip]")];
+/// assert_eq!(take_callout_marker(&mut text), Some(BlockQuoteKind::Tip));
+/// assert_eq!(text, []);
+/// ```
+pub fn take_callout_marker<T: AsMut<String>>(text_nodes: &mut Vec<T>) -> Option<BlockQuoteKind> {
+    let first = text_nodes.first_mut()?.as_mut();
+    let (kind, rest) = BlockQuoteKind::from_marker(first)?;
+    *first = rest.to_string();
+
+    if first.is_empty() {
+        text_nodes.remove(0);
+    }
+
+    Some(kind)
+}
+
 /// Returns `true` if the [`MarkdownNode`] should be closed upon encountering the given [`TagEnd`].
 fn matches_tag_end(node: &Node, tag_end: &TagEnd) -> bool {
     matches!(
@@ -355,7 +1004,8 @@ fn matches_tag_end(node: &Node, tag_end: &TagEnd) -> bool {
 
 /// Parses the given Markdown input into a list of [`Node`]s.
 ///
-/// This is a convenience function for constructing a [`Parser`] and calling [`Parser::parse`].  
+/// This is a convenience function for constructing a [`Parser`] and calling [`Parser::parse`].
+/// All [`Options`] are enabled, use [`Parser::new_with_options`] to parse with a subset of them.
 ///
 /// # Examples
 ///
@@ -372,12 +1022,14 @@ fn matches_tag_end(node: &Node, tag_end: &TagEnd) -> bool {
 ///       text: Text::from("My Heading"),
 ///     },
 ///     source_range: Range { start: 0, end: 13 },
+///     depth: 0,
 ///   },
 ///   Node {
 ///     markdown_node: MarkdownNode::Paragraph {
 ///       text: Text::from("Some text."),
 ///     },
 ///     source_range: Range { start: 14, end: 24 },
+///     depth: 0,
 ///   },
 /// ])
 /// ```
@@ -385,63 +1037,897 @@ pub fn from_str(text: &str) -> Vec<Node> {
     Parser::new(text).parse()
 }
 
-/// A parser that consumes [`pulldown_cmark::Event`]s and produces a [`Vec`] of [`Node`].
+/// Parses the given Markdown input into a list of [`Node`]s like [`from_str`], but keeps the
+/// line breaks inside paragraphs, headings and items as `\n` [`TextNode`]s, see
+/// [`Parser::with_breaks`].
+///
+/// This is useful when the lines of the source need to be reconstructed, e.g. for notes that are
+/// written with one sentence per line.
 ///
 /// # Examples
 ///
 /// ```
-/// use basalt_core::markdown::{Parser, Range, Node, MarkdownNode, HeadingLevel, Text};
+/// use basalt_core::markdown::{from_str, from_str_with_breaks, MarkdownNode, Node};
 ///
-/// let markdown = "# My Heading\n\nSome text.";
-/// let parser = Parser::new(markdown);
-/// let nodes = parser.parse();
+/// let markdown = "One sentence.\nAnother sentence.";
 ///
-/// assert_eq!(nodes, vec![
-///   Node {
+/// let text = |nodes: Vec<Node>| match &nodes[0].markdown_node {
+///     MarkdownNode::Paragraph { text } => text.iter().map(|node| node.content.as_str()).collect::<String>(),
+///     _ => unreachable!(),
+/// };
+///
+/// assert_eq!(text(from_str(markdown)), "One sentence.Another sentence.");
+/// assert_eq!(text(from_str_with_breaks(markdown)), markdown);
+/// ```
+pub fn from_str_with_breaks(text: &str) -> Vec<Node> {
+    Parser::new(text).with_breaks().parse()
+}
+
+/// Rewrites the numbers of ordered list items sequentially from the start number of each list.
+///
+/// Nested lists are numbered independently of the enclosing list. Only the numbers are replaced,
+/// so the indentation, the delimiter (`.` or `)`), and the item content are kept as they are.
+///
+/// # Examples
+///
+/// ```
+/// use basalt_core::markdown::renumber_ordered_lists;
+///
+/// let markdown = "3. First\n3. Second\n   1. Nested\n   1. Nested\n3. Third";
+///
+/// assert_eq!(
+///     renumber_ordered_lists(markdown),
+///     "3. First\n4. Second\n   1. Nested\n   2. Nested\n5. Third"
+/// );
+/// ```
+pub fn renumber_ordered_lists(source: &str) -> String {
+    // The next number of each open list, `None` for unordered lists
+    let mut lists: Vec<Option<u64>> = vec![];
+    let mut markers: Vec<(Range<usize>, u64)> = vec![];
+
+    let parser = pulldown_cmark::Parser::new_ext(source, Options::all()).into_offset_iter();
+
+    for (event, range) in parser {
+        match event {
+            Event::Start(Tag::List(start)) => lists.push(start),
+            Event::End(TagEnd::List(_)) => {
+                lists.pop();
+            }
+            Event::Start(Tag::Item) => {
+                if let Some(Some(number)) = lists.last_mut() {
+                    let item = &source[range.clone()];
+                    let start = range.start + item.len() - item.trim_start().len();
+                    let digits = source[start..]
+                        .bytes()
+                        .take_while(u8::is_ascii_digit)
+                        .count();
+
+                    markers.push((start..start + digits, *number));
+                    *number += 1;
+                }
+            }
+            _ => {}
+        }
+    }
+
+    let mut renumbered = String::with_capacity(source.len());
+    let end = markers.into_iter().fold(0, |end, (marker, number)| {
+        renumbered.push_str(&source[end..marker.start]);
+        renumbered.push_str(&number.to_string());
+        marker.end
+    });
+    renumbered.push_str(&source[end..]);
+
+    renumbered
+}
+
+/// Returns the values of the properties with one of the given keys in the frontmatter. A value
+/// is either a list, e.g. `[a, b]`, a comma separated value, or a YAML sequence on the following
+/// lines. Whitespace and quotes around the values are removed.
+fn frontmatter_values<'a>(frontmatter: &'a str, keys: &[&str]) -> Vec<&'a str> {
+    let mut lines = frontmatter.lines();
+    let mut values = vec![];
+
+    while let Some(line) = lines.next() {
+        let Some(value) = keys.iter().find_map(|key| {
+            line.strip_prefix(key)
+                .and_then(|value| value.strip_prefix(':'))
+        }) else {
+            continue;
+        };
+
+        let value = value.trim();
+        if value.is_empty() {
+            values.extend(
+                lines
+                    .clone()
+                    .map_while(|line| line.trim_start().strip_prefix("- ")),
+            );
+        } else {
+            values.extend(
+                value
+                    .trim_start_matches('[')
+                    .trim_end_matches(']')
+                    .split(','),
+            );
+        }
+    }
+
+    values
+        .into_iter()
+        .map(|value| value.trim().trim_matches(['"', '\'']))
+        .filter(|value| !value.is_empty())
+        .collect()
+}
+
+/// Returns the tags in the values of the `tags` property in the frontmatter, see
+/// [`frontmatter_values`]. Tags are also separated by spaces.
+fn frontmatter_tags(frontmatter: &str) -> Vec<String> {
+    frontmatter_values(frontmatter, &["tags", "tag"])
+        .into_iter()
+        .flat_map(|value| value.split(' '))
+        .map(|tag| tag.trim().trim_matches(['"', '\'']).trim_start_matches('#'))
+        .filter(|tag| !tag.is_empty())
+        .map(String::from)
+        .collect()
+}
+
+/// Returns the aliases of the Markdown content from the `aliases` property of the frontmatter,
+/// in the order they appear. Obsidian resolves links to an alias, e.g. `[[Alias]]`, to the note
+/// with the alias.
+///
+/// The property is either a list, e.g. `aliases: [First, Second]`, or a YAML sequence on the
+/// following lines. Content without frontmatter has no aliases.
+///
+/// # Examples
+///
+/// ```
+/// use basalt_core::markdown::aliases;
+///
+/// let markdown = "---\naliases:\n  - Basalt TUI\n  - \"Project B\"\n---\n# Basalt";
+///
+/// assert_eq!(aliases(markdown), ["Basalt TUI", "Project B"]);
+/// assert_eq!(aliases("aliases: [Basalt]"), Vec::<String>::new());
+/// ```
+pub fn aliases(source: &str) -> Vec<String> {
+    let mut in_metadata_block = false;
+    let mut aliases = vec![];
+
+    for event in pulldown_cmark::Parser::new_ext(source, Options::all()) {
+        match event {
+            Event::Start(Tag::MetadataBlock(_)) => in_metadata_block = true,
+            Event::End(TagEnd::MetadataBlock(_)) => break,
+            Event::Text(value) if in_metadata_block => aliases.extend(
+                frontmatter_values(&value, &["aliases", "alias"])
+                    .into_iter()
+                    .map(String::from),
+            ),
+            _ => {}
+        }
+    }
+
+    aliases
+}
+
+/// A property in the frontmatter of a note, e.g. `tags: [daily, work]`.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Property {
+    /// The name of the property.
+    pub key: String,
+    /// The values of the property. A scalar property has a single value and a list property one
+    /// value per item. A property without a value has no values.
+    pub values: Vec<String>,
+}
+
+/// Returns the properties in the frontmatter of the Markdown content, in the order they appear.
+///
+/// A property is a top-level `key: value` line. The value is either a scalar, a list, e.g.
+/// `[a, b]`, or a YAML sequence on the following lines. Quotes around the values are removed.
+/// Nested mappings are not supported, and their lines are skipped. Content without frontmatter
+/// has no properties.
+///
+/// # Examples
+///
+/// ```
+/// use basalt_core::markdown::{properties, Property};
+///
+/// let markdown = "---\ntags: [daily, work]\naliases:\n  - Today\ncreated: 2024-01-01\n---\n# Notes";
+///
+/// assert_eq!(
+///     properties(markdown),
+///     [
+///         Property { key: "tags".into(), values: vec!["daily".into(), "work".into()] },
+///         Property { key: "aliases".into(), values: vec!["Today".into()] },
+///         Property { key: "created".into(), values: vec!["2024-01-01".into()] },
+///     ]
+/// );
+/// assert_eq!(properties("# No frontmatter"), []);
+/// ```
+pub fn properties(source: &str) -> Vec<Property> {
+    let unquote = |value: &str| value.trim().trim_matches(['"', '\'']).to_string();
+    let mut in_metadata_block = false;
+    let mut properties: Vec<Property> = vec![];
+
+    for event in pulldown_cmark::Parser::new_ext(source, Options::all()) {
+        match event {
+            Event::Start(Tag::MetadataBlock(_)) => in_metadata_block = true,
+            Event::Text(frontmatter) if in_metadata_block => {
+                for line in frontmatter.lines() {
+                    // The items of a YAML sequence belong to the preceding property
+                    if let (Some(item), Some(property)) =
+                        (line.trim_start().strip_prefix("- "), properties.last_mut())
+                    {
+                        property.values.push(unquote(item));
+                        continue;
+                    }
+
+                    if line.starts_with(char::is_whitespace) {
+                        continue;
+                    }
+
+                    let Some((key, value)) = line.split_once(':') else {
+                        continue;
+                    };
+
+                    let value = value.trim();
+                    let values: Vec<String> = match value
+                        .strip_prefix('[')
+                        .and_then(|value| value.strip_suffix(']'))
+                    {
+                        Some(list) => list.split(',').map(unquote).collect(),
+                        None => vec![unquote(value)],
+                    };
+
+                    properties.push(Property {
+                        key: key.trim().to_string(),
+                        values: values
+                            .into_iter()
+                            .filter(|value| !value.is_empty())
+                            .collect(),
+                    });
+                }
+            }
+            _ => break,
+        }
+    }
+
+    properties
+}
+
+/// Returns the inline tags in the text, e.g. `#idea`. A tag starts after whitespace, consists of
+/// alphanumeric characters, `_`, `-` and `/`, and contains at least one non-numeric character.
+fn inline_tags(text: &str) -> Vec<String> {
+    let is_tag_char = |c: char| c.is_alphanumeric() || matches!(c, '_' | '-' | '/');
+
+    text.split(char::is_whitespace)
+        .filter_map(|word| word.strip_prefix('#'))
+        .map(|word| {
+            let end = word.find(|c| !is_tag_char(c)).unwrap_or(word.len());
+            word[..end].trim_end_matches('/')
+        })
+        .filter(|tag| tag.chars().any(|c| !c.is_numeric()))
+        .map(String::from)
+        .collect()
+}
+
+/// Returns the tags of the Markdown content without the leading `#`, in the order they appear.
+///
+/// Tags are read from the `tags` property of the frontmatter and from the text, e.g. `#idea` or
+/// the nested `#project/active`. Text in code is skipped. Each tag is returned once.
+///
+/// # Examples
+///
+/// ```
+/// use basalt_core::markdown::tags;
+///
+/// let markdown = "---\ntags: [daily]\n---\n# Notes #1\n\nWork on #project/basalt and `#code`.";
+///
+/// assert_eq!(tags(markdown), ["daily", "project/basalt"]);
+/// ```
+pub fn tags(source: &str) -> Vec<String> {
+    let mut tags: Vec<String> = vec![];
+    let mut text = String::new();
+    let mut in_code_block = false;
+    let mut in_metadata_block = false;
+
+    for event in pulldown_cmark::Parser::new_ext(source, Options::all()) {
+        match event {
+            Event::Start(Tag::CodeBlock(_)) => in_code_block = true,
+            Event::End(TagEnd::CodeBlock) => in_code_block = false,
+            Event::Start(Tag::MetadataBlock(_)) => in_metadata_block = true,
+            Event::End(TagEnd::MetadataBlock(_)) => in_metadata_block = false,
+            Event::Text(value) if in_metadata_block => tags.extend(frontmatter_tags(&value)),
+            Event::Text(value) if !in_code_block => text.push_str(&value),
+            // Separates the text of other elements, so that e.g. `code#tag` is not a tag
+            _ => text.push('\n'),
+        }
+    }
+
+    tags.extend(inline_tags(&text));
+
+    tags.into_iter().fold(vec![], |mut unique, tag| {
+        if !unique.contains(&tag) {
+            unique.push(tag);
+        }
+        unique
+    })
+}
+
+/// The default reading speed used for [`TextCounts::reading_minutes`].
+pub const DEFAULT_WORDS_PER_MINUTE: usize = 200;
+
+/// Word and character counts of Markdown content with an estimated reading time.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct TextCounts {
+    /// The number of words. Tokens without any alphanumeric characters, such as `-` or `>`, are
+    /// not words.
+    pub words: usize,
+    /// The number of characters in the text, without Markdown syntax.
+    pub chars: usize,
+    /// The estimated time to read the words in minutes, rounded up.
+    pub reading_minutes: usize,
+}
+
+/// Counts the words and characters of the given [`Node`]s with the reading time estimated at
+/// [`DEFAULT_WORDS_PER_MINUTE`].
+///
+/// Only the text of the nodes is counted, so code blocks, front matter and link targets are
+/// skipped while the display text of links is counted.
+///
+/// # Examples
+///
+/// ```
+/// use basalt_core::markdown::{counts, from_str, TextCounts};
+///
+/// let nodes = from_str("# Heading\n\nRead the [docs](https://example.com)!\n\n```\nlet code = 1;\n```");
+///
+/// assert_eq!(counts(&nodes), TextCounts { words: 4, chars: 21, reading_minutes: 1 });
+/// ```
+pub fn counts(nodes: &[Node]) -> TextCounts {
+    counts_with_words_per_minute(nodes, DEFAULT_WORDS_PER_MINUTE)
+}
+
+/// Counts the words and characters of the given [`Node`]s, see [`counts`], with the reading time
+/// estimated at the given words per minute.
+///
+/// # Examples
+///
+/// ```
+/// use basalt_core::markdown::{counts_with_words_per_minute, from_str};
+///
+/// let nodes = from_str(&"word ".repeat(500));
+///
+/// assert_eq!(counts_with_words_per_minute(&nodes, 100).reading_minutes, 5);
+/// assert_eq!(counts_with_words_per_minute(&nodes, 300).reading_minutes, 2);
+/// ```
+pub fn counts_with_words_per_minute(nodes: &[Node], words_per_minute: usize) -> TextCounts {
+    let (words, chars) = prose(nodes)
+        .into_iter()
+        .fold((0, 0), |(words, chars), node| {
+            (
+                words
+                    + node
+                        .content
+                        .split_whitespace()
+                        .filter(|word| word.chars().any(char::is_alphanumeric))
+                        .count(),
+                chars + node.content.chars().count(),
+            )
+        });
+
+    TextCounts {
+        words,
+        chars,
+        reading_minutes: words.div_ceil(words_per_minute.max(1)),
+    }
+}
+
+/// The number of tasks and completed tasks in Markdown content.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct TaskStats {
+    /// The number of task items, completed or not.
+    pub total: usize,
+    /// The number of completed task items, including loosely checked ones like `- [?] Task`.
+    pub done: usize,
+}
+
+/// Formats the stats as `done/total`, e.g. `4/9`.
+impl fmt::Display for TaskStats {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}/{}", self.done, self.total)
+    }
+}
+
+/// Counts the task items of the given [`Node`]s, descending into block quotes.
+///
+/// # Examples
+///
+/// ```
+/// use basalt_core::markdown::{from_str, task_stats, TaskStats};
+///
+/// let nodes = from_str("- [x] Done\n- [ ] Todo\n  - [?] Loosely done\n- Item");
+///
+/// assert_eq!(task_stats(&nodes), TaskStats { total: 3, done: 2 });
+/// assert_eq!(task_stats(&nodes).to_string(), "2/3");
+/// ```
+pub fn task_stats(nodes: &[Node]) -> TaskStats {
+    nodes.iter().fold(TaskStats::default(), |stats, node| {
+        match &node.markdown_node {
+            MarkdownNode::Item {
+                kind: Some(ItemKind::HardChecked | ItemKind::Checked),
+                ..
+            } => TaskStats {
+                total: stats.total + 1,
+                done: stats.done + 1,
+            },
+            MarkdownNode::Item {
+                kind: Some(ItemKind::Unchecked),
+                ..
+            } => TaskStats {
+                total: stats.total + 1,
+                ..stats
+            },
+            markdown_node => {
+                let children = task_stats(markdown_node.children());
+                TaskStats {
+                    total: stats.total + children.total,
+                    done: stats.done + children.done,
+                }
+            }
+        }
+    })
+}
+
+/// Returns the [`TextNode`]s of the prose in the given [`Node`]s, in document order.
+///
+/// This is the text that [`counts`] counts: code blocks are skipped and block quotes are
+/// descended into.
+///
+/// # Examples
+///
+/// ```
+/// use basalt_core::markdown::{from_str, prose};
+///
+/// let nodes = from_str("# Heading\n\n> Quote\n\n```\ncode\n```");
+/// let contents: Vec<_> = prose(&nodes).iter().map(|node| node.content.as_str()).collect();
+///
+/// assert_eq!(contents, ["Heading", "Quote"]);
+/// ```
+pub fn prose(nodes: &[Node]) -> Vec<&TextNode> {
+    nodes
+        .iter()
+        .flat_map(|node| match &node.markdown_node {
+            MarkdownNode::CodeBlock { .. } => vec![],
+            markdown_node if markdown_node.is_container() => prose(markdown_node.children()),
+            markdown_node => markdown_node
+                .as_text()
+                .map(|text| text.0.iter().collect())
+                .unwrap_or_default(),
+        })
+        .collect()
+}
+
+/// Serializes the given [`Node`]s back into Markdown.
+///
+/// Each node is sliced out of `source` using its `source_range`, which retains the original
+/// formatting, such as inline styles and links, that is not represented in the AST. When the
+/// range is not available in `source`, the markdown is regenerated from the AST fields instead.
+/// The source range of a task item only covers the `[ ]` marker, so the complete item is looked
+/// up from `source`.
+///
+/// Consecutive list items are separated by a single line break and other nodes by an empty line.
+///
+/// Content that is not represented in the AST, e.g. HTML blocks and tables, is not retained.
+///
+/// # Examples
+///
+/// ```
+/// use basalt_core::markdown::{from_str, to_markdown};
+///
+/// let markdown = "# My Heading\n\nSome *styled* text.\n";
+/// let nodes = from_str(markdown);
+///
+/// assert_eq!(to_markdown(&nodes, markdown), markdown);
+/// assert_eq!(to_markdown(&nodes, ""), "# My Heading\n\nSome styled text.\n");
+/// ```
+pub fn to_markdown(nodes: &[Node], source: &str) -> String {
+    let mut markdown =
+        nodes
+            .iter()
+            .enumerate()
+            .fold(String::new(), |mut markdown, (index, node)| {
+                if index > 0 {
+                    let is_list_item =
+                        |node: &Node| matches!(node.markdown_node, MarkdownNode::Item { .. });
+                    markdown.push_str(if is_list_item(&nodes[index - 1]) && is_list_item(node) {
+                        "\n"
+                    } else {
+                        "\n\n"
+                    });
+                }
+                markdown.push_str(node_to_markdown(node, source).trim_end());
+                markdown
+            });
+
+    if !markdown.is_empty() {
+        markdown.push('\n');
+    }
+
+    markdown
+}
+
+/// Returns the markdown of a single node, either from the `source` or regenerated from the AST.
+fn node_to_markdown(node: &Node, source: &str) -> String {
+    let is_task = matches!(
+        node.markdown_node,
+        MarkdownNode::Item {
+            kind: Some(ItemKind::Unchecked | ItemKind::Checked | ItemKind::HardChecked),
+            ..
+        }
+    );
+
+    let source_range = if is_task {
+        task_item_range(source, &node.source_range)
+    } else {
+        Some(node.source_range.clone())
+    };
+
+    // Nested list items retain their indentation
+    let source_range = source_range.map(|range| match node.markdown_node {
+        MarkdownNode::Item { .. } => indentation_start(source, range.start)..range.end,
+        _ => range,
+    });
+
+    match source_range.and_then(|range| source.get(range)) {
+        Some(slice) if !slice.is_empty() => slice.to_string(),
+        _ => regenerate_markdown(node, source),
+    }
+}
+
+/// Returns the start of the line if there is only whitespace between it and `start`.
+fn indentation_start(source: &str, start: usize) -> usize {
+    let Some(before) = source.get(..start) else {
+        return start;
+    };
+
+    let line_start = before.rfind('\n').map_or(0, |index| index + 1);
+
+    if before[line_start..].chars().all(char::is_whitespace) {
+        line_start
+    } else {
+        start
+    }
+}
+
+/// Returns the range of the innermost list item that contains the task marker.
+fn task_item_range(source: &str, marker_range: &Range<usize>) -> Option<Range<usize>> {
+    source.get(marker_range.clone())?;
+
+    pulldown_cmark::Parser::new_ext(source, Options::all())
+        .into_offset_iter()
+        .filter_map(|(event, range)| match event {
+            Event::Start(Tag::Item) if range.contains(&marker_range.start) => Some(range),
+            _ => None,
+        })
+        .max_by_key(|range| range.start)
+}
+
+fn regenerate_markdown(node: &Node, source: &str) -> String {
+    match &node.markdown_node {
+        MarkdownNode::Heading { level, text } => format!(
+            "{} {}",
+            "#".repeat(level.clone() as usize),
+            text_to_markdown(text)
+        ),
+        MarkdownNode::Paragraph { text } => text_to_markdown(text),
+        MarkdownNode::CodeBlock { lang, text } => {
+            let code = text
+                .clone()
+                .into_iter()
+                .map(|node| node.content)
+                .collect::<String>();
+
+            // The fence must be longer than any backtick sequence within the code
+            let fence = "`".repeat(longest_backtick_run(&code).max(2) + 1);
+            let line_break = if code.ends_with('\n') || code.is_empty() {
+                ""
+            } else {
+                "\n"
+            };
+
+            format!(
+                "{fence}{}\n{code}{line_break}{fence}",
+                lang.as_deref().unwrap_or_default()
+            )
+        }
+        MarkdownNode::Item { kind, text } => {
+            let marker = match kind {
+                Some(ItemKind::HardChecked) => "- [x]".to_string(),
+                Some(ItemKind::Checked) => "- [?]".to_string(),
+                Some(ItemKind::Unchecked) => "- [ ]".to_string(),
+                Some(ItemKind::Ordered(number)) => format!("{number}."),
+                Some(ItemKind::Unordered) | None => "-".to_string(),
+            };
+            format!(
+                "{}{marker} {}",
+                "  ".repeat(node.depth.saturating_sub(1)),
+                text_to_markdown(text)
+            )
+        }
+        MarkdownNode::BlockQuote { kind, nodes } => {
+            let callout = kind.as_ref().map(|kind| format!("[!{}]\n", kind.label()));
+
+            // The depth of the child nodes is relative to the block quote
+            let nodes = nodes
+                .iter()
+                .cloned()
+                .map(|child| {
+                    let depth = child.depth.saturating_sub(node.depth + 1);
+                    child.with_depth(depth)
+                })
+                .collect::<Vec<_>>();
+
+            format!(
+                "{}{}",
+                callout.unwrap_or_default(),
+                to_markdown(&nodes, source)
+            )
+            .lines()
+            .map(|line| match line {
+                "" => ">".to_string(),
+                line => format!("> {line}"),
+            })
+            .collect::<Vec<_>>()
+            .join("\n")
+        }
+    }
+}
+
+fn longest_backtick_run(text: &str) -> usize {
+    text.split(|c| c != '`')
+        .map(str::len)
+        .max()
+        .unwrap_or_default()
+}
+
+/// Converts the [`Text`] into inline markdown, wrapping code in backticks and escaping the
+/// characters that would otherwise be interpreted as markdown syntax.
+fn text_to_markdown(text: &Text) -> String {
+    let markdown = text
+        .clone()
+        .into_iter()
+        .map(|node| match node.style {
+            Some(Style::Code) => {
+                let fence = "`".repeat(longest_backtick_run(&node.content) + 1);
+                let padding = if node.content.starts_with('`') || node.content.ends_with('`') {
+                    " "
+                } else {
+                    ""
+                };
+                format!("{fence}{padding}{}{padding}{fence}", node.content)
+            }
+            _ => escape_markdown(&node.content),
+        })
+        .collect::<String>();
+
+    escape_line_start(&markdown)
+}
+
+fn escape_markdown(text: &str) -> String {
+    text.chars().fold(String::new(), |mut escaped, c| {
+        if "\\`*_[]<>#|~$&!{}".contains(c) {
+            escaped.push('\\');
+        }
+        escaped.push(c);
+        escaped
+    })
+}
+
+/// Escapes the characters at the start of a line that would begin a list item or a thematic
+/// break, e.g. `- text`, `+ text` or `1. text`.
+fn escape_line_start(text: &str) -> String {
+    if text.starts_with(['-', '+', '=']) {
+        return format!("\\{text}");
+    }
+
+    let digits = text.chars().take_while(char::is_ascii_digit).count();
+    match text[digits..].chars().next() {
+        Some('.' | ')') if digits > 0 => {
+            format!("{}\\{}", &text[..digits], &text[digits..])
+        }
+        _ => text.to_string(),
+    }
+}
+
+/// A parser that consumes [`pulldown_cmark::Event`]s and produces a [`Vec`] of [`Node`].
+///
+/// The parser iterates [`Node`]s through [`IntoIterator`], so it does not yield the
+/// [`pulldown_cmark::Event`]s itself. Use [`pulldown_cmark::Parser`] to iterate the events.
+///
+/// # Examples
+///
+/// ```
+/// use basalt_core::markdown::{Parser, Range, Node, MarkdownNode, HeadingLevel, Text};
+///
+/// let markdown = "# My Heading\n\nSome text.";
+/// let parser = Parser::new(markdown);
+/// let nodes = parser.parse();
+///
+/// assert_eq!(nodes, vec![
+///   Node {
 ///     markdown_node: MarkdownNode::Heading {
 ///       level: HeadingLevel::H1,
 ///       text: Text::from("My Heading"),
 ///     },
 ///     source_range: Range { start: 0, end: 13 },
+///     depth: 0,
 ///   },
 ///   Node {
 ///     markdown_node: MarkdownNode::Paragraph {
 ///       text: Text::from("Some text."),
 ///     },
 ///     source_range: Range { start: 14, end: 24 },
+///     depth: 0,
 ///   },
 /// ])
 /// ```
 pub struct Parser<'a> {
-    /// Contains the completed AST [`Node`]s.
+    /// Contains the completed AST [`Node`]s that have not been yielded by the [`NodeIterator`]
+    /// yet.
     pub output: Vec<Node>,
     inner: pulldown_cmark::TextMergeWithOffset<'a, pulldown_cmark::OffsetIter<'a>>,
+    /// The source text, used to tell the delimiters of emphasis apart.
+    text: &'a str,
     current_node: Option<Node>,
+    /// The inline styles that are open, the innermost last. Text gets the innermost style.
+    styles: Vec<Style>,
+    /// The number of currently open lists and block quotes.
+    depth: usize,
+    /// Whether callout markers are parsed, see [`Options::ENABLE_GFM`].
+    callouts: bool,
+    /// Whether line breaks are kept as text, see [`Parser::with_breaks`].
+    breaks: bool,
+}
+
+impl<'a> IntoIterator for Parser<'a> {
+    type Item = Node;
+    type IntoIter = NodeIterator<'a>;
+    fn into_iter(self) -> Self::IntoIter {
+        NodeIterator(self)
+    }
 }
 
-impl<'a> Iterator for Parser<'a> {
-    type Item = (Event<'a>, Range<usize>);
+/// An iterator that yields the [`Node`]s of a [`Parser`] one at a time.
+///
+/// The events of the underlying [`pulldown_cmark::Parser`] are processed only until the next node
+/// is complete, so large documents can be processed without building the whole AST first.
+///
+/// # Examples
+///
+/// ```
+/// use basalt_core::markdown::{MarkdownNode, Parser, Text};
+///
+/// let mut headings = vec![];
+///
+/// for node in Parser::new("# Title\n\nText\n\n## Section") {
+///     if let MarkdownNode::Heading { text, .. } = node.markdown_node {
+///         headings.push(text);
+///     }
+/// }
+///
+/// assert_eq!(headings, [Text::from("Title"), Text::from("Section")]);
+/// ```
+pub struct NodeIterator<'a>(Parser<'a>);
+
+impl Iterator for NodeIterator<'_> {
+    type Item = Node;
     fn next(&mut self) -> Option<Self::Item> {
-        self.inner.next()
+        let parser = &mut self.0;
+
+        loop {
+            if !parser.output.is_empty() {
+                return Some(parser.output.remove(0));
+            }
+
+            match parser.inner.next() {
+                Some((event, range)) => parser.handle_event(event, range),
+                // The last node is complete when the events end
+                None => return parser.current_node.take(),
+            }
+        }
     }
 }
 
 impl<'a> Parser<'a> {
-    /// Creates a new [`Parser`] from a Markdown input string.
-    ///
-    /// The parser uses [`pulldown_cmark::Parser::new_ext`] with [`Options::all()`] and
-    /// [`pulldown_cmark::TextMergeWithOffset`] internally.
+    /// Creates a new [`Parser`] from a Markdown input string with all [`Options`] enabled.
     ///
-    /// The offset is required to know where the node appears in the provided source text.
+    /// This is a convenience function for calling [`Parser::new_with_options`] with
+    /// [`Options::all()`].
     pub fn new(text: &'a str) -> Self {
+        Self::new_with_options(text, Options::all())
+    }
+
+    /// Creates a new [`Parser`] from a Markdown input string with the given [`Options`].
+    ///
+    /// The parser uses [`pulldown_cmark::Parser::new_ext`] and
+    /// [`pulldown_cmark::TextMergeWithOffset`] internally. The offset is required to know where
+    /// the node appears in the provided source text.
+    ///
+    /// The options affect the produced nodes as follows:
+    ///
+    /// - [`Options::ENABLE_TASKLISTS`]: task list items are parsed into [`MarkdownNode::Item`]s
+    ///   with an [`ItemKind`]. Otherwise the `[ ]` marker is part of the item text.
+    /// - [`Options::ENABLE_GFM`]: callouts are parsed into [`MarkdownNode::BlockQuote`]s with a
+    ///   [`BlockQuoteKind`], including the types that are not GitHub alert types, e.g. `[!bug]`.
+    ///   Otherwise the `[!NOTE]` marker is part of the block quote text.
+    /// - [`Options::ENABLE_STRIKETHROUGH`]: `~~text~~` is parsed into a [`TextNode`] with
+    ///   [`Style::Strikethrough`].
+    /// - [`Options::ENABLE_HEADING_ATTRIBUTES`]: `{#id .class}` attributes are removed from the
+    ///   [`MarkdownNode::Heading`] text.
+    /// - [`Options::ENABLE_SMART_PUNCTUATION`]: quotes, dashes, and ellipses are replaced with
+    ///   their typographic variants in the text.
+    /// - [`Options::ENABLE_YAML_STYLE_METADATA_BLOCKS`] and
+    ///   [`Options::ENABLE_PLUSES_DELIMITED_METADATA_BLOCKS`]: front matter is skipped instead of
+    ///   being parsed into [`MarkdownNode::Paragraph`]s and [`MarkdownNode::Heading`]s.
+    /// - [`Options::ENABLE_TABLES`], [`Options::ENABLE_FOOTNOTES`], [`Options::ENABLE_MATH`], and
+    ///   [`Options::ENABLE_DEFINITION_LIST`]: the content is recognized but not represented in
+    ///   the AST yet.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use basalt_core::markdown::{MarkdownNode, Options, Parser};
+    ///
+    /// let nodes = Parser::new_with_options("- [ ] Task", Options::empty()).parse();
+    ///
+    /// assert!(matches!(nodes[0].markdown_node, MarkdownNode::Item { kind: None, .. }));
+    /// ```
+    pub fn new_with_options(text: &'a str, options: Options) -> Self {
         let parser = pulldown_cmark::TextMergeWithOffset::new(
-            pulldown_cmark::Parser::new_ext(text, Options::all()).into_offset_iter(),
+            pulldown_cmark::Parser::new_ext(text, options).into_offset_iter(),
         );
 
         Self {
             inner: parser,
+            text,
             output: vec![],
             current_node: None,
+            styles: vec![],
+            depth: 0,
+            callouts: options.contains(Options::ENABLE_GFM),
+            breaks: false,
+        }
+    }
+
+    /// Keeps the line breaks inside paragraphs, headings and items as [`TextNode`]s with a `\n`
+    /// content and no [`Style`], so that the lines of the source can be told apart. By default
+    /// the line breaks are left out and the text of the lines follows each other.
+    ///
+    /// Both soft breaks, i.e. plain newlines, and hard breaks, e.g. a newline after two spaces,
+    /// are kept.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use basalt_core::markdown::{MarkdownNode, Parser, Text, TextNode};
+    ///
+    /// let nodes = Parser::new("First line\nsecond line").with_breaks().parse();
+    ///
+    /// assert_eq!(
+    ///     nodes[0].markdown_node,
+    ///     MarkdownNode::Paragraph {
+    ///         text: Text::from(vec![
+    ///             TextNode::from("First line"),
+    ///             TextNode::from("\n"),
+    ///             TextNode::from("second line"),
+    ///         ]),
+    ///     }
+    /// );
+    /// ```
+    pub fn with_breaks(self) -> Self {
+        Self {
+            breaks: true,
+            ..self
         }
     }
 
@@ -471,55 +1957,126 @@ impl<'a> Parser<'a> {
         self.current_node.replace(block.clone());
     }
 
+    /// Finishes the list item that contains a nested list starting at `start`.
+    ///
+    /// The source range of the item is cut at the start of the nested list, so the range does not
+    /// overlap with the nested items.
+    fn finish_parent_item(&mut self, start: usize) {
+        match &mut self.current_node {
+            Some(Node {
+                markdown_node: MarkdownNode::BlockQuote { nodes, .. },
+                ..
+            }) => {
+                if let Some(
+                    node @ Node {
+                        markdown_node: MarkdownNode::Item { .. },
+                        ..
+                    },
+                ) = nodes.last_mut()
+                {
+                    node.source_range.end = node.source_range.end.min(start);
+                }
+            }
+            Some(Node {
+                markdown_node: MarkdownNode::Item { .. },
+                ..
+            }) => {
+                if let Some(mut node) = self.current_node.take() {
+                    node.source_range.end = node.source_range.end.min(start);
+                    self.output.push(node);
+                }
+            }
+            _ => {}
+        }
+    }
+
     /// Handles the start of a [`Tag`]. Pushes the matching semantic node to be processed.
     fn tag(&mut self, tag: Tag<'a>, range: Range<usize>) {
+        let depth = self.depth;
+
         match tag {
-            Tag::Paragraph => self.push_node(Node::new(
-                MarkdownNode::Paragraph {
-                    text: Text::default(),
-                },
-                range,
-            )),
-            Tag::Heading { level, .. } => self.push_node(Node::new(
-                MarkdownNode::Heading {
-                    level: level.into(),
-                    text: Text::default(),
-                },
-                range,
-            )),
-            Tag::BlockQuote(kind) => self.push_node(Node::new(
-                MarkdownNode::BlockQuote {
-                    kind: kind.map(|kind| kind.into()),
-                    nodes: vec![],
-                },
-                range,
-            )),
-            Tag::CodeBlock(_) => self.push_node(Node::new(
-                MarkdownNode::CodeBlock {
-                    lang: None,
-                    text: Text::default(),
-                },
-                range,
-            )),
-            Tag::Item => self.push_node(Node::new(
-                MarkdownNode::Item {
-                    kind: None,
-                    text: Text::default(),
-                },
-                range,
-            )),
+            Tag::Paragraph => self.push_node(
+                Node::new(
+                    MarkdownNode::Paragraph {
+                        text: Text::default(),
+                    },
+                    range,
+                )
+                .with_depth(depth),
+            ),
+            Tag::Heading { level, .. } => self.push_node(
+                Node::new(
+                    MarkdownNode::Heading {
+                        level: level.into(),
+                        text: Text::default(),
+                    },
+                    range,
+                )
+                .with_depth(depth),
+            ),
+            Tag::BlockQuote(kind) => {
+                self.push_node(
+                    Node::new(
+                        MarkdownNode::BlockQuote {
+                            kind: kind.map(|kind| kind.into()),
+                            nodes: vec![],
+                        },
+                        range,
+                    )
+                    .with_depth(depth),
+                );
+                self.depth += 1;
+            }
+            Tag::CodeBlock(kind) => self.push_node(
+                Node::new(
+                    MarkdownNode::CodeBlock {
+                        lang: match kind {
+                            // The language is the first word of the info string, e.g. `rust` in
+                            // "```rust ignore"
+                            CodeBlockKind::Fenced(info) => {
+                                info.split_whitespace().next().map(String::from)
+                            }
+                            CodeBlockKind::Indented => None,
+                        },
+                        text: Text::default(),
+                    },
+                    range,
+                )
+                .with_depth(depth),
+            ),
+            Tag::Item => self.push_node(
+                Node::new(
+                    MarkdownNode::Item {
+                        kind: None,
+                        text: Text::default(),
+                    },
+                    range,
+                )
+                .with_depth(depth),
+            ),
+            Tag::List(_) => {
+                self.finish_parent_item(range.start);
+                self.depth += 1;
+            }
+            Tag::Emphasis => self.styles.push(match self.delimiter(&range) {
+                Some('_') => Style::Emphasis,
+                _ => Style::Italic,
+            }),
+            Tag::Strong => self.styles.push(match self.delimiter(&range) {
+                Some('_') => Style::Bold,
+                _ => Style::Strong,
+            }),
+            Tag::Strikethrough => self.styles.push(Style::Strikethrough),
+            Tag::Link { dest_url, .. } => self.styles.push(Style::Link {
+                url: dest_url.to_string(),
+            }),
             // For now everything below this comment are defined as paragraph nodes
             Tag::HtmlBlock
-            | Tag::List(_)
             | Tag::FootnoteDefinition(_)
             | Tag::Table(_)
             | Tag::TableHead
             | Tag::TableRow
             | Tag::TableCell
-            | Tag::Emphasis
-            | Tag::Strong
-            | Tag::Strikethrough
-            | Tag::Link { .. }
             | Tag::Image { .. }
             | Tag::MetadataBlock(_)
             | Tag::DefinitionList
@@ -528,13 +2085,36 @@ impl<'a> Parser<'a> {
         }
     }
 
+    /// Returns the delimiter character of the inline element at the source range, e.g. `*` for
+    /// `*italic*`.
+    fn delimiter(&self, range: &Range<usize>) -> Option<char> {
+        self.text.get(range.clone())?.chars().next()
+    }
+
     /// Handles the end of a [`Tag`], finalizing a node if matching.
     fn tag_end(&mut self, tag_end: TagEnd) {
+        if matches!(tag_end, TagEnd::List(..) | TagEnd::BlockQuote(..)) {
+            self.depth = self.depth.saturating_sub(1);
+        }
+
+        if matches!(
+            tag_end,
+            TagEnd::Emphasis | TagEnd::Strong | TagEnd::Strikethrough | TagEnd::Link
+        ) {
+            self.styles.pop();
+            return;
+        }
+
         let Some(node) = self.current_node.take() else {
             return;
         };
 
         if matches_tag_end(&node, &tag_end) {
+            let node = if self.callouts {
+                with_callout(node)
+            } else {
+                node
+            };
             self.output.push(node);
         } else {
             self.set_node(&node);
@@ -546,7 +2126,9 @@ impl<'a> Parser<'a> {
         match event {
             Event::Start(tag) => self.tag(tag, range),
             Event::End(tag_end) => self.tag_end(tag_end),
-            Event::Text(text) => self.push_text_node(TextNode::new(text.to_string(), None)),
+            Event::Text(text) => {
+                self.push_text_node(TextNode::new(text.to_string(), self.styles.last().cloned()))
+            }
             Event::Code(text) => {
                 self.push_text_node(TextNode::new(text.to_string(), Some(Style::Code)))
             }
@@ -555,27 +2137,37 @@ impl<'a> Parser<'a> {
                 // TODO: Add implementation for ListBlock, which will retain the complete source
                 // range.
                 if checked {
-                    self.set_node(&Node::new(
-                        MarkdownNode::Item {
-                            kind: Some(ItemKind::HardChecked),
-                            text: Text::default(),
-                        },
-                        range,
-                    ));
+                    self.set_node(
+                        &Node::new(
+                            MarkdownNode::Item {
+                                kind: Some(ItemKind::HardChecked),
+                                text: Text::default(),
+                            },
+                            range,
+                        )
+                        .with_depth(self.depth),
+                    );
                 } else {
-                    self.set_node(&Node::new(
-                        MarkdownNode::Item {
-                            kind: Some(ItemKind::Unchecked),
-                            text: Text::default(),
-                        },
-                        range,
-                    ));
+                    self.set_node(
+                        &Node::new(
+                            MarkdownNode::Item {
+                                kind: Some(ItemKind::Unchecked),
+                                text: Text::default(),
+                            },
+                            range,
+                        )
+                        .with_depth(self.depth),
+                    );
                 }
             }
+            Event::Html(html) | Event::InlineHtml(html) => Text::from_html(&html)
+                .into_iter()
+                .for_each(|node| self.push_text_node(node)),
+            Event::SoftBreak | Event::HardBreak if self.breaks => {
+                self.push_text_node(TextNode::from("\n"))
+            }
             Event::InlineMath(_)
             | Event::DisplayMath(_)
-            | Event::Html(_)
-            | Event::InlineHtml(_)
             | Event::SoftBreak
             | Event::HardBreak
             | Event::Rule
@@ -586,7 +2178,7 @@ impl<'a> Parser<'a> {
     }
 
     /// Consumes the parser, processing all remaining events from the stream into a list of
-    /// [`Node`]s.
+    /// [`Node`]s. Use [`Parser::into_iter`] to process the nodes one at a time instead.
     ///
     /// # Examples
     ///
@@ -602,25 +2194,19 @@ impl<'a> Parser<'a> {
     ///       text: Text::from("Hello world"),
     ///     },
     ///     source_range: Range { start: 0, end: 11 },
+    ///     depth: 0,
     ///   },
     /// ]);
     /// ```
-    pub fn parse(mut self) -> Vec<Node> {
-        while let Some((event, range)) = self.next() {
-            self.handle_event(event, range);
-        }
-
-        if let Some(node) = self.current_node.take() {
-            self.output.push(node);
-        }
-
-        self.output
+    pub fn parse(self) -> Vec<Node> {
+        self.into_iter().collect()
     }
 }
 
 #[cfg(test)]
 mod tests {
     use indoc::indoc;
+    use proptest::prelude::*;
 
     fn p(str: &str, range: Range<usize>) -> Node {
         Node::new(MarkdownNode::Paragraph { text: str.into() }, range)
@@ -734,9 +2320,9 @@ mod tests {
                 "#},
                 vec![
                     h2("Tasks", 0..9),
-                    task("Task", 12..15),
-                    completed_task("Completed task", 24..27),
-                    p("[?] Completed task", 46..65),
+                    task("Task", 12..15).with_depth(1),
+                    completed_task("Completed task", 24..27).with_depth(1),
+                    p("[?] Completed task", 46..65).with_depth(1),
                 ],
             ),
             (
@@ -753,7 +2339,7 @@ mod tests {
                     Node::new(MarkdownNode::Paragraph {
                         text: vec![
                             TextNode::new("You ".into(), None),
-                            TextNode::new("can".into(),None),
+                            TextNode::new("can".into(), Some(Style::Emphasis)),
                             TextNode::new(" quote text by adding a ".into(), None),
                             TextNode::new(">".into(), Some(Style::Code)),
                             TextNode::new(" symbols before the text.".into(), None),
@@ -761,8 +2347,8 @@ mod tests {
                         .into(),
                     }, 11..73),
                     blockquote(vec![
-                        p("Human beings face ever more complex and urgent problems, and their effectiveness in dealing with these problems is a matter that is critical to the stability and continued progress of society.", 76..269),
-                        item("Doug Engelbart, 1961", 272..295)
+                        p("Human beings face ever more complex and urgent problems, and their effectiveness in dealing with these problems is a matter that is critical to the stability and continued progress of society.", 76..269).with_depth(1),
+                        item("Doug Engelbart, 1961", 272..295).with_depth(2)
                     ], 74..295),
                 ],
             ),
@@ -772,4 +2358,921 @@ mod tests {
             .iter()
             .for_each(|test| assert_eq!(from_str(test.0), test.1));
     }
+
+    #[test]
+    fn test_nested_list_depth() {
+        let markdown = indoc! {r#"- First
+              - Second
+                - Third
+            - Fourth
+
+            > - Quoted
+            >   - Nested quoted
+            "#};
+
+        let nodes = from_str(markdown);
+
+        assert_eq!(
+            nodes,
+            vec![
+                item("First", 0..10).with_depth(1),
+                item("Second", 10..23).with_depth(2),
+                item("Third", 23..31).with_depth(3),
+                item("Fourth", 31..41).with_depth(1),
+                blockquote(
+                    vec![
+                        item("Quoted", 43..56).with_depth(2),
+                        item("Nested quoted", 56..72).with_depth(3),
+                    ],
+                    41..72
+                ),
+            ]
+        );
+
+        assert_eq!(to_markdown(&nodes, markdown), markdown);
+        assert_eq!(
+            to_markdown(&nodes, ""),
+            indoc! {r#"- First
+              - Second
+                - Third
+            - Fourth
+
+            > - Quoted
+            >   - Nested quoted
+            "#}
+        );
+    }
+
+    #[test]
+    fn test_parse_inline_styles() {
+        let text = |markdown| match &from_str(markdown)[0].markdown_node {
+            MarkdownNode::Paragraph { text } => text.clone(),
+            node => panic!("expected paragraph, got {node:?}"),
+        };
+
+        assert_eq!(
+            text("*em* and **strong**"),
+            vec![
+                TextNode::new("em".into(), Some(Style::Italic)),
+                TextNode::new(" and ".into(), None),
+                TextNode::new("strong".into(), Some(Style::Strong)),
+            ]
+            .into()
+        );
+        assert_eq!(
+            text("_em_ __bold__ ~~gone~~ [basalt](https://example.com)"),
+            vec![
+                TextNode::new("em".into(), Some(Style::Emphasis)),
+                TextNode::new(" ".into(), None),
+                TextNode::new("bold".into(), Some(Style::Bold)),
+                TextNode::new(" ".into(), None),
+                TextNode::new("gone".into(), Some(Style::Strikethrough)),
+                TextNode::new(" ".into(), None),
+                TextNode::new(
+                    "basalt".into(),
+                    Some(Style::Link {
+                        url: "https://example.com".into()
+                    })
+                ),
+            ]
+            .into()
+        );
+    }
+
+    #[test]
+    fn test_parse_with_breaks() {
+        let markdown = "First sentence.\nSecond *sentence*.";
+
+        assert_eq!(
+            from_str_with_breaks(markdown),
+            vec![Node::new(
+                MarkdownNode::Paragraph {
+                    text: vec![
+                        TextNode::new("First sentence.".into(), None),
+                        TextNode::new("\n".into(), None),
+                        TextNode::new("Second ".into(), None),
+                        TextNode::new("sentence".into(), Some(Style::Italic)),
+                        TextNode::new(".".into(), None),
+                    ]
+                    .into(),
+                },
+                0..34,
+            )]
+        );
+
+        assert_eq!(
+            from_str(markdown),
+            vec![Node::new(
+                MarkdownNode::Paragraph {
+                    text: vec![
+                        TextNode::new("First sentence.".into(), None),
+                        TextNode::new("Second ".into(), None),
+                        TextNode::new("sentence".into(), Some(Style::Italic)),
+                        TextNode::new(".".into(), None),
+                    ]
+                    .into(),
+                },
+                0..34,
+            )],
+            "Line breaks are left out by default"
+        );
+
+        assert_eq!(
+            from_str_with_breaks("- Hard  \n  break"),
+            vec![Node::new(
+                MarkdownNode::Item {
+                    kind: None,
+                    text: vec![
+                        TextNode::new("Hard".into(), None),
+                        TextNode::new("\n".into(), None),
+                        TextNode::new("break".into(), None),
+                    ]
+                    .into(),
+                },
+                0..16,
+            )
+            .with_depth(1)]
+        );
+    }
+
+    #[test]
+    fn test_parse_with_options() {
+        let markdown = indoc! {r#"# Heading {#id}
+
+            - [ ] Task
+            "#};
+
+        assert_eq!(
+            Parser::new_with_options(markdown, Options::empty()).parse(),
+            vec![
+                h1("Heading {#id}", 0..16),
+                item("[ ] Task", 17..28).with_depth(1)
+            ]
+        );
+
+        assert_eq!(
+            Parser::new_with_options(markdown, obsidian_options()).parse(),
+            vec![
+                h1("Heading {#id}", 0..16),
+                task("Task", 19..22).with_depth(1)
+            ]
+        );
+
+        assert_eq!(
+            Parser::new(markdown).parse(),
+            vec![h1("Heading", 0..16), task("Task", 19..22).with_depth(1)]
+        );
+    }
+
+    #[test]
+    fn test_custom_callout() {
+        let kind = |markdown| match &from_str(markdown)[0].markdown_node {
+            MarkdownNode::BlockQuote { kind, nodes } => (kind.clone(), nodes.len()),
+            node => panic!("Expected a block quote, got {node:?}"),
+        };
+
+        assert_eq!(
+            kind("> [!bug]\n> Crash on start"),
+            (Some(BlockQuoteKind::Custom("bug".into())), 1)
+        );
+        assert_eq!(
+            kind("> [!Example]+ Usage\n>\n> Body"),
+            (Some(BlockQuoteKind::Custom("Example".into())), 2)
+        );
+        assert_eq!(kind("> [!NOTE]\n> Note"), (Some(BlockQuoteKind::Note), 1));
+        assert_eq!(
+            kind("> [!bug]"),
+            (Some(BlockQuoteKind::Custom("bug".into())), 0)
+        );
+        assert_eq!(kind("> Quote with [!bug]"), (None, 1));
+
+        assert_eq!(
+            from_str("> [!bug] Crash\n> on start"),
+            vec![Node::new(
+                MarkdownNode::BlockQuote {
+                    kind: Some(BlockQuoteKind::Custom("bug".into())),
+                    nodes: vec![Node::new(
+                        MarkdownNode::Paragraph {
+                            text: Text(vec!["Crash".into(), "on start".into()]),
+                        },
+                        2..25
+                    )
+                    .with_depth(1)],
+                },
+                0..25
+            )]
+        );
+
+        // Without GFM the marker stays in the text
+        assert_eq!(
+            Parser::new_with_options("> [!bug] Crash", Options::empty()).parse(),
+            vec![blockquote(
+                vec![p("[!bug] Crash", 2..14).with_depth(1)],
+                0..14
+            )]
+        );
+    }
+
+    #[test]
+    fn test_predicates() {
+        let heading = MarkdownNode::Heading {
+            level: HeadingLevel::H3,
+            text: "Heading".into(),
+        };
+        let paragraph = MarkdownNode::Paragraph {
+            text: "Paragraph".into(),
+        };
+        let block_quote = MarkdownNode::BlockQuote {
+            kind: Some(BlockQuoteKind::Note),
+            nodes: vec![],
+        };
+        let code_block = MarkdownNode::CodeBlock {
+            lang: None,
+            text: "Code".into(),
+        };
+        let item = |kind| MarkdownNode::Item {
+            kind,
+            text: "Item".into(),
+        };
+
+        type Predicate = (&'static str, fn(&MarkdownNode) -> bool, fn(&Node) -> bool);
+        type Test = (
+            MarkdownNode,
+            &'static [&'static str],
+            Option<HeadingLevel>,
+            Option<&'static str>,
+        );
+
+        let predicates: [Predicate; 6] = [
+            ("is_heading", MarkdownNode::is_heading, Node::is_heading),
+            (
+                "is_paragraph",
+                MarkdownNode::is_paragraph,
+                Node::is_paragraph,
+            ),
+            (
+                "is_code_block",
+                MarkdownNode::is_code_block,
+                Node::is_code_block,
+            ),
+            ("is_list", MarkdownNode::is_list, Node::is_list),
+            (
+                "is_block_quote",
+                MarkdownNode::is_block_quote,
+                Node::is_block_quote,
+            ),
+            ("is_task", MarkdownNode::is_task, Node::is_task),
+        ];
+
+        // (node, predicates that return true, heading level, text)
+        let tests: [Test; 10] = [
+            (
+                heading,
+                &["is_heading"],
+                Some(HeadingLevel::H3),
+                Some("Heading"),
+            ),
+            (paragraph, &["is_paragraph"], None, Some("Paragraph")),
+            (block_quote, &["is_block_quote"], None, None),
+            (code_block, &["is_code_block"], None, Some("Code")),
+            (item(None), &["is_list"], None, Some("Item")),
+            (
+                item(Some(ItemKind::Unordered)),
+                &["is_list"],
+                None,
+                Some("Item"),
+            ),
+            (
+                item(Some(ItemKind::Ordered(1))),
+                &["is_list"],
+                None,
+                Some("Item"),
+            ),
+            (
+                item(Some(ItemKind::Unchecked)),
+                &["is_list", "is_task"],
+                None,
+                Some("Item"),
+            ),
+            (
+                item(Some(ItemKind::Checked)),
+                &["is_list", "is_task"],
+                None,
+                Some("Item"),
+            ),
+            (
+                item(Some(ItemKind::HardChecked)),
+                &["is_list", "is_task"],
+                None,
+                Some("Item"),
+            ),
+        ];
+
+        tests
+            .into_iter()
+            .for_each(|(markdown_node, expected, level, text)| {
+                let node = Node::new(markdown_node.clone(), 0..1);
+                let text = text.map(Text::from);
+
+                predicates
+                    .iter()
+                    .for_each(|(name, markdown_node_predicate, node_predicate)| {
+                        let expected = expected.contains(name);
+                        assert_eq!(
+                            markdown_node_predicate(&markdown_node),
+                            expected,
+                            "MarkdownNode::{name} {markdown_node:?}"
+                        );
+                        assert_eq!(node_predicate(&node), expected, "Node::{name} {node:?}");
+                    });
+
+                assert_eq!(markdown_node.heading_level(), level);
+                assert_eq!(node.heading_level(), level);
+                assert_eq!(markdown_node.as_text(), text.as_ref());
+                assert_eq!(node.as_text(), text.as_ref());
+            });
+    }
+
+    #[test]
+    fn test_counts() {
+        let tests = [
+            ("", TextCounts::default()),
+            (
+                "Sentence with punctuation? Yes! - and a dash",
+                TextCounts {
+                    words: 7,
+                    chars: 44,
+                    reading_minutes: 1,
+                },
+            ),
+            (
+                indoc! {r#"---
+                title: Front matter
+                ---
+
+                See [the docs](https://example.com/very/long/url) and `code`.
+                "#},
+                TextCounts {
+                    words: 5,
+                    chars: 22,
+                    reading_minutes: 1,
+                },
+            ),
+            (
+                indoc! {r#"> [!note]
+                > Quoted words
+
+                ```rust
+                fn main() {
+                    println!("Not counted");
+                }
+                ```
+                "#},
+                TextCounts {
+                    words: 2,
+                    chars: 12,
+                    reading_minutes: 1,
+                },
+            ),
+        ];
+
+        tests.into_iter().for_each(|(input, expected)| {
+            assert_eq!(counts(&from_str(input)), expected, "With input {input}")
+        });
+
+        let nodes = from_str(&"word ".repeat(401));
+        assert_eq!(counts(&nodes).reading_minutes, 3);
+        assert_eq!(counts_with_words_per_minute(&nodes, 0).reading_minutes, 401);
+    }
+
+    #[test]
+    fn test_task_stats() {
+        let tests = [
+            ("", TaskStats::default()),
+            ("# Heading\n\n- Item\n1. Ordered item", TaskStats::default()),
+            (
+                indoc! {r#"- [ ] Task
+                - [x] Done
+                  - [ ] Nested task
+                    - [X] Deeper done
+                1. [?] Loosely done
+                "#},
+                TaskStats { total: 5, done: 3 },
+            ),
+            (
+                indoc! {r#"> [!todo]
+                > - [x] Quoted done
+                >   - [ ] Nested quoted task
+                >
+                > > - [ ] Deep quoted task
+                "#},
+                TaskStats { total: 3, done: 1 },
+            ),
+        ];
+
+        tests.into_iter().for_each(|(input, expected)| {
+            assert_eq!(task_stats(&from_str(input)), expected, "With input {input}")
+        });
+    }
+
+    #[test]
+    #[allow(clippy::reversed_empty_ranges)]
+    fn test_source_slice() {
+        let source = "# Überschrift\n\nText";
+        let node = |range| {
+            Node::new(
+                MarkdownNode::Paragraph {
+                    text: Text::default(),
+                },
+                range,
+            )
+        };
+
+        let tests = [
+            (0..15, Some("# Überschrift\n")),
+            (16..20, Some("Text")),
+            (0..0, Some("")),
+            (2..3, None),
+            (16..21, None),
+            (20..16, None),
+        ];
+
+        tests.into_iter().for_each(|(range, expected)| {
+            assert_eq!(
+                node(range.clone()).source_slice(source),
+                expected,
+                "{range:?}"
+            );
+            if let Some(expected) = expected {
+                assert_eq!(node(range).source_slice_unchecked(source), expected);
+            }
+        });
+    }
+
+    #[test]
+    #[cfg(debug_assertions)]
+    #[should_panic(expected = "source range 2..3 is not on UTF-8 character boundaries")]
+    fn test_source_slice_unchecked_char_boundary() {
+        let node = Node::new(
+            MarkdownNode::Paragraph {
+                text: Text::default(),
+            },
+            2..3,
+        );
+        node.source_slice_unchecked("# Überschrift");
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_source_slice_unchecked_out_of_bounds() {
+        let node = Node::new(
+            MarkdownNode::Paragraph {
+                text: Text::default(),
+            },
+            0..100,
+        );
+        node.source_slice_unchecked("# Heading");
+    }
+
+    #[test]
+    fn test_find_by_range() {
+        let nodes = from_str("# Heading\n\nFirst paragraph\n\nSecond paragraph");
+
+        let tests = [
+            // Exact start of a node
+            (0, Some(0)),
+            (11, Some(1)),
+            (28, Some(2)),
+            // Middle of a node
+            (4, Some(0)),
+            (18, Some(1)),
+            (35, Some(2)),
+            // One past the end of a node
+            (10, None),
+            (27, None),
+            (44, None),
+        ];
+
+        tests.into_iter().for_each(|(offset, expected)| {
+            assert_eq!(Node::find_by_range(&nodes, offset), expected, "{offset}");
+        });
+
+        assert_eq!(Node::find_by_range(&[], 0), None);
+    }
+
+    #[test]
+    fn test_renumber_ordered_lists() {
+        let tests = [
+            ("1. One\n1. Two\n1. Three", "1. One\n2. Two\n3. Three"),
+            ("0) Zero\n5) One", "0) Zero\n1) One"),
+            ("- Bullet\n- Bullet", "- Bullet\n- Bullet"),
+            (
+                indoc! {r#"
+                    1. First
+                       - Bullet
+                         1. Nested
+                         1. Nested
+                       - Bullet
+                    1. Second
+                       1. Nested
+                       1. Nested
+                    1. [ ] Task
+                    1. [x] Task"#},
+                indoc! {r#"
+                    1. First
+                       - Bullet
+                         1. Nested
+                         2. Nested
+                       - Bullet
+                    2. Second
+                       1. Nested
+                       2. Nested
+                    3. [ ] Task
+                    4. [x] Task"#},
+            ),
+            (
+                indoc! {r#"
+                    > 9. Quoted
+                    > 9. Quoted
+
+                    Paragraph 1. with a number
+
+                    2. New list
+                    2. New list"#},
+                indoc! {r#"
+                    > 9. Quoted
+                    > 10. Quoted
+
+                    Paragraph 1. with a number
+
+                    2. New list
+                    3. New list"#},
+            ),
+        ];
+
+        tests.into_iter().for_each(|(markdown, expected)| {
+            assert_eq!(renumber_ordered_lists(markdown), expected);
+        });
+    }
+
+    #[test]
+    fn test_aliases() {
+        let tests = [
+            ("", vec![]),
+            ("# No frontmatter\n\naliases: [Other]", vec![]),
+            (
+                indoc! {r#"
+                    ---
+                    aliases: [Basalt TUI, "Project: B"]
+                    tags: [project]
+                    ---
+                    # Basalt"#},
+                vec!["Basalt TUI", "Project: B"],
+            ),
+            (
+                indoc! {r#"
+                    ---
+                    title: Basalt
+                    aliases:
+                      - Basalt TUI
+                      - 'bt'
+                    tags:
+                      - project
+                    ---"#},
+                vec!["Basalt TUI", "bt"],
+            ),
+            (
+                indoc! {r#"
+                    ---
+                    alias: Basalt
+                    ---"#},
+                vec!["Basalt"],
+            ),
+        ];
+
+        tests.into_iter().for_each(|(input, expected)| {
+            assert_eq!(aliases(input), expected, "With input {input}")
+        });
+    }
+
+    #[test]
+    fn test_properties() {
+        let property = |key: &str, values: &[&str]| Property {
+            key: key.into(),
+            values: values.iter().map(|value| value.to_string()).collect(),
+        };
+
+        let tests = [
+            ("", vec![]),
+            ("# No frontmatter\n\ntags: [notes]", vec![]),
+            (
+                indoc! {r#"
+                    ---
+                    title: "Project: Basalt"
+                    tags: [project, 'tui']
+                    aliases:
+                      - Basalt TUI
+                      - bt
+                    created: 2024-01-01
+                    draft:
+                    author:
+                      name: Basalt
+                    ---
+                    # Basalt"#},
+                vec![
+                    property("title", &["Project: Basalt"]),
+                    property("tags", &["project", "tui"]),
+                    property("aliases", &["Basalt TUI", "bt"]),
+                    property("created", &["2024-01-01"]),
+                    property("draft", &[]),
+                    property("author", &[]),
+                ],
+            ),
+        ];
+
+        tests.into_iter().for_each(|(input, expected)| {
+            assert_eq!(properties(input), expected, "With input {input}")
+        });
+    }
+
+    #[test]
+    fn test_tags() {
+        let tests = [
+            ("", vec![]),
+            ("#heading is not a tag\n\n# Heading", vec!["heading"]),
+            (
+                "Plans for #project/basalt, #idea and #idea again.",
+                vec!["project/basalt", "idea"],
+            ),
+            (
+                "Issue #123, url.com/#anchor, [[Note#Heading]] and C#",
+                vec![],
+            ),
+            (
+                "#tag-with_chars/nested/ and #2024-review",
+                vec!["tag-with_chars/nested", "2024-review"],
+            ),
+            (
+                indoc! {r#"
+                    Inline `#code` and
+
+                    ```
+                    #fenced
+                    ```
+                    *#emphasis*"#},
+                vec!["emphasis"],
+            ),
+            (
+                indoc! {r##"
+                    ---
+                    title: Daily
+                    tags: [daily, "#journal"]
+                    ---
+                    Text #daily"##},
+                vec!["daily", "journal"],
+            ),
+            (
+                indoc! {r#"
+                    ---
+                    tags:
+                      - project/basalt
+                      - 'idea'
+                    aliases: [Other]
+                    ---"#},
+                vec!["project/basalt", "idea"],
+            ),
+            (
+                indoc! {r#"
+                    ---
+                    tags: daily, journal
+                    ---"#},
+                vec!["daily", "journal"],
+            ),
+        ];
+
+        tests.into_iter().for_each(|(markdown, expected)| {
+            assert_eq!(tags(markdown), expected, "{markdown}");
+        });
+    }
+
+    #[test]
+    fn test_parse_html() {
+        assert_eq!(
+            from_str("Some <b>bold</b> text<br>&amp; a new line"),
+            [Node::new(
+                MarkdownNode::Paragraph {
+                    text: Text::from(vec![
+                        TextNode::from("Some "),
+                        TextNode::from("bold"),
+                        TextNode::from(" text"),
+                        TextNode::from("\n"),
+                        TextNode::from("& a new line"),
+                    ]),
+                },
+                0..41,
+            )]
+        );
+
+        let tests = [
+            ("", ""),
+            ("<p align=\"center\">", ""),
+            ("<em>text</em>", "text"),
+            ("a<br/>b<BR />c", "a\nb\nc"),
+            (
+                "&lt;tag&gt; &#38; &#x26; &unknown; & &amp",
+                "<tag> & & &unknown; & &amp",
+            ),
+            ("1 < 2", "1 < 2"),
+        ];
+
+        tests.into_iter().for_each(|(html, expected)| {
+            assert_eq!(
+                Text::from_html(html)
+                    .iter()
+                    .map(|node| node.content.as_str())
+                    .collect::<String>(),
+                expected,
+                "{html}"
+            )
+        });
+    }
+
+    #[test]
+    fn test_node_iterator() {
+        let markdown = indoc! {r#"# Heading
+
+            - Item
+              - Nested item
+
+            > [!NOTE]
+            > Quote
+
+            Paragraph"#};
+
+        let mut nodes = Parser::new(markdown).into_iter();
+
+        assert_eq!(
+            nodes.next().map(|node| node.markdown_node),
+            Some(MarkdownNode::Heading {
+                level: HeadingLevel::H1,
+                text: Text::from("Heading"),
+            })
+        );
+
+        let mut rest = vec![];
+        for node in nodes.by_ref() {
+            rest.push(node);
+        }
+
+        assert_eq!(rest, from_str(markdown)[1..]);
+        assert_eq!(nodes.next(), None);
+    }
+
+    #[test]
+    fn test_text_iter() {
+        let text = Text::from(vec![
+            TextNode::from("Run "),
+            TextNode::new("cargo".into(), Some(Style::Code)),
+        ]);
+
+        assert_eq!(
+            text.iter()
+                .map(|node| node.content.as_str())
+                .collect::<Vec<_>>(),
+            ["Run ", "cargo"]
+        );
+        assert_eq!((&text).into_iter().count(), 2);
+        assert_eq!(text.first(), Some(&TextNode::from("Run ")));
+        assert_eq!(
+            text.get(1).and_then(|node| node.style.as_ref()),
+            Some(&Style::Code)
+        );
+        assert_eq!(text.get(2), None);
+        assert_eq!(Text::default().first(), None);
+    }
+
+    /// Removes the source ranges, since they depend on the formatting of the serialized markdown.
+    fn without_ranges(nodes: Vec<Node>) -> Vec<MarkdownNode> {
+        nodes
+            .into_iter()
+            .map(|node| match node.markdown_node {
+                MarkdownNode::BlockQuote { kind, nodes } => MarkdownNode::BlockQuote {
+                    kind,
+                    nodes: without_ranges(nodes)
+                        .into_iter()
+                        .map(|markdown_node| Node::new(markdown_node, 0..0))
+                        .collect(),
+                },
+                markdown_node => markdown_node,
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_to_markdown() {
+        let tests = [
+            (
+                indoc! {r#"# Heading
+
+                Paragraph with *emphasis* and `code`
+                "#},
+                "# Heading\n\nParagraph with *emphasis* and `code`\n",
+                "# Heading\n\nParagraph with emphasis and `code`\n",
+            ),
+            (
+                indoc! {r#"## Tasks
+
+                - [ ] Task
+                - [x] Completed task
+                - Item
+                "#},
+                "## Tasks\n\n- [ ] Task\n- [x] Completed task\n- Item\n",
+                "## Tasks\n\n- [ ] Task\n- [x] Completed task\n- Item\n",
+            ),
+            (
+                indoc! {r#"```rust
+                let a = 1;
+                ```
+
+                > [!TIP]
+                > Quote
+                >
+                > - Item
+                "#},
+                "```rust\nlet a = 1;\n```\n\n> [!TIP]\n> Quote\n>\n> - Item\n",
+                "```rust\nlet a = 1;\n```\n\n> [!TIP]\n> Quote\n>\n> - Item\n",
+            ),
+            (
+                "1\\. Not a *list*\n\n\\- Not an item either\n",
+                "1\\. Not a *list*\n\n\\- Not an item either\n",
+                "1\\. Not a list\n\n\\- Not an item either\n",
+            ),
+        ];
+
+        tests.iter().for_each(|(input, from_source, regenerated)| {
+            let nodes = from_str(input);
+            assert_eq!(to_markdown(&nodes, input), *from_source);
+            assert_eq!(to_markdown(&nodes, ""), *regenerated);
+        });
+    }
+
+    fn code_block_langs(nodes: &[Node]) -> Vec<Option<String>> {
+        nodes
+            .iter()
+            .filter_map(|node| match &node.markdown_node {
+                MarkdownNode::CodeBlock { lang, .. } => Some(lang.clone()),
+                _ => None,
+            })
+            .collect()
+    }
+
+    fn markdown_block() -> impl Strategy<Value = String> {
+        let words = "[a-zA-Z][a-zA-Z0-9 ,.:?!#*_`>()-]{0,30}";
+        prop_oneof![
+            (1..=6usize, words).prop_map(|(level, text)| format!("{} {text}", "#".repeat(level))),
+            words,
+            prop::collection::vec(("[ x]", words), 1..4).prop_map(|tasks| tasks
+                .into_iter()
+                .map(|(checked, text)| format!("- [{checked}] {text}"))
+                .collect::<Vec<_>>()
+                .join("\n")),
+            prop::collection::vec(words, 1..4).prop_map(|items| items
+                .into_iter()
+                .map(|text| format!("- {text}"))
+                .collect::<Vec<_>>()
+                .join("\n")),
+            ("(rust)?", "[a-z =;]{0,20}")
+                .prop_map(|(lang, code)| format!("```{lang}\n{code}\n```")),
+            words.prop_map(|text| format!("> {text}")),
+        ]
+    }
+
+    proptest! {
+        #[test]
+        fn test_to_markdown_round_trip(blocks in prop::collection::vec(markdown_block(), 0..8)) {
+            let input = blocks.join("\n\n");
+            let nodes = from_str(&input);
+
+            prop_assert_eq!(
+                without_ranges(from_str(&to_markdown(&nodes, &input))),
+                without_ranges(nodes)
+            );
+        }
+
+        #[test]
+        fn test_to_markdown_regenerated_round_trip(
+            blocks in prop::collection::vec(markdown_block(), 0..8)
+        ) {
+            let input = blocks.join("\n\n");
+            let nodes = from_str(&input);
+            let markdown = to_markdown(&nodes, "");
+
+            // Inline styles are lost without the source, but the code block languages are kept
+            prop_assert_eq!(code_block_langs(&from_str(&markdown)), code_block_langs(&nodes));
+            prop_assert_eq!(
+                without_ranges(from_str(&markdown)),
+                without_ranges(from_str(&to_markdown(&from_str(&markdown), "")))
+            );
+        }
+    }
 }
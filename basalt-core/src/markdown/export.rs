@@ -0,0 +1,531 @@
+//! Exports the Markdown AST into other formats, e.g. HTML and plain text.
+use crate::obsidian::WikiLink;
+
+use super::{BlockQuoteKind, ItemKind, MarkdownNode, Node, Style, Text, TextNode};
+
+/// Escapes the characters that have a special meaning in HTML.
+fn escape(text: &str) -> String {
+    text.chars().fold(String::new(), |mut escaped, c| {
+        match c {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            _ => escaped.push(c),
+        }
+        escaped
+    })
+}
+
+/// Returns the text shown for the wiki link, which is the alias if there is one.
+fn wiki_link_label(link: &WikiLink) -> &str {
+    link.alias.as_deref().unwrap_or(&link.target)
+}
+
+/// Converts the heading text to the `id` of the exported heading, e.g. `Next steps` to
+/// `next-steps`. Whitespace becomes `-`, and punctuation other than `-` and `_` is left out.
+fn slug(text: &str) -> String {
+    text.trim()
+        .to_lowercase()
+        .chars()
+        .filter_map(|c| match c {
+            c if c.is_whitespace() => Some('-'),
+            c if c.is_alphanumeric() || c == '-' || c == '_' => Some(c),
+            _ => None,
+        })
+        .collect()
+}
+
+/// Converts the wiki link to an anchor pointing to the exported HTML file of the linked note, and
+/// to the heading of the note if the link has one.
+fn wiki_link_anchor(link: &WikiLink) -> String {
+    let file = format!("{}.html", link.target).replace(' ', "%20");
+    let href = match &link.heading {
+        Some(heading) => format!("{file}#{}", slug(heading)),
+        None => file,
+    };
+    format!(
+        "<a class=\"wiki-link\" href=\"{}\">{}</a>",
+        escape(&href),
+        escape(wiki_link_label(link))
+    )
+}
+
+fn html_text_node(node: &TextNode) -> String {
+    match node.style {
+        Some(Style::Code) => format!("<code>{}</code>", escape(&node.content)),
+        Some(Style::Emphasis | Style::Italic) => {
+            format!("<em>{}</em>", html_inline(&node.content))
+        }
+        Some(Style::Strikethrough) => format!("<del>{}</del>", html_inline(&node.content)),
+        Some(Style::Strong | Style::Bold) => {
+            format!("<strong>{}</strong>", html_inline(&node.content))
+        }
+        Some(Style::Link { ref url }) => {
+            format!("<a href=\"{}\">{}</a>", escape(url), escape(&node.content))
+        }
+        None => html_inline(&node.content),
+    }
+}
+
+/// Escapes the text and turns its wiki links into anchors.
+fn html_inline(content: &str) -> String {
+    let (mut html, end) = WikiLink::find_all(content).into_iter().fold(
+        (String::new(), 0),
+        |(mut html, end), (range, link)| {
+            html.push_str(&escape(&content[end..range.start]));
+            html.push_str(&wiki_link_anchor(&link));
+            (html, range.end)
+        },
+    );
+
+    html.push_str(&escape(&content[end..]));
+    html
+}
+
+fn html_text(text: &Text) -> String {
+    text.iter().map(html_text_node).collect()
+}
+
+fn block_quote_kind(kind: &BlockQuoteKind) -> String {
+    escape(&kind.label().to_lowercase().replace(' ', "-"))
+}
+
+/// Returns the tag of the list that contains the item.
+fn list_tag(kind: &Option<ItemKind>) -> &'static str {
+    match kind {
+        Some(ItemKind::Ordered(..)) => "ol",
+        _ => "ul",
+    }
+}
+
+/// Converts the nodes to HTML. List items deeper than `base_depth` are nested into the list of
+/// the previous item.
+fn html_nodes(nodes: &[Node], base_depth: usize) -> String {
+    let mut html = String::new();
+    // The tags of the open lists from the outermost to the innermost, each with an open item
+    let mut lists: Vec<&str> = vec![];
+
+    let close_lists = |html: &mut String, lists: &mut Vec<&str>, level: usize| {
+        while lists.len() > level {
+            if let Some(tag) = lists.pop() {
+                html.push_str(&format!("</li></{tag}>\n"));
+            }
+        }
+    };
+
+    nodes.iter().for_each(|node| match &node.markdown_node {
+        MarkdownNode::Item { kind, text } => {
+            let level = node.depth.saturating_sub(base_depth).max(1);
+            let tag = list_tag(kind);
+
+            close_lists(&mut html, &mut lists, level);
+
+            if lists.len() == level {
+                match lists.last() {
+                    Some(last) if *last == tag => html.push_str("</li>\n"),
+                    // An item of another kind of list starts a new list
+                    _ => close_lists(&mut html, &mut lists, level - 1),
+                }
+            }
+
+            while lists.len() < level {
+                match kind {
+                    Some(ItemKind::Ordered(start)) if *start != 1 => {
+                        html.push_str(&format!("<ol start=\"{start}\">\n"))
+                    }
+                    _ => html.push_str(&format!("<{tag}>\n")),
+                }
+                lists.push(tag);
+            }
+
+            let checkbox = match kind {
+                Some(ItemKind::HardChecked | ItemKind::Checked) => {
+                    "<input type=\"checkbox\" checked disabled> "
+                }
+                Some(ItemKind::Unchecked) => "<input type=\"checkbox\" disabled> ",
+                _ => "",
+            };
+
+            html.push_str(&format!("<li>{checkbox}{}", html_text(text)));
+        }
+        markdown_node => {
+            close_lists(&mut html, &mut lists, 0);
+
+            match markdown_node {
+                MarkdownNode::Heading { level, text } => {
+                    let level = level.clone() as u8;
+                    let content: String = text.iter().map(|node| node.content.as_str()).collect();
+                    html.push_str(&format!(
+                        "<h{level} id=\"{}\">{}</h{level}>\n",
+                        escape(&slug(&content)),
+                        html_text(text)
+                    ));
+                }
+                MarkdownNode::Paragraph { text } => {
+                    html.push_str(&format!("<p>{}</p>\n", html_text(text)))
+                }
+                MarkdownNode::CodeBlock { lang, text } => {
+                    let class = lang
+                        .as_ref()
+                        .map(|lang| format!(" class=\"language-{}\"", escape(lang)))
+                        .unwrap_or_default();
+                    let code: String = text.iter().map(|node| escape(&node.content)).collect();
+                    html.push_str(&format!("<pre><code{class}>{code}</code></pre>\n"));
+                }
+                MarkdownNode::BlockQuote {
+                    kind: Some(kind),
+                    nodes,
+                } => {
+                    html.push_str(&format!(
+                        "<div class=\"callout callout-{}\">\n<p class=\"callout-title\">{}</p>\n{}</div>\n",
+                        block_quote_kind(kind),
+                        escape(kind.label()),
+                        html_nodes(nodes, node.depth + 1)
+                    ));
+                }
+                MarkdownNode::BlockQuote { kind: None, nodes } => {
+                    html.push_str(&format!(
+                        "<blockquote>\n{}</blockquote>\n",
+                        html_nodes(nodes, node.depth + 1)
+                    ));
+                }
+                MarkdownNode::Item { .. } => {}
+            }
+        }
+    });
+
+    close_lists(&mut html, &mut lists, 0);
+
+    html
+}
+
+/// Converts the Markdown nodes to an HTML fragment.
+///
+/// Consecutive list items are grouped into `<ul>` and `<ol>` lists, task items are rendered with
+/// a disabled checkbox, callouts become `<div>`s with a `callout-<kind>` class and wiki links
+/// become anchors to the exported HTML files of the linked notes.
+///
+/// # Examples
+///
+/// ```
+/// use basalt_core::markdown::{from_str, to_html};
+///
+/// assert_eq!(
+///     to_html(&from_str("- [x] Read [[Notes|the notes]]")),
+///     "<ul>\n<li><input type=\"checkbox\" checked disabled> Read <a class=\"wiki-link\" href=\"Notes.html\">the notes</a></li></ul>\n"
+/// );
+/// ```
+pub fn to_html(nodes: &[Node]) -> String {
+    html_nodes(nodes, 0)
+}
+
+/// Wraps the HTML fragment, e.g. from [`to_html`], into a standalone HTML document with the given
+/// title.
+pub fn to_html_document(title: &str, body: &str) -> String {
+    format!(
+        "<!DOCTYPE html>\n<html>\n<head>\n<meta charset=\"utf-8\">\n<title>{}</title>\n</head>\n<body>\n{body}</body>\n</html>\n",
+        escape(title)
+    )
+}
+
+fn plain_text(text: &Text) -> String {
+    text.iter()
+        .map(|node| match node.style {
+            Some(Style::Code) => node.content.clone(),
+            _ => WikiLink::find_all(&node.content).into_iter().rev().fold(
+                node.content.clone(),
+                |mut content, (range, link)| {
+                    content.replace_range(range, wiki_link_label(&link));
+                    content
+                },
+            ),
+        })
+        .collect()
+}
+
+/// Converts the Markdown nodes to plain text without any markup.
+///
+/// Blocks are separated by an empty line, except for consecutive list items, which are written on
+/// their own lines. List items are prefixed with `- `, or with their number in ordered lists, and
+/// nested items are indented with two spaces. Wiki links are replaced by their alias or target.
+///
+/// # Examples
+///
+/// ```
+/// use basalt_core::markdown::{from_str, to_plain_text};
+///
+/// assert_eq!(
+///     to_plain_text(&from_str("# **Heading**\n\n- [ ] See [[Notes]]\n- *Item*")),
+///     "Heading\n\n- See Notes\n- Item"
+/// );
+/// ```
+pub fn to_plain_text(nodes: &[Node]) -> String {
+    let mut blocks: Vec<String> = vec![];
+    let mut previous: Option<&Node> = None;
+
+    nodes.iter().for_each(|node| {
+        let text = match &node.markdown_node {
+            MarkdownNode::Heading { text, .. } | MarkdownNode::Paragraph { text } => {
+                plain_text(text)
+            }
+            MarkdownNode::Item { kind, text } => {
+                let prefix = match kind {
+                    Some(ItemKind::Ordered(number)) => format!("{number}. "),
+                    _ => "- ".to_string(),
+                };
+                format!(
+                    "{}{prefix}{}",
+                    "  ".repeat(node.depth.saturating_sub(1)),
+                    plain_text(text)
+                )
+            }
+            MarkdownNode::CodeBlock { text, .. } => text
+                .iter()
+                .map(|node| node.content.as_str())
+                .collect::<String>(),
+            MarkdownNode::BlockQuote { nodes, .. } => to_plain_text(nodes),
+        };
+        let text = text.trim_end();
+
+        // Nested block quotes without text of their own leave an empty block
+        if text.is_empty() {
+            return;
+        }
+
+        match blocks.last_mut() {
+            Some(block) if node.is_list() && previous.is_some_and(Node::is_list) => {
+                block.push('\n');
+                block.push_str(text);
+            }
+            _ => blocks.push(text.to_string()),
+        }
+
+        previous = Some(node);
+    });
+
+    blocks.join("\n\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use std::fs;
+
+    use indoc::indoc;
+
+    use crate::markdown::from_str;
+
+    use super::*;
+
+    #[test]
+    fn test_to_html() {
+        let content = indoc! { r#"
+            # Heading 1
+
+            A paragraph with `code` and 1 < 2 & 3, linking to [[Other note#Heading|another note]].
+
+            ## Lists
+
+            - Item
+              - Nested item
+            - Another item
+
+            * [ ] Unchecked task
+            * [x] Checked task
+
+            > A block quote
+
+            > [!tip]
+            > A tip callout
+
+            ```
+            fn main() { println!("<[[not a link]]>"); }
+            ```
+            "#};
+
+        assert_eq!(
+            to_html(&from_str(content)),
+            indoc! { r#"
+                <h1 id="heading-1">Heading 1</h1>
+                <p>A paragraph with <code>code</code> and 1 &lt; 2 &amp; 3, linking to <a class="wiki-link" href="Other%20note.html#heading">another note</a>.</p>
+                <h2 id="lists">Lists</h2>
+                <ul>
+                <li>Item<ul>
+                <li>Nested item</li></ul>
+                </li>
+                <li>Another item</li>
+                <li><input type="checkbox" disabled> Unchecked task</li>
+                <li><input type="checkbox" checked disabled> Checked task</li></ul>
+                <blockquote>
+                <p>A block quote</p>
+                </blockquote>
+                <div class="callout callout-tip">
+                <p class="callout-title">TIP</p>
+                <p>A tip callout</p>
+                </div>
+                <pre><code>fn main() { println!(&quot;&lt;[[not a link]]&gt;&quot;); }
+                </code></pre>
+                "#}
+        );
+    }
+
+    #[test]
+    fn test_to_html_styles() {
+        let item = |kind, content: &str| {
+            Node::new(
+                MarkdownNode::Item {
+                    kind: Some(kind),
+                    text: content.into(),
+                },
+                0..0,
+            )
+            .with_depth(1)
+        };
+
+        let nodes = [
+            Node::new(
+                MarkdownNode::Paragraph {
+                    text: Text::from(vec![
+                        TextNode::new("strong".into(), Some(Style::Strong)),
+                        TextNode::from(" "),
+                        TextNode::new("emphasis".into(), Some(Style::Emphasis)),
+                        TextNode::from(" "),
+                        TextNode::new("deleted".into(), Some(Style::Strikethrough)),
+                        TextNode::from(" "),
+                        TextNode::new("[[Code]]".into(), Some(Style::Code)),
+                    ]),
+                },
+                0..0,
+            ),
+            Node::new(
+                MarkdownNode::Paragraph {
+                    text: "[[Plans#Next steps!]]".into(),
+                },
+                0..0,
+            ),
+            item(ItemKind::Ordered(3), "Third"),
+            item(ItemKind::Ordered(4), "Fourth"),
+            item(ItemKind::Unordered, "Item"),
+            Node::new(
+                MarkdownNode::CodeBlock {
+                    lang: Some("rust".into()),
+                    text: "fn main() {}\n".into(),
+                },
+                0..0,
+            ),
+        ];
+
+        assert_eq!(
+            to_html(&nodes),
+            indoc! { r#"
+                <p><strong>strong</strong> <em>emphasis</em> <del>deleted</del> <code>[[Code]]</code></p>
+                <p><a class="wiki-link" href="Plans.html#next-steps">Plans</a></p>
+                <ol start="3">
+                <li>Third</li>
+                <li>Fourth</li></ol>
+                <ul>
+                <li>Item</li></ul>
+                <pre><code class="language-rust">fn main() {}
+                </code></pre>
+                "#}
+        );
+    }
+
+    #[test]
+    fn test_to_html_document() {
+        assert_eq!(
+            to_html_document("Notes & ideas", "<p>Text</p>\n"),
+            indoc! { r#"
+                <!DOCTYPE html>
+                <html>
+                <head>
+                <meta charset="utf-8">
+                <title>Notes &amp; ideas</title>
+                </head>
+                <body>
+                <p>Text</p>
+                </body>
+                </html>
+                "#}
+        );
+    }
+
+    #[test]
+    fn test_to_plain_text() {
+        let content = indoc! { r#"
+            # Heading with **bold**
+
+            A paragraph with `code` and [[Other note|another note]].
+
+            - Item
+              - Nested [[item]]
+            - [x] Task
+
+            > [!note]
+            > A *callout*
+
+            ```rust
+            fn main() {}
+            ```
+            "#};
+
+        assert_eq!(
+            to_plain_text(&from_str(content)),
+            indoc! { r#"
+                Heading with bold
+
+                A paragraph with code and another note.
+
+                - Item
+                  - Nested item
+                - Task
+
+                A callout
+
+                fn main() {}"#}
+        );
+
+        let tests = [
+            ("", ""),
+            (
+                "- First\n- [ ] Task\n  - Nested\n    - Deeper",
+                "- First\n- Task\n  - Nested\n    - Deeper",
+            ),
+            (
+                "> [!tip]\n> Quoted text\n>\n> > Deep quote",
+                "Quoted text\n\nDeep quote",
+            ),
+        ];
+
+        tests.into_iter().for_each(|(content, expected)| {
+            assert_eq!(to_plain_text(&from_str(content)), expected, "{content:?}")
+        });
+    }
+
+    /// Compares the exported notes of the documentation vault to their snapshots. Symlinked notes
+    /// mirror the README and contributing guide, which change with every configuration change.
+    #[test]
+    fn test_export_docs() {
+        let docs = concat!(env!("CARGO_MANIFEST_DIR"), "/../docs");
+        let mut paths: Vec<_> = fs::read_dir(docs)
+            .unwrap()
+            .map(|entry| entry.unwrap().path())
+            .filter(|path| {
+                path.extension().is_some_and(|extension| extension == "md") && !path.is_symlink()
+            })
+            .collect();
+        paths.sort();
+
+        paths.iter().for_each(|path| {
+            let name = path
+                .file_stem()
+                .unwrap()
+                .to_string_lossy()
+                .replace(|c: char| !c.is_alphanumeric(), "_");
+            let nodes = from_str(&fs::read_to_string(path).unwrap());
+
+            insta::assert_snapshot!(format!("{name}.html"), to_html(&nodes));
+            insta::assert_snapshot!(format!("{name}.txt"), to_plain_text(&nodes));
+        });
+    }
+}
@@ -15,18 +15,33 @@
 //!
 //! _ = config.get_vault_by_name("Obsidian");
 //! ```
-use std::{io, path::PathBuf, result};
+use std::{
+    io,
+    path::{Path, PathBuf},
+    result,
+};
 
+mod bookmark;
 mod config;
+mod file_kind;
 mod note;
+pub mod uri;
 mod vault;
 mod vault_entry;
+mod wiki_link;
 
+pub use bookmark::Bookmark;
 pub use config::ObsidianConfig;
-pub use note::Note;
+pub use file_kind::FileKind;
+pub use note::{Note, DEFAULT_MAX_NOTE_BYTES};
+pub use uri::ObsidianUri;
 pub use vault::Vault;
 pub use vault_entry::FindNote;
+pub use vault_entry::ReadOptions;
+pub use vault_entry::SortEntry;
+pub use vault_entry::SortOrder;
 pub use vault_entry::VaultEntry;
+pub use wiki_link::WikiLink;
 
 /// A [`std::result::Result`] type for fallible operations in [`crate::obsidian`].
 ///
@@ -62,7 +77,124 @@ pub enum Error {
     #[error("JSON (de)serialization error: {0}")]
     Json(#[from] serde_json::Error),
 
-    /// I/O error, from [`std::io::Error`].
+    /// A file already exists at the path that was about to be created. The path is empty if the
+    /// error was converted from an [`io::Error`] without a path.
+    #[error("File already exists{}", path_detail(.0))]
+    AlreadyExists(PathBuf),
+
+    /// Missing permissions to read or write the path. The path is empty if the error was
+    /// converted from an [`io::Error`] without a path.
+    #[error("Permission denied{}", path_detail(.0))]
+    PermissionDenied(PathBuf),
+
+    /// The operation would break the structure of the vault, e.g. by creating a note outside of
+    /// the vault directory.
+    #[error("Invalid vault structure: {0}")]
+    InvalidVaultStructure(String),
+
+    /// The file is larger than the size limit for reading it in full, see
+    /// [`Note::read_to_string_checked`].
+    #[error("File is too large: {path} ({size} bytes, the limit is {max_bytes} bytes)")]
+    TooLarge {
+        /// Path of the file.
+        path: PathBuf,
+        /// Size of the file in bytes.
+        size: u64,
+        /// The size limit in bytes.
+        max_bytes: u64,
+    },
+
+    /// The content of the file is not valid UTF-8, e.g. because the file is binary.
+    #[error("File is not valid UTF-8 text: {0}")]
+    NotUtf8(PathBuf),
+
+    /// I/O error, from [`std::io::Error`] of a kind that has no variant of its own, see
+    /// [`Error::from`].
     #[error("I/O error: {0}")]
-    Io(#[from] io::Error),
+    Io(io::Error),
+
+    /// Malformed Obsidian URI, from [`uri::UriError`].
+    #[error(transparent)]
+    Uri(#[from] uri::UriError),
+}
+
+/// Formats the path of an error message, or nothing if the path is empty.
+fn path_detail(path: &Path) -> String {
+    match path.as_os_str().is_empty() {
+        true => String::new(),
+        false => format!(": {}", path.display()),
+    }
+}
+
+impl From<io::Error> for Error {
+    /// Converts the I/O error into the variant that matches the [`io::ErrorKind`]. The path of the
+    /// operation is not known, so the variants are left without one. Other kinds of errors are
+    /// kept as [`Error::Io`].
+    fn from(error: io::Error) -> Self {
+        match error.kind() {
+            io::ErrorKind::AlreadyExists => Error::AlreadyExists(PathBuf::new()),
+            io::ErrorKind::PermissionDenied => Error::PermissionDenied(PathBuf::new()),
+            _ => Error::Io(error),
+        }
+    }
+}
+
+impl Error {
+    /// Converts an I/O error of an operation on the path into the variant that matches the
+    /// [`io::ErrorKind`], so that the path is part of the error message. Other kinds of errors are
+    /// kept as [`Error::Io`].
+    pub(crate) fn from_io(error: io::Error, path: &Path) -> Self {
+        match error.kind() {
+            io::ErrorKind::NotFound => Error::PathNotFound(path.display().to_string()),
+            io::ErrorKind::AlreadyExists => Error::AlreadyExists(path.to_path_buf()),
+            io::ErrorKind::PermissionDenied => Error::PermissionDenied(path.to_path_buf()),
+            _ => Error::Io(error),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_from_io() {
+        let path = Path::new("vault/Note.md");
+
+        let tests = [
+            (io::ErrorKind::NotFound, "Path not found: vault/Note.md"),
+            (
+                io::ErrorKind::AlreadyExists,
+                "File already exists: vault/Note.md",
+            ),
+            (
+                io::ErrorKind::PermissionDenied,
+                "Permission denied: vault/Note.md",
+            ),
+            (
+                io::ErrorKind::Interrupted,
+                "I/O error: operation interrupted",
+            ),
+        ];
+
+        tests.into_iter().for_each(|(kind, expected)| {
+            assert_eq!(
+                Error::from_io(io::Error::from(kind), path).to_string(),
+                expected
+            )
+        });
+    }
+
+    #[test]
+    fn test_from_io_error() {
+        let tests = [
+            (io::ErrorKind::NotFound, "I/O error: entity not found"),
+            (io::ErrorKind::AlreadyExists, "File already exists"),
+            (io::ErrorKind::PermissionDenied, "Permission denied"),
+        ];
+
+        tests.into_iter().for_each(|(kind, expected)| {
+            assert_eq!(Error::from(io::Error::from(kind)).to_string(), expected)
+        });
+    }
 }
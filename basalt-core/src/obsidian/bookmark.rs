@@ -0,0 +1,132 @@
+use std::path::{Path, PathBuf};
+
+use serde_json::{json, Value};
+
+/// The file where Obsidian stores bookmarks, relative to the `.obsidian` directory of a vault.
+pub(crate) const BOOKMARKS_FILE: &str = "bookmarks.json";
+
+/// The file where older Obsidian versions stored starred notes, relative to the `.obsidian`
+/// directory of a vault. Uses the same format as [`BOOKMARKS_FILE`], but without groups.
+pub(crate) const STARRED_FILE: &str = "starred.json";
+
+/// A bookmarked file or folder in a vault.
+///
+/// Bookmarks of other kinds, like searches, have no path and are not listed.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Bookmark {
+    /// The title of the bookmark, or the file name without extension when the bookmark has no
+    /// title.
+    pub title: String,
+
+    /// Path of the bookmarked file or folder, relative to the vault directory.
+    pub path: PathBuf,
+
+    /// Titles of the groups containing the bookmark, the outermost group first.
+    pub group_path: Vec<String>,
+}
+
+/// Converts the path to the vault-relative format used by Obsidian, which always uses `/` as
+/// the separator.
+fn json_path(path: &Path) -> String {
+    path.components()
+        .map(|component| component.as_os_str().to_string_lossy())
+        .collect::<Vec<_>>()
+        .join("/")
+}
+
+fn flatten(items: &[Value], group_path: &[String]) -> Vec<Bookmark> {
+    items
+        .iter()
+        .flat_map(|item| {
+            let title = item.get("title").and_then(Value::as_str);
+
+            match item.get("type").and_then(Value::as_str) {
+                Some("group") => {
+                    let group_path =
+                        [group_path, &[title.unwrap_or_default().to_string()]].concat();
+                    let items = item.get("items").and_then(Value::as_array);
+                    items
+                        .map(|items| flatten(items, &group_path))
+                        .unwrap_or_default()
+                }
+                Some("file" | "folder") => item
+                    .get("path")
+                    .and_then(Value::as_str)
+                    .map(|path| {
+                        let path = PathBuf::from(path);
+                        let title = title.map(String::from).unwrap_or_else(|| {
+                            path.file_stem()
+                                .map(|stem| stem.to_string_lossy().to_string())
+                                .unwrap_or_default()
+                        });
+
+                        Bookmark {
+                            title,
+                            path,
+                            group_path: group_path.to_vec(),
+                        }
+                    })
+                    .into_iter()
+                    .collect(),
+                _ => vec![],
+            }
+        })
+        .collect()
+}
+
+/// Returns the bookmarks of all groups as a flat list. Items that do not match the format are
+/// skipped.
+pub(crate) fn from_json(json: &Value) -> Vec<Bookmark> {
+    json.get("items")
+        .and_then(Value::as_array)
+        .map(|items| flatten(items, &[]))
+        .unwrap_or_default()
+}
+
+/// Returns the top-level items of the bookmarks file, creating an empty list if there is none.
+///
+/// Returns [`None`] if the JSON is not in the bookmarks format.
+fn items_mut(json: &mut Value) -> Option<&mut Vec<Value>> {
+    json.as_object_mut()?
+        .entry("items")
+        .or_insert_with(|| json!([]))
+        .as_array_mut()
+}
+
+/// Adds a file bookmark to the top level. The item is formatted the way Obsidian writes it, with
+/// the creation time in milliseconds.
+///
+/// Returns [`None`] if the JSON is not in the bookmarks format.
+pub(crate) fn insert(json: &mut Value, path: &Path, ctime: u128) -> Option<()> {
+    items_mut(json)?.push(json!({
+        "type": "file",
+        "ctime": ctime,
+        "path": json_path(path),
+    }));
+
+    Some(())
+}
+
+/// Removes the file bookmarks of the path from all groups and returns the number of removed
+/// bookmarks. Other items, including their unknown fields, are kept as they are.
+pub(crate) fn remove(json: &mut Value, path: &Path) -> usize {
+    fn remove_items(items: &mut Vec<Value>, path: &str) -> usize {
+        let count = items.len();
+        items.retain(|item| {
+            item.get("type").and_then(Value::as_str) != Some("file")
+                || item.get("path").and_then(Value::as_str) != Some(path)
+        });
+
+        count - items.len()
+            + items
+                .iter_mut()
+                .filter_map(|item| item.get_mut("items").and_then(Value::as_array_mut))
+                .map(|items| remove_items(items, path))
+                .sum::<usize>()
+    }
+
+    match json.get_mut("items").and_then(Value::as_array_mut) {
+        Some(items) => remove_items(items, &json_path(path)),
+        None => 0,
+    }
+}
@@ -1,11 +1,11 @@
 use dirs::{config_dir, home_dir};
 
 use serde::{Deserialize, Deserializer};
-use std::path::Path;
+use std::path::{Component, Path};
+use std::{cmp::Reverse, env, result};
 use std::{collections::BTreeMap, fs, path::PathBuf};
-use std::{env, result};
 
-use crate::obsidian::{Error, Result, Vault};
+use crate::obsidian::{Error, ObsidianUri, Result, Vault};
 
 /// Represents the Obsidian configuration, typically loaded from an `obsidian.json` file.
 #[derive(Debug, Clone, Default, PartialEq)]
@@ -88,6 +88,40 @@ impl ObsidianConfig {
         self.vaults.values().collect()
     }
 
+    /// Returns the vaults ordered by when they were last opened, most recent first. Vaults with
+    /// the same timestamp, e.g. vaults without one, stay in name order.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use basalt_core::obsidian::{ObsidianConfig, Vault};
+    ///
+    /// let vault = |name: &str, ts: u64| Vault {
+    ///     name: name.into(),
+    ///     ts,
+    ///     ..Vault::default()
+    /// };
+    ///
+    /// let config = ObsidianConfig::from([
+    ///     ("Archive", vault("Archive", 1600000000000)),
+    ///     ("Obsidian", vault("Obsidian", 0)),
+    ///     ("Work", vault("Work", 1710000000000)),
+    /// ]);
+    ///
+    /// let names: Vec<_> = config
+    ///     .vaults_by_recent()
+    ///     .into_iter()
+    ///     .map(|vault| vault.name.as_str())
+    ///     .collect();
+    ///
+    /// assert_eq!(names, ["Work", "Archive", "Obsidian"]);
+    /// ```
+    pub fn vaults_by_recent(&self) -> Vec<&Vault> {
+        let mut vaults = self.vaults();
+        vaults.sort_by_key(|vault| Reverse(vault.ts));
+        vaults
+    }
+
     /// Finds a vault by name, returning a reference if it exists.
     ///
     /// # Examples
@@ -106,6 +140,64 @@ impl ObsidianConfig {
         self.vaults.get(name)
     }
 
+    /// Resolves the vault and the path of the file an [`ObsidianUri`] points to.
+    ///
+    /// The `.md` extension is added to files without an extension, as Obsidian omits it. When
+    /// the URI only names a vault, the path is the vault directory. Returns [`None`] if the vault
+    /// does not exist, the file is empty, or the path is outside of the vault, e.g. through `..`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::path::PathBuf;
+    /// use basalt_core::obsidian::{ObsidianConfig, ObsidianUri, Vault};
+    ///
+    /// let config = ObsidianConfig::from([(
+    ///     "Work",
+    ///     Vault {
+    ///         name: "Work".into(),
+    ///         path: "/home/user/Work".into(),
+    ///         ..Vault::default()
+    ///     },
+    /// )]);
+    ///
+    /// let uri: ObsidianUri = "obsidian://open?vault=Work&file=Daily%2FToday".parse().unwrap();
+    /// let (vault, path) = config.resolve_uri(&uri).unwrap();
+    ///
+    /// assert_eq!(vault.name, "Work");
+    /// assert_eq!(path, PathBuf::from("/home/user/Work/Daily/Today.md"));
+    /// ```
+    pub fn resolve_uri(&self, uri: &ObsidianUri) -> Option<(Vault, PathBuf)> {
+        match uri {
+            ObsidianUri::Open { vault, file } => {
+                let vault = self.get_vault_by_name(vault)?;
+
+                let Some(file) = file else {
+                    return Some((vault.clone(), vault.path.clone()));
+                };
+
+                let file = Path::new(file);
+                if file.as_os_str().is_empty() || !is_relative_within(file) {
+                    return None;
+                }
+
+                let path = vault.path.join(file);
+                let path = match path.extension() {
+                    Some(_) => path,
+                    None => path.with_extension("md"),
+                };
+
+                Some((vault.clone(), path))
+            }
+            ObsidianUri::OpenPath { path } => self
+                .vaults
+                .values()
+                .filter(|vault| path.strip_prefix(&vault.path).is_ok_and(is_relative_within))
+                .max_by_key(|vault| vault.path.components().count())
+                .map(|vault| (vault.clone(), path.clone())),
+        }
+    }
+
     /// Gets the currently opened vault marked by Obsidian.
     ///
     /// # Examples
@@ -129,6 +221,46 @@ impl ObsidianConfig {
     pub fn get_open_vault(&self) -> Option<&Vault> {
         self.vaults.values().find(|vault| vault.open)
     }
+
+    /// Gets the vault that was opened most recently, i.e. the vault with the latest timestamp.
+    /// Falls back to the vault marked open by Obsidian if no vault has a timestamp.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use basalt_core::obsidian::{ObsidianConfig, Vault};
+    ///
+    /// let config = ObsidianConfig::from([
+    ///     (
+    ///         "Obsidian",
+    ///         Vault {
+    ///             name: "Obsidian".into(),
+    ///             ts: 1700000000000,
+    ///             ..Vault::default()
+    ///         },
+    ///     ),
+    ///     (
+    ///         "Work",
+    ///         Vault {
+    ///             name: "Work".into(),
+    ///             ts: 1710000000000,
+    ///             ..Vault::default()
+    ///         },
+    ///     ),
+    /// ]);
+    ///
+    /// assert_eq!(
+    ///     config.most_recently_opened().map(|vault| vault.name.as_str()),
+    ///     Some("Work")
+    /// );
+    /// ```
+    pub fn most_recently_opened(&self) -> Option<&Vault> {
+        self.vaults
+            .values()
+            .filter(|vault| vault.ts > 0)
+            .max_by_key(|vault| vault.ts)
+            .or_else(|| self.get_open_vault())
+    }
 }
 
 impl<const N: usize> From<[(&str, Vault); N]> for ObsidianConfig {
@@ -282,3 +414,114 @@ pub fn obsidian_global_config_locations() -> Vec<PathBuf> {
         .flatten()
         .collect()
 }
+
+/// Returns `true` if the relative path stays within the directory it is joined to, i.e. it only
+/// has normal components and no `..`.
+fn is_relative_within(path: &Path) -> bool {
+    path.components()
+        .all(|component| matches!(component, Component::Normal(_)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_most_recently_opened() {
+        let vault = |name: &str, ts: u64, open: bool| Vault {
+            name: name.into(),
+            ts,
+            open,
+            ..Vault::default()
+        };
+
+        let config = ObsidianConfig::from([
+            ("Archive", vault("Archive", 1600000000000, false)),
+            ("Obsidian", vault("Obsidian", 1700000000000, true)),
+            ("Work", vault("Work", 1710000000000, false)),
+        ]);
+        assert_eq!(
+            config
+                .most_recently_opened()
+                .map(|vault| vault.name.as_str()),
+            Some("Work")
+        );
+
+        // Without timestamps the open vault is used
+        let config = ObsidianConfig::from([
+            ("Obsidian", vault("Obsidian", 0, false)),
+            ("Work", vault("Work", 0, true)),
+        ]);
+        assert_eq!(
+            config
+                .most_recently_opened()
+                .map(|vault| vault.name.as_str()),
+            Some("Work")
+        );
+
+        assert_eq!(ObsidianConfig::default().most_recently_opened(), None);
+    }
+
+    #[test]
+    fn test_resolve_uri() {
+        let vault = |name: &str, path: &str| Vault {
+            name: name.into(),
+            path: path.into(),
+            ..Vault::default()
+        };
+
+        let config = ObsidianConfig::from([
+            ("Notes", vault("Notes", "/home/user/Notes")),
+            ("Archive", vault("Archive", "/home/user/Notes/Archive")),
+            (
+                "My Vault ✨",
+                vault("My Vault ✨", "/home/user/My Vault ✨"),
+            ),
+        ]);
+
+        let resolve = |uri: &str| {
+            config
+                .resolve_uri(&uri.parse().unwrap())
+                .map(|(vault, path)| (vault.name, path))
+        };
+
+        let tests = [
+            (
+                "obsidian://open?vault=Notes",
+                Some(("Notes", "/home/user/Notes")),
+            ),
+            (
+                "obsidian://open?vault=My%20Vault%20%E2%9C%A8&file=Ideas",
+                Some(("My Vault ✨", "/home/user/My Vault ✨/Ideas.md")),
+            ),
+            (
+                "obsidian://open?vault=Notes&file=image.png",
+                Some(("Notes", "/home/user/Notes/image.png")),
+            ),
+            (
+                "obsidian://open?path=%2Fhome%2Fuser%2FNotes%2FArchive%2FOld.md",
+                Some(("Archive", "/home/user/Notes/Archive/Old.md")),
+            ),
+            ("obsidian://open?vault=Notes&file=..%2Fsecret", None),
+            ("obsidian://open?vault=Notes&file=", None),
+            (
+                "obsidian://open?path=%2Fhome%2Fuser%2FNotes%2F..%2Fsecret.md",
+                None,
+            ),
+            (
+                "obsidian://open?path=%2Fhome%2Fuser%2FNotes%2FArchive%2F..%2F..%2Fsecret.md",
+                None,
+            ),
+            ("obsidian://open?vault=Missing", None),
+            ("obsidian://open?path=%2Ftmp%2FNote.md", None),
+        ];
+
+        tests.into_iter().for_each(|(uri, expected)| {
+            assert_eq!(
+                resolve(uri),
+                expected.map(|(name, path)| (name.to_string(), PathBuf::from(path))),
+                "{uri}"
+            )
+        });
+    }
+}
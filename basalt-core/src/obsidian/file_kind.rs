@@ -0,0 +1,68 @@
+use std::{fmt, path::Path};
+
+/// The kind of a file in a vault, determined by its extension.
+///
+/// Only markdown files are notes, other files are attachments, like images embedded in notes.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum FileKind {
+    /// A markdown note, `.md`.
+    #[default]
+    Markdown,
+    /// An image, e.g. `.png` or `.jpg`.
+    Image,
+    /// A PDF document, `.pdf`.
+    Pdf,
+    /// An Obsidian canvas, `.canvas`.
+    Canvas,
+    /// Any other file.
+    Other,
+}
+
+impl FileKind {
+    /// Returns the kind of the file at the given path based on its extension. The extension is
+    /// matched case-insensitively.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::path::Path;
+    /// use basalt_core::obsidian::FileKind;
+    ///
+    /// assert_eq!(FileKind::from_path(Path::new("Notes/Idea.md")), FileKind::Markdown);
+    /// assert_eq!(FileKind::from_path(Path::new("Photo.JPG")), FileKind::Image);
+    /// assert_eq!(FileKind::from_path(Path::new("Board.canvas")), FileKind::Canvas);
+    /// assert_eq!(FileKind::from_path(Path::new("archive.zip")), FileKind::Other);
+    /// ```
+    pub fn from_path(path: &Path) -> Self {
+        let extension = path
+            .extension()
+            .map(|extension| extension.to_string_lossy().to_lowercase());
+
+        match extension.as_deref() {
+            Some("md") => Self::Markdown,
+            Some("png" | "jpg" | "jpeg" | "gif" | "bmp" | "svg" | "webp" | "avif") => Self::Image,
+            Some("pdf") => Self::Pdf,
+            Some("canvas") => Self::Canvas,
+            _ => Self::Other,
+        }
+    }
+
+    /// Returns `true` if the file is a markdown note.
+    pub fn is_markdown(self) -> bool {
+        self == Self::Markdown
+    }
+}
+
+impl fmt::Display for FileKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let label = match self {
+            Self::Markdown => "Markdown",
+            Self::Image => "Image",
+            Self::Pdf => "PDF",
+            Self::Canvas => "Canvas",
+            Self::Other => "File",
+        };
+
+        f.write_str(label)
+    }
+}
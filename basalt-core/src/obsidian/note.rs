@@ -1,18 +1,49 @@
-use std::{fs, path::PathBuf};
+use std::{
+    fs::{self, File},
+    io::{self, Read, Write},
+    path::PathBuf,
+};
 
-use crate::obsidian::{Error, Result};
+use crate::{
+    markdown,
+    obsidian::{Error, FileKind, Result},
+};
+
+/// The default size limit for reading a note in full, 5 MB, see
+/// [`Note::read_to_string_checked`].
+pub const DEFAULT_MAX_NOTE_BYTES: u64 = 5 * 1024 * 1024;
 
 /// Represents a single note (Markdown file) within a vault.
 #[derive(Debug, Clone, PartialEq, Default)]
 pub struct Note {
-    /// The base filename without `.md` extension.
+    /// The base filename without `.md` extension. Files that are not notes, like attachments, keep
+    /// their extension.
     pub name: String,
 
-    /// Filesystem path to the `.md` file.
+    /// Filesystem path to the file, usually a `.md` file.
     pub path: PathBuf,
 }
 
 impl Note {
+    /// Returns the kind of the file based on its extension. Only [`FileKind::Markdown`] files are
+    /// notes, other files are attachments.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use basalt_core::obsidian::{FileKind, Note};
+    ///
+    /// let note = Note {
+    ///     name: "diagram.png".to_string(),
+    ///     path: "path/to/diagram.png".into(),
+    /// };
+    ///
+    /// assert_eq!(note.kind(), FileKind::Image);
+    /// ```
+    pub fn kind(&self) -> FileKind {
+        FileKind::from_path(&self.path)
+    }
+
     /// Reads the note's contents from disk to a `String`.
     ///
     /// # Examples
@@ -28,11 +59,120 @@ impl Note {
     /// _ = Note::read_to_string(&note);
     /// ```
     pub fn read_to_string(note: &Note) -> Result<String> {
-        fs::read_to_string(&note.path).map_err(Error::Io)
+        fs::read_to_string(&note.path).map_err(|error| Error::from_io(error, &note.path))
+    }
+
+    /// Reads the note's contents from disk to a `String` if the file is at most `max_bytes` in
+    /// size. The size is checked before reading, so huge files are never read into memory.
+    ///
+    /// Returns [`Error::TooLarge`] if the file is larger than the limit, and [`Error::NotUtf8`] if
+    /// the content is not valid UTF-8, e.g. when a binary file was given a `.md` extension. Use
+    /// [`Note::read_preview`] to show the start of such files instead.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use basalt_core::obsidian::{Note, DEFAULT_MAX_NOTE_BYTES};
+    ///
+    /// let note = Note {
+    ///     name: "Example".to_string(),
+    ///     path: "path/to/Example.md".into(),
+    /// };
+    ///
+    /// _ = note.read_to_string_checked(DEFAULT_MAX_NOTE_BYTES);
+    /// ```
+    pub fn read_to_string_checked(&self, max_bytes: u64) -> Result<String> {
+        let size = fs::metadata(&self.path)
+            .map_err(|error| Error::from_io(error, &self.path))?
+            .len();
+
+        if size > max_bytes {
+            return Err(Error::TooLarge {
+                path: self.path.clone(),
+                size,
+                max_bytes,
+            });
+        }
+
+        let bytes = fs::read(&self.path).map_err(|error| Error::from_io(error, &self.path))?;
+        String::from_utf8(bytes).map_err(|_| Error::NotUtf8(self.path.clone()))
+    }
+
+    /// Reads at most the first `n_bytes` of the note from disk. Invalid UTF-8 is replaced with
+    /// `U+FFFD`, so that the start of any file can be shown, and a character that is cut off at
+    /// the end is left out.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use basalt_core::obsidian::Note;
+    ///
+    /// let note = Note {
+    ///     name: "Example".to_string(),
+    ///     path: "path/to/Example.md".into(),
+    /// };
+    ///
+    /// _ = note.read_preview(1024);
+    /// ```
+    pub fn read_preview(&self, n_bytes: u64) -> Result<String> {
+        let mut bytes = Vec::new();
+
+        File::open(&self.path)
+            .and_then(|file| file.take(n_bytes).read_to_end(&mut bytes))
+            .map_err(|error| Error::from_io(error, &self.path))?;
+
+        // A character cut off at the end is incomplete rather than invalid
+        if let Err(error) = std::str::from_utf8(&bytes) {
+            if error.error_len().is_none() {
+                bytes.truncate(error.valid_up_to());
+            }
+        }
+
+        Ok(String::from_utf8_lossy(&bytes).into_owned())
+    }
+
+    /// Reads the note from disk and returns its tags without the leading `#`, see
+    /// [`markdown::tags`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use basalt_core::obsidian::Note;
+    ///
+    /// let note = Note {
+    ///     name: "Example".to_string(),
+    ///     path: "path/to/Example.md".into(),
+    /// };
+    ///
+    /// _ = note.tags();
+    /// ```
+    pub fn tags(&self) -> Result<Vec<String>> {
+        Note::read_to_string(self).map(|content| markdown::tags(&content))
+    }
+
+    /// Reads the note from disk and returns the aliases in its frontmatter, see
+    /// [`markdown::aliases`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use basalt_core::obsidian::Note;
+    ///
+    /// let note = Note {
+    ///     name: "Example".to_string(),
+    ///     path: "path/to/Example.md".into(),
+    /// };
+    ///
+    /// assert!(note.aliases().is_err());
+    /// ```
+    pub fn aliases(&self) -> Result<Vec<String>> {
+        Note::read_to_string(self).map(|content| markdown::aliases(&content))
     }
 
     /// Replaces the content in the notes' markdown file with the given content.
     ///
+    /// Returns [`Error::PermissionDenied`] if the file is not writable.
+    ///
     /// # Examples
     ///
     /// ```
@@ -46,6 +186,171 @@ impl Note {
     /// _ = Note::write(&note, String::from("# Heading"));
     /// ```
     pub fn write(note: &Note, contents: String) -> Result<()> {
-        fs::write(&note.path, contents).map_err(Error::Io)
+        fs::write(&note.path, contents).map_err(|error| Error::from_io(error, &note.path))
+    }
+
+    /// Replaces the content of the note file atomically, so that a crash or a power failure
+    /// while writing leaves either the previous or the new content instead of a truncated file.
+    ///
+    /// The content is written to a temporary file next to the note, e.g. `Note.md.tmp`, which is
+    /// synced to disk and then renamed over the note file. The permissions of the note file are
+    /// kept. The temporary file is removed if any of the steps fails.
+    ///
+    /// If the note is a symlink, the file it points to is replaced, so that the symlink is kept.
+    ///
+    /// On Windows, [`fs::rename`] replaces the note file with `MoveFileExW`, which fails if
+    /// another process has the note file open without allowing it to be deleted.
+    ///
+    /// Returns [`Error::PermissionDenied`] if the directory of the note is not writable.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use basalt_core::obsidian::Note;
+    ///
+    /// let note = Note {
+    ///     name: "Example".to_string(),
+    ///     path: "path/to/Example.md".into(),
+    /// };
+    ///
+    /// assert!(note.atomic_write("# Heading").is_err());
+    /// ```
+    pub fn atomic_write(&self, content: &str) -> Result<()> {
+        let is_symlink = fs::symlink_metadata(&self.path)
+            .is_ok_and(|metadata| metadata.file_type().is_symlink());
+
+        let path = if is_symlink {
+            fs::canonicalize(&self.path).map_err(|error| Error::from_io(error, &self.path))?
+        } else {
+            self.path.clone()
+        };
+
+        let mut temp_path = path.clone().into_os_string();
+        temp_path.push(".tmp");
+        let temp_path = PathBuf::from(temp_path);
+
+        let write = || -> io::Result<()> {
+            let mut file = File::create(&temp_path)?;
+            file.write_all(content.as_bytes())?;
+
+            if let Ok(metadata) = fs::metadata(&path) {
+                file.set_permissions(metadata.permissions())?;
+            }
+
+            file.sync_all()?;
+            fs::rename(&temp_path, &path)
+        };
+
+        write().map_err(|error| {
+            _ = fs::remove_file(&temp_path);
+            Error::from_io(error, &self.path)
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::tempdir;
+
+    #[test]
+    fn test_atomic_write() {
+        let dir = tempdir().unwrap();
+        let note = Note {
+            name: "Note".into(),
+            path: dir.path().join("Note.md"),
+        };
+
+        note.atomic_write("# First").unwrap();
+        assert_eq!(Note::read_to_string(&note).unwrap(), "# First");
+
+        note.atomic_write("# Second").unwrap();
+        assert_eq!(Note::read_to_string(&note).unwrap(), "# Second");
+        assert!(!dir.path().join("Note.md.tmp").exists());
+    }
+
+    #[test]
+    fn test_read_to_string_checked() {
+        let dir = tempdir().unwrap();
+        let note = |name: &str, content: &[u8]| {
+            let path = dir.path().join(name);
+            fs::write(&path, content).unwrap();
+            Note {
+                name: name.into(),
+                path,
+            }
+        };
+
+        let small = note("Small.md", b"# Small");
+        assert_eq!(small.read_to_string_checked(7).unwrap(), "# Small");
+
+        let large = note("Large.md", &[b'a'; 64]);
+        assert!(matches!(
+            large.read_to_string_checked(32),
+            Err(Error::TooLarge {
+                size: 64,
+                max_bytes: 32,
+                ..
+            })
+        ));
+        assert_eq!(large.read_preview(4).unwrap(), "aaaa");
+
+        let binary = note("Binary.md", &[b'#', b' ', 0xff, 0xfe, b'a']);
+        assert!(matches!(
+            binary.read_to_string_checked(DEFAULT_MAX_NOTE_BYTES),
+            Err(Error::NotUtf8(_))
+        ));
+        assert_eq!(binary.read_preview(16).unwrap(), "# \u{fffd}\u{fffd}a");
+
+        // The preview leaves out a character that is cut off
+        let unicode = note("Unicode.md", "Ärrä".as_bytes());
+        assert_eq!(unicode.read_preview(5).unwrap(), "Ärr");
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_atomic_write_keeps_symlink() {
+        let dir = tempdir().unwrap();
+        let target = dir.path().join("Target.md");
+        fs::write(&target, "# First").unwrap();
+
+        let note = Note {
+            name: "Note".into(),
+            path: dir.path().join("Note.md"),
+        };
+        std::os::unix::fs::symlink(&target, &note.path).unwrap();
+
+        note.atomic_write("# Second").unwrap();
+
+        assert!(fs::symlink_metadata(&note.path)
+            .unwrap()
+            .file_type()
+            .is_symlink());
+        assert_eq!(fs::read_to_string(&target).unwrap(), "# Second");
+    }
+
+    #[test]
+    fn test_atomic_write_removes_temp_file_on_error() {
+        let dir = tempdir().unwrap();
+
+        // A directory cannot be replaced with a file, so the rename fails
+        let note = Note {
+            name: "Note".into(),
+            path: dir.path().join("Note.md"),
+        };
+        fs::create_dir(&note.path).unwrap();
+
+        assert!(note.atomic_write("# Heading").is_err());
+        assert!(note.path.is_dir());
+        assert!(!dir.path().join("Note.md.tmp").exists());
+
+        let missing = Note {
+            name: "Note".into(),
+            path: dir.path().join("missing/Note.md"),
+        };
+        assert!(matches!(
+            missing.atomic_write("# Heading"),
+            Err(Error::PathNotFound(_))
+        ));
     }
 }
@@ -0,0 +1,292 @@
+//! Parses and generates [Obsidian URIs](https://help.obsidian.md/Extending+Obsidian/Obsidian+URI),
+//! e.g. `obsidian://open?vault=Work&file=Inbox`, which open a vault or a note in Obsidian.
+//!
+//! # Examples
+//!
+//! ```
+//! use basalt_core::obsidian::uri::{parse_obsidian_uri, ObsidianUri};
+//!
+//! let uri = parse_obsidian_uri("obsidian://open?vault=My%20Vault&file=Daily%2FToday").unwrap();
+//!
+//! assert_eq!(
+//!     uri,
+//!     ObsidianUri::Open {
+//!         vault: "My Vault".into(),
+//!         file: Some("Daily/Today".into()),
+//!     }
+//! );
+//! assert_eq!(uri.to_string(), "obsidian://open?vault=My%20Vault&file=Daily%2FToday");
+//! ```
+use std::{fmt, path::PathBuf, result, str::FromStr};
+
+use super::{Error, Result};
+
+const SCHEME: &str = "obsidian://";
+
+/// Error type for malformed Obsidian URIs.
+#[derive(thiserror::Error, Debug, Clone, PartialEq)]
+pub enum UriError {
+    /// The URI does not start with `obsidian://`.
+    #[error("Not an Obsidian URI: {0}")]
+    InvalidScheme(String),
+
+    /// The URI uses an action other than `open`, e.g. `obsidian://search`.
+    #[error("Unsupported Obsidian URI action: {0}")]
+    UnsupportedAction(String),
+
+    /// A parameter that the action requires is missing, e.g. the `vault` of `open`.
+    #[error("Missing parameter in Obsidian URI: {0}")]
+    MissingParameter(&'static str),
+
+    /// A parameter is not valid percent-encoded UTF-8.
+    #[error("Invalid percent-encoding in Obsidian URI: {0}")]
+    InvalidEncoding(String),
+}
+
+/// An Obsidian URI that opens a vault or a note.
+#[derive(Debug, Clone, PartialEq)]
+pub enum ObsidianUri {
+    /// Opens the vault with the given name, and the file at the given path relative to the vault
+    /// directory, if any. The `.md` extension of the file is optional.
+    ///
+    /// `obsidian://open?vault=Work&file=Inbox`
+    Open {
+        /// Name of the vault.
+        vault: String,
+        /// Path of the file relative to the vault directory.
+        file: Option<String>,
+    },
+    /// Opens the file at the given absolute path in the vault that contains it.
+    ///
+    /// `obsidian://open?path=%2Fhome%2Fuser%2FWork%2FInbox.md`
+    OpenPath {
+        /// Absolute path of the file.
+        path: PathBuf,
+    },
+}
+
+/// Decodes the `%XX` escapes of a URI component.
+fn percent_decode(value: &str) -> result::Result<String, UriError> {
+    let invalid = || UriError::InvalidEncoding(value.to_string());
+    let mut bytes = vec![];
+    let mut rest = value.as_bytes();
+
+    while let Some((&byte, tail)) = rest.split_first() {
+        if byte == b'%' {
+            let hex = tail.get(..2).ok_or_else(invalid)?;
+            let hex = std::str::from_utf8(hex).map_err(|_| invalid())?;
+            bytes.push(u8::from_str_radix(hex, 16).map_err(|_| invalid())?);
+            rest = &tail[2..];
+        } else {
+            bytes.push(byte);
+            rest = tail;
+        }
+    }
+
+    String::from_utf8(bytes).map_err(|_| invalid())
+}
+
+/// Encodes all characters except the unreserved ones, so that e.g. `/` and spaces are escaped the
+/// same way Obsidian escapes them.
+fn percent_encode(value: &str) -> String {
+    value.bytes().fold(String::new(), |mut encoded, byte| {
+        match byte {
+            b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'_' | b'.' | b'~' => {
+                encoded.push(byte as char)
+            }
+            _ => encoded.push_str(&format!("%{byte:02X}")),
+        }
+        encoded
+    })
+}
+
+/// Parses an Obsidian URI.
+///
+/// Only the `open` action is supported. Parameters other than `vault`, `file` and `path` are
+/// ignored. Returns [`Error::Uri`] if the URI is malformed.
+///
+/// # Examples
+///
+/// ```
+/// use basalt_core::obsidian::{uri::{parse_obsidian_uri, ObsidianUri, UriError}, Error};
+///
+/// assert_eq!(
+///     parse_obsidian_uri("obsidian://open?path=%2Fnotes%2FInbox.md").unwrap(),
+///     ObsidianUri::OpenPath { path: "/notes/Inbox.md".into() }
+/// );
+/// assert!(matches!(
+///     parse_obsidian_uri("obsidian://open?file=Inbox"),
+///     Err(Error::Uri(UriError::MissingParameter("vault")))
+/// ));
+/// ```
+pub fn parse_obsidian_uri(uri: &str) -> Result<ObsidianUri> {
+    let rest = uri
+        .strip_prefix(SCHEME)
+        .ok_or_else(|| UriError::InvalidScheme(uri.to_string()))?;
+
+    let (action, query) = rest.split_once('?').unwrap_or((rest, ""));
+    let action = action.trim_end_matches('/');
+
+    if action != "open" {
+        return Err(UriError::UnsupportedAction(action.to_string()).into());
+    }
+
+    let mut vault = None;
+    let mut file = None;
+    let mut path = None;
+
+    for parameter in query.split('&').filter(|parameter| !parameter.is_empty()) {
+        let (key, value) = parameter.split_once('=').unwrap_or((parameter, ""));
+        let value = percent_decode(value)?;
+
+        match key {
+            "vault" => vault = Some(value),
+            "file" => file = Some(value),
+            "path" => path = Some(value),
+            _ => {}
+        }
+    }
+
+    match (path, vault) {
+        (Some(path), _) => Ok(ObsidianUri::OpenPath { path: path.into() }),
+        (None, Some(vault)) => Ok(ObsidianUri::Open { vault, file }),
+        (None, None) => Err(UriError::MissingParameter("vault").into()),
+    }
+}
+
+impl FromStr for ObsidianUri {
+    type Err = Error;
+
+    fn from_str(uri: &str) -> Result<Self> {
+        parse_obsidian_uri(uri)
+    }
+}
+
+impl fmt::Display for ObsidianUri {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            ObsidianUri::Open { vault, file } => {
+                write!(f, "{SCHEME}open?vault={}", percent_encode(vault))?;
+                if let Some(file) = file {
+                    write!(f, "&file={}", percent_encode(file))?;
+                }
+                Ok(())
+            }
+            ObsidianUri::OpenPath { path } => write!(
+                f,
+                "{SCHEME}open?path={}",
+                percent_encode(&path.to_string_lossy())
+            ),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_obsidian_uri() {
+        let open = |vault: &str, file: Option<&str>| ObsidianUri::Open {
+            vault: vault.into(),
+            file: file.map(String::from),
+        };
+
+        let tests = [
+            ("obsidian://open?vault=Work", open("Work", None)),
+            (
+                "obsidian://open/?vault=Work&file=inbox",
+                open("Work", Some("inbox")),
+            ),
+            (
+                "obsidian://open?file=Daily%2F2024-01-01.md&vault=My%20Vault&foo=bar",
+                open("My Vault", Some("Daily/2024-01-01.md")),
+            ),
+            (
+                "obsidian://open?vault=Work&path=%2Fhome%2Fuser%2FWork%2FInbox.md",
+                ObsidianUri::OpenPath {
+                    path: "/home/user/Work/Inbox.md".into(),
+                },
+            ),
+        ];
+
+        tests
+            .into_iter()
+            .for_each(|(uri, expected)| assert_eq!(parse_obsidian_uri(uri).unwrap(), expected));
+    }
+
+    #[test]
+    fn test_parse_obsidian_uri_errors() {
+        let tests = [
+            (
+                "https://open?vault=Work",
+                UriError::InvalidScheme("https://open?vault=Work".into()),
+            ),
+            (
+                "obsidian://search?query=x",
+                UriError::UnsupportedAction("search".into()),
+            ),
+            ("obsidian://open", UriError::MissingParameter("vault")),
+            (
+                "obsidian://open?file=Inbox",
+                UriError::MissingParameter("vault"),
+            ),
+            (
+                "obsidian://open?vault=Wo%2",
+                UriError::InvalidEncoding("Wo%2".into()),
+            ),
+            (
+                "obsidian://open?vault=%zz",
+                UriError::InvalidEncoding("%zz".into()),
+            ),
+            // A lone continuation byte is not valid UTF-8
+            (
+                "obsidian://open?vault=%80",
+                UriError::InvalidEncoding("%80".into()),
+            ),
+        ];
+
+        tests
+            .into_iter()
+            .for_each(|(uri, expected)| match parse_obsidian_uri(uri) {
+                Err(Error::Uri(error)) => assert_eq!(error, expected, "{uri}"),
+                result => panic!("Expected {expected:?} for {uri}, got {result:?}"),
+            });
+    }
+
+    #[test]
+    fn test_obsidian_uri_round_trip() {
+        let uris = [
+            ObsidianUri::Open {
+                vault: "Work".into(),
+                file: None,
+            },
+            ObsidianUri::Open {
+                vault: "My Vault & Co".into(),
+                file: Some("Projects/Q1 plan?.md".into()),
+            },
+            ObsidianUri::Open {
+                vault: "Notizbücher 📓".into(),
+                file: Some("日記/今日".into()),
+            },
+            ObsidianUri::OpenPath {
+                path: "/home/user/Мои заметки/Inbox.md".into(),
+            },
+        ];
+
+        uris.into_iter().for_each(|uri| {
+            let string = uri.to_string();
+            assert!(string.is_ascii(), "{string}");
+            assert_eq!(string.parse::<ObsidianUri>().unwrap(), uri);
+        });
+
+        assert_eq!(
+            ObsidianUri::Open {
+                vault: "My Vault".into(),
+                file: Some("Notes/Ünïcode".into()),
+            }
+            .to_string(),
+            "obsidian://open?vault=My%20Vault&file=Notes%2F%C3%9Cn%C3%AFcode"
+        );
+    }
+}
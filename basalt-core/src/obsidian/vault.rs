@@ -1,8 +1,20 @@
-use std::{path::PathBuf, result};
+use std::{
+    fs::{self, OpenOptions},
+    io::{self, Write},
+    path::{Component, Path, PathBuf},
+    result,
+    time::{SystemTime, UNIX_EPOCH},
+};
 
-use serde::{Deserialize, Deserializer};
+use chrono::Local;
+use serde::{de, Deserialize, Deserializer};
+use serde_json::{json, Value};
 
-use super::vault_entry::VaultEntry;
+use super::{
+    bookmark::{self, Bookmark, BOOKMARKS_FILE, STARRED_FILE},
+    vault_entry::{ReadOptions, SortOrder, VaultEntry},
+    Error, Note, Result, WikiLink,
+};
 
 /// Represents a single Obsidian vault.
 ///
@@ -27,7 +39,8 @@ impl Vault {
     /// Entries can be either directories or files (notes). If the directory is marked hidden with
     /// a dot (`.`) prefix it will be filtered out from the resulting [`Vec`].
     ///
-    /// The returned entries are not sorted.
+    /// The returned entries are in the order of the file system, use [`Vault::entries_sorted`] to
+    /// sort them.
     ///
     /// # Examples
     ///
@@ -43,13 +56,329 @@ impl Vault {
     /// assert_eq!(vault.entries(), vec![]);
     /// ```
     pub fn entries(&self) -> Vec<VaultEntry> {
-        match self.path.as_path().try_into() {
-            Ok(VaultEntry::Directory { entries, .. }) => entries
-                .into_iter()
-                .filter(|entry| !entry.name().starts_with('.'))
-                .collect(),
-            _ => vec![],
+        self.read_entries(&ReadOptions::default()).0
+    }
+
+    /// Returns the entries of this vault, see [`Vault::entries`], read with the given
+    /// [`ReadOptions`], together with the errors of the entries that could not be read, see
+    /// [`VaultEntry::read_options`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use basalt_core::obsidian::{ReadOptions, Vault};
+    ///
+    /// let vault = Vault {
+    ///     name: "MyVault".into(),
+    ///     path: "path/to/my_vault".into(),
+    ///     ..Default::default()
+    /// };
+    ///
+    /// let (entries, errors) = vault.read_entries(&ReadOptions::default());
+    ///
+    /// assert_eq!(entries, vec![]);
+    /// assert_eq!(errors.len(), 1);
+    /// ```
+    pub fn read_entries(&self, options: &ReadOptions) -> (Vec<VaultEntry>, Vec<Error>) {
+        match VaultEntry::read_options(&self.path, options) {
+            Ok((VaultEntry::Directory { entries, .. }, errors)) => (
+                entries
+                    .into_iter()
+                    .filter(|entry| !entry.name().starts_with('.'))
+                    .collect(),
+                errors,
+            ),
+            Ok((VaultEntry::File(_), errors)) => (vec![], errors),
+            Err(error) => (vec![], vec![error]),
+        }
+    }
+
+    /// Returns the entries of this vault, see [`Vault::entries`], sorted recursively in the given
+    /// [`SortOrder`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use basalt_core::obsidian::{SortOrder, Vault};
+    ///
+    /// let vault = Vault {
+    ///     name: "MyVault".into(),
+    ///     path: "path/to/my_vault".into(),
+    ///     ..Default::default()
+    /// };
+    ///
+    /// assert_eq!(vault.entries_sorted(SortOrder::FileType), vec![]);
+    /// ```
+    pub fn entries_sorted(&self, sort: SortOrder) -> Vec<VaultEntry> {
+        let mut entries = self.entries();
+        sort.sort(&mut entries);
+        entries
+    }
+
+    /// Returns the note with the given name, resolved the same way as a wiki link to the name, see
+    /// [`WikiLink::resolve`]. The name can contain a path relative to the vault, e.g.
+    /// `Projects/Idea`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use basalt_core::obsidian::Vault;
+    ///
+    /// let vault = Vault {
+    ///     name: "MyVault".into(),
+    ///     path: "path/to/my_vault".into(),
+    ///     ..Default::default()
+    /// };
+    ///
+    /// assert_eq!(vault.find_note_by_name("Idea"), None);
+    /// ```
+    pub fn find_note_by_name(&self, name: &str) -> Option<Note> {
+        WikiLink::parse(name)?.resolve(&self.entries()).cloned()
+    }
+
+    /// Creates an empty note with the given name in the vault and returns it.
+    ///
+    /// The name can contain a path relative to the vault, e.g. `Projects/Idea`, to create the
+    /// note in a subdirectory. The directory must already exist.
+    ///
+    /// Returns [`Error::AlreadyExists`] if a file with the same name already exists,
+    /// [`Error::InvalidVaultStructure`] if the name is empty or points outside of the vault, or
+    /// another [`Error`] if the file could not be created.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use basalt_core::obsidian::Vault;
+    ///
+    /// let vault = Vault {
+    ///     name: "MyVault".into(),
+    ///     path: "path/to/my_vault".into(),
+    ///     ..Default::default()
+    /// };
+    ///
+    /// _ = vault.create_note("Projects/Idea");
+    /// ```
+    pub fn create_note(&self, name: &str) -> Result<Note> {
+        self.create_note_with_template(name, "")
+    }
+
+    /// Creates a note with the given name in the vault using the template as its initial content
+    /// and returns it.
+    ///
+    /// The template can contain `{{title}}` and `{{date}}` placeholders, which are replaced with
+    /// the note name and the current local date in `YYYY-MM-DD` format.
+    ///
+    /// Returns [`Error::AlreadyExists`] if a file with the same name already exists,
+    /// [`Error::InvalidVaultStructure`] if the name is empty or points outside of the vault, or
+    /// another [`Error`] if the file could not be written. A file that could not be written is
+    /// removed, so that no partial note is left in the vault.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use basalt_core::obsidian::Vault;
+    ///
+    /// let vault = Vault {
+    ///     name: "MyVault".into(),
+    ///     path: "path/to/my_vault".into(),
+    ///     ..Default::default()
+    /// };
+    ///
+    /// _ = vault.create_note_with_template("Daily", "# {{title}}\n\nCreated {{date}}\n");
+    /// ```
+    pub fn create_note_with_template(&self, name: &str, template: &str) -> Result<Note> {
+        let is_within_vault = Path::new(name)
+            .components()
+            .all(|component| matches!(component, Component::Normal(_)));
+
+        if name.trim().is_empty() || !is_within_vault {
+            return Err(Error::InvalidVaultStructure(format!(
+                "'{name}' is not a note path within the vault"
+            )));
+        }
+
+        let path = self.path.join(format!("{name}.md"));
+
+        if path.exists() {
+            return Err(Error::AlreadyExists(path));
+        }
+
+        let title = path
+            .file_stem()
+            .map(|stem| stem.to_string_lossy().to_string())
+            .unwrap_or_default();
+
+        let mut file = OpenOptions::new()
+            .write(true)
+            .create_new(true)
+            .open(&path)
+            .map_err(|error| Error::from_io(error, &path))?;
+
+        if let Err(error) = file.write_all(render_template(template, &title, &today()).as_bytes()) {
+            drop(file);
+            _ = fs::remove_file(&path);
+            return Err(Error::from_io(error, &path));
+        }
+
+        Ok(Note { name: title, path })
+    }
+
+    /// Deletes the note's file from the vault.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use basalt_core::obsidian::{Note, Vault};
+    ///
+    /// let vault = Vault::default();
+    ///
+    /// let note = Note {
+    ///     name: "Example".to_string(),
+    ///     path: "path/to/Example.md".into(),
+    /// };
+    ///
+    /// assert!(vault.delete_note(&note).is_err());
+    /// ```
+    pub fn delete_note(&self, note: &Note) -> Result<()> {
+        fs::remove_file(&note.path).map_err(|error| Error::from_io(error, &note.path))
+    }
+
+    /// Renames the note's file within its directory and returns the renamed note.
+    ///
+    /// Returns [`Error::AlreadyExists`] if a file with the new name already exists,
+    /// [`Error::InvalidVaultStructure`] if the new name is empty or contains a path, or another
+    /// [`Error`] if the file could not be renamed.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use basalt_core::obsidian::{Note, Vault};
+    ///
+    /// let vault = Vault::default();
+    ///
+    /// let note = Note {
+    ///     name: "Example".to_string(),
+    ///     path: "path/to/Example.md".into(),
+    /// };
+    ///
+    /// _ = vault.rename_note(&note, "Renamed");
+    /// ```
+    pub fn rename_note(&self, note: &Note, name: &str) -> Result<Note> {
+        if name.trim().is_empty() || name.contains(['/', '\\']) {
+            return Err(Error::InvalidVaultStructure(format!(
+                "'{name}' is not a note name"
+            )));
+        }
+
+        let path = note.path.with_file_name(format!("{name}.md"));
+
+        // Renaming replaces an existing file on most platforms
+        if path.exists() {
+            return Err(Error::AlreadyExists(path));
         }
+
+        fs::rename(&note.path, &path).map_err(|error| Error::from_io(error, &note.path))?;
+
+        Ok(Note {
+            name: name.to_string(),
+            path,
+        })
+    }
+
+    /// Reads the bookmarks file of the vault, or the legacy starred file if there are no
+    /// bookmarks yet. Returns [`None`] if neither file exists.
+    fn read_bookmarks_file(&self) -> Result<Option<String>> {
+        [BOOKMARKS_FILE, STARRED_FILE]
+            .into_iter()
+            .map(|file_name| self.path.join(".obsidian").join(file_name))
+            .find_map(|path| match fs::read_to_string(&path) {
+                Ok(contents) => Some(Ok(contents)),
+                Err(error) if error.kind() == io::ErrorKind::NotFound => None,
+                Err(error) => Some(Err(Error::from_io(error, &path))),
+            })
+            .transpose()
+    }
+
+    /// Returns the bookmarks of the vault as a flat list, read from `.obsidian/bookmarks.json`.
+    ///
+    /// The legacy `.obsidian/starred.json` file is read when the vault has no bookmarks file.
+    /// Absent or corrupt files result in an empty list, only I/O errors other than a missing file
+    /// are returned as an [`Error`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use basalt_core::obsidian::Vault;
+    ///
+    /// let vault = Vault::default();
+    ///
+    /// assert_eq!(vault.bookmarks().unwrap(), vec![]);
+    /// ```
+    pub fn bookmarks(&self) -> Result<Vec<Bookmark>> {
+        Ok(self
+            .read_bookmarks_file()?
+            .and_then(|contents| serde_json::from_str(&contents).ok())
+            .map(|json| bookmark::from_json(&json))
+            .unwrap_or_default())
+    }
+
+    /// Applies the change to the bookmarks file and writes it back if the change returns `true`.
+    ///
+    /// The file is changed as JSON, so that the fields unknown to Basalt are preserved. Unlike
+    /// [`Vault::bookmarks`], a corrupt file is returned as an [`Error::Json`], so that it is not
+    /// overwritten.
+    fn update_bookmarks(&self, change: impl FnOnce(&mut Value) -> Result<bool>) -> Result<()> {
+        let mut json = match self.read_bookmarks_file()? {
+            Some(contents) => serde_json::from_str(&contents)?,
+            None => json!({ "items": [] }),
+        };
+
+        if !change(&mut json)? {
+            return Ok(());
+        }
+
+        let dir = self.path.join(".obsidian");
+        fs::create_dir_all(&dir).map_err(|error| Error::from_io(error, &dir))?;
+
+        let path = dir.join(BOOKMARKS_FILE);
+        fs::write(&path, serde_json::to_string_pretty(&json)?)
+            .map_err(|error| Error::from_io(error, &path))
+    }
+
+    /// Bookmarks the file at the path, which is either absolute or relative to the vault
+    /// directory. Does nothing if the file is already bookmarked.
+    ///
+    /// The bookmark is written to `.obsidian/bookmarks.json` in the format used by Obsidian.
+    /// Returns an [`Error`] if the bookmarks file is corrupt or could not be written.
+    pub fn add_bookmark(&self, path: &Path) -> Result<()> {
+        let path = path.strip_prefix(&self.path).unwrap_or(path);
+
+        self.update_bookmarks(|json| {
+            if bookmark::from_json(json)
+                .iter()
+                .any(|bookmark| bookmark.path == path)
+            {
+                return Ok(false);
+            }
+
+            let ctime = SystemTime::now()
+                .duration_since(UNIX_EPOCH)
+                .map(|duration| duration.as_millis())
+                .unwrap_or_default();
+
+            bookmark::insert(json, path, ctime)
+                .map(|_| true)
+                .ok_or_else(|| Error::Json(de::Error::custom("invalid bookmarks format")))
+        })
+    }
+
+    /// Removes the bookmarks of the file at the path from all bookmark groups. The path is either
+    /// absolute or relative to the vault directory.
+    ///
+    /// Returns an [`Error`] if the bookmarks file is corrupt or could not be written.
+    pub fn remove_bookmark(&self, path: &Path) -> Result<()> {
+        let path = path.strip_prefix(&self.path).unwrap_or(path);
+        self.update_bookmarks(|json| Ok(bookmark::remove(json, path) > 0))
     }
 }
 
@@ -86,3 +415,483 @@ impl<'de> Deserialize<'de> for Vault {
         deserialized.try_into().map_err(serde::de::Error::custom)
     }
 }
+
+/// Replaces the `{{title}}` and `{{date}}` placeholders of a note template.
+fn render_template(template: &str, title: &str, date: &str) -> String {
+    template
+        .replace("{{title}}", title)
+        .replace("{{date}}", date)
+}
+
+/// Returns the current date in the local time zone formatted as `YYYY-MM-DD`.
+fn today() -> String {
+    Local::now().date_naive().to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{fs, time::Duration};
+
+    use tempfile::tempdir;
+
+    use super::*;
+    use crate::obsidian::FileKind;
+
+    fn vault(path: PathBuf) -> Vault {
+        Vault {
+            name: "Vault".into(),
+            path,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_create_note() {
+        let dir = tempdir().unwrap();
+        let vault = vault(dir.path().to_path_buf());
+
+        let note = vault.create_note("Idea").unwrap();
+        assert_eq!(note.name, "Idea");
+        assert_eq!(note.path, dir.path().join("Idea.md"));
+        assert_eq!(fs::read_to_string(&note.path).unwrap(), "");
+
+        fs::create_dir(dir.path().join("Projects")).unwrap();
+        let note = vault.create_note("Projects/Plan").unwrap();
+        assert_eq!(note.name, "Plan");
+        assert_eq!(note.path, dir.path().join("Projects").join("Plan.md"));
+        assert!(note.path.exists());
+    }
+
+    #[test]
+    fn test_entries_with_attachments() {
+        let dir = tempdir().unwrap();
+        let vault = vault(dir.path().to_path_buf());
+
+        fs::create_dir(dir.path().join("Assets")).unwrap();
+        fs::write(dir.path().join("Idea.md"), "# Idea").unwrap();
+        fs::write(dir.path().join("Board.canvas"), "{}").unwrap();
+        fs::write(
+            dir.path().join("Assets/diagram.png"),
+            [0x89, b'P', b'N', b'G'],
+        )
+        .unwrap();
+
+        let mut entries = vault.entries();
+        entries.sort_by(|a, b| a.name().cmp(b.name()));
+
+        let Some(VaultEntry::Directory {
+            entries: assets, ..
+        }) = entries.first()
+        else {
+            panic!("Expected the Assets directory, got {entries:?}");
+        };
+
+        assert_eq!(
+            entries
+                .iter()
+                .chain(assets)
+                .map(|entry| (entry.name(), entry.kind()))
+                .collect::<Vec<_>>(),
+            [
+                ("Assets", None),
+                ("Board.canvas", Some(FileKind::Canvas)),
+                ("Idea", Some(FileKind::Markdown)),
+                ("diagram.png", Some(FileKind::Image)),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_read_entries() {
+        let dir = tempdir().unwrap();
+        let vault = vault(dir.path().to_path_buf());
+
+        fs::create_dir_all(dir.path().join("Projects/Archive")).unwrap();
+        fs::create_dir(dir.path().join(".obsidian")).unwrap();
+        fs::write(dir.path().join("Projects/Archive/Old.md"), "").unwrap();
+
+        let options = ReadOptions {
+            max_depth: Some(2),
+            ..Default::default()
+        };
+        let (entries, errors) = vault.read_entries(&options);
+        assert!(errors.is_empty(), "{errors:?}");
+        assert!(matches!(
+            entries.as_slice(),
+            [VaultEntry::Directory { name, entries, .. }]
+                if name == "Projects"
+                    && matches!(
+                        entries.as_slice(),
+                        [VaultEntry::Directory { entries, .. }] if entries.is_empty()
+                    )
+        ));
+
+        let vault = Vault {
+            path: dir.path().join("Missing"),
+            ..vault
+        };
+        let (entries, errors) = vault.read_entries(&ReadOptions::default());
+        assert_eq!(entries, vec![]);
+        assert!(matches!(errors.as_slice(), [Error::PathNotFound(_)]));
+    }
+
+    #[test]
+    fn test_entries_sorted() {
+        let dir = tempdir().unwrap();
+        let vault = vault(dir.path().to_path_buf());
+
+        fs::create_dir_all(dir.path().join("projects")).unwrap();
+        fs::create_dir_all(dir.path().join("Archive")).unwrap();
+
+        // Modified from oldest to newest. The times of the directories are set last, since
+        // writing the files changes them.
+        let paths = [
+            "projects/Plan.md",
+            "b.md",
+            "Archive/Old.md",
+            "C.md",
+            "a.md",
+            "projects",
+            "Archive",
+        ];
+        paths.iter().enumerate().for_each(|(index, path)| {
+            let path = dir.path().join(path);
+            if path.extension().is_some() {
+                fs::write(&path, "").unwrap();
+            }
+            fs::File::open(&path)
+                .unwrap()
+                .set_modified(UNIX_EPOCH + Duration::from_secs(index as u64 * 60))
+                .unwrap();
+        });
+
+        let names = |entries: &[VaultEntry]| -> Vec<String> {
+            entries
+                .iter()
+                .flat_map(|entry| match entry {
+                    VaultEntry::Directory { entries, .. } => [entry.name().to_string()]
+                        .into_iter()
+                        .chain(entries.iter().map(|entry| format!("  {}", entry.name())))
+                        .collect(),
+                    entry => vec![entry.name().to_string()],
+                })
+                .collect()
+        };
+
+        let tests = [
+            (
+                SortOrder::NameAsc,
+                ["a", "Archive", "  Old", "b", "C", "projects", "  Plan"],
+            ),
+            (
+                SortOrder::NameDesc,
+                ["projects", "  Plan", "C", "b", "Archive", "  Old", "a"],
+            ),
+            (
+                SortOrder::ModifiedAsc,
+                ["b", "C", "a", "projects", "  Plan", "Archive", "  Old"],
+            ),
+            (
+                SortOrder::ModifiedDesc,
+                ["Archive", "  Old", "projects", "  Plan", "a", "C", "b"],
+            ),
+            (
+                SortOrder::FileType,
+                ["Archive", "  Old", "projects", "  Plan", "a", "b", "C"],
+            ),
+        ];
+
+        tests.into_iter().for_each(|(sort, expected)| {
+            assert_eq!(names(&vault.entries_sorted(sort)), expected, "{sort:?}");
+        });
+    }
+
+    #[test]
+    fn test_create_note_already_exists() {
+        let dir = tempdir().unwrap();
+        let vault = vault(dir.path().to_path_buf());
+
+        fs::write(dir.path().join("Idea.md"), "# Existing").unwrap();
+
+        assert!(matches!(
+            vault.create_note("Idea"),
+            Err(Error::AlreadyExists(path)) if path == dir.path().join("Idea.md")
+        ));
+        assert_eq!(
+            fs::read_to_string(dir.path().join("Idea.md")).unwrap(),
+            "# Existing"
+        );
+    }
+
+    #[test]
+    fn test_create_note_with_template() {
+        let dir = tempdir().unwrap();
+        let vault = vault(dir.path().to_path_buf());
+
+        let note = vault
+            .create_note_with_template("Daily", "# {{title}}\n\nCreated {{date}}\n")
+            .unwrap();
+
+        assert_eq!(
+            fs::read_to_string(&note.path).unwrap(),
+            format!("# Daily\n\nCreated {}\n", today())
+        );
+        assert!(matches!(
+            vault.create_note_with_template("Daily", ""),
+            Err(Error::AlreadyExists(_))
+        ));
+    }
+
+    #[test]
+    fn test_today() {
+        let today = today();
+        assert_eq!(today.len(), 10, "{today}");
+        assert!(matches!(
+            today.split('-').collect::<Vec<_>>().as_slice(),
+            [year, month, day] if year.len() == 4 && month.len() == 2 && day.len() == 2
+        ));
+    }
+
+    #[test]
+    fn test_render_template() {
+        let tests = [
+            ("", ""),
+            ("No placeholders", "No placeholders"),
+            ("# {{title}}", "# Idea"),
+            ("{{date}} {{title}} {{date}}", "2025-01-31 Idea 2025-01-31"),
+            ("{{unknown}}", "{{unknown}}"),
+        ];
+
+        tests.into_iter().for_each(|(template, expected)| {
+            assert_eq!(render_template(template, "Idea", "2025-01-31"), expected)
+        });
+    }
+
+    #[test]
+    fn test_create_note_invalid_name() {
+        let dir = tempdir().unwrap();
+        let vault = vault(dir.path().join("Vault"));
+        fs::create_dir(&vault.path).unwrap();
+
+        [
+            "",
+            "  ",
+            "../Outside",
+            "/Absolute",
+            "Projects/../../Outside",
+        ]
+        .iter()
+        .for_each(|name| {
+            assert!(
+                matches!(
+                    vault.create_note(name),
+                    Err(Error::InvalidVaultStructure(_))
+                ),
+                "{name}"
+            )
+        });
+        assert!(!dir.path().join("Outside.md").exists());
+
+        assert!(matches!(
+            vault.create_note("Missing/Idea"),
+            Err(Error::PathNotFound(_))
+        ));
+    }
+
+    #[test]
+    fn test_rename_note_invalid_name() {
+        let dir = tempdir().unwrap();
+        let vault = vault(dir.path().to_path_buf());
+
+        let note = vault.create_note("Idea").unwrap();
+
+        ["", "Projects/Idea", "../Idea"].iter().for_each(|name| {
+            assert!(
+                matches!(
+                    vault.rename_note(&note, name),
+                    Err(Error::InvalidVaultStructure(_))
+                ),
+                "{name}"
+            )
+        });
+        assert!(note.path.exists());
+    }
+
+    #[test]
+    fn test_rename_note_already_exists() {
+        let dir = tempdir().unwrap();
+        let vault = vault(dir.path().to_path_buf());
+
+        let note = vault.create_note("Idea").unwrap();
+        vault.create_note("Plan").unwrap();
+
+        assert!(matches!(
+            vault.rename_note(&note, "Plan"),
+            Err(Error::AlreadyExists(path)) if path == dir.path().join("Plan.md")
+        ));
+        assert!(note.path.exists());
+    }
+
+    /// A bookmarks file written by Obsidian, with a nested group and bookmark kinds that have no
+    /// path.
+    const BOOKMARKS_JSON: &str = r##"{
+  "items": [
+    {
+      "type": "file",
+      "ctime": 1716299123503,
+      "path": "Daily.md"
+    },
+    {
+      "type": "group",
+      "ctime": 1716299170221,
+      "items": [
+        {
+          "type": "file",
+          "ctime": 1716299178912,
+          "path": "Projects/Basalt.md",
+          "title": "Basalt TUI"
+        },
+        {
+          "type": "group",
+          "ctime": 1716299201345,
+          "items": [
+            {
+              "type": "folder",
+              "ctime": 1716299210017,
+              "path": "Projects/Archive"
+            }
+          ],
+          "title": "Archive"
+        },
+        {
+          "type": "search",
+          "ctime": 1716299232751,
+          "query": "tag:#todo",
+          "title": "Todos"
+        }
+      ],
+      "title": "Projects"
+    },
+    {
+      "type": "file",
+      "ctime": 1716299250632,
+      "path": "Ideas.md",
+      "subpath": "#Later"
+    }
+  ]
+}"##;
+
+    fn bookmark(title: &str, path: &str, group_path: &[&str]) -> Bookmark {
+        Bookmark {
+            title: title.into(),
+            path: path.into(),
+            group_path: group_path.iter().map(|group| group.to_string()).collect(),
+        }
+    }
+
+    #[test]
+    fn test_bookmarks() {
+        let dir = tempdir().unwrap();
+        let vault = vault(dir.path().to_path_buf());
+        assert_eq!(vault.bookmarks().unwrap(), vec![]);
+
+        fs::create_dir(dir.path().join(".obsidian")).unwrap();
+        fs::write(
+            dir.path().join(".obsidian/starred.json"),
+            r#"{"items":[{"type":"file","title":"Daily","path":"Daily.md"}]}"#,
+        )
+        .unwrap();
+        assert_eq!(
+            vault.bookmarks().unwrap(),
+            vec![bookmark("Daily", "Daily.md", &[])]
+        );
+
+        fs::write(dir.path().join(".obsidian/bookmarks.json"), BOOKMARKS_JSON).unwrap();
+        assert_eq!(
+            vault.bookmarks().unwrap(),
+            vec![
+                bookmark("Daily", "Daily.md", &[]),
+                bookmark("Basalt TUI", "Projects/Basalt.md", &["Projects"]),
+                bookmark("Archive", "Projects/Archive", &["Projects", "Archive"]),
+                bookmark("Ideas", "Ideas.md", &[]),
+            ]
+        );
+
+        fs::write(dir.path().join(".obsidian/bookmarks.json"), "{\"items\": [").unwrap();
+        assert_eq!(vault.bookmarks().unwrap(), vec![]);
+        assert!(matches!(
+            vault.add_bookmark(Path::new("Daily.md")),
+            Err(Error::Json(_))
+        ));
+    }
+
+    #[test]
+    fn test_add_and_remove_bookmark() {
+        let dir = tempdir().unwrap();
+        let vault = vault(dir.path().to_path_buf());
+        let path = dir.path().join(".obsidian/bookmarks.json");
+
+        vault
+            .add_bookmark(&dir.path().join("Notes/Daily.md"))
+            .unwrap();
+        vault.add_bookmark(Path::new("Notes/Daily.md")).unwrap();
+        assert_eq!(
+            vault.bookmarks().unwrap(),
+            vec![bookmark("Daily", "Notes/Daily.md", &[])]
+        );
+
+        vault.remove_bookmark(Path::new("Notes/Daily.md")).unwrap();
+        assert_eq!(vault.bookmarks().unwrap(), vec![]);
+        assert_eq!(
+            serde_json::from_str::<Value>(&fs::read_to_string(&path).unwrap()).unwrap(),
+            json!({ "items": [] })
+        );
+    }
+
+    #[test]
+    fn test_bookmarks_round_trip() {
+        let dir = tempdir().unwrap();
+        let vault = vault(dir.path().to_path_buf());
+        let path = dir.path().join(".obsidian/bookmarks.json");
+        let read_json = || serde_json::from_str::<Value>(&fs::read_to_string(&path).unwrap());
+
+        fs::create_dir(dir.path().join(".obsidian")).unwrap();
+        fs::write(&path, BOOKMARKS_JSON).unwrap();
+
+        vault.add_bookmark(&dir.path().join("Ideas.md")).unwrap();
+        assert_eq!(
+            read_json().unwrap(),
+            serde_json::from_str::<Value>(BOOKMARKS_JSON).unwrap()
+        );
+
+        vault.add_bookmark(Path::new("Plan.md")).unwrap();
+        let json = read_json().unwrap();
+        let added = &json["items"][3];
+        assert_eq!(added["type"], "file");
+        assert_eq!(added["path"], "Plan.md");
+        assert!(added["ctime"].is_u64());
+
+        vault.remove_bookmark(Path::new("Plan.md")).unwrap();
+        assert_eq!(
+            read_json().unwrap(),
+            serde_json::from_str::<Value>(BOOKMARKS_JSON).unwrap()
+        );
+
+        vault
+            .remove_bookmark(Path::new("Projects/Basalt.md"))
+            .unwrap();
+        let json = read_json().unwrap();
+        assert_eq!(json["items"][1]["title"], "Projects");
+        assert_eq!(json["items"][1]["items"][0]["title"], "Archive");
+        assert_eq!(json["items"][1]["items"][1]["query"], "tag:#todo");
+        assert_eq!(
+            vault
+                .bookmarks()
+                .unwrap()
+                .into_iter()
+                .map(|bookmark| bookmark.title)
+                .collect::<Vec<_>>(),
+            ["Daily", "Archive", "Ideas"]
+        );
+    }
+}
@@ -1,9 +1,11 @@
 use std::{
-    fs::read_dir,
+    cmp::{Ordering, Reverse},
+    fs::{self, read_dir},
     path::{Path, PathBuf},
+    time::SystemTime,
 };
 
-use super::{Error, Note, Result};
+use super::{Error, FileKind, Note, Result};
 
 #[derive(Debug, Clone, PartialEq)]
 #[allow(missing_docs)]
@@ -23,39 +25,277 @@ impl VaultEntry {
             Self::Directory { name, .. } | Self::File(Note { name, .. }) => name.as_str(),
         }
     }
+
+    #[allow(missing_docs)]
+    pub fn path(&self) -> &Path {
+        match self {
+            Self::Directory { path, .. } | Self::File(Note { path, .. }) => path.as_path(),
+        }
+    }
+
+    /// Returns the kind of the file, or `None` if the entry is a directory.
+    pub fn kind(&self) -> Option<FileKind> {
+        match self {
+            Self::File(note) => Some(note.kind()),
+            Self::Directory { .. } => None,
+        }
+    }
 }
 
-impl TryFrom<&Path> for VaultEntry {
-    type Error = Error;
-    fn try_from(value: &Path) -> Result<Self> {
-        let name = value
-            .with_extension("")
+/// An entry of a directory tree that can be sorted with [`SortOrder::sort`], e.g. a
+/// [`VaultEntry`].
+pub trait SortEntry: Sized {
+    /// Returns the name the entries are ordered by.
+    fn name(&self) -> &str;
+
+    /// Returns the path the modification time of the entry is read from.
+    fn path(&self) -> &Path;
+
+    /// Returns the entries of a directory, or `None` if the entry is a file.
+    fn entries_mut(&mut self) -> Option<&mut [Self]>;
+
+    /// Returns `true` if the entry is a directory.
+    fn is_dir(&self) -> bool;
+}
+
+impl SortEntry for VaultEntry {
+    fn name(&self) -> &str {
+        VaultEntry::name(self)
+    }
+
+    fn path(&self) -> &Path {
+        VaultEntry::path(self)
+    }
+
+    fn entries_mut(&mut self) -> Option<&mut [Self]> {
+        match self {
+            Self::Directory { entries, .. } => Some(entries),
+            Self::File(_) => None,
+        }
+    }
+
+    fn is_dir(&self) -> bool {
+        self.kind().is_none()
+    }
+}
+
+/// The order of vault entries, see [`Vault::entries_sorted`](super::Vault::entries_sorted).
+///
+/// Names are compared case-insensitively. Entries that only differ by the case of their names,
+/// or that were modified at the same time, are ordered by name.
+#[derive(Debug, Default, Copy, Clone, PartialEq)]
+pub enum SortOrder {
+    /// Names from A to Z.
+    #[default]
+    NameAsc,
+    /// Names from Z to A.
+    NameDesc,
+    /// The least recently modified entries first.
+    ModifiedAsc,
+    /// The most recently modified entries first.
+    ModifiedDesc,
+    /// Directories before files, both from A to Z.
+    FileType,
+}
+
+impl SortOrder {
+    /// Compares names from A to Z case-insensitively, and by case if the names are otherwise
+    /// equal.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::cmp::Ordering;
+    /// use basalt_core::obsidian::SortOrder;
+    ///
+    /// assert_eq!(SortOrder::compare_names("apple", "Banana"), Ordering::Less);
+    /// assert_eq!(SortOrder::compare_names("Apple", "apple"), Ordering::Less);
+    /// ```
+    pub fn compare_names(a: &str, b: &str) -> Ordering {
+        a.to_lowercase()
+            .cmp(&b.to_lowercase())
+            .then_with(|| a.cmp(b))
+    }
+
+    /// Sorts the entries, and the entries of the directories among them, in this order.
+    ///
+    /// The modified orders read the modification times from the file system. Entries whose
+    /// modification time cannot be read are treated as the least recently modified.
+    pub fn sort<T: SortEntry>(self, entries: &mut [T]) {
+        let modified = |entry: &T| -> Option<SystemTime> {
+            fs::metadata(entry.path())
+                .and_then(|metadata| metadata.modified())
+                .ok()
+        };
+
+        // The sorts are stable, so sorting by name first orders the entries with equal keys
+        entries.sort_by(|a, b| Self::compare_names(a.name(), b.name()));
+
+        match self {
+            Self::NameAsc => {}
+            Self::NameDesc => entries.reverse(),
+            Self::ModifiedAsc => entries.sort_by_cached_key(modified),
+            Self::ModifiedDesc => entries.sort_by_cached_key(|entry| Reverse(modified(entry))),
+            Self::FileType => entries.sort_by_key(|entry| !entry.is_dir()),
+        }
+
+        entries.iter_mut().for_each(|entry| {
+            if let Some(entries) = entry.entries_mut() {
+                self.sort(entries);
+            }
+        });
+    }
+}
+
+/// Options for reading a directory tree with [`VaultEntry::read_options`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct ReadOptions {
+    /// Whether to follow symbolic links to files and directories. Links to directories that
+    /// contain the link are skipped regardless, since following them would never end.
+    pub follow_symlinks: bool,
+
+    /// How many directory levels below the path are read, or `None` to read all of them.
+    /// Directories below the limit are included without their entries.
+    pub max_depth: Option<usize>,
+}
+
+impl Default for ReadOptions {
+    fn default() -> Self {
+        Self {
+            follow_symlinks: true,
+            max_depth: None,
+        }
+    }
+}
+
+impl VaultEntry {
+    /// Reads the file or the directory tree at the path into a [`VaultEntry`].
+    ///
+    /// Entries that cannot be read, such as unreadable subdirectories or broken symbolic links,
+    /// do not stop the reading. Their errors are returned next to the entry, and unreadable
+    /// directories are included without their entries.
+    ///
+    /// Returns an [`Error`] only if the path itself has no file name.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::path::Path;
+    /// use basalt_core::obsidian::{ReadOptions, VaultEntry};
+    ///
+    /// let options = ReadOptions {
+    ///     max_depth: Some(1),
+    ///     ..Default::default()
+    /// };
+    ///
+    /// let (entry, errors) = VaultEntry::read_options(Path::new("path/to/my_vault"), &options)?;
+    ///
+    /// assert_eq!(entry.name(), "my_vault");
+    /// assert_eq!(errors.len(), 1);
+    /// # Ok::<(), basalt_core::obsidian::Error>(())
+    /// ```
+    pub fn read_options(path: &Path, options: &ReadOptions) -> Result<(Self, Vec<Error>)> {
+        let mut errors = vec![];
+        let entry = Self::read(path, options, 0, &mut vec![], &mut errors)?;
+        Ok((entry, errors))
+    }
+
+    /// Reads the entry at the path. `ancestors` holds the canonical paths of the directories
+    /// being read, which the path is a descendant of.
+    fn read(
+        path: &Path,
+        options: &ReadOptions,
+        depth: usize,
+        ancestors: &mut Vec<PathBuf>,
+        errors: &mut Vec<Error>,
+    ) -> Result<Self> {
+        let kind = FileKind::from_path(path);
+
+        // Attachments keep their extension, so that e.g. `image.png` and `image.jpg` can be told
+        // apart.
+        let name = if path.is_file() && !kind.is_markdown() {
+            path.to_path_buf()
+        } else {
+            path.with_extension("")
+        };
+
+        let name = name
             .file_name()
             .map(|file_name| file_name.to_string_lossy().into_owned())
-            .ok_or_else(|| Error::EmptyFileName(value.to_path_buf()))?;
+            .ok_or_else(|| Error::EmptyFileName(path.to_path_buf()))?;
 
-        if value.is_file() {
-            Ok(VaultEntry::File(Note {
-                name,
-                path: value.to_path_buf(),
-            }))
-        } else {
-            Ok(VaultEntry::Directory {
+        if path.is_file() {
+            return Ok(VaultEntry::File(Note {
                 name,
-                path: value.to_path_buf(),
-                entries: read_dir(value)
-                    .into_iter()
-                    .flatten()
-                    .filter_map(|entry| {
-                        // NOTE: Might want to propagate the try_into errors further up
-                        entry
-                            .map_err(Error::from)
-                            .and_then(|entry| entry.path().as_path().try_into())
-                            .ok()
-                    })
-                    .collect(),
-            })
+                path: path.to_path_buf(),
+            }));
+        }
+
+        let mut entries = vec![];
+
+        if options.max_depth.is_none_or(|max_depth| depth < max_depth) {
+            match read_dir(path) {
+                Ok(dir) => {
+                    ancestors.push(path.canonicalize().unwrap_or_else(|_| path.to_path_buf()));
+
+                    for entry in dir {
+                        let entry = match entry {
+                            Ok(entry) => entry,
+                            Err(error) => {
+                                errors.push(Error::from_io(error, path));
+                                continue;
+                            }
+                        };
+
+                        let entry_path = entry.path();
+                        let is_symlink = entry
+                            .file_type()
+                            .is_ok_and(|file_type| file_type.is_symlink());
+
+                        if is_symlink && !options.follow_symlinks {
+                            continue;
+                        }
+
+                        if !entry_path.exists() {
+                            errors.push(Error::PathNotFound(entry_path.display().to_string()));
+                            continue;
+                        }
+
+                        let is_cycle = entry_path.is_dir()
+                            && entry_path
+                                .canonicalize()
+                                .is_ok_and(|canonical| ancestors.contains(&canonical));
+
+                        if is_cycle {
+                            continue;
+                        }
+
+                        match Self::read(&entry_path, options, depth + 1, ancestors, errors) {
+                            Ok(entry) => entries.push(entry),
+                            Err(error) => errors.push(error),
+                        }
+                    }
+
+                    ancestors.pop();
+                }
+                Err(error) => errors.push(Error::from_io(error, path)),
+            }
         }
+
+        Ok(VaultEntry::Directory {
+            name,
+            path: path.to_path_buf(),
+            entries,
+        })
+    }
+}
+
+/// Reads the path with the default [`ReadOptions`] and drops the errors of the entries that
+/// could not be read.
+impl TryFrom<&Path> for VaultEntry {
+    type Error = Error;
+    fn try_from(value: &Path) -> Result<Self> {
+        Self::read_options(value, &ReadOptions::default()).map(|(entry, _)| entry)
     }
 }
 
@@ -84,3 +324,124 @@ impl FindNote for VaultEntry {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use std::fs;
+    #[cfg(unix)]
+    use std::os::unix::fs::symlink;
+
+    use tempfile::tempdir;
+
+    use super::*;
+
+    /// Returns the entry names as `parent/child` paths, sorted.
+    fn names(entry: &VaultEntry) -> Vec<String> {
+        fn collect(entry: &VaultEntry, prefix: &str, names: &mut Vec<String>) {
+            let name = format!("{prefix}{}", entry.name());
+            if let VaultEntry::Directory { entries, .. } = entry {
+                entries
+                    .iter()
+                    .for_each(|entry| collect(entry, &format!("{name}/"), names));
+            }
+            names.push(name);
+        }
+
+        let mut names = vec![];
+        if let VaultEntry::Directory { entries, .. } = entry {
+            entries
+                .iter()
+                .for_each(|entry| collect(entry, "", &mut names));
+        }
+        names.sort();
+        names
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_read_symlinks() {
+        let dir = tempdir().unwrap();
+        let vault = dir.path().join("Vault");
+        fs::create_dir_all(vault.join("Projects")).unwrap();
+        fs::write(vault.join("Projects/Plan.md"), "# Plan").unwrap();
+        fs::write(dir.path().join("Outside.md"), "# Outside").unwrap();
+
+        symlink("..", vault.join("Projects/loop")).unwrap();
+        symlink(&vault, vault.join("Projects/vault")).unwrap();
+        symlink("Projects", vault.join("Linked")).unwrap();
+        symlink(dir.path().join("Outside.md"), vault.join("Outside.md")).unwrap();
+
+        let (entry, errors) = VaultEntry::read_options(&vault, &ReadOptions::default()).unwrap();
+        assert!(errors.is_empty(), "{errors:?}");
+        assert_eq!(
+            names(&entry),
+            [
+                "Linked",
+                "Linked/Plan",
+                "Outside",
+                "Projects",
+                "Projects/Plan"
+            ]
+        );
+
+        let options = ReadOptions {
+            follow_symlinks: false,
+            ..Default::default()
+        };
+        let (entry, errors) = VaultEntry::read_options(&vault, &options).unwrap();
+        assert!(errors.is_empty(), "{errors:?}");
+        assert_eq!(names(&entry), ["Projects", "Projects/Plan"]);
+
+        assert_eq!(
+            names(&VaultEntry::try_from(vault.as_path()).unwrap()),
+            names(
+                &VaultEntry::read_options(&vault, &ReadOptions::default())
+                    .unwrap()
+                    .0
+            )
+        );
+    }
+
+    #[test]
+    fn test_read_max_depth() {
+        let dir = tempdir().unwrap();
+        fs::create_dir_all(dir.path().join("a/b/c")).unwrap();
+        fs::write(dir.path().join("a/b/c/Deep.md"), "").unwrap();
+
+        let tests = [
+            (Some(0), vec![]),
+            (Some(1), vec!["a"]),
+            (Some(2), vec!["a", "a/b"]),
+            (None, vec!["a", "a/b", "a/b/c", "a/b/c/Deep"]),
+        ];
+
+        tests.into_iter().for_each(|(max_depth, expected)| {
+            let options = ReadOptions {
+                max_depth,
+                ..Default::default()
+            };
+            let (entry, _) = VaultEntry::read_options(dir.path(), &options).unwrap();
+            assert_eq!(names(&entry), expected, "{max_depth:?}");
+        });
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_read_errors() {
+        let dir = tempdir().unwrap();
+        fs::write(dir.path().join("Note.md"), "").unwrap();
+        symlink("Missing.md", dir.path().join("Broken.md")).unwrap();
+
+        let (entry, errors) =
+            VaultEntry::read_options(dir.path(), &ReadOptions::default()).unwrap();
+        assert_eq!(names(&entry), ["Note"]);
+        assert!(matches!(
+            errors.as_slice(),
+            [Error::PathNotFound(path)] if path.ends_with("Broken.md")
+        ));
+
+        let (_, errors) =
+            VaultEntry::read_options(&dir.path().join("Missing"), &ReadOptions::default()).unwrap();
+        assert!(matches!(errors.as_slice(), [Error::PathNotFound(_)]));
+    }
+}
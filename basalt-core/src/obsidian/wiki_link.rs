@@ -0,0 +1,321 @@
+use std::{
+    ops::Range,
+    path::{Component, Path},
+};
+
+use super::{Note, VaultEntry};
+
+/// A link to another note in the vault, written as `[[Target]]`.
+///
+/// The target can be followed by a heading and an alias, e.g. `[[Target#Heading|Alias]]`.
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct WikiLink {
+    /// The name or path of the linked note without the `.md` extension.
+    pub target: String,
+
+    /// The heading within the linked note, if any.
+    pub heading: Option<String>,
+
+    /// The text shown instead of the target, if any.
+    pub alias: Option<String>,
+}
+
+impl WikiLink {
+    /// Parses the contents between the brackets of a wiki link.
+    ///
+    /// Returns [`None`] if the target is empty.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use basalt_core::obsidian::WikiLink;
+    ///
+    /// let link = WikiLink::parse("Notes/Target#Heading|Alias").unwrap();
+    ///
+    /// assert_eq!(link.target, "Notes/Target");
+    /// assert_eq!(link.heading.as_deref(), Some("Heading"));
+    /// assert_eq!(link.alias.as_deref(), Some("Alias"));
+    /// ```
+    pub fn parse(text: &str) -> Option<Self> {
+        let (link, alias) = match text.split_once('|') {
+            Some((link, alias)) => (link, Some(alias.to_string())),
+            None => (text, None),
+        };
+
+        let (target, heading) = match link.split_once('#') {
+            Some((target, heading)) => (target, Some(heading.to_string())),
+            None => (link, None),
+        };
+
+        let target = target.trim();
+        let target = target.strip_suffix(".md").unwrap_or(target);
+
+        (!target.is_empty()).then(|| Self {
+            target: target.to_string(),
+            heading,
+            alias,
+        })
+    }
+
+    /// Returns all wiki links in the given text in the order they appear.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use basalt_core::obsidian::WikiLink;
+    ///
+    /// let links = WikiLink::find_all("See [[First]] and [[Second|the second]]");
+    ///
+    /// assert_eq!(
+    ///     links.iter().map(|(_, link)| link.target.as_str()).collect::<Vec<_>>(),
+    ///     ["First", "Second"]
+    /// );
+    /// ```
+    pub fn find_all(text: &str) -> Vec<(Range<usize>, Self)> {
+        let mut links = vec![];
+        let mut offset = 0;
+
+        while let Some(start) = text[offset..].find("[[").map(|index| offset + index) {
+            let Some(end) = text[start + 2..]
+                .find("]]")
+                .map(|index| start + 2 + index + 2)
+            else {
+                break;
+            };
+
+            let inner = &text[start + 2..end - 2];
+
+            // A nested opening bracket means this was not the start of the link
+            if let Some(index) = inner.rfind("[[") {
+                offset = start + 2 + index;
+                continue;
+            }
+
+            if let Some(link) = WikiLink::parse(inner) {
+                links.push((start..end, link));
+            }
+
+            offset = end;
+        }
+
+        links
+    }
+
+    /// Returns the wiki link at the given character column of the line.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use basalt_core::obsidian::WikiLink;
+    ///
+    /// let line = "See [[Target]] for details";
+    ///
+    /// assert_eq!(WikiLink::at(line, 6).map(|link| link.target), Some("Target".into()));
+    /// assert_eq!(WikiLink::at(line, 0), None);
+    /// ```
+    pub fn at(line: &str, col: usize) -> Option<Self> {
+        let byte_index = line
+            .char_indices()
+            .nth(col)
+            .map_or(line.len(), |(index, _)| index);
+
+        WikiLink::find_all(line)
+            .into_iter()
+            .find(|(range, _)| range.contains(&byte_index))
+            .map(|(_, link)| link)
+    }
+
+    /// Resolves the link to a note in the given vault entries.
+    ///
+    /// The target is matched against the end of the note paths, so both `[[Note]]` and
+    /// `[[Folder/Note]]` resolve to `Folder/Note.md`. The match is case-insensitive. When
+    /// multiple notes match, the note with the shortest path is chosen, like in Obsidian. Notes
+    /// with equally short paths are ordered by path.
+    ///
+    /// If no note path matches, the target is matched case-insensitively against the aliases of
+    /// the notes, see [`Note::aliases`]. The notes are read from disk in this case.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use basalt_core::obsidian::{Note, VaultEntry, WikiLink};
+    ///
+    /// let entries = vec![VaultEntry::File(Note {
+    ///     name: "Target".into(),
+    ///     path: "vault/Target.md".into(),
+    /// })];
+    ///
+    /// let link = WikiLink::parse("Target").unwrap();
+    ///
+    /// assert_eq!(link.resolve(&entries).map(|note| note.name.as_str()), Some("Target"));
+    /// ```
+    pub fn resolve<'a>(&self, entries: &'a [VaultEntry]) -> Option<&'a Note> {
+        let target = components(Path::new(&self.target));
+        let notes = notes(entries);
+
+        notes
+            .iter()
+            .filter(|note| {
+                let path = components(&note.path.with_extension(""));
+                path.len() >= target.len() && path[path.len() - target.len()..] == target[..]
+            })
+            .min_by_key(|note| (note.path.components().count(), note.path.clone()))
+            .or_else(|| {
+                let alias = self.target.to_lowercase();
+                notes.iter().find(|note| {
+                    note.kind().is_markdown()
+                        && note.aliases().is_ok_and(|aliases| {
+                            aliases.iter().any(|other| other.to_lowercase() == alias)
+                        })
+                })
+            })
+            .copied()
+    }
+}
+
+/// Returns the lowercased normal components of the path.
+fn components(path: &Path) -> Vec<String> {
+    path.components()
+        .filter_map(|component| match component {
+            Component::Normal(name) => Some(name.to_string_lossy().to_lowercase()),
+            _ => None,
+        })
+        .collect()
+}
+
+fn notes(entries: &[VaultEntry]) -> Vec<&Note> {
+    entries
+        .iter()
+        .flat_map(|entry| match entry {
+            VaultEntry::File(note) => vec![note],
+            VaultEntry::Directory { entries, .. } => notes(entries),
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use std::fs;
+
+    use tempfile::tempdir;
+
+    use super::*;
+
+    fn note(path: &str) -> VaultEntry {
+        VaultEntry::File(Note {
+            name: Path::new(path)
+                .file_stem()
+                .unwrap()
+                .to_string_lossy()
+                .to_string(),
+            path: path.into(),
+        })
+    }
+
+    fn directory(path: &str, entries: Vec<VaultEntry>) -> VaultEntry {
+        VaultEntry::Directory {
+            name: path.rsplit('/').next().unwrap().into(),
+            path: path.into(),
+            entries,
+        }
+    }
+
+    #[test]
+    fn test_find_all() {
+        let tests = [
+            ("No links", vec![]),
+            ("[[Target]]", vec![(0..10, "Target")]),
+            ("[[A]] and [[B#Heading]]", vec![(0..5, "A"), (10..23, "B")]),
+            ("[[Broken [[Target]]", vec![(9..19, "Target")]),
+            ("[[]] and [[Unclosed", vec![]),
+        ];
+
+        tests.into_iter().for_each(|(text, expected)| {
+            assert_eq!(
+                WikiLink::find_all(text)
+                    .into_iter()
+                    .map(|(range, link)| (range, link.target))
+                    .collect::<Vec<_>>(),
+                expected
+                    .into_iter()
+                    .map(|(range, target)| (range, target.to_string()))
+                    .collect::<Vec<_>>(),
+                "{text}"
+            )
+        });
+    }
+
+    #[test]
+    fn test_resolve() {
+        let entries = vec![
+            note("vault/Note.md"),
+            note("vault/Target.md"),
+            directory(
+                "vault/Projects",
+                vec![
+                    note("vault/Projects/Note.md"),
+                    note("vault/Projects/Plan.md"),
+                    directory(
+                        "vault/Projects/Old",
+                        vec![note("vault/Projects/Old/Plan.md")],
+                    ),
+                ],
+            ),
+            directory("vault/Archive", vec![note("vault/Archive/Draft.md")]),
+            directory("vault/Drafts", vec![note("vault/Drafts/Draft.md")]),
+        ];
+
+        let tests = [
+            ("Target", Some("vault/Target.md")),
+            ("target.md", Some("vault/Target.md")),
+            // Ambiguous names resolve to the shortest path
+            ("Note", Some("vault/Note.md")),
+            ("Plan", Some("vault/Projects/Plan.md")),
+            // Equally short paths resolve in path order
+            ("Draft", Some("vault/Archive/Draft.md")),
+            ("Projects/Note", Some("vault/Projects/Note.md")),
+            ("Old/Plan", Some("vault/Projects/Old/Plan.md")),
+            ("Missing", None),
+            ("ject/Note", None),
+        ];
+
+        tests.into_iter().for_each(|(target, expected)| {
+            assert_eq!(
+                WikiLink::parse(target)
+                    .unwrap()
+                    .resolve(&entries)
+                    .map(|note| note.path.to_string_lossy().to_string()),
+                expected.map(String::from),
+                "{target}"
+            )
+        });
+    }
+
+    #[test]
+    fn test_resolve_alias() {
+        let dir = tempdir().unwrap();
+        [
+            ("Basalt.md", "---\naliases: [Basalt TUI, bt]\n---\n# Basalt"),
+            ("bt.md", "# Notes named like an alias"),
+            ("Other.md", "# Other"),
+        ]
+        .iter()
+        .for_each(|(name, content)| fs::write(dir.path().join(name), content).unwrap());
+
+        let entries = ["Basalt.md", "bt.md", "Other.md"]
+            .map(|name| note(&dir.path().join(name).to_string_lossy()))
+            .to_vec();
+
+        let resolve = |target| {
+            WikiLink::parse(target)
+                .unwrap()
+                .resolve(&entries)
+                .map(|note| note.name.clone())
+        };
+
+        assert_eq!(resolve("basalt tui"), Some("Basalt".into()));
+        // Note names take precedence over aliases
+        assert_eq!(resolve("bt"), Some("bt".into()));
+        assert_eq!(resolve("Missing"), None);
+    }
+}
@@ -1 +1,4 @@
+#![warn(clippy::redundant_clone)]
+
 pub mod markdown;
+pub mod stylized_text;
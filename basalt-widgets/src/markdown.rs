@@ -1,5 +1,9 @@
+mod highlight;
+pub mod render;
 mod state;
 mod view;
 
+pub use highlight::highlight;
+pub use render::{render_nodes, Theme};
 pub use state::MarkdownViewState;
 pub use view::MarkdownView;
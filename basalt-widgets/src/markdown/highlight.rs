@@ -0,0 +1,77 @@
+//! Syntax highlighting for code blocks using [`syntect`] with the syntaxes and themes bundled
+//! with it.
+
+use once_cell::sync::Lazy;
+use ratatui::{
+    style::{Color, Modifier, Style},
+    text::Span,
+};
+use syntect::{
+    easy::HighlightLines,
+    highlighting::{self, FontStyle, ThemeSet},
+    parsing::SyntaxSet,
+};
+
+static SYNTAX_SET: Lazy<SyntaxSet> = Lazy::new(SyntaxSet::load_defaults_newlines);
+static THEME_SET: Lazy<ThemeSet> = Lazy::new(ThemeSet::load_defaults);
+
+/// The bundled theme that provides the token colors. The background of the theme is not used, so
+/// that code blocks keep their own background color.
+const THEME: &str = "base16-ocean.dark";
+
+fn style(style: highlighting::Style) -> Style {
+    let highlighting::Color { r, g, b, .. } = style.foreground;
+
+    [
+        (FontStyle::BOLD, Modifier::BOLD),
+        (FontStyle::ITALIC, Modifier::ITALIC),
+        (FontStyle::UNDERLINE, Modifier::UNDERLINED),
+    ]
+    .into_iter()
+    .filter(|(font_style, _)| style.font_style.contains(*font_style))
+    .fold(
+        Style::new().fg(Color::Rgb(r, g, b)),
+        |style, (_, modifier)| style.add_modifier(modifier),
+    )
+}
+
+/// Highlights the code with the syntax of the language and returns the styled spans of each line.
+/// The code is split into lines at `\n`.
+///
+/// The language is matched against the file extensions and names of the syntaxes, e.g. `rs` and
+/// `rust` both find the Rust syntax. Returns [`None`] if no syntax matches the language, in which
+/// case the code should be rendered without highlighting.
+///
+/// # Example
+///
+/// ```
+/// use basalt_widgets::markdown::highlight;
+///
+/// let lines = highlight("fn main() {}", "rust").unwrap();
+/// assert_eq!(lines.len(), 1);
+/// assert!(lines[0].len() > 1);
+///
+/// assert_eq!(highlight("fn main() {}", "unknown"), None);
+/// ```
+pub fn highlight(code: &str, lang: &str) -> Option<Vec<Vec<Span<'static>>>> {
+    let syntax = SYNTAX_SET.find_syntax_by_token(lang)?;
+    let mut highlighter = HighlightLines::new(syntax, THEME_SET.themes.get(THEME)?);
+
+    code.split('\n')
+        .map(|line| {
+            // The syntaxes are loaded for lines that end with a line break
+            let line = format!("{line}\n");
+            let ranges = highlighter.highlight_line(&line, &SYNTAX_SET).ok()?;
+
+            Some(
+                ranges
+                    .into_iter()
+                    .map(|(token_style, text)| {
+                        Span::styled(text.trim_end_matches('\n').to_string(), style(token_style))
+                    })
+                    .filter(|span| !span.content.is_empty())
+                    .collect(),
+            )
+        })
+        .collect()
+}
@@ -0,0 +1,505 @@
+//! Conversion of Markdown nodes into styled [`ratatui::text::Line`] values.
+//!
+//! [`render_nodes`] renders the nodes of [`basalt_core::markdown`] in the style of the Basalt note
+//! editor. The functions for the individual elements, like [`heading`] and [`code_block`], are
+//! public as well, so that renderers with their own node types look the same.
+use std::mem;
+
+use ratatui::{
+    style::{Color, Modifier, Stylize},
+    text::{Line, Span},
+};
+use textwrap::core::display_width;
+
+use basalt_core::markdown::{self, BlockQuoteKind, HeadingLevel, ItemKind};
+
+use crate::stylized_text::{stylize, FontStyle};
+
+use super::highlight::highlight;
+
+/// The colors used to render Markdown.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Theme {
+    pub heading1: Color,
+    pub heading2: Color,
+    pub heading3: Color,
+    pub heading4: Color,
+    pub heading5: Color,
+    pub heading6: Color,
+    /// Background of code blocks.
+    pub code_bg: Color,
+    /// The `┃` bar in front of block quotes.
+    pub quote_bar: Color,
+    /// Callouts with a custom type, e.g. `[!bug]`. The other callout types have their own accent
+    /// colors, see [`callout_color`].
+    pub callout: Color,
+    /// The checkbox of completed tasks.
+    pub task_done: Color,
+}
+
+impl Default for Theme {
+    fn default() -> Self {
+        Self {
+            heading1: Color::Reset,
+            heading2: Color::Yellow,
+            heading3: Color::Cyan,
+            heading4: Color::Magenta,
+            heading5: Color::Reset,
+            heading6: Color::Reset,
+            code_bg: Color::Black,
+            quote_bar: Color::Magenta,
+            callout: Color::Gray,
+            task_done: Color::Magenta,
+        }
+    }
+}
+
+/// Returns the lines that end a block. Only top-level blocks are followed by an empty line, the
+/// blocks inside block quotes are separated by the bar instead.
+fn block_end<'a>(prefix: &Span) -> Vec<Line<'a>> {
+    if prefix.content.is_empty() {
+        [Line::default()].to_vec()
+    } else {
+        [].to_vec()
+    }
+}
+
+/// Renders a heading that fills the width with its underline for the first two levels.
+pub fn heading<'a>(
+    level: HeadingLevel,
+    text: String,
+    width: usize,
+    theme: &Theme,
+) -> Vec<Line<'a>> {
+    match level {
+        HeadingLevel::H1 => [
+            Line::default(),
+            Line::from(text.to_uppercase())
+                .italic()
+                .bold()
+                .fg(theme.heading1),
+            Line::from("▀".repeat(width)).fg(theme.heading1),
+            Line::default(),
+        ]
+        .to_vec(),
+        HeadingLevel::H2 => [
+            Line::from(text).bold().fg(theme.heading2),
+            Line::from("═".repeat(width)).fg(theme.heading2),
+        ]
+        .to_vec(),
+        HeadingLevel::H3 => [
+            Line::from(["⬤  ".into(), text.bold()].to_vec()).fg(theme.heading3),
+            Line::default(),
+        ]
+        .to_vec(),
+        HeadingLevel::H4 => [
+            Line::from(["● ".into(), text.bold()].to_vec()).fg(theme.heading4),
+            Line::default(),
+        ]
+        .to_vec(),
+        HeadingLevel::H5 => [
+            Line::from(["◆ ".into(), stylize(&text, FontStyle::Script).into()].to_vec())
+                .fg(theme.heading5),
+            Line::default(),
+        ]
+        .to_vec(),
+        HeadingLevel::H6 => [
+            Line::from(["✺ ".into(), stylize(&text, FontStyle::Script).into()].to_vec())
+                .fg(theme.heading6),
+            Line::default(),
+        ]
+        .to_vec(),
+    }
+}
+
+/// Renders a paragraph wrapped at word boundaries to fit within the width together with the
+/// prefix.
+pub fn paragraph<'a>(text: String, width: usize, prefix: Span<'a>) -> Vec<Line<'a>> {
+    let end = block_end(&prefix);
+
+    wrap_with_prefix(text, width, prefix)
+        .into_iter()
+        .chain(end)
+        .collect()
+}
+
+/// Wraps the text at word boundaries and puts the prefix in front of each line.
+pub fn wrap_with_prefix<'a>(text: String, width: usize, prefix: Span<'a>) -> Vec<Line<'a>> {
+    let options = textwrap::Options::new(width.saturating_sub(prefix.width())).break_words(false);
+
+    textwrap::wrap(&text, &options)
+        .into_iter()
+        .map(|wrapped_line| {
+            Line::from([prefix.clone(), Span::from(wrapped_line.to_string())].to_vec())
+        })
+        .collect()
+}
+
+/// Splits the spans of a line into chunks that fit within the display width. The spans are split
+/// at any character and keep their style. Returns the line as is when the width is zero.
+pub fn wrap_spans(spans: Vec<Span<'_>>, width: usize) -> Vec<Vec<Span<'_>>> {
+    if width == 0 {
+        return vec![spans];
+    }
+
+    let mut chunks = vec![vec![]];
+    let mut used_width = 0;
+
+    spans.into_iter().for_each(|span| {
+        let mut content = String::new();
+
+        span.content.chars().for_each(|c| {
+            let char_width = display_width(c.encode_utf8(&mut [0; 4]));
+            if used_width + char_width > width && used_width > 0 {
+                if let Some(chunk) = chunks.last_mut() {
+                    if !content.is_empty() {
+                        chunk.push(Span::styled(mem::take(&mut content), span.style));
+                    }
+                }
+                chunks.push(vec![]);
+                used_width = 0;
+            }
+            content.push(c);
+            used_width += char_width;
+        });
+
+        if let Some(chunk) = chunks.last_mut() {
+            if !content.is_empty() {
+                chunk.push(Span::styled(content, span.style));
+            }
+        }
+    });
+
+    chunks
+}
+
+/// Renders a list item with a `-` marker, or with the number of the item in ordered lists.
+pub fn item<'a>(number: Option<u64>, content: Vec<Span<'a>>, prefix: Span<'a>) -> Line<'a> {
+    let marker = match number {
+        Some(number) => [number.to_string().dark_gray(), ". ".into()].to_vec(),
+        None => ["- ".dark_gray()].to_vec(),
+    };
+
+    Line::from(
+        [prefix]
+            .into_iter()
+            .chain(marker)
+            .chain(content)
+            .collect::<Vec<_>>(),
+    )
+}
+
+/// Renders a task with its checkbox. Tasks marked done with `- [x]` are crossed out. Any other
+/// item kind is rendered as an unchecked task.
+pub fn task<'a>(
+    kind: &ItemKind,
+    content: Vec<Span<'a>>,
+    prefix: Span<'a>,
+    theme: &Theme,
+) -> Line<'a> {
+    let checkbox = match kind {
+        ItemKind::HardChecked | ItemKind::Checked => "■ ".fg(theme.task_done),
+        _ => "□ ".dark_gray(),
+    };
+
+    let line = Line::from(
+        [prefix, checkbox]
+            .into_iter()
+            .chain(content)
+            .collect::<Vec<_>>(),
+    );
+
+    match kind {
+        ItemKind::HardChecked => line.dark_gray().add_modifier(Modifier::CROSSED_OUT),
+        _ => line,
+    }
+}
+
+/// Renders a code block with the code lines padded to the width, so that the background fills
+/// the whole block. Lines longer than the width are soft-wrapped when `wrap` is set and left for
+/// the caller to clip otherwise.
+///
+/// The code is syntax highlighted when the language is known, and rendered as plain text
+/// otherwise.
+pub fn code_block<'a>(
+    lang: Option<&str>,
+    code: &str,
+    width: usize,
+    wrap: bool,
+    theme: &Theme,
+) -> Vec<Line<'a>> {
+    // The code is padded with one column of whitespace on both sides
+    let code_width = width.saturating_sub(2);
+
+    let lines = lang
+        .and_then(|lang| highlight(code, lang))
+        .unwrap_or_else(|| {
+            code.split('\n')
+                .map(|line| vec![Span::from(line.to_string())])
+                .collect()
+        })
+        .into_iter()
+        .flat_map(|spans| {
+            if wrap {
+                wrap_spans(spans, code_width)
+            } else {
+                vec![spans]
+            }
+        })
+        .map(|spans| {
+            let used_width = spans.iter().map(Span::width).sum::<usize>();
+            let padding = code_width.saturating_sub(used_width);
+
+            Line::from(
+                [Span::from(" ")]
+                    .into_iter()
+                    .chain(spans)
+                    .chain([Span::from(" ".repeat(padding + 1))])
+                    .collect::<Vec<_>>(),
+            )
+            .bg(theme.code_bg)
+        });
+
+    [Line::from(" ".repeat(width)).bg(theme.code_bg)]
+        .into_iter()
+        .chain(lines)
+        .chain([Line::default()])
+        .collect()
+}
+
+/// Returns the symbol in front of the title of a callout, e.g. `☆` for `[!tip]`.
+pub fn callout_symbol(kind: &BlockQuoteKind) -> &'static str {
+    match kind {
+        BlockQuoteKind::Note => "ⓘ",
+        BlockQuoteKind::Tip => "☆",
+        BlockQuoteKind::Important => "‼",
+        BlockQuoteKind::Warning => "⚠",
+        BlockQuoteKind::Caution => "⊗",
+        BlockQuoteKind::Custom(_) => "●",
+    }
+}
+
+/// Returns the accent color of a callout. Callouts with a custom type use [`Theme::callout`].
+pub fn callout_color(kind: &BlockQuoteKind, theme: &Theme) -> Color {
+    match kind {
+        BlockQuoteKind::Note => Color::Blue,
+        BlockQuoteKind::Tip => Color::Green,
+        BlockQuoteKind::Important => Color::Magenta,
+        BlockQuoteKind::Warning => Color::Yellow,
+        BlockQuoteKind::Caution => Color::Red,
+        BlockQuoteKind::Custom(_) => theme.callout,
+    }
+}
+
+/// Returns the title line of a callout, e.g. `☆ Tip` for `[!tip]` or `● Bug` for `[!bug]`, in
+/// the given color.
+pub fn callout_title<'a>(kind: &BlockQuoteKind, prefix: &Span, color: Color) -> Line<'a> {
+    let label = match kind {
+        BlockQuoteKind::Custom(label) => label.clone(),
+        kind => kind.label().to_lowercase(),
+    };
+
+    let mut chars = label.chars();
+    let title = chars
+        .next()
+        .map(|first| first.to_uppercase().chain(chars).collect::<String>())
+        .unwrap_or_default();
+
+    Line::from(vec![
+        Span::from(prefix.to_string() + "┃ ").fg(color),
+        Span::from(format!("{} {title}", callout_symbol(kind)))
+            .fg(color)
+            .bold(),
+    ])
+}
+
+/// Renders a block quote from the already rendered blocks of its children. The children are
+/// separated by a line with only the bar.
+pub fn block_quote<'a>(
+    title: Option<Line<'a>>,
+    children: Vec<Vec<Line<'a>>>,
+    prefix: &Span<'a>,
+    bar_color: Color,
+) -> Vec<Line<'a>> {
+    let separator = Line::from(prefix.to_string() + "┃ ").fg(bar_color);
+
+    title
+        .into_iter()
+        .chain(
+            children.into_iter().enumerate().flat_map(|(i, lines)| {
+                (i != 0).then(|| separator.clone()).into_iter().chain(lines)
+            }),
+        )
+        .chain(block_end(prefix))
+        .collect()
+}
+
+fn text_to_spans<'a>(text: &markdown::Text) -> Vec<Span<'a>> {
+    text.into_iter()
+        .map(|text| {
+            let span = Span::from(text.content.clone());
+            match text.style {
+                Some(markdown::Style::Emphasis | markdown::Style::Italic) => span.italic(),
+                Some(markdown::Style::Strong | markdown::Style::Bold) => span.bold(),
+                Some(markdown::Style::Strikethrough) => span.crossed_out(),
+                Some(markdown::Style::Link { .. }) => span.underlined(),
+                Some(markdown::Style::Code) | None => span,
+            }
+        })
+        .collect()
+}
+
+fn text_to_string(text: &markdown::Text) -> String {
+    text.into_iter().map(|text| text.content.as_str()).collect()
+}
+
+/// Renders the nodes into blocks of lines. Consecutive list items form a single block. The
+/// `base_depth` is the depth of the nodes at the current level, e.g. the children of a block quote
+/// are one level deeper than the block quote.
+fn blocks<'a>(
+    nodes: &[markdown::Node],
+    width: usize,
+    theme: &Theme,
+    prefix: Span<'a>,
+    base_depth: usize,
+) -> Vec<Vec<Line<'a>>> {
+    let mut blocks: Vec<Vec<Line<'a>>> = vec![];
+
+    for (i, node) in nodes.iter().enumerate() {
+        match &node.markdown_node {
+            markdown::MarkdownNode::Paragraph { text } => {
+                blocks.push(paragraph(text_to_string(text), width, prefix.clone()))
+            }
+            markdown::MarkdownNode::Heading { level, text } => {
+                blocks.push(heading(level.clone(), text_to_string(text), width, theme))
+            }
+            markdown::MarkdownNode::CodeBlock { lang, text } => blocks.push(code_block(
+                lang.as_deref(),
+                &text_to_string(text),
+                width,
+                false,
+                theme,
+            )),
+            markdown::MarkdownNode::Item { kind, text } => {
+                // Nested list items are indented by two spaces for each level of nesting
+                let indent = "  ".repeat(node.depth.saturating_sub(base_depth + 1));
+                let item_prefix = Span::from(format!("{prefix}{indent}")).style(prefix.style);
+                let content = text_to_spans(text);
+
+                let line = match kind {
+                    Some(ItemKind::Ordered(number)) => item(Some(*number), content, item_prefix),
+                    Some(ItemKind::Unordered) | None => item(None, content, item_prefix),
+                    Some(kind) => task(kind, content, item_prefix, theme),
+                };
+
+                let is_item = |index: usize| {
+                    nodes.get(index).is_some_and(|node| {
+                        matches!(node.markdown_node, markdown::MarkdownNode::Item { .. })
+                    })
+                };
+
+                match blocks.last_mut() {
+                    Some(lines) if i > 0 && is_item(i - 1) => lines.push(line),
+                    _ => blocks.push(vec![line]),
+                }
+
+                if !is_item(i + 1) {
+                    if let Some(lines) = blocks.last_mut() {
+                        lines.extend(block_end(&prefix));
+                    }
+                }
+            }
+            markdown::MarkdownNode::BlockQuote { kind, nodes } => {
+                let bar_color = kind
+                    .as_ref()
+                    .map_or(theme.quote_bar, |kind| callout_color(kind, theme));
+
+                let title = kind
+                    .as_ref()
+                    .map(|kind| callout_title(kind, &prefix, bar_color));
+
+                let children = self::blocks(
+                    nodes,
+                    width,
+                    theme,
+                    Span::from(prefix.to_string() + "┃ ").fg(bar_color),
+                    node.depth + 1,
+                );
+
+                blocks.push(block_quote(title, children, &prefix, bar_color));
+            }
+        }
+    }
+
+    blocks
+}
+
+/// Renders the nodes into lines that fit within the width.
+///
+/// # Examples
+///
+/// ```
+/// use basalt_core::markdown;
+/// use basalt_widgets::markdown::render::{render_nodes, Theme};
+///
+/// let nodes = markdown::from_str("## Tasks\n\n- [ ] Water the plants");
+/// let lines = render_nodes(&nodes, 10, &Theme::default())
+///     .into_iter()
+///     .map(|line| line.to_string())
+///     .collect::<Vec<_>>();
+///
+/// assert_eq!(lines, ["Tasks", "══════════", "□ Water the plants", ""]);
+/// ```
+pub fn render_nodes(nodes: &[markdown::Node], width: usize, theme: &Theme) -> Vec<Line<'static>> {
+    blocks(nodes, width, theme, Span::default(), 0)
+        .into_iter()
+        .flatten()
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_estimated_render_height() {
+        let width = 24;
+        let rendered_height = |node: &markdown::Node| {
+            render_nodes(std::slice::from_ref(node), width, &Theme::default()).len()
+        };
+
+        let tests = [
+            "# Heading 1",
+            "## Heading 2",
+            "### Heading 3",
+            "###### Heading 6",
+            "Short paragraph",
+            "A paragraph that wraps onto a second line",
+            "日本語の段落は二列の幅で数えられる",
+            "```rust\nfn main() {\n    println!(\"Hello\");\n}\n```",
+            "```\n```",
+            "> A quote",
+            "> [!tip]\n> A callout with two paragraphs\n>\n> Second",
+        ];
+
+        tests.into_iter().for_each(|source| {
+            markdown::from_str(source).iter().for_each(|node| {
+                assert_eq!(
+                    node.estimated_render_height(width),
+                    rendered_height(node),
+                    "{source:?}"
+                );
+            });
+        });
+
+        // Words that do not fill the lines wrap early, so the estimate can be off by a line
+        let node =
+            &markdown::from_str("Paragraphs wrap at word boundaries, which the estimate ignores")
+                [0];
+        assert!(
+            node.estimated_render_height(width)
+                .abs_diff(rendered_height(node))
+                <= 1
+        );
+    }
+}
@@ -1,3 +1,4 @@
+use basalt_core::markdown;
 use ratatui::widgets::ScrollbarState;
 
 #[derive(Clone, Debug, PartialEq, Default)]
@@ -10,6 +11,8 @@ pub struct Scrollbar {
 pub struct MarkdownViewState {
     pub(crate) text: String,
     pub(crate) scrollbar: Scrollbar,
+    /// The width the text was last rendered at, or `0` before the first render.
+    pub(crate) width: usize,
 }
 
 impl MarkdownViewState {
@@ -37,8 +40,18 @@ impl MarkdownViewState {
         }
     }
 
+    /// Scrolls down by the amount, stopping at the last line of the text once the view has been
+    /// rendered. The lines are estimated with [`markdown::Node::estimated_render_height`], so
+    /// that scrolling does not render the text.
     pub fn scroll_down(self, amount: usize) -> Self {
-        let new_position = self.scrollbar.position.saturating_add(amount);
+        let new_position = match self.width {
+            0 => self.scrollbar.position.saturating_add(amount),
+            width => self
+                .scrollbar
+                .position
+                .saturating_add(amount)
+                .min(self.estimated_line_count(width).saturating_sub(1)),
+        };
         let new_state = self.scrollbar.state.position(new_position);
 
         Self {
@@ -50,6 +63,14 @@ impl MarkdownViewState {
         }
     }
 
+    /// Returns the estimated number of lines the text takes up when rendered at the width.
+    fn estimated_line_count(&self, width: usize) -> usize {
+        markdown::from_str(&self.text)
+            .iter()
+            .map(|node| node.estimated_render_height(width))
+            .sum()
+    }
+
     pub fn set_text(self, text: String) -> Self {
         Self { text, ..self }
     }
@@ -61,3 +82,20 @@ impl MarkdownViewState {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_scroll_down() {
+        let state = MarkdownViewState::new("# Heading\n\nA paragraph");
+        assert_eq!(state.clone().scroll_down(10).scrollbar.position, 10);
+
+        // Once rendered, the heading and the paragraph take up 6 lines
+        let state = MarkdownViewState { width: 20, ..state };
+        assert_eq!(state.clone().scroll_down(3).scrollbar.position, 3);
+        assert_eq!(state.clone().scroll_down(10).scrollbar.position, 5);
+        assert_eq!(state.scroll_down(10).scroll_up(2).scrollbar.position, 3);
+    }
+}
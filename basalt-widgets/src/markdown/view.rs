@@ -6,52 +6,48 @@
 //! metadata.
 //!
 //! The module uses markdown parser [`basalt_core::markdown`] to produce
-//! [`basalt_core::markdown::Node`] values. Each node is converted to one or more
-//! [`ratatui::text::Line`] objects.
+//! [`basalt_core::markdown::Node`] values, which are converted to [`ratatui::text::Line`] objects
+//! with [`super::render::render_nodes`]. The output looks the same as the notes in Basalt.
 //!
 //! Example of rendered output
 //!
-//! ██ Headings
+//! Headings
+//! ════════════════════════════════════════════════════════════════
 //!
-//! █ This is a heading 1
+//! THIS IS A HEADING 1
+//! ▀▀▀▀▀▀▀▀▀▀▀▀▀▀▀▀▀▀▀▀▀▀▀▀▀▀▀▀▀▀▀▀▀▀▀▀▀▀▀▀▀▀▀▀▀▀▀▀▀▀▀▀▀▀▀▀▀▀▀▀▀▀▀▀
 //!
-//! ██ This is a heading 2
+//! This is a heading 2
+//! ════════════════════════════════════════════════════════════════
+//! ⬤  This is a heading 3
 //!
-//! ▓▓▓ This is a heading 3
-//!
-//! ▓▓▓▓ This is a heading 4
-//!
-//! ▓▓▓▓▓ This is a heading 5
-//!
-//! ░░░░░░ This is a heading 6
-//!
-//! ██ Quotes
+//! ● This is a heading 4
 //!
+//! Quotes
+//! ════════════════════════════════════════════════════════════════
 //! You can quote text by adding a > symbols before the text.
 //!
-//! ┃ Human beings face ever more complex and urgent problems, and their effectiveness in dealing with these problems is a matter that is critical to the stability and continued progress of society.
+//! ┃ Human beings face ever more complex and urgent problems, and
+//! ┃ their effectiveness in dealing with these problems is a matter
+//! ┃ that is critical to the stability and continued progress of
+//! ┃ society.
 //! ┃
 //! ┃ - Doug Engelbart, 1961
-//!
-//! ██ Bold, italics, highlights
-//!
-//! This line will not be bold
-//!
-//! \*\*This line will not be bold\*\*
 use ratatui::{
     buffer::Buffer,
     layout::Rect,
-    style::{Color, Modifier, Stylize},
-    text::{Line, Span},
     widgets::{
         self, Block, BorderType, Paragraph, ScrollbarOrientation, StatefulWidget,
         StatefulWidgetRef, Widget,
     },
 };
 
-use basalt_core::markdown::{self, HeadingLevel, ItemKind};
+use basalt_core::markdown;
 
-use super::state::MarkdownViewState;
+use super::{
+    render::{render_nodes, Theme},
+    state::MarkdownViewState,
+};
 
 /// A widget for rendering markdown text using [`MarkdownViewState`].
 ///
@@ -59,7 +55,7 @@ use super::state::MarkdownViewState;
 ///
 /// ```rust
 /// use basalt_core::markdown;
-/// use basalt_widgets::markdown::{MarkdownViewState, MarkdownView};
+/// use basalt_widgets::markdown::{render_nodes, MarkdownView, MarkdownViewState, Theme};
 /// use ratatui::prelude::*;
 /// use ratatui::widgets::StatefulWidgetRef;
 ///
@@ -71,151 +67,31 @@ use super::state::MarkdownViewState;
 ///
 /// MarkdownView.render_ref(area, &mut buffer, &mut state);
 ///
-/// let expected = [
-///   "╭──────────────────▲",
-///   "│█ Hello, world!   █",
-///   "│                  █",
-///   "│This is a test.   █",
-///   "│                  █",
-///   "│                  █",
-///   "│                  █",
-///   "│                  ║",
-///   "│                  ║",
-///   "╰──────────────────▼",
-/// ];
+/// let rows = (1..9)
+///     .map(|y| (1..19).map(|x| buffer[(x, y)].symbol()).collect::<String>())
+///     .collect::<Vec<_>>();
+///
+/// assert_eq!(rows[1], "HELLO, WORLD!     ");
+/// assert_eq!(rows[4], "This is a test.   ");
 ///
-/// // FIXME: Take styles into account
-/// // assert_eq!(buffer, Buffer::with_lines(expected));
+/// // The widget shows the lines of `render_nodes` inside its border
+/// let lines = render_nodes(&markdown::from_str(text), 18, &Theme::default());
+///
+/// lines.iter().zip(&rows).for_each(|(line, row)| {
+///     assert_eq!(format!("{:18}", line.to_string()), *row);
+/// });
 /// ```
 #[derive(Clone, Debug, PartialEq)]
 pub struct MarkdownView;
 
-impl MarkdownView {
-    fn heading(level: HeadingLevel, content: Vec<Span>) -> Line {
-        let prefix = match level {
-            HeadingLevel::H1 => Span::from("█ ").blue(),
-            HeadingLevel::H2 => Span::from("██ ").cyan(),
-            HeadingLevel::H3 => Span::from("▓▓▓ ").green(),
-            HeadingLevel::H4 => Span::from("▓▓▓▓ ").yellow(),
-            HeadingLevel::H5 => Span::from("▓▓▓▓▓ ").red(),
-            HeadingLevel::H6 => Span::from("░░░░░░ ").red(),
-        };
-        Line::from([prefix].into_iter().chain(content).collect::<Vec<_>>()).bold()
-    }
-
-    fn item<'a>(kind: Option<ItemKind>, content: Vec<Span<'a>>, prefix: Span<'a>) -> Line<'a> {
-        match kind {
-            Some(kind) => match kind {
-                ItemKind::Unchecked => Line::from(
-                    [prefix, "󰄱 ".black()]
-                        .into_iter()
-                        .chain(content)
-                        .collect::<Vec<_>>(),
-                ),
-                ItemKind::Checked => Line::from(
-                    [prefix, "󰄲 ".magenta()]
-                        .into_iter()
-                        .chain(content)
-                        .collect::<Vec<_>>(),
-                ),
-                ItemKind::HardChecked => Line::from(
-                    [prefix, "󰄲 ".magenta()]
-                        .into_iter()
-                        .chain(content)
-                        .collect::<Vec<_>>(),
-                )
-                .black()
-                .add_modifier(Modifier::CROSSED_OUT),
-                ItemKind::Ordered(num) => Line::from(
-                    [prefix, num.to_string().black(), ". ".into()]
-                        .into_iter()
-                        .chain(content)
-                        .collect::<Vec<_>>(),
-                ),
-                ItemKind::Unordered => Line::from(
-                    [prefix, "- ".black()]
-                        .into_iter()
-                        .chain(content)
-                        .collect::<Vec<_>>(),
-                ),
-            },
-            None => Line::from(
-                [prefix, "- ".black()]
-                    .into_iter()
-                    .chain(content)
-                    .collect::<Vec<_>>(),
-            ),
-        }
-    }
-
-    fn text_to_spans<'a>(text: markdown::Text) -> Vec<Span<'a>> {
-        text.into_iter()
-            .map(|text| Span::from(text.content))
-            .collect()
-    }
-
-    fn code_block<'a>(text: markdown::Text) -> Vec<Line<'a>> {
-        text.into_iter()
-            .flat_map(|text| {
-                text.content
-                    .clone()
-                    .split("\n")
-                    .map(String::from)
-                    .collect::<Vec<String>>()
-            })
-            .map(|text| Line::from(text).red().bg(Color::Rgb(10, 10, 10)))
-            .collect()
-    }
-
-    fn render_markdown<'a>(node: markdown::Node, prefix: Span<'a>) -> Vec<Line<'a>> {
-        match node.markdown_node {
-            markdown::MarkdownNode::Paragraph { text } => {
-                let mut spans = MarkdownView::text_to_spans(text);
-                spans.insert(0, prefix.clone());
-                vec![spans.into(), Line::from(prefix)]
-            }
-            markdown::MarkdownNode::Heading { level, text } => [
-                MarkdownView::heading(level, MarkdownView::text_to_spans(text)),
-                Line::default(),
-            ]
-            .to_vec(),
-            markdown::MarkdownNode::Item { kind, text } => [
-                MarkdownView::item(kind, MarkdownView::text_to_spans(text), prefix),
-                Line::default(),
-            ]
-            .to_vec(),
-            // TODO: Add lang support and syntax highlighting
-            markdown::MarkdownNode::CodeBlock { text, .. } => {
-                let mut lines = MarkdownView::code_block(text);
-                lines.insert(0, Line::default());
-                lines
-            }
-            // TODO: Support callout block quote types
-            markdown::MarkdownNode::BlockQuote { nodes, .. } => {
-                let mut lines = nodes
-                    .into_iter()
-                    .flat_map(|child| {
-                        MarkdownView::render_markdown(child, Span::from("┃ ").magenta())
-                    })
-                    .map(|line| line.dark_gray())
-                    .collect::<Vec<Line<'a>>>();
-
-                lines.push(Line::default());
-
-                lines
-            }
-        }
-    }
-}
-
 impl StatefulWidgetRef for MarkdownView {
     type State = MarkdownViewState;
 
     fn render_ref(&self, area: Rect, buf: &mut Buffer, state: &mut Self::State) {
-        let nodes = markdown::from_str(&state.text)
-            .into_iter()
-            .flat_map(|node| MarkdownView::render_markdown(node, Span::default()))
-            .collect::<Vec<Line<'_>>>();
+        // The border takes one column on both sides
+        let width = area.width.saturating_sub(2).into();
+        let nodes = render_nodes(&markdown::from_str(&state.text), width, &Theme::default());
+        state.width = width;
 
         let mut scroll_state = state.scrollbar.state.content_length(nodes.len());
 
@@ -233,3 +109,37 @@ impl StatefulWidgetRef for MarkdownView {
         );
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use indoc::indoc;
+    use insta::assert_snapshot;
+    use ratatui::{backend::TestBackend, Terminal};
+
+    use super::*;
+
+    #[test]
+    fn test_rendered_callouts() {
+        let text = indoc! { r#"## Callout Blocks
+
+        > [!tip]
+        >
+        >You can turn your quote into a [callout](https://help.obsidian.md/Editing+and+formatting/Callouts) by adding `[!info]` as the first line in a quote.
+        "#};
+
+        let mut terminal = Terminal::new(TestBackend::new(80, 10)).unwrap();
+
+        terminal
+            .draw(|frame| {
+                MarkdownView.render_ref(
+                    frame.area(),
+                    frame.buffer_mut(),
+                    &mut MarkdownViewState::new(text),
+                )
+            })
+            .unwrap();
+
+        assert!(terminal.backend().to_string().contains("☆ Tip"));
+        assert_snapshot!(terminal.backend());
+    }
+}
@@ -6,7 +6,7 @@
 //! # Examples
 //!
 //! ```
-//! use basalt_tui::stylized_text::{FontStyle, stylize};
+//! use basalt_widgets::stylized_text::{FontStyle, stylize};
 //!
 //! assert_eq!(stylize("My Heading", FontStyle::FrakturBold), "𝕸𝖞 𝕳𝖊𝖆𝖉𝖎𝖓𝖌");
 //! ```
@@ -16,6 +16,10 @@
 /// - BlackBoardBold (𝔹𝕝𝕒𝕔𝕜𝔹𝕠𝕒𝕣𝕕𝔹𝕠𝕝𝕕)
 /// - FrakturBold (𝕱𝖗𝖆𝖐𝖙𝖚𝖗𝕭𝖔𝖑𝖉)
 /// - Script (𝓢𝓬𝓻𝓲𝓹𝓽)
+/// - BoldSerif (𝐁𝐨𝐥𝐝𝐒𝐞𝐫𝐢𝐟)
+/// - ItalicSerif (𝐼𝑡𝑎𝑙𝑖𝑐𝑆𝑒𝑟𝑖𝑓)
+/// - BoldItalic (𝑩𝒐𝒍𝒅𝑰𝒕𝒂𝒍𝒊𝒄)
+/// - Monospace (𝙼𝚘𝚗𝚘𝚜𝚙𝚊𝚌𝚎)
 #[derive(Debug, Clone, Copy)]
 pub enum FontStyle {
     /// Blackboard Bold (Double-struck) style (e.g., 𝕋𝕚𝕥𝕝𝕖).
@@ -24,6 +28,14 @@ pub enum FontStyle {
     FrakturBold,
     /// Script style. (e.g., 𝓣𝓲𝓽𝓵𝓮)
     Script,
+    /// Bold serif style. (e.g., 𝐓𝐢𝐭𝐥𝐞)
+    BoldSerif,
+    /// Italic serif style, without digits. (e.g., 𝑇𝑖𝑡𝑙𝑒)
+    ItalicSerif,
+    /// Bold italic serif style, without digits. (e.g., 𝑻𝒊𝒕𝒍𝒆)
+    BoldItalic,
+    /// Monospace style. (e.g., 𝚃𝚒𝚝𝚕𝚎)
+    Monospace,
 }
 
 /// Stylizes the given input string using the specified [`FontStyle`].
@@ -34,11 +46,12 @@ pub enum FontStyle {
 /// # Examples
 ///
 /// ```
-/// use basalt_tui::stylized_text::{FontStyle, stylize};
+/// use basalt_widgets::stylized_text::{FontStyle, stylize};
 ///
 /// assert_eq!(stylize("Black Board Bold", FontStyle::BlackBoardBold), "𝔹𝕝𝕒𝕔𝕜 𝔹𝕠𝕒𝕣𝕕 𝔹𝕠𝕝𝕕");
 /// assert_eq!(stylize("Fraktur Bold", FontStyle::FrakturBold), "𝕱𝖗𝖆𝖐𝖙𝖚𝖗 𝕭𝖔𝖑𝖉");
 /// assert_eq!(stylize("Script", FontStyle::Script), "𝓢𝓬𝓻𝓲𝓹𝓽");
+/// assert_eq!(stylize("Monospace 42", FontStyle::Monospace), "𝙼𝚘𝚗𝚘𝚜𝚙𝚊𝚌𝚎 𝟺𝟸");
 /// ```
 pub fn stylize(input: &str, style: FontStyle) -> String {
     input.chars().map(|c| stylize_char(c, style)).collect()
@@ -78,6 +91,30 @@ fn stylize_char(c: char, style: FontStyle) -> char {
             '0'..='9' => char::from_u32(0x1D7CE + (c as u32 - '0' as u32)),
             _ => None,
         },
+        FontStyle::BoldSerif => match c {
+            'A'..='Z' => char::from_u32(0x1D400 + (c as u32 - 'A' as u32)),
+            'a'..='z' => char::from_u32(0x1D41A + (c as u32 - 'a' as u32)),
+            '0'..='9' => char::from_u32(0x1D7CE + (c as u32 - '0' as u32)),
+            _ => None,
+        },
+        FontStyle::ItalicSerif => match c {
+            // The italic small h was encoded before the rest of the alphabet
+            'h' => char::from_u32(0x210E),
+            'A'..='Z' => char::from_u32(0x1D434 + (c as u32 - 'A' as u32)),
+            'a'..='z' => char::from_u32(0x1D44E + (c as u32 - 'a' as u32)),
+            _ => None,
+        },
+        FontStyle::BoldItalic => match c {
+            'A'..='Z' => char::from_u32(0x1D468 + (c as u32 - 'A' as u32)),
+            'a'..='z' => char::from_u32(0x1D482 + (c as u32 - 'a' as u32)),
+            _ => None,
+        },
+        FontStyle::Monospace => match c {
+            'A'..='Z' => char::from_u32(0x1D670 + (c as u32 - 'A' as u32)),
+            'a'..='z' => char::from_u32(0x1D68A + (c as u32 - 'a' as u32)),
+            '0'..='9' => char::from_u32(0x1D7F6 + (c as u32 - '0' as u32)),
+            _ => None,
+        },
     }
     .unwrap_or(c)
 }
@@ -102,6 +139,22 @@ mod tests {
                 FontStyle::BlackBoardBold,
                 "𝔸𝔹ℂ𝔻𝔼𝔽𝔾ℍ𝕀𝕁𝕂𝕃𝕄ℕ𝕆ℙℚℝ𝕊𝕋𝕌𝕍𝕎𝕏𝕐ℤ𝕒𝕓𝕔𝕕𝕖𝕗𝕘𝕙𝕚𝕛𝕜𝕝𝕞𝕟𝕠𝕡𝕢𝕣𝕤𝕥𝕦𝕧𝕨𝕩𝕪𝕫𝟘𝟙𝟚𝟛𝟜𝟝𝟞𝟟𝟠𝟡",
             ),
+            (
+                FontStyle::BoldSerif,
+                "𝐀𝐁𝐂𝐃𝐄𝐅𝐆𝐇𝐈𝐉𝐊𝐋𝐌𝐍𝐎𝐏𝐐𝐑𝐒𝐓𝐔𝐕𝐖𝐗𝐘𝐙𝐚𝐛𝐜𝐝𝐞𝐟𝐠𝐡𝐢𝐣𝐤𝐥𝐦𝐧𝐨𝐩𝐪𝐫𝐬𝐭𝐮𝐯𝐰𝐱𝐲𝐳𝟎𝟏𝟐𝟑𝟒𝟓𝟔𝟕𝟖𝟗",
+            ),
+            (
+                FontStyle::ItalicSerif,
+                "𝐴𝐵𝐶𝐷𝐸𝐹𝐺𝐻𝐼𝐽𝐾𝐿𝑀𝑁𝑂𝑃𝑄𝑅𝑆𝑇𝑈𝑉𝑊𝑋𝑌𝑍𝑎𝑏𝑐𝑑𝑒𝑓𝑔ℎ𝑖𝑗𝑘𝑙𝑚𝑛𝑜𝑝𝑞𝑟𝑠𝑡𝑢𝑣𝑤𝑥𝑦𝑧0123456789",
+            ),
+            (
+                FontStyle::BoldItalic,
+                "𝑨𝑩𝑪𝑫𝑬𝑭𝑮𝑯𝑰𝑱𝑲𝑳𝑴𝑵𝑶𝑷𝑸𝑹𝑺𝑻𝑼𝑽𝑾𝑿𝒀𝒁𝒂𝒃𝒄𝒅𝒆𝒇𝒈𝒉𝒊𝒋𝒌𝒍𝒎𝒏𝒐𝒑𝒒𝒓𝒔𝒕𝒖𝒗𝒘𝒙𝒚𝒛0123456789",
+            ),
+            (
+                FontStyle::Monospace,
+                "𝙰𝙱𝙲𝙳𝙴𝙵𝙶𝙷𝙸𝙹𝙺𝙻𝙼𝙽𝙾𝙿𝚀𝚁𝚂𝚃𝚄𝚅𝚆𝚇𝚈𝚉𝚊𝚋𝚌𝚍𝚎𝚏𝚐𝚑𝚒𝚓𝚔𝚕𝚖𝚗𝚘𝚙𝚚𝚛𝚜𝚝𝚞𝚟𝚠𝚡𝚢𝚣𝟶𝟷𝟸𝟹𝟺𝟻𝟼𝟽𝟾𝟿",
+            ),
         ];
 
         tests
@@ -0,0 +1,48 @@
+use basalt_tui::note_editor::{Editor, EditorState};
+use criterion::{criterion_group, criterion_main, Criterion};
+use ratatui::{backend::TestBackend, widgets::StatefulWidget, Terminal};
+
+/// Returns a note of 5,000 blocks of headings, paragraphs, lists, code blocks and quotes.
+fn large_note() -> String {
+    (0..1_000)
+        .map(|i| {
+            format!(
+                "## Section {i}\n\n\
+                 A paragraph long enough to be wrapped over several lines of the pane, so that \
+                 rendering it has to break the text at word boundaries. Section {i}.\n\n\
+                 - First item\n- Second item\n  - [ ] Nested task\n\n\
+                 ```rust\nfn section_{i}() {{}}\n```\n\n\
+                 > [!tip]\n> Quoted text in section {i}\n\n"
+            )
+        })
+        .collect()
+}
+
+fn render_large_note(c: &mut Criterion) {
+    let mut terminal = Terminal::new(TestBackend::new(100, 40)).unwrap();
+    let mut state = EditorState::default().set_content(&large_note());
+
+    c.bench_function("render 5,000 blocks", |b| {
+        b.iter(|| {
+            terminal
+                .draw(|frame| {
+                    Editor::default().render(frame.area(), frame.buffer_mut(), &mut state)
+                })
+                .unwrap();
+        })
+    });
+
+    c.bench_function("scroll 5,000 blocks", |b| {
+        b.iter(|| {
+            state = std::mem::take(&mut state).scroll_down(1);
+            terminal
+                .draw(|frame| {
+                    Editor::default().render(frame.area(), frame.buffer_mut(), &mut state)
+                })
+                .unwrap();
+        })
+    });
+}
+
+criterion_group!(benches, render_large_note);
+criterion_main!(benches);
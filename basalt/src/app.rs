@@ -1,979 +1,6384 @@
-use basalt_core::obsidian::{Note, Vault, VaultEntry};
+use basalt_core::{
+    markdown,
+    obsidian::{self, Note, ObsidianConfig, ObsidianUri, ReadOptions, Vault, VaultEntry},
+};
 use ratatui::{
     buffer::Buffer,
-    crossterm::event::{self, Event, KeyEvent, KeyEventKind},
-    layout::{Constraint, Flex, Layout, Rect, Size},
-    widgets::{StatefulWidget, StatefulWidgetRef},
+    crossterm::{
+        event::{
+            self, DisableMouseCapture, EnableMouseCapture, Event, KeyCode, KeyEvent, KeyEventKind,
+            KeyModifiers, MouseButton, MouseEvent, MouseEventKind,
+        },
+        execute,
+    },
+    layout::{Constraint, Flex, Layout, Position, Rect, Size},
+    widgets::{ListState, StatefulWidget, StatefulWidgetRef, Widget},
     DefaultTerminal,
 };
 
-use std::{cell::RefCell, fmt::Debug, io::Result};
+use std::{
+    cell::RefCell,
+    collections::HashMap,
+    env,
+    fmt::Debug,
+    fs,
+    io::{self, Result},
+    panic,
+    path::{Path, PathBuf},
+    time::{Duration, Instant},
+};
 
 use crate::{
-    config::{self, Config},
+    clipboard::{self, Copied},
+    config::{self, Config, ConfigError, ConfigSection, KeyMatch, KeySequence},
+    confirm_modal::{ConfirmModal, ConfirmModalState},
+    error,
     explorer::{Explorer, ExplorerState},
     help_modal::{HelpModal, HelpModalState},
-    note_editor::{Editor, EditorState, Mode},
+    input_modal::{InputKind, InputModal, InputModalState},
+    note_editor::{Attachment, Editor, EditorState, Mode, Placeholder, ViewPosition},
     outline::{Outline, OutlineState},
+    recent_notes_modal::{RecentNote, RecentNotesModal, RecentNotesModalState},
+    session::{self, Session},
     splash::{Splash, SplashState},
     statusbar::{StatusBar, StatusBarState},
     stylized_text::{self, FontStyle},
-    text_counts::{CharCount, WordCount},
+    tab_bar::{Tab, TabBar},
+    tag_panel::{self as tags, TagPanel, TagPanelState},
+    text_counts::CharCount,
+    toast::{Toast, ToastKind, ToastPopup},
+    transition::{Transition, TransitionKind},
     vault_selector_modal::{VaultSelectorModal, VaultSelectorModalState},
 };
 
+#[cfg(test)]
+mod test_harness;
+
 const VERSION: &str = env!("CARGO_PKG_VERSION");
 
 const HELP_TEXT: &str = include_str!("./help.txt");
 
+/// How many recently opened notes are remembered.
+const RECENT_NOTES_LIMIT: usize = 20;
+
+/// How often a [`Message::Tick`] is emitted while an animation is running.
+const TICK_RATE: Duration = Duration::from_millis(50);
+
+/// Ctrl+C quits without asking to save unsaved changes when pressed twice within this duration.
+const INTERRUPT_TIMEOUT: Duration = Duration::from_secs(1);
+
+/// How long the next key of a key sequence, e.g. `space f`, is waited for.
+const KEY_SEQUENCE_TIMEOUT: Duration = Duration::from_millis(750);
+
+/// The interval of [`config::Autosave::IntervalSeconds`] doubles with every failed autosave, up to
+/// this many times.
+const MAX_AUTOSAVE_BACKOFF: u32 = 5;
+
 #[derive(Debug, Default, Clone, PartialEq)]
 pub enum ScrollAmount {
     #[default]
     One,
     HalfPage,
+    FullPage,
+    /// A fixed number of lines, e.g. `note_editor_scroll_down_lines = 5`.
+    Lines(usize),
 }
 
 fn calc_scroll_amount(scroll_amount: ScrollAmount, height: usize) -> usize {
     match scroll_amount {
         ScrollAmount::One => 1,
         ScrollAmount::HalfPage => height / 2,
+        ScrollAmount::FullPage => height,
+        ScrollAmount::Lines(lines) => lines,
+    }
+}
+
+/// A note editor pane of the split view.
+#[derive(Default, Clone)]
+struct EditorPane<'a> {
+    note_editor: EditorState<'a>,
+    selected_note: Option<SelectedNote>,
+}
+
+impl EditorPane<'_> {
+    fn save(self) -> io::Result<Self> {
+        let note_editor = self.note_editor.save()?;
+        let selected_note = self.selected_note.map(|note| SelectedNote {
+            content: note_editor.content().to_string(),
+            ..note
+        });
+
+        Ok(Self {
+            note_editor,
+            selected_note,
+        })
+    }
+
+    fn discard(self) -> Self {
+        let note_editor = self.note_editor.discard();
+        let selected_note = self.selected_note.map(|note| SelectedNote {
+            content: note_editor.content().to_string(),
+            ..note
+        });
+
+        Self {
+            note_editor,
+            selected_note,
+        }
     }
 }
 
 #[derive(Default, Clone)]
 struct MainState<'a> {
+    vault: Vault,
     active_pane: ActivePane,
     explorer: ExplorerState<'a>,
     note_editor: EditorState<'a>,
     outline: OutlineState,
     selected_note: Option<SelectedNote>,
+    /// The entries of the selected vault, used to resolve wiki links.
+    entries: Vec<VaultEntry>,
+    /// Paths of the notes that were open before following a link, the most recent last.
+    back_stack: Vec<PathBuf>,
+    /// The panes of the split note area from left to right, empty when the note area is not
+    /// split. The active split is edited through `note_editor` and `selected_note`, and its
+    /// entry is only brought up to date when another split is focused.
+    splits: Vec<EditorPane<'a>>,
+    active_split: usize,
+    /// The open notes of the tab bar from left to right, empty when a single note is open. Like
+    /// the splits, the active tab is edited through `note_editor` and `selected_note`. Tabs and
+    /// splits cannot be used at the same time.
+    tabs: Vec<EditorPane<'a>>,
+    active_tab: usize,
+    /// Paths of the recently opened notes without duplicates, the most recent first.
+    recent_notes: Vec<PathBuf>,
+    /// The view positions of the recently opened notes, restored when a note is opened again.
+    /// Only the notes in `recent_notes` are kept, so the least recently opened notes are
+    /// dropped first.
+    view_positions: HashMap<PathBuf, ViewPosition>,
+    /// The note is not in a vault, e.g. markdown piped to `basalt --render`. There is no
+    /// explorer, and the note cannot be edited or saved, see [`read_only_message`].
+    read_only: bool,
 }
 
 impl<'a> MainState<'a> {
-    fn new(selected_vault_name: &'a str, notes: Vec<VaultEntry>) -> Self {
+    fn focus(self, active_pane: ActivePane) -> Self {
         Self {
-            active_pane: ActivePane::Explorer,
-            explorer: ExplorerState::new(selected_vault_name, notes).set_active(true),
-            ..Default::default()
+            active_pane,
+            explorer: self
+                .explorer
+                .set_active(active_pane == ActivePane::Explorer),
+            note_editor: self
+                .note_editor
+                .set_active(active_pane == ActivePane::NoteEditor),
+            outline: self.outline.set_active(active_pane == ActivePane::Outline),
+            ..self
         }
     }
-}
-
-#[derive(Default, Clone)]
-pub struct AppState<'a> {
-    screen: ScreenState<'a>,
-    screen_size: Size,
-    is_running: bool,
 
-    help_modal: HelpModalState,
-    vault_selector_modal: VaultSelectorModalState<'a>,
-}
+    /// Returns the editor panes to render from left to right, with the active split taken from
+    /// the live editor state.
+    fn editor_panes(&self) -> Vec<EditorPane<'a>> {
+        let active = EditorPane {
+            note_editor: self.note_editor.clone(),
+            selected_note: self.selected_note.clone(),
+        };
 
-fn modal_area_height(size: Size) -> usize {
-    let vertical = Layout::vertical([Constraint::Percentage(50)]).flex(Flex::Center);
-    let [area] = vertical.areas(Rect::new(0, 0, size.width, size.height.saturating_sub(3)));
-    area.height.into()
-}
+        if self.splits.is_empty() {
+            return vec![active];
+        }
 
-#[derive(Clone)]
-enum ScreenState<'a> {
-    Splash(SplashState<'a>),
-    Main(Box<MainState<'a>>),
-}
+        let mut panes = self.splits.clone();
+        panes[self.active_split] = active;
+        panes
+    }
 
-impl<'a> AppState<'a> {
-    pub fn active_component(&self) -> ActivePane {
-        if self.help_modal.visible {
-            return ActivePane::HelpModal;
+    /// Replaces the editor panes with the panes returned by [`MainState::editor_panes`].
+    fn with_editor_panes(self, mut panes: Vec<EditorPane<'a>>) -> Self {
+        if self.splits.is_empty() {
+            let active = panes.pop().unwrap_or_default();
+            return Self {
+                note_editor: active.note_editor,
+                selected_note: active.selected_note,
+                ..self
+            };
         }
 
-        if self.vault_selector_modal.visible {
-            return ActivePane::VaultSelectorModal;
+        let active = panes[self.active_split].clone();
+        Self {
+            note_editor: active.note_editor,
+            selected_note: active.selected_note,
+            splits: panes,
+            ..self
         }
+    }
 
-        match &self.screen {
-            ScreenState::Splash(..) => ActivePane::Splash,
-            ScreenState::Main(state) => state.active_pane,
+    /// Returns the editor panes of all tabs, or of the splits when there are no tabs.
+    fn all_panes(&self) -> Vec<EditorPane<'a>> {
+        if self.tabs.is_empty() {
+            return self.editor_panes();
         }
+
+        let mut tabs = self.tabs.clone();
+        tabs[self.active_tab] = EditorPane {
+            note_editor: self.note_editor.clone(),
+            selected_note: self.selected_note.clone(),
+        };
+        tabs
     }
 
-    pub fn set_running(&self, is_running: bool) -> Self {
-        Self {
-            is_running,
-            ..self.clone()
+    /// Replaces the editor panes with the panes returned by [`MainState::all_panes`].
+    fn with_all_panes(self, panes: Vec<EditorPane<'a>>) -> Self {
+        if self.tabs.is_empty() {
+            return self.with_editor_panes(panes);
         }
-    }
 
-    fn with_vault_selector_modal_state(
-        &self,
-        vault_selector_modal: VaultSelectorModalState<'a>,
-    ) -> Self {
+        let active = panes[self.active_tab].clone();
         Self {
-            vault_selector_modal,
-            ..self.clone()
+            note_editor: active.note_editor,
+            selected_note: active.selected_note,
+            tabs: panes,
+            ..self
         }
     }
 
-    fn with_help_modal_state(&self, help_modal: HelpModalState) -> Self {
+    /// Returns the names of the notes with unsaved changes in any of the editor panes.
+    fn modified_notes(&self) -> Vec<String> {
+        self.all_panes()
+            .into_iter()
+            .filter(|pane| pane.note_editor.modified)
+            .filter_map(|pane| pane.selected_note.map(|note| note.name))
+            .collect()
+    }
+
+    /// Saves the unsaved changes of every editor pane.
+    fn save(self) -> io::Result<Self> {
+        let state = self.commit_edits().sync_note_panes();
+        let panes = state
+            .all_panes()
+            .into_iter()
+            .map(EditorPane::save)
+            .collect::<io::Result<Vec<_>>>()?;
+
+        Ok(state.with_all_panes(panes))
+    }
+
+    /// Discards the unsaved changes of every editor pane.
+    fn discard(self) -> Self {
+        let state = self.commit_edits().sync_note_panes();
+        let panes = state
+            .all_panes()
+            .into_iter()
+            .map(EditorPane::discard)
+            .collect();
+
+        state.with_all_panes(panes)
+    }
+
+    /// Brings the inactive panes that show the note of the active pane up to date with its
+    /// content, see [`EditorState::sync_content`]. Pending edits of the active note editor are
+    /// only included once committed.
+    fn sync_note_panes(self) -> Self {
+        let Some(path) = self.selected_note.as_ref().map(|note| note.path.clone()) else {
+            return self;
+        };
+
+        let note_editor = self.note_editor.clone();
+        self.map_inactive_panes(|pane| match &pane.selected_note {
+            Some(selected) if selected.path == path => EditorPane {
+                selected_note: pane.selected_note.clone().map(|note| SelectedNote {
+                    content: note_editor.content().to_string(),
+                    ..note
+                }),
+                note_editor: pane.note_editor.sync_content(&note_editor),
+            },
+            _ => pane,
+        })
+    }
+
+    /// Makes the active note editor take the content of an inactive pane that shows the same
+    /// note, so that a note opened again in another split keeps its unsaved changes.
+    fn with_note_from_panes(self) -> Self {
+        let Some(path) = self.selected_note.as_ref().map(|note| note.path.clone()) else {
+            return self;
+        };
+
+        let inactive = |panes: &[EditorPane<'a>], active: usize| {
+            panes
+                .iter()
+                .enumerate()
+                .filter(|(index, _)| *index != active)
+                .find(|(_, pane)| {
+                    pane.selected_note
+                        .as_ref()
+                        .is_some_and(|note| note.path == path)
+                })
+                .map(|(_, pane)| pane.note_editor.clone())
+        };
+
+        let Some(other) = inactive(&self.splits, self.active_split)
+            .or_else(|| inactive(&self.tabs, self.active_tab))
+        else {
+            return self;
+        };
+
+        let note_editor = self.note_editor.sync_content(&other);
         Self {
-            help_modal,
-            ..self.clone()
+            outline: OutlineState::new(
+                note_editor.nodes(),
+                note_editor.collapsed_ranges(),
+                note_editor.current_row,
+                self.outline.is_open(),
+            ),
+            selected_note: self.selected_note.map(|note| SelectedNote {
+                content: note_editor.content().to_string(),
+                ..note
+            }),
+            note_editor,
+            ..self
         }
     }
 
-    fn with_main_state(&self, main_state: MainState<'a>) -> Self {
+    /// Applies the pending edits of the active note editor, see [`EditorState::commit_edits`].
+    fn commit_edits(self) -> Self {
         Self {
-            screen: ScreenState::Main(Box::new(main_state)),
-            ..self.clone()
+            note_editor: self.note_editor.commit_edits(),
+            ..self
         }
     }
 
-    fn with_splash_state(&self, splash_state: SplashState<'a>) -> Self {
+    /// Splits the active note editor vertically. The new split opens the same note to the right
+    /// of the active split and becomes active.
+    fn split_vertical(self) -> Self {
+        let state = self.commit_edits();
+        let mut splits = state.editor_panes();
+        let active_split = if state.splits.is_empty() {
+            0
+        } else {
+            state.active_split
+        };
+
+        splits[active_split].note_editor =
+            splits[active_split].note_editor.clone().set_active(false);
+
+        // The new split is rendered at its own width, so it gets a render cache of its own
+        let note_editor = state.note_editor.clone().detach_render_cache();
+        splits.insert(
+            active_split + 1,
+            EditorPane {
+                note_editor: note_editor.clone(),
+                selected_note: splits[active_split].selected_note.clone(),
+            },
+        );
+
         Self {
-            screen: ScreenState::Splash(splash_state),
-            ..self.clone()
+            splits,
+            active_split: active_split + 1,
+            note_editor,
+            ..state
         }
     }
-}
 
-impl Default for ScreenState<'_> {
-    fn default() -> Self {
-        Self::Splash(SplashState::default())
+    /// Closes the active split and focuses the split that takes its place. Closing the second to
+    /// last split returns to the single note editor.
+    fn close_split(self) -> Self {
+        if self.splits.is_empty() {
+            return self;
+        }
+
+        let state = self.commit_edits().sync_note_panes();
+        let mut splits = state.splits.clone();
+        splits.remove(state.active_split);
+        let active_split = state.active_split.min(splits.len() - 1);
+
+        let state = Self {
+            splits: if splits.len() > 1 {
+                splits.clone()
+            } else {
+                vec![]
+            },
+            active_split: if splits.len() > 1 { active_split } else { 0 },
+            ..state
+        };
+
+        state.load_pane(splits[active_split].clone())
     }
-}
 
-pub mod splash {
-    use crate::splash::SplashState;
+    /// Focuses the split at the index, storing the state of the active split.
+    fn focus_split(self, index: usize) -> Self {
+        if self.splits.is_empty() || index >= self.splits.len() || index == self.active_split {
+            return self;
+        }
 
-    #[derive(Clone, Debug, PartialEq)]
-    pub enum Message {
-        Up,
-        Down,
-        Open,
+        let state = self.commit_edits().sync_note_panes();
+        let mut splits = state.editor_panes();
+        splits[state.active_split].note_editor = splits[state.active_split]
+            .note_editor
+            .clone()
+            .set_active(false);
+        let pane = splits[index].clone();
+
+        Self {
+            splits,
+            active_split: index,
+            ..state
+        }
+        .load_pane(pane)
     }
 
-    pub fn update(message: Message, state: SplashState) -> SplashState {
-        match message {
-            Message::Up => state.previous(),
-            Message::Down => state.next(),
-            Message::Open => state.select(),
+    /// Adds an empty tab after the active tab and makes it active, storing the state of the
+    /// active tab. The note is then opened in the new tab like in the active note editor.
+    fn new_tab(self) -> Self {
+        let mut tabs = self.all_panes();
+        let active_tab = if self.tabs.is_empty() {
+            0
+        } else {
+            self.active_tab
+        };
+
+        tabs.insert(active_tab + 1, EditorPane::default());
+
+        Self {
+            tabs,
+            active_tab: active_tab + 1,
+            ..self
         }
+        .load_pane(EditorPane::default())
     }
-}
 
-pub mod explorer {
-    use crate::explorer::ExplorerState;
+    /// Closes the active tab and selects the tab to its right, or the new last tab if the active
+    /// tab was the last one. Closing the second to last tab leaves the single note editor.
+    fn close_tab(self) -> Self {
+        if self.tabs.is_empty() {
+            return self;
+        }
 
-    use super::ScrollAmount;
+        let mut tabs = self.tabs.clone();
+        tabs.remove(self.active_tab);
+        let active_tab = self.active_tab.min(tabs.len() - 1);
 
-    #[derive(Clone, Debug, PartialEq)]
-    pub enum Message {
-        Up,
-        Down,
-        Open,
-        Sort,
-        Toggle,
-        ToggleOutline,
-        SwitchPaneNext,
-        SwitchPanePrevious,
-        ScrollUp(ScrollAmount),
-        ScrollDown(ScrollAmount),
+        let state = Self {
+            tabs: if tabs.len() > 1 { tabs.clone() } else { vec![] },
+            active_tab: if tabs.len() > 1 { active_tab } else { 0 },
+            ..self
+        };
+
+        state.load_pane(tabs[active_tab].clone())
     }
 
-    pub fn update(message: Message, state: ExplorerState) -> ExplorerState {
-        match message {
-            Message::Up => state.previous(1),
-            Message::Down => state.next(1),
-            Message::Sort => state.sort(),
-            Message::Open => state.select(),
-            Message::Toggle => state.toggle(),
-            Message::SwitchPaneNext | Message::SwitchPanePrevious => {
-                if state.active {
-                    state.set_active(false)
-                } else {
-                    state.set_active(true)
-                }
-            }
-            _ => state,
+    /// Selects the tab at the index, storing the state of the active tab.
+    fn focus_tab(self, index: usize) -> Self {
+        if self.tabs.is_empty() || index >= self.tabs.len() || index == self.active_tab {
+            return self;
+        }
+
+        let tabs = self.all_panes();
+        let pane = tabs[index].clone();
+
+        Self {
+            tabs,
+            active_tab: index,
+            ..self
         }
+        .load_pane(pane)
     }
-}
 
-pub mod outline {
-    use crate::outline::OutlineState;
+    /// Returns the index of the tab that shows the note at the path.
+    fn note_tab(&self, path: &Path) -> Option<usize> {
+        if self.tabs.is_empty() {
+            return None;
+        }
 
-    #[derive(Clone, Debug, PartialEq)]
-    pub enum Message {
-        Up,
-        Down,
-        Select,
-        Expand,
-        Toggle,
-        ToggleExplorer,
-        SwitchPaneNext,
-        SwitchPanePrevious,
+        self.all_panes().iter().position(|pane| {
+            pane.selected_note
+                .as_ref()
+                .is_some_and(|selected| Path::new(&selected.path) == path)
+        })
     }
 
-    pub fn update(message: Message, state: OutlineState) -> OutlineState {
-        match message {
-            Message::Up => state.previous(1),
-            Message::Down => state.next(1),
-            Message::Toggle => state.toggle(),
-            Message::SwitchPaneNext | Message::SwitchPanePrevious => {
-                if state.active {
-                    state.set_active(false)
-                } else {
-                    state.set_active(true)
-                }
+    /// Closes the tabs that show the note at the path, e.g. after the note was deleted. The active
+    /// tab stays active unless it is closed.
+    fn close_note_tabs(self, path: &Path) -> Self {
+        let mut state = self;
+
+        while let Some(index) = state.note_tab(path) {
+            let active = state
+                .selected_note
+                .as_ref()
+                .map(|note| PathBuf::from(&note.path));
+            state = state.focus_tab(index).close_tab();
+
+            if let Some(index) = active.and_then(|active| state.note_tab(&active)) {
+                state = state.focus_tab(index);
             }
-            _ => state,
         }
-    }
-}
 
-pub mod note_editor {
-    use ratatui::crossterm::event::{KeyCode, KeyEvent};
+        state
+    }
 
-    use super::ScrollAmount;
+    /// Returns the index of the tab after the active tab, wrapping around to the first tab.
+    fn next_tab(&self) -> usize {
+        (self.active_tab + 1) % self.tabs.len().max(1)
+    }
 
-    #[derive(Clone, Debug, PartialEq)]
-    pub enum Message {
-        Save,
-        SwitchPaneNext,
-        SwitchPanePrevious,
-        ToggleExplorer,
-        ToggleOutline,
-        EditMode,
-        ExitMode,
-        ReadMode,
-        KeyEvent(KeyEvent),
-        CursorUp,
-        CursorLeft,
-        CursorRight,
-        CursorWordForward,
-        CursorWordBackward,
-        CursorDown,
-        ScrollUp(ScrollAmount),
-        ScrollDown(ScrollAmount),
-        Delete,
+    /// Returns the index of the tab before the active tab, wrapping around to the last tab.
+    fn previous_tab(&self) -> usize {
+        (self.active_tab + self.tabs.len().max(1) - 1) % self.tabs.len().max(1)
     }
 
-    pub fn handle_editing_event(key: &KeyEvent) -> Option<Message> {
-        match key.code {
-            KeyCode::Up => Some(Message::CursorUp),
-            KeyCode::Down => Some(Message::CursorDown),
-            KeyCode::Esc => Some(Message::ExitMode),
-            KeyCode::Backspace => Some(Message::Delete),
-            _ => Some(Message::KeyEvent(*key)),
+    /// Makes the pane the live editor state and rebuilds the outline for its note.
+    fn load_pane(self, pane: EditorPane<'a>) -> Self {
+        let note_editor = pane
+            .note_editor
+            .set_active(self.active_pane == ActivePane::NoteEditor);
+        let outline = OutlineState::new(
+            note_editor.nodes(),
+            note_editor.collapsed_ranges(),
+            note_editor.current_row,
+            self.outline.is_open(),
+        )
+        .set_active(self.active_pane == ActivePane::Outline);
+
+        let state = match &pane.selected_note {
+            Some(note) => self.reveal(Path::new(&note.path)),
+            None => self,
+        };
+
+        Self {
+            note_editor,
+            outline,
+            selected_note: pane.selected_note,
+            ..state
         }
     }
-}
-
-pub mod help_modal {
-    use crate::help_modal::HelpModalState;
 
-    use super::ScrollAmount;
+    /// Returns the index of the split after the active split, or `None` if the active split is the
+    /// last one.
+    fn next_split(&self) -> Option<usize> {
+        (self.active_split + 1 < self.splits.len()).then_some(self.active_split + 1)
+    }
 
-    #[derive(Clone, Debug, PartialEq)]
-    pub enum Message {
-        Toggle,
-        Close,
-        ScrollUp(ScrollAmount),
-        ScrollDown(ScrollAmount),
+    /// Returns the index of the split before the active split, or `None` if the active split is
+    /// the first one.
+    fn previous_split(&self) -> Option<usize> {
+        (!self.splits.is_empty() && self.active_split > 0).then(|| self.active_split - 1)
     }
 
-    pub fn update(message: Message, state: HelpModalState) -> HelpModalState {
-        match message {
-            Message::Toggle => state.toggle_visibility(),
-            Message::Close => state.hide(),
-            _ => state,
+    /// Applies the function to the editor panes of the inactive splits and tabs.
+    fn map_inactive_panes(self, f: impl Fn(EditorPane<'a>) -> EditorPane<'a>) -> Self {
+        let map_inactive = |panes: &[EditorPane<'a>], active: usize| {
+            panes
+                .iter()
+                .cloned()
+                .enumerate()
+                .map(|(index, pane)| if index == active { pane } else { f(pane) })
+                .collect()
+        };
+
+        Self {
+            splits: map_inactive(&self.splits, self.active_split),
+            tabs: map_inactive(&self.tabs, self.active_tab),
+            ..self
         }
     }
-}
 
-pub mod vault_selector_modal {
-    use crate::vault_selector_modal::VaultSelectorModalState;
+    /// Points the open notes and recent notes at the given path to the renamed or moved note.
+    fn with_renamed_note(self, path: &Path, renamed: &Note) -> Self {
+        let rename = |pane: EditorPane<'a>| match &pane.selected_note {
+            Some(selected) if Path::new(&selected.path) == path => EditorPane {
+                note_editor: pane.note_editor.set_path(renamed.path.clone()),
+                selected_note: pane.selected_note.map(|note| SelectedNote {
+                    name: renamed.name.clone(),
+                    path: renamed.path.to_string_lossy().to_string(),
+                    ..note
+                }),
+            },
+            _ => pane,
+        };
 
-    #[derive(Clone, Debug, PartialEq)]
-    pub enum Message {
-        Toggle,
-        Up,
-        Down,
-        Select,
-        Close,
-    }
+        let main_state = self.map_inactive_panes(rename);
+        let pane = rename(EditorPane {
+            note_editor: main_state.note_editor.clone(),
+            selected_note: main_state.selected_note.clone(),
+        });
 
-    pub fn update(message: Message, state: VaultSelectorModalState) -> VaultSelectorModalState {
-        match message {
-            Message::Up => state.previous(),
-            Message::Down => state.next(),
-            Message::Toggle => state.toggle_visibility(),
-            Message::Select => state.select(),
-            Message::Close => state.hide(),
+        Self {
+            note_editor: pane.note_editor,
+            selected_note: pane.selected_note,
+            recent_notes: main_state
+                .recent_notes
+                .iter()
+                .map(|recent| match recent == path {
+                    true => renamed.path.clone(),
+                    false => recent.clone(),
+                })
+                .collect(),
+            ..main_state
         }
     }
-}
-
-#[derive(Clone, Debug, PartialEq)]
-pub enum Message {
-    Quit,
-    Resize(Size),
 
-    Splash(splash::Message),
-    Explorer(explorer::Message),
-    NoteEditor(note_editor::Message),
-    Outline(outline::Message),
-    HelpModal(help_modal::Message),
-    VaultSelectorModal(vault_selector_modal::Message),
-}
+    /// Reveals the note at the path in the explorer, see [`ExplorerState::reveal`].
+    ///
+    /// When the note is not in the explorer, e.g. because it was created outside basalt, the
+    /// entries are read again from the vault first. Returns the state unchanged if the note is
+    /// still not found.
+    fn reveal(self, path: &Path) -> Self {
+        let explorer = self.explorer.reveal(path);
+        if explorer.selected_path().as_deref() == Some(path) {
+            return Self { explorer, ..self };
+        }
 
-#[derive(Debug, Default, Clone, Copy, PartialEq)]
-pub enum ActivePane {
-    #[default]
-    Splash,
-    Explorer,
-    NoteEditor,
-    Outline,
-    HelpModal,
-    VaultSelectorModal,
-}
+        let entries = self.vault.entries();
+        let explorer = self.explorer.with_entries(entries.clone()).reveal(path);
+        if explorer.selected_path().as_deref() != Some(path) {
+            return self;
+        }
 
-impl From<ActivePane> for &str {
-    fn from(value: ActivePane) -> Self {
-        match value {
-            ActivePane::Splash => "Splash",
-            ActivePane::Explorer => "Explorer",
-            ActivePane::NoteEditor => "Note Editor",
-            ActivePane::Outline => "Outline",
-            ActivePane::HelpModal => "Help",
-            ActivePane::VaultSelectorModal => "Vault Selector",
+        Self {
+            explorer,
+            entries,
+            ..self
         }
     }
-}
 
-#[derive(Debug, Default, Clone, PartialEq)]
-pub struct SelectedNote {
-    name: String,
-    path: String,
-    content: String,
-}
+    /// Moves the note path to the front of the recent notes, dropping the oldest note when there
+    /// are more than [`RECENT_NOTES_LIMIT`] notes.
+    fn with_recent_note(self, path: PathBuf) -> Self {
+        let recent_notes: Vec<PathBuf> = [path.clone()]
+            .into_iter()
+            .chain(
+                self.recent_notes
+                    .iter()
+                    .filter(|recent| **recent != path)
+                    .cloned(),
+            )
+            .take(RECENT_NOTES_LIMIT)
+            .collect();
+
+        let mut view_positions = self.view_positions;
+        view_positions.retain(|path, _| recent_notes.contains(path));
 
-impl From<Note> for SelectedNote {
-    fn from(value: Note) -> Self {
         Self {
-            name: value.name.clone(),
-            path: value.path.to_string_lossy().to_string(),
-            content: Note::read_to_string(&value).unwrap_or_default(),
+            recent_notes,
+            view_positions,
+            ..self
         }
     }
-}
 
-fn help_text(version: &str) -> String {
-    HELP_TEXT.replace("%version-notice", version)
-}
+    /// Stores the view position of the open note, see [`ViewPosition`].
+    fn with_view_position_saved(self) -> Self {
+        let Some(note) = &self.selected_note else {
+            return self;
+        };
 
-pub struct App<'a> {
-    state: AppState<'a>,
-    config: Config,
-    terminal: RefCell<DefaultTerminal>,
-}
+        let mut view_positions = self.view_positions.clone();
+        view_positions.insert(PathBuf::from(&note.path), self.note_editor.view_position());
 
-impl<'a> App<'a> {
-    pub fn new(state: AppState<'a>, terminal: DefaultTerminal) -> Self {
         Self {
-            state,
-            // TODO: Surface toast if read config returns error
-            config: config::load().unwrap(),
-            terminal: RefCell::new(terminal),
+            view_positions,
+            ..self
         }
     }
 
-    pub fn start(terminal: DefaultTerminal, vaults: Vec<&Vault>) -> Result<()> {
-        let version = stylized_text::stylize(&format!("{VERSION}~beta"), FontStyle::Script);
-        let size = terminal.size()?;
+    /// Returns the recent notes named by their vault-relative path without the extension.
+    fn recent_note_items(&self) -> Vec<RecentNote> {
+        self.recent_notes
+            .iter()
+            .map(|path| RecentNote {
+                name: path
+                    .strip_prefix(&self.vault.path)
+                    .unwrap_or(path)
+                    .with_extension("")
+                    .to_string_lossy()
+                    .to_string(),
+                path: path.clone(),
+            })
+            .collect()
+    }
 
-        let state = AppState {
-            screen_size: size,
-            help_modal: HelpModalState::new(&help_text(&version)),
-            vault_selector_modal: VaultSelectorModalState::new(vaults.clone()),
-            ..Default::default()
-        }
-        .with_splash_state(SplashState::new(&version, vaults));
+    /// Reads the entries of the vault, see [`Vault::read_entries`], and returns the state of the
+    /// vault together with an error toast if some of the entries could not be read.
+    fn new(config: &Config, vault: &'a Vault) -> (Self, Option<Message>) {
+        let (entries, errors) = vault.read_entries(&ReadOptions::default());
+        let message = match errors.as_slice() {
+            [] => None,
+            [error] => Some(toast(
+                ToastKind::Error,
+                &format!("Failed to read vault entry: {error}"),
+            )),
+            [error, rest @ ..] => Some(toast(
+                ToastKind::Error,
+                &format!(
+                    "Failed to read vault entry: {error} (and {} more)",
+                    rest.len()
+                ),
+            )),
+        };
 
-        App::new(state, terminal).run()
+        (Self::with_entries(config, vault, entries), message)
     }
 
-    fn run(&'a mut self) -> Result<()> {
-        self.state.is_running = true;
+    /// Creates the state of a read-only note that is not in any vault. The vault is left empty,
+    /// so the state never reads from a vault directory.
+    fn read_only(name: &str, content: &str) -> Self {
+        let note_editor = EditorState::default().set_content(content).set_active(true);
+        let outline = OutlineState::new(
+            note_editor.nodes(),
+            note_editor.collapsed_ranges(),
+            note_editor.current_row,
+            false,
+        );
 
-        while self.state.is_running {
-            self.draw(&mut self.state.clone())?;
-            let event = event::read()?;
-            let action = self.handle_event(&event);
-            self.state = self.update(&self.state, action);
+        Self {
+            active_pane: ActivePane::NoteEditor,
+            note_editor,
+            outline,
+            selected_note: Some(SelectedNote {
+                name: name.to_string(),
+                path: name.to_string(),
+                content: content.to_string(),
+            }),
+            read_only: true,
+            ..Default::default()
         }
+    }
 
-        Ok(())
+    /// Returns the areas of the panes. A read-only note has no explorer, so the note editor takes
+    /// its place.
+    fn layout(&self, area: Rect) -> MainLayout {
+        let layout = MainLayout::new(area, self.explorer.open, self.outline.is_open());
+
+        let layout = match self.read_only {
+            true => MainLayout {
+                explorer: Rect::default(),
+                note_editor: layout.explorer.union(layout.note_editor),
+                ..layout
+            },
+            false => layout,
+        };
+
+        match self.tabs.is_empty() {
+            true => layout,
+            false => layout.with_tab_bar(),
+        }
     }
 
-    fn draw(&self, state: &'a mut AppState<'a>) -> Result<()> {
-        let mut terminal = self.terminal.borrow_mut();
+    /// Soft-wraps the text buffers of the editor panes to the width of their areas in the layout,
+    /// so that moving the cursor up and down follows the lines as they are shown.
+    fn with_wrap_widths(self, area: Rect) -> Self {
+        let areas = self.layout(area).splits(self.splits.len());
 
-        terminal.draw(move |frame| {
-            let area = frame.area();
-            let buf = frame.buffer_mut();
-            self.render_ref(area, buf, state);
-        })?;
+        let panes = self
+            .editor_panes()
+            .into_iter()
+            .zip(areas)
+            .map(|(pane, area)| {
+                let wrap_width = Editor::wrap_width(area, pane.note_editor.mode());
+                EditorPane {
+                    note_editor: pane.note_editor.with_wrap_width(wrap_width),
+                    ..pane
+                }
+            })
+            .collect();
 
-        Ok(())
+        self.with_editor_panes(panes)
     }
 
-    fn handle_event(&self, event: &Event) -> Option<Message> {
-        match event {
-            Event::Resize(cols, rows) => Some(Message::Resize(Size::new(*cols, *rows))),
-            Event::Key(key_event) if key_event.kind == KeyEventKind::Press => {
-                self.handle_key_event(key_event)
-            }
-            _ => None,
+    /// Creates the state of the vault with the given entries instead of reading them from the
+    /// vault directory.
+    fn with_entries(config: &Config, vault: &'a Vault, entries: Vec<VaultEntry>) -> Self {
+        Self {
+            vault: vault.clone(),
+            active_pane: ActivePane::Explorer,
+            explorer: ExplorerState::new(&vault.name, entries.clone())
+                .with_attachments(config.show_attachments())
+                .with_bookmarks(bookmark_paths(vault))
+                .set_active(true),
+            entries,
+            ..Default::default()
         }
     }
+}
 
-    #[rustfmt::skip]
-    fn handle_active_component_event(&self, key: &KeyEvent, active_component: ActivePane) -> Option<Message> {
-        match active_component {
-            ActivePane::Splash => self.config.splash.key_to_message(key.into()),
-            ActivePane::Explorer => self.config.explorer.key_to_message(key.into()),
-            ActivePane::NoteEditor => {
-                match &self.state.screen {
-                    ScreenState::Main(state) if state.note_editor.is_editing() => {
-                        note_editor::handle_editing_event(key).map(Message::NoteEditor)
-                    },
-                    ScreenState::Main(_) =>
-                        self.config.note_editor.key_to_message(key.into()),
-                    _ => None
-                }
+/// Returns the absolute paths of the bookmarked files in the vault. Bookmarks that cannot be read
+/// are not shown.
+fn bookmark_paths(vault: &Vault) -> Vec<PathBuf> {
+    vault
+        .bookmarks()
+        .unwrap_or_default()
+        .into_iter()
+        .map(|bookmark| vault.path.join(bookmark.path))
+        .collect()
+}
+
+/// The areas of the panes on the main screen.
+#[derive(Debug, Default, Clone, Copy, PartialEq)]
+struct MainLayout {
+    explorer: Rect,
+    note_editor: Rect,
+    outline: Rect,
+    statusbar: Rect,
+    /// The row above the note editor that shows the open tabs, empty without tabs.
+    tab_bar: Rect,
+}
+
+impl MainLayout {
+    fn new(area: Rect, explorer_open: bool, outline_open: bool) -> Self {
+        let [content, statusbar] = Layout::vertical([Constraint::Fill(1), Constraint::Length(1)])
+            .horizontal_margin(1)
+            .areas(area);
+
+        let [explorer, note_editor, outline] = Layout::horizontal([
+            if explorer_open {
+                Constraint::Length(35)
+            } else {
+                Constraint::Length(5)
+            },
+            Constraint::Fill(1),
+            if outline_open {
+                Constraint::Length(35)
+            } else {
+                Constraint::Length(4)
             },
-            ActivePane::Outline => self.config.outline.key_to_message(key.into()),
-            ActivePane::HelpModal => self.config.help_modal.key_to_message(key.into()),
-            ActivePane::VaultSelectorModal => self.config.vault_selector_modal.key_to_message(key.into()),
+        ])
+        .areas(content);
+
+        Self {
+            explorer,
+            note_editor,
+            outline,
+            statusbar,
+            tab_bar: Rect::default(),
         }
     }
 
-    fn handle_key_event(&self, key: &KeyEvent) -> Option<Message> {
-        let global_message = self.config.global.key_to_message(key.into());
-
-        let is_editing = match &self.state.screen {
-            ScreenState::Main(state) => state.note_editor.is_editing(),
-            _ => false,
-        };
+    /// Takes the first row of the note editor area for the tab bar.
+    fn with_tab_bar(self) -> Self {
+        let [tab_bar, note_editor] =
+            Layout::vertical([Constraint::Length(1), Constraint::Fill(1)]).areas(self.note_editor);
 
-        if global_message.is_some() && !is_editing {
-            return global_message;
+        Self {
+            tab_bar,
+            note_editor,
+            ..self
         }
+    }
 
-        let active_component = self.state.active_component();
-        self.handle_active_component_event(key, active_component)
+    /// Divides the note editor area into equal vertical chunks for the splits.
+    fn splits(&self, count: usize) -> Vec<Rect> {
+        Layout::horizontal(vec![Constraint::Fill(1); count.max(1)])
+            .split(self.note_editor)
+            .to_vec()
     }
 
-    fn update(&self, state: &AppState<'a>, message: Option<Message>) -> AppState<'a> {
-        let state = state.clone();
-        let Some(message) = message else {
-            return state;
-        };
+    fn pane_at(&self, position: Position) -> Option<ActivePane> {
+        [
+            (self.explorer, ActivePane::Explorer),
+            (self.note_editor, ActivePane::NoteEditor),
+            (self.outline, ActivePane::Outline),
+        ]
+        .into_iter()
+        .find_map(|(area, pane)| area.contains(position).then_some(pane))
+    }
+}
 
-        let screen = state.screen.clone();
+/// Returns the index of the list item rendered at the row of a bordered list with the given
+/// number of items. Rows below the last item and on the borders do not map to an item.
+///
+/// The offset is adjusted the same way the list adjusts it when rendering, so that the selected
+/// item is visible.
+fn list_index_at(area: Rect, list_state: &ListState, item_count: usize, row: u16) -> Option<usize> {
+    let height = usize::from(area.height.saturating_sub(2));
+    let row = usize::from(row.checked_sub(area.y + 1)?);
 
-        match message {
-            Message::Quit => state.set_running(false),
-            Message::Resize(size) => AppState {
-                screen_size: size,
-                ..state
-            },
-            Message::HelpModal(message) => {
-                let help_modal = help_modal::update(message.clone(), state.help_modal.clone());
+    if row >= height {
+        return None;
+    }
 
-                match message {
-                    help_modal::Message::ScrollDown(scroll_amount) => {
-                        state.with_help_modal_state(help_modal.scroll_down(calc_scroll_amount(
-                            scroll_amount,
-                            modal_area_height(state.screen_size),
-                        )))
-                    }
-                    help_modal::Message::ScrollUp(scroll_amount) => {
-                        state.with_help_modal_state(help_modal.scroll_up(calc_scroll_amount(
-                            scroll_amount,
-                            modal_area_height(state.screen_size),
-                        )))
-                    }
-                    _ => state.with_help_modal_state(help_modal),
-                }
-            }
-            Message::VaultSelectorModal(message) => {
-                let ScreenState::Main(_) = screen else {
-                    return state;
-                };
+    let offset = match list_state.selected() {
+        Some(selected) if selected >= list_state.offset() + height => selected + 1 - height,
+        Some(selected) if selected < list_state.offset() => selected,
+        _ => list_state.offset(),
+    };
 
-                let vault_selector_modal = vault_selector_modal::update(
-                    message.clone(),
-                    state.vault_selector_modal.clone(),
-                );
+    Some(offset + row).filter(|index| *index < item_count)
+}
 
-                match message {
-                    vault_selector_modal::Message::Select => vault_selector_modal
-                        .selected()
-                        .and_then(|index| vault_selector_modal.clone().get_item(index))
-                        .map(|vault| {
-                            state
-                                .with_main_state(MainState::new(&vault.name, vault.entries()))
-                                .with_vault_selector_modal_state(vault_selector_modal.hide())
-                        })
-                        .unwrap_or(state),
-                    _ => state.with_vault_selector_modal_state(vault_selector_modal),
-                }
-            }
-            Message::Splash(message) => {
-                let ScreenState::Splash(splash_state) = screen else {
-                    return state;
-                };
+#[derive(Default, Clone)]
+pub struct AppState<'a> {
+    screen: ScreenState<'a>,
+    screen_size: Size,
+    is_running: bool,
 
-                let splash_state = splash::update(message.clone(), splash_state);
+    help_modal: HelpModalState,
+    input_modal: InputModalState,
+    vault_selector_modal: VaultSelectorModalState<'a>,
+    recent_notes_modal: RecentNotesModalState,
+    tag_panel: TagPanelState,
+    confirm_modal: ConfirmModalState<Message>,
+    /// The toasts that have not expired yet, the newest last. Only the newest one is shown.
+    toasts: Vec<Toast>,
+    /// When Ctrl+C was last pressed.
+    interrupted_at: Option<Instant>,
+    /// The keys of a key sequence that is waiting for the next key.
+    pending_keys: Option<key_sequence::PendingKeys>,
+    /// When the interval of the next autosave started, see [`config::Autosave::IntervalSeconds`].
+    autosaved_at: Option<Instant>,
+    /// The number of autosaves that failed in a row.
+    autosave_failures: u32,
+    /// The OSC 52 escape sequence that is written to the terminal before the next frame, see
+    /// [`clipboard::Copied::Osc52`].
+    osc52: Option<String>,
+}
 
-                match message {
-                    splash::Message::Open => splash_state
-                        .selected()
-                        .and_then(|index| splash_state.clone().get_item(index))
-                        .map(|vault| {
-                            state.with_main_state(MainState::new(&vault.name, vault.entries()))
-                        })
-                        .unwrap_or(state),
-                    _ => state.with_splash_state(splash_state),
-                }
-            }
-            Message::Explorer(message) => {
-                let ScreenState::Main(main_state) = screen else {
-                    return state;
-                };
+fn modal_area_height(size: Size) -> usize {
+    let vertical = Layout::vertical([Constraint::Percentage(50)]).flex(Flex::Center);
+    let [area] = vertical.areas(Rect::new(0, 0, size.width, size.height.saturating_sub(3)));
+    area.height.into()
+}
 
-                let explorer = explorer::update(message.clone(), main_state.explorer.clone());
+#[derive(Clone)]
+enum ScreenState<'a> {
+    Splash(SplashState<'a>),
+    Main(Box<MainState<'a>>),
+    /// Animates the transition to the target screen, which replaces this state once the
+    /// transition has finished.
+    Transitioning(Transition, Box<ScreenState<'a>>),
+}
 
-                match message {
-                    explorer::Message::SwitchPaneNext => state.with_main_state(MainState {
-                        active_pane: ActivePane::NoteEditor,
-                        note_editor: main_state.note_editor.set_active(true),
-                        explorer,
-                        ..*main_state
-                    }),
-                    explorer::Message::SwitchPanePrevious => state.with_main_state(MainState {
-                        active_pane: ActivePane::Outline,
-                        outline: main_state.outline.set_active(true),
-                        explorer,
-                        ..*main_state
-                    }),
-                    explorer::Message::ScrollUp(scroll_amount) => {
-                        state.with_main_state(MainState {
-                            explorer: explorer.previous(calc_scroll_amount(
-                                scroll_amount,
-                                state.screen_size.height.into(),
-                            )),
-                            ..*main_state
-                        })
-                    }
-                    explorer::Message::ScrollDown(scroll_amount) => {
-                        state.with_main_state(MainState {
-                            explorer: explorer.next(calc_scroll_amount(
-                                scroll_amount,
-                                state.screen_size.height.into(),
-                            )),
-                            ..*main_state
-                        })
-                    }
-                    explorer::Message::Toggle => state.with_main_state(match explorer.open {
-                        true => MainState {
-                            explorer,
-                            ..*main_state
-                        },
-                        false => MainState {
-                            active_pane: ActivePane::NoteEditor,
-                            explorer: explorer.set_active(false),
-                            note_editor: main_state.note_editor.set_active(true),
-                            ..*main_state
-                        },
-                    }),
-                    explorer::Message::ToggleOutline => state.with_main_state(MainState {
-                        outline: main_state.outline.toggle(),
-                        ..*main_state
-                    }),
-                    explorer::Message::Open => {
-                        let selected_note = explorer.selected_note.clone().map(SelectedNote::from);
+impl ScreenState<'_> {
+    fn active_pane(&self) -> ActivePane {
+        match self {
+            ScreenState::Splash(..) => ActivePane::Splash,
+            ScreenState::Main(state) => state.active_pane,
+            ScreenState::Transitioning(_, screen) => screen.active_pane(),
+        }
+    }
+}
 
-                        let note_editor = selected_note
-                            .clone()
-                            .map(|note| {
-                                EditorState::default()
-                                    .set_mode(if self.config.experimental_editor {
-                                        main_state.note_editor.mode
-                                    } else {
-                                        Mode::Read
-                                    })
-                                    .set_content(&note.content)
-                                    .set_path(note.path.into())
-                            })
-                            .unwrap_or_default();
+impl<'a> AppState<'a> {
+    pub fn active_component(&self) -> ActivePane {
+        if self.confirm_modal.visible {
+            return ActivePane::ConfirmModal;
+        }
 
-                        let outline = OutlineState::new(
-                            note_editor.nodes(),
-                            note_editor.current_row,
-                            main_state.outline.is_open(),
-                        );
+        if self.input_modal.visible {
+            return ActivePane::InputModal;
+        }
 
-                        state.with_main_state(MainState {
-                            explorer,
-                            outline,
-                            note_editor,
-                            selected_note,
-                            ..*main_state
-                        })
-                    }
-                    _ => state.with_main_state(MainState {
-                        explorer,
-                        ..*main_state
-                    }),
-                }
-            }
-            Message::Outline(message) => {
-                let ScreenState::Main(main_state) = screen else {
-                    return state;
-                };
-
-                let outline = outline::update(message.clone(), main_state.outline.clone());
+        if self.help_modal.visible {
+            return ActivePane::HelpModal;
+        }
 
-                match message {
-                    outline::Message::SwitchPaneNext => state.with_main_state(MainState {
-                        active_pane: ActivePane::Explorer,
-                        explorer: main_state.explorer.set_active(true),
-                        outline,
-                        ..*main_state
-                    }),
-                    outline::Message::SwitchPanePrevious => state.with_main_state(MainState {
-                        active_pane: ActivePane::NoteEditor,
-                        note_editor: main_state.note_editor.set_active(true),
-                        outline,
-                        ..*main_state
-                    }),
-                    outline::Message::Toggle => state.with_main_state(match outline.open {
-                        true => MainState {
-                            outline,
-                            ..*main_state
-                        },
-                        false => MainState {
-                            active_pane: ActivePane::NoteEditor,
-                            outline: outline.set_active(false),
-                            note_editor: main_state.note_editor.set_active(true),
-                            ..*main_state
-                        },
-                    }),
-                    outline::Message::Expand => state.with_main_state(MainState {
-                        outline: main_state.outline.toggle_item(),
-                        ..*main_state
-                    }),
-                    outline::Message::Select => state.with_main_state(MainState {
-                        note_editor: main_state.note_editor.set_row(
-                            outline
-                                .selected()
-                                .map(|item| item.get_range().start)
-                                .unwrap_or_default(),
-                        ),
-                        ..*main_state
-                    }),
+        if self.vault_selector_modal.visible {
+            return ActivePane::VaultSelectorModal;
+        }
 
-                    _ => state.with_main_state(MainState {
-                        outline,
-                        ..*main_state
-                    }),
-                }
-            }
-            Message::NoteEditor(message) => {
-                let ScreenState::Main(main_state) = screen else {
-                    return state;
-                };
+        if self.recent_notes_modal.visible {
+            return ActivePane::RecentNotesModal;
+        }
 
-                let mode = &main_state.note_editor.mode();
+        if self.tag_panel.visible {
+            return ActivePane::TagPanel;
+        }
 
-                let editor_enabled = self.config.experimental_editor;
+        self.screen.active_pane()
+    }
 
-                if editor_enabled {
-                    match message {
-                        note_editor::Message::KeyEvent(key) if *mode == Mode::Edit => {
-                            let note_editor = main_state.note_editor.edit(key.into());
-                            let selected_note = main_state.selected_note.map(|note| SelectedNote {
-                                content: note_editor.content().to_string(),
-                                ..note
-                            });
+    pub fn is_transitioning(&self) -> bool {
+        matches!(self.screen, ScreenState::Transitioning(..))
+    }
 
-                            return state.with_main_state(MainState {
-                                note_editor,
-                                selected_note,
-                                ..*main_state
-                            });
-                        }
-                        note_editor::Message::CursorLeft => {
-                            return state.with_main_state(MainState {
-                                note_editor: main_state.note_editor.cursor_left(),
-                                ..*main_state
-                            })
-                        }
-                        note_editor::Message::CursorRight => {
-                            return state.with_main_state(MainState {
-                                note_editor: main_state.note_editor.cursor_right(),
-                                ..*main_state
-                            })
-                        }
-                        note_editor::Message::CursorWordForward => {
-                            return state.with_main_state(MainState {
-                                note_editor: main_state.note_editor.cursor_word_forward(),
-                                ..*main_state
-                            })
-                        }
-                        note_editor::Message::CursorWordBackward => {
-                            return state.with_main_state(MainState {
-                                note_editor: main_state.note_editor.cursor_word_backward(),
-                                ..*main_state
-                            })
-                        }
-                        note_editor::Message::Delete => {
-                            return state.with_main_state(MainState {
-                                note_editor: main_state.note_editor.delete_char(),
-                                ..*main_state
-                            })
-                        }
-                        note_editor::Message::EditMode if *mode != Mode::Edit => {
-                            if let Some(selected_note) = &main_state.selected_note {
-                                return state.with_main_state(MainState {
-                                    active_pane: ActivePane::NoteEditor,
-                                    note_editor: main_state
-                                        .note_editor
-                                        .clone()
-                                        .set_content(&selected_note.content)
-                                        .set_mode(Mode::Edit),
-                                    ..*main_state
-                                });
-                            } else {
-                                return state;
-                            }
-                        }
-                        note_editor::Message::ReadMode if *mode != Mode::Read => {
-                            return state.with_main_state(MainState {
-                                note_editor: main_state.note_editor.set_mode(Mode::Read),
-                                ..*main_state
-                            })
-                        }
-                        note_editor::Message::ExitMode if *mode == Mode::Read => {
-                            return state.with_main_state(MainState {
-                                note_editor: main_state.note_editor.set_mode(Mode::View),
-                                ..*main_state
-                            })
-                        }
-                        note_editor::Message::ExitMode if *mode == Mode::Edit => {
-                            let note_editor = main_state.note_editor.exit_insert();
-                            let outline = main_state.outline.set_nodes(note_editor.nodes());
-
-                            let selected_note = main_state
-                                .selected_note
-                                .map(|note| SelectedNote {
-                                    content: note_editor.content().to_string(),
-                                    ..note
-                                })
-                                .clone();
+    /// Returns the open vault and note as a session, or `None` if no vault is open.
+    pub fn session(&self) -> Option<Session> {
+        match &self.screen {
+            ScreenState::Main(main_state) if main_state.read_only => None,
+            ScreenState::Main(main_state) => Some(Session {
+                vault: main_state.vault.name.clone(),
+                note: main_state
+                    .selected_note
+                    .as_ref()
+                    .map(|note| PathBuf::from(&note.path)),
+            }),
+            _ => None,
+        }
+    }
 
-                            return state.with_main_state(MainState {
-                                note_editor: note_editor.set_mode(Mode::View),
-                                outline,
-                                selected_note,
-                                ..*main_state
-                            });
-                        }
-                        note_editor::Message::Save => {
-                            let note_editor = main_state.note_editor.save();
-                            let selected_note = main_state.selected_note.map(|note| SelectedNote {
-                                content: note_editor.content().to_string(),
-                                ..note
-                            });
+    pub fn set_running(&self, is_running: bool) -> Self {
+        Self {
+            is_running,
+            ..self.clone()
+        }
+    }
 
-                            return state.with_main_state(MainState {
-                                selected_note,
-                                note_editor,
-                                ..*main_state
-                            });
-                        }
-                        _ => {}
-                    }
-                }
+    fn with_vault_selector_modal_state(
+        &self,
+        vault_selector_modal: VaultSelectorModalState<'a>,
+    ) -> Self {
+        Self {
+            vault_selector_modal,
+            ..self.clone()
+        }
+    }
 
-                match message {
-                    note_editor::Message::CursorUp => {
-                        let note_editor = main_state.note_editor.cursor_up();
-                        let outline = main_state.outline.select_at(note_editor.current_row);
+    fn with_recent_notes_modal_state(&self, recent_notes_modal: RecentNotesModalState) -> Self {
+        Self {
+            recent_notes_modal,
+            ..self.clone()
+        }
+    }
 
-                        state.with_main_state(MainState {
-                            note_editor,
-                            outline,
-                            ..*main_state
-                        })
-                    }
-                    note_editor::Message::CursorDown => {
-                        let note_editor = main_state.note_editor.cursor_down();
-                        let outline = main_state.outline.select_at(note_editor.current_row);
+    fn with_tag_panel_state(&self, tag_panel: TagPanelState) -> Self {
+        Self {
+            tag_panel,
+            ..self.clone()
+        }
+    }
 
-                        state.with_main_state(MainState {
-                            note_editor,
-                            outline,
-                            ..*main_state
-                        })
-                    }
-                    note_editor::Message::ScrollUp(scroll_amount) if *mode != Mode::Edit => state
-                        .with_main_state(MainState {
-                            note_editor: main_state.note_editor.scroll_up(calc_scroll_amount(
-                                scroll_amount,
-                                state.screen_size.height.into(),
-                            )),
-                            ..*main_state
-                        }),
-                    note_editor::Message::ScrollDown(scroll_amount) if *mode != Mode::Edit => state
-                        .with_main_state(MainState {
-                            note_editor: main_state.note_editor.scroll_down(calc_scroll_amount(
-                                scroll_amount,
-                                state.screen_size.height.into(),
-                            )),
-                            ..*main_state
-                        }),
-                    note_editor::Message::ToggleExplorer if *mode != Mode::Edit => state
-                        .with_main_state(match main_state.explorer.open {
-                            true => MainState {
-                                explorer: main_state.explorer.toggle(),
-                                ..*main_state
-                            },
-                            false => MainState {
-                                active_pane: ActivePane::Explorer,
-                                explorer: main_state.explorer.toggle().set_active(true),
-                                note_editor: main_state.note_editor.set_active(false),
-                                ..*main_state
-                            },
-                        }),
-                    note_editor::Message::ToggleOutline if *mode != Mode::Edit => state
-                        .with_main_state(match main_state.outline.open {
-                            true => MainState {
-                                outline: main_state.outline.toggle(),
-                                ..*main_state
-                            },
-                            false => MainState {
-                                active_pane: ActivePane::Outline,
-                                outline: main_state.outline.toggle().set_active(true),
-                                note_editor: main_state.note_editor.set_active(false),
-                                ..*main_state
-                            },
-                        }),
-                    note_editor::Message::SwitchPaneNext => state.with_main_state(MainState {
-                        active_pane: ActivePane::Outline,
-                        note_editor: main_state.note_editor.set_active(false),
-                        outline: main_state.outline.set_active(true),
-                        ..*main_state
-                    }),
-                    note_editor::Message::SwitchPanePrevious => state.with_main_state(MainState {
-                        active_pane: ActivePane::Explorer,
-                        note_editor: main_state.note_editor.set_active(false),
-                        explorer: main_state.explorer.set_active(true),
-                        ..*main_state
-                    }),
-                    note_editor::Message::ScrollUp(_) if *mode == Mode::Edit => state
-                        .with_main_state(MainState {
-                            note_editor: main_state.note_editor.cursor_up(),
-                            ..*main_state
-                        }),
-                    note_editor::Message::ScrollDown(_) if *mode == Mode::Edit => state
-                        .with_main_state(MainState {
-                            note_editor: main_state.note_editor.cursor_down(),
-                            ..*main_state
-                        }),
-                    _ => state,
-                }
-            }
+    fn with_confirm_modal_state(&self, confirm_modal: ConfirmModalState<Message>) -> Self {
+        Self {
+            confirm_modal,
+            ..self.clone()
         }
     }
 
-    fn render_splash(&self, area: Rect, buf: &mut Buffer, state: &mut SplashState<'a>) {
-        Splash::default().render_ref(area, buf, state)
+    fn with_input_modal_state(&self, input_modal: InputModalState) -> Self {
+        Self {
+            input_modal,
+            ..self.clone()
+        }
     }
 
-    fn render_main(&self, area: Rect, buf: &mut Buffer, state: &mut MainState<'a>) {
-        let [content, statusbar] = Layout::vertical([Constraint::Fill(1), Constraint::Length(1)])
-            .horizontal_margin(1)
-            .areas(area);
+    fn with_help_modal_state(&self, help_modal: HelpModalState) -> Self {
+        Self {
+            help_modal,
+            ..self.clone()
+        }
+    }
 
-        let (left, right) = if state.explorer.open {
-            (Constraint::Length(35), Constraint::Fill(1))
-        } else {
-            (Constraint::Length(5), Constraint::Fill(1))
-        };
+    fn with_main_state(&self, main_state: MainState<'a>) -> Self {
+        Self {
+            screen: ScreenState::Main(Box::new(main_state)),
+            ..self.clone()
+        }
+    }
 
-        let [explorer_pane, note, outline] = Layout::horizontal([
-            left,
-            right,
-            if state.outline.is_open() {
-                Constraint::Length(35)
-            } else {
-                Constraint::Length(4)
+    /// Soft-wraps the note editors of the main screen to the current layout, see
+    /// [`MainState::with_wrap_widths`].
+    fn with_wrap_widths(self) -> Self {
+        match self.screen {
+            ScreenState::Main(main_state) => Self {
+                screen: ScreenState::Main(Box::new(
+                    main_state.with_wrap_widths(Rect::from((Position::ORIGIN, self.screen_size))),
+                )),
+                ..self
             },
-        ])
-        .areas(content);
+            _ => self,
+        }
+    }
 
-        Explorer::new().render(explorer_pane, buf, &mut state.explorer);
-        Editor::default().render(note, buf, &mut state.note_editor);
-        Outline.render(outline, buf, &mut state.outline);
+    /// Returns the time until the pending key sequence times out, if a sequence is pending.
+    fn key_sequence_timeout(&self, now: Instant) -> Option<Duration> {
+        self.pending_keys.as_ref().map(|pending_keys| {
+            KEY_SEQUENCE_TIMEOUT
+                .saturating_sub(now.saturating_duration_since(pending_keys.pressed_at))
+        })
+    }
 
-        let (_, counts) = state
-            .selected_note
-            .clone()
-            .map(|note| {
-                let content = note.content.as_str();
-                (
-                    note.name,
-                    (WordCount::from(content), CharCount::from(content)),
-                )
-            })
-            .unzip();
+    /// Returns the time until the next autosave, if notes are saved at an interval and any note
+    /// has unsaved changes. The interval doubles with every failed autosave.
+    fn autosave_timeout(&self, config: &Config, now: Instant) -> Option<Duration> {
+        let config::Autosave::IntervalSeconds(seconds) = config.autosave() else {
+            return None;
+        };
+
+        let ScreenState::Main(main_state) = &self.screen else {
+            return None;
+        };
 
-        let (word_count, char_count) = counts.unwrap_or_default();
+        if main_state.modified_notes().is_empty() {
+            return None;
+        }
 
-        let mut status_bar_state = StatusBarState::new(
-            state.active_pane.into(),
-            word_count.into(),
-            char_count.into(),
-        );
+        let backoff = 2u32.pow(self.autosave_failures.min(MAX_AUTOSAVE_BACKOFF));
+        let interval = Duration::from_secs(seconds) * backoff;
 
-        let status_bar = StatusBar::default();
-        status_bar.render_ref(statusbar, buf, &mut status_bar_state);
+        Some(self.autosaved_at.map_or(Duration::ZERO, |autosaved_at| {
+            interval.saturating_sub(now.saturating_duration_since(autosaved_at))
+        }))
     }
 
-    fn render_modals(&self, area: Rect, buf: &mut Buffer, state: &mut AppState<'a>) {
-        if state.vault_selector_modal.visible {
-            VaultSelectorModal::default().render(area, buf, &mut state.vault_selector_modal);
+    fn with_transition(&self, kind: TransitionKind, screen: ScreenState<'a>) -> Self {
+        Self {
+            screen: ScreenState::Transitioning(Transition::new(kind), Box::new(screen)),
+            ..self.clone()
         }
+    }
 
-        if state.help_modal.visible {
-            HelpModal.render(area, buf, &mut state.help_modal);
+    fn with_splash_state(&self, splash_state: SplashState<'a>) -> Self {
+        Self {
+            screen: ScreenState::Splash(splash_state),
+            ..self.clone()
         }
     }
 }
 
-impl<'a> StatefulWidgetRef for App<'a> {
-    type State = AppState<'a>;
+impl Default for ScreenState<'_> {
+    fn default() -> Self {
+        Self::Splash(SplashState::default())
+    }
+}
 
-    fn render_ref(&self, area: Rect, buf: &mut Buffer, state: &mut Self::State) {
-        match &mut state.screen {
-            ScreenState::Splash(state) => self.render_splash(area, buf, state),
-            ScreenState::Main(state) => self.render_main(area, buf, state),
-        };
+pub mod splash {
+    use ratatui::crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
 
-        self.render_modals(area, buf, state)
+    use crate::splash::SplashState;
+
+    #[derive(Clone, Debug, PartialEq)]
+    pub enum Message {
+        Up,
+        Down,
+        Open,
+        /// Reopens the vault and note of the previous session, if the splash screen offers one.
+        Resume,
+        FilterInput(char),
+        FilterBackspace,
+        ClearFilter,
+    }
+
+    /// Maps printable characters, backspace and Esc to filter messages. Keys with a binding,
+    /// e.g. `j` and `q`, keep their binding until the filter has been started with another
+    /// character.
+    pub fn handle_filter_event(
+        key: &KeyEvent,
+        search_query: &str,
+        is_bound: bool,
+    ) -> Option<Message> {
+        match key.code {
+            KeyCode::Char(_) if search_query.is_empty() && is_bound => None,
+            KeyCode::Char(c)
+                if (key.modifiers - KeyModifiers::SHIFT).is_empty() && !c.is_control() =>
+            {
+                Some(Message::FilterInput(c))
+            }
+            KeyCode::Backspace if !search_query.is_empty() => Some(Message::FilterBackspace),
+            KeyCode::Esc if !search_query.is_empty() => Some(Message::ClearFilter),
+            _ => None,
+        }
+    }
+
+    pub fn update(message: Message, state: SplashState) -> SplashState {
+        match message {
+            Message::Up => state.previous(),
+            Message::Down => state.next(),
+            Message::Open => state.select(),
+            Message::Resume => state,
+            Message::FilterInput(c) => state.filter_input(c),
+            Message::FilterBackspace => state.filter_backspace(),
+            Message::ClearFilter => state.clear_filter(),
+        }
+    }
+}
+
+pub mod explorer {
+    use std::path::PathBuf;
+
+    use crate::explorer::ExplorerState;
+
+    use super::ScrollAmount;
+
+    #[derive(Clone, Debug, PartialEq)]
+    pub enum Message {
+        Up,
+        Down,
+        Open,
+        Sort,
+        Toggle,
+        ToggleOutline,
+        SwitchPaneNext,
+        SwitchPanePrevious,
+        ScrollUp(ScrollAmount),
+        ScrollDown(ScrollAmount),
+        NewNote,
+        CreateNote(String),
+        Delete,
+        DeleteNote,
+        Rename,
+        RenameNote(String),
+        ToggleBookmark,
+        /// Reveals the open note in the explorer.
+        RevealCurrent,
+        Click(usize),
+        BeginMove,
+        DropIntoDirectory(PathBuf),
+        ExpandAll,
+        CollapseAll,
+        /// Opens the note at the cursor in a new tab, keeping the open note in its tab.
+        OpenInNewTab,
+    }
+
+    pub fn update(message: Message, state: ExplorerState) -> ExplorerState {
+        match message {
+            Message::Up => state.previous(1),
+            Message::Down => state.next(1),
+            Message::Sort => state.sort(),
+            Message::Open => state.select(),
+            Message::Toggle => state.toggle(),
+            Message::BeginMove => state.begin_move(state.note_at_cursor()),
+            Message::ExpandAll => state.expand_all(),
+            Message::CollapseAll => state.collapse_all(),
+            Message::SwitchPaneNext | Message::SwitchPanePrevious => {
+                if state.active {
+                    state.set_active(false)
+                } else {
+                    state.set_active(true)
+                }
+            }
+            _ => state,
+        }
+    }
+}
+
+pub mod outline {
+    use ratatui::crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+
+    use crate::outline::OutlineState;
+
+    #[derive(Clone, Debug, PartialEq)]
+    pub enum Message {
+        Up,
+        Down,
+        Select,
+        Open,
+        Expand,
+        Toggle,
+        ToggleExplorer,
+        SwitchPaneNext,
+        SwitchPanePrevious,
+        Click(usize),
+        ActivateSearch,
+        SearchInput(char),
+        SearchBackspace,
+        ClearSearch,
+        /// Selects the first visible heading with the text and moves the editor cursor to it.
+        SelectByHeadingText(String),
+    }
+
+    /// Maps printable characters and backspace to search messages while the search is active.
+    /// Enter moves the editor cursor to the selected heading and Esc clears the search.
+    pub fn handle_search_event(key: &KeyEvent, state: &OutlineState) -> Option<Message> {
+        if !state.is_searching() {
+            return None;
+        }
+
+        match key.code {
+            KeyCode::Char(c)
+                if (key.modifiers - KeyModifiers::SHIFT).is_empty() && !c.is_control() =>
+            {
+                Some(Message::SearchInput(c))
+            }
+            KeyCode::Backspace => Some(Message::SearchBackspace),
+            KeyCode::Enter => Some(Message::Select),
+            KeyCode::Esc => Some(Message::ClearSearch),
+            _ => None,
+        }
+    }
+
+    pub fn update(message: Message, state: OutlineState) -> OutlineState {
+        match message {
+            Message::Up => state.previous(1),
+            Message::Down => state.next(1),
+            Message::Toggle => state.toggle(),
+            Message::ActivateSearch => state.search(""),
+            Message::SearchInput(c) => state.search_input(c),
+            Message::SearchBackspace => state.search_backspace(),
+            Message::ClearSearch => state.clear_search(),
+            Message::SelectByHeadingText(text) => match state.find_by_heading_text(&text) {
+                Some(index) => state.select_index(index),
+                None => state,
+            },
+            Message::SwitchPaneNext | Message::SwitchPanePrevious => {
+                if state.active {
+                    state.set_active(false)
+                } else {
+                    state.set_active(true)
+                }
+            }
+            _ => state,
+        }
+    }
+}
+
+pub mod note_editor {
+    use std::path::PathBuf;
+
+    use ratatui::crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+
+    use super::ScrollAmount;
+
+    #[derive(Clone, Debug, PartialEq)]
+    pub enum Message {
+        Save,
+        SwitchPaneNext,
+        SwitchPanePrevious,
+        ToggleExplorer,
+        ToggleOutline,
+        EditMode,
+        ExitMode,
+        ReadMode,
+        NormalMode,
+        NormalKey(char),
+        KeyEvent(KeyEvent),
+        CursorUp,
+        CursorLeft,
+        CursorRight,
+        CursorWordForward,
+        CursorWordBackward,
+        CursorDown,
+        ScrollUp(ScrollAmount),
+        ScrollDown(ScrollAmount),
+        Delete,
+        IndentLine,
+        OutdentLine,
+        ToggleFold,
+        FoldAll,
+        UnfoldAll,
+        ToggleProperties,
+        FindStart,
+        FindInput(char),
+        FindBackspace,
+        FindConfirm,
+        FindCancel,
+        FindNext,
+        FindPrev,
+        Undo,
+        Redo,
+        ToggleTask,
+        DeleteLine,
+        InsertLineBelow,
+        InsertLineAbove,
+        FollowLink,
+        /// Opens the note named by the wiki link or the word at the cursor, see
+        /// [`EditorState::word_at_cursor`].
+        JumpToWikiLink,
+        Back,
+        Focus,
+        SplitVertical,
+        CloseSplit,
+        FocusNextSplit,
+        FocusSplit(usize),
+        NextTab,
+        PreviousTab,
+        CloseTab,
+        ExportHtml,
+        /// Asks for the file to export the note to.
+        Export,
+        ExportTo(PathBuf),
+        CopyObsidianUri,
+        /// Copies the selection in edit mode, or the source of the current block, to the
+        /// clipboard.
+        CopyBlock,
+        /// Copies the content of the note to the clipboard.
+        CopyNote,
+        OpenPrevious,
+        OpenExternally,
+        /// Reads the open note again from disk, discarding unsaved changes.
+        ReloadFromDisk,
+    }
+
+    pub fn handle_editing_event(key: &KeyEvent) -> Option<Message> {
+        // The text area keeps its own history for the current block, which would go out of sync
+        // with the note content, so the undo and redo keys are routed to the editor state instead.
+        if key.modifiers == KeyModifiers::CONTROL {
+            match key.code {
+                KeyCode::Char('u') => return Some(Message::Undo),
+                KeyCode::Char('r') => return Some(Message::Redo),
+                KeyCode::Char('c') => return Some(Message::CopyBlock),
+                _ => {}
+            }
+        }
+
+        match key.code {
+            KeyCode::Up => Some(Message::CursorUp),
+            KeyCode::Down => Some(Message::CursorDown),
+            KeyCode::Esc => Some(Message::ExitMode),
+            KeyCode::Backspace => Some(Message::Delete),
+            KeyCode::Tab => Some(Message::IndentLine),
+            KeyCode::BackTab => Some(Message::OutdentLine),
+            _ => Some(Message::KeyEvent(*key)),
+        }
+    }
+
+    pub fn handle_find_event(key: &KeyEvent) -> Option<Message> {
+        match key.code {
+            KeyCode::Esc => Some(Message::FindCancel),
+            KeyCode::Enter => Some(Message::FindConfirm),
+            KeyCode::Backspace => Some(Message::FindBackspace),
+            KeyCode::Char(c) => Some(Message::FindInput(c)),
+            _ => None,
+        }
+    }
+}
+
+pub mod help_modal {
+    use crate::help_modal::HelpModalState;
+
+    use super::ScrollAmount;
+
+    #[derive(Clone, Debug, PartialEq)]
+    pub enum Message {
+        Toggle,
+        Close,
+        ScrollUp(ScrollAmount),
+        ScrollDown(ScrollAmount),
+    }
+
+    pub fn update(message: Message, state: HelpModalState) -> HelpModalState {
+        match message {
+            Message::Toggle => state.toggle_visibility(),
+            Message::Close => state.hide(),
+            _ => state,
+        }
+    }
+}
+
+pub mod input_modal {
+    use ratatui::crossterm::event::{KeyCode, KeyEvent};
+
+    use crate::input_modal::{InputKind, InputModalState};
+
+    #[derive(Clone, Debug, PartialEq)]
+    pub enum Message {
+        Input(char),
+        Backspace,
+        Confirm,
+        Cancel,
+    }
+
+    pub fn handle_event(key: &KeyEvent, kind: InputKind) -> Option<Message> {
+        if kind.is_confirmation() {
+            return match key.code {
+                KeyCode::Char('y') | KeyCode::Enter => Some(Message::Confirm),
+                KeyCode::Char('n') | KeyCode::Esc => Some(Message::Cancel),
+                _ => None,
+            };
+        }
+
+        match key.code {
+            KeyCode::Esc => Some(Message::Cancel),
+            KeyCode::Enter => Some(Message::Confirm),
+            KeyCode::Backspace => Some(Message::Backspace),
+            KeyCode::Char(c) => Some(Message::Input(c)),
+            _ => None,
+        }
+    }
+
+    pub fn update(message: Message, state: InputModalState) -> InputModalState {
+        match message {
+            Message::Input(c) => state.input(c),
+            Message::Backspace => state.backspace(),
+            Message::Confirm | Message::Cancel => state.hide(),
+        }
+    }
+}
+
+pub mod vault_selector_modal {
+    use ratatui::crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+
+    use crate::vault_selector_modal::VaultSelectorModalState;
+
+    #[derive(Clone, Debug, PartialEq)]
+    pub enum Message {
+        Toggle,
+        Up,
+        Down,
+        Select,
+        Close,
+        FilterInput(char),
+        FilterBackspace,
+        ClearFilter,
+    }
+
+    /// Maps printable characters and backspace to filter messages. Esc clears a non-empty filter
+    /// before it closes the modal.
+    pub fn handle_filter_event(key: &KeyEvent, search_query: &str) -> Option<Message> {
+        match key.code {
+            KeyCode::Char(c)
+                if (key.modifiers - KeyModifiers::SHIFT).is_empty() && !c.is_control() =>
+            {
+                Some(Message::FilterInput(c))
+            }
+            KeyCode::Backspace => Some(Message::FilterBackspace),
+            KeyCode::Esc if !search_query.is_empty() => Some(Message::ClearFilter),
+            _ => None,
+        }
+    }
+
+    pub fn update(message: Message, state: VaultSelectorModalState) -> VaultSelectorModalState {
+        match message {
+            Message::Up => state.previous(),
+            Message::Down => state.next(),
+            Message::Toggle => state.toggle_visibility(),
+            Message::Select => state.select(),
+            Message::Close => state.hide(),
+            Message::FilterInput(c) => state.filter_input(c),
+            Message::FilterBackspace => state.filter_backspace(),
+            Message::ClearFilter => state.clear_filter(),
+        }
+    }
+}
+
+pub mod recent_notes_modal {
+    use crate::recent_notes_modal::RecentNotesModalState;
+
+    #[derive(Clone, Debug, PartialEq)]
+    pub enum Message {
+        Toggle,
+        Up,
+        Down,
+        Select,
+        Close,
+    }
+
+    pub fn update(message: Message, state: RecentNotesModalState) -> RecentNotesModalState {
+        match message {
+            Message::Up => state.previous(),
+            Message::Down => state.next(),
+            Message::Close => state.hide(),
+            _ => state,
+        }
+    }
+}
+
+pub mod tag_panel {
+    use crate::tag_panel::TagPanelState;
+
+    #[derive(Clone, Debug, PartialEq)]
+    pub enum Message {
+        Toggle,
+        Up,
+        Down,
+        /// Lists the notes of the selected tag, or opens the selected note.
+        Select,
+        Back,
+    }
+
+    pub fn update(message: Message, state: TagPanelState) -> TagPanelState {
+        match message {
+            Message::Up => state.previous(),
+            Message::Down => state.next(),
+            Message::Back => state.back(),
+            _ => state,
+        }
+    }
+}
+
+pub mod confirm_modal {
+    use crate::confirm_modal::ConfirmModalState;
+
+    #[derive(Clone, Debug, PartialEq)]
+    pub enum Message {
+        /// Saves the unsaved changes and performs the confirmed message.
+        Save,
+        /// Discards the unsaved changes and performs the confirmed message.
+        Discard,
+        Cancel,
+    }
+
+    pub fn update(
+        _message: Message,
+        state: ConfirmModalState<super::Message>,
+    ) -> ConfirmModalState<super::Message> {
+        state.hide()
+    }
+}
+
+pub mod key_sequence {
+    use std::time::Instant;
+
+    use crate::config::KeySequence;
+
+    /// The keys of a key sequence that have been pressed so far.
+    #[derive(Clone, Debug, PartialEq)]
+    pub struct PendingKeys {
+        pub keys: KeySequence,
+        /// The message the pressed keys are bound to on their own, which is sent when the
+        /// sequence times out.
+        pub fallback: Option<Box<super::Message>>,
+        pub pressed_at: Instant,
+    }
+
+    #[derive(Clone, Debug, PartialEq)]
+    pub enum Message {
+        /// Waits for the next key of the sequence.
+        Pending(PendingKeys),
+        /// Ends the sequence with the messages of the pressed keys, which are sent in order.
+        Complete(Vec<super::Message>),
+        /// Ends the sequence with the fallback message, as the next key was not pressed in time.
+        Timeout,
+        Cancel,
+    }
+
+    /// Returns the pending keys after the message, and the messages to send when the sequence
+    /// has ended.
+    pub fn update(
+        message: Message,
+        pending_keys: Option<PendingKeys>,
+    ) -> (Option<PendingKeys>, Vec<super::Message>) {
+        match message {
+            Message::Pending(pending_keys) => (Some(pending_keys), vec![]),
+            Message::Complete(messages) => (None, messages),
+            Message::Timeout => (
+                None,
+                pending_keys
+                    .and_then(|pending_keys| pending_keys.fallback)
+                    .map(|message| *message)
+                    .into_iter()
+                    .collect(),
+            ),
+            Message::Cancel => (None, vec![]),
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum Message {
+    Quit,
+    /// Reloads the configuration file, see [`update_with_config`].
+    ReloadConfig,
+    /// Ctrl+C was pressed at the given instant.
+    Interrupt(Instant),
+    /// Saves the notes with unsaved changes when the autosave interval has passed at the given
+    /// instant, see [`config::Autosave::IntervalSeconds`].
+    Autosave(Instant),
+    Resize(Size),
+    /// Advances animations and counts down the toasts, emitted on every [`TICK_RATE`] while an
+    /// animation is running or a toast is shown.
+    Tick,
+    /// Shows a toast that expires after the configured duration, see [`Config::toast_duration`].
+    Toast(ToastKind, String),
+    KeySequence(key_sequence::Message),
+
+    Splash(splash::Message),
+    Explorer(explorer::Message),
+    NoteEditor(note_editor::Message),
+    Outline(outline::Message),
+    HelpModal(help_modal::Message),
+    InputModal(input_modal::Message),
+    VaultSelectorModal(vault_selector_modal::Message),
+    RecentNotesModal(recent_notes_modal::Message),
+    TagPanel(tag_panel::Message),
+    ConfirmModal(confirm_modal::Message),
+}
+
+#[derive(Debug, Default, Clone, Copy, PartialEq)]
+pub enum ActivePane {
+    #[default]
+    Splash,
+    Explorer,
+    NoteEditor,
+    Outline,
+    HelpModal,
+    InputModal,
+    VaultSelectorModal,
+    RecentNotesModal,
+    TagPanel,
+    ConfirmModal,
+}
+
+impl From<ActivePane> for &str {
+    fn from(value: ActivePane) -> Self {
+        match value {
+            ActivePane::Splash => "Splash",
+            ActivePane::Explorer => "Explorer",
+            ActivePane::NoteEditor => "Note Editor",
+            ActivePane::Outline => "Outline",
+            ActivePane::HelpModal => "Help",
+            ActivePane::InputModal => "Input",
+            ActivePane::VaultSelectorModal => "Vault Selector",
+            ActivePane::RecentNotesModal => "Recent Notes",
+            ActivePane::TagPanel => "Tags",
+            ActivePane::ConfirmModal => "Confirm",
+        }
+    }
+}
+
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct SelectedNote {
+    name: String,
+    path: String,
+    content: String,
+}
+
+impl SelectedNote {
+    /// Reads the note from disk. Attachments are not read, they are shown as an info panel
+    /// instead.
+    ///
+    /// When the note cannot be read, the note is returned without content along with the error,
+    /// so that an unreadable note can be told apart from an empty one. Notes larger than
+    /// `max_bytes` are not read, see [`Note::read_to_string_checked`].
+    fn read(note: &Note, max_bytes: u64) -> (Self, Option<obsidian::Error>) {
+        let (content, error) = match note.kind().is_markdown() {
+            true => match note.read_to_string_checked(max_bytes) {
+                Ok(content) => (content, None),
+                Err(error) => (String::new(), Some(error)),
+            },
+            false => (String::new(), None),
+        };
+
+        let selected_note = Self {
+            name: note.name.clone(),
+            path: note.path.to_string_lossy().to_string(),
+            content,
+        };
+
+        (selected_note, error)
+    }
+}
+
+/// Returns the note name entered in an input, without surrounding whitespace and the `.md`
+/// extension, or `None` if it is empty.
+fn note_name(input: &str) -> Option<&str> {
+    let name = input.trim();
+    let name = name.strip_suffix(".md").unwrap_or(name).trim_end();
+    (!name.is_empty()).then_some(name)
+}
+
+fn version() -> String {
+    stylized_text::stylize(&format!("{VERSION}~beta"), FontStyle::Script)
+}
+
+/// Returns the help text with the key bindings of the given configuration.
+fn help_text(config: &Config) -> String {
+    HELP_TEXT
+        .replace("%version-notice", &version())
+        .replace("%key-bindings", &config::render_help(config))
+}
+
+pub struct App<'a> {
+    state: AppState<'a>,
+    config: Config,
+    terminal: RefCell<DefaultTerminal>,
+}
+
+impl<'a> App<'a> {
+    /// Creates the application with the user config. If the user config cannot be loaded, the
+    /// base config is used and the error is shown as a toast.
+    ///
+    /// Returns an [`Error`](error::Error) if the base config cannot be loaded either.
+    pub fn new(state: AppState<'a>, terminal: DefaultTerminal) -> error::Result<Self> {
+        let (config, config_error) = match config::load() {
+            Ok(config) => (config, None),
+            Err(error) => (config::load_base()?, Some(error)),
+        };
+
+        let mut app = Self {
+            state: AppState {
+                help_modal: HelpModalState::new(&help_text(&config)),
+                ..state
+            },
+            config,
+            terminal: RefCell::new(terminal),
+        };
+
+        let messages = [
+            config_error.map(|error| {
+                toast(
+                    ToastKind::Error,
+                    &format!("Failed to load user config: {error}"),
+                )
+            }),
+            conflicts_toast(&app.config),
+        ];
+
+        app.state = messages
+            .into_iter()
+            .flatten()
+            .fold(app.state.clone(), |state, message| {
+                update(&app.config, state, message)
+            });
+
+        Ok(app)
+    }
+
+    /// Starts the application. If a vault is given, the vault is opened instead of showing the
+    /// splash screen. If a note path is given, the vault containing the note is opened and the
+    /// note is selected in the explorer. If a line is given as well, the cursor is moved to the
+    /// node at the line (1-based) of the note.
+    ///
+    /// Without a vault or a note path, the splash screen offers to resume the vault and note of
+    /// the previous session when restoring sessions is enabled. A stale session is not offered.
+    ///
+    /// If the Obsidian config could not be loaded, the error is shown on the splash screen
+    /// instead of the vaults.
+    pub fn start(
+        terminal: DefaultTerminal,
+        obsidian_config: std::result::Result<&ObsidianConfig, &obsidian::Error>,
+        note_path: Option<&Path>,
+        note_line: Option<usize>,
+        vault: Option<&Vault>,
+    ) -> error::Result<()> {
+        let version = version();
+        let size = terminal.size()?;
+
+        let empty_config = ObsidianConfig::default();
+        let (vaults, splash_state) = match obsidian_config {
+            Ok(config) => (
+                config.vaults_by_recent(),
+                SplashState::new(&version, config),
+            ),
+            Err(error) => (
+                vec![],
+                SplashState::new(&version, &empty_config).with_error(error),
+            ),
+        };
+
+        let state = AppState {
+            screen_size: size,
+            vault_selector_modal: VaultSelectorModalState::new(vaults.clone()),
+            ..Default::default()
+        }
+        .with_splash_state(splash_state);
+
+        let mut app = App::new(state, terminal)?;
+
+        let previous_session = match (vault, note_path) {
+            (None, None) if app.config.restore_session() => session::load()
+                .ok()
+                .flatten()
+                .and_then(|session| Some((session.resolve(&vaults)?, session.note))),
+            _ => None,
+        };
+
+        if let (Some((vault, note)), ScreenState::Splash(splash_state)) =
+            (previous_session, &app.state.screen)
+        {
+            app.state = app
+                .state
+                .with_splash_state(splash_state.clone().with_session(vault, note));
+        }
+
+        if let Some(vault) = vault {
+            let (main_state, message) = MainState::new(&app.config, vault);
+            app.state = message
+                .into_iter()
+                .fold(app.state.with_main_state(main_state), |state, message| {
+                    update(&app.config, state, message)
+                });
+        }
+
+        if let Some(note_path) = note_path {
+            let message = match vaults
+                .into_iter()
+                .find(|vault| note_path.starts_with(&vault.path))
+            {
+                Some(vault) => {
+                    let (main_state, message) = MainState::new(&app.config, vault);
+                    app.state = message.into_iter().fold(
+                        app.state.with_main_state(main_state.reveal(note_path)),
+                        |state, message| update(&app.config, state, message),
+                    );
+                    Message::Explorer(explorer::Message::Open)
+                }
+                None => toast(
+                    ToastKind::Error,
+                    &format!("Note is not in any vault: {}", note_path.display()),
+                ),
+            };
+
+            app.state = update(&app.config, app.state.clone(), message);
+        }
+
+        if let (Some(line), ScreenState::Main(main_state)) = (note_line, &app.state.screen) {
+            let offset = line_offset(main_state.note_editor.content(), line);
+
+            app.state = app.state.clone().with_main_state(MainState {
+                note_editor: main_state.note_editor.clone().set_cursor_to_offset(offset),
+                ..*main_state.clone()
+            });
+        }
+
+        Ok(app.run_with_mouse()?)
+    }
+
+    /// Starts the application as a read-only pager of the markdown content, e.g. for
+    /// `basalt --render`. The Obsidian config is not read and no vault is opened, so there is no
+    /// explorer, and the note cannot be edited or saved.
+    pub fn start_read_only(
+        terminal: DefaultTerminal,
+        name: &str,
+        content: &str,
+    ) -> error::Result<()> {
+        let state = AppState {
+            screen_size: terminal.size()?,
+            ..Default::default()
+        }
+        .with_main_state(MainState::read_only(name, content));
+
+        Ok(App::new(state, terminal)?.run_with_mouse()?)
+    }
+
+    /// Runs the event loop, capturing the mouse while running if it is enabled in the config.
+    ///
+    /// The panic hook installed by [`ratatui::init`] restores the terminal, but leaves the mouse
+    /// captured, so the capture is released in the hook as well.
+    fn run_with_mouse(&'a mut self) -> Result<()> {
+        let mouse = self.config.mouse();
+
+        if mouse {
+            let hook = panic::take_hook();
+            panic::set_hook(Box::new(move |info| {
+                _ = execute!(io::stdout(), DisableMouseCapture);
+                hook(info);
+            }));
+
+            execute!(io::stdout(), EnableMouseCapture)?;
+        }
+
+        let result = self.run();
+
+        if mouse {
+            execute!(io::stdout(), DisableMouseCapture)?;
+        }
+
+        result
+    }
+
+    fn run(&'a mut self) -> Result<()> {
+        self.state.is_running = true;
+        let mut last_tick = Instant::now();
+
+        while self.state.is_running {
+            if let Some(sequence) = self.state.osc52.take() {
+                let result =
+                    clipboard::write_osc52(self.terminal.borrow_mut().backend_mut(), &sequence);
+
+                if let Err(error) = result {
+                    self.state = update(
+                        &self.config,
+                        self.state.clone(),
+                        toast(ToastKind::Error, &format!("Failed to copy: {error}")),
+                    );
+                }
+            }
+
+            self.draw(&mut self.state.clone())?;
+
+            // Wait for the next event only until the next tick is due while an animation is
+            // running or a toast is shown, so they progress without user input
+            let tick_timeout = (self.state.is_transitioning() || !self.state.toasts.is_empty())
+                .then(|| TICK_RATE.saturating_sub(last_tick.elapsed()));
+
+            let timeout = [
+                self.state.key_sequence_timeout(Instant::now()),
+                self.state.autosave_timeout(&self.config, Instant::now()),
+                tick_timeout,
+            ]
+            .into_iter()
+            .flatten()
+            .min();
+
+            let event = match timeout {
+                Some(timeout) if !event::poll(timeout)? => None,
+                _ => Some(event::read()?),
+            };
+
+            if let Some(message) = event.and_then(|event| self.handle_event(&event)) {
+                self.state = update_with_config(&mut self.config, self.state.clone(), message);
+            }
+
+            if self
+                .state
+                .key_sequence_timeout(Instant::now())
+                .is_some_and(|timeout| timeout.is_zero())
+            {
+                self.state = update_with_config(
+                    &mut self.config,
+                    self.state.clone(),
+                    Message::KeySequence(key_sequence::Message::Timeout),
+                );
+            }
+
+            if self
+                .state
+                .autosave_timeout(&self.config, Instant::now())
+                .is_some_and(|timeout| timeout.is_zero())
+            {
+                self.state = update(
+                    &self.config,
+                    self.state.clone(),
+                    Message::Autosave(Instant::now()),
+                );
+            }
+
+            if last_tick.elapsed() >= TICK_RATE {
+                self.state = update(&self.config, self.state.clone(), Message::Tick);
+                last_tick = Instant::now();
+            }
+        }
+
+        if self.config.restore_session() {
+            if let Some(session) = self.state.session() {
+                // The session is a convenience, failing to store it should not fail the exit
+                _ = session::save(&session);
+            }
+        }
+
+        Ok(())
+    }
+
+    fn draw(&self, state: &'a mut AppState<'a>) -> Result<()> {
+        let mut terminal = self.terminal.borrow_mut();
+
+        terminal.draw(move |frame| {
+            let area = frame.area();
+            let buf = frame.buffer_mut();
+            self.render_ref(area, buf, state);
+        })?;
+
+        Ok(())
+    }
+
+    fn handle_event(&self, event: &Event) -> Option<Message> {
+        match event {
+            Event::Resize(cols, rows) => Some(Message::Resize(Size::new(*cols, *rows))),
+            Event::Key(key_event) if key_event.kind == KeyEventKind::Press => {
+                handle_key_event(&self.config, &self.state, key_event)
+            }
+            Event::Mouse(mouse_event) if self.config.mouse() => {
+                self.handle_mouse_event(mouse_event)
+            }
+            _ => None,
+        }
+    }
+
+    fn handle_mouse_event(&self, mouse: &MouseEvent) -> Option<Message> {
+        let scroll = |up: Message, down: Message| match mouse.kind {
+            MouseEventKind::ScrollUp => Some(up),
+            MouseEventKind::ScrollDown => Some(down),
+            _ => None,
+        };
+
+        match self.state.active_component() {
+            ActivePane::HelpModal => scroll(
+                Message::HelpModal(help_modal::Message::ScrollUp(ScrollAmount::One)),
+                Message::HelpModal(help_modal::Message::ScrollDown(ScrollAmount::One)),
+            ),
+            ActivePane::VaultSelectorModal => scroll(
+                Message::VaultSelectorModal(vault_selector_modal::Message::Up),
+                Message::VaultSelectorModal(vault_selector_modal::Message::Down),
+            ),
+            ActivePane::RecentNotesModal => scroll(
+                Message::RecentNotesModal(recent_notes_modal::Message::Up),
+                Message::RecentNotesModal(recent_notes_modal::Message::Down),
+            ),
+            ActivePane::TagPanel => scroll(
+                Message::TagPanel(tag_panel::Message::Up),
+                Message::TagPanel(tag_panel::Message::Down),
+            ),
+            ActivePane::Splash => scroll(
+                Message::Splash(splash::Message::Up),
+                Message::Splash(splash::Message::Down),
+            ),
+            ActivePane::InputModal | ActivePane::ConfirmModal => None,
+            ActivePane::Explorer | ActivePane::NoteEditor | ActivePane::Outline => {
+                let ScreenState::Main(state) = &self.state.screen else {
+                    return None;
+                };
+
+                let layout = state.layout(Rect::from((Position::ORIGIN, self.state.screen_size)));
+
+                let pane = layout.pane_at(Position::new(mouse.column, mouse.row))?;
+
+                if matches!(
+                    mouse.kind,
+                    MouseEventKind::ScrollUp | MouseEventKind::ScrollDown
+                ) {
+                    return match pane {
+                        ActivePane::Explorer => scroll(
+                            Message::Explorer(explorer::Message::ScrollUp(ScrollAmount::One)),
+                            Message::Explorer(explorer::Message::ScrollDown(ScrollAmount::One)),
+                        ),
+                        ActivePane::Outline => scroll(
+                            Message::Outline(outline::Message::Up),
+                            Message::Outline(outline::Message::Down),
+                        ),
+                        _ => scroll(
+                            Message::NoteEditor(note_editor::Message::ScrollUp(ScrollAmount::One)),
+                            Message::NoteEditor(note_editor::Message::ScrollDown(
+                                ScrollAmount::One,
+                            )),
+                        ),
+                    };
+                }
+
+                if mouse.kind != MouseEventKind::Down(MouseButton::Left) {
+                    return None;
+                }
+
+                match pane {
+                    ActivePane::Explorer => {
+                        // The explorer adjusts its offset to the height of the list on render
+                        let mut explorer = state.explorer.clone();
+                        explorer.update_offset_mut(layout.explorer.height.saturating_sub(2).into());
+
+                        list_index_at(
+                            layout.explorer,
+                            &explorer.list_state,
+                            explorer.flat_items.len(),
+                            mouse.row,
+                        )
+                        .map(|index| Message::Explorer(explorer::Message::Click(index)))
+                    }
+                    ActivePane::Outline => list_index_at(
+                        layout.outline,
+                        &state.outline.list_state,
+                        state.outline.item_count(),
+                        mouse.row,
+                    )
+                    .map(|index| Message::Outline(outline::Message::Click(index))),
+                    _ if !state.splits.is_empty() => layout
+                        .splits(state.splits.len())
+                        .into_iter()
+                        .position(|area| area.contains(Position::new(mouse.column, mouse.row)))
+                        .map(|index| Message::NoteEditor(note_editor::Message::FocusSplit(index))),
+                    _ => Some(Message::NoteEditor(note_editor::Message::Focus)),
+                }
+            }
+        }
+    }
+
+    fn render_screen(
+        &self,
+        area: Rect,
+        buf: &mut Buffer,
+        screen: &mut ScreenState<'a>,
+        pending_keys: Option<String>,
+    ) {
+        match screen {
+            ScreenState::Splash(state) => self.render_splash(area, buf, state),
+            ScreenState::Main(state) => self.render_main(area, buf, state, pending_keys),
+            ScreenState::Transitioning(transition, screen) => {
+                self.render_screen(area, buf, screen, pending_keys);
+                transition.apply(area, buf);
+            }
+        }
+    }
+
+    fn render_splash(&self, area: Rect, buf: &mut Buffer, state: &mut SplashState<'a>) {
+        Splash::default()
+            .with_theme(&self.config.theme)
+            .render_ref(area, buf, state)
+    }
+
+    fn render_main(
+        &self,
+        area: Rect,
+        buf: &mut Buffer,
+        state: &mut MainState<'a>,
+        pending_keys: Option<String>,
+    ) {
+        let layout = state.layout(area);
+        let MainLayout {
+            explorer,
+            note_editor,
+            outline,
+            statusbar,
+            tab_bar,
+        } = layout;
+
+        let theme = &self.config.theme;
+
+        if !state.tabs.is_empty() {
+            let tabs = state
+                .all_panes()
+                .into_iter()
+                .map(|pane| Tab {
+                    name: pane.selected_note.map(|note| note.name).unwrap_or_default(),
+                    modified: pane.note_editor.modified,
+                })
+                .collect();
+
+            TabBar::new(tabs, state.active_tab)
+                .with_theme(theme)
+                .render(tab_bar, buf);
+        }
+
+        if !state.read_only {
+            Explorer::new()
+                .with_theme(theme)
+                .with_task_stats(state.note_editor.task_stats())
+                .render(explorer, buf, &mut state.explorer);
+        }
+
+        if state.splits.is_empty() {
+            Editor::default()
+                .with_theme(theme)
+                .with_wrap_code(self.config.wrap_code_blocks())
+                .with_callout_colors(self.config.callout_colors())
+                .render(note_editor, buf, &mut state.note_editor);
+        } else {
+            layout
+                .splits(state.splits.len())
+                .into_iter()
+                .enumerate()
+                .for_each(|(index, area)| {
+                    let note_editor = if index == state.active_split {
+                        &mut state.note_editor
+                    } else {
+                        &mut state.splits[index].note_editor
+                    };
+
+                    Editor::default()
+                        .with_theme(theme)
+                        .with_wrap_code(self.config.wrap_code_blocks())
+                        .with_callout_colors(self.config.callout_colors())
+                        .render(area, buf, note_editor);
+                });
+        }
+        Outline::default()
+            .with_theme(theme)
+            .render(outline, buf, &mut state.outline);
+
+        let (note_path, counts) = state
+            .selected_note
+            .clone()
+            .map(|note| {
+                let content = note.content.as_str();
+                let path = Path::new(&note.path);
+                (
+                    path.strip_prefix(&state.vault.path)
+                        .unwrap_or(path)
+                        .to_string_lossy()
+                        .to_string(),
+                    (
+                        markdown::counts_with_words_per_minute(
+                            &markdown::from_str(content),
+                            self.config.words_per_minute(),
+                        ),
+                        // Characters are counted as the user perceives them, so that e.g. an
+                        // emoji counts as one
+                        CharCount::graphemes(content),
+                    ),
+                )
+            })
+            .unzip();
+
+        let (counts, char_count) = counts.unwrap_or_default();
+
+        let mut status_bar_state = StatusBarState::new(
+            state.active_pane.into(),
+            counts.words,
+            char_count.into(),
+            state
+                .note_editor
+                .active()
+                .then(|| state.note_editor.cursor_position()),
+        )
+        .with_mode(note_path.is_some().then(|| state.note_editor.mode_label()))
+        .with_note_path(note_path)
+        .with_modified(state.note_editor.modified)
+        .with_reading_minutes(counts.reading_minutes)
+        .with_pending_keys(pending_keys);
+
+        let status_bar = StatusBar::default().with_theme(theme);
+        status_bar.render_ref(statusbar, buf, &mut status_bar_state);
+    }
+
+    fn render_modals(&self, area: Rect, buf: &mut Buffer, state: &mut AppState<'a>) {
+        if state.vault_selector_modal.visible {
+            VaultSelectorModal::default()
+                .with_theme(&self.config.theme)
+                .render(area, buf, &mut state.vault_selector_modal);
+        }
+
+        if state.help_modal.visible {
+            HelpModal::default().with_theme(&self.config.theme).render(
+                area,
+                buf,
+                &mut state.help_modal,
+            );
+        }
+
+        if state.recent_notes_modal.visible {
+            RecentNotesModal::default()
+                .with_theme(&self.config.theme)
+                .render(area, buf, &mut state.recent_notes_modal);
+        }
+
+        if state.tag_panel.visible {
+            TagPanel::default().with_theme(&self.config.theme).render(
+                area,
+                buf,
+                &mut state.tag_panel,
+            );
+        }
+
+        if state.input_modal.visible {
+            InputModal::default().with_theme(&self.config.theme).render(
+                area,
+                buf,
+                &mut state.input_modal,
+            );
+        }
+
+        if state.confirm_modal.visible {
+            ConfirmModal::default()
+                .with_theme(&self.config.theme)
+                .render(area, buf, &mut state.confirm_modal);
+        }
+
+        render_toasts(area, buf, &mut state.toasts);
+    }
+}
+
+/// Renders the newest toast on top of the screen.
+fn render_toasts(area: Rect, buf: &mut Buffer, toasts: &mut [Toast]) {
+    if let Some(toast) = toasts.last_mut() {
+        ToastPopup.render(area, buf, toast);
+    }
+}
+
+#[rustfmt::skip]
+fn handle_active_component_event(config: &Config, state: &AppState, key: &KeyEvent, active_component: ActivePane) -> Option<Message> {
+    match active_component {
+        ActivePane::Splash => {
+            match &state.screen {
+                ScreenState::Splash(splash_state) => splash::handle_filter_event(
+                    key,
+                    splash_state.search_query(),
+                    is_splash_key_bound(config, state, key),
+                )
+                .map(Message::Splash)
+                .or_else(|| config.splash.key_to_message(key.into())),
+                _ => config.splash.key_to_message(key.into()),
+            }
+        },
+        ActivePane::Explorer => config.explorer.key_to_message(key.into()),
+        ActivePane::NoteEditor => {
+            match &state.screen {
+                ScreenState::Main(state) if state.note_editor.is_editing() => {
+                    note_editor::handle_editing_event(key).map(Message::NoteEditor)
+                },
+                ScreenState::Main(state) if state.note_editor.find().is_prompt_open() => {
+                    note_editor::handle_find_event(key).map(Message::NoteEditor)
+                },
+                ScreenState::Main(state) if state.note_editor.find().is_active() && key.code == KeyCode::Esc => {
+                    Some(Message::NoteEditor(note_editor::Message::FindCancel))
+                },
+                ScreenState::Main(state) if state.note_editor.mode() == Mode::Normal => {
+                    state.note_editor.vim().handle_key(key, Instant::now())
+                        .map(Message::NoteEditor)
+                        .or_else(|| config.note_editor.key_to_message(key.into()))
+                },
+                ScreenState::Main(_) =>
+                    config.note_editor.key_to_message(key.into()),
+                _ => None
+            }
+        },
+        ActivePane::Outline => {
+            match &state.screen {
+                ScreenState::Main(state) => outline::handle_search_event(key, &state.outline)
+                    .map(Message::Outline)
+                    .or_else(|| config.outline.key_to_message(key.into())),
+                _ => None
+            }
+        },
+        ActivePane::HelpModal => config.help_modal.key_to_message(key.into()),
+        ActivePane::InputModal => input_modal::handle_event(key, state.input_modal.kind).map(Message::InputModal),
+        ActivePane::VaultSelectorModal => {
+            vault_selector_modal::handle_filter_event(key, state.vault_selector_modal.search_query())
+                .map(Message::VaultSelectorModal)
+                .or_else(|| config.vault_selector_modal.key_to_message(key.into()))
+        },
+        ActivePane::RecentNotesModal => config.recent_notes_modal.key_to_message(key.into()),
+        ActivePane::TagPanel => config.tag_panel.key_to_message(key.into()),
+        ActivePane::ConfirmModal => config.confirm_modal.key_to_message(key.into()),
+    }
+}
+
+/// Returns `true` if the key is bound on the splash screen or globally, or continues a pending
+/// key sequence.
+fn is_splash_key_bound(config: &Config, state: &AppState, key: &KeyEvent) -> bool {
+    let keys = KeySequence::default().with_key(key.into());
+
+    state.pending_keys.is_some()
+        || [&config.global, &config.splash]
+            .iter()
+            .any(|section| !matches!(section.match_keys(&keys), KeyMatch::NoMatch))
+}
+
+/// Returns the key bindings of the active component. Input modals have no key bindings.
+fn active_section<'c>(config: &'c Config, state: &AppState) -> Option<&'c ConfigSection> {
+    match state.active_component() {
+        ActivePane::Splash => Some(&config.splash),
+        ActivePane::Explorer => Some(&config.explorer),
+        ActivePane::NoteEditor => Some(&config.note_editor),
+        ActivePane::Outline => Some(&config.outline),
+        ActivePane::HelpModal => Some(&config.help_modal),
+        ActivePane::InputModal => None,
+        ActivePane::VaultSelectorModal => Some(&config.vault_selector_modal),
+        ActivePane::RecentNotesModal => Some(&config.recent_notes_modal),
+        ActivePane::TagPanel => Some(&config.tag_panel),
+        ActivePane::ConfirmModal => Some(&config.confirm_modal),
+    }
+}
+
+/// Matches the key against the key sequences of the global and the active key bindings, e.g.
+/// `space f`.
+///
+/// Returns `None` if no sequence is pending and the key does not start one, so that the key is
+/// handled on its own exactly like a single key binding. A key that does not continue the
+/// pending sequence ends it with the fallback of the pending keys, e.g. the command of `g` when
+/// both `g` and `g g` are bound, and is then handled on its own. Esc cancels the pending sequence.
+fn handle_key_sequence(
+    config: &Config,
+    state: &AppState,
+    key: &KeyEvent,
+    is_typing: bool,
+) -> Option<Message> {
+    let pending_keys = state.pending_keys.as_ref();
+
+    if pending_keys.is_some() && key.code == KeyCode::Esc {
+        return Some(Message::KeySequence(key_sequence::Message::Cancel));
+    }
+
+    let keys = pending_keys
+        .map(|pending_keys| pending_keys.keys.clone())
+        .unwrap_or_default()
+        .with_key(key.into());
+
+    let sections = match is_typing {
+        true => vec![],
+        false => [Some(&config.global), active_section(config, state)]
+            .into_iter()
+            .flatten()
+            .collect(),
+    };
+
+    let matches: Vec<KeyMatch> = sections
+        .iter()
+        .map(|section| section.match_keys(&keys))
+        .collect();
+
+    let is_prefix = matches
+        .iter()
+        .any(|key_match| matches!(key_match, KeyMatch::Prefix(..)));
+
+    let message = matches.into_iter().find_map(|key_match| match key_match {
+        KeyMatch::Complete(message) | KeyMatch::Prefix(Some(message)) => Some(message),
+        _ => None,
+    });
+
+    match (pending_keys, is_prefix, message) {
+        (_, true, fallback) => Some(Message::KeySequence(key_sequence::Message::Pending(
+            key_sequence::PendingKeys {
+                keys,
+                fallback: fallback.map(Box::new),
+                pressed_at: Instant::now(),
+            },
+        ))),
+        (None, false, _) => None,
+        (Some(_), false, Some(message)) => {
+            Some(Message::KeySequence(key_sequence::Message::Complete(vec![
+                message,
+            ])))
+        }
+        (Some(pending_keys), false, None) => {
+            let state = AppState {
+                pending_keys: None,
+                ..state.clone()
+            };
+
+            let messages: Vec<Message> = pending_keys
+                .fallback
+                .clone()
+                .map(|message| *message)
+                .into_iter()
+                .chain(handle_key_event(config, &state, key))
+                .collect();
+
+            Some(Message::KeySequence(match messages.is_empty() {
+                true => key_sequence::Message::Cancel,
+                false => key_sequence::Message::Complete(messages),
+            }))
+        }
+    }
+}
+
+/// Converts the key event to a message using the key bindings of the active component.
+pub fn handle_key_event(config: &Config, state: &AppState, key: &KeyEvent) -> Option<Message> {
+    let global_message = config.global.key_to_message(key.into());
+
+    let is_editing = match &state.screen {
+        ScreenState::Main(main_state) => {
+            state.input_modal.visible
+                || main_state.note_editor.is_editing()
+                || main_state.note_editor.find().is_prompt_open()
+        }
+        _ => false,
+    };
+
+    // Typed characters filter the vaults or search the outline instead of triggering global key
+    // bindings
+    let is_filtering = match (state.active_component(), &state.screen) {
+        (ActivePane::VaultSelectorModal, _) => vault_selector_modal::handle_filter_event(
+            key,
+            state.vault_selector_modal.search_query(),
+        )
+        .is_some(),
+        (ActivePane::Outline, ScreenState::Main(main_state)) => {
+            outline::handle_search_event(key, &main_state.outline).is_some()
+        }
+        (ActivePane::Splash, ScreenState::Splash(splash_state)) => splash::handle_filter_event(
+            key,
+            splash_state.search_query(),
+            is_splash_key_bound(config, state, key),
+        )
+        .is_some(),
+        _ => false,
+    };
+
+    if let Some(message) = handle_key_sequence(config, state, key, is_editing || is_filtering) {
+        return Some(message);
+    }
+
+    if global_message.is_some() && !is_editing && !is_filtering {
+        let is_interrupt = key.code == KeyCode::Char('c') && key.modifiers == KeyModifiers::CONTROL;
+
+        return if is_interrupt {
+            Some(Message::Interrupt(Instant::now()))
+        } else {
+            global_message
+        };
+    }
+
+    let active_component = state.active_component();
+    handle_active_component_event(config, state, key, active_component)
+}
+
+/// Returns the byte offset of the start of the line (1-based) in the content. Lines past the end
+/// of the content return the length of the content.
+fn line_offset(content: &str, line: usize) -> usize {
+    content
+        .split_inclusive('\n')
+        .take(line.saturating_sub(1))
+        .map(str::len)
+        .sum()
+}
+
+/// Selects the note at the path in the explorer and opens it in the note editor.
+fn open_note_at_path<'a>(config: &Config, state: AppState<'a>, path: &Path) -> AppState<'a> {
+    let ScreenState::Main(main_state) = &state.screen else {
+        return state;
+    };
+
+    let main_state = main_state.clone().reveal(path);
+
+    if main_state.explorer.selected_path().as_deref() != Some(path) {
+        return update(
+            config,
+            state,
+            toast(
+                ToastKind::Warning,
+                &format!("Note not found: {}", path.display()),
+            ),
+        );
+    }
+
+    let state = state.with_main_state(main_state);
+
+    update(config, state, Message::Explorer(explorer::Message::Open))
+}
+
+/// Size of the start of a note that is shown in place of a note that is too large to open.
+const PREVIEW_BYTES: u64 = 4 * 1024;
+
+/// Returns the placeholder that is shown in place of a note that is too large to open or that is
+/// not text, or `None` if the note could not be read for another reason.
+fn placeholder(note: &Note, error: &obsidian::Error) -> Option<Placeholder> {
+    let megabytes = |bytes: u64| bytes as f64 / (1024.0 * 1024.0);
+
+    match error {
+        obsidian::Error::TooLarge {
+            size, max_bytes, ..
+        } => Some(Placeholder {
+            reason: format!(
+                "{} is too large to open ({:.1} MB, the limit is {:.1} MB)",
+                note.name,
+                megabytes(*size),
+                megabytes(*max_bytes)
+            ),
+            preview: note.read_preview(PREVIEW_BYTES).ok(),
+        }),
+        obsidian::Error::NotUtf8(_) => Some(Placeholder {
+            reason: format!("{} is not a text file and cannot be opened", note.name),
+            preview: None,
+        }),
+        _ => None,
+    }
+}
+
+/// Returns the Obsidian URI that opens the note at the path in the vault. Notes are referred to
+/// by their path relative to the vault without the `.md` extension, the same way Obsidian does.
+fn obsidian_uri(vault: &Vault, path: &Path) -> ObsidianUri {
+    match path.strip_prefix(&vault.path) {
+        Ok(file) => {
+            let file = match file.extension() {
+                Some(extension) if extension == "md" => file.with_extension(""),
+                _ => file.to_path_buf(),
+            };
+
+            ObsidianUri::Open {
+                vault: vault.name.clone(),
+                file: Some(
+                    file.components()
+                        .map(|component| component.as_os_str().to_string_lossy())
+                        .collect::<Vec<_>>()
+                        .join("/"),
+                ),
+            }
+        }
+        Err(_) => ObsidianUri::OpenPath {
+            path: path.to_path_buf(),
+        },
+    }
+}
+
+/// Converts the note content to a standalone HTML document titled with the note name.
+pub fn export_note_html(note: &SelectedNote) -> String {
+    markdown::to_html_document(
+        &note.name,
+        &markdown::to_html(&markdown::from_str(&note.content)),
+    )
+}
+
+/// Converts the note content to the format of the target file, which is plain text for `.txt`
+/// files and an HTML document otherwise.
+fn export_note(note: &SelectedNote, path: &Path) -> String {
+    match path.extension().and_then(|extension| extension.to_str()) {
+        Some("txt") => markdown::to_plain_text(&markdown::from_str(&note.content)),
+        _ => export_note_html(note),
+    }
+}
+
+/// Creates a message that shows a toast.
+fn toast(kind: ToastKind, message: &str) -> Message {
+    Message::Toast(kind, message.to_string())
+}
+
+/// Returns the number of ticks a toast is shown for, rounded up so that a toast is shown for at
+/// least the configured duration.
+fn toast_ticks(config: &Config) -> u32 {
+    let ticks = config
+        .toast_duration()
+        .as_millis()
+        .div_ceil(TICK_RATE.as_millis());
+
+    u32::try_from(ticks).unwrap_or(u32::MAX)
+}
+
+/// Copies the text to the clipboard and shows a toast with the number of copied characters, see
+/// [`copy`].
+fn copy_text<'a>(config: &Config, state: AppState<'a>, text: &str) -> AppState<'a> {
+    let copied = format!("{} characters", text.chars().count());
+    copy(config, state, text, &copied)
+}
+
+/// Copies the text to the clipboard and shows a toast that tells what was copied and whether the
+/// system clipboard or the OSC 52 escape sequence was used. The escape sequence is written to the
+/// terminal by the run loop.
+fn copy<'a>(config: &Config, state: AppState<'a>, text: &str, copied: &str) -> AppState<'a> {
+    let (osc52, message) = match clipboard::copy(text) {
+        Copied::System => (None, format!("Copied {copied}")),
+        Copied::Osc52 {
+            sequence,
+            error: None,
+        } => (Some(sequence), format!("Copied {copied} with OSC 52")),
+        Copied::Osc52 {
+            sequence,
+            error: Some(error),
+        } => (
+            Some(sequence),
+            format!("Copied {copied} with OSC 52, the system clipboard is not available: {error}"),
+        ),
+    };
+
+    update(
+        config,
+        AppState { osc52, ..state },
+        toast(ToastKind::Info, &message),
+    )
+}
+
+/// Returns a warning toast listing the keys that are bound to more than one command, including
+/// global keys that shadow the bindings of a section, and the commands that the user bound to
+/// more than one key, or `None` if there are none.
+fn conflicts_toast(config: &Config) -> Option<Message> {
+    let conflicts = config
+        .conflicts()
+        .into_iter()
+        .chain(config.shadowed_bindings())
+        .map(|conflict| conflict.to_string())
+        .collect::<Vec<_>>();
+
+    let duplicates = config
+        .user_duplicate_commands()
+        .iter()
+        .map(|(command, bindings)| config::format_duplicate_command(command, bindings))
+        .collect::<Vec<_>>();
+
+    let warnings = [
+        ("Conflicting key bindings", conflicts),
+        ("Commands bound to multiple keys", duplicates),
+    ]
+    .into_iter()
+    .filter(|(_, warnings)| !warnings.is_empty())
+    .map(|(title, warnings)| format!("{title}: {}", warnings.join("; ")))
+    .collect::<Vec<_>>();
+
+    (!warnings.is_empty()).then(|| toast(ToastKind::Warning, &warnings.join(". ")))
+}
+
+/// Replaces the configuration with the loaded configuration and reports the conflicting key
+/// bindings of the new configuration. If loading failed, the current configuration is kept and
+/// the error is shown instead.
+fn reload_config<'a>(
+    config: &mut Config,
+    state: AppState<'a>,
+    loaded: std::result::Result<Config, ConfigError>,
+) -> AppState<'a> {
+    match loaded {
+        Ok(loaded) => {
+            *config = loaded;
+            let state = AppState {
+                help_modal: HelpModalState {
+                    visible: state.help_modal.visible,
+                    ..HelpModalState::new(&help_text(config))
+                },
+                ..state
+            };
+            [
+                Some(toast(ToastKind::Info, "Config reloaded")),
+                conflicts_toast(config),
+            ]
+            .into_iter()
+            .flatten()
+            .fold(state, |state, message| update(config, state, message))
+        }
+        Err(error) => update(
+            config,
+            state,
+            toast(
+                ToastKind::Error,
+                &format!("Failed to reload config: {error}"),
+            ),
+        ),
+    }
+}
+
+/// Updates the state like [`update`], and handles the messages that replace the configuration,
+/// which [`update`] cannot do as it only borrows the configuration.
+pub fn update_with_config<'a>(
+    config: &mut Config,
+    state: AppState<'a>,
+    message: Message,
+) -> AppState<'a> {
+    // The layout may have changed since the previous message, e.g. by resizing the terminal or
+    // toggling the explorer
+    let state = state.with_wrap_widths();
+
+    let state = match message {
+        Message::ReloadConfig => reload_config(config, state, config::load()),
+        // The message that ends a key sequence may replace the configuration
+        Message::KeySequence(message) => {
+            let (pending_keys, messages) =
+                key_sequence::update(message, state.pending_keys.clone());
+            let state = AppState {
+                pending_keys,
+                ..state
+            };
+
+            messages.into_iter().fold(state, |state, message| {
+                update_with_config(config, state, message)
+            })
+        }
+        message @ Message::NoteEditor(_) if config.autosave() == config::Autosave::OnBlockExit => {
+            let content = |state: &AppState| match &state.screen {
+                ScreenState::Main(main_state) => Some(main_state.note_editor.content().to_string()),
+                _ => None,
+            };
+
+            let previous_content = content(&state);
+            let state = update(config, state, message);
+
+            match &state.screen {
+                ScreenState::Main(main_state)
+                    if main_state.note_editor.modified && content(&state) != previous_content =>
+                {
+                    autosave(config, state)
+                }
+                _ => state,
+            }
+        }
+        message => update(config, state, message),
+    };
+
+    // The autosave interval starts again with the next unsaved change once all notes are saved
+    // or discarded
+    match &state.screen {
+        ScreenState::Main(main_state) if main_state.modified_notes().is_empty() => AppState {
+            autosaved_at: None,
+            ..state
+        },
+        _ => state,
+    }
+}
+
+/// Saves the notes with unsaved changes without asking. A failed autosave shows an error toast
+/// only once until an autosave succeeds again, and delays the next interval autosave.
+fn autosave<'a>(config: &Config, state: AppState<'a>) -> AppState<'a> {
+    let ScreenState::Main(main_state) = state.screen.clone() else {
+        return state;
+    };
+
+    match main_state.save() {
+        Ok(main_state) => AppState {
+            autosaved_at: None,
+            autosave_failures: 0,
+            ..state.with_main_state(main_state)
+        },
+        Err(error) => {
+            let state = AppState {
+                autosave_failures: state.autosave_failures + 1,
+                ..state
+            };
+
+            if state.autosave_failures == 1 {
+                update(
+                    config,
+                    state,
+                    toast(
+                        ToastKind::Error,
+                        &format!("Failed to autosave note: {error}"),
+                    ),
+                )
+            } else {
+                state
+            }
+        }
+    }
+}
+
+/// Returns the modal that asks what to do with the unsaved changes before performing the message.
+fn unsaved_changes_modal(
+    main_state: &MainState,
+    message: Message,
+    action: &str,
+) -> ConfirmModalState<Message> {
+    let prompt = match main_state.modified_notes().as_slice() {
+        [name] => format!("{name} has unsaved changes."),
+        names => format!("{} notes have unsaved changes.", names.len()),
+    };
+
+    ConfirmModalState::new(message, action)
+        .with_title("Unsaved changes")
+        .with_prompt(&prompt)
+}
+
+/// Opens the note at the cursor of the explorer in a new tab. A note that is already open in a
+/// tab is shown in its tab instead. Without an open note, or for a directory, this is the same
+/// as [`explorer::Message::Open`].
+fn open_in_new_tab<'a>(
+    config: &Config,
+    state: AppState<'a>,
+    main_state: MainState<'a>,
+) -> AppState<'a> {
+    let open = Message::Explorer(explorer::Message::Open);
+
+    let Some(note) = main_state.explorer.note_at_cursor() else {
+        return update(config, state, open);
+    };
+
+    if main_state.selected_note.is_none() || main_state.explorer.pending_move.is_some() {
+        return update(config, state, open);
+    }
+
+    if !main_state.splits.is_empty() {
+        return update(
+            config,
+            state,
+            toast(ToastKind::Info, "Close the splits to open notes in tabs"),
+        );
+    }
+
+    let is_open = main_state
+        .selected_note
+        .as_ref()
+        .is_some_and(|selected| Path::new(&selected.path) == note.path);
+
+    match main_state.note_tab(&note.path) {
+        Some(index) => state.with_main_state(main_state.focus_tab(index)),
+        None if is_open => state.with_main_state(main_state),
+        None => update(config, state.with_main_state(main_state.new_tab()), open),
+    }
+}
+
+/// Saves the unsaved changes before performing a message that replaces the note editors, e.g.
+/// opening another note. When autosave is off, asks what to do with the changes instead.
+///
+/// The message is not performed if saving fails, so that the changes are not lost.
+fn save_changes_before<'a>(
+    config: &Config,
+    state: AppState<'a>,
+    main_state: MainState<'a>,
+    message: Message,
+    action: &str,
+) -> AppState<'a> {
+    if config.autosave() == config::Autosave::Off {
+        let confirm_modal = unsaved_changes_modal(&main_state, message, action);
+        return state
+            .with_main_state(main_state)
+            .with_confirm_modal_state(confirm_modal);
+    }
+
+    match main_state.clone().save() {
+        Ok(main_state) => update(config, state.with_main_state(main_state), message),
+        Err(error) => update(
+            config,
+            state.with_main_state(main_state),
+            toast(ToastKind::Error, &format!("Failed to save note: {error}")),
+        ),
+    }
+}
+
+/// Maps the messages of a read-only note, see [`MainState::read_only`]. Messages that would edit
+/// or save the note, or that need a vault, are dropped. Switching panes skips the absent
+/// explorer, so it moves between the note editor and the outline.
+fn read_only_message(message: Message) -> Option<Message> {
+    match message {
+        Message::Quit
+        | Message::ReloadConfig
+        | Message::Interrupt(_)
+        | Message::Autosave(_)
+        | Message::Resize(_)
+        | Message::Tick
+        | Message::Toast(..)
+        | Message::KeySequence(_)
+        | Message::HelpModal(_)
+        | Message::ConfirmModal(_) => Some(message),
+        Message::Outline(outline::Message::SwitchPaneNext) => {
+            Some(Message::Outline(outline::Message::SwitchPanePrevious))
+        }
+        Message::Outline(_) => Some(message),
+        Message::NoteEditor(note_editor::Message::SwitchPanePrevious) => {
+            Some(Message::NoteEditor(note_editor::Message::SwitchPaneNext))
+        }
+        Message::NoteEditor(
+            note_editor::Message::SwitchPaneNext
+            | note_editor::Message::ToggleOutline
+            | note_editor::Message::ExitMode
+            | note_editor::Message::ReadMode
+            | note_editor::Message::CursorUp
+            | note_editor::Message::CursorLeft
+            | note_editor::Message::CursorRight
+            | note_editor::Message::CursorWordForward
+            | note_editor::Message::CursorWordBackward
+            | note_editor::Message::CursorDown
+            | note_editor::Message::ScrollUp(_)
+            | note_editor::Message::ScrollDown(_)
+            | note_editor::Message::ToggleFold
+            | note_editor::Message::FoldAll
+            | note_editor::Message::UnfoldAll
+            | note_editor::Message::ToggleProperties
+            | note_editor::Message::FindStart
+            | note_editor::Message::FindInput(_)
+            | note_editor::Message::FindBackspace
+            | note_editor::Message::FindConfirm
+            | note_editor::Message::FindCancel
+            | note_editor::Message::FindNext
+            | note_editor::Message::FindPrev
+            | note_editor::Message::Focus,
+        ) => Some(message),
+        Message::NoteEditor(_)
+        | Message::Splash(_)
+        | Message::Explorer(_)
+        | Message::InputModal(_)
+        | Message::VaultSelectorModal(_)
+        | Message::RecentNotesModal(_)
+        | Message::TagPanel(_) => None,
+    }
+}
+
+pub fn update<'a>(config: &Config, state: AppState<'a>, message: Message) -> AppState<'a> {
+    let screen = state.screen.clone();
+
+    let message = match &screen {
+        ScreenState::Main(main_state) if main_state.read_only => match read_only_message(message) {
+            Some(message) => message,
+            None => return state,
+        },
+        _ => message,
+    };
+
+    match message {
+        // Replacing the configuration is handled by update_with_config
+        Message::ReloadConfig => state,
+        Message::Quit => match screen {
+            ScreenState::Main(main_state) if !main_state.modified_notes().is_empty() => {
+                let confirm_modal = unsaved_changes_modal(&main_state, Message::Quit, "quit");
+                state.with_confirm_modal_state(confirm_modal)
+            }
+            _ => state.set_running(false),
+        },
+        Message::Interrupt(now) => {
+            let is_repeated = state
+                .interrupted_at
+                .is_some_and(|at| now.saturating_duration_since(at) < INTERRUPT_TIMEOUT);
+
+            if is_repeated {
+                state.set_running(false)
+            } else {
+                update(
+                    config,
+                    AppState {
+                        interrupted_at: Some(now),
+                        ..state
+                    },
+                    Message::Quit,
+                )
+            }
+        }
+        Message::ConfirmModal(message) => {
+            let confirmed = state.confirm_modal.message.clone();
+            let state = state.with_confirm_modal_state(confirm_modal::update(
+                message.clone(),
+                state.confirm_modal.clone(),
+            ));
+
+            let (ScreenState::Main(main_state), Some(confirmed)) = (screen, confirmed) else {
+                return state;
+            };
+
+            match message {
+                confirm_modal::Message::Save => match main_state.save() {
+                    Ok(main_state) => update(config, state.with_main_state(main_state), confirmed),
+                    Err(error) => update(
+                        config,
+                        state,
+                        toast(ToastKind::Error, &format!("Failed to save note: {error}")),
+                    ),
+                },
+                confirm_modal::Message::Discard => update(
+                    config,
+                    state.with_main_state(main_state.discard()),
+                    confirmed,
+                ),
+                confirm_modal::Message::Cancel => state,
+            }
+        }
+        Message::Autosave(now) => {
+            let is_due = state
+                .autosave_timeout(config, now)
+                .is_some_and(|timeout| timeout.is_zero());
+
+            match state.autosaved_at {
+                _ if !is_due => state,
+                // The interval starts with the first unsaved change
+                None => AppState {
+                    autosaved_at: Some(now),
+                    ..state
+                },
+                Some(_) => autosave(
+                    config,
+                    AppState {
+                        autosaved_at: Some(now),
+                        ..state
+                    },
+                ),
+            }
+        }
+        Message::Resize(size) => AppState {
+            screen_size: size,
+            ..state
+        },
+        Message::Tick => {
+            let toasts = state
+                .toasts
+                .iter()
+                .cloned()
+                .map(Toast::tick)
+                .filter(|toast| !toast.is_expired())
+                .collect();
+
+            match screen {
+                ScreenState::Transitioning(transition, target) => {
+                    let transition = transition.next();
+                    AppState {
+                        screen: if transition.is_finished() {
+                            *target
+                        } else {
+                            ScreenState::Transitioning(transition, target)
+                        },
+                        toasts,
+                        ..state
+                    }
+                }
+                _ => AppState { toasts, ..state },
+            }
+        }
+        Message::Toast(kind, message) => {
+            let mut toasts = state.toasts.clone();
+            toasts.push(Toast::new(kind, &message, toast_ticks(config)));
+            AppState { toasts, ..state }
+        }
+        Message::KeySequence(message) => {
+            let (pending_keys, messages) =
+                key_sequence::update(message, state.pending_keys.clone());
+            let state = AppState {
+                pending_keys,
+                ..state
+            };
+
+            messages
+                .into_iter()
+                .fold(state, |state, message| update(config, state, message))
+        }
+        Message::HelpModal(message) => {
+            let help_modal = help_modal::update(message.clone(), state.help_modal.clone());
+
+            match message {
+                help_modal::Message::ScrollDown(scroll_amount) => {
+                    state.with_help_modal_state(help_modal.scroll_down(calc_scroll_amount(
+                        scroll_amount,
+                        modal_area_height(state.screen_size),
+                    )))
+                }
+                help_modal::Message::ScrollUp(scroll_amount) => {
+                    state.with_help_modal_state(help_modal.scroll_up(calc_scroll_amount(
+                        scroll_amount,
+                        modal_area_height(state.screen_size),
+                    )))
+                }
+                _ => state.with_help_modal_state(help_modal),
+            }
+        }
+        Message::InputModal(message) => {
+            let input_modal = input_modal::update(message.clone(), state.input_modal.clone());
+            let state = state.with_input_modal_state(input_modal.clone());
+
+            if message != input_modal::Message::Confirm {
+                return state;
+            }
+
+            let value = input_modal.value().to_string();
+
+            let message = match input_modal.kind {
+                InputKind::NewNote => Message::Explorer(explorer::Message::CreateNote(value)),
+                InputKind::RenameNote => Message::Explorer(explorer::Message::RenameNote(value)),
+                InputKind::DeleteNote => Message::Explorer(explorer::Message::DeleteNote),
+                InputKind::ExportNote => {
+                    Message::NoteEditor(note_editor::Message::ExportTo(value.into()))
+                }
+            };
+
+            update(config, state, message)
+        }
+        Message::VaultSelectorModal(message) => {
+            let ScreenState::Main(main_state) = screen else {
+                return state;
+            };
+
+            // Switching vaults replaces the note editors, so their unsaved changes are saved or
+            // confirmed first
+            if message == vault_selector_modal::Message::Select {
+                let main_state = main_state.commit_edits();
+
+                if !main_state.modified_notes().is_empty() {
+                    return save_changes_before(
+                        config,
+                        state,
+                        main_state,
+                        Message::VaultSelectorModal(message),
+                        "switch vault",
+                    );
+                }
+            }
+
+            let vault_selector_modal =
+                vault_selector_modal::update(message.clone(), state.vault_selector_modal.clone());
+
+            match message {
+                vault_selector_modal::Message::Select => vault_selector_modal
+                    .selected()
+                    .and_then(|index| vault_selector_modal.clone().get_item(index))
+                    .map(|vault| {
+                        let (main_state, message) = MainState::new(config, vault);
+                        let state = state
+                            .with_main_state(main_state)
+                            .with_vault_selector_modal_state(vault_selector_modal.hide())
+                            .with_recent_notes_modal_state(RecentNotesModalState::default())
+                            .with_tag_panel_state(TagPanelState::default());
+
+                        message
+                            .into_iter()
+                            .fold(state, |state, message| update(config, state, message))
+                    })
+                    .unwrap_or(state),
+                _ => state.with_vault_selector_modal_state(vault_selector_modal),
+            }
+        }
+        Message::RecentNotesModal(message) => {
+            let ScreenState::Main(main_state) = screen else {
+                return state;
+            };
+
+            let recent_notes_modal =
+                recent_notes_modal::update(message.clone(), state.recent_notes_modal.clone());
+
+            match message {
+                recent_notes_modal::Message::Toggle if recent_notes_modal.visible => {
+                    state.with_recent_notes_modal_state(recent_notes_modal.hide())
+                }
+                recent_notes_modal::Message::Toggle => state.with_recent_notes_modal_state(
+                    recent_notes_modal.show(main_state.recent_note_items()),
+                ),
+                recent_notes_modal::Message::Select => {
+                    let Some(note) = recent_notes_modal.selected().cloned() else {
+                        return state;
+                    };
+
+                    open_note_at_path(
+                        config,
+                        state.with_recent_notes_modal_state(recent_notes_modal.hide()),
+                        &note.path,
+                    )
+                }
+                _ => state.with_recent_notes_modal_state(recent_notes_modal),
+            }
+        }
+        Message::TagPanel(message) => {
+            let ScreenState::Main(main_state) = screen else {
+                return state;
+            };
+
+            let tag_panel = tag_panel::update(message.clone(), state.tag_panel.clone());
+
+            match message {
+                tag_panel::Message::Toggle if tag_panel.visible => {
+                    state.with_tag_panel_state(tag_panel.hide())
+                }
+                tag_panel::Message::Toggle => {
+                    state.with_tag_panel_state(tag_panel.show(tags::note_tags(&main_state.entries)))
+                }
+                tag_panel::Message::Select => match tag_panel.selected_note().cloned() {
+                    Some(note) => open_note_at_path(
+                        config,
+                        state.with_tag_panel_state(tag_panel.hide()),
+                        &note.path,
+                    ),
+                    None => state.with_tag_panel_state(tag_panel.select()),
+                },
+                _ => state.with_tag_panel_state(tag_panel),
+            }
+        }
+        Message::Splash(message) => {
+            let ScreenState::Splash(splash_state) = screen else {
+                return state;
+            };
+
+            let splash_state = splash::update(message.clone(), splash_state);
+
+            match message {
+                splash::Message::Open => splash_state
+                    .selected()
+                    .and_then(|index| splash_state.clone().get_item(index))
+                    .map(|vault| {
+                        let (main_state, message) = MainState::new(config, vault);
+                        let state = state.with_transition(
+                            TransitionKind::FadeIn,
+                            ScreenState::Main(Box::new(main_state)),
+                        );
+
+                        message
+                            .into_iter()
+                            .fold(state, |state, message| update(config, state, message))
+                    })
+                    .unwrap_or(state),
+                splash::Message::Resume => match splash_state.session {
+                    Some((vault, note)) => {
+                        let (main_state, message) = MainState::new(config, vault);
+                        let main_state = match &note {
+                            Some(note) => main_state.reveal(note),
+                            None => main_state,
+                        };
+
+                        let state = message
+                            .into_iter()
+                            .chain(note.map(|_| Message::Explorer(explorer::Message::Open)))
+                            .fold(state.with_main_state(main_state), |state, message| {
+                                update(config, state, message)
+                            });
+
+                        state.with_transition(TransitionKind::FadeIn, state.screen.clone())
+                    }
+                    None => state,
+                },
+                _ => state.with_splash_state(splash_state),
+            }
+        }
+        Message::Explorer(message) => {
+            let ScreenState::Main(main_state) = screen else {
+                return state;
+            };
+
+            // Opening a directory while a move is pending drops the note into the directory
+            if let (explorer::Message::Open, Some(_), Some(directory)) = (
+                &message,
+                &main_state.explorer.pending_move,
+                main_state.explorer.directory_at_cursor(),
+            ) {
+                return update(
+                    config,
+                    state,
+                    Message::Explorer(explorer::Message::DropIntoDirectory(directory)),
+                );
+            }
+
+            if message == explorer::Message::OpenInNewTab {
+                return open_in_new_tab(config, state, *main_state);
+            }
+
+            // A note that is already open in a tab is shown in its tab, so that the note is not
+            // edited in two tabs at once
+            if let (explorer::Message::Open, Some(index)) = (
+                &message,
+                main_state
+                    .explorer
+                    .note_at_cursor()
+                    .and_then(|note| main_state.note_tab(&note.path)),
+            ) {
+                return state.with_main_state(main_state.focus_tab(index));
+            }
+
+            // Opening a note replaces the active note editor, so its unsaved changes are saved or
+            // confirmed first
+            if message == explorer::Message::Open
+                && main_state.explorer.directory_at_cursor().is_none()
+            {
+                let main_state = main_state.clone().commit_edits();
+
+                if main_state.note_editor.modified {
+                    return save_changes_before(
+                        config,
+                        state,
+                        main_state,
+                        Message::Explorer(message),
+                        "open",
+                    );
+                }
+            }
+
+            let explorer = explorer::update(message.clone(), main_state.explorer.clone());
+
+            match message {
+                // Toggling a directory keeps the open note
+                explorer::Message::Open if main_state.explorer.directory_at_cursor().is_some() => {
+                    state.with_main_state(MainState {
+                        explorer,
+                        ..*main_state
+                    })
+                }
+                explorer::Message::SwitchPaneNext => state.with_main_state(
+                    MainState {
+                        active_pane: ActivePane::NoteEditor,
+                        note_editor: main_state.note_editor.set_active(true),
+                        explorer,
+                        ..*main_state
+                    }
+                    .focus_split(0),
+                ),
+                explorer::Message::SwitchPanePrevious => state.with_main_state(MainState {
+                    active_pane: ActivePane::Outline,
+                    outline: main_state.outline.set_active(true),
+                    explorer,
+                    ..*main_state
+                }),
+                explorer::Message::ScrollUp(scroll_amount) => state.with_main_state(MainState {
+                    explorer: explorer.previous(calc_scroll_amount(
+                        scroll_amount,
+                        state.screen_size.height.into(),
+                    )),
+                    ..*main_state
+                }),
+                explorer::Message::ScrollDown(scroll_amount) => state.with_main_state(MainState {
+                    explorer: explorer.next(calc_scroll_amount(
+                        scroll_amount,
+                        state.screen_size.height.into(),
+                    )),
+                    ..*main_state
+                }),
+                explorer::Message::Toggle => state.with_main_state(match explorer.open {
+                    true => MainState {
+                        explorer,
+                        ..*main_state
+                    },
+                    false => MainState {
+                        active_pane: ActivePane::NoteEditor,
+                        explorer: explorer.set_active(false),
+                        note_editor: main_state.note_editor.set_active(true),
+                        ..*main_state
+                    },
+                }),
+                explorer::Message::Click(index) => {
+                    let state = state.with_main_state(
+                        MainState {
+                            explorer: main_state.explorer.clone().select_index(index),
+                            ..*main_state
+                        }
+                        .focus(ActivePane::Explorer),
+                    );
+
+                    update(config, state, Message::Explorer(explorer::Message::Open))
+                }
+                explorer::Message::NewNote => {
+                    state.with_input_modal_state(InputModalState::new(InputKind::NewNote))
+                }
+                explorer::Message::CreateNote(name) => {
+                    let Some(name) = note_name(&name) else {
+                        return state;
+                    };
+
+                    // Notes are created next to the item under the cursor
+                    let name = match explorer.selected_directory().and_then(|directory| {
+                        directory
+                            .strip_prefix(&main_state.vault.path)
+                            .map(Path::to_path_buf)
+                            .ok()
+                    }) {
+                        Some(directory) => directory.join(name).to_string_lossy().to_string(),
+                        None => name.to_string(),
+                    };
+
+                    let note = match main_state.vault.create_note(&name) {
+                        Ok(note) => note,
+                        Err(error) => {
+                            return update(
+                                config,
+                                state,
+                                toast(ToastKind::Error, &format!("Failed to create note: {error}")),
+                            );
+                        }
+                    };
+
+                    let entries = main_state.vault.entries();
+
+                    open_note_at_path(
+                        config,
+                        state.with_main_state(MainState {
+                            explorer: explorer.with_entries(entries.clone()),
+                            entries,
+                            ..*main_state
+                        }),
+                        &note.path,
+                    )
+                }
+                explorer::Message::Delete => match explorer.note_at_cursor() {
+                    Some(note) => state.with_input_modal_state(
+                        InputModalState::new(InputKind::DeleteNote)
+                            .with_prompt(&format!("Delete {}?", note.name)),
+                    ),
+                    None => state,
+                },
+                explorer::Message::Rename => match explorer.note_at_cursor() {
+                    Some(note) => state.with_input_modal_state(
+                        InputModalState::new(InputKind::RenameNote).with_value(&note.name),
+                    ),
+                    None => state,
+                },
+                explorer::Message::RevealCurrent => match &main_state.selected_note {
+                    Some(note) => {
+                        let path = PathBuf::from(&note.path);
+                        state.with_main_state(main_state.clone().reveal(&path))
+                    }
+                    None => state,
+                },
+                explorer::Message::ToggleBookmark => {
+                    let Some(note) = explorer.note_at_cursor() else {
+                        return state;
+                    };
+
+                    let result = if explorer.is_bookmarked(&note.path) {
+                        main_state.vault.remove_bookmark(&note.path)
+                    } else {
+                        main_state.vault.add_bookmark(&note.path)
+                    };
+
+                    if let Err(error) = result {
+                        return update(
+                            config,
+                            state,
+                            toast(
+                                ToastKind::Error,
+                                &format!("Failed to update bookmarks: {error}"),
+                            ),
+                        );
+                    }
+
+                    state.with_main_state(MainState {
+                        explorer: explorer.with_bookmarks(bookmark_paths(&main_state.vault)),
+                        ..*main_state
+                    })
+                }
+                explorer::Message::DeleteNote => {
+                    let Some(note) = explorer.note_at_cursor() else {
+                        return state;
+                    };
+
+                    if let Err(error) = main_state.vault.delete_note(&note) {
+                        return update(
+                            config,
+                            state,
+                            toast(ToastKind::Error, &format!("Failed to delete note: {error}")),
+                        );
+                    }
+
+                    let entries = main_state.vault.entries();
+                    let explorer = explorer.with_deleted_note(&note.path, entries.clone());
+
+                    let main_state = main_state.close_note_tabs(&note.path);
+
+                    let is_open = main_state
+                        .selected_note
+                        .as_ref()
+                        .is_some_and(|selected| Path::new(&selected.path) == note.path);
+
+                    let main_state =
+                        main_state.map_inactive_panes(|pane| match &pane.selected_note {
+                            Some(selected) if Path::new(&selected.path) == note.path => {
+                                EditorPane::default()
+                            }
+                            _ => pane,
+                        });
+                    let main_state = MainState {
+                        recent_notes: main_state
+                            .recent_notes
+                            .iter()
+                            .filter(|path| **path != note.path)
+                            .cloned()
+                            .collect(),
+                        ..main_state
+                    };
+
+                    state.with_main_state(if is_open {
+                        MainState {
+                            explorer,
+                            entries,
+                            note_editor: EditorState::default(),
+                            outline: OutlineState::new(&[], &[], 0, main_state.outline.is_open()),
+                            selected_note: None,
+                            ..main_state
+                        }
+                    } else {
+                        MainState {
+                            explorer,
+                            entries,
+                            ..main_state
+                        }
+                    })
+                }
+                explorer::Message::RenameNote(name) => {
+                    let (Some(name), Some(note)) = (note_name(&name), explorer.note_at_cursor())
+                    else {
+                        return state;
+                    };
+
+                    let renamed = match main_state.vault.rename_note(&note, name) {
+                        Ok(renamed) => renamed,
+                        Err(error) => {
+                            return update(
+                                config,
+                                state,
+                                toast(ToastKind::Error, &format!("Failed to rename note: {error}")),
+                            );
+                        }
+                    };
+
+                    let entries = main_state.vault.entries();
+                    let explorer =
+                        explorer.with_renamed_note(&note.path, renamed.clone(), entries.clone());
+
+                    state.with_main_state(MainState {
+                        explorer,
+                        entries,
+                        ..main_state.with_renamed_note(&note.path, &renamed)
+                    })
+                }
+                explorer::Message::ToggleOutline => state.with_main_state(MainState {
+                    outline: main_state.outline.toggle(),
+                    ..*main_state
+                }),
+                explorer::Message::BeginMove => {
+                    let Some(note) = &explorer.pending_move else {
+                        return state;
+                    };
+
+                    let message = toast(
+                        ToastKind::Info,
+                        &format!("Open a directory to move {} into it", note.name),
+                    );
+
+                    update(
+                        config,
+                        state.with_main_state(MainState {
+                            explorer,
+                            ..*main_state
+                        }),
+                        message,
+                    )
+                }
+                explorer::Message::DropIntoDirectory(directory) => {
+                    let Some(note) = explorer.pending_move.clone() else {
+                        return state;
+                    };
+
+                    let explorer = match explorer.move_note(&note, &directory) {
+                        Ok(explorer) => explorer,
+                        Err(error) => {
+                            return update(
+                                config,
+                                state.with_main_state(MainState {
+                                    explorer: explorer.begin_move(None),
+                                    ..*main_state
+                                }),
+                                toast(ToastKind::Error, &format!("Failed to move note: {error}")),
+                            );
+                        }
+                    };
+
+                    let moved = Note {
+                        name: note.name.clone(),
+                        path: directory.join(note.path.file_name().unwrap_or_default()),
+                    };
+
+                    state.with_main_state(MainState {
+                        explorer,
+                        entries: main_state.vault.entries(),
+                        ..main_state.with_renamed_note(&note.path, &moved)
+                    })
+                }
+                explorer::Message::Open => {
+                    let main_state = main_state.with_view_position_saved();
+
+                    let (selected_note, read_error) = match explorer.selected_file() {
+                        Some(note) => {
+                            let (selected_note, error) =
+                                SelectedNote::read(note, config.max_note_bytes());
+                            (Some(selected_note), error)
+                        }
+                        None => (None, None),
+                    };
+
+                    let placeholder = explorer.selected_file().zip(read_error.as_ref()).and_then(
+                        |(note, error)| {
+                            placeholder(note, error)
+                                .map(|placeholder| (note.path.clone(), placeholder))
+                        },
+                    );
+
+                    let note_editor = match (&explorer.preview, placeholder) {
+                        (Some(note), _) => EditorState::default()
+                            .set_attachment(Attachment::from(note))
+                            .set_path(note.path.clone()),
+                        (None, Some((path, placeholder))) => EditorState::default()
+                            .set_placeholder(placeholder)
+                            .set_path(path),
+                        _ => selected_note
+                            .clone()
+                            .map(|note| {
+                                let note_editor = EditorState::default()
+                                    .set_vim_mode(config.vim_mode)
+                                    .set_mode(if config.experimental_editor {
+                                        main_state.note_editor.mode
+                                    } else {
+                                        Mode::Read
+                                    })
+                                    .set_content(&note.content);
+
+                                // Reopening a note restores the view where it was left
+                                let note_editor =
+                                    match main_state.view_positions.get(Path::new(&note.path)) {
+                                        Some(position) => note_editor.with_view_position(*position),
+                                        None => note_editor,
+                                    };
+
+                                note_editor.set_path(note.path.into())
+                            })
+                            .unwrap_or_default(),
+                    };
+
+                    let outline = OutlineState::new(
+                        note_editor.nodes(),
+                        note_editor.collapsed_ranges(),
+                        note_editor.current_row,
+                        main_state.outline.is_open(),
+                    );
+
+                    let main_state = match &selected_note {
+                        Some(note) => main_state.with_recent_note(PathBuf::from(&note.path)),
+                        None => main_state,
+                    };
+
+                    let state = state.with_main_state(
+                        MainState {
+                            explorer,
+                            outline,
+                            note_editor,
+                            selected_note,
+                            ..main_state
+                        }
+                        .with_note_from_panes(),
+                    );
+
+                    match read_error {
+                        // The placeholder in the editor explains the error
+                        Some(obsidian::Error::TooLarge { .. } | obsidian::Error::NotUtf8(_)) => {
+                            state
+                        }
+                        Some(error) => update(
+                            config,
+                            state,
+                            toast(ToastKind::Error, &format!("Failed to read note: {error}")),
+                        ),
+                        None => state,
+                    }
+                }
+                _ => state.with_main_state(MainState {
+                    explorer,
+                    ..*main_state
+                }),
+            }
+        }
+        Message::Outline(message) => {
+            let ScreenState::Main(main_state) = screen else {
+                return state;
+            };
+
+            let outline = outline::update(message.clone(), main_state.outline.clone());
+
+            match message {
+                outline::Message::SwitchPaneNext => state.with_main_state(MainState {
+                    active_pane: ActivePane::Explorer,
+                    explorer: main_state.explorer.set_active(true),
+                    outline,
+                    ..*main_state
+                }),
+                outline::Message::SwitchPanePrevious => {
+                    let last_split = main_state.splits.len().saturating_sub(1);
+
+                    state.with_main_state(
+                        MainState {
+                            active_pane: ActivePane::NoteEditor,
+                            note_editor: main_state.note_editor.set_active(true),
+                            outline,
+                            ..*main_state
+                        }
+                        .focus_split(last_split),
+                    )
+                }
+                outline::Message::Toggle => state.with_main_state(match outline.open {
+                    true => MainState {
+                        outline,
+                        ..*main_state
+                    },
+                    false => MainState {
+                        active_pane: ActivePane::NoteEditor,
+                        outline: outline.set_active(false),
+                        note_editor: main_state.note_editor.set_active(true),
+                        ..*main_state
+                    },
+                }),
+                outline::Message::Expand => {
+                    let outline = main_state.outline.toggle_item();
+                    // The fold follows the new state of the item instead of being toggled, so
+                    // that it cannot end up inverted
+                    let note_editor = match outline.selected() {
+                        Some(item) if item.is_expandable() => main_state
+                            .note_editor
+                            .set_fold(item.get_range().start, !item.is_expanded()),
+                        _ => main_state.note_editor,
+                    };
+
+                    state.with_main_state(MainState {
+                        outline,
+                        note_editor,
+                        ..*main_state
+                    })
+                }
+                outline::Message::Click(index) => {
+                    let state = state.with_main_state(
+                        MainState {
+                            outline: main_state.outline.clone().select_index(index),
+                            ..*main_state
+                        }
+                        .focus(ActivePane::Outline),
+                    );
+
+                    update(config, state, Message::Outline(outline::Message::Select))
+                }
+                outline::Message::Select => state.with_main_state(MainState {
+                    note_editor: main_state.note_editor.set_row(
+                        outline
+                            .selected()
+                            .map(|item| item.get_range().start)
+                            .unwrap_or_default(),
+                    ),
+                    ..*main_state
+                }),
+                outline::Message::SelectByHeadingText(text) => {
+                    if main_state.outline.find_by_heading_text(&text).is_none() {
+                        return state;
+                    }
+
+                    let row = outline
+                        .selected()
+                        .map(|item| item.get_range().start)
+                        .unwrap_or_default();
+
+                    state.with_main_state(MainState {
+                        note_editor: main_state.note_editor.set_row(row),
+                        outline,
+                        ..*main_state
+                    })
+                }
+                outline::Message::Open => {
+                    let Some(row) = outline.selected().map(|item| item.get_range().start) else {
+                        return state;
+                    };
+
+                    state.with_main_state(
+                        MainState {
+                            note_editor: main_state.note_editor.reveal_row(row),
+                            outline,
+                            ..*main_state
+                        }
+                        .focus(ActivePane::NoteEditor),
+                    )
+                }
+
+                _ => state.with_main_state(MainState {
+                    outline,
+                    ..*main_state
+                }),
+            }
+        }
+        Message::NoteEditor(message) => {
+            let ScreenState::Main(main_state) = screen else {
+                return state;
+            };
+
+            let mode = &main_state.note_editor.mode();
+
+            let editor_enabled = config.experimental_editor;
+
+            // Attachments and placeholders have no markdown content to edit or export
+            if (main_state.note_editor.attachment().is_some()
+                || main_state.note_editor.placeholder().is_some())
+                && matches!(
+                    message,
+                    note_editor::Message::EditMode
+                        | note_editor::Message::NormalMode
+                        | note_editor::Message::ExportHtml
+                        | note_editor::Message::Export
+                        | note_editor::Message::ExportTo(_)
+                        | note_editor::Message::ReloadFromDisk
+                )
+            {
+                return state;
+            }
+
+            if editor_enabled {
+                match message {
+                    note_editor::Message::KeyEvent(key) if *mode == Mode::Edit => {
+                        let note_editor = main_state.note_editor.edit(key.into());
+                        let selected_note = main_state.selected_note.map(|note| SelectedNote {
+                            content: note_editor.content().to_string(),
+                            ..note
+                        });
+
+                        return state.with_main_state(MainState {
+                            note_editor,
+                            selected_note,
+                            ..*main_state
+                        });
+                    }
+                    note_editor::Message::CursorLeft => {
+                        return state.with_main_state(MainState {
+                            note_editor: main_state.note_editor.cursor_left(),
+                            ..*main_state
+                        })
+                    }
+                    note_editor::Message::CursorRight => {
+                        return state.with_main_state(MainState {
+                            note_editor: main_state.note_editor.cursor_right(),
+                            ..*main_state
+                        })
+                    }
+                    note_editor::Message::CursorWordForward => {
+                        return state.with_main_state(MainState {
+                            note_editor: main_state.note_editor.cursor_word_forward(),
+                            ..*main_state
+                        })
+                    }
+                    note_editor::Message::CursorWordBackward => {
+                        return state.with_main_state(MainState {
+                            note_editor: main_state.note_editor.cursor_word_backward(),
+                            ..*main_state
+                        })
+                    }
+                    note_editor::Message::Delete => {
+                        return state.with_main_state(MainState {
+                            note_editor: main_state.note_editor.delete_char(),
+                            ..*main_state
+                        })
+                    }
+                    // Outside of lists the keys are typed as is, e.g. a tab in a code block
+                    note_editor::Message::IndentLine | note_editor::Message::OutdentLine
+                        if *mode == Mode::Edit && !main_state.note_editor.is_in_list() =>
+                    {
+                        let key = match message {
+                            note_editor::Message::IndentLine => KeyEvent::from(KeyCode::Tab),
+                            _ => KeyEvent::new(KeyCode::BackTab, KeyModifiers::SHIFT),
+                        };
+
+                        return update(
+                            config,
+                            state,
+                            Message::NoteEditor(note_editor::Message::KeyEvent(key)),
+                        );
+                    }
+                    note_editor::Message::IndentLine if *mode == Mode::Edit => {
+                        return state.with_main_state(MainState {
+                            note_editor: main_state.note_editor.indent_line(),
+                            ..*main_state
+                        })
+                    }
+                    note_editor::Message::OutdentLine if *mode == Mode::Edit => {
+                        return state.with_main_state(MainState {
+                            note_editor: main_state.note_editor.outdent_line(),
+                            ..*main_state
+                        })
+                    }
+                    note_editor::Message::EditMode if *mode != Mode::Edit => {
+                        if let Some(selected_note) = &main_state.selected_note {
+                            return state.with_main_state(MainState {
+                                active_pane: ActivePane::NoteEditor,
+                                note_editor: main_state
+                                    .note_editor
+                                    .clone()
+                                    .set_content(&selected_note.content)
+                                    .set_mode(if config.vim_mode {
+                                        Mode::Normal
+                                    } else {
+                                        Mode::Edit
+                                    }),
+                                ..*main_state
+                            });
+                        } else {
+                            return state;
+                        }
+                    }
+                    note_editor::Message::NormalMode if *mode != Mode::Normal => {
+                        if main_state.selected_note.is_none() {
+                            return state;
+                        }
+
+                        return state.with_main_state(MainState {
+                            active_pane: ActivePane::NoteEditor,
+                            note_editor: main_state.note_editor.set_mode(Mode::Normal),
+                            ..*main_state
+                        });
+                    }
+                    note_editor::Message::NormalKey(_)
+                    | note_editor::Message::DeleteLine
+                    | note_editor::Message::InsertLineBelow
+                    | note_editor::Message::InsertLineAbove
+                        if *mode == Mode::Normal =>
+                    {
+                        let note_editor = match message {
+                            note_editor::Message::NormalKey(c) => {
+                                main_state.note_editor.normal_key(c)
+                            }
+                            note_editor::Message::DeleteLine => {
+                                main_state.note_editor.delete_line()
+                            }
+                            note_editor::Message::InsertLineBelow => {
+                                main_state.note_editor.insert_line_below()
+                            }
+                            _ => main_state.note_editor.insert_line_above(),
+                        };
+                        let outline = main_state.outline.select_at(note_editor.current_row);
+                        let selected_note = main_state.selected_note.map(|note| SelectedNote {
+                            content: note_editor.content().to_string(),
+                            ..note
+                        });
+
+                        return state.with_main_state(MainState {
+                            note_editor,
+                            outline,
+                            selected_note,
+                            ..*main_state
+                        });
+                    }
+                    note_editor::Message::ReadMode if *mode != Mode::Read => {
+                        return state.with_main_state(MainState {
+                            note_editor: main_state.note_editor.set_mode(Mode::Read),
+                            ..*main_state
+                        })
+                    }
+                    note_editor::Message::ExitMode if *mode == Mode::Read => {
+                        return state.with_main_state(MainState {
+                            note_editor: main_state.note_editor.set_mode(Mode::View),
+                            ..*main_state
+                        })
+                    }
+                    note_editor::Message::ExitMode
+                        if *mode == Mode::Edit || *mode == Mode::Normal =>
+                    {
+                        let next_mode = if main_state.note_editor.is_insert_from_normal() {
+                            Mode::Normal
+                        } else {
+                            Mode::View
+                        };
+                        let note_editor = main_state.note_editor.exit_insert();
+                        let outline = main_state
+                            .outline
+                            .set_nodes(note_editor.nodes(), note_editor.collapsed_ranges());
+
+                        let selected_note = main_state.selected_note.map(|note| SelectedNote {
+                            content: note_editor.content().to_string(),
+                            ..note
+                        });
+
+                        return state.with_main_state(MainState {
+                            note_editor: note_editor.set_mode(next_mode),
+                            outline,
+                            selected_note,
+                            ..*main_state
+                        });
+                    }
+                    note_editor::Message::Undo | note_editor::Message::Redo
+                        if *mode != Mode::Read =>
+                    {
+                        let note_editor = match message {
+                            note_editor::Message::Undo => main_state.note_editor.undo(),
+                            _ => main_state.note_editor.redo(),
+                        };
+                        let outline = main_state
+                            .outline
+                            .set_nodes(note_editor.nodes(), note_editor.collapsed_ranges())
+                            .select_at(note_editor.current_row);
+                        let selected_note = main_state.selected_note.map(|note| SelectedNote {
+                            content: note_editor.content().to_string(),
+                            ..note
+                        });
+
+                        return state.with_main_state(MainState {
+                            note_editor,
+                            outline,
+                            selected_note,
+                            ..*main_state
+                        });
+                    }
+                    note_editor::Message::ToggleTask
+                        if *mode == Mode::Read || *mode == Mode::View =>
+                    {
+                        let note_editor = main_state.note_editor.toggle_task();
+                        let selected_note = main_state.selected_note.map(|note| SelectedNote {
+                            content: note_editor.content().to_string(),
+                            ..note
+                        });
+
+                        return state.with_main_state(MainState {
+                            note_editor,
+                            selected_note,
+                            ..*main_state
+                        });
+                    }
+                    note_editor::Message::Save => {
+                        let is_modified = main_state.note_editor.modified;
+
+                        let note_editor = match main_state.note_editor.clone().save() {
+                            Ok(note_editor) => note_editor,
+                            Err(error) => {
+                                return update(
+                                    config,
+                                    state,
+                                    toast(
+                                        ToastKind::Error,
+                                        &format!("Failed to save note: {error}"),
+                                    ),
+                                );
+                            }
+                        };
+
+                        let selected_note = main_state.selected_note.map(|note| SelectedNote {
+                            content: note_editor.content().to_string(),
+                            ..note
+                        });
+
+                        // A manual save starts the autosave interval again
+                        let state = AppState {
+                            autosaved_at: None,
+                            ..state.with_main_state(
+                                MainState {
+                                    selected_note,
+                                    note_editor,
+                                    ..*main_state
+                                }
+                                .sync_note_panes(),
+                            )
+                        };
+
+                        return if is_modified {
+                            update(config, state, toast(ToastKind::Info, "Note saved"))
+                        } else {
+                            state
+                        };
+                    }
+                    _ => {}
+                }
+            }
+
+            match message {
+                // Scrolling moves the cursor while editing, so the outline follows it the same way
+                note_editor::Message::CursorUp | note_editor::Message::ScrollUp(_)
+                    if *mode == Mode::Edit || message == note_editor::Message::CursorUp =>
+                {
+                    let note_editor = main_state.note_editor.cursor_up();
+                    let outline = main_state.outline.select_at(note_editor.current_row);
+
+                    state.with_main_state(MainState {
+                        note_editor,
+                        outline,
+                        ..*main_state
+                    })
+                }
+                note_editor::Message::CursorDown | note_editor::Message::ScrollDown(_)
+                    if *mode == Mode::Edit || message == note_editor::Message::CursorDown =>
+                {
+                    let note_editor = main_state.note_editor.cursor_down();
+                    let outline = main_state.outline.select_at(note_editor.current_row);
+
+                    state.with_main_state(MainState {
+                        note_editor,
+                        outline,
+                        ..*main_state
+                    })
+                }
+                note_editor::Message::FindStart if *mode != Mode::Edit => {
+                    state.with_main_state(MainState {
+                        note_editor: main_state.note_editor.find_start(),
+                        ..*main_state
+                    })
+                }
+                note_editor::Message::FindInput(c) => state.with_main_state(MainState {
+                    note_editor: main_state.note_editor.find_input(c),
+                    ..*main_state
+                }),
+                note_editor::Message::FindBackspace => state.with_main_state(MainState {
+                    note_editor: main_state.note_editor.find_backspace(),
+                    ..*main_state
+                }),
+                note_editor::Message::FindCancel => state.with_main_state(MainState {
+                    note_editor: main_state.note_editor.find_cancel(),
+                    ..*main_state
+                }),
+                note_editor::Message::FindConfirm
+                | note_editor::Message::FindNext
+                | note_editor::Message::FindPrev => {
+                    let note_editor = match message {
+                        note_editor::Message::FindConfirm => main_state.note_editor.find_confirm(),
+                        note_editor::Message::FindNext => main_state.note_editor.find_next(),
+                        _ => main_state.note_editor.find_previous(),
+                    };
+                    let outline = main_state.outline.select_at(note_editor.current_row);
+
+                    state.with_main_state(MainState {
+                        note_editor,
+                        outline,
+                        ..*main_state
+                    })
+                }
+                note_editor::Message::ToggleFold if *mode != Mode::Edit => {
+                    let row = main_state.note_editor.current_row;
+                    let note_editor = main_state.note_editor.toggle_fold_at(row);
+                    let outline = main_state.outline.set_folds(note_editor.collapsed_ranges());
+
+                    state.with_main_state(MainState {
+                        note_editor,
+                        outline,
+                        ..*main_state
+                    })
+                }
+                note_editor::Message::FoldAll | note_editor::Message::UnfoldAll
+                    if *mode != Mode::Edit =>
+                {
+                    let note_editor = match message {
+                        note_editor::Message::FoldAll => main_state.note_editor.fold_all(),
+                        _ => main_state.note_editor.unfold_all(),
+                    };
+                    let outline = main_state
+                        .outline
+                        .set_folds(note_editor.collapsed_ranges())
+                        .select_at(note_editor.current_row);
+
+                    state.with_main_state(MainState {
+                        note_editor,
+                        outline,
+                        ..*main_state
+                    })
+                }
+                note_editor::Message::ToggleProperties if *mode != Mode::Edit => state
+                    .with_main_state(MainState {
+                        note_editor: main_state.note_editor.toggle_properties(),
+                        ..*main_state
+                    }),
+                note_editor::Message::FollowLink if *mode != Mode::Edit => {
+                    let note_editor = main_state.note_editor.clone().cancel_pending();
+
+                    let Some(link) = note_editor.wiki_link_at_cursor() else {
+                        return state.with_main_state(MainState {
+                            note_editor,
+                            ..*main_state
+                        });
+                    };
+
+                    let Some(note) = link.resolve(&main_state.entries) else {
+                        return update(
+                            config,
+                            state,
+                            toast(
+                                ToastKind::Warning,
+                                &format!("Note not found: {}", link.target),
+                            ),
+                        );
+                    };
+
+                    let mut back_stack = main_state.back_stack.clone();
+                    back_stack.extend(
+                        main_state
+                            .selected_note
+                            .as_ref()
+                            .map(|note| PathBuf::from(&note.path)),
+                    );
+
+                    let path = note.path.clone();
+                    open_note_at_path(
+                        config,
+                        state.with_main_state(MainState {
+                            note_editor,
+                            back_stack,
+                            ..*main_state
+                        }),
+                        &path,
+                    )
+                }
+                note_editor::Message::JumpToWikiLink if *mode != Mode::Edit => {
+                    let note_editor = main_state.note_editor.clone().cancel_pending();
+
+                    let Some(word) = note_editor.word_at_cursor() else {
+                        return state.with_main_state(MainState {
+                            note_editor,
+                            ..*main_state
+                        });
+                    };
+
+                    let Some(note) = main_state.vault.find_note_by_name(&word) else {
+                        return update(
+                            config,
+                            state,
+                            toast(ToastKind::Warning, &format!("Note not found: {word}")),
+                        );
+                    };
+
+                    let mut back_stack = main_state.back_stack.clone();
+                    back_stack.extend(
+                        main_state
+                            .selected_note
+                            .as_ref()
+                            .map(|note| PathBuf::from(&note.path)),
+                    );
+
+                    open_note_at_path(
+                        config,
+                        state.with_main_state(MainState {
+                            note_editor,
+                            back_stack,
+                            ..*main_state
+                        }),
+                        &note.path,
+                    )
+                }
+                note_editor::Message::Back if *mode != Mode::Edit => {
+                    let mut back_stack = main_state.back_stack.clone();
+                    let Some(path) = back_stack.pop() else {
+                        return state;
+                    };
+
+                    open_note_at_path(
+                        config,
+                        state.with_main_state(MainState {
+                            back_stack,
+                            ..*main_state
+                        }),
+                        &path,
+                    )
+                }
+                note_editor::Message::ScrollUp(scroll_amount) if *mode != Mode::Edit => state
+                    .with_main_state(MainState {
+                        note_editor: main_state.note_editor.scroll_up(calc_scroll_amount(
+                            scroll_amount,
+                            state.screen_size.height.into(),
+                        )),
+                        ..*main_state
+                    }),
+                note_editor::Message::ScrollDown(scroll_amount) if *mode != Mode::Edit => state
+                    .with_main_state(MainState {
+                        note_editor: main_state.note_editor.scroll_down(calc_scroll_amount(
+                            scroll_amount,
+                            state.screen_size.height.into(),
+                        )),
+                        ..*main_state
+                    }),
+                note_editor::Message::ToggleExplorer if *mode != Mode::Edit => state
+                    .with_main_state(match main_state.explorer.open {
+                        true => MainState {
+                            explorer: main_state.explorer.toggle(),
+                            ..*main_state
+                        },
+                        false => MainState {
+                            active_pane: ActivePane::Explorer,
+                            explorer: main_state.explorer.toggle().set_active(true),
+                            note_editor: main_state.note_editor.set_active(false),
+                            ..*main_state
+                        },
+                    }),
+                note_editor::Message::ToggleOutline if *mode != Mode::Edit => state
+                    .with_main_state(match main_state.outline.open {
+                        true => MainState {
+                            outline: main_state.outline.toggle(),
+                            ..*main_state
+                        },
+                        false => MainState {
+                            active_pane: ActivePane::Outline,
+                            outline: main_state.outline.toggle().set_active(true),
+                            note_editor: main_state.note_editor.set_active(false),
+                            ..*main_state
+                        },
+                    }),
+                note_editor::Message::Focus => {
+                    state.with_main_state(main_state.focus(ActivePane::NoteEditor))
+                }
+                note_editor::Message::SwitchPaneNext => match main_state.next_split() {
+                    Some(index) => state.with_main_state(main_state.focus_split(index)),
+                    None => state.with_main_state(MainState {
+                        active_pane: ActivePane::Outline,
+                        note_editor: main_state.note_editor.set_active(false),
+                        outline: main_state.outline.set_active(true),
+                        ..*main_state
+                    }),
+                },
+                note_editor::Message::SwitchPanePrevious => match main_state.previous_split() {
+                    Some(index) => state.with_main_state(main_state.focus_split(index)),
+                    None => state.with_main_state(MainState {
+                        active_pane: ActivePane::Explorer,
+                        note_editor: main_state.note_editor.set_active(false),
+                        explorer: main_state.explorer.set_active(true),
+                        ..*main_state
+                    }),
+                },
+                note_editor::Message::SplitVertical
+                    if *mode != Mode::Edit && !main_state.tabs.is_empty() =>
+                {
+                    update(
+                        config,
+                        state,
+                        toast(
+                            ToastKind::Info,
+                            "Close the other tabs to split the note editor",
+                        ),
+                    )
+                }
+                note_editor::Message::SplitVertical if *mode != Mode::Edit => {
+                    state.with_main_state(main_state.split_vertical())
+                }
+                note_editor::Message::CloseSplit if *mode != Mode::Edit => {
+                    state.with_main_state(main_state.close_split())
+                }
+                note_editor::Message::FocusNextSplit if *mode != Mode::Edit => {
+                    let index = main_state.next_split().unwrap_or_default();
+                    state.with_main_state(main_state.focus_split(index))
+                }
+                note_editor::Message::FocusSplit(index) => state
+                    .with_main_state(main_state.focus(ActivePane::NoteEditor).focus_split(index)),
+                note_editor::Message::NextTab if *mode != Mode::Edit => {
+                    let index = main_state.next_tab();
+                    state.with_main_state(main_state.focus_tab(index))
+                }
+                note_editor::Message::PreviousTab if *mode != Mode::Edit => {
+                    let index = main_state.previous_tab();
+                    state.with_main_state(main_state.focus_tab(index))
+                }
+                note_editor::Message::CloseTab if *mode != Mode::Edit => {
+                    let main_state = main_state.commit_edits();
+
+                    match main_state.note_editor.modified {
+                        true => save_changes_before(
+                            config,
+                            state,
+                            main_state,
+                            Message::NoteEditor(message),
+                            "close",
+                        ),
+                        false => state.with_main_state(main_state.close_tab()),
+                    }
+                }
+                note_editor::Message::OpenPrevious if *mode != Mode::Edit => {
+                    match main_state.recent_notes.get(1) {
+                        Some(path) => open_note_at_path(config, state.clone(), path),
+                        None => state,
+                    }
+                }
+                note_editor::Message::ReloadFromDisk => {
+                    let mut note_editor = main_state.note_editor.clone();
+
+                    if let Err(error) = note_editor.reload_from_disk(config.max_note_bytes()) {
+                        let message =
+                            toast(ToastKind::Error, &format!("Failed to reload note: {error}"));
+                        return update(config, state, message);
+                    }
+
+                    let outline = main_state
+                        .outline
+                        .set_nodes(note_editor.nodes(), note_editor.collapsed_ranges())
+                        .select_at(note_editor.current_row);
+                    let selected_note = main_state.selected_note.map(|note| SelectedNote {
+                        content: note_editor.content().to_string(),
+                        ..note
+                    });
+
+                    state.with_main_state(MainState {
+                        note_editor,
+                        outline,
+                        selected_note,
+                        ..*main_state
+                    })
+                }
+                note_editor::Message::OpenExternally => {
+                    let Some(note) = &main_state.selected_note else {
+                        return state;
+                    };
+
+                    match open::that_detached(&note.path) {
+                        Ok(_) => state,
+                        Err(error) => {
+                            let message = toast(
+                                ToastKind::Error,
+                                &format!("Failed to open {}: {error}", note.name),
+                            );
+                            update(config, state, message)
+                        }
+                    }
+                }
+                note_editor::Message::CopyObsidianUri => {
+                    let Some(note) = &main_state.selected_note else {
+                        return state;
+                    };
+
+                    let uri = obsidian_uri(&main_state.vault, Path::new(&note.path)).to_string();
+                    copy(config, state, &uri, &uri)
+                }
+                note_editor::Message::CopyBlock => {
+                    let Some(text) = main_state.note_editor.block_text() else {
+                        return state;
+                    };
+
+                    // The selection is kept for pasting in the text area as well
+                    let mut note_editor = main_state.note_editor.clone();
+                    if note_editor.is_editing() {
+                        note_editor.text_buffer_as_mut().textarea_as_mut().copy();
+                    }
+
+                    copy_text(
+                        config,
+                        state.with_main_state(MainState {
+                            note_editor,
+                            ..*main_state
+                        }),
+                        &text,
+                    )
+                }
+                note_editor::Message::CopyNote => {
+                    if main_state.selected_note.is_none() {
+                        return state;
+                    }
+
+                    let content = main_state.note_editor.content().to_string();
+                    copy_text(config, state, &content)
+                }
+                note_editor::Message::ExportHtml => {
+                    let Some(note) = &main_state.selected_note else {
+                        return state;
+                    };
+
+                    let path = env::temp_dir().join(format!("{}.html", note.name));
+
+                    let message = match fs::write(&path, export_note_html(note))
+                        .and_then(|_| open::that_detached(&path))
+                    {
+                        Ok(_) => toast(
+                            ToastKind::Info,
+                            &format!("Exported note to {}", path.display()),
+                        ),
+                        Err(error) => {
+                            toast(ToastKind::Error, &format!("Failed to export note: {error}"))
+                        }
+                    };
+
+                    update(config, state, message)
+                }
+                note_editor::Message::Export => {
+                    let Some(note) = &main_state.selected_note else {
+                        return state;
+                    };
+
+                    let path = Path::new(&note.path).with_extension("html");
+                    state.with_input_modal_state(
+                        InputModalState::new(InputKind::ExportNote)
+                            .with_value(&path.to_string_lossy()),
+                    )
+                }
+                note_editor::Message::ExportTo(path) => {
+                    let Some(note) = &main_state.selected_note else {
+                        return state;
+                    };
+
+                    // Relative paths are relative to the directory of the note
+                    let path = Path::new(&note.path)
+                        .parent()
+                        .map_or(path.clone(), |directory| directory.join(path));
+
+                    let message = match fs::write(&path, export_note(note, &path)) {
+                        Ok(_) => toast(
+                            ToastKind::Info,
+                            &format!("Exported note to {}", path.display()),
+                        ),
+                        Err(error) => {
+                            toast(ToastKind::Error, &format!("Failed to export note: {error}"))
+                        }
+                    };
+
+                    update(config, state, message)
+                }
+                _ => state,
+            }
+        }
+    }
+}
+
+impl<'a> StatefulWidgetRef for App<'a> {
+    type State = AppState<'a>;
+
+    fn render_ref(&self, area: Rect, buf: &mut Buffer, state: &mut Self::State) {
+        let pending_keys = state
+            .pending_keys
+            .as_ref()
+            .map(|pending_keys| pending_keys.keys.to_string());
+        self.render_screen(area, buf, &mut state.screen, pending_keys);
+        self.render_modals(area, buf, state)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{
+        fs,
+        rc::Rc,
+        time::{Duration, Instant},
+    };
+
+    use basalt_core::obsidian::FileKind;
+    use indoc::indoc;
+    use insta::assert_snapshot;
+    use ratatui::{backend::TestBackend, Terminal};
+    use tempfile::tempdir;
+
+    use super::{
+        test_harness::{note, TestHarness},
+        *,
+    };
+
+    #[test]
+    fn test_calc_scroll_amount() {
+        let cases = [
+            (ScrollAmount::One, 0, 1),
+            (ScrollAmount::One, 40, 1),
+            (ScrollAmount::HalfPage, 0, 0),
+            (ScrollAmount::HalfPage, 1, 0),
+            (ScrollAmount::HalfPage, 41, 20),
+            (ScrollAmount::FullPage, 0, 0),
+            (ScrollAmount::FullPage, 1, 1),
+            (ScrollAmount::FullPage, 41, 41),
+            (ScrollAmount::Lines(0), 41, 0),
+            (ScrollAmount::Lines(5), 0, 5),
+            (ScrollAmount::Lines(5), 41, 5),
+        ];
+
+        cases
+            .into_iter()
+            .for_each(|(scroll_amount, height, expected)| {
+                assert_eq!(
+                    calc_scroll_amount(scroll_amount.clone(), height),
+                    expected,
+                    "{scroll_amount:?} at height {height}"
+                );
+            });
+    }
+
+    #[test]
+    fn test_line_offset() {
+        let content = "# Heading\n\nParagraph\n";
+
+        let tests = [(0, 0), (1, 0), (2, 10), (3, 11), (4, 21), (10, 21)];
+
+        tests.into_iter().for_each(|(line, expected)| {
+            assert_eq!(line_offset(content, line), expected, "{line}");
+        });
+    }
+
+    #[test]
+    fn test_toasts() {
+        let mut config = config::load_base().unwrap();
+        // Rounded up to three ticks
+        config.toast_duration = Some(Duration::from_millis(120));
+
+        let mut terminal = Terminal::new(TestBackend::new(50, 5)).unwrap();
+        let mut render = |state: &AppState| {
+            _ = terminal.clear();
+            terminal
+                .draw(|frame| {
+                    render_toasts(frame.area(), frame.buffer_mut(), &mut state.toasts.clone())
+                })
+                .unwrap();
+            terminal.backend().clone()
+        };
+
+        let state = update(
+            &config,
+            AppState::default(),
+            Message::Toast(ToastKind::Error, "Failed to save note".into()),
+        );
+        assert_eq!(state.toasts[0].ticks, 3);
+        assert_snapshot!("toasts_error", render(&state));
+
+        // The newest toast is shown, the older one keeps counting down behind it
+        let state = update(&config, state, Message::Tick);
+        let state = update(
+            &config,
+            state,
+            Message::Toast(ToastKind::Info, "Note saved".into()),
+        );
+        assert_snapshot!("toasts_info", render(&state));
+
+        let state = update(&config, state, Message::Tick);
+        let state = update(&config, state, Message::Tick);
+        assert_eq!(state.toasts.len(), 1);
+        assert_eq!(state.toasts[0].message, "Note saved");
+
+        let state = update(&config, state, Message::Tick);
+        assert!(state.toasts.is_empty());
+        assert_snapshot!("toasts_expired", render(&state));
+    }
+
+    #[test]
+    fn test_main_layout_pane_at() {
+        let area = Rect::new(0, 0, 120, 40);
+
+        let tests = [
+            ((true, true), (0, 0), None),
+            ((true, true), (1, 0), Some(ActivePane::Explorer)),
+            ((true, true), (35, 20), Some(ActivePane::Explorer)),
+            ((true, true), (36, 20), Some(ActivePane::NoteEditor)),
+            ((true, true), (83, 20), Some(ActivePane::NoteEditor)),
+            ((true, true), (84, 20), Some(ActivePane::Outline)),
+            ((false, false), (6, 20), Some(ActivePane::NoteEditor)),
+            ((false, false), (115, 20), Some(ActivePane::Outline)),
+            // The status bar is not a pane
+            ((true, true), (40, 39), None),
+        ];
+
+        tests
+            .into_iter()
+            .for_each(|((explorer_open, outline_open), (x, y), expected)| {
+                assert_eq!(
+                    MainLayout::new(area, explorer_open, outline_open).pane_at(Position::new(x, y)),
+                    expected,
+                    "({x}, {y})"
+                )
+            });
+    }
+
+    #[test]
+    fn test_wrap_widths() {
+        let area = Rect::new(0, 0, 100, 20);
+        let wrap_widths = |state: &MainState| {
+            state
+                .editor_panes()
+                .iter()
+                .map(|pane| pane.note_editor.text_buffer().wrap_width())
+                .collect::<Vec<_>>()
+        };
+
+        let state = MainState {
+            note_editor: EditorState::default()
+                .set_content("# Note")
+                .set_mode(Mode::Edit),
+            ..Default::default()
+        };
+
+        let layout = state.layout(area);
+        let state = state.with_wrap_widths(area);
+        assert_eq!(
+            wrap_widths(&state),
+            [Some(layout.note_editor.width as usize - 4)]
+        );
+
+        // Each split wraps to its own width
+        let state = state.split_vertical().with_wrap_widths(area);
+        let splits = state.layout(area).splits(2);
+        assert_eq!(
+            wrap_widths(&state),
+            splits
+                .iter()
+                .map(|split| Some(split.width as usize - 4))
+                .collect::<Vec<_>>()
+        );
+
+        // The rendered markdown of Read mode is not soft-wrapped
+        let state = MainState {
+            note_editor: state.note_editor.clone().set_mode(Mode::Read),
+            ..state
+        }
+        .with_wrap_widths(area);
+        assert_eq!(wrap_widths(&state)[state.active_split], None);
+    }
+
+    #[test]
+    fn test_splits() {
+        let note = |name: &str| SelectedNote {
+            name: name.to_string(),
+            path: format!("{name}.md"),
+            content: format!("# {name}"),
+        };
+        let open = |state: MainState<'static>, name: &str| MainState {
+            note_editor: EditorState::default()
+                .set_content(&note(name).content)
+                .set_path(note(name).path.into())
+                .set_active(true),
+            selected_note: Some(note(name)),
+            ..state
+        };
+        let selected_notes = |state: &MainState| {
+            state
+                .editor_panes()
+                .into_iter()
+                .map(|pane| pane.selected_note.map(|note| note.name).unwrap_or_default())
+                .collect::<Vec<_>>()
+        };
+
+        let state = open(
+            MainState {
+                active_pane: ActivePane::NoteEditor,
+                ..Default::default()
+            },
+            "Daily",
+        );
+        assert_eq!(selected_notes(&state), ["Daily"]);
+        assert_eq!(state.clone().close_split().splits.len(), 0);
+
+        let split = state.clone().split_vertical();
+        assert!(!Rc::ptr_eq(
+            &split.splits[0].note_editor.render_cache(),
+            &split.note_editor.render_cache()
+        ));
+
+        let state = open(state.split_vertical(), "Project");
+        assert_eq!(selected_notes(&state), ["Daily", "Project"]);
+        assert_eq!(state.active_split, 1);
+        assert_eq!(state.next_split(), None);
+        assert_eq!(state.previous_split(), Some(0));
+
+        let state = state.split_vertical().focus_split(0);
+        assert_eq!(selected_notes(&state), ["Daily", "Project", "Project"]);
+        assert_eq!(state.selected_note, Some(note("Daily")));
+        assert!(state.note_editor.active());
+        assert!(!state.splits[1].note_editor.active());
+        assert_eq!(state.next_split(), Some(1));
+        assert_eq!(state.previous_split(), None);
+
+        let state = state.close_split();
+        assert_eq!(selected_notes(&state), ["Project", "Project"]);
+        assert_eq!(state.active_split, 0);
+
+        let state = state.close_split();
+        assert!(state.splits.is_empty());
+        assert_eq!(selected_notes(&state), ["Project"]);
+    }
+
+    #[test]
+    fn test_splits_share_note_content() {
+        let dir = tempdir().unwrap();
+        let path = dir.path().join("Tasks.md");
+        fs::write(&path, "- [ ] A\n\n# Heading\n\n- [ ] B\n").unwrap();
+
+        let note = SelectedNote {
+            name: "Tasks".to_string(),
+            path: path.to_string_lossy().to_string(),
+            content: "- [ ] A\n\n# Heading\n\n- [ ] B\n".to_string(),
+        };
+        let toggle_task = |state: MainState<'static>, row: usize| MainState {
+            note_editor: state.note_editor.clone().set_row(row).toggle_task(),
+            ..state
+        };
+
+        let state = MainState {
+            active_pane: ActivePane::NoteEditor,
+            note_editor: EditorState::default()
+                .set_content(&note.content)
+                .set_path(path.clone())
+                .set_active(true),
+            selected_note: Some(note),
+            ..Default::default()
+        };
+
+        // The edit of the right split is shown in the left split once it is focused
+        let state = toggle_task(state.split_vertical(), 0).focus_split(0);
+        assert_eq!(
+            state.note_editor.content(),
+            "- [x] A\n\n# Heading\n\n- [ ] B\n"
+        );
+        assert!(state.note_editor.modified);
+
+        // Saving from the left split keeps the edits of both splits
+        let state = toggle_task(state, 2).save().unwrap();
+        assert_eq!(
+            fs::read_to_string(&path).unwrap(),
+            "- [x] A\n\n# Heading\n\n- [x] B\n"
+        );
+        assert!(state.modified_notes().is_empty());
+
+        let state = state.focus_split(1);
+        assert_eq!(
+            state.note_editor.content(),
+            "- [x] A\n\n# Heading\n\n- [x] B\n"
+        );
+    }
+
+    #[test]
+    fn test_tabs() {
+        let dir = tempdir().unwrap();
+        let [a, b, c] = ["A", "B", "C"].map(|name| {
+            let path = dir.path().join(format!("{name}.md"));
+            fs::write(&path, format!("# {name}")).unwrap();
+            path
+        });
+
+        fn open_in_new_tab<'a>(mut harness: TestHarness<'a>, path: &Path) -> TestHarness<'a> {
+            if let ScreenState::Main(main_state) = &mut harness.state.screen {
+                main_state.explorer = main_state.explorer.reveal(path);
+            }
+            harness.send(Message::Explorer(explorer::Message::OpenInNewTab))
+        }
+
+        fn send(harness: TestHarness, message: note_editor::Message) -> TestHarness {
+            harness.send(Message::NoteEditor(message))
+        }
+
+        let tabs = |harness: &TestHarness| {
+            let main_state = harness.main_state();
+            let names = main_state
+                .all_panes()
+                .into_iter()
+                .map(|pane| pane.selected_note.map(|note| note.name).unwrap_or_default())
+                .collect::<Vec<_>>();
+            (names, main_state.active_tab)
+        };
+
+        // Without an open note, the note is opened without a tab bar
+        let vault = test_vault(dir.path());
+        let harness = open_in_new_tab(TestHarness::new(&vault, vault.entries()), &a);
+        assert_eq!(tabs(&harness), (vec!["A".to_string()], 0));
+        assert!(harness.main_state().tabs.is_empty());
+
+        let harness = open_in_new_tab(open_in_new_tab(harness, &b), &c);
+        assert_eq!(
+            tabs(&harness),
+            (vec!["A".into(), "B".into(), "C".into()], 2)
+        );
+        assert_eq!(harness.selected_note(), Some("C"));
+
+        let area = Rect::new(0, 0, 120, 40);
+        assert_eq!(harness.main_state().layout(area).tab_bar.height, 1);
+
+        // Switching tabs wraps around
+        let harness = send(harness, note_editor::Message::NextTab);
+        assert_eq!(harness.selected_note(), Some("A"));
+        assert_eq!(
+            harness.main_state().explorer.selected_path(),
+            Some(a.clone())
+        );
+        let harness = send(harness, note_editor::Message::PreviousTab);
+        assert_eq!(harness.selected_note(), Some("C"));
+
+        // A note that is already open is shown in its tab
+        let harness = open_in_new_tab(harness, &a);
+        assert_eq!(tabs(&harness).1, 0);
+        assert_eq!(tabs(&harness).0.len(), 3);
+
+        // Closing the active tab selects the tab to its right, or the left one for the last tab
+        let harness = send(
+            send(harness, note_editor::Message::NextTab),
+            note_editor::Message::CloseTab,
+        );
+        assert_eq!(tabs(&harness), (vec!["A".into(), "C".into()], 1));
+        assert_eq!(harness.selected_note(), Some("C"));
+
+        let harness = send(harness, note_editor::Message::CloseTab);
+        assert!(harness.main_state().tabs.is_empty());
+        assert_eq!(harness.selected_note(), Some("A"));
+        assert_eq!(harness.main_state().layout(area).tab_bar, Rect::default());
+
+        // The last note is not closed
+        let harness = send(harness, note_editor::Message::CloseTab);
+        assert_eq!(harness.selected_note(), Some("A"));
+
+        // Tabs and splits are not used at the same time
+        let harness = open_in_new_tab(harness, &b);
+        let harness = send(harness, note_editor::Message::SplitVertical);
+        assert!(harness.main_state().splits.is_empty());
+
+        let harness = send(
+            send(harness, note_editor::Message::CloseTab),
+            note_editor::Message::SplitVertical,
+        );
+        let harness = open_in_new_tab(harness, &c);
+        assert_eq!(harness.main_state().splits.len(), 2);
+        assert!(harness.main_state().tabs.is_empty());
+    }
+
+    #[test]
+    fn test_tabs_open_and_delete() {
+        let dir = tempdir().unwrap();
+        let [a, b, c] = ["A", "B", "C"].map(|name| {
+            let path = dir.path().join(format!("{name}.md"));
+            fs::write(&path, format!("# {name}")).unwrap();
+            path
+        });
+
+        fn send<'a>(
+            mut harness: TestHarness<'a>,
+            path: &Path,
+            message: explorer::Message,
+        ) -> TestHarness<'a> {
+            if let ScreenState::Main(main_state) = &mut harness.state.screen {
+                main_state.explorer = main_state.explorer.reveal(path);
+            }
+            harness.send(Message::Explorer(message))
+        }
+
+        let tabs = |harness: &TestHarness| {
+            let main_state = harness.main_state();
+            let names = main_state
+                .all_panes()
+                .into_iter()
+                .map(|pane| pane.selected_note.map(|note| note.name).unwrap_or_default())
+                .collect::<Vec<_>>();
+            (names, main_state.active_tab)
+        };
+
+        let vault = test_vault(dir.path());
+        let harness = [&a, &b, &c].into_iter().fold(
+            TestHarness::new(&vault, vault.entries()),
+            |harness, path| send(harness, path, explorer::Message::OpenInNewTab),
+        );
+        assert_eq!(
+            tabs(&harness),
+            (vec!["A".into(), "B".into(), "C".into()], 2)
+        );
+
+        // Opening a note that is open in another tab shows its tab
+        let harness = send(harness, &a, explorer::Message::Open);
+        assert_eq!(
+            tabs(&harness),
+            (vec!["A".into(), "B".into(), "C".into()], 0)
+        );
+        assert_eq!(harness.selected_note(), Some("A"));
+
+        // Deleting a note closes its tab and keeps the active tab
+        let harness = send(harness, &b, explorer::Message::DeleteNote);
+        assert_eq!(tabs(&harness), (vec!["A".into(), "C".into()], 0));
+        assert_eq!(harness.selected_note(), Some("A"));
+
+        let harness = send(harness, &a, explorer::Message::DeleteNote);
+        assert!(harness.main_state().tabs.is_empty());
+        assert_eq!(harness.selected_note(), Some("C"));
+    }
+
+    #[test]
+    fn test_jump_to_wiki_link() {
+        let dir = tempdir().unwrap();
+        let [index, _, missing] = [
+            ("Index", "Project"),
+            ("Project", "# Project"),
+            ("Missing", "Nowhere"),
+        ]
+        .map(|(name, content)| {
+            let path = dir.path().join(format!("{name}.md"));
+            fs::write(&path, content).unwrap();
+            path
+        });
+
+        let vault = test_vault(dir.path());
+        let open = |path: &Path| {
+            let mut harness = TestHarness::new(&vault, vault.entries());
+            if let ScreenState::Main(main_state) = &mut harness.state.screen {
+                main_state.explorer = main_state.explorer.reveal(path);
+            }
+            harness
+                .send(Message::Explorer(explorer::Message::Open))
+                .send(Message::NoteEditor(note_editor::Message::JumpToWikiLink))
+        };
+
+        let harness = open(&index);
+        assert_eq!(harness.selected_note(), Some("Project"));
+        assert_eq!(harness.main_state().back_stack, [index]);
+
+        let harness = open(&missing);
+        assert_eq!(harness.selected_note(), Some("Missing"));
+        assert!(harness.main_state().back_stack.is_empty());
+    }
+
+    #[test]
+    fn test_indent_line() {
+        let dir = tempdir().unwrap();
+        let [list, paragraph] =
+            [("List", "- One\n- Two\n"), ("Paragraph", "Text\n")].map(|(name, content)| {
+                let path = dir.path().join(format!("{name}.md"));
+                fs::write(&path, content).unwrap();
+                path
+            });
+
+        let vault = test_vault(dir.path());
+
+        fn edit<'a>(
+            vault: &'a Vault,
+            path: &Path,
+            message: note_editor::Message,
+        ) -> TestHarness<'a> {
+            let mut harness = TestHarness::new(vault, vault.entries());
+            harness.config.experimental_editor = true;
+            harness
+                .open(path)
+                .send(Message::NoteEditor(note_editor::Message::EditMode))
+                .send(Message::NoteEditor(message))
+        }
+        let lines = |harness: &TestHarness| {
+            harness
+                .main_state()
+                .note_editor
+                .text_buffer()
+                .lines()
+                .to_vec()
+        };
+
+        let harness = edit(&vault, &list, note_editor::Message::IndentLine);
+        assert_eq!(lines(&harness), ["  - One", "- Two"]);
+
+        // Outside of lists the tab is typed
+        let harness = edit(&vault, &paragraph, note_editor::Message::IndentLine);
+        assert_eq!(lines(&harness), ["    Text"]);
+
+        // A line without indentation is left as is and there is nothing to undo
+        let harness = edit(&vault, &list, note_editor::Message::OutdentLine);
+        assert_eq!(lines(&harness), ["- One", "- Two"]);
+        assert!(!harness.main_state().note_editor.can_undo());
+    }
+
+    fn test_vault(path: &Path) -> Vault {
+        Vault {
+            name: "Vault".into(),
+            path: path.to_path_buf(),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_explorer_open() {
+        let vault = Vault::default();
+        let harness = TestHarness::new(&vault, vec![note("Daily"), note("Ideas")]);
+        assert_eq!(harness.active_component(), ActivePane::Explorer);
+        assert_eq!(harness.selected_note(), None);
+
+        let harness = harness.keys([KeyEvent::from(KeyCode::Enter)]);
+        assert_eq!(harness.selected_note(), Some("Daily"));
+
+        let harness = harness.type_str("j").keys([KeyEvent::from(KeyCode::Enter)]);
+        assert_eq!(harness.selected_note(), Some("Ideas"));
+        assert_eq!(harness.main_state().recent_notes.len(), 2);
+    }
+
+    #[test]
+    fn test_explorer_open_unreadable() {
+        let dir = tempdir().unwrap();
+        let empty = dir.path().join("Empty.md");
+        let broken = dir.path().join("Broken.md");
+        fs::write(&empty, "").unwrap();
+        // A directory with a note extension cannot be read as a note
+        fs::create_dir(&broken).unwrap();
+
+        let entries = [("Empty", &empty), ("Broken", &broken)]
+            .into_iter()
+            .map(|(name, path)| {
+                VaultEntry::File(Note {
+                    name: name.to_string(),
+                    path: path.clone(),
+                })
+            })
+            .collect();
+
+        let vault = test_vault(dir.path());
+        let harness = TestHarness::new(&vault, entries).open(&empty);
+        assert_eq!(harness.selected_note(), Some("Empty"));
+        assert!(harness.state.toasts.is_empty());
+
+        let harness = harness.open(&broken);
+        assert_eq!(harness.selected_note(), Some("Broken"));
+        assert_eq!(harness.state.toasts.len(), 1);
+        assert_eq!(harness.state.toasts[0].kind, ToastKind::Error);
+        assert!(harness.state.toasts[0]
+            .message
+            .starts_with("Failed to read note: "));
+    }
+
+    #[test]
+    fn test_note_editor_export() {
+        let dir = tempdir().unwrap();
+        let path = dir.path().join("Note.md");
+        fs::write(&path, "# Note\n\n- [x] See [[Other]]\n").unwrap();
+
+        let entries = vec![VaultEntry::File(Note {
+            name: "Note".into(),
+            path: path.clone(),
+        })];
+
+        let vault = test_vault(dir.path());
+        let harness = TestHarness::new(&vault, entries)
+            .open(&path)
+            .send(Message::NoteEditor(note_editor::Message::Export));
+        assert_eq!(harness.active_component(), ActivePane::InputModal);
+        assert_eq!(
+            harness.state.input_modal.value(),
+            dir.path().join("Note.html").to_string_lossy()
+        );
+
+        let harness = harness.keys([KeyEvent::from(KeyCode::Enter)]);
+        assert_eq!(harness.state.toasts[0].kind, ToastKind::Info);
+        assert!(fs::read_to_string(dir.path().join("Note.html"))
+            .unwrap()
+            .contains("<h1 id=\"note\">Note</h1>"));
+
+        let harness = harness.send(Message::NoteEditor(note_editor::Message::ExportTo(
+            "Note.txt".into(),
+        )));
+        assert_eq!(harness.state.toasts.len(), 2);
+        assert_eq!(
+            fs::read_to_string(dir.path().join("Note.txt")).unwrap(),
+            "Note\n\n- See Other"
+        );
+
+        let harness = harness.send(Message::NoteEditor(note_editor::Message::ExportTo(
+            "Missing/Note.html".into(),
+        )));
+        assert_eq!(harness.state.toasts[2].kind, ToastKind::Error);
+        assert!(harness.state.toasts[2]
+            .message
+            .starts_with("Failed to export note: "));
+    }
+
+    #[test]
+    fn test_obsidian_uri() {
+        let vault = Vault {
+            name: "My Vault".into(),
+            path: "/home/user/My Vault".into(),
+            ..Default::default()
+        };
+
+        let tests = [
+            (
+                "/home/user/My Vault/Daily/Today.md",
+                "obsidian://open?vault=My%20Vault&file=Daily%2FToday",
+            ),
+            (
+                "/home/user/My Vault/image.png",
+                "obsidian://open?vault=My%20Vault&file=image.png",
+            ),
+            ("/tmp/Note.md", "obsidian://open?path=%2Ftmp%2FNote.md"),
+        ];
+
+        tests.into_iter().for_each(|(path, expected)| {
+            let uri = obsidian_uri(&vault, Path::new(path));
+            assert_eq!(uri.to_string(), expected);
+            assert_eq!(
+                obsidian::ObsidianConfig::from([("My Vault", vault.clone())])
+                    .resolve_uri(&uri)
+                    .map(|(_, resolved)| resolved),
+                Some(PathBuf::from(path)).filter(|path| path.starts_with(&vault.path))
+            );
+        });
+    }
+
+    #[test]
+    fn test_outline_follows_cursor() {
+        let dir = tempdir().unwrap();
+        let path = dir.path().join("Note.md");
+        fs::write(
+            &path,
+            indoc! {r#"
+                # One
+
+                Text
+
+                ## Two
+
+                Text
+
+                # Three
+            "#},
+        )
+        .unwrap();
+
+        let entries = vec![VaultEntry::File(Note {
+            name: "Note".into(),
+            path: path.clone(),
+        })];
+
+        let vault = test_vault(dir.path());
+        let harness = TestHarness::new(&vault, entries).open(&path);
+        let selected = |harness: &TestHarness| harness.main_state().outline.list_state.selected();
+        assert_eq!(selected(&harness), Some(0));
+
+        let harness = harness
+            .send(Message::NoteEditor(note_editor::Message::CursorDown))
+            .send(Message::NoteEditor(note_editor::Message::CursorDown));
+        assert_eq!(harness.main_state().note_editor.current_row, 2);
+        assert_eq!(selected(&harness), Some(1));
+
+        let harness = harness.send(Message::NoteEditor(note_editor::Message::CursorUp));
+        assert_eq!(selected(&harness), Some(0));
+
+        let harness = harness.send(Message::Outline(outline::Message::SelectByHeadingText(
+            "Three".into(),
+        )));
+        assert_eq!(selected(&harness), Some(2));
+        assert_eq!(harness.main_state().note_editor.current_row, 4);
+
+        let harness = harness.send(Message::Outline(outline::Message::SelectByHeadingText(
+            "Missing".into(),
+        )));
+        assert_eq!(selected(&harness), Some(2));
+        assert_eq!(harness.main_state().note_editor.current_row, 4);
+    }
+
+    #[test]
+    fn test_indent_list_item() {
+        let dir = tempdir().unwrap();
+        let path = dir.path().join("List.md");
+        fs::write(&path, "- One\n- Two\n").unwrap();
+
+        let entries = vec![VaultEntry::File(Note {
+            name: "List".into(),
+            path: path.clone(),
+        })];
+
+        let vault = test_vault(dir.path());
+        let mut harness = TestHarness::new(&vault, entries);
+        harness.config.experimental_editor = true;
+
+        let harness = harness
+            .open(&path)
+            .send(Message::NoteEditor(note_editor::Message::EditMode))
+            .send(Message::NoteEditor(note_editor::Message::CursorDown))
+            .keys([KeyEvent::new(KeyCode::Tab, KeyModifiers::NONE)]);
+
+        let lines = harness.main_state().note_editor.text_buffer().lines();
+        assert_eq!(lines[1], "  - Two");
+
+        let harness = harness.send(Message::NoteEditor(note_editor::Message::ExitMode));
+        assert_eq!(
+            harness.main_state().note_editor.content(),
+            "- One\n  - Two\n"
+        );
+
+        let harness = harness
+            .send(Message::NoteEditor(note_editor::Message::EditMode))
+            .send(Message::NoteEditor(note_editor::Message::CursorDown))
+            .keys([KeyEvent::new(KeyCode::BackTab, KeyModifiers::SHIFT)])
+            .send(Message::NoteEditor(note_editor::Message::ExitMode));
+        assert_eq!(harness.main_state().note_editor.content(), "- One\n- Two\n");
+    }
+
+    #[test]
+    fn test_key_sequences() {
+        let vault = Vault::default();
+        let mut harness =
+            TestHarness::new(&vault, vec![note("A"), note("B"), note("C"), note("D")]);
+        harness.config = config::load_from_str(indoc! {r#"
+            [global]
+            key_bindings = [
+             { key = "space t", command = "recent_notes_modal_toggle" },
+            ]
+
+            [explorer]
+            key_bindings = [
+             { key = "z", command = "explorer_down" },
+             { key = "z z", command = "explorer_up" },
+            ]
+        "#})
+        .unwrap();
+
+        let selected = |harness: &TestHarness| harness.main_state().explorer.list_state.selected();
+        let pending = |harness: &TestHarness| {
+            harness
+                .state
+                .pending_keys
+                .as_ref()
+                .map(|pending_keys| pending_keys.keys.to_string())
+        };
+        let space = KeyEvent::from(KeyCode::Char(' '));
+
+        // Single keys behave as before
+        let harness = harness.type_str("j");
+        assert_eq!(selected(&harness), Some(1));
+        assert_eq!(pending(&harness), None);
+
+        // The prefix is pending until the sequence is complete
+        let started = harness.clone().keys([space]);
+        assert_eq!(pending(&started), Some("space".to_string()));
+        assert_eq!(started.active_component(), ActivePane::Explorer);
+
+        let completed = started.clone().type_str("t");
+        assert_eq!(pending(&completed), None);
+        assert_eq!(completed.active_component(), ActivePane::RecentNotesModal);
+
+        // Esc cancels the sequence
+        let cancelled = started.clone().keys([KeyEvent::from(KeyCode::Esc)]);
+        assert_eq!(pending(&cancelled), None);
+        assert_eq!(cancelled.active_component(), ActivePane::Explorer);
+
+        // A key that does not continue the sequence is handled on its own
+        let interrupted = started.type_str("j");
+        assert_eq!(pending(&interrupted), None);
+        assert_eq!(selected(&interrupted), Some(2));
+
+        // The longer sequence is preferred, and the prefix is used when the sequence times out
+        let prefix = harness.type_str("z");
+        assert_eq!(pending(&prefix), Some("z".to_string()));
+        assert_eq!(selected(&prefix), Some(1));
+
+        assert_eq!(selected(&prefix.clone().type_str("z")), Some(0));
+
+        // A key that does not continue the sequence sends the prefix first
+        let down = Message::Explorer(explorer::Message::Down);
+        assert_eq!(
+            handle_key_sequence(
+                &prefix.config,
+                &prefix.state,
+                &KeyEvent::from(KeyCode::Char('j')),
+                false
+            ),
+            Some(Message::KeySequence(key_sequence::Message::Complete(vec![
+                down.clone(),
+                down
+            ])))
+        );
+        let interrupted = prefix.clone().type_str("j");
+        assert_eq!(pending(&interrupted), None);
+        assert_eq!(selected(&interrupted), Some(3));
+
+        let timed_out = prefix.send(Message::KeySequence(key_sequence::Message::Timeout));
+        assert_eq!(pending(&timed_out), None);
+        assert_eq!(selected(&timed_out), Some(2));
+    }
+
+    #[test]
+    fn test_splash_filter() {
+        let obsidian_config = ObsidianConfig::from(
+            [("Notes", 3), ("Journal", 2), ("Work", 1)].map(|(name, ts)| {
+                (
+                    name,
+                    Vault {
+                        name: name.to_string(),
+                        path: ".".into(),
+                        ts,
+                        ..Default::default()
+                    },
+                )
+            }),
+        );
+        let config = config::load_base().unwrap();
+        let state = AppState::default()
+            .with_splash_state(SplashState::new("0.0.0", &obsidian_config))
+            .set_running(true);
+        let harness = TestHarness { config, state };
+
+        // Returns the filter query and the vault that would be opened
+        let splash_state = |harness: &TestHarness| match &harness.state.screen {
+            ScreenState::Splash(splash_state) => (
+                splash_state.search_query().to_string(),
+                splash_state.select().selected(),
+            ),
+            _ => panic!("Expected the splash screen"),
+        };
+
+        // Bound keys keep their binding until the filter is started
+        let harness = harness.type_str("j");
+        assert_eq!(splash_state(&harness), (String::new(), Some(1)));
+
+        // Once started, bound keys are typed as well
+        let harness = harness.type_str("or").type_str("kq");
+        assert!(harness.state.is_running);
+        assert_eq!(splash_state(&harness), ("orkq".to_string(), None));
+
+        let harness = harness.keys([KeyEvent::from(KeyCode::Backspace)].repeat(2));
+        assert_eq!(splash_state(&harness), ("or".to_string(), Some(2)));
+
+        // Esc clears the filter before the global key bindings apply again
+        let harness = harness.keys([KeyEvent::from(KeyCode::Esc)]);
+        assert_eq!(splash_state(&harness), (String::new(), Some(0)));
+
+        let harness = harness.type_str("?");
+        assert_eq!(harness.active_component(), ActivePane::HelpModal);
+    }
+
+    #[test]
+    fn test_splash_resume() {
+        let dir = tempdir().unwrap();
+        ["Daily", "Ideas"].iter().for_each(|name| {
+            fs::write(dir.path().join(format!("{name}.md")), format!("# {name}")).unwrap()
+        });
+
+        let obsidian_config = ObsidianConfig::from([("Vault", test_vault(dir.path()))]);
+        let vault = obsidian_config.vaults_by_recent()[0];
+        let config = config::load_base().unwrap();
+        let resume = KeyEvent::new(KeyCode::Char('r'), KeyModifiers::CONTROL);
+        let splash = |splash_state| TestHarness {
+            config: config.clone(),
+            state: AppState::default()
+                .with_splash_state(splash_state)
+                .set_running(true),
+        };
+        let splash_state = SplashState::new("0.0.0", &obsidian_config);
+
+        // Without a previous session there is nothing to resume
+        let harness = splash(splash_state.clone()).keys([resume]);
+        assert_eq!(harness.active_component(), ActivePane::Splash);
+
+        let harness = splash(splash_state.with_session(vault, Some(dir.path().join("Ideas.md"))))
+            .keys([resume]);
+        assert!(harness.state.is_transitioning());
+
+        let harness = TestHarness {
+            state: (0..20).fold(harness.state, |state, _| {
+                update(&harness.config, state, Message::Tick)
+            }),
+            ..harness
+        };
+        assert_eq!(harness.main_state().vault.name, "Vault");
+        assert_eq!(harness.selected_note(), Some("Ideas"));
+    }
+
+    #[test]
+    fn test_help_modal_toggle() {
+        let vault = Vault::default();
+        let harness = TestHarness::new(&vault, vec![note("Daily")]);
+
+        let harness = harness.type_str("?");
+        assert_eq!(harness.active_component(), ActivePane::HelpModal);
+
+        // Explorer key bindings do not apply while the help modal is open
+        let harness = harness.keys([KeyEvent::from(KeyCode::Enter)]);
+        assert_eq!(harness.selected_note(), None);
+
+        let harness = harness.type_str("?");
+        assert_eq!(harness.active_component(), ActivePane::Explorer);
+
+        let harness = harness.type_str("?").keys([KeyEvent::from(KeyCode::Esc)]);
+        assert_eq!(harness.active_component(), ActivePane::Explorer);
+    }
+
+    #[test]
+    fn test_toggle_bookmark() {
+        let dir = tempdir().unwrap();
+        ["Daily", "Ideas"].iter().for_each(|name| {
+            fs::write(dir.path().join(format!("{name}.md")), format!("# {name}")).unwrap()
+        });
+
+        let vault = test_vault(dir.path());
+        let daily = dir.path().join("Daily.md");
+
+        let harness = TestHarness::new(&vault, vault.entries()).type_str("b");
+        assert!(harness.main_state().explorer.is_bookmarked(&daily));
+        assert_eq!(
+            vault.bookmarks().unwrap()[0].path,
+            PathBuf::from("Daily.md")
+        );
+
+        // Bookmarks are read from the vault when it is opened
+        let harness = TestHarness::new(&vault, vault.entries());
+        assert!(harness.main_state().explorer.is_bookmarked(&daily));
+
+        let harness = harness.type_str("b");
+        assert!(!harness.main_state().explorer.is_bookmarked(&daily));
+        assert_eq!(vault.bookmarks().unwrap(), vec![]);
+    }
+
+    #[test]
+    fn test_outline_open() {
+        let dir = tempdir().unwrap();
+        let path = dir.path().join("Note.md");
+        fs::write(
+            &path,
+            indoc! {r#"
+                # Note
+
+                Introduction.
+
+                ## First
+
+                First section.
+
+                ## Second
+
+                Second section.
+            "#},
+        )
+        .unwrap();
+
+        let vault = test_vault(dir.path());
+        let harness = TestHarness::new(&vault, vault.entries())
+            .open(&path)
+            .send(Message::NoteEditor(note_editor::Message::SwitchPaneNext));
+        assert_eq!(harness.active_component(), ActivePane::Outline);
+
+        // The headings are the nodes 0, 2 and 4
+        [(0, 0), (1, 2), (2, 4)]
+            .into_iter()
+            .for_each(|(heading, row)| {
+                let harness = harness.clone().type_str(&"j".repeat(heading)).type_str("o");
+
+                assert_eq!(harness.main_state().note_editor.current_row, row);
+                assert_eq!(harness.active_component(), ActivePane::NoteEditor);
+            });
+    }
+
+    #[test]
+    fn test_outline_expand_follows_folds() {
+        let dir = tempdir().unwrap();
+        let path = dir.path().join("Note.md");
+        fs::write(
+            &path,
+            indoc! {r#"
+                # Note
+
+                ## First
+
+                First section.
+
+                ## Second
+
+                Second section.
+            "#},
+        )
+        .unwrap();
+
+        let vault = test_vault(dir.path());
+        let harness = TestHarness::new(&vault, vault.entries()).open(&path);
+        let expanded = |harness: &TestHarness| {
+            harness
+                .main_state()
+                .outline
+                .selected()
+                .is_some_and(|item| item.is_expanded())
+        };
+        let collapsed_ranges =
+            |harness: &TestHarness| harness.main_state().note_editor.collapsed_ranges().to_vec();
+
+        let harness = harness.send(Message::NoteEditor(note_editor::Message::FoldAll));
+        assert_eq!(collapsed_ranges(&harness), [1..5, 2..3, 4..5]);
+        assert!(!expanded(&harness));
+
+        // Expanding the folded heading unfolds its section instead of toggling the fold
+        let harness = harness.send(Message::Outline(outline::Message::Expand));
+        assert!(expanded(&harness));
+        assert_eq!(collapsed_ranges(&harness), [2..3, 4..5]);
+
+        let harness = harness.send(Message::Outline(outline::Message::Expand));
+        assert!(!expanded(&harness));
+        assert_eq!(collapsed_ranges(&harness), [2..3, 4..5, 1..5]);
+
+        let harness = harness
+            .send(Message::NoteEditor(note_editor::Message::UnfoldAll))
+            .send(Message::Outline(outline::Message::Expand));
+        assert!(!expanded(&harness));
+        assert_eq!(collapsed_ranges(&harness), vec![1..5]);
+
+        // Reloading the outline, e.g. after an edit, keeps the folded headings collapsed
+        let main_state = harness.main_state();
+        let outline = main_state.outline.clone().set_nodes(
+            main_state.note_editor.nodes(),
+            main_state.note_editor.collapsed_ranges(),
+        );
+        assert_eq!(outline, main_state.outline);
+    }
+
+    #[test]
+    fn test_open_attachment() {
+        let dir = tempdir().unwrap();
+        let note = dir.path().join("Daily.md");
+        let image = dir.path().join("diagram.png");
+        let canvas = dir.path().join("Board.canvas");
+        fs::write(&note, "# Daily").unwrap();
+        fs::write(
+            &image,
+            [
+                [0x89, b'P', b'N', b'G', b'\r', b'\n', 0x1a, b'\n'],
+                [0, 0, 0, 13, b'I', b'H', b'D', b'R'],
+                [0, 0, 2, 128, 0, 0, 1, 224],
+            ]
+            .concat(),
+        )
+        .unwrap();
+        fs::write(&canvas, r#"{"nodes":[{},{}],"edges":[]}"#).unwrap();
+
+        let vault = test_vault(dir.path());
+        let mut harness = TestHarness::new(&vault, vault.entries());
+        harness.config.experimental_editor = true;
+
+        let harness = harness.open(&image);
+        assert_eq!(harness.selected_note(), Some("diagram.png"));
+        assert_eq!(
+            harness.main_state().note_editor.attachment(),
+            Some(&Attachment {
+                name: "diagram.png".into(),
+                kind: FileKind::Image,
+                size: 24,
+                details: Some("640 × 480 px".into()),
+            })
+        );
+
+        // The canvas is not parsed as markdown and cannot be edited
+        let harness = harness
+            .open(&canvas)
+            .send(Message::NoteEditor(note_editor::Message::EditMode));
+        let note_editor = &harness.main_state().note_editor;
+        assert_eq!(
+            note_editor.attachment().map(|attachment| attachment.kind),
+            Some(FileKind::Canvas)
+        );
+        assert_eq!(
+            note_editor
+                .attachment()
+                .and_then(|attachment| attachment.details.as_deref()),
+            Some("2 cards, 0 connections")
+        );
+        assert_eq!(note_editor.mode(), Mode::Read);
+        assert!(note_editor.nodes().is_empty());
+
+        let harness = harness.open(&note);
+        assert_eq!(harness.main_state().note_editor.attachment(), None);
+        assert_eq!(harness.main_state().note_editor.content(), "# Daily");
+    }
+
+    #[test]
+    fn test_read_only() {
+        let content = "# Title\n\nParagraph\n\n## Section\n\nText";
+        let harness = TestHarness::read_only("stdin", content);
+
+        let main_state = harness.main_state();
+        assert_eq!(main_state.vault, Vault::default());
+        assert!(main_state.entries.is_empty());
+        assert!(!main_state.explorer.is_open());
+        assert_eq!(harness.active_component(), ActivePane::NoteEditor);
+        assert_eq!(harness.selected_note(), Some("stdin"));
+        assert_eq!(harness.state.session(), None);
+
+        // Editing, saving and the explorer are not available
+        let harness = harness
+            .send(Message::NoteEditor(note_editor::Message::EditMode))
+            .type_str("x")
+            .send(Message::NoteEditor(note_editor::Message::Save))
+            .send(Message::NoteEditor(note_editor::Message::ToggleExplorer))
+            .send(Message::Explorer(explorer::Message::Open));
+        let main_state = harness.main_state();
+        assert_eq!(main_state.note_editor.mode(), Mode::Read);
+        assert_eq!(main_state.note_editor.content(), content);
+        assert!(!main_state.note_editor.modified);
+        assert!(!main_state.explorer.is_open());
+        assert_eq!(harness.active_component(), ActivePane::NoteEditor);
+
+        let harness = harness.send(Message::NoteEditor(note_editor::Message::CursorDown));
+        assert_eq!(harness.main_state().note_editor.current_row, 1);
+
+        // Switching panes skips the explorer
+        let harness = harness.send(Message::NoteEditor(
+            note_editor::Message::SwitchPanePrevious,
+        ));
+        assert_eq!(harness.active_component(), ActivePane::Outline);
+        let harness = harness.send(Message::Outline(outline::Message::SwitchPaneNext));
+        assert_eq!(harness.active_component(), ActivePane::NoteEditor);
+
+        let harness = harness.type_str("?");
+        assert_eq!(harness.active_component(), ActivePane::HelpModal);
+
+        let harness = harness.type_str("?q");
+        assert!(!harness.state.is_running);
+    }
+
+    #[test]
+    fn test_open_unreadable_note() {
+        let dir = tempdir().unwrap();
+        let large = dir.path().join("Large.md");
+        let binary = dir.path().join("Binary.md");
+        fs::write(&large, "# Large\n".repeat(256 * 1024)).unwrap();
+        fs::write(&binary, [b'#', b' ', 0xff, 0xfe]).unwrap();
+
+        let vault = test_vault(dir.path());
+        let mut harness = TestHarness::new(&vault, vault.entries());
+        harness.config.experimental_editor = true;
+        harness.config.max_note_size_mb = Some(1);
+
+        let harness = harness
+            .open(&large)
+            .send(Message::NoteEditor(note_editor::Message::EditMode));
+        let note_editor = &harness.main_state().note_editor;
+        let placeholder = note_editor.placeholder().unwrap();
+        assert_eq!(
+            placeholder.reason,
+            "Large is too large to open (2.0 MB, the limit is 1.0 MB)"
+        );
+        assert!(placeholder
+            .preview
+            .as_deref()
+            .is_some_and(|preview| preview.starts_with("# Large\n# Large\n")));
+        assert_eq!(note_editor.mode(), Mode::Read);
+        assert_eq!(note_editor.content(), "");
+        assert!(harness.state.toasts.is_empty());
+
+        let harness = harness.open(&binary);
+        assert_eq!(
+            harness.main_state().note_editor.placeholder(),
+            Some(&Placeholder {
+                reason: "Binary is not a text file and cannot be opened".into(),
+                preview: None,
+            })
+        );
+        assert!(harness.state.toasts.is_empty());
+    }
+
+    #[test]
+    fn test_tag_panel() {
+        let dir = tempdir().unwrap();
+        [
+            ("Daily", "Work on #project/basalt"),
+            ("Ideas", "---\ntags: [idea]\n---\n# Ideas"),
+        ]
+        .iter()
+        .for_each(|(name, content)| {
+            fs::write(dir.path().join(format!("{name}.md")), content).unwrap()
+        });
+
+        let vault = test_vault(dir.path());
+        let harness = TestHarness::new(&vault, vault.entries()).type_str("#");
+        assert_eq!(harness.active_component(), ActivePane::TagPanel);
+        assert_eq!(
+            harness.state.tag_panel.tags.keys().collect::<Vec<_>>(),
+            ["idea", "project", "project/basalt"]
+        );
+
+        // Lists the notes of the project tag and opens the first one
+        let harness = harness.type_str("j").keys([
+            KeyEvent::from(KeyCode::Enter),
+            KeyEvent::from(KeyCode::Enter),
+        ]);
+        assert_eq!(harness.selected_note(), Some("Daily"));
+        assert_eq!(harness.active_component(), ActivePane::Explorer);
+
+        let harness = harness.type_str("#").keys([KeyEvent::from(KeyCode::Esc)]);
+        assert_eq!(harness.active_component(), ActivePane::Explorer);
+    }
+
+    #[test]
+    fn test_reload_config() {
+        let dir = tempdir().unwrap();
+        let vault = test_vault(dir.path());
+        let harness = TestHarness::new(&vault, vec![]);
+
+        let user_toml = indoc! {r#"
+            [global]
+            key_bindings = [{ key = "x", command = "quit" }]
+
+            [explorer]
+            key_bindings = [{ key = "k", command = "explorer_down" }]
+        "#};
+
+        let mut config = harness.config.clone();
+        let state = reload_config(
+            &mut config,
+            harness.state.clone(),
+            config::load_from_str(user_toml),
+        );
+        assert_eq!(
+            state
+                .toasts
+                .iter()
+                .map(|toast| (toast.kind, toast.message.as_str()))
+                .collect::<Vec<_>>(),
+            [
+                (ToastKind::Info, "Config reloaded"),
+                (
+                    ToastKind::Warning,
+                    "Conflicting key bindings: [explorer] k is bound to Explorer(Up), \
+                     Explorer(Down). Commands bound to multiple keys: Explorer(Down) is bound to \
+                     [explorer] down, [explorer] j, [explorer] k; Quit is bound to \
+                     [global] ctrl+c, [global] q, [global] x"
+                ),
+            ]
+        );
+
+        // The reloaded key bindings take effect
+        let harness = TestHarness { config, state };
+        assert!(!harness.clone().type_str("x").state.is_running);
+
+        // A config that fails to parse keeps the current configuration
+        let mut config = harness.config.clone();
+        let state = reload_config(
+            &mut config,
+            AppState::default(),
+            config::load_from_str("[global"),
+        );
+        assert_eq!(config, harness.config);
+        assert_eq!(state.toasts.len(), 1);
+        assert_eq!(state.toasts[0].kind, ToastKind::Error);
+        assert!(state.toasts[0]
+            .message
+            .starts_with("Failed to reload config: "));
+    }
+
+    #[test]
+    fn test_session() {
+        let dir = tempdir().unwrap();
+        let path = dir.path().join("Daily.md");
+        fs::write(&path, "# Daily").unwrap();
+
+        let vault = test_vault(dir.path());
+
+        assert_eq!(AppState::default().session(), None);
+
+        let harness = TestHarness::new(&vault, vault.entries());
+        assert_eq!(
+            harness.state.session(),
+            Some(Session {
+                vault: vault.name.clone(),
+                note: None,
+            })
+        );
+
+        let harness = harness.open(&path);
+        assert_eq!(
+            harness.state.session(),
+            Some(Session {
+                vault: vault.name.clone(),
+                note: Some(path),
+            })
+        );
+    }
+
+    #[test]
+    fn test_open_previous() {
+        let dir = tempdir().unwrap();
+        ["Daily", "Project", "Ideas"].iter().for_each(|name| {
+            fs::write(dir.path().join(format!("{name}.md")), format!("# {name}")).unwrap()
+        });
+
+        let vault = test_vault(dir.path());
+
+        let harness = ["Daily", "Project", "Ideas"].iter().fold(
+            TestHarness::new(&vault, vault.entries()),
+            |harness, name| harness.open(&dir.path().join(format!("{name}.md"))),
+        );
+        assert_eq!(harness.selected_note(), Some("Ideas"));
+
+        let open_previous = Message::NoteEditor(note_editor::Message::OpenPrevious);
+
+        let harness = harness.send(open_previous.clone());
+        assert_eq!(harness.selected_note(), Some("Project"));
+
+        let harness = harness.send(open_previous);
+        assert_eq!(harness.selected_note(), Some("Ideas"));
+
+        assert_eq!(
+            harness
+                .main_state()
+                .recent_note_items()
+                .into_iter()
+                .map(|note| note.name)
+                .collect::<Vec<_>>(),
+            ["Ideas", "Project", "Daily"]
+        );
+    }
+
+    #[test]
+    fn test_explorer_reveal() {
+        let dir = tempdir().unwrap();
+        fs::create_dir_all(dir.path().join("Projects/Basalt")).unwrap();
+        fs::write(dir.path().join("Daily.md"), "# Daily").unwrap();
+        let nested = dir.path().join("Projects/Basalt/Roadmap.md");
+        fs::write(&nested, "# Roadmap").unwrap();
+
+        let vault = test_vault(dir.path());
+        let harness = TestHarness::new(&vault, vault.entries());
+        let explorer = |harness: &TestHarness| {
+            let explorer = &harness.main_state().explorer;
+            (explorer.list_state.selected(), explorer.selected_path())
+        };
+
+        let harness = harness.open(&nested);
+        assert_eq!(explorer(&harness), (Some(2), Some(nested.clone())));
+
+        // Moving the cursor does not change the open note, and revealing it moves the cursor back
+        let harness = harness
+            .send(Message::Explorer(explorer::Message::Up))
+            .send(Message::Explorer(explorer::Message::Up));
+        assert_eq!(harness.main_state().explorer.list_state.selected(), Some(0));
+        let harness = harness.send(Message::Explorer(explorer::Message::RevealCurrent));
+        assert_eq!(explorer(&harness), (Some(2), Some(nested.clone())));
+
+        // A note created outside basalt is found after reading the vault again
+        let external = dir.path().join("Projects/Ideas.md");
+        fs::write(&external, "# Ideas").unwrap();
+        let harness = harness.open(&external);
+        assert_eq!(harness.selected_note(), Some("Ideas"));
+        assert_eq!(explorer(&harness), (Some(3), Some(external.clone())));
+
+        // Focusing a split reveals the note of the split
+        let daily = dir.path().join("Daily.md");
+        let harness = harness
+            .send(Message::NoteEditor(note_editor::Message::SplitVertical))
+            .open(&daily);
+        assert_eq!(explorer(&harness).1, Some(daily));
+        let harness = harness.send(Message::NoteEditor(note_editor::Message::FocusSplit(0)));
+        assert_eq!(harness.selected_note(), Some("Ideas"));
+        assert_eq!(explorer(&harness), (Some(3), Some(external)));
+    }
+
+    /// Opens a note with an unchecked task and checks the task, so that the note has unsaved
+    /// changes.
+    fn modified_task_note<'a>(vault: &'a Vault, path: &Path) -> TestHarness<'a> {
+        fs::write(path, "- [ ] Task").unwrap();
+
+        let mut harness = TestHarness::new(vault, vault.entries());
+        // Tasks are only toggled with the experimental editor
+        harness.config.experimental_editor = true;
+
+        harness
+            .open(path)
+            .send(Message::NoteEditor(note_editor::Message::ToggleTask))
+    }
+
+    #[test]
+    fn test_copy_note() {
+        let dir = tempdir().unwrap();
+        let path = dir.path().join("Note.md");
+        fs::write(&path, "Ärrä").unwrap();
+        let vault = test_vault(dir.path());
+
+        let harness = TestHarness::new(&vault, vault.entries())
+            .open(&path)
+            .send(Message::NoteEditor(note_editor::Message::CopyNote));
+
+        // Whether the system clipboard is available depends on the machine, so the toast only
+        // has to tell which way the text was copied
+        let [toast] = harness.state.toasts.as_slice() else {
+            panic!("Expected a single toast, got {:?}", harness.state.toasts);
+        };
+        assert_eq!(toast.kind, ToastKind::Info);
+        assert!(toast.message.starts_with("Copied 4 characters"));
+        assert_eq!(
+            harness.state.osc52.is_some(),
+            toast.message.contains("with OSC 52")
+        );
+    }
+
+    #[test]
+    fn test_confirm_quit() {
+        let dir = tempdir().unwrap();
+        let path = dir.path().join("Tasks.md");
+        let vault = test_vault(dir.path());
+
+        // Quits right away without unsaved changes
+        fs::write(&path, "- [ ] Task").unwrap();
+        let harness = TestHarness::new(&vault, vault.entries()).open(&path);
+        assert!(!harness.send(Message::Quit).state.is_running);
+
+        let harness = modified_task_note(&vault, &path).send(Message::Quit);
+        assert!(harness.state.is_running);
+        assert_eq!(harness.active_component(), ActivePane::ConfirmModal);
+        assert_eq!(
+            harness.state.confirm_modal.prompt,
+            "Tasks has unsaved changes."
+        );
+
+        let cancelled = harness.clone().keys([KeyEvent::from(KeyCode::Esc)]);
+        assert!(cancelled.state.is_running);
+        assert!(!cancelled.state.confirm_modal.visible);
+        assert_eq!(fs::read_to_string(&path).unwrap(), "- [ ] Task");
+
+        let discarded = harness.clone().type_str("d");
+        assert!(!discarded.state.is_running);
+        assert_eq!(fs::read_to_string(&path).unwrap(), "- [ ] Task");
+
+        let saved = harness.type_str("s");
+        assert!(!saved.state.is_running);
+        assert_eq!(fs::read_to_string(&path).unwrap(), "- [x] Task");
+    }
+
+    #[test]
+    fn test_interrupt() {
+        let dir = tempdir().unwrap();
+        let path = dir.path().join("Tasks.md");
+        let vault = test_vault(dir.path());
+
+        let now = Instant::now();
+        let harness = modified_task_note(&vault, &path).send(Message::Interrupt(now));
+        assert!(harness.state.is_running);
+        assert!(harness.state.confirm_modal.visible);
+
+        let interrupt_at = |duration| {
+            harness
+                .clone()
+                .send(Message::Interrupt(now + duration))
+                .state
+                .is_running
+        };
+
+        assert!(!interrupt_at(Duration::from_millis(500)));
+        assert!(interrupt_at(INTERRUPT_TIMEOUT));
+        assert_eq!(fs::read_to_string(&path).unwrap(), "- [ ] Task");
+    }
+
+    #[test]
+    fn test_open_note_with_unsaved_changes() {
+        let dir = tempdir().unwrap();
+        let path = dir.path().join("Tasks.md");
+        let other = dir.path().join("Other.md");
+        fs::write(&other, "Other").unwrap();
+        let vault = test_vault(dir.path());
+
+        let harness = modified_task_note(&vault, &path).open(&other);
+        assert_eq!(harness.active_component(), ActivePane::ConfirmModal);
+        assert_eq!(harness.selected_note(), Some("Tasks"));
+
+        let cancelled = harness.clone().keys([KeyEvent::from(KeyCode::Esc)]);
+        assert_eq!(cancelled.selected_note(), Some("Tasks"));
+        assert!(cancelled.main_state().note_editor.modified);
+
+        let discarded = harness.clone().type_str("d");
+        assert_eq!(discarded.selected_note(), Some("Other"));
+        assert_eq!(fs::read_to_string(&path).unwrap(), "- [ ] Task");
+
+        let saved = harness.type_str("s");
+        assert_eq!(saved.selected_note(), Some("Other"));
+        assert_eq!(fs::read_to_string(&path).unwrap(), "- [x] Task");
+
+        // With autosave the changes are saved without asking
+        fs::write(&path, "- [ ] Task").unwrap();
+        let mut harness = TestHarness::new(&vault, vault.entries());
+        harness.config.experimental_editor = true;
+        harness.config.autosave = Some(config::Autosave::IntervalSeconds(30));
+
+        let harness = harness
+            .open(&path)
+            .send(Message::NoteEditor(note_editor::Message::ToggleTask));
+        assert_eq!(fs::read_to_string(&path).unwrap(), "- [ ] Task");
+
+        let harness = harness.open(&other);
+        assert_eq!(harness.active_component(), ActivePane::Explorer);
+        assert_eq!(harness.selected_note(), Some("Other"));
+        assert_eq!(fs::read_to_string(&path).unwrap(), "- [x] Task");
+    }
+
+    #[test]
+    fn test_switch_vault_with_unsaved_changes() {
+        let dir = tempdir().unwrap();
+        let path = dir.path().join("Tasks.md");
+        let vault = test_vault(dir.path());
+
+        let other_dir = tempdir().unwrap();
+        let other_vault = Vault {
+            name: "Other".into(),
+            path: other_dir.path().to_path_buf(),
+            ..Default::default()
+        };
+
+        let mut harness = modified_task_note(&vault, &path);
+        harness.state = harness
+            .state
+            .with_vault_selector_modal_state(VaultSelectorModalState::new(vec![&other_vault]));
+
+        let harness = harness.send(Message::VaultSelectorModal(
+            vault_selector_modal::Message::Select,
+        ));
+        assert_eq!(harness.active_component(), ActivePane::ConfirmModal);
+        assert_eq!(harness.main_state().vault.name, "Vault");
+
+        let saved = harness.type_str("s");
+        assert_eq!(saved.main_state().vault.name, "Other");
+        assert_eq!(fs::read_to_string(&path).unwrap(), "- [x] Task");
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_switch_vault_with_unreadable_entries() {
+        let dir = tempdir().unwrap();
+        let vault = test_vault(dir.path());
+
+        let other_dir = tempdir().unwrap();
+        fs::write(other_dir.path().join("Note.md"), "").unwrap();
+        std::os::unix::fs::symlink("Missing.md", other_dir.path().join("Broken.md")).unwrap();
+        let other_vault = test_vault(other_dir.path());
+
+        let mut harness = TestHarness::new(&vault, vault.entries());
+        harness.state = harness
+            .state
+            .with_vault_selector_modal_state(VaultSelectorModalState::new(vec![&other_vault]));
+
+        let harness = harness.send(Message::VaultSelectorModal(
+            vault_selector_modal::Message::Select,
+        ));
+        assert_eq!(harness.main_state().entries.len(), 1);
+        assert!(matches!(
+            harness.state.toasts.as_slice(),
+            [toast] if toast.kind == ToastKind::Error
+                && toast.message.starts_with("Failed to read vault entry: Path not found")
+                && toast.message.ends_with("Broken.md")
+        ));
+    }
+
+    #[test]
+    fn test_autosave_on_block_exit() {
+        let dir = tempdir().unwrap();
+        let path = dir.path().join("Draft.md");
+        fs::write(&path, "Draft\n\nNext\n").unwrap();
+        let vault = test_vault(dir.path());
+
+        let mut harness = TestHarness::new(&vault, vault.entries());
+        harness.config.experimental_editor = true;
+        harness.config.autosave = Some(config::Autosave::OnBlockExit);
+
+        let harness = harness
+            .open(&path)
+            .send(Message::NoteEditor(note_editor::Message::EditMode))
+            .type_str("A ");
+        assert_eq!(fs::read_to_string(&path).unwrap(), "Draft\n\nNext\n");
+
+        let harness = harness.send(Message::NoteEditor(note_editor::Message::ExitMode));
+        assert_eq!(fs::read_to_string(&path).unwrap(), "A Draft\n\nNext\n");
+        assert!(!harness.main_state().note_editor.modified);
+    }
+
+    #[test]
+    fn test_autosave_interval() {
+        let dir = tempdir().unwrap();
+        let path = dir.path().join("Tasks.md");
+        fs::write(&path, "- [ ] Task").unwrap();
+        let vault = test_vault(dir.path());
+
+        let mut harness = TestHarness::new(&vault, vault.entries());
+        harness.config.experimental_editor = true;
+        harness.config.autosave = Some(config::Autosave::IntervalSeconds(30));
+
+        let harness = harness.open(&path);
+        let now = Instant::now();
+        assert_eq!(harness.state.autosave_timeout(&harness.config, now), None);
+
+        // The interval starts with the first unsaved change
+        let harness = harness
+            .send(Message::NoteEditor(note_editor::Message::ToggleTask))
+            .send(Message::Autosave(now));
+        assert_eq!(
+            harness.state.autosave_timeout(&harness.config, now),
+            Some(Duration::from_secs(30))
+        );
+
+        let early = harness
+            .clone()
+            .send(Message::Autosave(now + Duration::from_secs(29)));
+        assert_eq!(fs::read_to_string(&path).unwrap(), "- [ ] Task");
+        assert!(early.main_state().note_editor.modified);
+
+        let saved = harness
+            .clone()
+            .send(Message::Autosave(now + Duration::from_secs(30)));
+        assert_eq!(fs::read_to_string(&path).unwrap(), "- [x] Task");
+        assert_eq!(saved.state.autosave_timeout(&saved.config, now), None);
+
+        // Saving the change or toggling the task back resets the interval, so that the next
+        // change starts a new one
+        let manually_saved = harness
+            .clone()
+            .send(Message::NoteEditor(note_editor::Message::Save));
+        assert_eq!(manually_saved.state.autosaved_at, None);
+
+        let undone = harness
+            .clone()
+            .send(Message::NoteEditor(note_editor::Message::ToggleTask));
+        assert!(!undone.main_state().note_editor.modified);
+        assert_eq!(undone.state.autosaved_at, None);
+
+        // Failed autosaves double the interval and show a single toast
+        fs::remove_file(&path).unwrap();
+        fs::create_dir(&path).unwrap();
+
+        let failed = harness.send(Message::Autosave(now + Duration::from_secs(30)));
+        assert_eq!(failed.state.toasts.len(), 1);
+        assert_eq!(
+            failed
+                .state
+                .autosave_timeout(&failed.config, now + Duration::from_secs(30)),
+            Some(Duration::from_secs(60))
+        );
+
+        let failed = failed.send(Message::Autosave(now + Duration::from_secs(90)));
+        assert_eq!(failed.state.toasts.len(), 1);
+        assert_eq!(failed.state.autosave_failures, 2);
+        assert_eq!(
+            failed
+                .state
+                .autosave_timeout(&failed.config, now + Duration::from_secs(90)),
+            Some(Duration::from_secs(120))
+        );
+        assert!(failed.main_state().note_editor.modified);
+    }
+
+    #[test]
+    fn test_recent_notes_limit() {
+        let state = (0..25).fold(MainState::default(), |state, i| {
+            state.with_recent_note(PathBuf::from(format!("{}.md", i % 22)))
+        });
+
+        assert_eq!(state.recent_notes.len(), RECENT_NOTES_LIMIT);
+        assert_eq!(state.recent_notes[0], PathBuf::from("2.md"));
+        assert_eq!(state.recent_notes[1], PathBuf::from("1.md"));
+        assert_eq!(state.recent_notes[3], PathBuf::from("21.md"));
+    }
+
+    #[test]
+    fn test_reopen_note_restores_view() {
+        let dir = tempdir().unwrap();
+        let long = dir.path().join("Long.md");
+        let content = (1..=40)
+            .map(|i| format!("Paragraph {i}"))
+            .collect::<Vec<_>>()
+            .join("\n\n");
+        fs::write(&long, content).unwrap();
+        let daily = dir.path().join("Daily.md");
+        fs::write(&daily, "# Daily").unwrap();
+
+        let vault = test_vault(dir.path());
+        let harness = TestHarness::new(&vault, vault.entries())
+            .open(&long)
+            .send(Message::NoteEditor(note_editor::Message::CursorDown))
+            .send(Message::NoteEditor(note_editor::Message::CursorDown))
+            .send(Message::NoteEditor(note_editor::Message::CursorDown))
+            .send(Message::NoteEditor(note_editor::Message::ScrollDown(
+                ScrollAmount::Lines(10),
+            )));
+        let position = harness.main_state().note_editor.view_position();
+        assert_eq!((position.row, position.scroll), (3, 10));
+
+        let harness = harness.open(&daily);
+        assert_eq!(harness.main_state().note_editor.current_row, 0);
+
+        let harness = harness.open(&long);
+        assert_eq!(harness.selected_note(), Some("Long"));
+        assert_eq!(harness.main_state().note_editor.view_position(), position);
+
+        // The view positions are dropped together with the recent notes
+        let state = (0..RECENT_NOTES_LIMIT).fold(harness.main_state().clone(), |state, i| {
+            state.with_recent_note(PathBuf::from(format!("{i}.md")))
+        });
+        assert!(state.view_positions.is_empty());
+    }
+
+    #[test]
+    fn test_main_layout_splits() {
+        let layout = MainLayout::new(Rect::new(0, 0, 120, 40), true, true);
+
+        assert_eq!(layout.splits(0), [layout.note_editor]);
+        assert_eq!(layout.splits(1), [layout.note_editor]);
+        assert_eq!(
+            layout
+                .splits(2)
+                .iter()
+                .map(|area| (area.x, area.width))
+                .collect::<Vec<_>>(),
+            [(36, 24), (60, 24)]
+        );
+    }
+
+    #[test]
+    fn test_list_index_at() {
+        let area = Rect::new(1, 0, 35, 12);
+
+        let tests = [
+            (ListState::default(), 20, 0, None),
+            (ListState::default(), 20, 1, Some(0)),
+            (ListState::default(), 20, 10, Some(9)),
+            (ListState::default(), 20, 11, None),
+            (ListState::default().with_offset(5), 20, 1, Some(5)),
+            // The selected item is scrolled into view
+            (ListState::default().with_selected(Some(14)), 20, 1, Some(5)),
+            (
+                ListState::default().with_offset(8).with_selected(Some(2)),
+                20,
+                3,
+                Some(4),
+            ),
+            // Rows below the last item
+            (ListState::default(), 3, 3, Some(2)),
+            (ListState::default(), 3, 4, None),
+            (ListState::default(), 0, 1, None),
+        ];
+
+        tests
+            .into_iter()
+            .for_each(|(list_state, item_count, row, expected)| {
+                assert_eq!(
+                    list_index_at(area, &list_state, item_count, row),
+                    expected,
+                    "{list_state:?} {item_count} {row}"
+                )
+            });
     }
 }
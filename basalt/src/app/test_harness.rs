@@ -0,0 +1,106 @@
+//! Drives the app without a terminal, so that the behavior of the app can be tested by feeding
+//! key events and asserting the resulting [`AppState`].
+
+use std::path::Path;
+
+use basalt_core::obsidian::{Note, Vault, VaultEntry};
+use ratatui::crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+
+use crate::config::{self, Config};
+
+use super::{
+    handle_key_event, open_note_at_path, update_with_config, ActivePane, AppState, MainState,
+    Message, ScreenState,
+};
+
+/// Creates a key press event of the character without modifiers.
+pub fn key(c: char) -> KeyEvent {
+    KeyEvent::new(KeyCode::Char(c), KeyModifiers::NONE)
+}
+
+/// Creates a note entry for a note that only exists in the harness. Reading the note from disk
+/// fails, so the note is opened without content and an error toast is shown.
+pub fn note(name: &str) -> VaultEntry {
+    VaultEntry::File(Note {
+        name: name.to_string(),
+        path: format!("{name}.md").into(),
+    })
+}
+
+/// Sends key events and messages through the key bindings and [`update_with_config`] of the app, the same
+/// way the event loop does.
+#[derive(Clone)]
+pub struct TestHarness<'a> {
+    pub config: Config,
+    pub state: AppState<'a>,
+}
+
+impl<'a> TestHarness<'a> {
+    /// Creates a harness that shows the vault with the given entries on the main screen. The
+    /// entries are used as is instead of reading them from the vault directory. Uses the base
+    /// configuration, so user configuration does not affect the tests.
+    pub fn new(vault: &'a Vault, entries: Vec<VaultEntry>) -> Self {
+        let config = config::load_base().unwrap();
+        let state = AppState::default()
+            .with_main_state(MainState::with_entries(&config, vault, entries))
+            .set_running(true);
+
+        Self { config, state }
+    }
+
+    /// Creates a harness that shows the content as a read-only note that is not in any vault.
+    pub fn read_only(name: &str, content: &str) -> Self {
+        let config = config::load_base().unwrap();
+        let state = AppState::default()
+            .with_main_state(MainState::read_only(name, content))
+            .set_running(true);
+
+        Self { config, state }
+    }
+
+    /// Sends the key events in order. Key events without a key binding are ignored.
+    pub fn keys(self, keys: impl IntoIterator<Item = KeyEvent>) -> Self {
+        keys.into_iter().fold(self, |harness, key| {
+            match handle_key_event(&harness.config, &harness.state, &key) {
+                Some(message) => harness.send(message),
+                None => harness,
+            }
+        })
+    }
+
+    /// Sends the characters as key presses without modifiers.
+    pub fn type_str(self, text: &str) -> Self {
+        self.keys(text.chars().map(key))
+    }
+
+    /// Selects the note at the path in the explorer and opens it.
+    pub fn open(self, path: &Path) -> Self {
+        let state = open_note_at_path(&self.config, self.state.clone(), path);
+        Self { state, ..self }
+    }
+
+    pub fn send(mut self, message: Message) -> Self {
+        self.state = update_with_config(&mut self.config, self.state.clone(), message);
+        self
+    }
+
+    pub fn active_component(&self) -> ActivePane {
+        self.state.active_component()
+    }
+
+    /// Returns the main screen state. Panics if the main screen is not shown.
+    pub fn main_state(&self) -> &MainState<'a> {
+        match &self.state.screen {
+            ScreenState::Main(main_state) => main_state,
+            _ => panic!("Expected the main screen"),
+        }
+    }
+
+    /// Returns the name of the note that is open in the active note editor.
+    pub fn selected_note(&self) -> Option<&str> {
+        self.main_state()
+            .selected_note
+            .as_ref()
+            .map(|note| note.name.as_str())
+    }
+}
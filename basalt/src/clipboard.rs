@@ -0,0 +1,162 @@
+//! Copies text to the system clipboard.
+//!
+//! The system clipboard is only available when basalt is built with the `clipboard` feature,
+//! which is enabled by default. Without it, or when the system clipboard cannot be opened, e.g.
+//! in an SSH session without a display server, the text is sent to the terminal with the OSC 52
+//! escape sequence instead. Most terminal emulators put the text on the clipboard of the machine
+//! they run on.
+use std::{
+    env,
+    io::{self, Write},
+};
+
+#[derive(Debug, thiserror::Error)]
+pub enum ClipboardError {
+    /// Error of the system clipboard, from [`arboard::Error`].
+    #[cfg(feature = "clipboard")]
+    #[error(transparent)]
+    Arboard(#[from] arboard::Error),
+    /// Writing the OSC 52 escape sequence to the terminal failed, from [`std::io::Error`].
+    #[error(transparent)]
+    Io(#[from] io::Error),
+}
+
+/// How [`copy`] puts the text on the clipboard.
+#[derive(Debug)]
+pub enum Copied {
+    /// The text is on the system clipboard.
+    System,
+    /// The text is put on the clipboard once the OSC 52 escape sequence is written to the
+    /// terminal with [`write_osc52`].
+    Osc52 {
+        sequence: String,
+        /// Why the system clipboard was not used, or `None` without the `clipboard` feature.
+        error: Option<ClipboardError>,
+    },
+}
+
+/// Puts the text on the system clipboard, falling back to the OSC 52 escape sequence if the
+/// system clipboard is not available.
+pub fn copy(text: &str) -> Copied {
+    #[cfg(feature = "clipboard")]
+    let error = match copy_to_system(text) {
+        Ok(()) => return Copied::System,
+        Err(error) => Some(error),
+    };
+
+    #[cfg(not(feature = "clipboard"))]
+    let error = None;
+
+    Copied::Osc52 {
+        sequence: osc52(text, env::var_os("TMUX").is_some()),
+        error,
+    }
+}
+
+/// Puts the text on the system clipboard.
+///
+/// On Linux the clipboard contents are owned by the application that copied them, so the
+/// clipboard handle is kept alive until basalt exits.
+#[cfg(feature = "clipboard")]
+fn copy_to_system(text: &str) -> Result<(), ClipboardError> {
+    use std::sync::Mutex;
+
+    static CLIPBOARD: Mutex<Option<arboard::Clipboard>> = Mutex::new(None);
+
+    let mut clipboard = CLIPBOARD.lock().unwrap_or_else(|error| error.into_inner());
+
+    if clipboard.is_none() {
+        *clipboard = Some(arboard::Clipboard::new()?);
+    }
+
+    if let Some(clipboard) = clipboard.as_mut() {
+        clipboard.set_text(text)?;
+    }
+
+    Ok(())
+}
+
+/// Writes the OSC 52 escape sequence to the terminal.
+///
+/// The writer is the ratatui backend, so that the sequence is written in one go between the
+/// frames drawn by ratatui. Terminals do not print the sequence, so the screen is left as is.
+pub fn write_osc52(writer: &mut impl Write, sequence: &str) -> Result<(), ClipboardError> {
+    writer.write_all(sequence.as_bytes())?;
+    writer.flush()?;
+
+    Ok(())
+}
+
+/// Returns the OSC 52 escape sequence that sets the clipboard to the text.
+///
+/// Inside tmux the sequence is wrapped in a passthrough sequence, since tmux does not forward
+/// unknown sequences to the terminal by default.
+fn osc52(text: &str, tmux: bool) -> String {
+    let sequence = format!("\x1b]52;c;{}\x07", base64(text.as_bytes()));
+
+    match tmux {
+        true => format!("\x1bPtmux;{}\x1b\\", sequence.replace('\x1b', "\x1b\x1b")),
+        false => sequence,
+    }
+}
+
+/// Encodes the bytes with the standard base64 alphabet and padding.
+fn base64(bytes: &[u8]) -> String {
+    const ALPHABET: &[u8; 64] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
+
+    bytes
+        .chunks(3)
+        .flat_map(|chunk| {
+            let triple = chunk
+                .iter()
+                .enumerate()
+                .fold(0u32, |triple, (index, byte)| {
+                    triple | u32::from(*byte) << (16 - 8 * index)
+                });
+
+            (0..4).map(move |index| match index <= chunk.len() {
+                true => ALPHABET[(triple >> (18 - 6 * index) & 0x3f) as usize] as char,
+                false => '=',
+            })
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_base64() {
+        let tests = [
+            ("", ""),
+            ("f", "Zg=="),
+            ("fo", "Zm8="),
+            ("foo", "Zm9v"),
+            ("Basalt", "QmFzYWx0"),
+            ("Ärrä", "w4RycsOk"),
+            ("ノート", "44OO44O844OI"),
+            ("🪨\n", "8J+qqAo="),
+        ];
+
+        tests.into_iter().for_each(|(text, expected)| {
+            assert_eq!(base64(text.as_bytes()), expected, "base64 of {text:?}");
+        });
+    }
+
+    #[test]
+    fn test_write_osc52() {
+        let mut output = vec![];
+        write_osc52(&mut output, &osc52("Ärrä", false)).unwrap();
+        assert_eq!(output, b"\x1b]52;c;w4RycsOk\x07");
+    }
+
+    #[test]
+    fn test_osc52() {
+        assert_eq!(osc52("Ärrä", false), "\x1b]52;c;w4RycsOk\x07");
+        assert_eq!(
+            osc52("Ärrä", true),
+            "\x1bPtmux;\x1b\x1b]52;c;w4RycsOk\x07\x1b\\"
+        );
+    }
+}
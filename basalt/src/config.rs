@@ -1,14 +1,22 @@
 mod key_binding;
+mod theme;
 
 use core::fmt;
-use std::{collections::BTreeMap, fs::read_to_string};
+use std::{collections::BTreeMap, fs::read_to_string, time::Duration};
 
+use basalt_core::{markdown::DEFAULT_WORDS_PER_MINUTE, obsidian::DEFAULT_MAX_NOTE_BYTES};
 use etcetera::{choose_base_strategy, home_dir, BaseStrategy};
 use key_binding::{Command, KeyBinding};
+use ratatui::style::Color;
 use serde::Deserialize;
 
-use crate::app::Message;
-pub(crate) use key_binding::Key;
+use crate::{
+    app::{explorer, help_modal, note_editor, Message, ScrollAmount},
+    toast::DEFAULT_TOAST_DURATION,
+};
+pub(crate) use key_binding::{Key, KeySequence};
+pub use theme::Theme;
+use theme::ThemeColors;
 
 #[derive(Debug, thiserror::Error)]
 pub enum ConfigError {
@@ -29,25 +37,140 @@ pub enum ConfigError {
     UnknownKeyModifiers(String),
     #[error("User config not found: {0}")]
     UserConfigNotFound(String),
+    #[error("Invalid color for theme.{key}: {value}")]
+    InvalidColor { key: String, value: String },
+    #[error("Unknown theme color: {0}")]
+    UnknownThemeKey(String),
 }
 
+/// Formats a duplicate command of [`Config::duplicate_commands`], e.g.
+/// `Explorer(Sort) is bound to [explorer] s, [explorer] x`.
+pub fn format_duplicate_command(command: &str, bindings: &[(String, String)]) -> String {
+    format!(
+        "{command} is bound to {}",
+        bindings
+            .iter()
+            .map(|(section, key)| format!("[{section}] {key}"))
+            .collect::<Vec<_>>()
+            .join(", ")
+    )
+}
+
+/// A key that is bound to more than one command in the same config section.
+///
+/// Only the last binding takes effect, so the other commands are silently unreachable with the
+/// key.
 #[derive(Clone, Debug, PartialEq)]
+pub struct KeyConflict {
+    pub section: String,
+    pub key: KeySequence,
+    pub commands: Vec<Message>,
+}
+
+impl fmt::Display for KeyConflict {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "[{}] {} is bound to {}",
+            self.section,
+            self.key,
+            self.commands
+                .iter()
+                .map(|message| format!("{message:?}"))
+                .collect::<Vec<_>>()
+                .join(", ")
+        )
+    }
+}
+
+#[derive(Clone, Debug, Default, PartialEq)]
 pub struct ConfigSection {
     pub key_bindings: BTreeMap<String, Message>,
+    /// The parsed keys of the key bindings, used to report conflicts.
+    keys: BTreeMap<String, KeySequence>,
+    /// Keys that were bound to multiple commands, in the order they were bound.
+    conflicts: Vec<(KeySequence, Vec<Message>)>,
+}
+
+/// The result of matching the keys pressed so far against the key bindings of a section.
+#[derive(Clone, Debug, PartialEq)]
+pub enum KeyMatch {
+    /// The keys are bound to the message and do not start a longer key sequence.
+    Complete(Message),
+    /// The keys start a longer key sequence. Holds the message the keys are bound to on their
+    /// own, which is used when the sequence is not continued in time.
+    Prefix(Option<Message>),
+    NoMatch,
 }
 
 impl ConfigSection {
+    /// Records a conflict when the given key is already bound to a different message.
+    fn record_conflict(&mut self, key: &KeySequence, message: &Message) {
+        let Some(existing) = self.key_bindings.get(&key.to_string()) else {
+            return;
+        };
+
+        if existing == message {
+            return;
+        }
+
+        match self
+            .conflicts
+            .iter_mut()
+            .find(|(conflict_key, _)| conflict_key == key)
+        {
+            Some((_, commands)) => commands.push(message.clone()),
+            None => self
+                .conflicts
+                .push((key.clone(), vec![existing.clone(), message.clone()])),
+        }
+    }
+
     /// Takes self and another config and merges the `key_bindings` together overwriting the
     /// existing entries with the value from another config.
+    ///
+    /// Overwritten bindings that pointed to a different command are recorded as conflicts.
     pub(crate) fn merge_key_bindings(&mut self, config: Self) {
+        self.conflicts.extend(config.conflicts);
+
         config.key_bindings.into_iter().for_each(|(key, message)| {
+            if let Some(parsed_key) = config.keys.get(&key) {
+                self.record_conflict(parsed_key, &message);
+                self.keys.insert(key.clone(), parsed_key.clone());
+            }
             self.key_bindings.insert(key, message);
         });
     }
 
+    /// Returns the commands of all key bindings, in the order of their keys. A command that is
+    /// bound to several keys is returned once for each key.
+    pub fn all_commands(&self) -> Vec<&Message> {
+        self.key_bindings.values().collect()
+    }
+
     pub fn key_to_message(&self, key: Key) -> Option<Message> {
         self.key_bindings.get(&key.to_string()).cloned()
     }
+
+    /// Matches the keys pressed so far against the key bindings. A longer key sequence is
+    /// preferred over a binding of its prefix, e.g. `g g` over `g`.
+    pub fn match_keys(&self, keys: &KeySequence) -> KeyMatch {
+        let sequence = keys.to_string();
+        let prefix = format!("{sequence} ");
+
+        // Sequences that start with the prefix are sorted right after it
+        let is_prefix = self
+            .key_bindings
+            .range(prefix.clone()..)
+            .next()
+            .is_some_and(|(bound, _)| bound.starts_with(&prefix));
+
+        match (self.key_bindings.get(&sequence), is_prefix) {
+            (message, true) => KeyMatch::Prefix(message.cloned()),
+            (Some(message), false) => KeyMatch::Complete(message.clone()),
+            (None, false) => KeyMatch::NoMatch,
+        }
+    }
 }
 
 impl fmt::Display for ConfigSection {
@@ -60,9 +183,54 @@ impl fmt::Display for ConfigSection {
     }
 }
 
+/// When notes with unsaved changes are saved without the save command.
+///
+/// Configured with `autosave = "off"`, `autosave = "on_block_exit"` or
+/// `autosave = { interval_seconds = 30 }`.
+#[derive(Clone, Copy, Debug, Default, Deserialize, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum Autosave {
+    /// Notes are only saved with the save command.
+    #[default]
+    Off,
+    /// Saves the note whenever the changes of an edited block are applied to the note, e.g. when
+    /// leaving the block.
+    OnBlockExit,
+    /// Saves the notes with unsaved changes at most once in the given number of seconds.
+    IntervalSeconds(u64),
+}
+
 #[derive(Clone, Debug, PartialEq)]
 pub struct Config {
     pub experimental_editor: bool,
+    /// Enables Vim-style modal editing in the experimental editor.
+    pub vim_mode: bool,
+    /// How long toast notifications are shown. Uses [`DEFAULT_TOAST_DURATION`] when not set.
+    pub toast_duration: Option<Duration>,
+    /// Captures mouse events for scrolling and clicking. Enabled when not set. When disabled, the
+    /// terminal handles text selection.
+    pub mouse: Option<bool>,
+    /// Reading speed used to estimate the reading time of notes. Uses
+    /// [`DEFAULT_WORDS_PER_MINUTE`] when not set.
+    pub words_per_minute: Option<usize>,
+    /// Soft-wraps code lines that do not fit the note editor. Enabled when not set.
+    pub wrap_code_blocks: Option<bool>,
+    /// Offers to reopen the vault and note of the previous session on the splash screen.
+    /// Disabled when not set.
+    pub restore_session: Option<bool>,
+    /// Lists attachments, i.e. files that are not markdown notes, in the explorer. Enabled when
+    /// not set.
+    pub show_attachments: Option<bool>,
+    /// Saves notes with unsaved changes automatically. Disabled when not set.
+    pub autosave: Option<Autosave>,
+    /// Size limit in megabytes for opening notes in the editor. Larger notes are shown as a
+    /// placeholder instead. Uses [`DEFAULT_MAX_NOTE_BYTES`] when not set.
+    pub max_note_size_mb: Option<u64>,
+    /// Colors of the widgets, the default palette with the configured colors applied.
+    pub theme: Theme,
+    /// The configured colors, kept separately so that merging only overrides the colors that
+    /// are set.
+    theme_colors: ThemeColors,
     pub global: ConfigSection,
     pub splash: ConfigSection,
     pub explorer: ConfigSection,
@@ -70,6 +238,9 @@ pub struct Config {
     pub help_modal: ConfigSection,
     pub note_editor: ConfigSection,
     pub vault_selector_modal: ConfigSection,
+    pub recent_notes_modal: ConfigSection,
+    pub tag_panel: ConfigSection,
+    pub confirm_modal: ConfigSection,
 }
 
 impl Default for Config {
@@ -82,6 +253,17 @@ impl From<TomlConfig> for Config {
     fn from(value: TomlConfig) -> Self {
         Self {
             experimental_editor: value.experimental_editor,
+            vim_mode: value.vim_mode,
+            toast_duration: value.toast_duration_ms.map(Duration::from_millis),
+            mouse: value.mouse,
+            words_per_minute: value.words_per_minute,
+            wrap_code_blocks: value.wrap_code_blocks,
+            restore_session: value.restore_session,
+            show_attachments: value.show_attachments,
+            autosave: value.autosave,
+            max_note_size_mb: value.max_note_size_mb,
+            theme: Theme::default().with_colors(&value.theme),
+            theme_colors: value.theme,
             global: value.global.into(),
             splash: value.splash.into(),
             explorer: value.explorer.into(),
@@ -89,18 +271,25 @@ impl From<TomlConfig> for Config {
             help_modal: value.help_modal.into(),
             note_editor: value.note_editor.into(),
             vault_selector_modal: value.vault_selector_modal.into(),
+            recent_notes_modal: value.recent_notes_modal.into(),
+            tag_panel: value.tag_panel.into(),
+            confirm_modal: value.confirm_modal.into(),
         }
     }
 }
 
 impl From<TomlConfigSection> for ConfigSection {
     fn from(TomlConfigSection { key_bindings }: TomlConfigSection) -> Self {
-        Self {
-            key_bindings: key_bindings
-                .into_iter()
-                .map(|KeyBinding { key, command }| (key.to_string(), command.into()))
-                .collect(),
-        }
+        key_bindings.into_iter().fold(
+            Self::default(),
+            |mut section, KeyBinding { key, command }| {
+                let message = command.into();
+                section.record_conflict(&key, &message);
+                section.keys.insert(key.to_string(), key.clone());
+                section.key_bindings.insert(key.to_string(), message);
+                section
+            },
+        )
     }
 }
 
@@ -109,15 +298,324 @@ impl Config {
     /// existing entries with the value from another config.
     pub(crate) fn merge(&mut self, config: Self) -> Self {
         self.experimental_editor = config.experimental_editor;
+        self.vim_mode = config.vim_mode;
+        if config.toast_duration.is_some() {
+            self.toast_duration = config.toast_duration;
+        }
+        if config.mouse.is_some() {
+            self.mouse = config.mouse;
+        }
+        if config.words_per_minute.is_some() {
+            self.words_per_minute = config.words_per_minute;
+        }
+        if config.wrap_code_blocks.is_some() {
+            self.wrap_code_blocks = config.wrap_code_blocks;
+        }
+        if config.restore_session.is_some() {
+            self.restore_session = config.restore_session;
+        }
+        if config.show_attachments.is_some() {
+            self.show_attachments = config.show_attachments;
+        }
+        if config.autosave.is_some() {
+            self.autosave = config.autosave;
+        }
+        if config.max_note_size_mb.is_some() {
+            self.max_note_size_mb = config.max_note_size_mb;
+        }
+        self.theme_colors.merge(config.theme_colors);
+        self.theme = Theme::default().with_colors(&self.theme_colors);
         self.global.merge_key_bindings(config.global);
         self.explorer.merge_key_bindings(config.explorer);
         self.splash.merge_key_bindings(config.splash);
+        self.outline.merge_key_bindings(config.outline);
         self.note_editor.merge_key_bindings(config.note_editor);
         self.help_modal.merge_key_bindings(config.help_modal);
         self.vault_selector_modal
             .merge_key_bindings(config.vault_selector_modal);
+        self.recent_notes_modal
+            .merge_key_bindings(config.recent_notes_modal);
+        self.tag_panel.merge_key_bindings(config.tag_panel);
+        self.confirm_modal.merge_key_bindings(config.confirm_modal);
         self.clone()
     }
+
+    /// Returns the colors of custom callout types by the lowercase type, e.g. `bug` for a
+    /// `callout_bug` key in the `[theme]` section.
+    pub fn callout_colors(&self) -> BTreeMap<String, Color> {
+        self.theme_colors.callouts()
+    }
+
+    pub fn toast_duration(&self) -> Duration {
+        self.toast_duration.unwrap_or(DEFAULT_TOAST_DURATION)
+    }
+
+    pub fn mouse(&self) -> bool {
+        self.mouse.unwrap_or(true)
+    }
+
+    pub fn wrap_code_blocks(&self) -> bool {
+        self.wrap_code_blocks.unwrap_or(true)
+    }
+
+    pub fn restore_session(&self) -> bool {
+        self.restore_session.unwrap_or(false)
+    }
+
+    pub fn show_attachments(&self) -> bool {
+        self.show_attachments.unwrap_or(true)
+    }
+
+    pub fn autosave(&self) -> Autosave {
+        self.autosave.unwrap_or_default()
+    }
+
+    pub fn words_per_minute(&self) -> usize {
+        self.words_per_minute.unwrap_or(DEFAULT_WORDS_PER_MINUTE)
+    }
+
+    pub fn max_note_bytes(&self) -> u64 {
+        self.max_note_size_mb
+            .map_or(DEFAULT_MAX_NOTE_BYTES, |mb| mb.saturating_mul(1024 * 1024))
+    }
+
+    /// Returns the keys that are bound to more than one command within the same section.
+    ///
+    /// A conflict is recorded when a user binding overwrites a default binding with a different
+    /// command, or when the same key is bound multiple times in the user configuration.
+    pub fn conflicts(&self) -> Vec<KeyConflict> {
+        self.sections()
+            .into_iter()
+            .flat_map(|(section, config_section)| {
+                config_section
+                    .conflicts
+                    .iter()
+                    .map(|(key, commands)| KeyConflict {
+                        section: section.to_string(),
+                        key: key.clone(),
+                        commands: commands.clone(),
+                    })
+            })
+            .collect()
+    }
+
+    /// Returns the commands that are bound to more than one key within the same section, by the
+    /// `Debug` output of the command, with the section and key of each of its bindings.
+    ///
+    /// The keys are returned as written in the configuration file instead of as a `Key`, since
+    /// a binding can be a key sequence such as `g g`. The commands are in a [`BTreeMap`], so that
+    /// the warnings listing them are always in the same order.
+    pub fn duplicate_commands(&self) -> BTreeMap<String, Vec<(String, String)>> {
+        self.sections()
+            .into_iter()
+            .flat_map(|(section, config_section)| {
+                let mut keys_by_command: BTreeMap<String, Vec<(String, String)>> = BTreeMap::new();
+
+                config_section
+                    .key_bindings
+                    .iter()
+                    .for_each(|(key, message)| {
+                        keys_by_command
+                            .entry(format!("{message:?}"))
+                            .or_default()
+                            .push((section.to_string(), key.clone()))
+                    });
+
+                keys_by_command
+                    .into_iter()
+                    .filter(|(_, bindings)| bindings.len() > 1)
+            })
+            .fold(
+                BTreeMap::new(),
+                |mut duplicates: BTreeMap<_, Vec<_>>, (command, bindings)| {
+                    duplicates.entry(command).or_default().extend(bindings);
+                    duplicates
+                },
+            )
+    }
+
+    /// Returns the duplicate commands of [`Config::duplicate_commands`] that the base
+    /// configuration does not have. The base configuration binds many commands to several keys
+    /// on purpose, e.g. `k` and `up`, so only the duplicates added by the user are returned.
+    pub fn user_duplicate_commands(&self) -> BTreeMap<String, Vec<(String, String)>> {
+        let base_duplicates = load_base()
+            .map(|base| base.duplicate_commands())
+            .unwrap_or_default();
+
+        self.duplicate_commands()
+            .into_iter()
+            .filter(|(command, bindings)| base_duplicates.get(command) != Some(bindings))
+            .collect()
+    }
+
+    /// Returns the keys of the global section that are also bound to a different command in
+    /// another section. Global key bindings take precedence outside of editing and filtering, so
+    /// the command of the section is unreachable with the key most of the time.
+    pub fn shadowed_bindings(&self) -> Vec<KeyConflict> {
+        self.sections()
+            .into_iter()
+            .skip(1)
+            .flat_map(|(section, config_section)| {
+                config_section
+                    .key_bindings
+                    .iter()
+                    .filter_map(move |(key, message)| {
+                        let global_message = self.global.key_bindings.get(key)?;
+                        let key = config_section.keys.get(key)?;
+
+                        (global_message != message).then(|| KeyConflict {
+                            section: format!("global/{section}"),
+                            key: key.clone(),
+                            commands: vec![global_message.clone(), message.clone()],
+                        })
+                    })
+            })
+            .collect()
+    }
+
+    /// Returns the key binding sections by their name in the configuration file.
+    fn sections(&self) -> [(&'static str, &ConfigSection); 10] {
+        [
+            ("global", &self.global),
+            ("splash", &self.splash),
+            ("explorer", &self.explorer),
+            ("outline", &self.outline),
+            ("help_modal", &self.help_modal),
+            ("note_editor", &self.note_editor),
+            ("vault_selector_modal", &self.vault_selector_modal),
+            ("recent_notes_modal", &self.recent_notes_modal),
+            ("tag_panel", &self.tag_panel),
+            ("confirm_modal", &self.confirm_modal),
+        ]
+    }
+}
+
+/// Renders the key bindings of the configuration as help text, grouped by section.
+///
+/// Every bound command is listed with its keys in the section it is bound in. Commands that are
+/// not bound in any section are listed as `(unbound)` in the section their name starts with, or
+/// in the global section.
+pub fn render_help(config: &Config) -> String {
+    let sections = config.sections();
+
+    let is_bound = |message: &Message| {
+        sections.iter().any(|(_, section)| {
+            section
+                .key_bindings
+                .values()
+                .any(|bound| without_line_count(bound) == *message)
+        })
+    };
+
+    let home_section = |command: &Command| {
+        let name = command.name();
+        sections
+            .iter()
+            .map(|(section, _)| *section)
+            .filter(|section| name.starts_with(&format!("{section}_")))
+            .max_by_key(|section| section.len())
+            .unwrap_or("global")
+    };
+
+    sections
+        .iter()
+        .filter_map(|(section_name, section)| {
+            let entries: Vec<(String, String)> = Command::ALL
+                .iter()
+                .filter_map(|command| {
+                    let message = Message::from(command.clone());
+                    let keys: Vec<String> = section
+                        .key_bindings
+                        .iter()
+                        .filter(|(_, bound)| without_line_count(bound) == message)
+                        .map(|(key, _)| format!("‹{key}›"))
+                        .collect();
+
+                    let keys = if !keys.is_empty() {
+                        keys.join(", ")
+                    } else if !is_bound(&message) && home_section(command) == *section_name {
+                        "(unbound)".to_string()
+                    } else {
+                        return None;
+                    };
+
+                    Some((keys, command_title(&command.name(), section_name)))
+                })
+                .collect();
+
+            if entries.is_empty() {
+                return None;
+            }
+
+            let width = entries
+                .iter()
+                .map(|(keys, _)| keys.chars().count())
+                .max()
+                .unwrap_or_default();
+
+            let lines = entries
+                .iter()
+                .map(|(keys, title)| {
+                    let padding = " ".repeat(width - keys.chars().count());
+                    format!("    {keys}{padding}  {title}")
+                })
+                .collect::<Vec<_>>()
+                .join("\n");
+
+            Some(format!(
+                "  {}\n\n{lines}",
+                section_name.replace('_', " ").to_uppercase()
+            ))
+        })
+        .collect::<Vec<_>>()
+        .join("\n\n")
+}
+
+/// Replaces the number of lines of a [`ScrollAmount::Lines`] scroll with the one used in
+/// [`Command::ALL`], so that e.g. `note_editor_scroll_down_lines = 5` is listed under that command.
+fn without_line_count(message: &Message) -> Message {
+    let amount = |amount: &ScrollAmount| match amount {
+        ScrollAmount::Lines(_) => ScrollAmount::Lines(1),
+        amount => amount.clone(),
+    };
+
+    match message {
+        Message::Explorer(explorer::Message::ScrollUp(a)) => {
+            Message::Explorer(explorer::Message::ScrollUp(amount(a)))
+        }
+        Message::Explorer(explorer::Message::ScrollDown(a)) => {
+            Message::Explorer(explorer::Message::ScrollDown(amount(a)))
+        }
+        Message::HelpModal(help_modal::Message::ScrollUp(a)) => {
+            Message::HelpModal(help_modal::Message::ScrollUp(amount(a)))
+        }
+        Message::HelpModal(help_modal::Message::ScrollDown(a)) => {
+            Message::HelpModal(help_modal::Message::ScrollDown(amount(a)))
+        }
+        Message::NoteEditor(note_editor::Message::ScrollUp(a)) => {
+            Message::NoteEditor(note_editor::Message::ScrollUp(amount(a)))
+        }
+        Message::NoteEditor(note_editor::Message::ScrollDown(a)) => {
+            Message::NoteEditor(note_editor::Message::ScrollDown(amount(a)))
+        }
+        message => message.clone(),
+    }
+}
+
+/// Turns a command name into a human-readable title, without the prefix of the section it is
+/// listed in, e.g. `explorer_toggle_bookmark` becomes `Toggle bookmark` in the explorer section.
+fn command_title(name: &str, section: &str) -> String {
+    let name = name
+        .strip_prefix(section)
+        .and_then(|name| name.strip_prefix('_'))
+        .unwrap_or(name)
+        .replace('_', " ");
+
+    let mut chars = name.chars();
+    chars
+        .next()
+        .map(|first| first.to_uppercase().chain(chars).collect())
+        .unwrap_or_default()
 }
 
 impl fmt::Display for Config {
@@ -128,6 +626,9 @@ impl fmt::Display for Config {
         writeln!(f, "[note_editor]\n{}", self.note_editor)?;
         writeln!(f, "[help_modal]\n{}", self.help_modal)?;
         writeln!(f, "[vault_selector_modal]\n{}", self.vault_selector_modal)?;
+        writeln!(f, "[recent_notes_modal]\n{}", self.recent_notes_modal)?;
+        writeln!(f, "[tag_panel]\n{}", self.tag_panel)?;
+        writeln!(f, "[confirm_modal]\n{}", self.confirm_modal)?;
 
         Ok(())
     }
@@ -137,6 +638,7 @@ impl From<BTreeMap<String, Message>> for ConfigSection {
     fn from(value: BTreeMap<String, Message>) -> Self {
         Self {
             key_bindings: value,
+            ..Default::default()
         }
     }
 }
@@ -182,6 +684,26 @@ struct TomlConfig {
     #[serde(default)]
     experimental_editor: bool,
     #[serde(default)]
+    vim_mode: bool,
+    #[serde(default)]
+    toast_duration_ms: Option<u64>,
+    #[serde(default)]
+    mouse: Option<bool>,
+    #[serde(default)]
+    words_per_minute: Option<usize>,
+    #[serde(default)]
+    wrap_code_blocks: Option<bool>,
+    #[serde(default)]
+    restore_session: Option<bool>,
+    #[serde(default)]
+    show_attachments: Option<bool>,
+    #[serde(default)]
+    autosave: Option<Autosave>,
+    #[serde(default)]
+    max_note_size_mb: Option<u64>,
+    #[serde(default)]
+    theme: ThemeColors,
+    #[serde(default)]
     global: TomlConfigSection,
     #[serde(default)]
     splash: TomlConfigSection,
@@ -195,6 +717,12 @@ struct TomlConfig {
     note_editor: TomlConfigSection,
     #[serde(default)]
     vault_selector_modal: TomlConfigSection,
+    #[serde(default)]
+    recent_notes_modal: TomlConfigSection,
+    #[serde(default)]
+    tag_panel: TomlConfigSection,
+    #[serde(default)]
+    confirm_modal: TomlConfigSection,
 }
 
 /// Finds and reads the user configuration file in order of priority.
@@ -206,7 +734,7 @@ struct TomlConfig {
 ///
 /// It first attempts to find the config file in the home directory. If not found, it then checks
 /// the config directory.
-fn read_user_config() -> Result<Config, ConfigError> {
+fn read_user_config() -> Result<String, ConfigError> {
     let home_dir_path = home_dir().map(|home_dir| home_dir.join(".basalt.toml"));
     let config_dir_path =
         choose_base_strategy().map(|strategy| strategy.config_dir().join("basalt/config.toml"));
@@ -219,14 +747,35 @@ fn read_user_config() -> Result<Config, ConfigError> {
             "Could not find user config".to_string(),
         ))?;
 
-    toml::from_str::<TomlConfig>(&read_to_string(config_path)?)
-        .map(Config::from)
-        .map_err(ConfigError::from)
+    Ok(read_to_string(config_path)?)
 }
 
 const BASE_CONFIGURATION_STR: &str =
     include_str!(concat!(env!("CARGO_MANIFEST_DIR"), "/config.toml"));
 
+/// Applies the system overrides (Ctrl+C) that cannot be changed by users.
+fn apply_system_overrides(mut config: Config) -> Config {
+    let system_key_binding_overrides: ConfigSection =
+        [(Key::CTRL_C.to_string(), Message::Quit)].into();
+
+    config
+        .global
+        .merge_key_bindings(system_key_binding_overrides);
+
+    config
+}
+
+/// Loads the base configuration from the embedded config.toml without the user configuration.
+///
+/// Used as a fallback when the user configuration cannot be loaded.
+pub fn load_base() -> Result<Config, ConfigError> {
+    // TODO: Use compile time toml parsing instead to check the build error during compile time
+    // Requires a custom proc-macro workspace crate
+    let base_config: Config = toml::from_str::<TomlConfig>(BASE_CONFIGURATION_STR)?.into();
+
+    Ok(apply_system_overrides(base_config))
+}
+
 /// Loads and merges configuration from multiple sources in priority order.
 ///
 /// The configuration is built by layering sources with increasing precedence:
@@ -234,27 +783,26 @@ const BASE_CONFIGURATION_STR: &str =
 /// 2. User-specific configuration from user's config directory
 /// 3. System overrides (Ctrl+C) that cannot be changed by users (highest priority)
 ///
+/// A missing user configuration is not an error, but a user configuration that cannot be read or
+/// parsed is.
+///
 /// # Configuration Precedence
 /// System overrides > User config > Base config
 pub fn load() -> Result<Config, ConfigError> {
-    // TODO: Use compile time toml parsing instead to check the build error during compile time
-    // Requires a custom proc-macro workspace crate
-    let mut base_config: Config = toml::from_str::<TomlConfig>(BASE_CONFIGURATION_STR)?.into();
-
-    // TODO: Parsing errors related to the configuration file should ideally be surfaced as warnings.
-    // This is pending a solution for toast notifications and proper warning/error logging.
-    if let Ok(user_config) = read_user_config() {
-        base_config.merge(user_config);
+    match read_user_config() {
+        Ok(user_config) => load_from_str(&user_config),
+        Err(ConfigError::UserConfigNotFound(_)) => load_base(),
+        Err(error) => Err(error),
     }
+}
 
-    let system_key_binding_overrides: ConfigSection =
-        [(Key::CTRL_C.to_string(), Message::Quit)].into();
-
-    base_config
-        .global
-        .merge_key_bindings(system_key_binding_overrides);
+/// Merges the user configuration in TOML format over the base configuration, the same way
+/// [`load`] merges the user's config file.
+pub fn load_from_str(user_config: &str) -> Result<Config, ConfigError> {
+    let mut base_config: Config = toml::from_str::<TomlConfig>(BASE_CONFIGURATION_STR)?.into();
+    base_config.merge(toml::from_str::<TomlConfig>(user_config)?.into());
 
-    Ok(base_config)
+    Ok(apply_system_overrides(base_config))
 }
 
 #[cfg(test)]
@@ -262,7 +810,9 @@ mod tests {
     use ratatui::crossterm::event::KeyModifiers;
 
     use super::*;
-    // use insta::assert_snapshot;
+    use crate::app::{explorer, note_editor, outline};
+    use indoc::indoc;
+    use insta::assert_snapshot;
 
     #[test]
     fn test_base_config_snapshot() {
@@ -306,6 +856,10 @@ mod tests {
         };
 
         assert_eq!(dummy_toml_config, expected_toml_config);
+        assert_eq!(
+            Config::from(dummy_toml_config.clone()).toast_duration(),
+            DEFAULT_TOAST_DURATION
+        );
 
         let expected_config = Config::default().merge(expected_toml_config.into());
 
@@ -314,4 +868,430 @@ mod tests {
             expected_config
         );
     }
+
+    #[test]
+    fn test_config_toast_duration() {
+        let user_config =
+            Config::from(toml::from_str::<TomlConfig>("toast_duration_ms = 1500").unwrap());
+
+        let mut config = load_base().unwrap().merge(user_config);
+        assert_eq!(config.toast_duration(), Duration::from_millis(1500));
+
+        let config = config.merge(Config::default());
+        assert_eq!(config.toast_duration(), Duration::from_millis(1500));
+    }
+
+    #[test]
+    fn test_config_mouse() {
+        assert!(Config::default().mouse());
+
+        let mut config = load_base().unwrap();
+        assert!(config.mouse());
+
+        let mut config = config.merge(Config::default());
+        assert!(config.mouse());
+
+        let user_config = Config::from(toml::from_str::<TomlConfig>("mouse = false").unwrap());
+        assert!(!config.merge(user_config).mouse());
+    }
+
+    #[test]
+    fn test_config_autosave() {
+        let mut config = load_base().unwrap();
+        assert_eq!(config.autosave(), Autosave::Off);
+
+        let tests = [
+            ("autosave = \"off\"", Autosave::Off),
+            ("autosave = \"on_block_exit\"", Autosave::OnBlockExit),
+            (
+                "autosave = { interval_seconds = 30 }",
+                Autosave::IntervalSeconds(30),
+            ),
+        ];
+
+        tests.into_iter().for_each(|(toml, expected)| {
+            let user_config = Config::from(toml::from_str::<TomlConfig>(toml).unwrap());
+            let mut config = config.merge(user_config);
+            assert_eq!(config.autosave(), expected);
+            assert_eq!(config.merge(Config::default()).autosave(), expected);
+        });
+
+        assert!(toml::from_str::<TomlConfig>("autosave = \"always\"").is_err());
+    }
+
+    #[test]
+    fn test_config_theme() {
+        use ratatui::style::Color;
+
+        let mut config = load_base().unwrap();
+        assert_eq!(config.theme, Theme::default());
+
+        let user_toml = r##"
+        [theme]
+        heading2 = "light-blue"
+        selection = "#d75f87"
+        code_bg = "236"
+        callout_Bug = "red"
+    "##;
+        let user_config = Config::from(toml::from_str::<TomlConfig>(user_toml).unwrap());
+        let mut config = config.merge(user_config);
+
+        let expected_theme = Theme {
+            heading2: Color::LightBlue,
+            selection: Color::Rgb(215, 95, 135),
+            code_bg: Color::Indexed(236),
+            ..Default::default()
+        };
+        assert_eq!(config.theme, expected_theme);
+        assert_eq!(
+            config.callout_colors(),
+            BTreeMap::from([("bug".to_string(), Color::Red)])
+        );
+
+        let user_config =
+            Config::from(toml::from_str::<TomlConfig>("[theme]\nheading3 = \"green\"").unwrap());
+        assert_eq!(
+            config.merge(user_config).theme,
+            Theme {
+                heading3: Color::Green,
+                ..expected_theme
+            }
+        );
+    }
+
+    #[test]
+    fn test_config_theme_errors() {
+        let tests = [
+            (
+                "[theme]\nquote_bar = \"not-a-color\"",
+                "Invalid color for theme.quote_bar: not-a-color",
+            ),
+            (
+                "[theme]\nheading7 = \"red\"",
+                "Unknown theme color: heading7",
+            ),
+        ];
+
+        tests.into_iter().for_each(|(toml, expected)| {
+            let error = ConfigError::from(toml::from_str::<TomlConfig>(toml).unwrap_err());
+            assert!(
+                error.to_string().contains(expected),
+                "{error} does not contain {expected}"
+            );
+        });
+    }
+
+    #[test]
+    fn test_load_from_str() {
+        let config = load_from_str(indoc! {r#"
+            [global]
+            key_bindings = [
+             { key = "q", command = "help_modal_toggle" },
+             { key = "x", command = "quit" },
+            ]
+        "#})
+        .unwrap();
+
+        assert_eq!(
+            config
+                .conflicts()
+                .iter()
+                .map(ToString::to_string)
+                .collect::<Vec<_>>(),
+            ["[global] q is bound to Quit, HelpModal(Toggle)"]
+        );
+        assert_eq!(config.global.key_bindings.get("x"), Some(&Message::Quit));
+
+        assert!(matches!(
+            load_from_str("[global]\nkey_bindings = 1"),
+            Err(ConfigError::Toml(_))
+        ));
+        assert_eq!(load_from_str("").unwrap(), load_base().unwrap());
+    }
+
+    #[test]
+    fn test_config_scroll_lines() {
+        let config = load_from_str(indoc! {r#"
+            [note_editor]
+            key_bindings = [
+             { key = "alt+j", command = { note_editor_scroll_down_lines = 5 } },
+            ]
+        "#})
+        .unwrap();
+
+        assert_eq!(
+            config.note_editor.key_bindings.get("alt+j"),
+            Some(&Message::NoteEditor(note_editor::Message::ScrollDown(
+                ScrollAmount::Lines(5)
+            )))
+        );
+        assert!(render_help(&config)
+            .lines()
+            .any(|line| line.contains("‹alt+j›") && line.ends_with("Scroll down lines")));
+    }
+
+    #[test]
+    fn test_config_conflicts() {
+        use key_binding::Key;
+
+        let user_toml = r#"
+        [explorer]
+        key_bindings = [
+         { key = "s", command = "explorer_sort" },
+         { key = "k", command = "explorer_down" },
+        ]
+
+        [note_editor]
+        key_bindings = [
+         { key = "x", command = "note_editor_cursor_up" },
+         { key = "x", command = "note_editor_cursor_down" },
+        ]
+
+        [outline]
+        key_bindings = [
+         { key = "j", command = "outline_up" },
+        ]
+    "#;
+
+        let mut base_config: Config = toml::from_str::<TomlConfig>(BASE_CONFIGURATION_STR)
+            .unwrap()
+            .into();
+        let user_config = Config::from(toml::from_str::<TomlConfig>(user_toml).unwrap());
+        let config = base_config.merge(user_config);
+
+        assert_eq!(
+            config.conflicts(),
+            vec![
+                KeyConflict {
+                    section: "explorer".to_string(),
+                    key: Key::from('k').into(),
+                    commands: vec![
+                        Message::Explorer(explorer::Message::Up),
+                        Message::Explorer(explorer::Message::Down),
+                    ],
+                },
+                KeyConflict {
+                    section: "outline".to_string(),
+                    key: Key::from('j').into(),
+                    commands: vec![
+                        Message::Outline(outline::Message::Down),
+                        Message::Outline(outline::Message::Up),
+                    ],
+                },
+                KeyConflict {
+                    section: "note_editor".to_string(),
+                    key: Key::from('x').into(),
+                    commands: vec![
+                        Message::NoteEditor(note_editor::Message::CursorUp),
+                        Message::NoteEditor(note_editor::Message::CursorDown),
+                    ],
+                },
+            ]
+        );
+
+        let base_config: Config = toml::from_str::<TomlConfig>(BASE_CONFIGURATION_STR)
+            .unwrap()
+            .into();
+        assert_eq!(base_config.conflicts(), vec![]);
+    }
+
+    #[test]
+    fn test_duplicate_commands() {
+        let user_toml = r#"
+        [global]
+        key_bindings = [
+         { key = "q", command = "quit" },
+         { key = "ctrl+q", command = "quit" },
+        ]
+
+        [explorer]
+        key_bindings = [
+         { key = "s", command = "explorer_sort" },
+         { key = "x", command = "explorer_sort" },
+         { key = "j", command = "explorer_down" },
+        ]
+
+        [outline]
+        key_bindings = [
+         { key = "q", command = "quit" },
+        ]
+    "#;
+
+        let config = Config::from(toml::from_str::<TomlConfig>(user_toml).unwrap());
+
+        assert_eq!(
+            config.explorer.all_commands(),
+            [
+                &Message::Explorer(explorer::Message::Down),
+                &Message::Explorer(explorer::Message::Sort),
+                &Message::Explorer(explorer::Message::Sort),
+            ]
+        );
+
+        assert_eq!(
+            config.duplicate_commands(),
+            BTreeMap::from([
+                (
+                    "Explorer(Sort)".to_string(),
+                    vec![
+                        ("explorer".to_string(), "s".to_string()),
+                        ("explorer".to_string(), "x".to_string()),
+                    ]
+                ),
+                (
+                    "Quit".to_string(),
+                    vec![
+                        ("global".to_string(), "ctrl+q".to_string()),
+                        ("global".to_string(), "q".to_string()),
+                    ]
+                ),
+            ])
+        );
+
+        // The duplicates of the base configuration are intended
+        assert_eq!(
+            load_base().unwrap().user_duplicate_commands(),
+            BTreeMap::new()
+        );
+        assert_eq!(
+            load_from_str(user_toml)
+                .unwrap()
+                .user_duplicate_commands()
+                .keys()
+                .collect::<Vec<_>>(),
+            ["Explorer(Sort)", "Quit"]
+        );
+    }
+
+    #[test]
+    fn test_shadowed_bindings() {
+        use ratatui::crossterm::event::KeyCode;
+
+        let user_toml = r#"
+        [global]
+        key_bindings = [{ key = "alt+x", command = "quit" }]
+
+        [explorer]
+        key_bindings = [
+         { key = "alt+x", command = "explorer_sort" },
+         { key = "q", command = "quit" },
+        ]
+    "#;
+
+        assert_eq!(
+            load_from_str(user_toml).unwrap().shadowed_bindings(),
+            vec![KeyConflict {
+                section: "global/explorer".to_string(),
+                key: Key::new(KeyCode::Char('x'), KeyModifiers::ALT).into(),
+                commands: vec![Message::Quit, Message::Explorer(explorer::Message::Sort)],
+            }]
+        );
+
+        assert_eq!(load_base().unwrap().shadowed_bindings(), vec![]);
+    }
+
+    #[test]
+    fn test_key_display() {
+        use ratatui::crossterm::event::KeyCode;
+
+        let tests = [
+            (
+                Key::new(KeyCode::Char('g'), KeyModifiers::CONTROL),
+                "ctrl+g",
+            ),
+            (Key::new(KeyCode::Char('G'), KeyModifiers::SHIFT), "shift+g"),
+            (
+                Key::new(KeyCode::BackTab, KeyModifiers::SHIFT),
+                "shift+backtab",
+            ),
+            (Key::new(KeyCode::Left, KeyModifiers::ALT), "alt+left"),
+            (Key::new(KeyCode::PageDown, KeyModifiers::NONE), "page_down"),
+            (Key::new(KeyCode::F(5), KeyModifiers::NONE), "f5"),
+            (Key::new(KeyCode::Char(' '), KeyModifiers::NONE), "space"),
+            (Key::new(KeyCode::Delete, KeyModifiers::NONE), "delete"),
+        ];
+
+        tests.into_iter().for_each(|(key, expected)| {
+            assert_eq!(key.to_string(), expected);
+            assert_eq!(
+                toml::Value::String(expected.to_string())
+                    .try_into::<Key>()
+                    .unwrap()
+                    .to_string(),
+                expected
+            );
+        });
+    }
+
+    #[test]
+    fn test_match_keys() {
+        use ratatui::crossterm::event::KeyCode;
+
+        let config = load_from_str(indoc! {r#"
+            [explorer]
+            key_bindings = [
+             { key = "space f", command = "explorer_open" },
+             { key = "g", command = "explorer_down" },
+             { key = "g g", command = "explorer_up" },
+            ]
+        "#})
+        .unwrap();
+
+        let keys = |keys: Vec<Key>| {
+            keys.into_iter()
+                .fold(KeySequence::default(), |sequence, key| {
+                    sequence.with_key(key)
+                })
+        };
+        let space = Key::new(KeyCode::Char(' '), KeyModifiers::NONE);
+        let open = Message::Explorer(explorer::Message::Open);
+        let down = Message::Explorer(explorer::Message::Down);
+        let up = Message::Explorer(explorer::Message::Up);
+
+        let tests = [
+            (keys(vec![space.clone()]), KeyMatch::Prefix(None)),
+            (
+                keys(vec![space.clone(), 'f'.into()]),
+                KeyMatch::Complete(open),
+            ),
+            (keys(vec![space, 'x'.into()]), KeyMatch::NoMatch),
+            (keys(vec!['g'.into()]), KeyMatch::Prefix(Some(down))),
+            (keys(vec!['g'.into(), 'g'.into()]), KeyMatch::Complete(up)),
+            (keys(vec!['g'.into(), 'j'.into()]), KeyMatch::NoMatch),
+            (
+                keys(vec!['j'.into()]),
+                KeyMatch::Complete(Message::Explorer(explorer::Message::Down)),
+            ),
+            (keys(vec!['x'.into()]), KeyMatch::NoMatch),
+        ];
+
+        tests.into_iter().for_each(|(keys, expected)| {
+            assert_eq!(config.explorer.match_keys(&keys), expected, "{keys}")
+        });
+
+        assert_eq!(
+            config.explorer.key_bindings.get("space f"),
+            Some(&Message::Explorer(explorer::Message::Open))
+        );
+    }
+
+    #[test]
+    fn test_key_sequence_errors() {
+        let tests = [r#""g bogus""#, r#""ctrl+nope f""#, r#""  ""#];
+
+        tests.into_iter().for_each(|key| {
+            let error = load_from_str(&format!(
+                "[global]\nkey_bindings = [{{ key = {key}, command = \"quit\" }}]"
+            ))
+            .unwrap_err()
+            .to_string();
+
+            assert!(error.contains(&format!("Invalid key {key}")), "{error}");
+        });
+    }
+
+    #[test]
+    fn test_render_help() {
+        assert_snapshot!(render_help(&load_base().unwrap()));
+    }
 }
@@ -8,44 +8,119 @@ use serde::{
 };
 
 use crate::app::{
-    explorer, help_modal, note_editor, outline, splash, vault_selector_modal, Message, ScrollAmount,
+    confirm_modal, explorer, help_modal, note_editor, outline, recent_notes_modal, splash,
+    tag_panel, vault_selector_modal, Message, ScrollAmount,
 };
 use crate::config::ConfigError;
 
 #[derive(Clone, Debug, PartialEq, Deserialize)]
 pub(crate) struct KeyBinding {
-    pub key: Key,
+    pub key: KeySequence,
     pub command: Command,
 }
 
 impl From<(Key, Command)> for KeyBinding {
     fn from((key, command): (Key, Command)) -> Self {
-        Self::new(key, command)
+        Self::new(key.into(), command)
     }
 }
 
 impl KeyBinding {
-    pub const fn new(key: Key, command: Command) -> Self {
+    pub const fn new(key: KeySequence, command: Command) -> Self {
         Self { key, command }
     }
 }
 
+/// Keys that are pressed one after another to trigger a command, written separated by spaces in
+/// the configuration file, e.g. `space f` or `g g`. Most key bindings are a single key.
+#[derive(Clone, Debug, Default, Eq, Hash, PartialEq)]
+pub struct KeySequence(Vec<Key>);
+
+impl KeySequence {
+    pub fn keys(&self) -> &[Key] {
+        &self.0
+    }
+
+    /// Returns the sequence followed by the given key.
+    pub fn with_key(&self, key: Key) -> Self {
+        Self(self.0.iter().cloned().chain([key]).collect())
+    }
+}
+
+impl From<Key> for KeySequence {
+    fn from(key: Key) -> Self {
+        Self(vec![key])
+    }
+}
+
+/// Formats the keys the same way they are written in the configuration file, e.g. `space f`.
+impl fmt::Display for KeySequence {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let keys = self
+            .0
+            .iter()
+            .map(|key| key.to_string())
+            .collect::<Vec<_>>()
+            .join(" ");
+
+        write!(f, "{keys}")
+    }
+}
+
+impl<'de> Deserialize<'de> for KeySequence {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let value = String::deserialize(deserializer)?;
+
+        let keys = value
+            .split_whitespace()
+            .map(parse_key)
+            .collect::<Result<Vec<Key>, ConfigError>>()
+            .map_err(|error| de::Error::custom(format!("Invalid key {value:?}: {error}")))?;
+
+        if keys.is_empty() {
+            return Err(de::Error::custom(format!("Invalid key {value:?}: no keys")));
+        }
+
+        Ok(Self(keys))
+    }
+}
+
 #[derive(Clone, Debug, Eq, Hash, PartialEq)]
 pub struct Key {
     pub modifiers: KeyModifiers,
     pub code: KeyCode,
 }
 
+/// Formats the key the same way it is written in the configuration file, e.g. `ctrl+g` or
+/// `page_down`.
 impl fmt::Display for Key {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        let code = self.code.to_string().to_lowercase().replace(" ", "_");
-
-        if self.modifiers.is_empty() {
-            write!(f, "{code}")
-        } else {
-            let modifiers = self.modifiers.to_string().to_ascii_lowercase();
-            write!(f, "{modifiers}-{code}")
-        }
+        let code = match self.code {
+            KeyCode::Char(' ') => "space".to_string(),
+            KeyCode::Char(c) => c.to_lowercase().to_string(),
+            KeyCode::F(n) => format!("f{n}"),
+            KeyCode::BackTab => "backtab".to_string(),
+            KeyCode::Delete => "delete".to_string(),
+            KeyCode::Insert => "insert".to_string(),
+            code => code.to_string().to_lowercase().replace(' ', "_"),
+        };
+
+        [
+            (KeyModifiers::CONTROL, "ctrl"),
+            (KeyModifiers::ALT, "alt"),
+            (KeyModifiers::SHIFT, "shift"),
+            (KeyModifiers::SUPER, "super"),
+            (KeyModifiers::HYPER, "hyper"),
+            (KeyModifiers::META, "meta"),
+        ]
+        .into_iter()
+        .filter(|(modifier, _)| self.modifiers.contains(*modifier))
+        .try_for_each(|(_, name)| write!(f, "{name}+"))?;
+
+        write!(f, "{code}")
     }
 }
 
@@ -97,25 +172,28 @@ impl Visitor<'_> for KeyVisitor {
     where
         E: de::Error,
     {
-        let value = value.to_lowercase();
-        let mut parts = value.split('+');
-        // Does not panic if the str is empty
-        let code = parts.by_ref().next_back().unwrap();
-        let modifiers = parts
-            .map(parse_modifiers)
-            .collect::<Result<Vec<KeyModifiers>, ConfigError>>()
-            .map_err(de::Error::custom)?
-            .into_iter()
-            .reduce(|acc, modifiers| acc.union(modifiers))
-            .unwrap_or(KeyModifiers::NONE);
-
-        Ok(Key {
-            modifiers,
-            code: parse_code(code).map_err(de::Error::custom)?,
-        })
+        parse_key(value).map_err(de::Error::custom)
     }
 }
 
+fn parse_key(value: &str) -> Result<Key, ConfigError> {
+    let value = value.to_lowercase();
+    let mut parts = value.split('+');
+    // Does not panic if the str is empty
+    let code = parts.by_ref().next_back().unwrap();
+    let modifiers = parts
+        .map(parse_modifiers)
+        .collect::<Result<Vec<KeyModifiers>, ConfigError>>()?
+        .into_iter()
+        .reduce(|acc, modifiers| acc.union(modifiers))
+        .unwrap_or(KeyModifiers::NONE);
+
+    Ok(Key {
+        modifiers,
+        code: parse_code(code)?,
+    })
+}
+
 fn parse_modifiers(modifiers: &str) -> Result<KeyModifiers, ConfigError> {
     match modifiers {
         "" => Ok(KeyModifiers::NONE),
@@ -180,10 +258,12 @@ impl From<&KeyEvent> for Key {
 #[serde(rename_all = "snake_case")]
 pub(crate) enum Command {
     Quit,
+    ReloadConfig,
 
     SplashUp,
     SplashDown,
     SplashOpen,
+    SplashResume,
 
     ExplorerUp,
     ExplorerDown,
@@ -197,20 +277,39 @@ pub(crate) enum Command {
     ExplorerScrollDownOne,
     ExplorerScrollUpHalfPage,
     ExplorerScrollDownHalfPage,
+    ExplorerScrollUpFullPage,
+    ExplorerScrollDownFullPage,
+    ExplorerScrollUpLines(usize),
+    ExplorerScrollDownLines(usize),
+    ExplorerNewNote,
+    ExplorerDeleteNote,
+    ExplorerRenameNote,
+    ExplorerToggleBookmark,
+    ExplorerBeginMove,
+    ExplorerRevealCurrent,
+    ExplorerExpandAll,
+    ExplorerCollapseAll,
+    ExplorerOpenInNewTab,
 
     OutlineUp,
     OutlineDown,
     OutlineSelect,
+    OutlineOpen,
     OutlineExpand,
     OutlineToggle,
     OutlineToggleExplorer,
     OutlineSwitchPaneNext,
     OutlineSwitchPanePrevious,
+    OutlineSearch,
 
     HelpModalScrollUpOne,
     HelpModalScrollDownOne,
     HelpModalScrollUpHalfPage,
     HelpModalScrollDownHalfPage,
+    HelpModalScrollUpFullPage,
+    HelpModalScrollDownFullPage,
+    HelpModalScrollUpLines(usize),
+    HelpModalScrollDownLines(usize),
     HelpModalToggle,
     HelpModalClose,
 
@@ -218,19 +317,51 @@ pub(crate) enum Command {
     NoteEditorScrollDownOne,
     NoteEditorScrollUpHalfPage,
     NoteEditorScrollDownHalfPage,
+    NoteEditorScrollUpFullPage,
+    NoteEditorScrollDownFullPage,
+    NoteEditorScrollUpLines(usize),
+    NoteEditorScrollDownLines(usize),
     NoteEditorSwitchPaneNext,
     NoteEditorSwitchPanePrevious,
     NoteEditorToggleExplorer,
     NoteEditorToggleOutline,
     NoteEditorCursorUp,
     NoteEditorCursorDown,
+    NoteEditorToggleFold,
+    NoteEditorFoldAll,
+    NoteEditorUnfoldAll,
+    NoteEditorToggleProperties,
+    NoteEditorFindStart,
+    NoteEditorFindNext,
+    NoteEditorFindPrev,
+    NoteEditorFollowLink,
+    NoteEditorJumpToWikiLink,
+    NoteEditorBack,
+    NoteEditorSplitVertical,
+    NoteEditorCloseSplit,
+    NoteEditorFocusNextSplit,
+    NoteEditorNextTab,
+    NoteEditorPreviousTab,
+    NoteEditorCloseTab,
+    NoteEditorExportHtml,
+    NoteEditorExport,
+    NoteEditorCopyObsidianUri,
+    NoteEditorCopyBlock,
+    NoteEditorCopyNote,
+    NoteEditorOpenPrevious,
+    NoteEditorOpenExternally,
+    NoteEditorReloadFromDisk,
 
     // # Experimental editor
     NoteEditorExperimentalCursorWordForward,
     NoteEditorExperimentalCursorWordBackward,
     NoteEditorExperimentalSetEditMode,
     NoteEditorExperimentalSetReadMode,
+    NoteEditorExperimentalSetNormalMode,
     NoteEditorExperimentalSave,
+    NoteEditorExperimentalUndo,
+    NoteEditorExperimentalRedo,
+    NoteEditorExperimentalToggleTask,
     NoteEditorExperimentalExitMode,
     NoteEditorExperimentalCursorLeft,
     NoteEditorExperimentalCursorRight,
@@ -240,16 +371,177 @@ pub(crate) enum Command {
     VaultSelectorModalClose,
     VaultSelectorModalOpen,
     VaultSelectorModalToggle,
+
+    RecentNotesModalUp,
+    RecentNotesModalDown,
+    RecentNotesModalClose,
+    RecentNotesModalOpen,
+    RecentNotesModalToggle,
+
+    TagPanelUp,
+    TagPanelDown,
+    TagPanelSelect,
+    TagPanelBack,
+    TagPanelToggle,
+
+    ConfirmModalSave,
+    ConfirmModalDiscard,
+    ConfirmModalCancel,
+}
+
+impl Command {
+    /// All commands in the order they are listed in the help.
+    pub(crate) const ALL: [Command; 119] = [
+        Command::Quit,
+        Command::ReloadConfig,
+        Command::SplashUp,
+        Command::SplashDown,
+        Command::SplashOpen,
+        Command::SplashResume,
+        Command::ExplorerUp,
+        Command::ExplorerDown,
+        Command::ExplorerOpen,
+        Command::ExplorerSort,
+        Command::ExplorerToggle,
+        Command::ExplorerToggleOutline,
+        Command::ExplorerSwitchPaneNext,
+        Command::ExplorerSwitchPanePrevious,
+        Command::ExplorerScrollUpOne,
+        Command::ExplorerScrollDownOne,
+        Command::ExplorerScrollUpHalfPage,
+        Command::ExplorerScrollDownHalfPage,
+        Command::ExplorerScrollUpFullPage,
+        Command::ExplorerScrollDownFullPage,
+        Command::ExplorerScrollUpLines(1),
+        Command::ExplorerScrollDownLines(1),
+        Command::ExplorerNewNote,
+        Command::ExplorerDeleteNote,
+        Command::ExplorerRenameNote,
+        Command::ExplorerToggleBookmark,
+        Command::ExplorerBeginMove,
+        Command::ExplorerRevealCurrent,
+        Command::ExplorerExpandAll,
+        Command::ExplorerCollapseAll,
+        Command::ExplorerOpenInNewTab,
+        Command::OutlineUp,
+        Command::OutlineDown,
+        Command::OutlineSelect,
+        Command::OutlineOpen,
+        Command::OutlineExpand,
+        Command::OutlineToggle,
+        Command::OutlineToggleExplorer,
+        Command::OutlineSwitchPaneNext,
+        Command::OutlineSwitchPanePrevious,
+        Command::OutlineSearch,
+        Command::HelpModalScrollUpOne,
+        Command::HelpModalScrollDownOne,
+        Command::HelpModalScrollUpHalfPage,
+        Command::HelpModalScrollDownHalfPage,
+        Command::HelpModalScrollUpFullPage,
+        Command::HelpModalScrollDownFullPage,
+        Command::HelpModalScrollUpLines(1),
+        Command::HelpModalScrollDownLines(1),
+        Command::HelpModalToggle,
+        Command::HelpModalClose,
+        Command::NoteEditorScrollUpOne,
+        Command::NoteEditorScrollDownOne,
+        Command::NoteEditorScrollUpHalfPage,
+        Command::NoteEditorScrollDownHalfPage,
+        Command::NoteEditorScrollUpFullPage,
+        Command::NoteEditorScrollDownFullPage,
+        Command::NoteEditorScrollUpLines(1),
+        Command::NoteEditorScrollDownLines(1),
+        Command::NoteEditorSwitchPaneNext,
+        Command::NoteEditorSwitchPanePrevious,
+        Command::NoteEditorToggleExplorer,
+        Command::NoteEditorToggleOutline,
+        Command::NoteEditorCursorUp,
+        Command::NoteEditorCursorDown,
+        Command::NoteEditorToggleFold,
+        Command::NoteEditorFoldAll,
+        Command::NoteEditorUnfoldAll,
+        Command::NoteEditorToggleProperties,
+        Command::NoteEditorFindStart,
+        Command::NoteEditorFindNext,
+        Command::NoteEditorFindPrev,
+        Command::NoteEditorFollowLink,
+        Command::NoteEditorJumpToWikiLink,
+        Command::NoteEditorBack,
+        Command::NoteEditorSplitVertical,
+        Command::NoteEditorCloseSplit,
+        Command::NoteEditorFocusNextSplit,
+        Command::NoteEditorNextTab,
+        Command::NoteEditorPreviousTab,
+        Command::NoteEditorCloseTab,
+        Command::NoteEditorExportHtml,
+        Command::NoteEditorExport,
+        Command::NoteEditorCopyObsidianUri,
+        Command::NoteEditorCopyBlock,
+        Command::NoteEditorCopyNote,
+        Command::NoteEditorOpenPrevious,
+        Command::NoteEditorOpenExternally,
+        Command::NoteEditorReloadFromDisk,
+        Command::NoteEditorExperimentalCursorWordForward,
+        Command::NoteEditorExperimentalCursorWordBackward,
+        Command::NoteEditorExperimentalSetEditMode,
+        Command::NoteEditorExperimentalSetReadMode,
+        Command::NoteEditorExperimentalSetNormalMode,
+        Command::NoteEditorExperimentalSave,
+        Command::NoteEditorExperimentalUndo,
+        Command::NoteEditorExperimentalRedo,
+        Command::NoteEditorExperimentalToggleTask,
+        Command::NoteEditorExperimentalExitMode,
+        Command::NoteEditorExperimentalCursorLeft,
+        Command::NoteEditorExperimentalCursorRight,
+        Command::VaultSelectorModalUp,
+        Command::VaultSelectorModalDown,
+        Command::VaultSelectorModalClose,
+        Command::VaultSelectorModalOpen,
+        Command::VaultSelectorModalToggle,
+        Command::RecentNotesModalUp,
+        Command::RecentNotesModalDown,
+        Command::RecentNotesModalClose,
+        Command::RecentNotesModalOpen,
+        Command::RecentNotesModalToggle,
+        Command::TagPanelUp,
+        Command::TagPanelDown,
+        Command::TagPanelSelect,
+        Command::TagPanelBack,
+        Command::TagPanelToggle,
+        Command::ConfirmModalSave,
+        Command::ConfirmModalDiscard,
+        Command::ConfirmModalCancel,
+    ];
+
+    /// Returns the name of the command used in the configuration file, e.g. `explorer_up`.
+    pub(crate) fn name(&self) -> String {
+        let debug = format!("{self:?}");
+        debug
+            .split('(')
+            .next()
+            .unwrap_or_default()
+            .chars()
+            .enumerate()
+            .fold(String::new(), |mut name, (index, c)| {
+                if c.is_uppercase() && index > 0 {
+                    name.push('_');
+                }
+                name.push(c.to_ascii_lowercase());
+                name
+            })
+    }
 }
 
 impl From<Command> for Message {
     fn from(value: Command) -> Self {
         match value {
             Command::Quit => Message::Quit,
+            Command::ReloadConfig => Message::ReloadConfig,
 
             Command::SplashUp => Message::Splash(splash::Message::Up),
             Command::SplashDown => Message::Splash(splash::Message::Down),
             Command::SplashOpen => Message::Splash(splash::Message::Open),
+            Command::SplashResume => Message::Splash(splash::Message::Resume),
 
             Command::ExplorerUp => Message::Explorer(explorer::Message::Up),
             Command::ExplorerDown => Message::Explorer(explorer::Message::Down),
@@ -273,10 +565,32 @@ impl From<Command> for Message {
             Command::ExplorerScrollDownHalfPage => {
                 Message::Explorer(explorer::Message::ScrollDown(ScrollAmount::HalfPage))
             }
+            Command::ExplorerScrollUpFullPage => {
+                Message::Explorer(explorer::Message::ScrollUp(ScrollAmount::FullPage))
+            }
+            Command::ExplorerScrollDownFullPage => {
+                Message::Explorer(explorer::Message::ScrollDown(ScrollAmount::FullPage))
+            }
+            Command::ExplorerScrollUpLines(lines) => {
+                Message::Explorer(explorer::Message::ScrollUp(ScrollAmount::Lines(lines)))
+            }
+            Command::ExplorerScrollDownLines(lines) => {
+                Message::Explorer(explorer::Message::ScrollDown(ScrollAmount::Lines(lines)))
+            }
+            Command::ExplorerNewNote => Message::Explorer(explorer::Message::NewNote),
+            Command::ExplorerDeleteNote => Message::Explorer(explorer::Message::Delete),
+            Command::ExplorerRenameNote => Message::Explorer(explorer::Message::Rename),
+            Command::ExplorerToggleBookmark => Message::Explorer(explorer::Message::ToggleBookmark),
+            Command::ExplorerBeginMove => Message::Explorer(explorer::Message::BeginMove),
+            Command::ExplorerRevealCurrent => Message::Explorer(explorer::Message::RevealCurrent),
+            Command::ExplorerExpandAll => Message::Explorer(explorer::Message::ExpandAll),
+            Command::ExplorerCollapseAll => Message::Explorer(explorer::Message::CollapseAll),
+            Command::ExplorerOpenInNewTab => Message::Explorer(explorer::Message::OpenInNewTab),
 
             Command::OutlineUp => Message::Outline(outline::Message::Up),
             Command::OutlineDown => Message::Outline(outline::Message::Down),
             Command::OutlineSelect => Message::Outline(outline::Message::Select),
+            Command::OutlineOpen => Message::Outline(outline::Message::Open),
             Command::OutlineExpand => Message::Outline(outline::Message::Expand),
             Command::OutlineToggle => Message::Outline(outline::Message::Toggle),
             Command::OutlineToggleExplorer => Message::Outline(outline::Message::ToggleExplorer),
@@ -284,6 +598,7 @@ impl From<Command> for Message {
             Command::OutlineSwitchPanePrevious => {
                 Message::Outline(outline::Message::SwitchPanePrevious)
             }
+            Command::OutlineSearch => Message::Outline(outline::Message::ActivateSearch),
 
             Command::HelpModalScrollUpOne => {
                 Message::HelpModal(help_modal::Message::ScrollUp(ScrollAmount::One))
@@ -297,6 +612,18 @@ impl From<Command> for Message {
             Command::HelpModalScrollDownHalfPage => {
                 Message::HelpModal(help_modal::Message::ScrollDown(ScrollAmount::HalfPage))
             }
+            Command::HelpModalScrollUpFullPage => {
+                Message::HelpModal(help_modal::Message::ScrollUp(ScrollAmount::FullPage))
+            }
+            Command::HelpModalScrollDownFullPage => {
+                Message::HelpModal(help_modal::Message::ScrollDown(ScrollAmount::FullPage))
+            }
+            Command::HelpModalScrollUpLines(lines) => {
+                Message::HelpModal(help_modal::Message::ScrollUp(ScrollAmount::Lines(lines)))
+            }
+            Command::HelpModalScrollDownLines(lines) => {
+                Message::HelpModal(help_modal::Message::ScrollDown(ScrollAmount::Lines(lines)))
+            }
             Command::HelpModalToggle => Message::HelpModal(help_modal::Message::Toggle),
             Command::HelpModalClose => Message::HelpModal(help_modal::Message::Close),
 
@@ -312,6 +639,18 @@ impl From<Command> for Message {
             Command::NoteEditorScrollDownHalfPage => {
                 Message::NoteEditor(note_editor::Message::ScrollDown(ScrollAmount::HalfPage))
             }
+            Command::NoteEditorScrollUpFullPage => {
+                Message::NoteEditor(note_editor::Message::ScrollUp(ScrollAmount::FullPage))
+            }
+            Command::NoteEditorScrollDownFullPage => {
+                Message::NoteEditor(note_editor::Message::ScrollDown(ScrollAmount::FullPage))
+            }
+            Command::NoteEditorScrollUpLines(lines) => {
+                Message::NoteEditor(note_editor::Message::ScrollUp(ScrollAmount::Lines(lines)))
+            }
+            Command::NoteEditorScrollDownLines(lines) => {
+                Message::NoteEditor(note_editor::Message::ScrollDown(ScrollAmount::Lines(lines)))
+            }
             Command::NoteEditorSwitchPaneNext => {
                 Message::NoteEditor(note_editor::Message::SwitchPaneNext)
             }
@@ -320,6 +659,48 @@ impl From<Command> for Message {
             }
             Command::NoteEditorCursorUp => Message::NoteEditor(note_editor::Message::CursorUp),
             Command::NoteEditorCursorDown => Message::NoteEditor(note_editor::Message::CursorDown),
+            Command::NoteEditorToggleFold => Message::NoteEditor(note_editor::Message::ToggleFold),
+            Command::NoteEditorFoldAll => Message::NoteEditor(note_editor::Message::FoldAll),
+            Command::NoteEditorUnfoldAll => Message::NoteEditor(note_editor::Message::UnfoldAll),
+            Command::NoteEditorToggleProperties => {
+                Message::NoteEditor(note_editor::Message::ToggleProperties)
+            }
+            Command::NoteEditorFindStart => Message::NoteEditor(note_editor::Message::FindStart),
+            Command::NoteEditorFindNext => Message::NoteEditor(note_editor::Message::FindNext),
+            Command::NoteEditorFindPrev => Message::NoteEditor(note_editor::Message::FindPrev),
+            Command::NoteEditorFollowLink => Message::NoteEditor(note_editor::Message::FollowLink),
+            Command::NoteEditorJumpToWikiLink => {
+                Message::NoteEditor(note_editor::Message::JumpToWikiLink)
+            }
+            Command::NoteEditorBack => Message::NoteEditor(note_editor::Message::Back),
+            Command::NoteEditorSplitVertical => {
+                Message::NoteEditor(note_editor::Message::SplitVertical)
+            }
+            Command::NoteEditorCloseSplit => Message::NoteEditor(note_editor::Message::CloseSplit),
+            Command::NoteEditorFocusNextSplit => {
+                Message::NoteEditor(note_editor::Message::FocusNextSplit)
+            }
+            Command::NoteEditorNextTab => Message::NoteEditor(note_editor::Message::NextTab),
+            Command::NoteEditorPreviousTab => {
+                Message::NoteEditor(note_editor::Message::PreviousTab)
+            }
+            Command::NoteEditorCloseTab => Message::NoteEditor(note_editor::Message::CloseTab),
+            Command::NoteEditorExportHtml => Message::NoteEditor(note_editor::Message::ExportHtml),
+            Command::NoteEditorExport => Message::NoteEditor(note_editor::Message::Export),
+            Command::NoteEditorCopyObsidianUri => {
+                Message::NoteEditor(note_editor::Message::CopyObsidianUri)
+            }
+            Command::NoteEditorCopyBlock => Message::NoteEditor(note_editor::Message::CopyBlock),
+            Command::NoteEditorCopyNote => Message::NoteEditor(note_editor::Message::CopyNote),
+            Command::NoteEditorOpenPrevious => {
+                Message::NoteEditor(note_editor::Message::OpenPrevious)
+            }
+            Command::NoteEditorOpenExternally => {
+                Message::NoteEditor(note_editor::Message::OpenExternally)
+            }
+            Command::NoteEditorReloadFromDisk => {
+                Message::NoteEditor(note_editor::Message::ReloadFromDisk)
+            }
             Command::NoteEditorToggleExplorer => {
                 Message::NoteEditor(note_editor::Message::ToggleExplorer)
             }
@@ -333,7 +714,15 @@ impl From<Command> for Message {
             Command::NoteEditorExperimentalSetReadMode => {
                 Message::NoteEditor(note_editor::Message::ReadMode)
             }
+            Command::NoteEditorExperimentalSetNormalMode => {
+                Message::NoteEditor(note_editor::Message::NormalMode)
+            }
             Command::NoteEditorExperimentalSave => Message::NoteEditor(note_editor::Message::Save),
+            Command::NoteEditorExperimentalUndo => Message::NoteEditor(note_editor::Message::Undo),
+            Command::NoteEditorExperimentalRedo => Message::NoteEditor(note_editor::Message::Redo),
+            Command::NoteEditorExperimentalToggleTask => {
+                Message::NoteEditor(note_editor::Message::ToggleTask)
+            }
             Command::NoteEditorExperimentalExitMode => {
                 Message::NoteEditor(note_editor::Message::ExitMode)
             }
@@ -364,6 +753,29 @@ impl From<Command> for Message {
             Command::VaultSelectorModalOpen => {
                 Message::VaultSelectorModal(vault_selector_modal::Message::Select)
             }
+            Command::RecentNotesModalUp => {
+                Message::RecentNotesModal(recent_notes_modal::Message::Up)
+            }
+            Command::RecentNotesModalDown => {
+                Message::RecentNotesModal(recent_notes_modal::Message::Down)
+            }
+            Command::RecentNotesModalClose => {
+                Message::RecentNotesModal(recent_notes_modal::Message::Close)
+            }
+            Command::RecentNotesModalOpen => {
+                Message::RecentNotesModal(recent_notes_modal::Message::Select)
+            }
+            Command::RecentNotesModalToggle => {
+                Message::RecentNotesModal(recent_notes_modal::Message::Toggle)
+            }
+            Command::TagPanelUp => Message::TagPanel(tag_panel::Message::Up),
+            Command::TagPanelDown => Message::TagPanel(tag_panel::Message::Down),
+            Command::TagPanelSelect => Message::TagPanel(tag_panel::Message::Select),
+            Command::TagPanelBack => Message::TagPanel(tag_panel::Message::Back),
+            Command::TagPanelToggle => Message::TagPanel(tag_panel::Message::Toggle),
+            Command::ConfirmModalSave => Message::ConfirmModal(confirm_modal::Message::Save),
+            Command::ConfirmModalDiscard => Message::ConfirmModal(confirm_modal::Message::Discard),
+            Command::ConfirmModalCancel => Message::ConfirmModal(confirm_modal::Message::Cancel),
         }
     }
 }
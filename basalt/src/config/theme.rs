@@ -0,0 +1,164 @@
+use std::{collections::BTreeMap, str::FromStr};
+
+use ratatui::style::Color;
+use serde::{de, Deserialize, Deserializer};
+
+use super::ConfigError;
+
+/// The prefix of the theme keys that color custom callout types, e.g. `callout_bug`.
+const CALLOUT_PREFIX: &str = "callout_";
+
+/// Colors used by the widgets, read from the `[theme]` section of the configuration.
+///
+/// Each color accepts a named ANSI color (e.g. `"magenta"`, `"light-blue"`), an ANSI color index
+/// (e.g. `"208"`) or a hex string (e.g. `"#d75f87"`). Colors that are not configured use the
+/// default palette.
+///
+/// Custom callout types are colored with keys like `callout_bug`, see [`ThemeColors::callouts`].
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Theme {
+    pub heading1: Color,
+    pub heading2: Color,
+    pub heading3: Color,
+    pub heading4: Color,
+    pub heading5: Color,
+    pub heading6: Color,
+    /// Background of code blocks and inline code in tables.
+    pub code_bg: Color,
+    /// The `┃` bar in front of block quotes.
+    pub quote_bar: Color,
+    /// Callouts with a custom type that has no configured color, e.g. `[!bug]`.
+    pub callout: Color,
+    /// The checkbox of completed tasks.
+    pub task_done: Color,
+    pub mode_view: Color,
+    pub mode_edit: Color,
+    pub mode_read: Color,
+    pub mode_normal: Color,
+    /// Border of the active pane.
+    pub border_active: Color,
+    /// The active pane indicator in the status bar.
+    pub statusbar_accent: Color,
+    /// Highlighted item in lists.
+    pub selection: Color,
+}
+
+impl Default for Theme {
+    fn default() -> Self {
+        Self {
+            heading1: Color::Reset,
+            heading2: Color::Yellow,
+            heading3: Color::Cyan,
+            heading4: Color::Magenta,
+            heading5: Color::Reset,
+            heading6: Color::Reset,
+            code_bg: Color::Black,
+            quote_bar: Color::Magenta,
+            callout: Color::Gray,
+            task_done: Color::Magenta,
+            mode_view: Color::Blue,
+            mode_edit: Color::Green,
+            mode_read: Color::Red,
+            mode_normal: Color::Yellow,
+            border_active: Color::Reset,
+            statusbar_accent: Color::DarkGray,
+            selection: Color::DarkGray,
+        }
+    }
+}
+
+impl Theme {
+    /// Returns the colors used to render Markdown.
+    pub fn markdown(&self) -> basalt_widgets::markdown::Theme {
+        basalt_widgets::markdown::Theme {
+            heading1: self.heading1,
+            heading2: self.heading2,
+            heading3: self.heading3,
+            heading4: self.heading4,
+            heading5: self.heading5,
+            heading6: self.heading6,
+            code_bg: self.code_bg,
+            quote_bar: self.quote_bar,
+            callout: self.callout,
+            task_done: self.task_done,
+        }
+    }
+
+    fn color_mut(&mut self, key: &str) -> Option<&mut Color> {
+        match key {
+            "heading1" => Some(&mut self.heading1),
+            "heading2" => Some(&mut self.heading2),
+            "heading3" => Some(&mut self.heading3),
+            "heading4" => Some(&mut self.heading4),
+            "heading5" => Some(&mut self.heading5),
+            "heading6" => Some(&mut self.heading6),
+            "code_bg" => Some(&mut self.code_bg),
+            "quote_bar" => Some(&mut self.quote_bar),
+            "callout" => Some(&mut self.callout),
+            "task_done" => Some(&mut self.task_done),
+            "mode_view" => Some(&mut self.mode_view),
+            "mode_edit" => Some(&mut self.mode_edit),
+            "mode_read" => Some(&mut self.mode_read),
+            "mode_normal" => Some(&mut self.mode_normal),
+            "border_active" => Some(&mut self.border_active),
+            "statusbar_accent" => Some(&mut self.statusbar_accent),
+            "selection" => Some(&mut self.selection),
+            _ => None,
+        }
+    }
+
+    /// Returns the theme with the given colors replacing the colors of the matching keys.
+    pub(crate) fn with_colors(mut self, colors: &ThemeColors) -> Self {
+        colors.0.iter().for_each(|(key, color)| {
+            if let Some(theme_color) = self.color_mut(key) {
+                *theme_color = *color;
+            }
+        });
+        self
+    }
+}
+
+/// The colors configured in a `[theme]` section by key.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub(crate) struct ThemeColors(BTreeMap<String, Color>);
+
+impl ThemeColors {
+    /// Takes the colors of another theme section overwriting the existing colors.
+    pub(crate) fn merge(&mut self, colors: Self) {
+        self.0.extend(colors.0);
+    }
+
+    /// Returns the colors of custom callout types by the lowercase type, configured with keys
+    /// like `callout_bug`.
+    pub(crate) fn callouts(&self) -> BTreeMap<String, Color> {
+        self.0
+            .iter()
+            .filter_map(|(key, color)| {
+                key.strip_prefix(CALLOUT_PREFIX)
+                    .map(|kind| (kind.to_lowercase(), *color))
+            })
+            .collect()
+    }
+}
+
+impl<'de> Deserialize<'de> for ThemeColors {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        BTreeMap::<String, String>::deserialize(deserializer)?
+            .into_iter()
+            .map(|(key, value)| {
+                if Theme::default().color_mut(&key).is_none() && !key.starts_with(CALLOUT_PREFIX) {
+                    return Err(ConfigError::UnknownThemeKey(key));
+                }
+
+                Color::from_str(&value)
+                    .map(|color| (key.clone(), color))
+                    .map_err(|_| ConfigError::InvalidColor { key, value })
+            })
+            .collect::<Result<BTreeMap<_, _>, _>>()
+            .map(Self)
+            .map_err(de::Error::custom)
+    }
+}
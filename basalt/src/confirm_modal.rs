@@ -0,0 +1,171 @@
+use std::marker::PhantomData;
+
+use ratatui::{
+    buffer::Buffer,
+    layout::{Alignment, Constraint, Flex, Layout, Rect},
+    style::{Style, Stylize},
+    text::{Line, Span},
+    widgets::{Block, BorderType, Clear, Paragraph, StatefulWidget, Widget},
+};
+
+use crate::config::Theme;
+
+/// Asks whether unsaved changes should be saved or discarded before a destructive message is
+/// performed.
+///
+/// The modal is generic over the confirmed message, so the same modal can guard any action that
+/// would lose unsaved changes.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ConfirmModalState<M> {
+    /// The message that is performed once the modal is confirmed.
+    pub message: Option<M>,
+    pub title: String,
+    pub prompt: String,
+    /// Verb describing the confirmed message, shown in the choices, e.g. `quit`.
+    pub action: String,
+    pub visible: bool,
+}
+
+impl<M> Default for ConfirmModalState<M> {
+    fn default() -> Self {
+        Self {
+            message: None,
+            title: String::new(),
+            prompt: String::new(),
+            action: String::new(),
+            visible: false,
+        }
+    }
+}
+
+impl<M> ConfirmModalState<M> {
+    pub fn new(message: M, action: &str) -> Self {
+        Self {
+            message: Some(message),
+            action: action.to_string(),
+            visible: true,
+            ..Default::default()
+        }
+    }
+
+    pub fn with_title(self, title: &str) -> Self {
+        Self {
+            title: title.to_string(),
+            ..self
+        }
+    }
+
+    pub fn with_prompt(self, prompt: &str) -> Self {
+        Self {
+            prompt: prompt.to_string(),
+            ..self
+        }
+    }
+
+    pub fn hide(self) -> Self {
+        Self {
+            visible: false,
+            ..self
+        }
+    }
+}
+
+fn modal_area(area: Rect) -> Rect {
+    let vertical = Layout::vertical([Constraint::Length(5)]).flex(Flex::Center);
+    let horizontal = Layout::horizontal([Constraint::Length(60)]).flex(Flex::Center);
+    let [area] = vertical.areas(area);
+    let [area] = horizontal.areas(area);
+    area
+}
+
+pub struct ConfirmModal<M> {
+    theme: Theme,
+    _message: PhantomData<M>,
+}
+
+impl<M> Default for ConfirmModal<M> {
+    fn default() -> Self {
+        Self {
+            theme: Theme::default(),
+            _message: PhantomData,
+        }
+    }
+}
+
+impl<M> ConfirmModal<M> {
+    pub fn with_theme(self, theme: &Theme) -> Self {
+        Self {
+            theme: *theme,
+            ..self
+        }
+    }
+}
+
+impl<M> StatefulWidget for ConfirmModal<M> {
+    type State = ConfirmModalState<M>;
+
+    fn render(self, area: Rect, buf: &mut Buffer, state: &mut Self::State)
+    where
+        Self: Sized,
+    {
+        let block = Block::bordered()
+            .border_type(BorderType::Rounded)
+            .border_style(Style::new().fg(self.theme.border_active))
+            .title_style(Style::default().italic().bold())
+            .title(format!(" {} ", state.title))
+            .title(Line::from(" (Esc) ").alignment(Alignment::Right));
+
+        let choice = |key: &str, label: String| {
+            [
+                Span::from(format!("‹{key}› ")).dark_gray(),
+                Span::from(label),
+                Span::from("   "),
+            ]
+        };
+
+        let choices = [
+            choice("s", format!("Save and {}", state.action)),
+            choice("d", format!("Discard and {}", state.action)),
+            choice("Esc", "Cancel".to_string()),
+        ]
+        .concat();
+
+        let area = modal_area(area);
+
+        Widget::render(Clear, area, buf);
+        Widget::render(
+            Paragraph::new(vec![
+                Line::from(state.prompt.clone()),
+                Line::default(),
+                Line::from(choices),
+            ])
+            .block(block),
+            area,
+            buf,
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use insta::assert_snapshot;
+    use ratatui::{backend::TestBackend, Terminal};
+
+    #[test]
+    fn test_confirm_modal() {
+        let mut state = ConfirmModalState::new((), "quit")
+            .with_title("Unsaved changes")
+            .with_prompt("Notes/Daily.md has unsaved changes.");
+
+        let mut terminal = Terminal::new(TestBackend::new(64, 7)).unwrap();
+        terminal
+            .draw(|frame| {
+                ConfirmModal::default().render(frame.area(), frame.buffer_mut(), &mut state)
+            })
+            .unwrap();
+        assert_snapshot!(terminal.backend());
+
+        assert!(!state.hide().visible);
+    }
+}
@@ -0,0 +1,52 @@
+//! The error type of basalt.
+use std::{io, result};
+
+use basalt_core::obsidian;
+
+use crate::config::ConfigError;
+
+/// A type alias for [`Result<T, Error>`](result::Result).
+pub type Result<T> = result::Result<T, Error>;
+
+/// Error type for the fallible startup and shutdown of basalt.
+///
+/// Errors that occur while basalt is running are shown as toasts instead.
+#[derive(thiserror::Error, Debug)]
+pub enum Error {
+    /// The configuration could not be loaded, from [`ConfigError`].
+    #[error("Failed to load config: {0}")]
+    Config(#[from] ConfigError),
+
+    /// The Obsidian config or a vault could not be read, from [`obsidian::Error`]. A failure to
+    /// load the Obsidian config is reported with [`crate::splash::error_message`] instead, which
+    /// explains how to fix it.
+    #[error(transparent)]
+    Obsidian(#[from] obsidian::Error),
+
+    /// Standard IO error, e.g. from the terminal, from [`std::io::Error`].
+    #[error(transparent)]
+    Io(#[from] io::Error),
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_error_display() {
+        let error: Error = obsidian::Error::PathNotFound("vault/Note.md".to_string()).into();
+        assert_eq!(error.to_string(), "Path not found: vault/Note.md");
+
+        let error: Error = obsidian::Error::AlreadyExists("vault/Note.md".into()).into();
+        assert_eq!(error.to_string(), "File already exists: vault/Note.md");
+
+        let error: Error = ConfigError::UnknownThemeKey("accent".to_string()).into();
+        assert_eq!(
+            error.to_string(),
+            "Failed to load config: Unknown theme color: accent"
+        );
+
+        let error: Error = io::Error::other("terminal closed").into();
+        assert_eq!(error.to_string(), "terminal closed");
+    }
+}
@@ -7,7 +7,10 @@ pub use state::Sort;
 
 use std::{marker::PhantomData, path::PathBuf};
 
-use basalt_core::obsidian::Note;
+use basalt_core::{
+    markdown::TaskStats,
+    obsidian::{FileKind, Note},
+};
 use ratatui::{
     buffer::Buffer,
     layout::{Alignment, Constraint, Layout, Rect},
@@ -16,24 +19,55 @@ use ratatui::{
     widgets::{Block, BorderType, List, ListItem, StatefulWidget},
 };
 
+use crate::config::Theme;
+
 const SORT_SYMBOL_ASC: &str = "↑𝌆";
 const SORT_SYMBOL_DESC: &str = "↓𝌆";
 
+/// Returns the symbol shown in front of attachments of the given kind, or `None` for notes.
+fn attachment_symbol(kind: FileKind) -> Option<&'static str> {
+    match kind {
+        FileKind::Markdown => None,
+        FileKind::Image => Some("▨"),
+        FileKind::Pdf => Some("▤"),
+        FileKind::Canvas => Some("▦"),
+        FileKind::Other => Some("▢"),
+    }
+}
+
 #[derive(Default)]
 pub struct Explorer<'a> {
+    theme: Theme,
+    /// Task progress of the open note, shown next to its name when it has tasks.
+    task_stats: TaskStats,
     _lifetime: PhantomData<&'a ()>,
 }
 
 impl Explorer<'_> {
     pub fn new() -> Self {
         Self {
+            theme: Theme::default(),
+            task_stats: TaskStats::default(),
             _lifetime: PhantomData::<&()>,
         }
     }
 
+    pub fn with_task_stats(self, task_stats: TaskStats) -> Self {
+        Self { task_stats, ..self }
+    }
+
+    pub fn with_theme(self, theme: &Theme) -> Self {
+        Self {
+            theme: *theme,
+            ..self
+        }
+    }
+
     fn list_item<'a>(
         selected_path: Option<PathBuf>,
         is_open: bool,
+        bookmarks: &'a [PathBuf],
+        task_stats: TaskStats,
     ) -> impl Fn(&'a (Item, usize)) -> ListItem<'a> {
         move |(item, depth)| {
             let indentation = if *depth > 0 {
@@ -46,9 +80,23 @@ impl Explorer<'_> {
                     let is_selected = selected_path
                         .as_ref()
                         .is_some_and(|selected| selected == path);
+                    let bookmark = if bookmarks.contains(path) {
+                        " ★".yellow()
+                    } else {
+                        "".into()
+                    };
+                    let name = match attachment_symbol(FileKind::from_path(path)) {
+                        Some(symbol) => Span::from(format!("{symbol} {name}")).dark_gray(),
+                        None => name.into(),
+                    };
+                    let tasks = if is_selected && task_stats.total > 0 {
+                        format!(" ☑ {task_stats}").dark_gray()
+                    } else {
+                        "".into()
+                    };
                     ListItem::new(Line::from(match (is_open, is_selected) {
-                        (true, true) => [indentation, "◆ ".into(), name.into()].to_vec(),
-                        (true, false) => [indentation, "  ".into(), name.into()].to_vec(),
+                        (true, true) => [indentation, "◆ ".into(), name, bookmark, tasks].to_vec(),
+                        (true, false) => [indentation, "  ".into(), name, bookmark].to_vec(),
                         (false, true) => ["◆".into()].to_vec(),
                         (false, false) => ["◦".dark_gray()].to_vec(),
                     }))
@@ -76,6 +124,11 @@ impl<'a> StatefulWidget for Explorer<'a> {
             } else {
                 BorderType::Rounded
             })
+            .border_style(if state.active {
+                Style::new().fg(self.theme.border_active)
+            } else {
+                Style::new()
+            })
             .title_style(Style::default().italic().bold());
 
         let Rect { height, .. } = block.inner(area);
@@ -89,7 +142,12 @@ impl<'a> StatefulWidget for Explorer<'a> {
         let items: Vec<ListItem> = state
             .flat_items
             .iter()
-            .map(Explorer::list_item(state.selected_path(), state.is_open()))
+            .map(Explorer::list_item(
+                state.selected_path(),
+                state.is_open(),
+                &state.bookmarks,
+                self.task_stats,
+            ))
             .collect();
 
         if state.open {
@@ -100,7 +158,7 @@ impl<'a> StatefulWidget for Explorer<'a> {
                             .alignment(Alignment::Right),
                     ),
                 )
-                .highlight_style(Style::new().reversed().dark_gray())
+                .highlight_style(Style::new().reversed().fg(self.theme.selection))
                 .highlight_symbol(" ")
                 .render(area, buf, &mut state.list_state);
         } else {
@@ -108,7 +166,7 @@ impl<'a> StatefulWidget for Explorer<'a> {
 
             List::new(items)
                 .block(block.title(" ▶ "))
-                .highlight_style(Style::new().reversed().dark_gray())
+                .highlight_style(Style::new().reversed().fg(self.theme.selection))
                 .highlight_symbol(" ")
                 .render(layout[0], buf, &mut state.list_state);
         }
@@ -118,6 +176,8 @@ impl<'a> StatefulWidget for Explorer<'a> {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use std::path::{Path, PathBuf};
+
     use basalt_core::obsidian::VaultEntry;
     use insta::assert_snapshot;
     use ratatui::{backend::TestBackend, Terminal};
@@ -188,4 +248,431 @@ mod tests {
             assert_snapshot!(terminal.backend());
         });
     }
+
+    #[test]
+    fn test_render_attachments() {
+        let items = [
+            note("Test", "test.md"),
+            note("diagram.png", "diagram.png"),
+            note("Board.canvas", "board.canvas"),
+            note("Manual.pdf", "manual.pdf"),
+            note("archive.zip", "archive.zip"),
+        ]
+        .to_vec();
+
+        let mut terminal = Terminal::new(TestBackend::new(30, 8)).unwrap();
+
+        [true, false].into_iter().for_each(|show_attachments| {
+            _ = terminal.clear();
+            terminal
+                .draw(|frame| {
+                    Explorer::default().render(
+                        frame.area(),
+                        frame.buffer_mut(),
+                        &mut ExplorerState::new("Test", items.clone())
+                            .with_attachments(show_attachments),
+                    )
+                })
+                .unwrap();
+            assert_snapshot!(terminal.backend());
+        });
+    }
+
+    #[test]
+    fn test_select_attachment() {
+        let items = [note("Test", "test.md"), note("diagram.png", "diagram.png")].to_vec();
+        let state = ExplorerState::new("Test", items);
+
+        let state = state.reveal(Path::new("diagram.png")).select();
+        assert_eq!(state.selected_note, None);
+        assert_eq!(
+            state.preview.as_ref().map(|note| note.name.as_str()),
+            Some("diagram.png")
+        );
+        assert_eq!(state.selected_path(), Some(PathBuf::from("diagram.png")));
+
+        let state = state.reveal(Path::new("test.md")).select();
+        assert_eq!(
+            state.selected_note.as_ref().map(|note| note.name.as_str()),
+            Some("Test")
+        );
+        assert_eq!(state.preview, None);
+    }
+
+    #[test]
+    fn test_reveal() {
+        let items = [
+            VaultEntry::File(Note {
+                name: "Test".into(),
+                path: "test.md".into(),
+            }),
+            VaultEntry::Directory {
+                name: "TestDir".into(),
+                path: "test_dir".into(),
+                entries: vec![
+                    VaultEntry::Directory {
+                        name: "Archive".into(),
+                        path: "test_dir/archive".into(),
+                        entries: vec![VaultEntry::File(Note {
+                            name: "Old".into(),
+                            path: "test_dir/archive/old.md".into(),
+                        })],
+                    },
+                    VaultEntry::Directory {
+                        name: "Notes".into(),
+                        path: "test_dir/notes".into(),
+                        entries: vec![VaultEntry::File(Note {
+                            name: "Pathing".into(),
+                            path: "test_dir/notes/pathing.md".into(),
+                        })],
+                    },
+                ],
+            },
+        ]
+        .to_vec();
+
+        let state =
+            ExplorerState::new("Test", items).reveal(Path::new("test_dir/notes/pathing.md"));
+
+        // Only the ancestors of the note are expanded
+        assert_eq!(
+            state
+                .flat_items
+                .iter()
+                .map(|(item, depth)| match item {
+                    Item::Directory { name, expanded, .. } => (name.as_str(), *depth, *expanded),
+                    Item::File(note) => (note.name.as_str(), *depth, false),
+                })
+                .collect::<Vec<_>>(),
+            [
+                ("TestDir", 0, true),
+                ("Archive", 1, false),
+                ("Notes", 1, true),
+                ("Pathing", 2, false),
+                ("Test", 0, false),
+            ]
+        );
+        assert_eq!(state.list_state.selected(), Some(3));
+        assert_eq!(state.selected_item_index, Some(3));
+        assert_eq!(
+            state.selected_path(),
+            Some(PathBuf::from("test_dir/notes/pathing.md"))
+        );
+        assert_eq!(
+            state.selected_note.as_ref().map(|note| note.name.as_str()),
+            Some("Pathing")
+        );
+
+        let unchanged = state.reveal(Path::new("missing.md"));
+        assert_eq!(unchanged, state);
+
+        let mut terminal = Terminal::new(TestBackend::new(30, 10)).unwrap();
+        terminal
+            .draw(|frame| {
+                Explorer::default().render(frame.area(), frame.buffer_mut(), &mut state.clone())
+            })
+            .unwrap();
+        assert_snapshot!(terminal.backend());
+    }
+
+    #[test]
+    fn test_expand_and_collapse_all() {
+        let items = [
+            note("Test", "test.md"),
+            VaultEntry::Directory {
+                name: "TestDir".into(),
+                path: "test_dir".into(),
+                entries: vec![
+                    VaultEntry::Directory {
+                        name: "Archive".into(),
+                        path: "test_dir/archive".into(),
+                        entries: vec![note("Old", "test_dir/archive/old.md")],
+                    },
+                    note("Pathing", "test_dir/pathing.md"),
+                ],
+            },
+        ]
+        .to_vec();
+
+        let expanded = ExplorerState::new("Test", items).expand_all();
+        assert_eq!(expanded.flat_items.len(), 5);
+
+        let expanded = expanded.next(2);
+        assert_eq!(
+            expanded.note_at_cursor().map(|note| note.name),
+            Some("Old".to_string())
+        );
+
+        let collapsed = expanded.collapse_all();
+        assert_eq!(collapsed.flat_items.len(), 2);
+        assert_eq!(
+            collapsed.directory_at_cursor(),
+            Some(PathBuf::from("test_dir")),
+            "Cursor moves to the closest visible directory of the hidden note"
+        );
+
+        let mut terminal = Terminal::new(TestBackend::new(30, 8)).unwrap();
+
+        [expanded, collapsed].into_iter().for_each(|mut state| {
+            _ = terminal.clear();
+            terminal
+                .draw(|frame| {
+                    Explorer::default().render(frame.area(), frame.buffer_mut(), &mut state)
+                })
+                .unwrap();
+            assert_snapshot!(terminal.backend());
+        });
+    }
+
+    #[test]
+    fn test_create_note() {
+        let note = |name: &str, path: &str| {
+            VaultEntry::File(Note {
+                name: name.into(),
+                path: path.into(),
+            })
+        };
+
+        let entries = |notes: Vec<VaultEntry>| {
+            [
+                note("Test", "test.md"),
+                VaultEntry::Directory {
+                    name: "TestDir".into(),
+                    path: "test_dir".into(),
+                    entries: vec![
+                        note("Andesite", "test_dir/andesite.md"),
+                        VaultEntry::Directory {
+                            name: "Notes".into(),
+                            path: "test_dir/notes".into(),
+                            entries: notes,
+                        },
+                    ],
+                },
+            ]
+            .to_vec()
+        };
+
+        let state = ExplorerState::new("Test", entries(vec![]));
+        assert_eq!(state.selected_directory(), Some(PathBuf::from("test_dir")));
+
+        // Expand TestDir and move the cursor to the collapsed Notes directory
+        let state = state.select().next(1);
+        assert_eq!(
+            state.selected_directory(),
+            Some(PathBuf::from("test_dir/notes"))
+        );
+        assert_eq!(
+            state.clone().next(1).selected_directory(),
+            Some(PathBuf::from("test_dir"))
+        );
+
+        // The new note is created inside the collapsed Notes directory
+        let state = state
+            .with_entries(entries(vec![note("Pathing", "test_dir/notes/pathing.md")]))
+            .reveal(Path::new("test_dir/notes/pathing.md"));
+
+        assert_eq!(
+            state
+                .flat_items
+                .iter()
+                .map(|(item, depth)| (item.name(), *depth))
+                .collect::<Vec<_>>(),
+            [
+                ("TestDir", 0),
+                ("Notes", 1),
+                ("Pathing", 2),
+                ("Andesite", 1),
+                ("Test", 0)
+            ]
+        );
+        assert_eq!(state.list_state.selected(), Some(2));
+        assert_eq!(
+            state.selected_directory(),
+            Some(PathBuf::from("test_dir/notes"))
+        );
+
+        let state = state.next(2);
+        assert_eq!(state.selected_directory(), None);
+    }
+
+    fn names(state: &ExplorerState) -> Vec<(String, usize)> {
+        state
+            .flat_items
+            .iter()
+            .map(|(item, depth)| (item.name().to_string(), *depth))
+            .collect()
+    }
+
+    fn note(name: &str, path: &str) -> VaultEntry {
+        VaultEntry::File(Note {
+            name: name.into(),
+            path: path.into(),
+        })
+    }
+
+    fn directory(notes: Vec<VaultEntry>) -> VaultEntry {
+        VaultEntry::Directory {
+            name: "TestDir".into(),
+            path: "test_dir".into(),
+            entries: notes,
+        }
+    }
+
+    #[test]
+    fn test_delete_note() {
+        let entries = [
+            directory(vec![
+                note("Andesite", "test_dir/andesite.md"),
+                note("Basalt", "test_dir/basalt.md"),
+            ]),
+            note("Test", "test.md"),
+        ]
+        .to_vec();
+
+        // Expand TestDir and open Andesite
+        let state = ExplorerState::new("Test", entries)
+            .select()
+            .next(1)
+            .select();
+
+        assert_eq!(
+            state.note_at_cursor().map(|note| note.name),
+            Some("Andesite".into())
+        );
+
+        // The cursor moves to the next sibling
+        let state = state.with_deleted_note(
+            Path::new("test_dir/andesite.md"),
+            [
+                directory(vec![note("Basalt", "test_dir/basalt.md")]),
+                note("Test", "test.md"),
+            ]
+            .to_vec(),
+        );
+
+        assert_eq!(
+            names(&state),
+            [
+                ("TestDir".into(), 0),
+                ("Basalt".into(), 1),
+                ("Test".into(), 0)
+            ]
+        );
+        assert_eq!(state.list_state.selected(), Some(1));
+        assert_eq!(state.selected_note, None);
+        assert_eq!(state.selected_path(), None);
+
+        // The cursor moves up when the last note in a directory is deleted
+        let state = state.with_deleted_note(
+            Path::new("test_dir/basalt.md"),
+            [directory(vec![]), note("Test", "test.md")].to_vec(),
+        );
+
+        assert_eq!(names(&state), [("TestDir".into(), 0), ("Test".into(), 0)]);
+        assert_eq!(state.list_state.selected(), Some(0));
+
+        // The selected note stays selected when another note is deleted
+        let state = state.next(1).select().with_deleted_note(
+            Path::new("test_dir/other.md"),
+            [directory(vec![]), note("Test", "test.md")].to_vec(),
+        );
+
+        assert_eq!(state.selected_path(), Some(PathBuf::from("test.md")));
+    }
+
+    #[test]
+    fn test_rename_note() {
+        let entries = [
+            directory(vec![
+                note("Andesite", "test_dir/andesite.md"),
+                note("Basalt", "test_dir/basalt.md"),
+            ]),
+            note("Test", "test.md"),
+        ]
+        .to_vec();
+
+        // Expand TestDir and open Andesite
+        let state = ExplorerState::new("Test", entries)
+            .select()
+            .next(1)
+            .select();
+
+        let renamed = Note {
+            name: "Obsidian".into(),
+            path: "test_dir/obsidian.md".into(),
+        };
+
+        let state = state.with_renamed_note(
+            Path::new("test_dir/andesite.md"),
+            renamed.clone(),
+            [
+                directory(vec![
+                    note("Basalt", "test_dir/basalt.md"),
+                    VaultEntry::File(renamed.clone()),
+                ]),
+                note("Test", "test.md"),
+            ]
+            .to_vec(),
+        );
+
+        assert_eq!(
+            names(&state),
+            [
+                ("TestDir".into(), 0),
+                ("Basalt".into(), 1),
+                ("Obsidian".into(), 1),
+                ("Test".into(), 0)
+            ]
+        );
+        assert_eq!(state.list_state.selected(), Some(2));
+        assert_eq!(state.selected_item_index, Some(2));
+        assert_eq!(state.selected_note, Some(renamed));
+    }
+
+    #[test]
+    fn test_move_note() {
+        let dir = tempfile::tempdir().unwrap();
+        let projects = dir.path().join("Projects");
+        let daily = dir.path().join("Daily.md");
+        std::fs::create_dir(&projects).unwrap();
+        std::fs::write(projects.join("Idea.md"), "").unwrap();
+        std::fs::write(&daily, "# Daily").unwrap();
+
+        let entries = [projects.as_path(), daily.as_path()]
+            .into_iter()
+            .map(|path| VaultEntry::try_from(path).unwrap())
+            .collect();
+
+        // Open Daily, which is listed after the collapsed Projects directory
+        let state = ExplorerState::new("Test", entries).next(1).select();
+        let note = state.note_at_cursor().unwrap();
+        let state = state.begin_move(Some(note.clone()));
+
+        let state = state.move_note(&note, &projects).unwrap();
+        let moved = projects.join("Daily.md");
+
+        assert!(!daily.exists());
+        assert_eq!(std::fs::read_to_string(&moved).unwrap(), "# Daily");
+        assert_eq!(
+            names(&state),
+            [
+                ("Projects".into(), 0),
+                ("Daily".into(), 1),
+                ("Idea".into(), 1)
+            ]
+        );
+        assert_eq!(state.list_state.selected(), Some(1));
+        assert_eq!(state.selected_path(), Some(moved));
+        assert_eq!(state.pending_move, None);
+
+        // Moving into a directory that already contains the file fails
+        std::fs::write(&daily, "").unwrap();
+        let state =
+            ExplorerState::new("Test", vec![VaultEntry::try_from(daily.as_path()).unwrap()]);
+        let note = state.note_at_cursor().unwrap();
+        assert_eq!(
+            state.move_note(&note, &projects).unwrap_err().kind(),
+            std::io::ErrorKind::AlreadyExists
+        );
+    }
 }
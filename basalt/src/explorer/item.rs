@@ -1,6 +1,6 @@
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 
-use basalt_core::obsidian::{Note, VaultEntry};
+use basalt_core::obsidian::{Note, SortEntry, VaultEntry};
 
 #[derive(Debug, Clone, PartialEq)]
 pub enum Item {
@@ -20,9 +20,41 @@ impl Item {
         }
     }
 
+    pub(crate) fn path(&self) -> &Path {
+        match self {
+            Self::Directory { path, .. } | Self::File(Note { path, .. }) => path.as_path(),
+        }
+    }
+
     pub(crate) fn is_dir(&self) -> bool {
         matches!(self, Self::Directory { .. })
     }
+
+    /// Returns `true` if the item is a file that is not a markdown note, e.g. an image.
+    pub(crate) fn is_attachment(&self) -> bool {
+        matches!(self, Self::File(note) if !note.kind().is_markdown())
+    }
+}
+
+impl SortEntry for Item {
+    fn name(&self) -> &str {
+        Item::name(self)
+    }
+
+    fn path(&self) -> &Path {
+        Item::path(self)
+    }
+
+    fn entries_mut(&mut self) -> Option<&mut [Self]> {
+        match self {
+            Self::Directory { items, .. } => Some(items),
+            Self::File(_) => None,
+        }
+    }
+
+    fn is_dir(&self) -> bool {
+        Item::is_dir(self)
+    }
 }
 
 impl From<VaultEntry> for Item {
@@ -1,11 +1,13 @@
 use std::{
-    cmp::Ordering,
+    fs, io,
     path::{Path, PathBuf},
 };
 
-use basalt_core::obsidian::{Note, VaultEntry};
+use basalt_core::obsidian::{Note, SortOrder, VaultEntry};
 use ratatui::widgets::ListState;
 
+use crate::scroll::calculate_offset;
+
 use super::Item;
 
 #[derive(Debug, Default, Copy, Clone, PartialEq)]
@@ -15,10 +17,24 @@ pub enum Sort {
     Desc,
 }
 
+impl Sort {
+    /// Returns the order of the items: directories before files from A to Z, or all items from Z
+    /// to A.
+    fn order(self) -> SortOrder {
+        match self {
+            Sort::Asc => SortOrder::FileType,
+            Sort::Desc => SortOrder::NameDesc,
+        }
+    }
+}
+
 #[derive(Debug, Default, Clone, PartialEq)]
 pub struct ExplorerState<'a> {
     pub(crate) title: &'a str,
     pub(crate) selected_note: Option<Note>,
+    /// The selected attachment, e.g. an image or a canvas. Attachments are previewed instead of
+    /// opened in the editor, so they never populate `selected_note`.
+    pub(crate) preview: Option<Note>,
     pub(crate) selected_item_index: Option<usize>,
     pub(crate) selected_item_path: Option<PathBuf>,
     pub(crate) items: Vec<Item>,
@@ -27,46 +43,19 @@ pub struct ExplorerState<'a> {
     pub(crate) sort: Sort,
     pub(crate) list_state: ListState,
     pub(crate) active: bool,
+    /// Paths of the bookmarked files, which are marked with a star.
+    pub(crate) bookmarks: Vec<PathBuf>,
+    /// Leaves out attachments, i.e. files that are not markdown notes, from the listed items.
+    pub(crate) hide_attachments: bool,
+    /// The note that is moved into the next directory that is opened.
+    pub(crate) pending_move: Option<Note>,
 }
 
-/// Calculates the vertical offset of list items in rows.
-///
-/// When the selected item is near the end of the list and there aren't enough items
-/// remaining to keep the selection vertically centered, we shift the offset to show
-/// as many trailing items as possible instead of centering the selection.
-///
-/// This prevents empty lines from appearing at the bottom of the list when the
-/// selection moves toward the end.
-///
-/// Without this check, you'd see output like:
-/// ╭────────╮
-/// │ 3 item │
-/// │>4 item │
-/// │ 5 item │
-/// │        │
-/// ╰────────╯
-///
-/// With this check, the list scrolls up to fill the remaining space:
-/// ╭────────╮
-/// │ 2 item │
-/// │ 3 item │
-/// │>4 item │
-/// │ 5 item │
-/// ╰────────╯
-///
-/// The goal is to avoid showing unnecessary blank rows and to maximize visible items.
-fn calculate_offset(row: usize, items_count: usize, window_height: usize) -> usize {
-    let half = window_height / 2;
-
-    if row + half > items_count.saturating_sub(1) {
-        items_count.saturating_sub(window_height)
-    } else {
-        row.saturating_sub(half)
-    }
-}
-
-pub fn flatten(sort: Sort, depth: usize) -> impl Fn(&Item) -> Vec<(Item, usize)> {
+/// Flattens the item and the items of its expanded directories, which are expected to be sorted
+/// already.
+pub fn flatten(hide_attachments: bool, depth: usize) -> impl Fn(&Item) -> Vec<(Item, usize)> {
     move |item| match item {
+        Item::File(..) if hide_attachments && item.is_attachment() => vec![],
         Item::File(..) => vec![(item.clone(), depth)],
         Item::Directory {
             expanded: true,
@@ -74,14 +63,7 @@ pub fn flatten(sort: Sort, depth: usize) -> impl Fn(&Item) -> Vec<(Item, usize)>
             ..
         } => [(item.clone(), depth)]
             .into_iter()
-            .chain({
-                let mut items = items.clone();
-                items.sort_by(sort_items_by(sort));
-                items
-                    .iter()
-                    .flat_map(flatten(sort, depth + 1))
-                    .collect::<Vec<_>>()
-            })
+            .chain(items.iter().flat_map(flatten(hide_attachments, depth + 1)))
             .collect(),
         Item::Directory {
             expanded: false, ..
@@ -89,22 +71,9 @@ pub fn flatten(sort: Sort, depth: usize) -> impl Fn(&Item) -> Vec<(Item, usize)>
     }
 }
 
-fn sort_items_by(sort: Sort) -> impl Fn(&Item, &Item) -> Ordering {
-    move |a, b| match (a.is_dir(), b.is_dir()) {
-        (true, false) => Ordering::Less,
-        (false, true) => Ordering::Greater,
-        _ => {
-            let a = a.name().to_lowercase();
-            let b = b.name().to_lowercase();
-            match sort {
-                Sort::Asc => a.cmp(&b),
-                Sort::Desc => b.cmp(&a),
-            }
-        }
-    }
-}
-
 impl<'a> ExplorerState<'a> {
+    /// Creates the explorer of the vault entries, which are sorted with the [`SortOrder`] of the
+    /// default [`Sort`].
     pub fn new(title: &'a str, items: Vec<VaultEntry>) -> Self {
         let items: Vec<Item> = items.into_iter().map(|entry| entry.into()).collect();
         let sort = Sort::default();
@@ -149,10 +118,13 @@ impl<'a> ExplorerState<'a> {
 
     pub fn flatten_with_sort(&self, sort: Sort) -> Self {
         let mut items = self.items.clone();
-        items.sort_by(sort_items_by(sort));
+        sort.order().sort(&mut items);
 
         Self {
-            flat_items: items.iter().flat_map(flatten(sort, 0)).collect(),
+            flat_items: items
+                .iter()
+                .flat_map(flatten(self.hide_attachments, 0))
+                .collect(),
             items,
             sort,
             ..self.clone()
@@ -161,10 +133,13 @@ impl<'a> ExplorerState<'a> {
 
     pub fn flatten_with_items(&self, items: &[Item]) -> Self {
         let mut items = items.to_vec();
-        items.sort_by(sort_items_by(self.sort));
+        self.sort.order().sort(&mut items);
 
         Self {
-            flat_items: items.iter().flat_map(flatten(self.sort, 0)).collect(),
+            flat_items: items
+                .iter()
+                .flat_map(flatten(self.hide_attachments, 0))
+                .collect(),
             items: items.to_vec(),
             ..self.clone()
         }
@@ -182,7 +157,7 @@ impl<'a> ExplorerState<'a> {
     pub fn update_offset_mut(&mut self, window_height: usize) -> &Self {
         if !self.items.is_empty() {
             let idx = self.list_state.selected().unwrap_or_default();
-            let items_count = self.items.len();
+            let items_count = self.flat_items.len();
 
             let offset = calculate_offset(idx, items_count, window_height);
 
@@ -223,6 +198,333 @@ impl<'a> ExplorerState<'a> {
         }
     }
 
+    fn set_expanded_in_tree(item: &Item, expanded: bool) -> Item {
+        match item {
+            Item::Directory {
+                path, name, items, ..
+            } => Item::Directory {
+                name: name.clone(),
+                path: path.clone(),
+                expanded,
+                items: items
+                    .iter()
+                    .map(|child| Self::set_expanded_in_tree(child, expanded))
+                    .collect(),
+            },
+            _ => item.clone(),
+        }
+    }
+
+    /// Expands or collapses every directory. The cursor stays on the item under it, or moves to
+    /// the closest directory containing the item when the item is no longer visible.
+    fn with_all_expanded(&self, expanded: bool) -> Self {
+        let cursor_path = self
+            .list_state
+            .selected()
+            .and_then(|index| self.flat_items.get(index))
+            .map(|(item, _)| item.path().to_path_buf());
+
+        let items: Vec<Item> = self
+            .items
+            .iter()
+            .map(|item| Self::set_expanded_in_tree(item, expanded))
+            .collect();
+
+        let mut state = self.flatten_with_items(&items);
+
+        if let Some(index) = cursor_path.and_then(|cursor_path| {
+            state
+                .flat_items
+                .iter()
+                .rposition(|(item, _)| cursor_path.starts_with(item.path()))
+        }) {
+            state.list_state.select(Some(index));
+        }
+
+        state
+    }
+
+    /// Expands every directory in the tree.
+    pub fn expand_all(&self) -> Self {
+        self.with_all_expanded(true)
+    }
+
+    /// Collapses every directory in the tree.
+    pub fn collapse_all(&self) -> Self {
+        self.with_all_expanded(false)
+    }
+
+    fn expand_item_in_tree(item: &Item, identifier: &Path) -> Item {
+        match item {
+            Item::Directory {
+                expanded,
+                path,
+                name,
+                items,
+            } => Item::Directory {
+                name: name.clone(),
+                path: path.clone(),
+                expanded: *expanded || identifier.starts_with(path),
+                items: items
+                    .iter()
+                    .map(|child| Self::expand_item_in_tree(child, identifier))
+                    .collect(),
+            },
+            _ => item.clone(),
+        }
+    }
+
+    fn expanded_paths(items: &[Item]) -> Vec<PathBuf> {
+        items
+            .iter()
+            .flat_map(|item| match item {
+                Item::Directory {
+                    path,
+                    expanded,
+                    items,
+                    ..
+                } => expanded
+                    .then(|| path.clone())
+                    .into_iter()
+                    .chain(Self::expanded_paths(items))
+                    .collect(),
+                _ => vec![],
+            })
+            .collect()
+    }
+
+    fn restore_expanded(item: Item, expanded_paths: &[PathBuf]) -> Item {
+        match item {
+            Item::Directory {
+                name, path, items, ..
+            } => Item::Directory {
+                name,
+                expanded: expanded_paths.contains(&path),
+                path,
+                items: items
+                    .into_iter()
+                    .map(|item| Self::restore_expanded(item, expanded_paths))
+                    .collect(),
+            },
+            _ => item,
+        }
+    }
+
+    /// Replaces the items with the given vault entries, e.g. after the vault was changed on disk.
+    ///
+    /// Directories that were expanded stay expanded.
+    pub fn with_entries(&self, entries: Vec<VaultEntry>) -> Self {
+        let expanded_paths = Self::expanded_paths(&self.items);
+
+        let items: Vec<Item> = entries
+            .into_iter()
+            .map(|entry| Self::restore_expanded(entry.into(), &expanded_paths))
+            .collect();
+
+        self.flatten_with_items(&items)
+    }
+
+    /// Replaces the items after the note at the given path was deleted.
+    ///
+    /// The cursor moves to the next sibling of the deleted note, or to the item above it if the
+    /// note was the last one in its directory. The note is unselected if it was selected.
+    pub fn with_deleted_note(&self, path: &Path, entries: Vec<VaultEntry>) -> Self {
+        let mut state = self.with_entries(entries);
+
+        if let Some(index) = self.list_state.selected() {
+            let depth = self.flat_items.get(index).map(|(_, depth)| *depth);
+
+            let index = match state.flat_items.get(index) {
+                Some((_, next_depth)) if Some(*next_depth) == depth => index,
+                _ => index.saturating_sub(1),
+            };
+
+            state.list_state.select(Some(index));
+        }
+
+        if self.selected_item_path.as_deref() == Some(path) {
+            state.selected_note = None;
+            state.preview = None;
+            state.selected_item_index = None;
+            state.selected_item_path = None;
+        }
+
+        state
+    }
+
+    /// Replaces the items after the note at the given path was renamed, keeping the cursor on the
+    /// renamed note. The renamed note stays selected if it was selected.
+    pub fn with_renamed_note(&self, path: &Path, note: Note, entries: Vec<VaultEntry>) -> Self {
+        let mut state = self.with_entries(entries);
+
+        if let Some(index) = state.flat_items.iter().position(|(item, _)| match item {
+            Item::File(renamed) => renamed.path == note.path,
+            _ => false,
+        }) {
+            state.list_state.select(Some(index));
+
+            if self.selected_item_path.as_deref() == Some(path) {
+                state = state.with_selected_file(index, note);
+            }
+        }
+
+        state
+    }
+
+    /// Moves the note into the destination directory on disk and rebuilds the items from the
+    /// new filesystem state, keeping the cursor on the moved note. The directories leading to
+    /// the moved note are expanded, and the note stays selected if it was selected.
+    ///
+    /// The pending move is cleared. Returns an [`io::ErrorKind::AlreadyExists`] error if the
+    /// destination directory already contains a file with the same name.
+    pub fn move_note(&self, note: &Note, destination_dir: &Path) -> Result<Self, io::Error> {
+        let state = Self {
+            pending_move: None,
+            ..self.clone()
+        };
+
+        let Some(file_name) = note.path.file_name() else {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                format!("Not a file: {}", note.path.display()),
+            ));
+        };
+
+        let path = destination_dir.join(file_name);
+        if path == note.path {
+            return Ok(state);
+        }
+
+        if path.exists() {
+            return Err(io::Error::new(
+                io::ErrorKind::AlreadyExists,
+                format!("File already exists: {}", path.display()),
+            ));
+        }
+
+        fs::rename(&note.path, &path)?;
+
+        let entries = state
+            .items
+            .iter()
+            .map(|item| match item {
+                Item::File(note) => note.path.as_path(),
+                Item::Directory { path, .. } => path.as_path(),
+            })
+            .filter(|path| path.exists())
+            .filter_map(|path| VaultEntry::try_from(path).ok())
+            .collect();
+
+        let items: Vec<Item> = state
+            .items
+            .iter()
+            .map(|item| Self::expand_item_in_tree(item, &path))
+            .collect();
+
+        let moved = Note {
+            name: note.name.clone(),
+            path,
+        };
+
+        Ok(Self { items, ..state }.with_renamed_note(&note.path, moved, entries))
+    }
+
+    /// Marks the note to be moved into the next directory that is opened.
+    pub fn begin_move(&self, note: Option<Note>) -> Self {
+        Self {
+            pending_move: note,
+            ..self.clone()
+        }
+    }
+
+    pub fn with_bookmarks(&self, bookmarks: Vec<PathBuf>) -> Self {
+        Self {
+            bookmarks,
+            ..self.clone()
+        }
+    }
+
+    /// Shows or hides attachments, i.e. files that are not markdown notes.
+    pub fn with_attachments(&self, show: bool) -> Self {
+        Self {
+            hide_attachments: !show,
+            ..self.clone()
+        }
+        .flatten_with_sort(self.sort)
+    }
+
+    pub fn is_bookmarked(&self, path: &Path) -> bool {
+        self.bookmarks.iter().any(|bookmark| bookmark == path)
+    }
+
+    /// Returns the note under the cursor, which is not necessarily the selected note.
+    pub fn note_at_cursor(&self) -> Option<Note> {
+        match self.flat_items.get(self.list_state.selected()?)? {
+            (Item::File(note), _) => Some(note.clone()),
+            _ => None,
+        }
+    }
+
+    /// Returns the path of the directory under the cursor, if the item under the cursor is a
+    /// directory.
+    pub fn directory_at_cursor(&self) -> Option<PathBuf> {
+        match self.flat_items.get(self.list_state.selected()?)? {
+            (Item::Directory { path, .. }, _) => Some(path.clone()),
+            _ => None,
+        }
+    }
+
+    /// Returns the path of the directory that contains the item under the cursor, or the path of
+    /// the item itself if it is a directory.
+    ///
+    /// Returns `None` if the item is at the root of the vault.
+    pub fn selected_directory(&self) -> Option<PathBuf> {
+        let index = self.list_state.selected()?;
+
+        match self.flat_items.get(index)? {
+            (Item::Directory { path, .. }, _) => Some(path.clone()),
+            (Item::File(..), depth) => self.flat_items[..index]
+                .iter()
+                .rev()
+                .find(|(_, parent_depth)| parent_depth + 1 == *depth)
+                .and_then(|(item, _)| match item {
+                    Item::Directory { path, .. } => Some(path.clone()),
+                    _ => None,
+                }),
+        }
+    }
+
+    /// Selects the note at the given path and moves the cursor to it, expanding all directories
+    /// leading to it. The cursor is centered in the view on the next render.
+    ///
+    /// Returns the state unchanged if there is no note with the given path, e.g. when the note
+    /// was created outside basalt and the entries have not been read again.
+    pub fn reveal(&self, path: &Path) -> Self {
+        let items: Vec<Item> = self
+            .items
+            .iter()
+            .map(|item| Self::expand_item_in_tree(item, path))
+            .collect();
+
+        let mut state = self.flatten_with_items(&items);
+
+        let Some((index, note)) =
+            state
+                .flat_items
+                .iter()
+                .enumerate()
+                .find_map(|(index, (item, _))| match item {
+                    Item::File(note) if note.path == path => Some((index, note.clone())),
+                    _ => None,
+                })
+        else {
+            return self.clone();
+        };
+
+        state.list_state.select(Some(index));
+        state.with_selected_file(index, note)
+    }
+
     pub fn select(&self) -> Self {
         let Some(selected_item_index) = self.list_state.selected() else {
             return self.clone();
@@ -243,15 +545,34 @@ impl<'a> ExplorerState<'a> {
 
                 self.flatten_with_items(&items)
             }
-            (Item::File(note), _) => Self {
-                selected_note: Some(note.clone()),
-                selected_item_index: Some(selected_item_index),
-                selected_item_path: Some(note.path.clone()),
-                ..self.clone()
-            },
+            (Item::File(note), _) => self
+                .clone()
+                .with_selected_file(selected_item_index, note.clone()),
         }
     }
 
+    /// Selects the file at the given index of the flattened items. Markdown notes are opened in
+    /// the editor, other files are set as the preview.
+    fn with_selected_file(self, index: usize, note: Note) -> Self {
+        let (selected_note, preview) = match note.kind().is_markdown() {
+            true => (Some(note.clone()), None),
+            false => (None, Some(note.clone())),
+        };
+
+        Self {
+            selected_note,
+            preview,
+            selected_item_index: Some(index),
+            selected_item_path: Some(note.path),
+            ..self
+        }
+    }
+
+    /// Returns the selected file, which is either the selected note or the previewed attachment.
+    pub fn selected_file(&self) -> Option<&Note> {
+        self.selected_note.as_ref().or(self.preview.as_ref())
+    }
+
     pub fn selected_path(&self) -> Option<PathBuf> {
         self.selected_item_path.clone()
     }
@@ -260,6 +581,17 @@ impl<'a> ExplorerState<'a> {
         self.open
     }
 
+    /// Moves the cursor to the item at the given index of the flattened items.
+    ///
+    /// Returns the state unchanged if the index is out of bounds.
+    pub fn select_index(mut self, index: usize) -> Self {
+        if index < self.flat_items.len() {
+            self.list_state.select(Some(index));
+        }
+
+        self
+    }
+
     pub fn next(mut self, amount: usize) -> Self {
         let index = self
             .list_state
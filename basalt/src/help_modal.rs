@@ -9,6 +9,8 @@ use ratatui::{
     },
 };
 
+use crate::config::Theme;
+
 #[derive(Debug, Default, Clone, PartialEq)]
 pub struct HelpModalState {
     pub scrollbar_state: ScrollbarState,
@@ -83,7 +85,16 @@ fn modal_area(area: Rect) -> Rect {
     area
 }
 
-pub struct HelpModal;
+#[derive(Default)]
+pub struct HelpModal {
+    theme: Theme,
+}
+
+impl HelpModal {
+    pub fn with_theme(self, theme: &Theme) -> Self {
+        Self { theme: *theme }
+    }
+}
 
 impl StatefulWidget for HelpModal {
     type State = HelpModalState;
@@ -93,8 +104,8 @@ impl StatefulWidget for HelpModal {
         Self: Sized,
     {
         let block = Block::bordered()
-            .dark_gray()
             .border_type(BorderType::Rounded)
+            .border_style(Style::new().fg(self.theme.border_active))
             .padding(Padding::uniform(1))
             .title_style(Style::default().italic().bold())
             .title(" Help ")
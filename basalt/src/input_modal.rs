@@ -0,0 +1,146 @@
+use ratatui::{
+    buffer::Buffer,
+    layout::{Alignment, Constraint, Flex, Layout, Rect},
+    style::{Style, Stylize},
+    text::Line,
+    widgets::{Block, BorderType, Clear, Paragraph, StatefulWidget, Widget},
+};
+
+use crate::config::Theme;
+
+/// What the value of the input modal is used for once it is confirmed.
+#[derive(Debug, Default, Clone, Copy, PartialEq)]
+pub enum InputKind {
+    #[default]
+    NewNote,
+    RenameNote,
+    /// Asks for a confirmation instead of a value.
+    DeleteNote,
+    /// Asks for the file the open note is exported to.
+    ExportNote,
+}
+
+impl InputKind {
+    fn title(&self) -> &'static str {
+        match self {
+            InputKind::NewNote => "New note",
+            InputKind::RenameNote => "Rename note",
+            InputKind::DeleteNote => "Delete note",
+            InputKind::ExportNote => "Export note",
+        }
+    }
+
+    pub fn is_confirmation(&self) -> bool {
+        matches!(self, InputKind::DeleteNote)
+    }
+}
+
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct InputModalState {
+    pub kind: InputKind,
+    /// Text shown before the value, e.g. the question of a confirmation.
+    pub prompt: String,
+    pub value: String,
+    pub visible: bool,
+}
+
+impl InputModalState {
+    pub fn new(kind: InputKind) -> Self {
+        Self {
+            kind,
+            visible: true,
+            ..Default::default()
+        }
+    }
+
+    pub fn with_prompt(self, prompt: &str) -> Self {
+        Self {
+            prompt: prompt.to_string(),
+            ..self
+        }
+    }
+
+    pub fn with_value(self, value: &str) -> Self {
+        Self {
+            value: value.to_string(),
+            ..self
+        }
+    }
+
+    pub fn value(&self) -> &str {
+        &self.value
+    }
+
+    pub fn input(self, c: char) -> Self {
+        Self {
+            value: self.value + &c.to_string(),
+            ..self
+        }
+    }
+
+    pub fn backspace(mut self) -> Self {
+        self.value.pop();
+        self
+    }
+
+    pub fn hide(self) -> Self {
+        Self {
+            visible: false,
+            ..self
+        }
+    }
+}
+
+fn modal_area(area: Rect) -> Rect {
+    let vertical = Layout::vertical([Constraint::Length(3)]).flex(Flex::Center);
+    let horizontal = Layout::horizontal([Constraint::Length(60)]).flex(Flex::Center);
+    let [area] = vertical.areas(area);
+    let [area] = horizontal.areas(area);
+    area
+}
+
+#[derive(Default)]
+pub struct InputModal {
+    theme: Theme,
+}
+
+impl InputModal {
+    pub fn with_theme(self, theme: &Theme) -> Self {
+        Self { theme: *theme }
+    }
+}
+
+impl StatefulWidget for InputModal {
+    type State = InputModalState;
+
+    fn render(self, area: Rect, buf: &mut Buffer, state: &mut Self::State)
+    where
+        Self: Sized,
+    {
+        let block = Block::bordered()
+            .border_type(BorderType::Rounded)
+            .border_style(Style::new().fg(self.theme.border_active))
+            .title_style(Style::default().italic().bold())
+            .title(format!(" {} ", state.kind.title()))
+            .title(Line::from(" (Esc) ").alignment(Alignment::Right));
+
+        let area = modal_area(area);
+
+        Widget::render(Clear, area, buf);
+        Widget::render(
+            Paragraph::new(Line::from(if state.kind.is_confirmation() {
+                [state.prompt.clone().into(), " ‹y/n›".dark_gray()].to_vec()
+            } else {
+                [
+                    state.prompt.clone().into(),
+                    state.value.clone().into(),
+                    "█".into(),
+                ]
+                .to_vec()
+            }))
+            .block(block),
+            area,
+            buf,
+        );
+    }
+}
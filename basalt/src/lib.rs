@@ -1,12 +1,25 @@
+#![warn(clippy::redundant_clone)]
+
 pub mod app;
+pub mod clipboard;
 pub mod config;
+pub mod confirm_modal;
+pub mod error;
 pub mod explorer;
 pub mod help_modal;
+pub mod input_modal;
 pub mod note_editor;
 pub mod outline;
+pub mod recent_notes_modal;
+pub mod scroll;
+pub mod session;
 pub mod splash;
 pub mod statusbar;
-pub mod stylized_text;
+pub mod tab_bar;
+pub use basalt_widgets::stylized_text;
+pub mod tag_panel;
 pub mod text_counts;
+pub mod toast;
+pub mod transition;
 pub mod vault_selector;
 pub mod vault_selector_modal;
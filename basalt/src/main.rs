@@ -1,18 +1,378 @@
-use std::io;
+use std::{
+    env, fs,
+    io::{self, IsTerminal},
+    path::{self, Path, PathBuf},
+    process::ExitCode,
+};
 
-use basalt_core::obsidian::ObsidianConfig;
-use basalt_tui::app::App;
+use basalt_core::obsidian::{ObsidianConfig, ObsidianUri, Vault};
+use basalt_tui::{app::App, config, error, splash};
+
+/// Command line arguments of basalt.
+#[derive(Debug, Default, PartialEq)]
+struct Args {
+    check_config: bool,
+    list_vaults: bool,
+    /// Path of the note to open on startup.
+    open: Option<PathBuf>,
+    /// Line of the note to move the cursor to on startup, given as `--open <path>:<line>`.
+    line: Option<usize>,
+    /// Name of the vault to open on startup instead of showing the splash screen.
+    vault: Option<String>,
+    /// Obsidian URI of the vault or note to open on startup, e.g.
+    /// `obsidian://open?vault=Work&file=Inbox`.
+    uri: Option<ObsidianUri>,
+    /// Shows a markdown file, or standard input without a path, as a read-only note instead of
+    /// opening a vault.
+    render: bool,
+    /// Path of the markdown file to show with `--render <path>`.
+    render_path: Option<PathBuf>,
+}
+
+/// Parses the command line arguments without the program name. Unknown arguments are ignored.
+fn parse_args(args: impl IntoIterator<Item = String>) -> Result<Args, String> {
+    let mut args = args.into_iter().peekable();
+    let mut parsed = Args::default();
+
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--check-config" => parsed.check_config = true,
+            "--list-vaults" => parsed.list_vaults = true,
+            "--open" => match args.next() {
+                Some(path) => {
+                    let (path, line) = split_line(&path);
+                    parsed.open = Some(PathBuf::from(path));
+                    parsed.line = line;
+                }
+                None => return Err("--open requires a path to a note".to_string()),
+            },
+            "--vault" => match args.next() {
+                Some(name) => parsed.vault = Some(name),
+                None => return Err("--vault requires the name of a vault".to_string()),
+            },
+            "--render" => {
+                parsed.render = true;
+
+                if args.peek().is_some_and(|arg| !arg.starts_with('-')) {
+                    parsed.render_path = args.next().map(PathBuf::from);
+                }
+            }
+            "--stdin" => parsed.render = true,
+            uri if uri.starts_with("obsidian://") => {
+                parsed.uri = Some(uri.parse().map_err(|error| format!("{error}"))?)
+            }
+            _ => {}
+        }
+    }
+
+    Ok(parsed)
+}
+
+/// Splits a trailing `:<line>` from the path. Paths without a line number are returned as is.
+fn split_line(path: &str) -> (&str, Option<usize>) {
+    match path.rsplit_once(':') {
+        Some((note, line)) if !note.is_empty() => match line.parse() {
+            Ok(line) => (note, Some(line)),
+            Err(_) => (path, None),
+        },
+        _ => (path, None),
+    }
+}
+
+/// Formats the vault names and paths as aligned rows, one vault per line.
+fn format_vaults(vaults: &[&Vault]) -> String {
+    let name_width = vaults
+        .iter()
+        .map(|vault| vault.name.chars().count())
+        .max()
+        .unwrap_or_default();
+
+    vaults
+        .iter()
+        .map(|vault| format!("{:name_width$}  {}\n", vault.name, vault.path.display()))
+        .collect()
+}
+
+/// Loads the configuration and reports keys that are bound to multiple commands, global keys
+/// that shadow the bindings of a section and commands that the user bound to multiple keys.
+///
+/// Returns a failing exit code if the configuration cannot be loaded or contains conflicts.
+/// Commands bound to multiple keys are only reported, since they are often intended.
+fn check_config() -> ExitCode {
+    let config = match config::load() {
+        Ok(config) => config,
+        Err(error) => {
+            eprintln!("error: {error}");
+            return ExitCode::FAILURE;
+        }
+    };
+
+    let conflicts = config
+        .conflicts()
+        .into_iter()
+        .chain(config.shadowed_bindings())
+        .collect::<Vec<_>>();
+
+    conflicts
+        .iter()
+        .for_each(|conflict| eprintln!("warning: {conflict}"));
+
+    config
+        .user_duplicate_commands()
+        .iter()
+        .for_each(|(command, bindings)| {
+            eprintln!(
+                "warning: {}",
+                config::format_duplicate_command(command, bindings)
+            )
+        });
+
+    if conflicts.is_empty() {
+        println!("No key binding conflicts found");
+        ExitCode::SUCCESS
+    } else {
+        ExitCode::FAILURE
+    }
+}
+
+/// Reads the markdown file, or standard input without a path, and shows it as a read-only note.
+/// The Obsidian config is not read.
+///
+/// Piped input is read to the end before the app starts. The terminal events are then read from
+/// `/dev/tty`, which crossterm opens when standard input is not a terminal.
+fn render(path: Option<&Path>) -> io::Result<ExitCode> {
+    let (name, content) = match path {
+        Some(path) => match fs::read_to_string(path) {
+            Ok(content) => (path.display().to_string(), content),
+            Err(error) => {
+                eprintln!("error: failed to read {}: {error}", path.display());
+                return Ok(ExitCode::FAILURE);
+            }
+        },
+        None if io::stdin().is_terminal() => {
+            eprintln!("error: --render requires a path to a markdown file or piped input");
+            return Ok(ExitCode::FAILURE);
+        }
+        None => ("stdin".to_string(), io::read_to_string(io::stdin())?),
+    };
+
+    let mut terminal = ratatui::init();
+
+    terminal.show_cursor()?;
+
+    let result = App::start_read_only(terminal, &name, &content);
+
+    ratatui::restore();
+
+    Ok(exit_code(result))
+}
+
+/// Reports the error of the app after the terminal has been restored, so that the message is
+/// not lost with the alternate screen.
+fn exit_code(result: error::Result<()>) -> ExitCode {
+    match result {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(error) => {
+            eprintln!("error: {error}");
+            ExitCode::FAILURE
+        }
+    }
+}
+
+fn main() -> io::Result<ExitCode> {
+    let args = match parse_args(env::args().skip(1)) {
+        Ok(args) => args,
+        Err(error) => {
+            eprintln!("error: {error}");
+            return Ok(ExitCode::FAILURE);
+        }
+    };
+
+    if args.check_config {
+        return Ok(check_config());
+    }
+
+    if args.render {
+        return render(args.render_path.as_deref());
+    }
+
+    let note_path = args.open.map(path::absolute).transpose()?;
+
+    let obsidian_config = ObsidianConfig::load();
+    let vaults = obsidian_config
+        .as_ref()
+        .map(ObsidianConfig::vaults_by_recent);
+
+    if args.list_vaults || args.vault.is_some() || args.uri.is_some() {
+        if let Err(error) = &vaults {
+            eprintln!("error: {}", splash::error_message(error));
+            return Ok(ExitCode::FAILURE);
+        }
+    }
+
+    if args.list_vaults {
+        print!("{}", format_vaults(&vaults.unwrap_or_default()));
+        return Ok(ExitCode::SUCCESS);
+    }
+
+    let vault = match (args.vault, &obsidian_config) {
+        (Some(name), Ok(obsidian_config)) => match obsidian_config.get_vault_by_name(&name) {
+            Some(vault) => Some(vault),
+            None => {
+                eprintln!("error: vault '{name}' was not found. Available vaults:\n");
+                eprint!("{}", format_vaults(&obsidian_config.vaults()));
+                return Ok(ExitCode::FAILURE);
+            }
+        },
+        _ => None,
+    };
+
+    let resolved_uri = match (&args.uri, &obsidian_config) {
+        (Some(uri), Ok(obsidian_config)) => match obsidian_config.resolve_uri(uri) {
+            Some(resolved) => Some(resolved),
+            None => {
+                eprintln!("error: {uri} does not point to a note in any vault");
+                return Ok(ExitCode::FAILURE);
+            }
+        },
+        _ => None,
+    };
+
+    let (vault, note_path) = match &resolved_uri {
+        Some((vault, path)) if *path == vault.path => (Some(vault), note_path),
+        Some((vault, path)) => (Some(vault), Some(path.clone())),
+        None => (vault, note_path),
+    };
 
-fn main() -> io::Result<()> {
     let mut terminal = ratatui::init();
-    let obsidian_config = ObsidianConfig::load().unwrap();
-    let vaults = obsidian_config.vaults();
 
     terminal.show_cursor()?;
 
-    App::start(terminal, vaults)?;
+    let result = App::start(
+        terminal,
+        obsidian_config.as_ref(),
+        note_path.as_deref(),
+        args.line,
+        vault,
+    );
 
     ratatui::restore();
 
-    Ok(())
+    Ok(exit_code(result))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn args(args: &[&str]) -> Result<Args, String> {
+        parse_args(args.iter().map(|arg| arg.to_string()))
+    }
+
+    #[test]
+    fn test_parse_args() {
+        assert_eq!(args(&[]), Ok(Args::default()));
+        assert_eq!(
+            args(&["--vault", "Notes", "--unknown"]),
+            Ok(Args {
+                vault: Some("Notes".to_string()),
+                ..Default::default()
+            })
+        );
+        assert_eq!(
+            args(&["--list-vaults", "--open", "vault/note.md"]),
+            Ok(Args {
+                list_vaults: true,
+                open: Some(PathBuf::from("vault/note.md")),
+                ..Default::default()
+            })
+        );
+        assert_eq!(
+            args(&["--open", "vault/note.md:12"]),
+            Ok(Args {
+                open: Some(PathBuf::from("vault/note.md")),
+                line: Some(12),
+                ..Default::default()
+            })
+        );
+        assert_eq!(
+            args(&["--open", "vault/10:30 meeting.md"]),
+            Ok(Args {
+                open: Some(PathBuf::from("vault/10:30 meeting.md")),
+                ..Default::default()
+            })
+        );
+        assert_eq!(
+            args(&["--render", "notes.md", "--unknown"]),
+            Ok(Args {
+                render: true,
+                render_path: Some(PathBuf::from("notes.md")),
+                ..Default::default()
+            })
+        );
+        assert_eq!(
+            args(&["--render", "--check-config"]),
+            Ok(Args {
+                render: true,
+                check_config: true,
+                ..Default::default()
+            })
+        );
+        assert_eq!(
+            args(&["--stdin"]),
+            Ok(Args {
+                render: true,
+                ..Default::default()
+            })
+        );
+        assert_eq!(
+            args(&["--check-config"]),
+            Ok(Args {
+                check_config: true,
+                ..Default::default()
+            })
+        );
+        assert_eq!(
+            args(&["obsidian://open?vault=My%20Vault&file=Inbox"]),
+            Ok(Args {
+                uri: Some(ObsidianUri::Open {
+                    vault: "My Vault".to_string(),
+                    file: Some("Inbox".to_string()),
+                }),
+                ..Default::default()
+            })
+        );
+        assert_eq!(
+            args(&["obsidian://search?query=x"]),
+            Err("Unsupported Obsidian URI action: search".to_string())
+        );
+        assert_eq!(
+            args(&["--vault"]),
+            Err("--vault requires the name of a vault".to_string())
+        );
+        assert_eq!(
+            args(&["--open"]),
+            Err("--open requires a path to a note".to_string())
+        );
+    }
+
+    #[test]
+    fn test_format_vaults() {
+        let notes = Vault {
+            name: "Notes".into(),
+            path: "/home/user/Notes".into(),
+            ..Default::default()
+        };
+        let work = Vault {
+            name: "Work vault".into(),
+            path: "/home/user/Work".into(),
+            ..Default::default()
+        };
+
+        assert_eq!(format_vaults(&[]), "");
+        assert_eq!(
+            format_vaults(&[&notes, &work]),
+            "Notes       /home/user/Notes\nWork vault  /home/user/Work\n"
+        );
+    }
 }
@@ -1,6 +1,9 @@
 mod editor;
+mod find;
+mod render_cache;
 mod state;
 mod text_buffer;
+mod vim;
 
 /// Provides Markdown parser that supports Obsidian flavor.
 /// Obsidian flavor is a combination of different flavors and a few differences.
@@ -18,5 +21,6 @@ mod text_buffer;
 pub mod markdown_parser;
 
 pub use editor::Editor;
-pub use state::{EditorState, Mode};
+pub use state::{Attachment, EditorState, Mode, Placeholder, ViewPosition};
 pub use text_buffer::TextBuffer;
+pub use vim::VimState;
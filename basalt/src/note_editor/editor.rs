@@ -37,12 +37,12 @@
 //! ┃ society.
 //! ┃
 //! ┃ - Doug Engelbart, 1961
-use std::marker::PhantomData;
+use std::{collections::BTreeMap, marker::PhantomData};
 
 use ratatui::{
     buffer::Buffer,
-    layout::{Offset, Rect},
-    style::{Color, Modifier, Style, Stylize},
+    layout::Rect,
+    style::{Color, Style, Stylize},
     text::{Line, Span},
     widgets::{
         self, Block, BorderType, Clear, Padding, Paragraph, ScrollbarOrientation, StatefulWidget,
@@ -50,43 +50,111 @@ use ratatui::{
     },
 };
 
-use crate::stylized_text::{stylize, FontStyle};
+use basalt_core::markdown::{BlockQuoteKind, ItemKind, Property};
+use basalt_widgets::markdown::render;
+use textwrap::core::display_width;
 
-use super::{markdown_parser, state::Mode};
+use crate::{config::Theme, scroll::calculate_offset};
 
-use super::state::EditorState;
+use super::{
+    find::{find_matches, highlight_line},
+    markdown_parser,
+    state::{Attachment, Mode, Placeholder, RowScroll},
+};
+
+use super::{
+    state::EditorState,
+    text_buffer::{TextBuffer, VisualLine},
+};
 
 #[derive(Clone, Debug, Default, PartialEq)]
-pub struct Editor<'text_buffer>(PhantomData<&'text_buffer ()>);
+pub struct Editor<'text_buffer> {
+    theme: Theme,
+    /// Soft-wraps code lines that are longer than the pane instead of clipping them.
+    wrap_code: bool,
+    /// Colors of custom callout types by the lowercase type. Other custom types use
+    /// [`Theme::callout`].
+    callout_colors: BTreeMap<String, Color>,
+    _lifetime: PhantomData<&'text_buffer ()>,
+}
 
 impl Editor<'_> {
+    /// Returns the width the text buffer is soft-wrapped to when the editor is rendered in the
+    /// area, or `None` in Read mode, which shows the rendered markdown instead of the text buffer,
+    /// and if the area has no room for text, e.g. before the terminal size is known.
+    pub fn wrap_width(area: Rect, mode: Mode) -> Option<usize> {
+        let inner_area = Block::bordered()
+            .padding(Padding::horizontal(1))
+            .inner(area);
+
+        (mode != Mode::Read && inner_area.width > 0).then_some(inner_area.width as usize)
+    }
+
+    pub fn with_theme(self, theme: &Theme) -> Self {
+        Self {
+            theme: *theme,
+            ..self
+        }
+    }
+
+    pub fn with_wrap_code(self, wrap_code: bool) -> Self {
+        Self { wrap_code, ..self }
+    }
+
+    pub fn with_callout_colors(self, callout_colors: BTreeMap<String, Color>) -> Self {
+        Self {
+            callout_colors,
+            ..self
+        }
+    }
+
+    /// Returns the settings of the editor, which the lines in the
+    /// [`RenderCache`](super::render_cache::RenderCache) were rendered
+    /// with.
+    fn settings(&self) -> Editor<'static> {
+        Editor {
+            theme: self.theme,
+            wrap_code: self.wrap_code,
+            callout_colors: self.callout_colors.clone(),
+            _lifetime: PhantomData,
+        }
+    }
+
+    /// Returns the title line of a callout, e.g. `☆ Tip` for `[!tip]`, and the color of its bar.
+    /// Regular block quotes have no title line.
+    ///
+    /// The colors configured for callout types take precedence over the default accent colors.
+    fn callout_title<'a>(
+        &self,
+        kind: Option<&markdown_parser::BlockQuoteKind>,
+        prefix: &Span<'a>,
+    ) -> (Option<Line<'a>>, Color) {
+        let Some(kind) = kind.cloned().map(BlockQuoteKind::from) else {
+            return (None, self.theme.quote_bar);
+        };
+
+        let color = self
+            .callout_colors
+            .get(&kind.label().to_lowercase())
+            .copied()
+            .unwrap_or_else(|| render::callout_color(&kind, &self.theme.markdown()));
+
+        (Some(render::callout_title(&kind, prefix, color)), color)
+    }
+
     fn task<'a>(
-        kind: markdown_parser::TaskListItemKind,
+        &self,
+        kind: &markdown_parser::TaskListItemKind,
         content: Vec<Span<'a>>,
         prefix: Span<'a>,
     ) -> Line<'a> {
-        match kind {
-            markdown_parser::TaskListItemKind::Unchecked => Line::from(
-                [prefix, "□ ".dark_gray()]
-                    .into_iter()
-                    .chain(content)
-                    .collect::<Vec<_>>(),
-            ),
-            markdown_parser::TaskListItemKind::Checked => Line::from(
-                [prefix, "■ ".magenta()]
-                    .into_iter()
-                    .chain(content)
-                    .collect::<Vec<_>>(),
-            )
-            .dark_gray()
-            .add_modifier(Modifier::CROSSED_OUT),
-            markdown_parser::TaskListItemKind::LooselyChecked => Line::from(
-                [prefix, "■ ".magenta()]
-                    .into_iter()
-                    .chain(content)
-                    .collect::<Vec<_>>(),
-            ),
-        }
+        let kind = match kind {
+            markdown_parser::TaskListItemKind::Unchecked => ItemKind::Unchecked,
+            markdown_parser::TaskListItemKind::Checked => ItemKind::HardChecked,
+            markdown_parser::TaskListItemKind::LooselyChecked => ItemKind::Checked,
+        };
+
+        render::task(&kind, content, prefix, &self.theme.markdown())
     }
 
     fn item<'a>(
@@ -95,122 +163,387 @@ impl Editor<'_> {
         prefix: Span<'a>,
     ) -> Line<'a> {
         match kind {
-            markdown_parser::ItemKind::Ordered(num) => Line::from(
-                [prefix, num.to_string().dark_gray(), ". ".into()]
-                    .into_iter()
-                    .chain(content)
-                    .collect::<Vec<_>>(),
-            ),
-            markdown_parser::ItemKind::Unordered => Line::from(
-                [prefix, "- ".dark_gray()]
-                    .into_iter()
-                    .chain(content)
-                    .collect::<Vec<_>>(),
-            ),
+            markdown_parser::ItemKind::Ordered(num) => render::item(Some(num), content, prefix),
+            markdown_parser::ItemKind::Unordered => render::item(None, content, prefix),
         }
     }
 
-    fn text_to_spans<'a>(text: markdown_parser::Text) -> Vec<Span<'a>> {
-        text.into_iter()
-            .map(|text| Span::from(text.content))
+    fn text_to_spans<'a>(text: &markdown_parser::Text) -> Vec<Span<'a>> {
+        text.iter()
+            .map(|text| Span::from(text.content.clone()))
             .collect()
     }
 
-    fn code_block<'a>(text: markdown_parser::Text, width: usize) -> Vec<Line<'a>> {
-        text.into_iter()
-            .flat_map(|text| {
-                text.content
-                    .clone()
-                    .split("\n")
-                    .map(|line| {
-                        format!(
-                            " {} {}",
-                            line,
-                            // We subtract two to take the whitespace into account, which are
-                            // added in the format string.
-                            (line.chars().count()..width - 2)
-                                .map(|_| " ")
-                                .collect::<String>()
-                        )
-                    })
-                    .collect::<Vec<String>>()
+    /// Truncates the text nodes of a single table cell to fit within the given display width
+    /// and pads the result according to the column alignment.
+    ///
+    /// Truncated cells end with `…`.
+    fn table_cell<'a>(
+        &self,
+        text: &markdown_parser::Text,
+        width: usize,
+        alignment: markdown_parser::Alignment,
+    ) -> Vec<Span<'a>> {
+        let content = String::from(text);
+        let truncate = display_width(&content) > width;
+        let max_width = if truncate {
+            width.saturating_sub(1)
+        } else {
+            width
+        };
+
+        let mut used_width = 0;
+        let mut spans = text
+            .iter()
+            .map_while(|node| {
+                let remaining_width = max_width - used_width;
+                if remaining_width == 0 {
+                    return None;
+                }
+
+                let mut content = String::new();
+                for char in node.content.chars() {
+                    let char_width = display_width(char.encode_utf8(&mut [0; 4]));
+                    if display_width(&content) + char_width > remaining_width {
+                        break;
+                    }
+                    content.push(char);
+                }
+
+                used_width += display_width(&content);
+
+                Some(match node.style {
+                    Some(markdown_parser::Style::Code) => {
+                        Span::from(content).bg(self.theme.code_bg)
+                    }
+                    None => Span::from(content),
+                })
             })
-            .map(|text| Line::from(text).bg(Color::Black))
-            .collect()
-    }
+            .collect::<Vec<_>>();
+
+        if truncate {
+            spans.push(Span::from("…"));
+            used_width += 1;
+        }
 
-    fn wrap_with_prefix(text: String, width: usize, prefix: Span) -> Vec<Line> {
-        let options =
-            textwrap::Options::new(width.saturating_sub(prefix.width())).break_words(false);
+        let padding = width.saturating_sub(used_width);
+        let (left, right) = match alignment {
+            markdown_parser::Alignment::Right => (padding, 0),
+            markdown_parser::Alignment::Center => (padding / 2, padding - padding / 2),
+            markdown_parser::Alignment::Left | markdown_parser::Alignment::None => (0, padding),
+        };
 
-        textwrap::wrap(&text, &options)
+        [Span::from(" ".repeat(left))]
             .into_iter()
-            .map(|wrapped_line| {
-                Line::from([prefix.clone(), Span::from(wrapped_line.to_string())].to_vec())
-            })
+            .chain(spans)
+            .chain([Span::from(" ".repeat(right))])
             .collect()
     }
 
-    fn heading<'a>(
-        level: markdown_parser::HeadingLevel,
-        text: String,
+    /// Renders a table with box-drawing borders.
+    ///
+    /// Column widths are computed from the widest cell and capped to the available width. When
+    /// the table does not fit the available width, columns are clipped from the right and the
+    /// rows are marked with `▶`.
+    fn table<'a>(
+        &self,
+        alignments: &[markdown_parser::Alignment],
+        header: &[markdown_parser::Text],
+        rows: &[Vec<markdown_parser::Text>],
         width: usize,
+        prefix: Span<'a>,
     ) -> Vec<Line<'a>> {
-        match level {
-            markdown_parser::HeadingLevel::H1 => [
-                Line::default(),
-                Line::from(text.to_uppercase()).italic().bold(),
-                (0..width).map(|_| "▀").collect::<String>().into(),
-                Line::default(),
-            ]
-            .to_vec(),
-            markdown_parser::HeadingLevel::H2 => [
-                Line::from(text).bold().yellow(),
-                Line::from((0..width).map(|_| "═").collect::<String>()).yellow(),
-            ]
-            .to_vec(),
-            markdown_parser::HeadingLevel::H3 => [
-                Line::from(["⬤  ".into(), text.bold()].to_vec()).cyan(),
-                Line::default(),
-            ]
-            .to_vec(),
-            markdown_parser::HeadingLevel::H4 => [
-                Line::from(["● ".into(), text.bold()].to_vec()).magenta(),
-                Line::default(),
-            ]
-            .to_vec(),
-            markdown_parser::HeadingLevel::H5 => [
-                Line::from(["◆ ".into(), stylize(&text, FontStyle::Script).into()].to_vec()),
+        let available_width = width.saturating_sub(prefix.width());
+        // Reserve space for the side borders, cell padding and the possible clipping marker
+        let max_column_width = available_width.saturating_sub(5).max(1);
+
+        let column_count = [header]
+            .into_iter()
+            .chain(rows.iter().map(Vec::as_slice))
+            .map(|row| row.len())
+            .chain([alignments.len()])
+            .max()
+            .unwrap_or_default();
+
+        let column_widths = (0..column_count)
+            .map(|column| {
+                [header]
+                    .into_iter()
+                    .chain(rows.iter().map(Vec::as_slice))
+                    .filter_map(|row| row.get(column))
+                    .map(|cell| display_width(&String::from(cell)))
+                    .max()
+                    .unwrap_or_default()
+                    .clamp(1, max_column_width)
+            })
+            .collect::<Vec<_>>();
+
+        // The table starts with a left border and each column takes its width, the padding on
+        // both sides and a right border.
+        let table_width = 1 + column_widths.iter().map(|width| width + 3).sum::<usize>();
+        let clipped = table_width > available_width;
+
+        let column_widths = if clipped {
+            let mut table_width = 1;
+            column_widths
+                .into_iter()
+                .take_while(|width| {
+                    table_width += width + 3;
+                    // Take the clipping marker into account
+                    table_width < available_width
+                })
+                .collect::<Vec<_>>()
+        } else {
+            column_widths
+        };
+
+        let border = |left: &str, middle: &str, right: &str| {
+            Line::from(
+                [
+                    prefix.clone(),
+                    format!(
+                        "{left}{}{right}",
+                        column_widths
+                            .iter()
+                            .map(|width| "─".repeat(width + 2))
+                            .collect::<Vec<_>>()
+                            .join(middle)
+                    )
+                    .dark_gray(),
+                ]
+                .to_vec(),
+            )
+        };
+
+        let empty_cell = markdown_parser::Text::default();
+        let row = |cells: &[markdown_parser::Text]| {
+            let mut cells = cells.iter();
+            Line::from(
+                [prefix.clone(), "│".dark_gray()]
+                    .into_iter()
+                    .chain(column_widths.iter().enumerate().flat_map(|(i, width)| {
+                        let alignment = alignments.get(i).copied().unwrap_or_default();
+                        [Span::from(" ")]
+                            .into_iter()
+                            .chain(self.table_cell(
+                                cells.next().unwrap_or(&empty_cell),
+                                *width,
+                                alignment,
+                            ))
+                            .chain([Span::from(" "), "│".dark_gray()])
+                    }))
+                    .chain(clipped.then(|| "▶".dark_gray()))
+                    .collect::<Vec<_>>(),
+            )
+        };
+
+        [
+            border("┌", "┬", "┐"),
+            row(header).bold(),
+            border("├", "┼", "┤"),
+        ]
+        .into_iter()
+        .chain(rows.iter().map(|cells| row(cells)))
+        .chain([border("└", "┴", "┘")])
+        .chain(if prefix.to_string().is_empty() {
+            [Line::default()].to_vec()
+        } else {
+            [].to_vec()
+        })
+        .collect()
+    }
+
+    /// Renders the lines of the text buffer soft-wrapped to the width, so that the edited block
+    /// keeps the same shape as the rendered markdown. The cursor is shown with a reversed style
+    /// and the line of the cursor is underlined.
+    /// Formats the file size in bytes with a binary unit, e.g. `1.5 KiB`.
+    fn file_size(bytes: u64) -> String {
+        const UNITS: [&str; 4] = ["KiB", "MiB", "GiB", "TiB"];
+
+        if bytes < 1024 {
+            return format!("{bytes} B");
+        }
+
+        let mut size = bytes as f64 / 1024.0;
+        let mut unit = UNITS[0];
+        for next_unit in &UNITS[1..] {
+            if size < 1024.0 {
+                break;
+            }
+            size /= 1024.0;
+            unit = next_unit;
+        }
+
+        format!("{size:.1} {unit}")
+    }
+
+    /// Lines of the info panel that is shown instead of the content of an attachment.
+    fn attachment_info<'a>(attachment: &Attachment) -> Vec<Line<'a>> {
+        [
+            Line::from(attachment.name.clone()).bold(),
+            Line::default(),
+            Line::from(["Kind: ".dark_gray(), attachment.kind.to_string().into()].to_vec()),
+            Line::from(
+                [
+                    "Size: ".dark_gray(),
+                    Editor::file_size(attachment.size).into(),
+                ]
+                .to_vec(),
+            ),
+        ]
+        .into_iter()
+        .chain(
+            attachment
+                .details
+                .clone()
+                .map(|details| Line::from(["Details: ".dark_gray(), details.into()].to_vec())),
+        )
+        .chain([
+            Line::default(),
+            Line::from("Press o to open externally")
+                .dark_gray()
+                .italic(),
+        ])
+        .collect()
+    }
+
+    /// Lines of the panel that is shown instead of the content of a note that cannot be opened.
+    fn placeholder_info<'a>(placeholder: &Placeholder) -> Vec<Line<'a>> {
+        let preview = placeholder.preview.iter().flat_map(|preview| {
+            [
+                Line::from("Start of the file:").dark_gray().italic(),
                 Line::default(),
             ]
-            .to_vec(),
-            markdown_parser::HeadingLevel::H6 => [
-                Line::from(["✺ ".into(), stylize(&text, FontStyle::Script).into()].to_vec()),
+            .into_iter()
+            .chain(
+                preview
+                    .lines()
+                    .map(|line| Line::from(line.to_string()).dark_gray()),
+            )
+        });
+
+        [
+            Line::from(placeholder.reason.clone()).bold(),
+            Line::default(),
+        ]
+        .into_iter()
+        .chain(preview)
+        .collect()
+    }
+
+    /// Lines of the properties panel that is shown above the nodes. A collapsed panel shows only
+    /// the number of properties, and a note without properties has no panel.
+    ///
+    /// List values are joined with commas, and values that do not fit within the width are
+    /// truncated with an ellipsis.
+    fn properties_panel<'a>(
+        properties: &[Property],
+        collapsed: bool,
+        width: usize,
+    ) -> Vec<Line<'a>> {
+        if properties.is_empty() {
+            return vec![];
+        }
+
+        if collapsed {
+            return [
+                Line::from(format!("▸ Properties ({})", properties.len()))
+                    .dark_gray()
+                    .italic(),
                 Line::default(),
             ]
-            .to_vec(),
+            .to_vec();
         }
+
+        let key_width = properties
+            .iter()
+            .map(|property| display_width(&property.key))
+            .max()
+            .unwrap_or_default()
+            .min(width / 3);
+
+        let truncate = |text: &str, width: usize| {
+            if display_width(text) <= width {
+                return text.to_string();
+            }
+
+            let mut truncated = String::new();
+            for c in text.chars() {
+                if display_width(&truncated) + display_width(&c.to_string()) >= width {
+                    break;
+                }
+                truncated.push(c);
+            }
+            truncated + "…"
+        };
+
+        [Line::from("▾ Properties").dark_gray().italic()]
+            .into_iter()
+            .chain(properties.iter().map(|property| {
+                let key = truncate(&property.key, key_width);
+                let padding = " ".repeat(key_width - display_width(&key));
+                let value_width = width.saturating_sub(key_width + 4);
+
+                Line::from(vec![
+                    format!("  {key}{padding}  ").dark_gray(),
+                    truncate(&property.values.join(", "), value_width).into(),
+                ])
+            }))
+            .chain([Line::default()])
+            .collect()
+    }
+
+    fn text_buffer_lines<'a>(text_buffer: &TextBuffer, width: u16) -> Vec<Line<'a>> {
+        let width = width as usize;
+        let (cursor_line, cursor_col) = text_buffer.visual_cursor(width);
+
+        text_buffer
+            .visual_lines(width)
+            .into_iter()
+            .enumerate()
+            .map(|(index, VisualLine { row, cols })| {
+                let chars = text_buffer.lines()[row]
+                    .chars()
+                    .skip(cols.start)
+                    .take(cols.len())
+                    .collect::<Vec<_>>();
+
+                if index != cursor_line {
+                    return Line::from(chars.into_iter().collect::<String>());
+                }
+
+                let before = chars[..cursor_col.min(chars.len())]
+                    .iter()
+                    .collect::<String>();
+                let cursor = chars
+                    .get(cursor_col)
+                    .map_or(" ".to_string(), char::to_string);
+                let after = chars
+                    .get(cursor_col + 1..)
+                    .map(|chars| chars.iter().collect::<String>())
+                    .unwrap_or_default();
+
+                Line::from(vec![before.into(), cursor.reversed(), after.into()]).underlined()
+            })
+            .collect()
     }
 
     fn render_markdown<'a>(
+        &self,
         node: &markdown_parser::Node,
         area: Rect,
         prefix: Span<'a>,
     ) -> Vec<Line<'a>> {
-        match node.markdown_node.clone() {
+        match &node.markdown_node {
             markdown_parser::MarkdownNode::Paragraph { text } => {
-                Editor::wrap_with_prefix(text.into(), area.width.into(), prefix.clone())
-                    .into_iter()
-                    .chain(if prefix.to_string().is_empty() {
-                        [Line::default()].to_vec()
-                    } else {
-                        [].to_vec()
-                    })
-                    .collect::<Vec<_>>()
-            }
-            markdown_parser::MarkdownNode::Heading { level, text } => {
-                Editor::heading(level, text.into(), area.width.into())
+                render::paragraph(text.into(), area.width.into(), prefix)
             }
+            markdown_parser::MarkdownNode::Heading { level, text } => render::heading(
+                (*level).into(),
+                text.into(),
+                area.width.into(),
+                &self.theme.markdown(),
+            ),
             markdown_parser::MarkdownNode::Item { text } => [Editor::item(
                 markdown_parser::ItemKind::Unordered,
                 Editor::text_to_spans(text),
@@ -218,80 +551,95 @@ impl Editor<'_> {
             )]
             .to_vec(),
             markdown_parser::MarkdownNode::TaskListItem { kind, text } => {
-                [Editor::task(kind, Editor::text_to_spans(text), prefix)].to_vec()
-            }
-            // TODO: Add lang support and syntax highlighting
-            markdown_parser::MarkdownNode::CodeBlock { text, .. } => {
-                [Line::from((0..area.width).map(|_| " ").collect::<String>()).bg(Color::Black)]
-                    .into_iter()
-                    .chain(Editor::code_block(text, area.width.into()))
-                    .chain([Line::default()])
-                    .collect::<Vec<_>>()
+                [self.task(kind, Editor::text_to_spans(text), prefix)].to_vec()
             }
-            markdown_parser::MarkdownNode::List { nodes, kind } => nodes
-                .into_iter()
-                .enumerate()
-                .flat_map(|(i, child)| match child.markdown_node {
-                    markdown_parser::MarkdownNode::TaskListItem { kind, text } => [Editor::task(
-                        kind,
-                        Editor::text_to_spans(text),
-                        prefix.clone(),
-                    )]
-                    .to_vec(),
-                    markdown_parser::MarkdownNode::Item { text } => {
-                        let item = match kind {
-                            markdown_parser::ListKind::Ordered(start) => Editor::item(
-                                markdown_parser::ItemKind::Ordered(start + i as u64),
-                                Editor::text_to_spans(text),
-                                prefix.clone(),
-                            ),
-                            _ => Editor::item(
-                                markdown_parser::ItemKind::Unordered,
-                                Editor::text_to_spans(text),
-                                prefix.clone(),
-                            ),
-                        };
-
-                        [item].to_vec()
+            markdown_parser::MarkdownNode::CodeBlock { lang, text } => render::code_block(
+                lang.as_deref(),
+                &String::from(text),
+                area.width.into(),
+                self.wrap_code,
+                &self.theme.markdown(),
+            ),
+            markdown_parser::MarkdownNode::List { nodes, kind } => {
+                // Only the items advance the numbering, nested lists and other children do not
+                let numbers = nodes.iter().scan(0, |item_count, child| {
+                    let index = *item_count;
+                    if matches!(
+                        child.markdown_node,
+                        markdown_parser::MarkdownNode::Item { .. }
+                            | markdown_parser::MarkdownNode::TaskListItem { .. }
+                    ) {
+                        *item_count += 1;
                     }
-                    _ => Editor::render_markdown(&child, area, Span::from(format!("  {prefix}"))),
-                })
-                .chain(if prefix.to_string().is_empty() {
-                    [Line::default()].to_vec()
-                } else {
-                    [].to_vec()
-                })
-                .collect::<Vec<Line<'a>>>(),
+                    Some(index)
+                });
+
+                let number = |index: u64| match kind {
+                    markdown_parser::ListKind::Ordered(start) => Some(*start + index),
+                    markdown_parser::ListKind::Unordered => None,
+                };
+
+                nodes
+                    .iter()
+                    .zip(numbers)
+                    .flat_map(|(child, index)| match &child.markdown_node {
+                        markdown_parser::MarkdownNode::TaskListItem { kind, text } => {
+                            let mut task =
+                                self.task(kind, Editor::text_to_spans(text), prefix.clone());
+
+                            if let Some(number) = number(index) {
+                                task.spans
+                                    .splice(1..1, [number.to_string().dark_gray(), ". ".into()]);
+                            }
+
+                            [task].to_vec()
+                        }
+                        markdown_parser::MarkdownNode::Item { text } => [Editor::item(
+                            number(index)
+                                .map(markdown_parser::ItemKind::Ordered)
+                                .unwrap_or(markdown_parser::ItemKind::Unordered),
+                            Editor::text_to_spans(text),
+                            prefix.clone(),
+                        )]
+                        .to_vec(),
+                        // Nested children are indented after the prefix, so that the bars of
+                        // enclosing block quotes stay stacked in front of the indentation.
+                        _ => self.render_markdown(
+                            child,
+                            area,
+                            Span::from(format!("{prefix}  ")).style(prefix.style),
+                        ),
+                    })
+                    .chain(if prefix.to_string().is_empty() {
+                        [Line::default()].to_vec()
+                    } else {
+                        [].to_vec()
+                    })
+                    .collect::<Vec<Line<'a>>>()
+            }
 
-            // TODO: Support callout block quote types
-            markdown_parser::MarkdownNode::BlockQuote { nodes, .. } => nodes
-                .iter()
-                .map(|child| {
-                    // We need this to be a block of lines to make sure we enumarate and add
-                    // prefixed line breaks correctly.
-                    [Editor::render_markdown(
-                        child,
-                        area,
-                        Span::from(prefix.to_string() + "┃ ").magenta(),
-                    )]
-                    .to_vec()
-                })
-                .enumerate()
-                .flat_map(|(i, mut line_blocks)| {
-                    if i != 0 && i != nodes.len() {
-                        line_blocks.insert(
-                            0,
-                            [Line::from(prefix.to_string() + "┃ ").magenta()].to_vec(),
-                        );
-                    }
-                    line_blocks.into_iter().flatten().collect::<Vec<_>>()
-                })
-                .chain(if prefix.to_string().is_empty() {
-                    [Line::default()].to_vec()
-                } else {
-                    [].to_vec()
-                })
-                .collect::<Vec<Line<'a>>>(),
+            markdown_parser::MarkdownNode::Table {
+                alignments,
+                header,
+                rows,
+            } => self.table(alignments, header, rows, area.width.into(), prefix),
+
+            markdown_parser::MarkdownNode::BlockQuote { kind, nodes } => {
+                let (title, bar_color) = self.callout_title(kind.as_ref(), &prefix);
+
+                let children = nodes
+                    .iter()
+                    .map(|child| {
+                        self.render_markdown(
+                            child,
+                            area,
+                            Span::from(prefix.to_string() + "┃ ").fg(bar_color),
+                        )
+                    })
+                    .collect();
+
+                render::block_quote(title, children, &prefix, bar_color)
+            }
         }
     }
 }
@@ -301,9 +649,10 @@ impl<'text_buffer> StatefulWidget for Editor<'text_buffer> {
 
     fn render(self, area: Rect, buf: &mut Buffer, state: &mut Self::State) {
         let mode_color = match state.mode {
-            Mode::View => Color::Blue,
-            Mode::Edit => Color::Green,
-            Mode::Read => Color::Red,
+            Mode::View => self.theme.mode_view,
+            Mode::Edit => self.theme.mode_edit,
+            Mode::Read => self.theme.mode_read,
+            Mode::Normal => self.theme.mode_normal,
         };
         let block = Block::bordered()
             .border_type(if state.active() {
@@ -311,69 +660,117 @@ impl<'text_buffer> StatefulWidget for Editor<'text_buffer> {
             } else {
                 BorderType::Rounded
             })
+            .border_style(if state.active() {
+                Style::new().fg(self.theme.border_active)
+            } else {
+                Style::new()
+            })
             .title_bottom(
                 [
-                    format!(" {}", state.mode).fg(mode_color).bold().italic(),
+                    format!(" {}", state.mode_label())
+                        .fg(mode_color)
+                        .bold()
+                        .italic(),
                     if state.modified {
                         "* ".bold().italic()
                     } else {
                         " ".into()
                     },
                 ]
-                .to_vec(),
+                .into_iter()
+                .chain(
+                    (state.task_stats().total > 0)
+                        .then(|| format!("☑ {} ", state.task_stats()).italic()),
+                )
+                .chain(state.find().is_active().then(|| {
+                    let find = state.find();
+                    format!(
+                        "{}/{} ",
+                        find.current().map_or(0, |current| current + 1),
+                        find.matches().len()
+                    )
+                    .italic()
+                }))
+                .collect::<Vec<_>>(),
             )
             .padding(Padding::horizontal(1));
 
         let inner_area = block.inner(area);
 
+        if let Some(attachment) = state.attachment() {
+            Paragraph::new(Editor::attachment_info(attachment))
+                .block(block)
+                .render(area, buf);
+            return;
+        }
+
+        if let Some(placeholder) = state.placeholder() {
+            Paragraph::new(Editor::placeholder_info(placeholder))
+                .block(block)
+                .render(area, buf);
+            return;
+        }
+
         let nodes = state.nodes();
 
+        let render_cache = state.render_cache();
+        let mut render_cache = render_cache.borrow_mut();
+        render_cache.validate(inner_area.width, self.settings(), nodes.len());
+
+        let render =
+            |node: &markdown_parser::Node| self.render_markdown(node, inner_area, Span::default());
+
         let rendered_nodes: Vec<_> = nodes
             .iter()
             .enumerate()
             .map(|(i, node)| {
-                // TODO: Figure out how to wrap the text while editing / viewing the markdown
-                // blocks.
-                //
-                // The following code is not good, but might act as something that can be
-                // considered.
-                //
-                // let (row, col) = state.text_buffer().cursor();
-                //
-                // let mut ta = TextArea::from(
-                //     wrap_text(state.text_buffer().raw(), inner_area.width as usize).lines(),
-                // );
-                //
-                // let offset_row = col as u16 / inner_area.width;
-                //
-                // ta.move_cursor(tui_textarea::CursorMove::Jump(
-                //     row as u16 + offset_row,
-                //     if offset_row > 0 {
-                //         (col as u16).saturating_sub(inner_area.width * offset_row) as u16
-                //     } else {
-                //         col as u16
-                //     },
-                // ));
+                // Nodes inside a folded section are skipped, and the first one is replaced with a
+                // placeholder. The empty entries keep the rendered nodes aligned with node indices.
+                if let Some(range) = state.collapsed_range_at(i) {
+                    if i != range.start {
+                        return vec![];
+                    }
+
+                    let hidden_lines = range
+                        .clone()
+                        .map(|i| render_cache.lines(i, &nodes[i], render).len())
+                        .sum::<usize>();
+
+                    return [
+                        Line::from(format!("  ▸ ({hidden_lines} lines hidden)"))
+                            .dark_gray()
+                            .italic(),
+                        Line::default(),
+                    ]
+                    .to_vec();
+                }
 
                 match (i == state.current_row, &state.mode) {
                     (true, Mode::Read) => {
                         let (row, _) = state.text_buffer().cursor();
-                        Editor::render_markdown(node, inner_area, Span::default())
-                            .into_iter()
+                        let is_table = matches!(
+                            node.markdown_node,
+                            markdown_parser::MarkdownNode::Table { .. }
+                        );
+                        render_cache
+                            .lines(i, node, render)
+                            .iter()
+                            .cloned()
                             .enumerate()
-                            .map(|(i, line)| if i == row { line.underlined() } else { line })
+                            .map(|(i, line)| {
+                                if i == row || (is_table && !line.spans.is_empty()) {
+                                    line.underlined()
+                                } else {
+                                    line
+                                }
+                            })
                             .collect()
                     }
                     (true, _) => {
-                        let expected_line_count =
-                            Editor::render_markdown(node, inner_area, Span::default()).len();
+                        let expected_line_count = render_cache.lines(i, node, render).len();
 
-                        let mut buffer_lines: Vec<Line> = state
-                            .text_buffer()
-                            .lines()
-                            .iter()
-                            .map(|line| Line::from(line.clone()))
-                            .collect();
+                        let mut buffer_lines =
+                            Editor::text_buffer_lines(state.text_buffer(), inner_area.width);
 
                         if buffer_lines.len() < expected_line_count {
                             buffer_lines.resize(expected_line_count.max(1), Line::default());
@@ -381,65 +778,145 @@ impl<'text_buffer> StatefulWidget for Editor<'text_buffer> {
 
                         buffer_lines
                     }
-                    (false, _) => Editor::render_markdown(node, inner_area, Span::default()),
+                    (false, _) => render_cache.lines(i, node, render).to_vec(),
                 }
             })
             .collect();
 
-        let offset_row = if !rendered_nodes.is_empty() {
-            rendered_nodes[..state.current_row]
-                .iter()
-                .map(|lines| lines.len())
-                .sum::<usize>()
-        } else {
-            0
+        drop(render_cache);
+
+        // The properties panel is part of the rendered lines, so it is counted in the line offsets
+        // of the nodes below it
+        let properties = match state.mode {
+            Mode::Read | Mode::View => Editor::properties_panel(
+                state.properties(),
+                state.properties_collapsed(),
+                inner_area.width as usize,
+            ),
+            Mode::Edit | Mode::Normal => vec![],
         };
 
-        let current_node_height = rendered_nodes
-            .get(state.current_row)
-            .map_or(0, |lines| lines.len() as u16);
+        let find = state.find();
 
-        fn calculate_clipped_rows(offset: i16, pos_y: u16, height: u16, max: u16) -> u16 {
-            if offset < 0 {
-                height.saturating_sub(height.saturating_sub(offset.unsigned_abs()))
-            } else {
-                (pos_y + height).saturating_sub(max)
-            }
-        }
+        // The node index and the occurrence index within the node of the current match
+        let current_match = find.current_match().and_then(|current_match| {
+            let index = nodes
+                .iter()
+                .rposition(|node| node.source_range.start <= current_match.start)?;
+            let occurrence = find
+                .matches()
+                .iter()
+                .filter(|m| {
+                    nodes[index].source_range.start <= m.start && m.start < current_match.start
+                })
+                .count();
+            Some((index, occurrence))
+        });
 
-        let scrollbar = state.scrollbar();
+        let mut current_match_line = None;
+
+        let rendered_nodes: Vec<Vec<Line>> = rendered_nodes
+            .into_iter()
+            .enumerate()
+            .map(|(i, lines)| {
+                if find.query().is_empty() || state.collapsed_range_at(i).is_some() {
+                    return lines;
+                }
 
-        // We take the borders into consideration, thus we add 1, otherwise the calculated
-        // rect would be rendered over the block border.
-        let unsigned_clamped_vertical_offset =
-            (offset_row + 1).saturating_sub(scrollbar.position).max(1) as u16;
+                let mut occurrences = 0;
 
-        let vertical_offset = offset_row as i16 - scrollbar.position as i16;
+                lines
+                    .into_iter()
+                    .enumerate()
+                    .map(|(line_index, line)| {
+                        let line_text = line
+                            .spans
+                            .iter()
+                            .map(|span| span.content.as_ref())
+                            .collect::<String>();
+                        let count = find_matches(&line_text, find.query()).len();
 
-        let max_height = inner_area.bottom();
+                        let current = current_match.and_then(|(index, occurrence)| {
+                            (index == i && (occurrences..occurrences + count).contains(&occurrence))
+                                .then(|| occurrence - occurrences)
+                        });
 
-        // Amount of rows that get clipped
-        let clipped_rows = calculate_clipped_rows(
-            vertical_offset,
-            unsigned_clamped_vertical_offset,
-            current_node_height,
-            max_height,
-        );
+                        if current.is_some() {
+                            current_match_line = Some((i, line_index));
+                        }
 
-        let rect = Rect::new(
-            0,
-            0,
-            inner_area.width,
-            current_node_height.saturating_sub(clipped_rows),
-        )
-        .offset(Offset {
-            x: inner_area.x as i32,
-            y: unsigned_clamped_vertical_offset as i32,
-        })
-        .clamp(inner_area);
+                        occurrences += count;
+                        highlight_line(line, find.query(), current)
+                    })
+                    .collect()
+            })
+            .collect();
+
+        if state.find().scroll_pending {
+            if let Some((index, line_index)) =
+                current_match_line.or_else(|| current_match.map(|(index, _)| (index, 0)))
+            {
+                let target_row = properties.len()
+                    + rendered_nodes[..index]
+                        .iter()
+                        .map(|lines| lines.len())
+                        .sum::<usize>()
+                    + line_index;
+
+                let position = state.scrollbar().position;
+                let height = inner_area.height as usize;
+
+                if target_row < position || target_row >= position + height {
+                    state.scroll_to(target_row.saturating_sub(height / 2));
+                }
+            }
+
+            state.find_scrolled();
+        }
+
+        if let Some(row_scroll) = state.row_scroll() {
+            let offset_row = properties.len()
+                + rendered_nodes
+                    .iter()
+                    .take(state.current_row)
+                    .map(|lines| lines.len())
+                    .sum::<usize>();
+
+            let position = match row_scroll {
+                RowScroll::Top => offset_row,
+                RowScroll::Follow => {
+                    let current_lines = rendered_nodes
+                        .get(state.current_row)
+                        .map_or(0, |lines| lines.len());
+                    // The lines of the current node match the rendered markdown in Read mode and
+                    // the soft-wrapped text buffer otherwise
+                    let (cursor_line, _) = match state.mode {
+                        Mode::Read => state.text_buffer().cursor(),
+                        _ => state.text_buffer().visual_cursor(inner_area.width as usize),
+                    };
+
+                    calculate_offset(
+                        offset_row + cursor_line.min(current_lines.saturating_sub(1)),
+                        properties.len()
+                            + rendered_nodes
+                                .iter()
+                                .map(|lines| lines.len())
+                                .sum::<usize>(),
+                        inner_area.height as usize,
+                    )
+                }
+            };
+
+            state.scroll_to(position);
+            state.row_scrolled();
+        }
 
-        let r = rendered_nodes.into_iter().flatten().collect::<Vec<_>>();
+        let r = properties
+            .into_iter()
+            .chain(rendered_nodes.into_iter().flatten())
+            .collect::<Vec<_>>();
         let r_len = r.len();
+        let scrollbar = state.scrollbar();
         let mut scroll_state = scrollbar.state.content_length(r.len());
 
         let root_node = Paragraph::new(r)
@@ -448,45 +925,24 @@ impl<'text_buffer> StatefulWidget for Editor<'text_buffer> {
 
         Widget::render(root_node, area, buf);
 
-        // TODO: Investigate why crash happens when complete node is rendered
-        if rect.top() < max_height && state.mode != Mode::Read {
-            // Nothing is visible, so we exit early
-            if (vertical_offset < 0 && clipped_rows == 0) || state.mode == Mode::Read {
-                return;
-            }
-
-            let buffer = state.text_buffer_as_mut();
-            let textarea = buffer.textarea_as_mut();
-
-            if vertical_offset > 0 && clipped_rows != 0 {
-                let (row, col) = textarea.cursor();
-                let fixed_scroll = current_node_height.saturating_sub(clipped_rows);
-
-                if (row as u16 + 1) > fixed_scroll {
-                    textarea.set_cursor_style(Style::default());
-                    textarea.set_cursor_line_style(Style::default());
-                    textarea.move_cursor(tui_textarea::CursorMove::Jump(
-                        fixed_scroll.saturating_sub(1),
-                        col as u16,
-                    ));
-                }
-            } else if vertical_offset < 0 && clipped_rows != 0 {
-                let (row, col) = textarea.cursor();
-                let row = row as u16;
-
-                textarea.scroll((clipped_rows as i16, 0));
-
-                if row < clipped_rows && textarea.lines().len() > 1 {
-                    textarea.move_cursor(tui_textarea::CursorMove::Jump(clipped_rows, col as u16));
-                    textarea.set_cursor_style(Style::default());
-                    textarea.set_cursor_line_style(Style::default());
-                } else {
-                    textarea.move_cursor(tui_textarea::CursorMove::Jump(row, col as u16));
-                }
-            }
+        if state.find().is_prompt_open() && inner_area.height > 0 {
+            let prompt_area = Rect::new(
+                inner_area.x,
+                inner_area.bottom().saturating_sub(1),
+                inner_area.width,
+                1,
+            );
 
-            Clear.render(rect, buf);
-            textarea.render(rect, buf);
+            Clear.render(prompt_area, buf);
+            Line::from(
+                [
+                    "/".bold(),
+                    state.find().query().to_string().into(),
+                    "█".into(),
+                ]
+                .to_vec(),
+            )
+            .render(prompt_area, buf);
         }
 
         if r_len as u16 > inner_area.height {
@@ -504,10 +960,11 @@ impl<'text_buffer> StatefulWidget for Editor<'text_buffer> {
 mod tests {
     use super::*;
     use indoc::indoc;
-    use insta::assert_snapshot;
+    use insta::{assert_debug_snapshot, assert_snapshot};
     use ratatui::{
         backend::TestBackend,
         crossterm::event::{KeyCode, KeyEvent, KeyModifiers},
+        style::{Color, Modifier},
         Terminal,
     };
 
@@ -616,6 +1073,22 @@ mod tests {
             }
             ```
             "#},
+            indoc! { r#"## Ordered lists
+
+            An ordered list can start from any number.
+
+            5. Fifth list item
+            6. Sixth list item
+               - Nested list item
+            7. Seventh list item
+            "#},
+            indoc! { r#"## Ordered task lists
+
+            1. First list item
+            2. [ ] Incomplete task
+            3. [x] Completed task
+            4. Fourth list item
+            "#},
         ];
 
         let mut terminal = Terminal::new(TestBackend::new(80, 20)).unwrap();
@@ -635,6 +1108,660 @@ mod tests {
         });
     }
 
+    #[test]
+    fn test_rendered_narrow_code_block() {
+        let content = indoc! { r#"## Code
+
+            ```
+            cd ~/Desktop
+            ```
+            "#};
+
+        (0..=4).for_each(|width| {
+            let mut terminal = Terminal::new(TestBackend::new(width, 10)).unwrap();
+            terminal
+                .draw(|frame| {
+                    Editor::default().with_wrap_code(true).render(
+                        frame.area(),
+                        frame.buffer_mut(),
+                        &mut EditorState::default().set_content(content),
+                    )
+                })
+                .unwrap();
+        });
+    }
+
+    #[test]
+    fn test_rendered_wrapped_code_block() {
+        let content = indoc! { r#"## Code
+
+            ```
+            cargo install basalt-tui --locked --features experimental-editor
+            ls
+            ```
+            "#};
+
+        let tests = [("code_block_wrapped", true), ("code_block_clipped", false)];
+
+        tests.into_iter().for_each(|(name, wrap_code)| {
+            let mut terminal = Terminal::new(TestBackend::new(40, 12)).unwrap();
+            terminal
+                .draw(|frame| {
+                    Editor::default().with_wrap_code(wrap_code).render(
+                        frame.area(),
+                        frame.buffer_mut(),
+                        &mut EditorState::default().set_content(content),
+                    )
+                })
+                .unwrap();
+            assert_snapshot!(name, terminal.backend());
+        });
+    }
+
+    #[test]
+    fn test_rendered_highlighted_code_block() {
+        let content = indoc! { r#"```rust
+            // Greets the world
+            fn main() {
+                println!("Hello");
+            }
+            ```
+
+            ```unknown
+            fn main() {}
+            ```
+            "#};
+
+        let mut terminal = Terminal::new(TestBackend::new(30, 14)).unwrap();
+        terminal
+            .draw(|frame| {
+                Editor::default().render(
+                    frame.area(),
+                    frame.buffer_mut(),
+                    &mut EditorState::default().set_content(content),
+                )
+            })
+            .unwrap();
+
+        assert_debug_snapshot!(terminal.backend().buffer());
+    }
+
+    #[test]
+    fn test_rendered_deep_quotes() {
+        let content = indoc! { r#"## Deep Quotes
+
+            You can have deeper levels of quotes by adding a > symbols before the text inside the block quote.
+
+            > Regular thoughts
+            >
+            > > Deeper thoughts
+            > >
+            > > > Very deep thoughts
+            > > >
+            > > > - Someone on the internet 1996
+            > > >   - Nested in a deep quote
+            >
+            > Back to regular thoughts
+
+            - Item
+              > Quoted in a list
+            "#};
+
+        let mut terminal = Terminal::new(TestBackend::new(50, 24)).unwrap();
+        terminal
+            .draw(|frame| {
+                Editor::default().render(
+                    frame.area(),
+                    frame.buffer_mut(),
+                    &mut EditorState::default().set_content(content),
+                )
+            })
+            .unwrap();
+        assert_snapshot!("deep_quotes", terminal.backend());
+    }
+
+    #[test]
+    fn test_wrap_spans() {
+        let tests = [
+            ("", 4, vec![""]),
+            ("abc", 0, vec!["abc"]),
+            ("abc", 4, vec!["abc"]),
+            ("abcdefghij", 4, vec!["abcd", "efgh", "ij"]),
+            ("日本語です", 4, vec!["日本", "語で", "す"]),
+        ];
+
+        tests.into_iter().for_each(|(line, width, expected)| {
+            let chunks = render::wrap_spans(vec![Span::from(line)], width)
+                .into_iter()
+                .map(|spans| spans.iter().map(|span| span.content.as_ref()).collect())
+                .collect::<Vec<String>>();
+            assert_eq!(chunks, expected)
+        });
+
+        // The styles of the spans are kept when a span is split
+        assert_eq!(
+            render::wrap_spans(vec!["let".red(), " x".into(), " = 1".blue()], 4),
+            vec![
+                vec!["let".red(), " ".into()],
+                vec!["x".into(), " = ".blue()],
+                vec!["1".blue()]
+            ]
+        );
+    }
+
+    #[test]
+    fn test_rendered_table() {
+        let content = indoc! { r#"## Tables
+
+            | Crate | Description | Downloads |
+            |:------|:------------|----------:|
+            | `basalt-core` | Obsidian vault and note handling | 1200 |
+            | `basalt-widgets` | Reusable widgets | 85 |
+            | `basalt-tui` | Terminal user interface for Obsidian notes | 34000 |
+            "#};
+
+        let tests = [
+            (
+                "table_wide",
+                80,
+                EditorState::default().set_content(content),
+            ),
+            (
+                "table_narrow",
+                40,
+                EditorState::default().set_content(content),
+            ),
+        ];
+
+        tests.into_iter().for_each(|(name, width, mut state)| {
+            let mut terminal = Terminal::new(TestBackend::new(width, 14)).unwrap();
+            terminal
+                .draw(|frame| {
+                    Editor::default().render(frame.area(), frame.buffer_mut(), &mut state)
+                })
+                .unwrap();
+            assert_snapshot!(name, terminal.backend());
+        });
+
+        // A cell wider than the editor is truncated with an ellipsis
+        let content = indoc! { r#"
+            | Crate | Downloads |
+            |:------|----------:|
+            | `basalt-tui`, the terminal user interface for Obsidian notes | 34000 |
+            "#};
+
+        let mut terminal = Terminal::new(TestBackend::new(40, 8)).unwrap();
+        let mut state = EditorState::default().set_content(content);
+        terminal
+            .draw(|frame| Editor::default().render(frame.area(), frame.buffer_mut(), &mut state))
+            .unwrap();
+        assert_snapshot!("table_truncated", terminal.backend());
+
+        // The whole table is underlined when it is selected in Read mode
+        let mut terminal = Terminal::new(TestBackend::new(80, 14)).unwrap();
+        let mut state = EditorState::default()
+            .set_content(indoc! { r#"## Tables
+
+                | Crate | Downloads |
+                |:------|----------:|
+                | `basalt-core` | 1200 |
+                "#})
+            .cursor_down()
+            .set_mode(Mode::Read);
+        terminal
+            .draw(|frame| Editor::default().render(frame.area(), frame.buffer_mut(), &mut state))
+            .unwrap();
+
+        let buffer = terminal.backend().buffer();
+        let is_underlined = |y: u16| {
+            (0..buffer.area.width).any(|x| buffer[(x, y)].modifier.contains(Modifier::UNDERLINED))
+        };
+        assert!(!is_underlined(1));
+        assert!((3..=7).all(is_underlined));
+        assert!(!is_underlined(8));
+    }
+
+    #[test]
+    fn test_rendered_wrapped_edit_mode() {
+        let content = indoc! { r#"## Wrapping
+
+            A paragraph that is long enough to be wrapped over several lines when rendered in a narrow editor.
+
+            Next paragraph
+            "#};
+
+        let mut terminal = Terminal::new(TestBackend::new(40, 12)).unwrap();
+        let mut state = EditorState::default().set_content(content).cursor_down();
+
+        let mut render = |name: &str, state: &mut EditorState| {
+            _ = terminal.clear();
+            terminal
+                .draw(|frame| Editor::default().render(frame.area(), frame.buffer_mut(), state))
+                .unwrap();
+            assert_snapshot!(name, terminal.backend());
+        };
+
+        render("wrapped_read_mode", &mut state);
+
+        let mut state = state.set_mode(Mode::Edit);
+        state = state.with_wrap_width(Editor::wrap_width(Rect::new(0, 0, 40, 12), Mode::Edit));
+        render("wrapped_edit_mode", &mut state);
+
+        // Moving down stays within the paragraph and moves to the next visual line
+        let mut state = state.cursor_down();
+        assert_eq!(state.current_row, 1);
+        assert_eq!(state.text_buffer().cursor(), (0, 35));
+        render("wrapped_edit_mode_cursor_down", &mut state);
+    }
+
+    #[test]
+    fn test_rendered_folded_sections() {
+        let content = indoc! { r#"## First section
+
+            Paragraph in the first section.
+
+            - First list item
+            - Second list item
+
+            ### Subsection
+
+            Paragraph in the subsection.
+
+            ## Second section
+
+            Paragraph in the second section.
+            "#};
+
+        let tests = [
+            (
+                "fold_first_section",
+                EditorState::default()
+                    .set_content(content)
+                    .toggle_fold_at(0),
+            ),
+            (
+                "fold_subsection",
+                EditorState::default()
+                    .set_content(content)
+                    .toggle_fold_at(3),
+            ),
+            (
+                "fold_and_unfold_first_section",
+                EditorState::default()
+                    .set_content(content)
+                    .toggle_fold_at(0)
+                    .toggle_fold_at(0),
+            ),
+            (
+                "fold_subsection_and_first_section",
+                EditorState::default()
+                    .set_content(content)
+                    .toggle_fold_at(3)
+                    .toggle_fold_at(0),
+            ),
+            (
+                "fold_all_sections",
+                EditorState::default().set_content(content).fold_all(),
+            ),
+            (
+                "fold_all_and_unfold_first_section",
+                EditorState::default()
+                    .set_content(content)
+                    .fold_all()
+                    .toggle_fold_at(0),
+            ),
+            (
+                "fold_all_and_unfold_all",
+                EditorState::default()
+                    .set_content(content)
+                    .fold_all()
+                    .unfold_all(),
+            ),
+        ];
+
+        let mut terminal = Terminal::new(TestBackend::new(80, 20)).unwrap();
+
+        tests.into_iter().for_each(|(name, mut state)| {
+            _ = terminal.clear();
+            terminal
+                .draw(|frame| {
+                    Editor::default().render(frame.area(), frame.buffer_mut(), &mut state)
+                })
+                .unwrap();
+            assert_snapshot!(name, terminal.backend());
+        });
+    }
+
+    #[test]
+    fn test_rendered_properties() {
+        let content = indoc! { r#"---
+            tags: [daily, work, basalt]
+            aliases:
+              - Today
+            created: 2024-01-01
+            description: A property value that is too long to fit within the width of the editor
+            ---
+            ## Notes
+
+            Paragraph after the properties.
+            "#};
+
+        let tests = [
+            (
+                "properties_read",
+                EditorState::default().set_content(content),
+            ),
+            (
+                "properties_collapsed",
+                EditorState::default()
+                    .set_content(content)
+                    .toggle_properties(),
+            ),
+            (
+                "properties_edit",
+                EditorState::default()
+                    .set_content(content)
+                    .set_mode(Mode::Edit),
+            ),
+        ];
+
+        let mut terminal = Terminal::new(TestBackend::new(60, 14)).unwrap();
+
+        tests.into_iter().for_each(|(name, mut state)| {
+            _ = terminal.clear();
+            terminal
+                .draw(|frame| {
+                    Editor::default().render(frame.area(), frame.buffer_mut(), &mut state)
+                })
+                .unwrap();
+            assert_snapshot!(name, terminal.backend());
+        });
+    }
+
+    #[test]
+    fn test_properties_scroll_offsets() {
+        let content = format!(
+            "---\ntags: [daily]\ncreated: 2024-01-01\n---\n{}",
+            (1..=20)
+                .map(|i| format!("Paragraph {i}.\n\n"))
+                .collect::<String>()
+        );
+
+        let mut terminal = Terminal::new(TestBackend::new(50, 12)).unwrap();
+        let mut render = |state: &mut EditorState| {
+            terminal
+                .draw(|frame| Editor::default().render(frame.area(), frame.buffer_mut(), state))
+                .unwrap();
+            state.scrollbar().position
+        };
+
+        // The frontmatter is not a node, so the cursor starts on the first paragraph and never
+        // moves onto the properties
+        let state = EditorState::default().set_content(&content);
+        assert_eq!(state.nodes().len(), 20);
+        assert_eq!(state.clone().cursor_up().current_row, 0);
+
+        // Each paragraph is rendered as two lines below the four lines of the panel
+        assert_eq!(render(&mut state.clone().reveal_row(5)), 4 + 5 * 2);
+        assert_eq!(
+            render(&mut state.clone().toggle_properties().reveal_row(5)),
+            2 + 5 * 2
+        );
+        assert_eq!(
+            render(&mut state.clone().set_mode(Mode::View).reveal_row(5)),
+            4 + 5 * 2
+        );
+
+        // The panel is part of the content length, so the cursor is followed to the last line
+        let mut state = (1..20).fold(state, |state, _| state.cursor_down());
+        assert_eq!(render(&mut state), 4 + 20 * 2 - 10);
+    }
+
+    #[test]
+    fn test_rendered_folded_scrollbar() {
+        let section = |title: &str| {
+            format!(
+                "## {title}\n\n{}",
+                (1..=4)
+                    .map(|i| format!("Paragraph {i} of {title}.\n\n"))
+                    .collect::<String>()
+            )
+        };
+        let content = ["First", "Second", "Third"].map(section).concat();
+
+        let tests = [
+            (
+                "scrollbar_unfolded",
+                EditorState::default().set_content(&content),
+            ),
+            (
+                "scrollbar_fold_all",
+                EditorState::default().set_content(&content).fold_all(),
+            ),
+            (
+                "scrollbar_fold_scrolled",
+                EditorState::default()
+                    .set_content(&content)
+                    .scroll_down(10)
+                    .toggle_fold_at(5),
+            ),
+        ];
+
+        let mut terminal = Terminal::new(TestBackend::new(50, 16)).unwrap();
+
+        tests.into_iter().for_each(|(name, mut state)| {
+            _ = terminal.clear();
+            terminal
+                .draw(|frame| {
+                    Editor::default().render(frame.area(), frame.buffer_mut(), &mut state)
+                })
+                .unwrap();
+            assert_snapshot!(name, terminal.backend());
+        });
+    }
+
+    #[test]
+    fn test_reveal_row_scrolls_to_row() {
+        let section = |title: &str| {
+            format!(
+                "## {title}\n\n{}",
+                (1..=4)
+                    .map(|i| format!("Paragraph {i} of {title}.\n\n"))
+                    .collect::<String>()
+            )
+        };
+        let content = ["First", "Second", "Third"].map(section).concat();
+
+        let tests = [
+            (
+                EditorState::default().set_content(&content).reveal_row(5),
+                5,
+                10,
+            ),
+            (
+                EditorState::default().set_content(&content).reveal_row(10),
+                10,
+                20,
+            ),
+            // Revealing a row in a folded section unfolds only that section
+            (
+                EditorState::default()
+                    .set_content(&content)
+                    .fold_all()
+                    .reveal_row(7),
+                7,
+                8,
+            ),
+        ];
+
+        let mut terminal = Terminal::new(TestBackend::new(50, 16)).unwrap();
+
+        tests
+            .into_iter()
+            .for_each(|(mut state, expected_row, expected_position)| {
+                terminal
+                    .draw(|frame| {
+                        Editor::default().render(frame.area(), frame.buffer_mut(), &mut state)
+                    })
+                    .unwrap();
+
+                assert_eq!(state.current_row, expected_row);
+                assert_eq!(state.scrollbar().position, expected_position);
+            });
+    }
+
+    #[test]
+    fn test_scroll_follows_cursor() {
+        let content = (1..=30)
+            .map(|i| format!("Paragraph {i}.\n\n"))
+            .collect::<String>();
+
+        let mut terminal = Terminal::new(TestBackend::new(50, 12)).unwrap();
+        let height = 10;
+
+        let mut render = |state: &mut EditorState| {
+            terminal
+                .draw(|frame| Editor::default().render(frame.area(), frame.buffer_mut(), state))
+                .unwrap();
+            state.scrollbar().position
+        };
+
+        let mut state = EditorState::default().set_content(&content);
+        let mut positions = vec![render(&mut state)];
+
+        (1..30).for_each(|_| {
+            state = state.clone().cursor_down();
+            positions.push(render(&mut state));
+
+            // Each paragraph is rendered as two lines
+            let cursor_line = state.current_row * 2;
+            let position = *positions.last().unwrap();
+            assert!((position..position + height).contains(&cursor_line));
+        });
+
+        assert!(positions
+            .windows(2)
+            .all(|pair| pair[0] <= pair[1] && pair[1] - pair[0] <= 2));
+        assert_eq!(positions[..3], [0; 3]);
+        // The view stops scrolling when the end of the note is visible
+        assert_eq!(positions.last(), Some(&(30 * 2 - height)));
+
+        // Scrolling the view does not move the cursor, but the next cursor movement brings the
+        // cursor back into the view
+        let mut state = state.scroll_up(40);
+        assert_eq!(render(&mut state), 10);
+        let mut state = state.cursor_up();
+        assert_eq!(render(&mut state), 50);
+    }
+
+    #[test]
+    fn test_fold_all_moves_cursor() {
+        let content = indoc! { r#"# Note
+
+            ## First section
+
+            Paragraph in the first section.
+
+            ### Subsection
+            "#};
+
+        let state = EditorState::default().set_content(content);
+        let state = (0..2).fold(state, |state, _| state.cursor_down());
+        assert_eq!(state.current_row, 2);
+
+        let state = state.fold_all();
+        assert_eq!(state.current_row, 0);
+        assert_eq!(state.collapsed_ranges(), [1..4, 2..4]);
+        assert_eq!(
+            state.unfold_all().collapsed_ranges(),
+            [] as [std::ops::Range<usize>; 0]
+        );
+    }
+
+    #[test]
+    fn test_cursor_skips_folded_sections() {
+        let content = indoc! { r#"## First section
+
+            Paragraph in the first section.
+
+            ## Second section
+            "#};
+
+        let state = EditorState::default()
+            .set_content(content)
+            .toggle_fold_at(0)
+            .cursor_down();
+        assert_eq!(state.current_row, 2);
+
+        let state = state.cursor_up();
+        assert_eq!(state.current_row, 0);
+
+        let state = state.toggle_fold_at(0).cursor_down();
+        assert_eq!(state.current_row, 1);
+    }
+
+    #[test]
+    fn test_rendered_find() {
+        let content = indoc! { r#"## Find
+
+            First paragraph mentions basalt.
+
+            - Basalt is a rock
+            - Lava cools into basalt
+
+            Last paragraph.
+            "#};
+
+        let long_content = (1..=30)
+            .map(|i| format!("Paragraph {i}"))
+            .chain(["Needle in the haystack".to_string()])
+            .collect::<Vec<_>>()
+            .join("\n\n");
+
+        let find = |state: EditorState<'static>, query: &str| {
+            query
+                .chars()
+                .fold(state.find_start(), |state, c| state.find_input(c))
+        };
+
+        let tests = [
+            (
+                "find_prompt_open",
+                find(EditorState::default().set_content(content), "basalt"),
+            ),
+            (
+                "find_confirmed",
+                find(EditorState::default().set_content(content), "basalt").find_confirm(),
+            ),
+            (
+                "find_smart_case",
+                find(EditorState::default().set_content(content), "Basalt").find_confirm(),
+            ),
+            (
+                "find_previous_wraps_around",
+                find(EditorState::default().set_content(content), "basalt")
+                    .find_confirm()
+                    .find_previous(),
+            ),
+            (
+                "find_scrolls_match_into_view",
+                find(EditorState::default().set_content(&long_content), "needle").find_confirm(),
+            ),
+        ];
+
+        let mut terminal = Terminal::new(TestBackend::new(80, 20)).unwrap();
+
+        tests.into_iter().for_each(|(name, mut state)| {
+            _ = terminal.clear();
+            terminal
+                .draw(|frame| {
+                    Editor::default().render(frame.area(), frame.buffer_mut(), &mut state)
+                })
+                .unwrap();
+            assert_snapshot!(name, terminal.backend());
+        });
+    }
+
     #[test]
     fn test_rendered_editor_states() {
         let content = indoc! { r#"## Deep Quotes
@@ -727,4 +1854,209 @@ mod tests {
             assert_snapshot!(name, terminal.backend());
         });
     }
+
+    #[test]
+    fn test_rendered_task_stats() {
+        let content = indoc! { r#"## Tasks
+
+            - [x] Done
+            - [ ] Todo
+              - [?] Loosely done
+
+            > - [ ] Quoted task
+            "#};
+
+        let mut terminal = Terminal::new(TestBackend::new(40, 10)).unwrap();
+        let mut render = |state: &mut EditorState| {
+            terminal
+                .draw(|frame| Editor::default().render(frame.area(), frame.buffer_mut(), state))
+                .unwrap();
+            terminal.backend().to_string()
+        };
+
+        let mut state = EditorState::default().set_content(content);
+        assert_snapshot!(render(&mut state));
+
+        // The stats follow the toggled tasks
+        let mut state = state.set_row(1);
+        state.update_text_buffer();
+        let mut state = state.toggle_task();
+        assert!(render(&mut state).contains("☑ 1/4"));
+
+        // Notes without tasks have no indicator
+        let mut state = EditorState::default().set_content("- Item");
+        assert!(!render(&mut state).contains("☑"));
+    }
+
+    #[test]
+    fn test_rendered_custom_callouts() {
+        let content = indoc! { r#"> [!bug]-
+            > Crash on start
+
+            > [!example]
+            > Usage
+            "#};
+
+        let mut state = EditorState::default().set_content(content);
+        let mut terminal = Terminal::new(TestBackend::new(30, 8)).unwrap();
+        terminal
+            .draw(|frame| {
+                Editor::default()
+                    .with_callout_colors(BTreeMap::from([("bug".to_string(), Color::Red)]))
+                    .render(frame.area(), frame.buffer_mut(), &mut state)
+            })
+            .unwrap();
+
+        assert_debug_snapshot!(terminal.backend().buffer());
+    }
+
+    #[test]
+    fn test_rendered_callout_kinds() {
+        let tests = [
+            ("note", "ⓘ Note"),
+            ("tip", "☆ Tip"),
+            ("important", "‼ Important"),
+            ("warning", "⚠ Warning"),
+            ("caution", "⊗ Caution"),
+            ("bug", "● Bug"),
+        ];
+
+        let mut terminal = Terminal::new(TestBackend::new(30, 6)).unwrap();
+
+        tests.into_iter().for_each(|(marker, title)| {
+            let content = format!("> [!{marker}]\n> Callout text\n");
+            let mut state = EditorState::default().set_content(&content);
+
+            _ = terminal.clear();
+            terminal
+                .draw(|frame| {
+                    Editor::default().render(frame.area(), frame.buffer_mut(), &mut state)
+                })
+                .unwrap();
+
+            let rendered = terminal.backend().to_string();
+            assert!(rendered.contains(title), "{marker}:\n{rendered}");
+            assert!(!rendered.contains("[!"), "{marker}:\n{rendered}");
+        });
+
+        // The symbol follows the parsed kind, not the text of the quote
+        let mut state =
+            EditorState::default().set_content("> [!warning]\n> [!tip] is not a marker\n");
+        terminal
+            .draw(|frame| Editor::default().render(frame.area(), frame.buffer_mut(), &mut state))
+            .unwrap();
+
+        let rendered = terminal.backend().to_string();
+        assert!(rendered.contains("⚠ Warning"), "{rendered}");
+        assert!(!rendered.contains("☆"), "{rendered}");
+    }
+
+    #[test]
+    fn test_rendered_custom_theme() {
+        let content = indoc! { r#"## Theme
+
+            > Quote
+
+            - [x] Done
+
+            ```
+            code
+            ```
+            "#};
+
+        let theme = Theme {
+            heading2: Color::Rgb(215, 95, 135),
+            quote_bar: Color::Green,
+            task_done: Color::Indexed(208),
+            code_bg: Color::Blue,
+            mode_read: Color::LightCyan,
+            ..Default::default()
+        };
+
+        let mut state = EditorState::default().set_content(content);
+        let mut terminal = Terminal::new(TestBackend::new(30, 12)).unwrap();
+        terminal
+            .draw(|frame| {
+                Editor::default().with_theme(&theme).render(
+                    frame.area(),
+                    frame.buffer_mut(),
+                    &mut state,
+                )
+            })
+            .unwrap();
+
+        assert_debug_snapshot!(terminal.backend().buffer());
+    }
+
+    #[test]
+    fn test_render_nodes_matches_editor() {
+        let tests = [
+            indoc! { r#"# Heading 1
+
+            ## Heading 2
+
+            ### Heading 3
+
+            #### Heading 4
+
+            ##### Heading 5
+
+            ###### Heading 6
+            "#},
+            indoc! { r#"A paragraph that is long enough to be wrapped at word boundaries.
+
+            Another paragraph.
+            "#},
+            indoc! { r#"```rust
+            fn main() {}
+            ```
+
+            ```
+            plain code
+            ```
+            "#},
+            indoc! { r#"> Human beings face ever more complex and urgent problems.
+            >
+            > - Doug Engelbart, 1961
+
+            > [!bug]
+            > Crash on start
+
+            > [!tip]
+            > Callouts have symbols
+            "#},
+            indoc! { r#"- Item
+              - Nested item
+            - [ ] Task
+            - [x] Done
+
+            > Quoted list
+            > - Item
+            "#},
+        ];
+
+        let theme = Theme::default();
+        let area = Rect::new(0, 0, 30, 40);
+
+        tests.iter().for_each(|text| {
+            let editor_lines = markdown_parser::from_str(text)
+                .iter()
+                .flat_map(|node| {
+                    Editor::default().with_theme(&theme).render_markdown(
+                        node,
+                        area,
+                        Span::default(),
+                    )
+                })
+                .collect::<Vec<_>>();
+
+            let shared_lines = render::render_nodes(
+                &basalt_core::markdown::from_str(text),
+                area.width.into(),
+                &theme.markdown(),
+            );
+
+            assert_eq!(editor_lines, shared_lines, "{text}");
+        });
+    }
 }
@@ -0,0 +1,245 @@
+use std::ops::Range;
+
+use ratatui::{
+    style::Stylize,
+    text::{Line, Span},
+};
+
+/// State of the in-note search.
+///
+/// Matches are stored as byte ranges into the note content. The search is case-insensitive,
+/// unless the query contains an uppercase character (smart-case).
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct FindState {
+    query: String,
+    prompt_open: bool,
+    matches: Vec<Range<usize>>,
+    current: Option<usize>,
+    pub(crate) scroll_pending: bool,
+}
+
+impl FindState {
+    pub fn open() -> Self {
+        Self {
+            prompt_open: true,
+            ..Default::default()
+        }
+    }
+
+    pub fn query(&self) -> &str {
+        &self.query
+    }
+
+    pub fn is_prompt_open(&self) -> bool {
+        self.prompt_open
+    }
+
+    /// Returns `true` if the prompt is open or there is a query with highlighted matches.
+    pub fn is_active(&self) -> bool {
+        self.prompt_open || !self.query.is_empty()
+    }
+
+    pub fn matches(&self) -> &[Range<usize>] {
+        &self.matches
+    }
+
+    pub fn current(&self) -> Option<usize> {
+        self.current
+    }
+
+    pub fn current_match(&self) -> Option<&Range<usize>> {
+        self.current.and_then(|index| self.matches.get(index))
+    }
+
+    pub fn push(mut self, c: char, content: &str) -> Self {
+        self.query.push(c);
+        self.update_matches(content)
+    }
+
+    pub fn pop(mut self, content: &str) -> Self {
+        self.query.pop();
+        self.update_matches(content)
+    }
+
+    /// Closes the prompt and jumps to the first match.
+    pub fn confirm(mut self) -> Self {
+        self.prompt_open = false;
+        self.current = (!self.matches.is_empty()).then_some(0);
+        self.scroll_pending = self.current.is_some();
+        self
+    }
+
+    pub fn next(mut self) -> Self {
+        if !self.matches.is_empty() {
+            self.current = Some(
+                self.current
+                    .map_or(0, |current| (current + 1) % self.matches.len()),
+            );
+            self.scroll_pending = true;
+        }
+        self
+    }
+
+    pub fn previous(mut self) -> Self {
+        if !self.matches.is_empty() {
+            let len = self.matches.len();
+            self.current = Some(
+                self.current
+                    .map_or(len - 1, |current| (current + len - 1) % len),
+            );
+            self.scroll_pending = true;
+        }
+        self
+    }
+
+    /// Recomputes the matches, for example, after the content has been modified.
+    pub fn update_matches(mut self, content: &str) -> Self {
+        self.matches = find_matches(content, &self.query);
+        self.current = self.current.filter(|current| *current < self.matches.len());
+        self
+    }
+}
+
+fn is_case_sensitive(query: &str) -> bool {
+    query.chars().any(char::is_uppercase)
+}
+
+fn chars_eq(a: char, b: char, case_sensitive: bool) -> bool {
+    if case_sensitive {
+        a == b
+    } else {
+        a == b || a.to_lowercase().eq(b.to_lowercase())
+    }
+}
+
+/// Finds all non-overlapping occurrences of the query in the text and returns their byte ranges.
+///
+/// The search is case-insensitive, unless the query contains an uppercase character.
+pub fn find_matches(text: &str, query: &str) -> Vec<Range<usize>> {
+    if query.is_empty() {
+        return vec![];
+    }
+
+    let case_sensitive = is_case_sensitive(query);
+    let mut matches: Vec<Range<usize>> = vec![];
+
+    for (start, _) in text.char_indices() {
+        if matches.last().is_some_and(|last| start < last.end) {
+            continue;
+        }
+
+        let mut text_chars = text[start..].char_indices();
+        let is_match = query.chars().all(|query_char| {
+            text_chars
+                .next()
+                .is_some_and(|(_, c)| chars_eq(c, query_char, case_sensitive))
+        });
+
+        if is_match {
+            let end = text_chars
+                .next()
+                .map_or(text.len(), |(offset, _)| start + offset);
+            matches.push(start..end);
+        }
+    }
+
+    matches
+}
+
+/// Highlights the occurrences of the query in the rendered line with a reversed style.
+///
+/// The occurrence with the index `current` is additionally highlighted with a yellow color.
+pub fn highlight_line<'a>(line: Line<'a>, query: &str, current: Option<usize>) -> Line<'a> {
+    let text = line
+        .spans
+        .iter()
+        .map(|span| span.content.as_ref())
+        .collect::<String>();
+
+    let matches = find_matches(&text, query);
+
+    if matches.is_empty() {
+        return line;
+    }
+
+    let mut offset = 0;
+    let spans = line
+        .spans
+        .iter()
+        .flat_map(|span| {
+            let span_range = offset..offset + span.content.len();
+            offset = span_range.end;
+
+            let mut boundaries = vec![span_range.start, span_range.end];
+            boundaries.extend(
+                matches
+                    .iter()
+                    .flat_map(|m| [m.start, m.end])
+                    .filter(|boundary| span_range.contains(boundary)),
+            );
+            boundaries.sort_unstable();
+            boundaries.dedup();
+
+            boundaries
+                .windows(2)
+                .map(|window| {
+                    let content = span.content
+                        [window[0] - span_range.start..window[1] - span_range.start]
+                        .to_string();
+                    let segment = Span::styled(content, span.style);
+
+                    match matches.iter().position(|m| m.contains(&window[0])) {
+                        Some(index) if Some(index) == current => segment.reversed().yellow(),
+                        Some(_) => segment.reversed(),
+                        None => segment,
+                    }
+                })
+                .collect::<Vec<_>>()
+        })
+        .collect::<Vec<_>>();
+
+    Line { spans, ..line }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_find_matches() {
+        let tests = [
+            ("", "foo", vec![]),
+            ("foo", "", vec![]),
+            ("Foo bar foo", "foo", vec![0..3, 8..11]),
+            ("Foo bar foo Foo", "Foo", vec![0..3, 12..15]),
+            ("aaaa", "aa", vec![0..2, 2..4]),
+            ("Ärrä ärrä", "ärrä", vec![0..6, 7..13]),
+        ];
+
+        tests.into_iter().for_each(|(text, query, expected)| {
+            assert_eq!(find_matches(text, query), expected, "{text} / {query}")
+        });
+    }
+
+    #[test]
+    fn test_find_state_cycles_matches() {
+        let content = "one two one two one";
+
+        let state = "one"
+            .chars()
+            .fold(FindState::open(), |state, c| state.push(c, content));
+
+        assert_eq!(state.matches().len(), 3);
+        assert_eq!(state.current(), None);
+
+        let state = state.confirm();
+        assert!(!state.is_prompt_open());
+        assert_eq!(state.current_match(), Some(&(0..3)));
+
+        let state = state.next().next().next();
+        assert_eq!(state.current(), Some(0));
+
+        let state = state.previous();
+        assert_eq!(state.current(), Some(2));
+    }
+}
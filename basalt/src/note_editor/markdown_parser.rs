@@ -25,12 +25,14 @@
 //!       text: Text::from("My Heading"),
 //!     },
 //!     source_range: Range { start: 0, end: 13 },
+//!     depth: 0,
 //!   },
 //!   Node {
 //!     markdown_node: MarkdownNode::Paragraph {
 //!       text: Text::from("Some text."),
 //!     },
-//!     source_range: Range { start: 14, end: 24 }
+//!     source_range: Range { start: 14, end: 24 },
+//!     depth: 0,
 //!   },
 //! ])
 //! ```
@@ -49,10 +51,9 @@
 //! ## Not yet implemented
 //!
 //! - Handling of inline HTML, math blocks, etc.
-//! - Tracking code block language (`lang`) properly (currently set to [`None`]).
-use std::{iter::Peekable, vec::IntoIter};
+use std::{iter::Peekable, mem, slice::Iter, vec::IntoIter};
 
-use pulldown_cmark::{Event, Options, Tag, TagEnd};
+use pulldown_cmark::{CodeBlockKind, Event, Options, Tag, TagEnd};
 
 /// A style that can be applied to [`TextNode`] (code, emphasis, strikethrough, strong).
 #[derive(Clone, Debug, PartialEq)]
@@ -114,6 +115,19 @@ impl From<pulldown_cmark::HeadingLevel> for HeadingLevel {
     }
 }
 
+impl From<HeadingLevel> for basalt_core::markdown::HeadingLevel {
+    fn from(value: HeadingLevel) -> Self {
+        match value {
+            HeadingLevel::H1 => basalt_core::markdown::HeadingLevel::H1,
+            HeadingLevel::H2 => basalt_core::markdown::HeadingLevel::H2,
+            HeadingLevel::H3 => basalt_core::markdown::HeadingLevel::H3,
+            HeadingLevel::H4 => basalt_core::markdown::HeadingLevel::H4,
+            HeadingLevel::H5 => basalt_core::markdown::HeadingLevel::H5,
+            HeadingLevel::H6 => basalt_core::markdown::HeadingLevel::H6,
+        }
+    }
+}
+
 /// Represents specialized block quote kind variants (tip, note, warning, etc.).
 ///
 /// Currently, the underlying [`pulldown_cmark`] parser distinguishes these via syntax like `">
@@ -126,6 +140,55 @@ pub enum BlockQuoteKind {
     Important,
     Warning,
     Caution,
+    /// A callout type that is not one of the GitHub alert types, e.g. `[!bug]`.
+    Custom(String),
+}
+
+impl From<BlockQuoteKind> for basalt_core::markdown::BlockQuoteKind {
+    fn from(value: BlockQuoteKind) -> Self {
+        match value {
+            BlockQuoteKind::Note => basalt_core::markdown::BlockQuoteKind::Note,
+            BlockQuoteKind::Tip => basalt_core::markdown::BlockQuoteKind::Tip,
+            BlockQuoteKind::Important => basalt_core::markdown::BlockQuoteKind::Important,
+            BlockQuoteKind::Warning => basalt_core::markdown::BlockQuoteKind::Warning,
+            BlockQuoteKind::Caution => basalt_core::markdown::BlockQuoteKind::Caution,
+            BlockQuoteKind::Custom(label) => basalt_core::markdown::BlockQuoteKind::Custom(label),
+        }
+    }
+}
+
+impl From<basalt_core::markdown::BlockQuoteKind> for BlockQuoteKind {
+    fn from(value: basalt_core::markdown::BlockQuoteKind) -> Self {
+        match value {
+            basalt_core::markdown::BlockQuoteKind::Note => BlockQuoteKind::Note,
+            basalt_core::markdown::BlockQuoteKind::Tip => BlockQuoteKind::Tip,
+            basalt_core::markdown::BlockQuoteKind::Important => BlockQuoteKind::Important,
+            basalt_core::markdown::BlockQuoteKind::Warning => BlockQuoteKind::Warning,
+            basalt_core::markdown::BlockQuoteKind::Caution => BlockQuoteKind::Caution,
+            basalt_core::markdown::BlockQuoteKind::Custom(label) => BlockQuoteKind::Custom(label),
+        }
+    }
+}
+
+/// Detects a callout marker that [`pulldown_cmark`] does not recognize, e.g. `[!bug]`, at the
+/// start of the first paragraph of the block quote and moves it from the text into the kind, see
+/// [`basalt_core::markdown::take_callout_marker`].
+fn callout(nodes: &mut Vec<Node>) -> Option<BlockQuoteKind> {
+    let Some(Node {
+        markdown_node: MarkdownNode::Paragraph { text },
+        ..
+    }) = nodes.first_mut()
+    else {
+        return None;
+    };
+
+    let kind = basalt_core::markdown::take_callout_marker(&mut text.0)?;
+
+    if text.0.is_empty() {
+        nodes.remove(0);
+    }
+
+    Some(kind.into())
 }
 
 impl From<pulldown_cmark::BlockQuoteKind> for BlockQuoteKind {
@@ -149,6 +212,33 @@ pub enum ListKind {
     Unordered,
 }
 
+/// Represents the horizontal alignment of a table column.
+///
+/// The alignment is controlled with the colons in the table delimiter row, e.g. `|:--|:-:|--:|`.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub enum Alignment {
+    /// No alignment was specified (`---`), which is rendered as left aligned.
+    #[default]
+    None,
+    /// Left aligned column (`:--`).
+    Left,
+    /// Center aligned column (`:-:`).
+    Center,
+    /// Right aligned column (`--:`).
+    Right,
+}
+
+impl From<pulldown_cmark::Alignment> for Alignment {
+    fn from(value: pulldown_cmark::Alignment) -> Self {
+        match value {
+            pulldown_cmark::Alignment::None => Alignment::None,
+            pulldown_cmark::Alignment::Left => Alignment::Left,
+            pulldown_cmark::Alignment::Center => Alignment::Center,
+            pulldown_cmark::Alignment::Right => Alignment::Right,
+        }
+    }
+}
+
 /// A single unit of text that is optionally styled (e.g., code).
 ///
 /// [`TextNode`] can be any combination of sentence, words or characters.
@@ -178,6 +268,12 @@ impl From<String> for TextNode {
     }
 }
 
+impl AsMut<String> for TextNode {
+    fn as_mut(&mut self) -> &mut String {
+        &mut self.content
+    }
+}
+
 impl TextNode {
     /// Creates a new [`TextNode`] from `content` and optional [`Style`].
     pub fn new(content: String, style: Option<Style>) -> Self {
@@ -191,7 +287,7 @@ pub struct Text(Vec<TextNode>);
 
 impl From<&Text> for String {
     fn from(value: &Text) -> Self {
-        value.clone().into_iter().map(|node| node.content).collect()
+        value.iter().map(|node| node.content.as_str()).collect()
     }
 }
 
@@ -242,7 +338,30 @@ impl IntoIterator for Text {
     }
 }
 
+impl<'a> IntoIterator for &'a Text {
+    type Item = &'a TextNode;
+    type IntoIter = Iter<'a, TextNode>;
+    fn into_iter(self) -> Self::IntoIter {
+        self.0.iter()
+    }
+}
+
 impl Text {
+    /// Returns an iterator over the [`TextNode`]s without consuming the text.
+    pub fn iter(&self) -> Iter<'_, TextNode> {
+        self.0.iter()
+    }
+
+    /// Returns the [`TextNode`] at the index, or [`None`] if the index is out of bounds.
+    pub fn get(&self, index: usize) -> Option<&TextNode> {
+        self.0.get(index)
+    }
+
+    /// Returns the first [`TextNode`], or [`None`] if the text is empty.
+    pub fn first(&self) -> Option<&TextNode> {
+        self.0.first()
+    }
+
     /// Appends a [`TextNode`] to the inner text list.
     fn push(&mut self, node: TextNode) {
         self.0.push(node);
@@ -261,6 +380,7 @@ impl Text {
 ///     text: Text::default(),
 ///   },
 ///   source_range: Range::default(),
+///   depth: 0,
 /// };
 /// ```
 pub type Range<Idx> = std::ops::Range<Idx>;
@@ -304,6 +424,29 @@ impl Node {
         }
     }
 
+    /// Returns the source text that the node covers, or [`None`] if the source range is out of
+    /// bounds or does not fall on UTF-8 character boundaries.
+    pub fn source_slice<'a>(&self, source: &'a str) -> Option<&'a str> {
+        source.get(self.source_range.clone())
+    }
+
+    /// Returns the source text that the node covers, for sources the node was parsed from.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the source range is out of bounds or does not fall on UTF-8 character
+    /// boundaries.
+    pub fn source_slice_unchecked<'a>(&self, source: &'a str) -> &'a str {
+        let Range { start, end } = self.source_range;
+
+        debug_assert!(
+            source.is_char_boundary(start) && source.is_char_boundary(end),
+            "source range {start}..{end} is not on UTF-8 character boundaries of the source"
+        );
+
+        &source[start..end]
+    }
+
     /// Pushes a [`TextNode`] into the markdown node, if it contains a text buffer.
     ///
     /// If the markdown node is a [`MarkdownNode::BlockQuote`], the [`TextNode`] will be pushed
@@ -316,8 +459,10 @@ impl Node {
             | MarkdownNode::CodeBlock { text, .. }
             | MarkdownNode::TaskListItem { text, .. }
             | MarkdownNode::Item { text, .. } => text.push(node),
-            MarkdownNode::List { nodes, .. } | MarkdownNode::BlockQuote { nodes, .. } => {
-                if let Some(last_node) = nodes.last_mut() {
+            // Table cells are populated while parsing the table itself
+            MarkdownNode::Table { .. } => {}
+            markdown_node => {
+                if let Some(last_node) = markdown_node.children_mut().last_mut() {
                     last_node.push_text_node(node);
                 }
             }
@@ -378,6 +523,57 @@ pub enum MarkdownNode {
         kind: TaskListItemKind,
         text: Text,
     },
+
+    /// A GitHub flavored markdown table.
+    ///
+    /// Each column has an [`Alignment`]. The header row is stored separately from the body rows
+    /// and every cell is represented as [`Text`].
+    Table {
+        alignments: Vec<Alignment>,
+        header: Vec<Text>,
+        rows: Vec<Vec<Text>>,
+    },
+}
+
+impl MarkdownNode {
+    /// Returns `true` if the node contains child nodes, i.e. it is a [`MarkdownNode::List`] or a
+    /// [`MarkdownNode::BlockQuote`].
+    pub fn is_container(&self) -> bool {
+        matches!(
+            self,
+            MarkdownNode::List { .. } | MarkdownNode::BlockQuote { .. }
+        )
+    }
+
+    /// Returns the child nodes of a container node, or an empty slice for the other nodes.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use basalt_tui::note_editor::markdown_parser::from_str;
+    ///
+    /// let nodes = from_str("- One\n- Two\n\nParagraph");
+    ///
+    /// assert!(nodes[0].markdown_node.is_container());
+    /// assert_eq!(nodes[0].markdown_node.children().len(), 2);
+    ///
+    /// assert!(!nodes[1].markdown_node.is_container());
+    /// assert!(nodes[1].markdown_node.children().is_empty());
+    /// ```
+    pub fn children(&self) -> &[Node] {
+        match self {
+            MarkdownNode::List { nodes, .. } | MarkdownNode::BlockQuote { nodes, .. } => nodes,
+            _ => &[],
+        }
+    }
+
+    /// Returns the child nodes of a container node mutably, see [`MarkdownNode::children`].
+    pub fn children_mut(&mut self) -> &mut [Node] {
+        match self {
+            MarkdownNode::List { nodes, .. } | MarkdownNode::BlockQuote { nodes, .. } => nodes,
+            _ => &mut [],
+        }
+    }
 }
 
 /// Returns `true` if the [`Tag`] should be closed upon encountering the given [`TagEnd`].
@@ -412,12 +608,14 @@ fn matches_tag_end(tag: &Tag, tag_end: &TagEnd) -> bool {
 ///       text: Text::from("My Heading"),
 ///     },
 ///     source_range: Range { start: 0, end: 13 },
+///     depth: 0,
 ///   },
 ///   Node {
 ///     markdown_node: MarkdownNode::Paragraph {
 ///       text: Text::from("Some text."),
 ///     },
 ///     source_range: Range { start: 14, end: 24 },
+///     depth: 0,
 ///   },
 /// ])
 /// ```
@@ -443,12 +641,14 @@ pub fn from_str(text: &str) -> Vec<Node> {
 ///       text: Text::from("My Heading"),
 ///     },
 ///     source_range: Range { start: 0, end: 13 },
+///     depth: 0,
 ///   },
 ///   Node {
 ///     markdown_node: MarkdownNode::Paragraph {
 ///       text: Text::from("Some text."),
 ///     },
 ///     source_range: Range { start: 14, end: 24 },
+///     depth: 0,
 ///   },
 /// ])
 /// ```
@@ -461,47 +661,6 @@ impl<'a> Iterator for Parser<'a> {
     }
 }
 
-fn parse_blockquote<'a>(
-    events: &mut Peekable<Parser<'a>>,
-    source_range: Range<usize>,
-) -> Node {
-    let mut nodes = Parser::parse_events(events, None);
-
-    let mut kind: Option<BlockQuoteKind> = None;
-
-    if let Some(Node {
-        value: MarkdownNode::Paragraph { nodes: para_nodes },
-        ..
-    }) = nodes.first_mut()
-    {
-        if let Some(Node {
-            value: MarkdownNode::Text { ref mut text, .. },
-            ..
-        }) = para_nodes.first_mut()
-        {
-            let stripped = text.trim();
-
-            if stripped == "[!note]" {
-                kind = Some(BlockQuoteKind::Note);
-                *text = Text::from(""); // blank it out
-            } else if stripped == "[!tip]" {
-                kind = Some(BlockQuoteKind::Tip);
-                *text = Text::from("");
-            }
-        }
-    }
-
-    Node::new(
-        MarkdownNode::BlockQuote {
-            kind: kind.map(|k| k.into()), // still an Option
-            nodes,
-        },
-        source_range,
-    )
-}
-
-
-
 impl<'a> Parser<'a> {
     /// Creates a new [`Parser`] from a Markdown input string.
     ///
@@ -523,7 +682,18 @@ impl<'a> Parser<'a> {
         source_range: Range<usize>,
     ) -> Option<Node> {
         match tag {
-            Tag::BlockQuote(_) => Some(parse_blockquote(events, source_range)),
+            Tag::BlockQuote(kind) => {
+                let mut nodes = Parser::parse_events(events, Some(tag));
+                let kind = match kind {
+                    Some(kind) => Some(kind.into()),
+                    None => callout(&mut nodes),
+                };
+
+                Some(Node::new(
+                    MarkdownNode::BlockQuote { kind, nodes },
+                    source_range,
+                ))
+            }
             Tag::List(start) => Some(Node::new(
                 MarkdownNode::List {
                     kind: start.map(ListKind::Ordered).unwrap_or(ListKind::Unordered),
@@ -538,9 +708,16 @@ impl<'a> Parser<'a> {
                 },
                 source_range,
             )),
-            Tag::CodeBlock(_) => Some(Node::new(
+            Tag::CodeBlock(kind) => Some(Node::new(
                 MarkdownNode::CodeBlock {
-                    lang: None,
+                    lang: match kind {
+                        // The language is the first word of the info string, e.g. `rust` in
+                        // "```rust ignore"
+                        CodeBlockKind::Fenced(info) => {
+                            info.split_whitespace().next().map(String::from)
+                        }
+                        CodeBlockKind::Indented => None,
+                    },
                     text: Text::default(),
                 },
                 source_range,
@@ -557,16 +734,13 @@ impl<'a> Parser<'a> {
                 },
                 source_range,
             )),
+            Tag::Table(alignments) => Some(Parser::parse_table(events, alignments, source_range)),
             // NOTE: After all tags have been implemented the Option wrapper can be removed.
             //
             // Missing tags:
             //
             // | Tag::HtmlBlock
             // | Tag::FootnoteDefinition(_)
-            // | Tag::Table(_)
-            // | Tag::TableHead
-            // | Tag::TableRow
-            // | Tag::TableCell
             // | Tag::Emphasis
             // | Tag::Strong
             // | Tag::Strikethrough
@@ -582,6 +756,44 @@ impl<'a> Parser<'a> {
         }
     }
 
+    /// Consumes the table events until the end of the table and collects the header and body
+    /// cells into a [`MarkdownNode::Table`].
+    ///
+    /// The header row is not wrapped in a [`Tag::TableRow`], instead the cells are direct children
+    /// of the [`Tag::TableHead`].
+    fn parse_table(
+        events: &mut Peekable<Parser<'a>>,
+        alignments: Vec<pulldown_cmark::Alignment>,
+        source_range: Range<usize>,
+    ) -> Node {
+        let mut header = Vec::new();
+        let mut rows = Vec::new();
+        let mut row = Vec::new();
+        let mut cell = Text::default();
+
+        for (event, _) in events.by_ref() {
+            match event {
+                Event::Start(Tag::TableCell) => cell = Text::default(),
+                Event::End(TagEnd::TableCell) => row.push(mem::take(&mut cell)),
+                Event::End(TagEnd::TableHead) => header = mem::take(&mut row),
+                Event::End(TagEnd::TableRow) => rows.push(mem::take(&mut row)),
+                Event::End(TagEnd::Table) => break,
+                Event::Text(text) => cell.push(text.to_string().into()),
+                Event::Code(text) => cell.push(TextNode::new(text.to_string(), Some(Style::Code))),
+                _ => {}
+            }
+        }
+
+        Node::new(
+            MarkdownNode::Table {
+                alignments: alignments.into_iter().map(Alignment::from).collect(),
+                header,
+                rows,
+            },
+            source_range,
+        )
+    }
+
     fn parse_events(events: &mut Peekable<Parser<'a>>, current_tag: Option<Tag>) -> Vec<Node> {
         let mut nodes = Vec::new();
 
@@ -692,6 +904,7 @@ impl<'a> Parser<'a> {
     ///       text: Text::from("Hello world"),
     ///     },
     ///     source_range: Range { start: 0, end: 11 },
+    ///     depth: 0,
     ///   },
     /// ]);
     /// ```
@@ -865,6 +1078,41 @@ mod tests {
                     ),
                 ],
             ),
+            (
+                indoc! {r#"| Name | Kind | Size |
+                |:-----|:----:|-----:|
+                | `a` | file | 10 |
+                "#},
+                vec![Node::new(
+                    MarkdownNode::Table {
+                        alignments: vec![Alignment::Left, Alignment::Center, Alignment::Right],
+                        header: vec!["Name".into(), "Kind".into(), "Size".into()],
+                        rows: vec![vec![
+                            TextNode::new("a".into(), Some(Style::Code)).into(),
+                            "file".into(),
+                            "10".into(),
+                        ]],
+                    },
+                    0..66,
+                )],
+            ),
+            (
+                indoc! {r#"> [!bug] Crash
+                > on start
+                "#},
+                vec![Node::new(
+                    MarkdownNode::BlockQuote {
+                        kind: Some(BlockQuoteKind::Custom("bug".into())),
+                        nodes: vec![Node::new(
+                            MarkdownNode::Paragraph {
+                                text: vec!["Crash".into(), "on start".into()].into(),
+                            },
+                            2..26,
+                        )],
+                    },
+                    0..26,
+                )],
+            ),
         ];
 
         tests
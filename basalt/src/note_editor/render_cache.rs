@@ -0,0 +1,134 @@
+//! Cache of the rendered lines of the nodes of a note.
+//!
+//! Rendering a node wraps its text to the width of the pane, which makes rendering every node on
+//! every frame slow for large notes. [`RenderCache`] keeps the rendered lines of each node by the
+//! index of the node, so that a frame only renders the nodes that changed since the last one.
+//!
+//! The cached lines are invalidated as follows:
+//!
+//! - All lines are dropped when the width of the pane or the settings of the [`Editor`], i.e.
+//!   the theme, code wrapping and callout colors, differ from the ones they were rendered with.
+//! - The lines of a node are rendered again when the node differs from the node they were
+//!   rendered from, e.g. after the text of the node was edited. Nodes that only moved in the
+//!   source, because a node before them was edited, keep their lines.
+//! - The lines of indices past the last node are dropped when the note has fewer nodes.
+//!
+//! The cache is shared by the clones of an [`EditorState`](super::EditorState), as the lines
+//! only depend on the nodes, the width and the settings that are checked above.
+use ratatui::text::Line;
+
+use super::{markdown_parser, Editor};
+
+/// The rendered lines of a node and the node they were rendered from.
+#[derive(Clone, Debug, PartialEq)]
+struct CachedBlock {
+    markdown_node: markdown_parser::MarkdownNode,
+    lines: Vec<Line<'static>>,
+}
+
+/// Rendered lines of the nodes of a note by node index. See the [module docs](self) for when
+/// the lines are invalidated.
+#[derive(Clone, Debug, Default)]
+pub struct RenderCache {
+    width: u16,
+    settings: Option<Editor<'static>>,
+    blocks: Vec<Option<CachedBlock>>,
+}
+
+impl RenderCache {
+    /// Drops the cached lines if they were rendered with a different width or settings, and the
+    /// lines of the indices past `node_count`.
+    pub fn validate(&mut self, width: u16, settings: Editor<'static>, node_count: usize) {
+        if self.width != width || self.settings.as_ref() != Some(&settings) {
+            self.width = width;
+            self.settings = Some(settings);
+            self.blocks.clear();
+        }
+
+        self.blocks.resize(node_count, None);
+    }
+
+    /// Returns the lines of the node at the index, rendering them with `render` if the cached
+    /// lines are missing or were rendered from a different node.
+    pub fn lines(
+        &mut self,
+        index: usize,
+        node: &markdown_parser::Node,
+        render: impl FnOnce(&markdown_parser::Node) -> Vec<Line<'static>>,
+    ) -> &[Line<'static>] {
+        if index >= self.blocks.len() {
+            self.blocks.resize(index + 1, None);
+        }
+
+        let block = &mut self.blocks[index];
+
+        match block {
+            Some(cached) if cached.markdown_node == node.markdown_node => {}
+            _ => {
+                *block = Some(CachedBlock {
+                    markdown_node: node.markdown_node.clone(),
+                    lines: render(node),
+                })
+            }
+        }
+
+        block.as_ref().map_or(&[], |cached| cached.lines.as_slice())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::cell::Cell;
+
+    use super::*;
+
+    #[test]
+    fn test_render_cache() {
+        let nodes = markdown_parser::from_str("# Heading\n\nParagraph");
+        let edited = markdown_parser::from_str("# Heading\n\nEdited paragraph");
+
+        let renders = Cell::new(0);
+        let render = |node: &markdown_parser::Node| {
+            renders.set(renders.get() + 1);
+            vec![Line::from(format!("{:?}", node.source_range))]
+        };
+
+        let mut cache = RenderCache::default();
+
+        let render_all = |cache: &mut RenderCache, nodes: &[markdown_parser::Node]| {
+            nodes
+                .iter()
+                .enumerate()
+                .flat_map(|(i, node)| cache.lines(i, node, render).to_vec())
+                .collect::<Vec<_>>()
+        };
+
+        cache.validate(80, Editor::default(), nodes.len());
+        render_all(&mut cache, &nodes);
+        assert_eq!(renders.get(), 2);
+
+        // Unchanged nodes are not rendered again
+        render_all(&mut cache, &nodes);
+        assert_eq!(renders.get(), 2);
+
+        // Only the edited node is rendered again
+        assert_eq!(
+            render_all(&mut cache, &edited),
+            [Line::from("0..10"), Line::from("11..27")]
+        );
+        assert_eq!(renders.get(), 3);
+
+        // A different width renders all nodes again
+        cache.validate(40, Editor::default(), edited.len());
+        render_all(&mut cache, &edited);
+        assert_eq!(renders.get(), 5);
+
+        // Different settings render all nodes again
+        cache.validate(40, Editor::default().with_wrap_code(true), edited.len());
+        render_all(&mut cache, &edited);
+        assert_eq!(renders.get(), 7);
+
+        cache.validate(40, Editor::default().with_wrap_code(true), 1);
+        assert_eq!(cache.blocks.len(), 1);
+    }
+}
@@ -1,17 +1,28 @@
 use core::fmt;
 
 use std::{
-    fs::File,
-    io::{self, Write},
-    ops::RangeBounds,
-    path::PathBuf,
+    cell::RefCell,
+    fs::{self, File},
+    io::{self, Read},
+    ops::{Range, RangeBounds},
+    path::{Path, PathBuf},
+    rc::Rc,
     slice::SliceIndex,
+    time::Instant,
 };
 
+use basalt_core::{
+    markdown,
+    obsidian::{self, FileKind, Note, WikiLink},
+};
 use ratatui::widgets::ScrollbarState;
-use tui_textarea::Input;
+use serde::{de::IgnoredAny, Deserialize};
+use tui_textarea::{Input, Key};
 
-use super::{markdown_parser, text_buffer::CursorMove, TextBuffer};
+use super::{
+    find::FindState, markdown_parser, render_cache::RenderCache, text_buffer::CursorMove,
+    TextBuffer, VimState,
+};
 
 #[derive(Clone, Debug, PartialEq, Default)]
 pub struct Scrollbar {
@@ -19,12 +30,122 @@ pub struct Scrollbar {
     pub position: usize,
 }
 
+/// The part of a note that is in view, stored when another note is opened so that the view can
+/// be restored when the note is opened again.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct ViewPosition {
+    /// The row of the cursor, i.e. the index of the node.
+    pub row: usize,
+    /// The scroll position of the view, see [`Scrollbar`].
+    pub scroll: usize,
+}
+
+/// Scrolls the view to the current row on the next render, when the rendered line offsets of the
+/// nodes are known.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub(crate) enum RowScroll {
+    /// Scrolls the current row to the top of the view.
+    Top,
+    /// Keeps the cursor line centered in the view, like the explorer keeps the selected item.
+    Follow,
+}
+
+/// A file that is not a markdown note. The editor shows information about the file instead of
+/// parsing its content as markdown.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Attachment {
+    pub name: String,
+    pub kind: FileKind,
+    /// Size of the file in bytes.
+    pub size: u64,
+    /// A summary of the content of the file, e.g. the dimensions of an image or the number of
+    /// cards of a canvas. `None` if the file has no summary or it could not be read.
+    pub details: Option<String>,
+}
+
+impl From<&Note> for Attachment {
+    fn from(note: &Note) -> Self {
+        Self {
+            name: note.name.clone(),
+            kind: note.kind(),
+            size: fs::metadata(&note.path)
+                .map(|metadata| metadata.len())
+                .unwrap_or_default(),
+            details: attachment_details(&note.path, note.kind()),
+        }
+    }
+}
+
+/// Returns the dimensions of PNG and GIF images and the number of cards and connections of
+/// canvases.
+fn attachment_details(path: &Path, kind: FileKind) -> Option<String> {
+    match kind {
+        FileKind::Image => {
+            // The dimensions are within the first 24 bytes of the supported formats
+            let mut header = Vec::with_capacity(24);
+            File::open(path)
+                .ok()?
+                .take(24)
+                .read_to_end(&mut header)
+                .ok()?;
+
+            let (width, height) = image_dimensions(&header)?;
+            Some(format!("{width} × {height} px"))
+        }
+        FileKind::Canvas => {
+            #[derive(Deserialize)]
+            struct Canvas {
+                #[serde(default)]
+                nodes: Vec<IgnoredAny>,
+                #[serde(default)]
+                edges: Vec<IgnoredAny>,
+            }
+
+            let canvas: Canvas = serde_json::from_str(&fs::read_to_string(path).ok()?).ok()?;
+            Some(format!(
+                "{} cards, {} connections",
+                canvas.nodes.len(),
+                canvas.edges.len()
+            ))
+        }
+        _ => None,
+    }
+}
+
+/// Reads the width and height of a PNG or GIF image from the start of the file.
+fn image_dimensions(header: &[u8]) -> Option<(u32, u32)> {
+    match header {
+        [0x89, b'P', b'N', b'G', b'\r', b'\n', 0x1a, b'\n', _, _, _, _, b'I', b'H', b'D', b'R', w0, w1, w2, w3, h0, h1, h2, h3, ..] => {
+            Some((
+                u32::from_be_bytes([*w0, *w1, *w2, *w3]),
+                u32::from_be_bytes([*h0, *h1, *h2, *h3]),
+            ))
+        }
+        [b'G', b'I', b'F', b'8', b'7' | b'9', b'a', w0, w1, h0, h1, ..] => Some((
+            u16::from_le_bytes([*w0, *w1]).into(),
+            u16::from_le_bytes([*h0, *h1]).into(),
+        )),
+        _ => None,
+    }
+}
+
+/// Shown instead of the content of a note that cannot be opened in the editor, e.g. because it
+/// is too large or not text.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Placeholder {
+    /// Why the note cannot be opened.
+    pub reason: String,
+    /// The start of the file, if it is shown.
+    pub preview: Option<String>,
+}
+
 #[derive(Clone, Copy, Debug, Default, PartialEq)]
 pub enum Mode {
     #[default]
     Read,
     View,
     Edit,
+    Normal,
 }
 
 impl fmt::Display for Mode {
@@ -33,10 +154,26 @@ impl fmt::Display for Mode {
             Mode::View => write!(f, "VIEW"),
             Mode::Edit => write!(f, "EDIT"),
             Mode::Read => write!(f, "READ"),
+            Mode::Normal => write!(f, "NORMAL"),
         }
     }
 }
 
+/// The column that vertical movement aims for, like in most text editors. Moving through a line
+/// that is shorter than the column clamps the cursor to the end of the line, and the column is
+/// restored on the next line that is long enough.
+///
+/// The column is kept together with the position the cursor was moved to, so that any other
+/// movement of the cursor resets it.
+#[derive(Clone, Copy, Debug, PartialEq)]
+struct GoalColumn {
+    col: usize,
+    /// The node the cursor was moved to.
+    row: usize,
+    /// The position of the cursor within the node.
+    cursor: (usize, usize),
+}
+
 // TODO: Two editing modes
 // 1. Obsidian (Partial editing)
 // 2. Full editing
@@ -44,7 +181,6 @@ impl fmt::Display for Mode {
 //
 // TODO:
 // - Better movement
-// - Vim mode
 // - Command mode to open a different text editor like Neovim or helix
 #[derive(Clone, Debug, Default)]
 pub struct EditorState<'text_buffer> {
@@ -54,13 +190,33 @@ pub struct EditorState<'text_buffer> {
     content_original: String,
     path: PathBuf,
     nodes: Vec<markdown_parser::Node>,
+    /// The frontmatter properties, which are shown in a panel above the nodes instead of being a
+    /// node themselves, so the cursor never moves onto them.
+    properties: Vec<markdown::Property>,
+    properties_collapsed: bool,
+    /// The number of tasks and completed tasks in the content.
+    task_stats: markdown::TaskStats,
     scrollbar: Scrollbar,
     pub current_row: usize,
-    // TODO: This can be utilized after toast implementation
-    // error_message: Option<String>,
     active: bool,
     pub modified: bool,
     dirty: bool,
+    collapsed_ranges: Vec<Range<usize>>,
+    find: FindState,
+    row_scroll: Option<RowScroll>,
+    vim: VimState,
+    vim_mode: bool,
+    insert_from_normal: bool,
+    attachment: Option<Attachment>,
+    placeholder: Option<Placeholder>,
+    goal_column: Option<GoalColumn>,
+    /// Previous versions of the content, the most recent last.
+    undo_history: Vec<String>,
+    redo_history: Vec<String>,
+    /// Rendered lines of the nodes, shared by the clones of the state. The app state is cloned
+    /// for every frame and message, so the cache is only detached for an editor that is shown
+    /// next to the original, see [`EditorState::detach_render_cache`].
+    render_cache: Rc<RefCell<RenderCache>>,
 }
 
 impl<'text_buffer> EditorState<'text_buffer> {
@@ -83,14 +239,78 @@ impl<'text_buffer> EditorState<'text_buffer> {
         self.mode
     }
 
+    /// Returns `true` if edit mode was entered from normal mode, in which case exiting edit mode
+    /// should return to normal mode.
+    pub fn is_insert_from_normal(&self) -> bool {
+        self.insert_from_normal
+    }
+
+    pub fn vim(&self) -> &VimState {
+        &self.vim
+    }
+
+    /// Returns `true` if the editor uses Vim-style modal editing, where editing starts in normal
+    /// mode.
+    pub fn is_vim_mode(&self) -> bool {
+        self.vim_mode
+    }
+
+    pub fn set_vim_mode(mut self, vim_mode: bool) -> Self {
+        self.vim_mode = vim_mode;
+        self
+    }
+
+    /// Returns the mode label shown in the editor. Edit mode is shown as insert mode when Vim
+    /// mode is enabled.
+    pub fn mode_label(&self) -> String {
+        match self.mode {
+            Mode::Edit if self.vim_mode => "INSERT".to_string(),
+            mode => mode.to_string(),
+        }
+    }
+
     pub fn nodes(&self) -> &[markdown_parser::Node] {
         self.nodes.as_slice()
     }
 
+    /// Returns the cache of the rendered lines of the nodes, see [`RenderCache`].
+    pub(crate) fn render_cache(&self) -> Rc<RefCell<RenderCache>> {
+        Rc::clone(&self.render_cache)
+    }
+
+    /// Gives the state a cache of its own, e.g. for a new split of the note. Editors of different
+    /// widths would otherwise invalidate each other's cached lines on every frame.
+    pub fn detach_render_cache(self) -> Self {
+        Self {
+            render_cache: Rc::new(RefCell::new(self.render_cache.borrow().clone())),
+            ..self
+        }
+    }
+
     pub fn nodes_as_mut(&mut self) -> &mut [markdown_parser::Node] {
         self.nodes.as_mut_slice()
     }
 
+    /// Returns the number of tasks and completed tasks in the content, see
+    /// [`markdown::task_stats`].
+    pub fn task_stats(&self) -> markdown::TaskStats {
+        self.task_stats
+    }
+
+    pub fn properties(&self) -> &[markdown::Property] {
+        self.properties.as_slice()
+    }
+
+    pub fn properties_collapsed(&self) -> bool {
+        self.properties_collapsed
+    }
+
+    /// Expands or collapses the properties panel.
+    pub fn toggle_properties(mut self) -> Self {
+        self.properties_collapsed = !self.properties_collapsed;
+        self
+    }
+
     pub fn scrollbar(&self) -> &Scrollbar {
         &self.scrollbar
     }
@@ -99,9 +319,135 @@ impl<'text_buffer> EditorState<'text_buffer> {
         self.active
     }
 
+    /// Returns the attachment that is open instead of a note.
+    pub fn attachment(&self) -> Option<&Attachment> {
+        self.attachment.as_ref()
+    }
+
+    pub fn set_attachment(mut self, attachment: Attachment) -> Self {
+        self.attachment = Some(attachment);
+        self
+    }
+
+    /// Returns the placeholder that is shown instead of the content of a note that could not be
+    /// opened.
+    pub fn placeholder(&self) -> Option<&Placeholder> {
+        self.placeholder.as_ref()
+    }
+
+    pub fn set_placeholder(mut self, placeholder: Placeholder) -> Self {
+        self.placeholder = Some(placeholder);
+        self
+    }
+
+    pub fn find(&self) -> &FindState {
+        &self.find
+    }
+
+    pub fn collapsed_ranges(&self) -> &[Range<usize>] {
+        self.collapsed_ranges.as_slice()
+    }
+
+    /// Returns the outermost collapsed range that contains the given node index.
+    pub fn collapsed_range_at(&self, index: usize) -> Option<&Range<usize>> {
+        self.collapsed_ranges
+            .iter()
+            .filter(|range| range.contains(&index))
+            .min_by_key(|range| range.start)
+    }
+
+    /// Returns the range of nodes that belong to the section of the heading at the given row.
+    ///
+    /// The section spans until the next heading of the same or higher level, or until the end of
+    /// the note. The heading itself is not part of the range.
+    fn section_range(&self, row: usize) -> Option<Range<usize>> {
+        let markdown_parser::MarkdownNode::Heading { level, .. } =
+            self.nodes.get(row)?.markdown_node
+        else {
+            return None;
+        };
+
+        let end = self.nodes[row + 1..]
+            .iter()
+            .position(|node| {
+                matches!(node.markdown_node,
+                    markdown_parser::MarkdownNode::Heading { level: next_level, .. }
+                        if next_level <= level)
+            })
+            .map_or(self.nodes.len(), |position| row + 1 + position);
+
+        Some(row + 1..end).filter(|range| !range.is_empty())
+    }
+
+    /// Folds the section of the heading at the given row if `folded` is `true` and unfolds it
+    /// otherwise. A section that is already folded or unfolded is left as it is.
+    ///
+    /// The folded range matches the section of the corresponding heading item in the outline. If
+    /// the cursor is inside the folded section, it is moved to the heading.
+    pub fn set_fold(mut self, row: usize, folded: bool) -> Self {
+        let Some(range) = self.section_range(row) else {
+            return self;
+        };
+
+        match (
+            self.collapsed_ranges.iter().position(|r| *r == range),
+            folded,
+        ) {
+            (Some(index), false) => {
+                self.collapsed_ranges.remove(index);
+            }
+            (None, true) => {
+                if range.contains(&self.current_row) {
+                    self.current_row = row;
+                    self.update_text_buffer();
+                }
+                self.collapsed_ranges.push(range);
+            }
+            _ => {}
+        }
+
+        self
+    }
+
+    /// Folds or unfolds the section of the heading at the given row, see [`Self::set_fold`].
+    pub fn toggle_fold_at(self, row: usize) -> Self {
+        let folded = self
+            .section_range(row)
+            .is_some_and(|range| self.collapsed_ranges.contains(&range));
+
+        self.set_fold(row, !folded)
+    }
+
+    /// Folds the sections of all headings, including the nested ones, so that unfolding a section
+    /// keeps its subsections folded.
+    ///
+    /// If the cursor is inside a folded section, it is moved to the outermost folded heading.
+    pub fn fold_all(mut self) -> Self {
+        self.collapsed_ranges = (0..self.nodes.len())
+            .filter_map(|row| self.section_range(row))
+            .collect();
+
+        if let Some(range) = self.collapsed_range_at(self.current_row).cloned() {
+            // Sections start right after their heading
+            self.current_row = range.start.saturating_sub(1);
+            self.update_text_buffer();
+        }
+
+        self
+    }
+
+    pub fn unfold_all(mut self) -> Self {
+        self.collapsed_ranges.clear();
+        self
+    }
+
     pub fn new(content: &str, path: PathBuf) -> Self {
+        let nodes = markdown_parser::from_str(content);
+
         Self {
-            nodes: markdown_parser::from_str(content),
+            task_stats: task_stats(&nodes),
+            nodes,
+            properties: markdown::properties(content),
             content_original: content.to_string(),
             content: content.to_string(),
             path,
@@ -111,12 +457,30 @@ impl<'text_buffer> EditorState<'text_buffer> {
 
     pub fn set_content(mut self, content: &str) -> Self {
         self.nodes = markdown_parser::from_str(content);
+        self.properties = markdown::properties(content);
+        self.task_stats = task_stats(&self.nodes);
+        self.collapsed_ranges.clear();
+        self.find = FindState::default();
+        self.undo_history.clear();
+        self.redo_history.clear();
         self.content_original = content.to_string();
         self.content = content.to_string();
         self.update_text_buffer();
         self
     }
 
+    /// Takes the content of another editor of the same note, e.g. of another split, so that the
+    /// editors of a note act as one buffer. The cursor row is kept, and the unsaved changes of the
+    /// other editor become the unsaved changes of this one.
+    pub fn sync_content(mut self, other: &EditorState) -> Self {
+        if self.content != other.content || self.content_original != other.content_original {
+            self.content_original = other.content_original.clone();
+            self.replace_content(other.content.clone());
+            self.dirty = false;
+        }
+        self
+    }
+
     pub fn set_path(mut self, path: PathBuf) -> Self {
         self.path = path;
         self
@@ -132,23 +496,111 @@ impl<'text_buffer> EditorState<'text_buffer> {
             let start = node.source_range.start;
             let end = node.source_range.end;
 
-            let str_start = &self.content_slice(..start.saturating_sub(1));
+            let str_start = &self.content_slice(..start);
             let str_end = &self.content_slice(end..);
 
-            let modified_str = self.text_buffer().to_string();
+            // Items added or removed in the middle of an ordered list shift the numbers of the
+            // following items
+            let modified_str = markdown::renumber_ordered_lists(&self.text_buffer().to_string());
 
-            let complete_modified_content = [str_start, modified_str.as_str(), str_end].join("\n");
+            // The text buffer does not contain the trailing line break of the node
+            let line_break = if self.content_slice(start..end).ends_with('\n') {
+                "\n"
+            } else {
+                ""
+            };
+
+            let complete_modified_content =
+                [str_start, modified_str.as_str(), line_break, str_end].concat();
 
             if self.content != complete_modified_content {
-                self.nodes = markdown_parser::from_str(&complete_modified_content);
-                self.content = complete_modified_content;
-                self.update_text_buffer();
+                let previous_content = self.replace_content(complete_modified_content);
+                self.undo_history.push(previous_content);
+                self.redo_history.clear();
             }
 
             self.modified = self.content != self.content_original;
         }
     }
 
+    /// Replaces the content and reparses the nodes. Returns the previous content.
+    fn replace_content(&mut self, content: String) -> String {
+        self.nodes = markdown_parser::from_str(&content);
+        self.properties = markdown::properties(&content);
+        self.task_stats = task_stats(&self.nodes);
+        // Node indices might have shifted, so the folds can no longer be trusted
+        self.collapsed_ranges.clear();
+        self.find = self.find.clone().update_matches(&content);
+        self.current_row = self.current_row.min(self.nodes.len().saturating_sub(1));
+        self.modified = content != self.content_original;
+        let previous_content = std::mem::replace(&mut self.content, content);
+        self.update_text_buffer();
+        previous_content
+    }
+
+    /// Flushes any pending edits in the text buffer, so they become part of the history.
+    fn flush_edits(&mut self) {
+        if self.dirty {
+            self.intermediate_save();
+            self.dirty = false;
+        }
+    }
+
+    /// Applies the pending edits of the text buffer to the note content, e.g. before the editor
+    /// is replaced, so that they are not lost.
+    pub fn commit_edits(mut self) -> Self {
+        self.flush_edits();
+        self
+    }
+
+    pub fn can_undo(&self) -> bool {
+        self.dirty || !self.undo_history.is_empty()
+    }
+
+    pub fn can_redo(&self) -> bool {
+        !self.redo_history.is_empty()
+    }
+
+    /// Restores the content to the state before the latest edit.
+    ///
+    /// Unsaved edits in the text buffer are committed first, so that they are the ones undone.
+    pub fn undo(mut self) -> Self {
+        self.flush_edits();
+
+        if let Some(content) = self.undo_history.pop() {
+            let current_content = self.replace_content(content);
+            self.redo_history.push(current_content);
+        }
+
+        self
+    }
+
+    /// Reverts the unsaved changes to the last saved content. The discarded changes can still be
+    /// undone.
+    pub fn discard(mut self) -> Self {
+        self.flush_edits();
+
+        if self.modified {
+            let current_content = self.replace_content(self.content_original.clone());
+            self.undo_history.push(current_content);
+            self.redo_history.clear();
+        }
+
+        self
+    }
+
+    /// Reapplies the latest edit that was undone.
+    pub fn redo(mut self) -> Self {
+        self.flush_edits();
+
+        if let Some(content) = self.redo_history.pop() {
+            let current_content = self.replace_content(content);
+            self.undo_history.push(current_content);
+        }
+
+        self
+    }
+
     pub fn delete_char(mut self) -> Self {
         let (row, col) = self.text_buffer.cursor();
 
@@ -163,7 +615,7 @@ impl<'text_buffer> EditorState<'text_buffer> {
                 nodes.get(current_row).map(|node| node.source_range.end)
             {
                 if let Some(prev_node) = nodes.get_mut(current_row - 1) {
-                    let content = &content[prev_node.source_range.clone()];
+                    let content = prev_node.source_slice_unchecked(&content);
                     prev_node.source_range = prev_node.source_range.start..current_node_range_end;
                     self.update_text_buffer_content(content);
                     nodes.remove(current_row);
@@ -185,17 +637,99 @@ impl<'text_buffer> EditorState<'text_buffer> {
         self
     }
 
+    /// Indents the line under the cursor, see [`TextBuffer::indent_line`].
+    pub fn indent_line(mut self) -> Self {
+        let lines = self.text_buffer.lines().to_vec();
+        self.text_buffer.indent_line();
+        self.dirty |= self.text_buffer.lines() != lines;
+        self
+    }
+
+    /// Outdents the line under the cursor, see [`TextBuffer::outdent_line`].
+    pub fn outdent_line(mut self) -> Self {
+        let lines = self.text_buffer.lines().to_vec();
+        self.text_buffer.outdent_line();
+        self.dirty |= self.text_buffer.lines() != lines;
+        self
+    }
+
+    /// Returns `true` if the cursor is in a list, where lines can be indented and outdented.
+    pub fn is_in_list(&self) -> bool {
+        matches!(
+            self.nodes
+                .get(self.current_row)
+                .map(|node| &node.markdown_node),
+            Some(markdown_parser::MarkdownNode::List { .. })
+        )
+    }
+
     pub fn edit(mut self, input: Input) -> Self {
-        self.text_buffer.edit(input);
+        let in_code_block = matches!(
+            self.nodes
+                .get(self.current_row)
+                .map(|node| &node.markdown_node),
+            Some(markdown_parser::MarkdownNode::CodeBlock { .. })
+        );
+
+        match input {
+            Input {
+                key: Key::Enter,
+                ctrl: false,
+                alt: false,
+                ..
+            } if !in_code_block => self.text_buffer.insert_newline(),
+            input => self.text_buffer.edit(input),
+        }
+
         if self.text_buffer.is_modified() {
             self.dirty = true;
         }
         self
     }
 
-    pub fn cursor_up(mut self) -> Self {
-        let (row, _) = self.text_buffer.cursor();
-        if row == 0 {
+    /// Moves the cursor up a line, onto the last line of the previous node from the first line of
+    /// a node. The column is kept across lines of varying length, like in most text editors.
+    pub fn cursor_up(self) -> Self {
+        let col = self.goal_col();
+        self.move_up().with_goal_col(col)
+    }
+
+    /// Moves the cursor down a line, onto the first line of the next node from the last line of
+    /// a node. The column is kept across lines of varying length, like in most text editors.
+    pub fn cursor_down(self) -> Self {
+        let col = self.goal_col();
+        self.move_down().with_goal_col(col)
+    }
+
+    /// Returns the column that vertical movement aims for. The remembered goal column only
+    /// applies while the cursor has not moved since the last vertical movement.
+    fn goal_col(&self) -> usize {
+        match self.goal_column {
+            Some(goal)
+                if goal.row == self.current_row && goal.cursor == self.text_buffer.cursor() =>
+            {
+                goal.col
+            }
+            _ => self.text_buffer.cursor_col(),
+        }
+    }
+
+    /// Moves the cursor to the column on the current line, clamped to the length of the line, and
+    /// remembers the column for the next vertical movement.
+    fn with_goal_col(mut self, col: usize) -> Self {
+        self.text_buffer.move_to_col(col);
+        self.goal_column = Some(GoalColumn {
+            col,
+            row: self.current_row,
+            cursor: self.text_buffer.cursor(),
+        });
+        self
+    }
+
+    fn move_up(mut self) -> Self {
+        self.row_scroll = Some(RowScroll::Follow);
+
+        if self.text_buffer.is_cursor_on_first_line() {
             if self.dirty {
                 self.intermediate_save();
                 self.dirty = false;
@@ -205,7 +739,10 @@ impl<'text_buffer> EditorState<'text_buffer> {
                 return self;
             }
 
-            self.current_row = self.current_row.saturating_sub(1);
+            let previous_row = self.current_row.saturating_sub(1);
+            self.current_row = self
+                .collapsed_range_at(previous_row)
+                .map_or(previous_row, |range| range.start.saturating_sub(1));
             self.update_text_buffer();
             self.text_buffer.cursor_move(CursorMove::Bottom);
         } else {
@@ -245,9 +782,60 @@ impl<'text_buffer> EditorState<'text_buffer> {
         self
     }
 
-    pub fn cursor_down(mut self) -> Self {
-        let (row, _) = self.text_buffer.cursor();
-        if row < self.text_buffer.lines().len().saturating_sub(1) {
+    pub fn view_position(&self) -> ViewPosition {
+        ViewPosition {
+            row: self.current_row,
+            scroll: self.scrollbar.position,
+        }
+    }
+
+    /// Moves the cursor to the row and scrolls the view to the stored position. The row is
+    /// clamped to the last node, since the note may have changed since the position was stored.
+    pub fn with_view_position(self, position: ViewPosition) -> Self {
+        if self.nodes.is_empty() {
+            return self;
+        }
+
+        let row = position.row.min(self.nodes.len() - 1);
+        let mut state = self.jump_to_row(row);
+        state.row_scroll = None;
+        state.scroll_to(position.scroll);
+        state
+    }
+
+    /// Moves the cursor to the node that contains the byte offset of the content, see
+    /// [`EditorState::reveal_row`]. Offsets between nodes move the cursor to the preceding node.
+    pub fn set_cursor_to_offset(self, byte_offset: usize) -> Self {
+        let row = self
+            .nodes
+            .partition_point(|node| node.source_range.start <= byte_offset)
+            .saturating_sub(1);
+
+        self.reveal_row(row)
+    }
+
+    /// Moves the cursor to the start of the node at the row and scrolls the node to the top of
+    /// the view on the next render.
+    ///
+    /// Folded sections that contain the row are unfolded.
+    pub fn reveal_row(mut self, row: usize) -> Self {
+        if self.nodes.is_empty() {
+            return self;
+        }
+
+        let row = row.min(self.nodes.len() - 1);
+        self.collapsed_ranges.retain(|range| !range.contains(&row));
+
+        self = self.jump_to_row(row);
+        self.text_buffer.cursor_move(CursorMove::Jump(0, 0));
+        self.row_scroll = Some(RowScroll::Top);
+        self
+    }
+
+    fn move_down(mut self) -> Self {
+        self.row_scroll = Some(RowScroll::Follow);
+
+        if !self.text_buffer.is_cursor_on_last_line() {
             self.text_buffer.cursor_move(CursorMove::Down);
             return self;
         } else {
@@ -266,9 +854,16 @@ impl<'text_buffer> EditorState<'text_buffer> {
             // let diff = nodes_amount.abs_diff(nodes.len());
             // self.nodes = nodes;
 
-            self.current_row = self
-                .current_row
-                .saturating_add(1)
+            let next_row = self.current_row.saturating_add(1);
+            let next_row = self
+                .collapsed_range_at(next_row)
+                .map_or(next_row, |range| range.end);
+
+            if next_row >= nodes_amount {
+                return self;
+            }
+
+            self.current_row = next_row
                 // .saturating_add(diff)
                 .min(self.nodes.len().saturating_sub(1));
 
@@ -279,28 +874,210 @@ impl<'text_buffer> EditorState<'text_buffer> {
         self
     }
 
-    pub fn save(mut self) -> Self {
+    /// Writes the modified content to the note file. Does nothing if there are no modifications.
+    pub fn save(mut self) -> io::Result<Self> {
         if !self.modified {
-            return self;
+            return Ok(self);
         }
 
-        match self.save_modified_to_file() {
-            Ok(_) => self,
-            Err(_err) => Self {
-                // TODO: Display error messages
-                // error_message: Some(format!("Failed to save file: {}", err)),
-                ..self
-            },
-        }
+        self.save_modified_to_file()?;
+        Ok(self)
+    }
+
+    /// Replaces the content with the content of the note file, e.g. after the note was changed
+    /// by another application. Unsaved modifications are discarded.
+    ///
+    /// Fails without changing the content if the note is larger than `max_bytes` or not valid
+    /// UTF-8, see [`Note::read_to_string_checked`].
+    pub fn reload_from_disk(&mut self, max_bytes: u64) -> obsidian::Result<()> {
+        let note = Note {
+            path: self.path.clone(),
+            ..Default::default()
+        };
+        let content = note.read_to_string_checked(max_bytes)?;
+
+        *self = std::mem::take(self).set_content(&content);
+        self.current_row = self.current_row.min(self.nodes.len().saturating_sub(1));
+        self.modified = false;
+        self.dirty = false;
+        self.update_text_buffer();
+
+        Ok(())
     }
 
     fn save_modified_to_file(&mut self) -> io::Result<()> {
-        let mut file = File::create(&self.path)?;
-        file.write_all(self.content.as_bytes())?;
+        let note = Note {
+            path: self.path.clone(),
+            ..Default::default()
+        };
+        note.atomic_write(&self.content)
+            .map_err(|error| match error {
+                obsidian::Error::Io(error) => error,
+                obsidian::Error::PathNotFound(_) => io::Error::new(io::ErrorKind::NotFound, error),
+                obsidian::Error::PermissionDenied(_) => {
+                    io::Error::new(io::ErrorKind::PermissionDenied, error)
+                }
+                error => io::Error::other(error),
+            })?;
+        self.content_original = self.content.clone();
         self.modified = false;
         Ok(())
     }
 
+    /// Returns the byte offset of the line under the cursor within the current node.
+    fn cursor_line_range(&self) -> Option<Range<usize>> {
+        let node = self.nodes.get(self.current_row)?;
+        let (row, _) = self.text_buffer.cursor();
+
+        let mut line_start = node.source_range.start;
+        let mut lines = node
+            .source_slice_unchecked(&self.content)
+            .split_inclusive('\n');
+
+        for line in lines.by_ref().take(row) {
+            line_start += line.len();
+        }
+
+        let line_end = line_start + lines.next().map_or(0, |line| line.trim_end().len());
+        Some(line_start..line_end)
+    }
+
+    /// Toggles the task item under the cursor between checked and unchecked.
+    ///
+    /// The task marker is flipped directly in the content using the source range of the task, so
+    /// the rest of the note keeps its formatting. Loosely checked tasks, e.g. `- [?]`, become
+    /// unchecked.
+    pub fn toggle_task(mut self) -> Self {
+        self.flush_edits();
+
+        let Some(line_range) = self.cursor_line_range() else {
+            return self;
+        };
+
+        let Some(node) = self.nodes.get(self.current_row) else {
+            return self;
+        };
+
+        // The innermost task that starts on or before the cursor line and covers it
+        let Some((kind, marker_range)) = task_items(std::slice::from_ref(node))
+            .into_iter()
+            .filter(|(_, task)| {
+                task.source_range.start <= line_range.end
+                    && task.source_range.end > line_range.start
+            })
+            .max_by_key(|(_, task)| task.source_range.start)
+            .and_then(|(kind, task)| {
+                task_marker_range(&self.content, task.source_range.start).map(|range| (kind, range))
+            })
+        else {
+            return self;
+        };
+
+        let marker = match kind {
+            markdown_parser::TaskListItemKind::Unchecked => "x",
+            markdown_parser::TaskListItemKind::Checked
+            | markdown_parser::TaskListItemKind::LooselyChecked => " ",
+        };
+
+        let mut content = self.content.clone();
+        content.replace_range(marker_range, marker);
+
+        let previous_content = self.replace_content(content);
+        self.undo_history.push(previous_content);
+        self.redo_history.clear();
+        self
+    }
+
+    /// Opens the find prompt and clears any previous query.
+    pub fn find_start(mut self) -> Self {
+        self.find = FindState::open();
+        self
+    }
+
+    pub fn find_input(mut self, c: char) -> Self {
+        self.find = self.find.push(c, &self.content);
+        self
+    }
+
+    pub fn find_backspace(mut self) -> Self {
+        self.find = self.find.pop(&self.content);
+        self
+    }
+
+    /// Closes the find prompt and jumps to the first match.
+    pub fn find_confirm(mut self) -> Self {
+        self.find = self.find.confirm();
+        self.jump_to_current_match();
+        self
+    }
+
+    pub fn find_next(mut self) -> Self {
+        self.find = self.find.next();
+        self.jump_to_current_match();
+        self
+    }
+
+    pub fn find_previous(mut self) -> Self {
+        self.find = self.find.previous();
+        self.jump_to_current_match();
+        self
+    }
+
+    pub fn find_cancel(mut self) -> Self {
+        self.find = FindState::default();
+        self
+    }
+
+    pub(crate) fn find_scrolled(&mut self) {
+        self.find.scroll_pending = false;
+    }
+
+    pub(crate) fn row_scroll(&self) -> Option<RowScroll> {
+        self.row_scroll
+    }
+
+    pub(crate) fn row_scrolled(&mut self) {
+        self.row_scroll = None;
+    }
+
+    /// Moves the cursor to the current find match.
+    ///
+    /// Folded sections that contain the match are unfolded.
+    fn jump_to_current_match(&mut self) {
+        let Some(current_match) = self.find.current_match().cloned() else {
+            return;
+        };
+
+        let Some(index) = self
+            .nodes
+            .iter()
+            .rposition(|node| node.source_range.start <= current_match.start)
+        else {
+            return;
+        };
+
+        self.collapsed_ranges
+            .retain(|range| !range.contains(&index));
+
+        let node_start = self.nodes[index].source_range.start;
+        let preceding_text = &self.content[node_start..current_match.start];
+        let row = preceding_text.matches('\n').count();
+        let col = preceding_text
+            .rsplit('\n')
+            .next()
+            .map_or(0, |line| line.chars().count());
+
+        self.current_row = index;
+        self.update_text_buffer();
+        self.text_buffer
+            .cursor_move(CursorMove::Jump(row as u16, col as u16));
+    }
+
+    pub fn scroll_to(&mut self, position: usize) {
+        self.scrollbar.position = position;
+        self.scrollbar.state = self.scrollbar.state.position(position);
+    }
+
     pub fn scroll_up(self, amount: usize) -> Self {
         let new_position = self.scrollbar.position.saturating_sub(amount);
         let new_state = self.scrollbar.state.position(new_position);
@@ -333,14 +1110,222 @@ impl<'text_buffer> EditorState<'text_buffer> {
     }
 
     pub fn set_mode(mut self, mode: Mode) -> Self {
+        if mode != Mode::Edit {
+            self.insert_from_normal = false;
+        }
+        self.vim = self.vim.clear();
         self.mode = mode;
         self
     }
 
+    fn enter_insert_from_normal(mut self) -> Self {
+        self = self.set_mode(Mode::Edit);
+        self.insert_from_normal = true;
+        self
+    }
+
+    fn jump_to_row(mut self, row: usize) -> Self {
+        if self.dirty {
+            self.intermediate_save();
+            self.dirty = false;
+        }
+
+        self.current_row = row;
+        self.update_text_buffer();
+        self.row_scroll = Some(RowScroll::Follow);
+        self
+    }
+
+    /// Deletes the line under the cursor.
+    pub fn delete_line(mut self) -> Self {
+        self.vim = self.vim.clear();
+        self.text_buffer.delete_line();
+        self.dirty = true;
+        self
+    }
+
+    /// Opens a new line below the cursor and enters insert mode.
+    pub fn insert_line_below(mut self) -> Self {
+        self.text_buffer.insert_line_below();
+        self.dirty = true;
+        self.enter_insert_from_normal()
+    }
+
+    /// Opens a new line above the cursor and enters insert mode.
+    pub fn insert_line_above(mut self) -> Self {
+        self.text_buffer.insert_line_above();
+        self.dirty = true;
+        self.enter_insert_from_normal()
+    }
+
+    /// Handles a key in Vim-style normal mode.
+    ///
+    /// Motions are translated into [`CursorMove`]s and edits into the corresponding
+    /// [`TextBuffer`] operations. The `d` and `g` keys are operators that wait for the next key,
+    /// e.g. `dd`, `dw` and `gg`. Any other key cancels the pending operator, as does not pressing
+    /// the next key within [`PENDING_KEY_TIMEOUT`](super::vim::PENDING_KEY_TIMEOUT).
+    pub fn normal_key(mut self, c: char) -> Self {
+        let now = Instant::now();
+
+        if let Some(operator) = self.vim.pending(now) {
+            self.vim = self.vim.clear();
+
+            match (operator, c) {
+                ('d', 'd') => return self.delete_line(),
+                ('d', 'w') => {
+                    self.text_buffer.delete_next_word();
+                    self.dirty = true;
+                }
+                ('g', 'g') => {
+                    self = self.jump_to_row(0);
+                    self.text_buffer.cursor_move(CursorMove::Top);
+                    self.text_buffer.cursor_move(CursorMove::Head);
+                }
+                _ => {}
+            }
+
+            return self;
+        }
+
+        match c {
+            'h' => self.cursor_left(),
+            'j' => self.cursor_down(),
+            'k' => self.cursor_up(),
+            'l' => self.cursor_right(),
+            'w' => self.cursor_word_forward(),
+            'b' => self.cursor_word_backward(),
+            '0' => {
+                self.text_buffer.cursor_move(CursorMove::Head);
+                self
+            }
+            '$' => {
+                // The cursor is placed on the last character instead of after it
+                self.text_buffer.cursor_move(CursorMove::End);
+                if self.text_buffer.cursor().1 > 0 {
+                    self.text_buffer.cursor_move(CursorMove::Left);
+                }
+                self
+            }
+            'G' => {
+                let last_row = self.nodes.len().saturating_sub(1);
+                self = self.jump_to_row(last_row);
+                self.text_buffer.cursor_move(CursorMove::Bottom);
+                self.text_buffer.cursor_move(CursorMove::Head);
+                self
+            }
+            'x' => {
+                let (row, col) = self.text_buffer.cursor();
+                // Unlike in insert mode, deleting at the end of the line does not join lines
+                if self
+                    .text_buffer
+                    .lines()
+                    .get(row)
+                    .is_some_and(|line| col < line.chars().count())
+                {
+                    self.text_buffer.delete_next_char();
+                    self.dirty = true;
+                }
+                self
+            }
+            'i' => self.enter_insert_from_normal(),
+            'a' => self.cursor_right().enter_insert_from_normal(),
+            'o' => self.insert_line_below(),
+            'O' => self.insert_line_above(),
+            'd' | 'g' => {
+                self.vim = self.vim.set_pending(c, now);
+                self
+            }
+            _ => self,
+        }
+    }
+
+    /// Returns the wiki link under the cursor. In read and view modes the cursor does not move
+    /// within the line, so the first link on the cursor line is used instead.
+    pub fn wiki_link_at_cursor(&self) -> Option<WikiLink> {
+        let (row, col) = self.text_buffer.cursor();
+        let line = self.text_buffer.lines().get(row)?;
+
+        WikiLink::at(line, col).or_else(|| match self.mode {
+            Mode::Read | Mode::View => WikiLink::find_all(line)
+                .into_iter()
+                .next()
+                .map(|(_, link)| link),
+            _ => None,
+        })
+    }
+
+    /// Returns the word under the cursor, made of alphanumeric characters, `-` and `_`. Inside a
+    /// wiki link the whole target of the link is returned instead, e.g. `Daily notes` for
+    /// `[[Daily notes]]`.
+    ///
+    /// The cursor may also be right after the last character of the word, like at the end of a
+    /// line in edit mode.
+    pub fn word_at_cursor(&self) -> Option<String> {
+        let (row, col) = self.text_buffer.cursor();
+        let line = self.text_buffer.lines().get(row)?;
+
+        if let Some(link) = WikiLink::at(line, col) {
+            return Some(link.target);
+        }
+
+        let chars: Vec<char> = line.chars().collect();
+        let col = col.min(chars.len());
+        let is_word_char = |c: &&char| c.is_alphanumeric() || matches!(c, '-' | '_');
+
+        let start = col - chars[..col].iter().rev().take_while(is_word_char).count();
+        let end = col + chars[col..].iter().take_while(is_word_char).count();
+
+        (start < end).then(|| chars[start..end].iter().collect())
+    }
+
+    /// Returns the text of the current block to copy. In edit mode the selection is returned if
+    /// there is one, and otherwise the block with its unsaved edits. In other modes the source of
+    /// the node at the current row is returned without trailing newlines.
+    pub fn block_text(&self) -> Option<String> {
+        if self.is_editing() {
+            return Some(
+                self.text_buffer
+                    .selected_text()
+                    .unwrap_or_else(|| self.text_buffer.to_string()),
+            );
+        }
+
+        self.nodes.get(self.current_row).map(|node| {
+            node.source_slice_unchecked(&self.content)
+                .trim_end_matches('\n')
+                .to_string()
+        })
+    }
+
+    /// Cancels the pending key of a multi-key sequence in normal mode.
+    pub fn cancel_pending(mut self) -> Self {
+        self.vim = self.vim.clear();
+        self
+    }
+
     pub fn text_buffer(&self) -> &TextBuffer<'text_buffer> {
         &self.text_buffer
     }
 
+    /// Returns the 0-indexed `(line, column)` of the cursor in the note. The text buffer only
+    /// holds the current block, so the lines before the block are added to its cursor row.
+    pub fn cursor_position(&self) -> (usize, usize) {
+        let (row, col) = self.text_buffer.cursor();
+        let block_line = self.nodes.get(self.current_row).map_or(0, |node| {
+            self.content_slice(..node.source_range.start)
+                .matches('\n')
+                .count()
+        });
+
+        (block_line + row, col)
+    }
+
+    /// Soft-wraps the text buffer to the width, see [`Editor::wrap_width`](super::Editor::wrap_width).
+    pub fn with_wrap_width(mut self, width: Option<usize>) -> Self {
+        self.text_buffer.set_wrap_width(width);
+        self
+    }
+
     pub fn text_buffer_as_mut(&mut self) -> &mut TextBuffer<'text_buffer> {
         self.text_buffer.as_mut()
     }
@@ -354,14 +1339,16 @@ impl<'text_buffer> EditorState<'text_buffer> {
         let text_buffer_content = self.text_buffer().to_string();
         let (_, col) = self.text_buffer.cursor();
         self.text_buffer = TextBuffer::from(format!("{content}\n{text_buffer_content}"))
-            .with_cursor_position((content.lines().count() + 1, col));
+            .with_cursor_position((content.lines().count() + 1, col))
+            .with_wrap_width(self.text_buffer.wrap_width());
     }
 
     pub fn update_text_buffer(&mut self) {
         if let Some(node) = self.nodes().get(self.current_row) {
-            let node_content = self.content_slice(node.source_range.clone());
-            self.text_buffer =
-                TextBuffer::from(node_content).with_cursor_position(self.text_buffer.cursor());
+            let node_content = node.source_slice_unchecked(&self.content);
+            self.text_buffer = TextBuffer::from(node_content)
+                .with_cursor_position(self.text_buffer.cursor())
+                .with_wrap_width(self.text_buffer.wrap_width());
         }
     }
 
@@ -372,3 +1359,544 @@ impl<'text_buffer> EditorState<'text_buffer> {
         }
     }
 }
+
+/// Counts the task items of the nodes, see [`markdown::task_stats`].
+fn task_stats(nodes: &[markdown_parser::Node]) -> markdown::TaskStats {
+    task_items(nodes)
+        .iter()
+        .fold(markdown::TaskStats::default(), |stats, (kind, _)| {
+            markdown::TaskStats {
+                total: stats.total + 1,
+                done: stats.done
+                    + usize::from(!matches!(
+                        kind,
+                        markdown_parser::TaskListItemKind::Unchecked
+                    )),
+            }
+        })
+}
+
+/// Collects the task items and their kinds from the nodes, including the tasks nested in lists
+/// and block quotes.
+fn task_items(
+    nodes: &[markdown_parser::Node],
+) -> Vec<(markdown_parser::TaskListItemKind, &markdown_parser::Node)> {
+    nodes
+        .iter()
+        .flat_map(|node| match &node.markdown_node {
+            markdown_parser::MarkdownNode::TaskListItem { kind, .. } => vec![(kind.clone(), node)],
+            markdown_node => task_items(markdown_node.children()),
+        })
+        .collect()
+}
+
+/// Returns the byte range of the character between the brackets of the task marker, e.g. `x` in
+/// `- [x] Task`, for the task item starting at `item_start`.
+fn task_marker_range(content: &str, item_start: usize) -> Option<Range<usize>> {
+    let item = content.get(item_start..)?;
+
+    // Skip the list marker, e.g. `-` or `1.`, and the following whitespace
+    let marker_end = item.find(char::is_whitespace)?;
+    let bracket_start = marker_end + item[marker_end..].find(|c: char| !c.is_whitespace())?;
+
+    let mut chars = item[bracket_start..].char_indices();
+    match (chars.next(), chars.next(), chars.next()) {
+        (Some((_, '[')), Some((offset, c)), Some((_, ']'))) => {
+            let start = item_start + bracket_start + offset;
+            Some(start..start + c.len_utf8())
+        }
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use basalt_core::obsidian::DEFAULT_MAX_NOTE_BYTES;
+    use indoc::indoc;
+    use tempfile::tempdir;
+
+    use super::*;
+
+    fn normal_keys<'a>(state: EditorState<'a>, keys: &str) -> EditorState<'a> {
+        keys.chars().fold(state, |state, c| state.normal_key(c))
+    }
+
+    #[test]
+    fn test_normal_mode_motions() {
+        let content = indoc! {r#"First line of text
+            Second line of text
+
+            Last paragraph"#};
+
+        let state = EditorState::default()
+            .set_content(content)
+            .set_mode(Mode::Normal);
+
+        let tests = [
+            ("l", (0, 1)),
+            ("lllh", (0, 2)),
+            ("w", (0, 6)),
+            ("wwb", (0, 6)),
+            ("$", (0, 17)),
+            ("$0", (0, 0)),
+            ("j", (1, 0)),
+            ("jk", (0, 0)),
+        ];
+
+        tests.into_iter().for_each(|(keys, expected)| {
+            assert_eq!(
+                normal_keys(state.clone(), keys).text_buffer().cursor(),
+                expected,
+                "{keys}"
+            )
+        });
+
+        let state = normal_keys(state, "G");
+        assert_eq!(state.current_row, 1);
+
+        let state = normal_keys(state, "gg");
+        assert_eq!(state.current_row, 0);
+        assert_eq!(state.text_buffer().cursor(), (0, 0));
+    }
+
+    #[test]
+    fn test_normal_mode_edits() {
+        let content = indoc! {r#"First line of text
+        Second line of text"#};
+
+        let state = EditorState::default()
+            .set_content(content)
+            .set_mode(Mode::Normal);
+
+        let tests = [
+            ("x", "irst line of text\nSecond line of text"),
+            ("$x", "First line of tex\nSecond line of text"),
+            ("dd", "Second line of text"),
+            ("jdd", "First line of text"),
+            ("dw", "line of text\nSecond line of text"),
+            ("wdw", "First of text\nSecond line of text"),
+            // Unsupported motion cancels the pending operator and is not applied
+            ("dlx", "irst line of text\nSecond line of text"),
+        ];
+
+        tests.into_iter().for_each(|(keys, expected)| {
+            assert_eq!(
+                normal_keys(state.clone(), keys).text_buffer().to_string(),
+                expected,
+                "{keys}"
+            )
+        });
+
+        let state = normal_keys(state, "dd").exit_insert();
+        assert_eq!(state.content(), "Second line of text");
+        assert!(state.modified);
+    }
+
+    /// Types the characters in Edit mode. Line breaks are typed with the Enter key.
+    fn type_str<'a>(state: EditorState<'a>, text: &str) -> EditorState<'a> {
+        text.chars().fold(state, |state, c| {
+            state.edit(Input {
+                key: match c {
+                    '\n' => Key::Enter,
+                    c => Key::Char(c),
+                },
+                ..Default::default()
+            })
+        })
+    }
+
+    #[test]
+    fn test_list_continuation_and_renumbering() {
+        let content = indoc! {r#"
+        # List
+
+        1. One
+        2. Two
+           - [ ] Task
+        "#};
+
+        let mut state = EditorState::default()
+            .set_content(content)
+            .set_mode(Mode::Edit)
+            .cursor_down();
+        state.text_buffer_as_mut().cursor_move(CursorMove::End);
+
+        let state = type_str(state, "\nInserted").exit_insert();
+        assert_eq!(
+            state.content(),
+            indoc! {r#"
+            # List
+
+            1. One
+            2. Inserted
+            3. Two
+               - [ ] Task
+            "#}
+        );
+
+        let mut state = state.set_mode(Mode::Edit);
+        state.text_buffer_as_mut().cursor_move(CursorMove::Bottom);
+        state.text_buffer_as_mut().cursor_move(CursorMove::End);
+
+        // Enter on the new empty item removes the marker and ends the list
+        let state = type_str(state, "\nNext task\n\n").exit_insert();
+        assert_eq!(
+            state.content(),
+            indoc! {r#"
+            # List
+
+            1. One
+            2. Inserted
+            3. Two
+               - [ ] Task
+               - [ ] Next task
+
+            "#}
+        );
+    }
+
+    #[test]
+    fn test_undo_redo() {
+        let content = indoc! {r#"
+        # Heading
+
+        Paragraph"#};
+
+        let state = EditorState::default()
+            .set_content(content)
+            .set_mode(Mode::Edit);
+
+        assert!(!state.can_undo());
+
+        let state = type_str(state, "New ").exit_insert();
+        assert_eq!(state.content(), "New # Heading\n\nParagraph");
+        assert!(state.modified);
+
+        let state = state.undo();
+        assert_eq!(state.content(), content);
+        assert_eq!(state.nodes(), markdown_parser::from_str(content));
+        assert!(!state.modified);
+        assert!(!state.can_undo());
+        assert!(state.can_redo());
+
+        let state = state.redo();
+        assert_eq!(state.content(), "New # Heading\n\nParagraph");
+        assert!(state.modified);
+
+        let state = state.undo();
+        assert_eq!(state.content(), content);
+
+        let state = type_str(state.set_mode(Mode::Edit), "New ").exit_insert();
+        let edited_content = state.content().to_string();
+        assert_ne!(edited_content, content);
+
+        let state = state.discard();
+        assert_eq!(state.content(), content);
+        assert!(!state.modified);
+
+        let state = state.undo();
+        assert_eq!(state.content(), edited_content);
+        assert!(state.modified);
+    }
+
+    #[test]
+    fn test_undo_unsaved_edits() {
+        let content = indoc! {r#"
+        # Heading
+
+        Paragraph"#};
+
+        let state = EditorState::default()
+            .set_content(content)
+            .set_mode(Mode::Edit)
+            .cursor_down();
+
+        let mut state = type_str(state, "Edited ").cursor_up();
+        state.text_buffer_as_mut().cursor_move(CursorMove::Head);
+        let state = type_str(state, "Edited ");
+
+        assert_eq!(state.content(), "# Heading\n\nEdited Paragraph");
+        assert!(state.can_undo());
+
+        let state = state.undo();
+        assert_eq!(state.content(), "# Heading\n\nEdited Paragraph");
+        assert_eq!(state.text_buffer().to_string(), "# Heading");
+
+        let state = state.undo();
+        assert_eq!(state.content(), content);
+        assert_eq!(state.text_buffer().to_string(), "# Heading");
+
+        let state = state.redo().redo();
+        assert_eq!(state.content(), "Edited # Heading\n\nEdited Paragraph");
+
+        let state = state.undo().cursor_down();
+        let state = type_str(state, "New ").exit_insert();
+        assert!(!state.can_redo());
+    }
+
+    #[test]
+    fn test_toggle_task() {
+        let content = indoc! {r#"# Tasks
+
+        - [ ] Task
+        - [x] Completed task
+          - [?] Nested task
+        1. [ ] Ordered task
+
+        > - [ ] Quoted task
+        >   continues here
+        "#};
+
+        let state = EditorState::default().set_content(content);
+
+        let toggle_at = |row: usize, line: usize| {
+            let mut state = state.clone().set_row(row);
+            state.update_text_buffer();
+            state
+                .text_buffer_as_mut()
+                .cursor_move(CursorMove::Jump(line as u16, 0));
+            state.toggle_task()
+        };
+
+        let tests = [
+            ((1, 0), "- [x] Task\n"),
+            ((1, 1), "- [ ] Completed task\n"),
+            ((1, 2), "  - [ ] Nested task\n"),
+            ((2, 0), "1. [x] Ordered task\n"),
+            ((3, 0), "> - [x] Quoted task\n"),
+            ((3, 1), "> - [x] Quoted task\n"),
+        ];
+
+        tests.into_iter().for_each(|((row, line), expected)| {
+            let state = toggle_at(row, line);
+            assert!(state.content().contains(expected), "{row}:{line}");
+            assert!(state.modified, "{row}:{line}");
+            assert_eq!(state.current_row, row, "{row}:{line}");
+            assert_eq!(state.text_buffer().cursor(), (line, 0), "{row}:{line}");
+            assert_eq!(state.nodes(), markdown_parser::from_str(state.content()));
+        });
+
+        // The heading does not contain a task
+        let state = toggle_at(0, 0);
+        assert_eq!(state.content(), content);
+        assert!(!state.modified);
+
+        let state = toggle_at(1, 0).toggle_task();
+        assert_eq!(state.content(), content);
+        assert!(!state.modified);
+        assert!(state.undo().content().contains("- [x] Task\n"));
+    }
+
+    #[test]
+    fn test_set_cursor_to_offset() {
+        let state = EditorState::default()
+            .set_content("# Heading\n\nFirst paragraph\n\nSecond paragraph")
+            .set_row(2);
+
+        let tests = [(0, 0), (4, 0), (10, 0), (11, 1), (27, 1), (28, 2), (100, 2)];
+
+        tests.into_iter().for_each(|(offset, row)| {
+            assert_eq!(
+                state.clone().set_cursor_to_offset(offset).current_row,
+                row,
+                "{offset}"
+            );
+        });
+    }
+
+    #[test]
+    fn test_cursor_position() {
+        let content = "# Heading\n\nFirst line\nSecond line\n\n- Item";
+        let state = EditorState::new(content, PathBuf::new()).set_mode(Mode::Edit);
+        assert_eq!(state.cursor_position(), (0, 0));
+
+        // The second line of the paragraph is the fourth line of the note
+        let state = state.cursor_down().cursor_down().cursor_right();
+        assert_eq!(state.current_row, 1);
+        assert_eq!(state.text_buffer().cursor(), (1, 1));
+        assert_eq!(state.cursor_position(), (3, 1));
+
+        let state = state.cursor_down();
+        assert_eq!(state.cursor_position(), (5, 1));
+    }
+
+    #[test]
+    fn test_reload_from_disk() {
+        let dir = tempdir().unwrap();
+        let path = dir.path().join("Note.md");
+        fs::write(&path, "# Note\n\nFirst paragraph\n\nSecond paragraph").unwrap();
+
+        let mut state = EditorState::new(&fs::read_to_string(&path).unwrap(), path.clone())
+            .set_mode(Mode::Edit)
+            .cursor_down()
+            .cursor_down();
+        state.modified = true;
+        assert_eq!(state.current_row, 2);
+
+        // The note was shortened by another application
+        fs::write(&path, "# Changed").unwrap();
+        state.reload_from_disk(DEFAULT_MAX_NOTE_BYTES).unwrap();
+
+        assert!(!state.modified);
+        assert!(!state.can_undo());
+        assert_eq!(state.content(), "# Changed");
+        assert_eq!(state.current_row, 0);
+        assert_eq!(state.text_buffer().to_string(), "# Changed");
+
+        fs::write(&path, "# Too large").unwrap();
+        assert!(matches!(
+            state.reload_from_disk(4),
+            Err(obsidian::Error::TooLarge { .. })
+        ));
+        assert_eq!(state.content(), "# Changed");
+
+        fs::remove_file(&path).unwrap();
+        assert!(state.reload_from_disk(DEFAULT_MAX_NOTE_BYTES).is_err());
+        assert_eq!(state.content(), "# Changed");
+    }
+
+    #[test]
+    fn test_wiki_link_at_cursor() {
+        let content = "See [[First]] and [[Second]]";
+
+        let state = EditorState::default().set_content(content);
+        assert_eq!(
+            state.wiki_link_at_cursor().map(|link| link.target),
+            Some("First".into())
+        );
+
+        let state = state.set_mode(Mode::Normal);
+        assert_eq!(state.wiki_link_at_cursor(), None);
+
+        let state = normal_keys(state, "$");
+        assert_eq!(
+            state.wiki_link_at_cursor().map(|link| link.target),
+            Some("Second".into())
+        );
+    }
+
+    #[test]
+    fn test_cursor_keeps_goal_column() {
+        let content = indoc! {r#"First paragraph line
+            ab
+            Third line of the paragraph
+
+            Short
+
+            Last paragraph of text"#};
+
+        let state = EditorState::default()
+            .set_content(content)
+            .set_mode(Mode::Edit)
+            .cursor_move_col(12);
+
+        let expected = [(0, (1, 2)), (0, (2, 12)), (1, (0, 5)), (2, (0, 12))];
+
+        expected
+            .into_iter()
+            .fold(state, |state, (expected_row, expected_cursor)| {
+                let state = state.cursor_down();
+                assert_eq!(state.current_row, expected_row);
+                assert_eq!(state.text_buffer().cursor(), expected_cursor);
+                state
+            });
+
+        // Moving horizontally resets the goal column
+        let state = EditorState::default()
+            .set_content(content)
+            .set_mode(Mode::Edit)
+            .cursor_move_col(12)
+            .cursor_down()
+            .cursor_left()
+            .cursor_down();
+        assert_eq!(state.text_buffer().cursor(), (2, 1));
+
+        let state = state.cursor_up().cursor_up();
+        assert_eq!(state.text_buffer().cursor(), (0, 1));
+    }
+
+    #[test]
+    fn test_word_at_cursor() {
+        let state = EditorState::default()
+            .set_content("Read well-known_notes, see [[Daily notes]]")
+            .set_mode(Mode::Normal);
+
+        let tests = [
+            (0, Some("Read")),
+            (3, Some("Read")),
+            (4, Some("Read")),
+            (5, Some("well-known_notes")),
+            (12, Some("well-known_notes")),
+            (21, Some("well-known_notes")),
+            (22, None),
+            (30, Some("Daily notes")),
+            (35, Some("Daily notes")),
+        ];
+
+        tests.into_iter().for_each(|(col, expected)| {
+            let state = state.clone().cursor_move_col(col);
+            assert_eq!(state.word_at_cursor().as_deref(), expected, "{col}");
+        });
+
+        let end_of_line = EditorState::default()
+            .set_content("Last word")
+            .set_mode(Mode::Edit)
+            .cursor_move_col(9);
+        assert_eq!(end_of_line.word_at_cursor().as_deref(), Some("word"));
+    }
+
+    #[test]
+    fn test_block_text() {
+        let state = EditorState::default().set_content("# Heading\n\nFirst line\nsecond line");
+        assert_eq!(state.block_text().as_deref(), Some("# Heading"));
+
+        let state = state.cursor_down();
+        assert_eq!(
+            state.block_text().as_deref(),
+            Some("First line\nsecond line")
+        );
+
+        let mut state = state.set_mode(Mode::Edit).cursor_move_col(6);
+        assert_eq!(
+            state.block_text().as_deref(),
+            Some("First line\nsecond line")
+        );
+
+        let textarea = state.text_buffer_as_mut().textarea_as_mut();
+        textarea.start_selection();
+        textarea.move_cursor(tui_textarea::CursorMove::Down);
+        assert_eq!(state.block_text().as_deref(), Some("line\nsecond"));
+    }
+
+    #[test]
+    fn test_vim_mode_label() {
+        let state = EditorState::default().set_mode(Mode::Edit);
+        assert_eq!(state.mode_label(), "EDIT");
+
+        let state = state.set_vim_mode(true);
+        assert_eq!(state.mode_label(), "INSERT");
+        assert_eq!(state.set_mode(Mode::Normal).mode_label(), "NORMAL");
+    }
+
+    #[test]
+    fn test_normal_mode_enter_insert() {
+        let state = EditorState::default()
+            .set_content("Text")
+            .set_mode(Mode::Normal);
+
+        let tests = [("i", (0, 0)), ("a", (0, 1)), ("o", (1, 0)), ("O", (0, 0))];
+
+        tests.into_iter().for_each(|(keys, expected)| {
+            let state = normal_keys(state.clone(), keys);
+            assert_eq!(state.mode(), Mode::Edit, "{keys}");
+            assert!(state.is_insert_from_normal(), "{keys}");
+            assert_eq!(state.text_buffer().cursor(), expected, "{keys}");
+        });
+
+        let below = normal_keys(state.clone(), "o");
+        assert_eq!(below.text_buffer().to_string(), "Text\n");
+
+        let above = normal_keys(state, "O");
+        assert_eq!(above.text_buffer().to_string(), "\nText");
+    }
+}
@@ -1,18 +1,124 @@
 use core::fmt;
+use std::ops::Range;
 
+use textwrap::core::display_width;
 use tui_textarea::{Input, TextArea};
 
-// TODO: Text wrapping according to the available width of the area
 #[derive(Clone, Debug, Default)]
 pub struct TextBuffer<'a> {
     textarea: TextArea<'a>,
     modified: bool,
+    /// The width the lines are soft-wrapped to when displayed. When set, the vertical cursor
+    /// movement follows the visual lines instead of the logical lines.
+    wrap_width: Option<usize>,
+}
+
+/// A part of a logical line that fits on a single row when the line is soft-wrapped.
+#[derive(Clone, Debug, PartialEq)]
+pub struct VisualLine {
+    /// The index of the logical line.
+    pub row: usize,
+    /// The character range of the logical line shown on this visual line.
+    pub cols: Range<usize>,
+}
+
+fn char_width(c: char) -> usize {
+    display_width(c.encode_utf8(&mut [0; 4]))
+}
+
+/// Returns the display width of the characters in the range of the line.
+fn range_width(line: &str, cols: Range<usize>) -> usize {
+    line.chars()
+        .skip(cols.start)
+        .take(cols.len())
+        .map(char_width)
+        .sum()
+}
+
+/// Returns the character column within the range of the line that is shown at the display
+/// column, counted from the start of the range. Wide characters that cover the display column
+/// are moved past only when they fit before it.
+fn col_at_width(line: &str, cols: Range<usize>, width: usize) -> usize {
+    let mut used_width = 0;
+
+    line.chars()
+        .skip(cols.start)
+        .take(cols.len())
+        .take_while(|c| {
+            used_width += char_width(*c);
+            used_width <= width
+        })
+        .count()
+}
+
+/// Returns the byte length of the list item marker at the start of the line, including the
+/// indentation and block quote markers, and the marker that continues the list on the next line.
+///
+/// Ordered items continue with the next number, task items with an unchecked task and bullet
+/// items with the same bullet. Returns [`None`] if the line is not a list item.
+fn list_marker(line: &str) -> Option<(usize, String)> {
+    let content = line.trim_start_matches([' ', '\t', '>']);
+    let prefix = &line[..line.len() - content.len()];
+
+    let (marker_len, next_marker) = match content.as_bytes() {
+        [bullet @ (b'-' | b'*' | b'+'), b' ', b'[', _, b']', b' ', ..] => {
+            (6, format!("{} [ ] ", *bullet as char))
+        }
+        [bullet @ (b'-' | b'*' | b'+'), b' ', ..] => (2, format!("{} ", *bullet as char)),
+        _ => {
+            let digits = content.bytes().take_while(u8::is_ascii_digit).count();
+            let number = content[..digits].parse::<u64>().ok()?;
+            match content.as_bytes()[digits..] {
+                [delimiter @ (b'.' | b')'), b' ', ..] if digits <= 9 => {
+                    (digits + 2, format!("{}{} ", number + 1, delimiter as char))
+                }
+                _ => return None,
+            }
+        }
+    };
+
+    Some((prefix.len() + marker_len, format!("{prefix}{next_marker}")))
+}
+
+/// Splits the line into character ranges that fit within the width. Lines are broken after the
+/// last whitespace that fits, or at the width if a single word is too long.
+pub fn wrap_line(line: &str, width: usize) -> Vec<Range<usize>> {
+    let chars = line.chars().collect::<Vec<_>>();
+    let width = width.max(1);
+
+    let mut ranges = vec![];
+    let mut start = 0;
+
+    loop {
+        let mut line_width = 0;
+        let overflow = chars[start..].iter().position(|c| {
+            line_width += char_width(*c);
+            line_width > width
+        });
+
+        let Some(overflow) = overflow.map(|offset| start + offset) else {
+            ranges.push(start..chars.len());
+            return ranges;
+        };
+
+        let end = chars[start..overflow]
+            .iter()
+            .rposition(|c| c.is_whitespace())
+            .map(|offset| start + offset + 1)
+            .unwrap_or(overflow)
+            .max(start + 1);
+
+        ranges.push(start..end);
+        start = end;
+    }
 }
 
 #[derive(Clone, Debug)]
 pub enum CursorMove {
     Top,
     Bottom,
+    Head,
+    End,
     WordForward,
     WordBackward,
     Up,
@@ -90,6 +196,34 @@ impl<'a> TextBuffer<'a> {
         self.textarea.lines()
     }
 
+    /// Returns the selected text with the lines joined by newlines, or [`None`] if nothing is
+    /// selected.
+    pub fn selected_text(&self) -> Option<String> {
+        let ((start_row, start_col), (end_row, end_col)) = self.textarea.selection_range()?;
+
+        let text = self.lines()[start_row..=end_row]
+            .iter()
+            .enumerate()
+            .map(|(index, line)| {
+                let row = start_row + index;
+                let start = if row == start_row { start_col } else { 0 };
+                let end = if row == end_row {
+                    end_col
+                } else {
+                    line.chars().count()
+                };
+
+                line.chars()
+                    .skip(start)
+                    .take(end.saturating_sub(start))
+                    .collect::<String>()
+            })
+            .collect::<Vec<_>>()
+            .join("\n");
+
+        (!text.is_empty()).then_some(text)
+    }
+
     pub fn modified(&self) -> bool {
         self.modified
     }
@@ -98,12 +232,138 @@ impl<'a> TextBuffer<'a> {
         self.modified = self.textarea.input(input);
     }
 
+    /// Breaks the line at the cursor. At the end of a list item the new line continues the list
+    /// with the next item marker, and on an empty list item the marker is removed instead.
+    pub fn insert_newline(&mut self) {
+        let (row, col) = self.cursor();
+        let line = self.lines().get(row).cloned().unwrap_or_default();
+
+        match list_marker(&line) {
+            Some((marker_len, _)) if line.trim_end().len() < marker_len => {
+                self.textarea.move_cursor(tui_textarea::CursorMove::Head);
+                self.textarea.delete_line_by_end();
+            }
+            Some((_, next_marker)) if col == line.chars().count() => {
+                self.textarea.insert_newline();
+                self.textarea.insert_str(next_marker);
+            }
+            _ => self.textarea.insert_newline(),
+        }
+
+        self.modified = true;
+    }
+
+    pub fn delete_next_char(&mut self) {
+        self.modified = self.textarea.delete_next_char();
+    }
+
+    /// Deletes from the cursor to the start of the next word. If the next word is on another
+    /// line, deletes until the end of the current line.
+    pub fn delete_next_word(&mut self) {
+        let (row, col) = self.cursor();
+        self.textarea
+            .move_cursor(tui_textarea::CursorMove::WordForward);
+
+        let count = match self.cursor() {
+            (next_row, next_col) if next_row == row => next_col.saturating_sub(col),
+            _ => self
+                .lines()
+                .get(row)
+                .map_or(0, |line| line.chars().count().saturating_sub(col)),
+        };
+
+        self.textarea
+            .move_cursor(tui_textarea::CursorMove::Jump(row as u16, col as u16));
+        self.modified = self.textarea.delete_str(count);
+    }
+
+    /// Indents the line under the cursor by two spaces, e.g. to nest a list item under the
+    /// previous one. The cursor stays on the same character.
+    pub fn indent_line(&mut self) {
+        let (row, col) = self.cursor();
+
+        self.textarea.move_cursor(tui_textarea::CursorMove::Head);
+        self.textarea.insert_str("  ");
+        self.textarea
+            .move_cursor(tui_textarea::CursorMove::Jump(row as u16, (col + 2) as u16));
+        self.modified = true;
+    }
+
+    /// Removes up to two spaces of indentation from the line under the cursor. The cursor stays
+    /// on the same character, or moves to the start of the line if it was in the indentation.
+    pub fn outdent_line(&mut self) {
+        let (row, col) = self.cursor();
+        let spaces = self.lines().get(row).map_or(0, |line| {
+            line.chars().take(2).take_while(|c| *c == ' ').count()
+        });
+
+        if spaces == 0 {
+            return;
+        }
+
+        self.textarea.move_cursor(tui_textarea::CursorMove::Head);
+        self.textarea.delete_str(spaces);
+        self.textarea.move_cursor(tui_textarea::CursorMove::Jump(
+            row as u16,
+            col.saturating_sub(spaces) as u16,
+        ));
+        self.modified = true;
+    }
+
+    /// Deletes the line under the cursor and moves the cursor to the start of the following line.
+    pub fn delete_line(&mut self) {
+        let (row, _) = self.cursor();
+        let line_count = self.lines().len();
+
+        if row >= line_count {
+            return;
+        }
+
+        self.textarea.move_cursor(tui_textarea::CursorMove::Head);
+        if !self.lines()[row].is_empty() {
+            self.textarea.delete_line_by_end();
+        }
+
+        // Remove the line break of the now empty line, joining it with the following line, or
+        // with the previous line if it is the last one
+        if row + 1 < line_count {
+            self.textarea.delete_next_char();
+        } else if row > 0 {
+            self.textarea.delete_newline();
+            self.textarea.move_cursor(tui_textarea::CursorMove::Head);
+        }
+
+        self.modified = true;
+    }
+
+    /// Inserts an empty line below the cursor and moves the cursor to it.
+    pub fn insert_line_below(&mut self) {
+        self.textarea.move_cursor(tui_textarea::CursorMove::End);
+        self.textarea.insert_newline();
+        self.modified = true;
+    }
+
+    pub fn insert_line_above(&mut self) {
+        self.textarea.move_cursor(tui_textarea::CursorMove::Head);
+        self.textarea.insert_newline();
+        self.textarea.move_cursor(tui_textarea::CursorMove::Up);
+        self.modified = true;
+    }
+
     pub fn cursor_move(&mut self, cursor_move: CursorMove) {
         match cursor_move {
             CursorMove::Top => self.textarea.move_cursor(tui_textarea::CursorMove::Top),
             CursorMove::Bottom => self.textarea.move_cursor(tui_textarea::CursorMove::Bottom),
-            CursorMove::Up => self.textarea.move_cursor(tui_textarea::CursorMove::Up),
-            CursorMove::Down => self.textarea.move_cursor(tui_textarea::CursorMove::Down),
+            CursorMove::Head => self.textarea.move_cursor(tui_textarea::CursorMove::Head),
+            CursorMove::End => self.textarea.move_cursor(tui_textarea::CursorMove::End),
+            CursorMove::Up => match self.wrap_width {
+                Some(width) => self.move_visual(width, -1),
+                None => self.textarea.move_cursor(tui_textarea::CursorMove::Up),
+            },
+            CursorMove::Down => match self.wrap_width {
+                Some(width) => self.move_visual(width, 1),
+                None => self.textarea.move_cursor(tui_textarea::CursorMove::Down),
+            },
             CursorMove::Left => self.textarea.move_cursor(tui_textarea::CursorMove::Back),
             CursorMove::Right => self.textarea.move_cursor(tui_textarea::CursorMove::Forward),
             CursorMove::WordForward => self
@@ -137,4 +397,332 @@ impl<'a> TextBuffer<'a> {
     pub fn cursor(&self) -> (usize, usize) {
         self.textarea.cursor()
     }
+
+    pub fn wrap_width(&self) -> Option<usize> {
+        self.wrap_width
+    }
+
+    pub fn set_wrap_width(&mut self, width: Option<usize>) {
+        self.wrap_width = width;
+    }
+
+    pub fn with_wrap_width(mut self, width: Option<usize>) -> Self {
+        self.wrap_width = width;
+        self
+    }
+
+    /// Returns the visual lines of the buffer, wrapped to the given width.
+    pub fn visual_lines(&self, width: usize) -> Vec<VisualLine> {
+        self.lines()
+            .iter()
+            .enumerate()
+            .flat_map(|(row, line)| {
+                wrap_line(line, width)
+                    .into_iter()
+                    .map(move |cols| VisualLine { row, cols })
+            })
+            .collect()
+    }
+
+    /// Maps a logical position to the index of the visual line and the column within it.
+    fn to_visual(visual_lines: &[VisualLine], (row, col): (usize, usize)) -> (usize, usize) {
+        let index = visual_lines
+            .iter()
+            .rposition(|line| line.row == row && line.cols.start <= col)
+            .unwrap_or_default();
+
+        let col = visual_lines
+            .get(index)
+            .map_or(0, |line| col.saturating_sub(line.cols.start));
+
+        (index, col)
+    }
+
+    /// Returns the cursor position in visual lines, wrapped to the given width.
+    pub fn visual_cursor(&self, width: usize) -> (usize, usize) {
+        Self::to_visual(&self.visual_lines(width), self.cursor())
+    }
+
+    fn visual_cursor_line(&self) -> Option<(usize, usize)> {
+        self.wrap_width.map(|width| {
+            let visual_lines = self.visual_lines(width);
+            let (index, _) = Self::to_visual(&visual_lines, self.cursor());
+            (index, visual_lines.len())
+        })
+    }
+
+    /// Returns `true` if the cursor is on the first line, taking soft-wrapping into account.
+    pub fn is_cursor_on_first_line(&self) -> bool {
+        match self.visual_cursor_line() {
+            Some((index, _)) => index == 0,
+            None => self.cursor().0 == 0,
+        }
+    }
+
+    /// Returns `true` if the cursor is on the last line, taking soft-wrapping into account.
+    pub fn is_cursor_on_last_line(&self) -> bool {
+        match self.visual_cursor_line() {
+            Some((index, len)) => index + 1 >= len,
+            None => self.cursor().0 + 1 >= self.lines().len(),
+        }
+    }
+
+    /// Returns the display width of the visual line up to the cursor.
+    fn visual_cursor_width(&self, visual_lines: &[VisualLine]) -> (usize, usize) {
+        let (row, col) = self.cursor();
+        let (index, _) = Self::to_visual(visual_lines, (row, col));
+        let start = visual_lines.get(index).map_or(0, |line| line.cols.start);
+        let line = self.lines().get(row).map_or("", String::as_str);
+
+        (index, range_width(line, start..col))
+    }
+
+    /// Moves the cursor by the given amount of visual lines, keeping the visual column when
+    /// possible.
+    fn move_visual(&mut self, width: usize, amount: isize) {
+        let visual_lines = self.visual_lines(width);
+        let (index, col) = self.visual_cursor_width(&visual_lines);
+
+        if let Some(index) = index.checked_add_signed(amount) {
+            self.jump_visual(&visual_lines, index, col);
+        }
+    }
+
+    /// Returns the display column of the cursor, within the visual line when soft-wrapping. Wide
+    /// characters, e.g. CJK characters and emoji, take two columns.
+    pub fn cursor_col(&self) -> usize {
+        match self.wrap_width {
+            Some(width) => self.visual_cursor_width(&self.visual_lines(width)).1,
+            None => {
+                let (row, col) = self.cursor();
+                let line = self.lines().get(row).map_or("", String::as_str);
+                range_width(line, 0..col)
+            }
+        }
+    }
+
+    /// Moves the cursor to the display column of the current line, within the visual line when
+    /// soft-wrapping. The column is clamped to the end of the line.
+    pub fn move_to_col(&mut self, col: usize) {
+        match self.wrap_width {
+            Some(width) => {
+                let visual_lines = self.visual_lines(width);
+                let (index, _) = Self::to_visual(&visual_lines, self.cursor());
+                self.jump_visual(&visual_lines, index, col);
+            }
+            None => {
+                let row = self.cursor().0;
+                let line = self.lines().get(row).map_or("", String::as_str);
+                let col = col_at_width(line, 0..line.chars().count(), col);
+
+                self.textarea
+                    .move_cursor(tui_textarea::CursorMove::Jump(row as u16, col as u16));
+            }
+        }
+    }
+
+    /// Moves the cursor to the display column of the visual line at the index, clamped to the end
+    /// of the visual line.
+    fn jump_visual(&mut self, visual_lines: &[VisualLine], index: usize, col: usize) {
+        let Some(target) = visual_lines.get(index) else {
+            return;
+        };
+
+        let is_last_segment = visual_lines
+            .iter()
+            .rfind(|line| line.row == target.row)
+            .is_some_and(|line| line == target);
+
+        // The end of a wrapped segment is the start of the next one, so the cursor has to stay
+        // before it to remain on the target visual line.
+        let max_col = if is_last_segment {
+            target.cols.end
+        } else {
+            target.cols.end.saturating_sub(1)
+        };
+
+        let line = self.lines().get(target.row).map_or("", String::as_str);
+        let col = col_at_width(line, target.cols.clone(), col);
+
+        self.textarea.move_cursor(tui_textarea::CursorMove::Jump(
+            target.row as u16,
+            (target.cols.start + col).min(max_col) as u16,
+        ));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_wrap_line() {
+        let tests = [
+            ("", 10, vec![Range { start: 0, end: 0 }]),
+            ("Short line", 10, vec![Range { start: 0, end: 10 }]),
+            ("Some words that wrap", 10, vec![0..5, 5..11, 11..20]),
+            ("Averyverylongword", 5, vec![0..5, 5..10, 10..15, 15..17]),
+            ("Ärrä ärrä", 5, vec![0..5, 5..9]),
+        ];
+
+        tests.into_iter().for_each(|(line, width, expected)| {
+            assert_eq!(wrap_line(line, width), expected, "{line}")
+        });
+    }
+
+    #[test]
+    fn test_list_marker() {
+        let tests = [
+            ("- Item", Some((2, "- ".to_string()))),
+            ("* Item", Some((2, "* ".to_string()))),
+            ("  + Nested", Some((4, "  + ".to_string()))),
+            ("- [x] Task", Some((6, "- [ ] ".to_string()))),
+            ("9. Item", Some((3, "10. ".to_string()))),
+            ("   2) Nested", Some((6, "   3) ".to_string()))),
+            ("> 1. Quoted", Some((5, "> 2. ".to_string()))),
+            ("-Item", None),
+            ("1.5 Number", None),
+            ("Paragraph", None),
+            ("", None),
+        ];
+
+        tests
+            .into_iter()
+            .for_each(|(line, expected)| assert_eq!(list_marker(line), expected, "{line}"));
+    }
+
+    #[test]
+    fn test_insert_newline() {
+        let tests = [
+            ("1. One", (0, 6), "1. One\n2. "),
+            ("- [ ] Task", (0, 10), "- [ ] Task\n- [ ] "),
+            // The list continues only from the end of the item
+            ("- Item", (0, 2), "- \nItem"),
+            ("- Item\n- ", (1, 2), "- Item\n"),
+            ("Paragraph", (0, 9), "Paragraph\n"),
+        ];
+
+        tests.into_iter().for_each(|(text, cursor, expected)| {
+            let mut buffer = TextBuffer::from(text).with_cursor_position(cursor);
+            buffer.insert_newline();
+            assert_eq!(buffer.to_string(), expected, "{text}");
+            assert!(buffer.is_modified());
+        });
+    }
+
+    #[test]
+    fn test_delete_line() {
+        let tests = [
+            ("One\nTwo\nThree", (0, 2), "Two\nThree", (0, 0)),
+            ("One\nTwo\nThree", (1, 1), "One\nThree", (1, 0)),
+            ("One\nTwo\nThree", (2, 3), "One\nTwo", (1, 0)),
+            ("One\n\nThree", (1, 0), "One\nThree", (1, 0)),
+            ("One", (0, 1), "", (0, 0)),
+        ];
+
+        tests
+            .into_iter()
+            .for_each(|(text, cursor, expected, expected_cursor)| {
+                let mut buffer = TextBuffer::from(text).with_cursor_position(cursor);
+                buffer.delete_line();
+                assert_eq!(buffer.to_string(), expected, "{text}");
+                assert_eq!(buffer.cursor(), expected_cursor, "{text}");
+                assert!(buffer.is_modified());
+            });
+
+        // The deletion is part of the undo history of the text area
+        let mut buffer = TextBuffer::from("One\nTwo").with_cursor_position((0, 0));
+        buffer.delete_line();
+        while buffer.textarea.undo() {}
+        assert_eq!(buffer.to_string(), "One\nTwo");
+    }
+
+    #[test]
+    fn test_indent_and_outdent_line() {
+        let mut buffer = TextBuffer::from("- One\n- Two").with_cursor_position((1, 4));
+
+        buffer.indent_line();
+        assert_eq!(buffer.to_string(), "- One\n  - Two");
+        assert_eq!(buffer.cursor(), (1, 6));
+        assert!(buffer.is_modified());
+
+        buffer.indent_line();
+        buffer.outdent_line();
+        assert_eq!(buffer.to_string(), "- One\n  - Two");
+        assert_eq!(buffer.cursor(), (1, 6));
+
+        buffer.outdent_line();
+        assert_eq!(buffer.to_string(), "- One\n- Two");
+        assert_eq!(buffer.cursor(), (1, 4));
+
+        // Lines without indentation are left as is
+        buffer.outdent_line();
+        assert_eq!(buffer.to_string(), "- One\n- Two");
+
+        // A single space of indentation is removed, and the cursor does not move past the start
+        let mut buffer = TextBuffer::from(" - Item").with_cursor_position((0, 0));
+        buffer.outdent_line();
+        assert_eq!(buffer.to_string(), "- Item");
+        assert_eq!(buffer.cursor(), (0, 0));
+    }
+
+    #[test]
+    fn test_cursor_col_display_width() {
+        // Each of the CJK characters takes two columns
+        let mut buffer =
+            TextBuffer::from("日本語のテキスト\nabcdefghij").with_cursor_position((0, 2));
+        assert_eq!(buffer.cursor_col(), 4);
+
+        buffer.cursor_move(CursorMove::Jump(1, 0));
+        buffer.move_to_col(4);
+        assert_eq!(buffer.cursor(), (1, 4));
+
+        // A wide character that does not fit before the column is not moved past
+        buffer.cursor_move(CursorMove::Jump(0, 0));
+        buffer.move_to_col(3);
+        assert_eq!(buffer.cursor(), (0, 1));
+
+        buffer.set_wrap_width(Some(6));
+        buffer.cursor_move(CursorMove::Jump(0, 4));
+        assert_eq!(buffer.cursor_col(), 2);
+
+        buffer.cursor_move(CursorMove::Down);
+        assert_eq!(buffer.cursor(), (0, 7));
+        assert_eq!(buffer.cursor_col(), 2);
+
+        buffer.cursor_move(CursorMove::Down);
+        assert_eq!(buffer.cursor(), (1, 2));
+    }
+
+    #[test]
+    fn test_visual_cursor_movement() {
+        let mut buffer = TextBuffer::from("Some words that wrap\nNext");
+        buffer.set_wrap_width(Some(10));
+
+        assert_eq!(buffer.visual_cursor(10), (0, 0));
+        assert!(buffer.is_cursor_on_first_line());
+
+        buffer.cursor_move(CursorMove::Jump(0, 3));
+        buffer.cursor_move(CursorMove::Down);
+        assert_eq!(buffer.cursor(), (0, 8));
+        assert_eq!(buffer.visual_cursor(10), (1, 3));
+
+        buffer.cursor_move(CursorMove::Down);
+        buffer.cursor_move(CursorMove::Down);
+        assert_eq!(buffer.cursor(), (1, 3));
+        assert_eq!(buffer.visual_cursor(10), (3, 3));
+        assert!(buffer.is_cursor_on_last_line());
+
+        buffer.cursor_move(CursorMove::Down);
+        assert_eq!(buffer.cursor(), (1, 3));
+
+        buffer.cursor_move(CursorMove::Up);
+        buffer.cursor_move(CursorMove::Up);
+        assert_eq!(buffer.cursor(), (0, 8));
+
+        // Without a wrap width the cursor moves by logical lines
+        buffer.set_wrap_width(None);
+        buffer.cursor_move(CursorMove::Down);
+        assert_eq!(buffer.cursor(), (1, 4));
+    }
 }
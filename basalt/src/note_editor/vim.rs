@@ -0,0 +1,135 @@
+use std::time::{Duration, Instant};
+
+use ratatui::crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+
+use crate::app::note_editor::Message;
+
+/// How long a pending key, e.g. the first `g` of `gg`, waits for the next key before it is
+/// discarded.
+pub const PENDING_KEY_TIMEOUT: Duration = Duration::from_secs(1);
+
+/// Vim-style modal layer for the note editor.
+///
+/// Translates keys pressed in normal mode into note editor messages and keeps track of the
+/// pending key of a multi-key sequence like `dd` or `gg`.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct VimState {
+    pending: Option<(char, Instant)>,
+}
+
+impl VimState {
+    /// Returns the pending key, unless it has timed out.
+    pub fn pending(&self, now: Instant) -> Option<char> {
+        self.pending
+            .filter(|(_, pressed_at)| now.duration_since(*pressed_at) < PENDING_KEY_TIMEOUT)
+            .map(|(c, _)| c)
+    }
+
+    pub fn set_pending(self, c: char, now: Instant) -> Self {
+        Self {
+            pending: Some((c, now)),
+        }
+    }
+
+    pub fn clear(self) -> Self {
+        Self { pending: None }
+    }
+
+    /// Translates a key pressed in normal mode into a message. Keys that are not part of normal
+    /// mode return `None` and fall back to the configured key bindings.
+    pub fn handle_key(&self, key: &KeyEvent, now: Instant) -> Option<Message> {
+        if key
+            .modifiers
+            .intersects(KeyModifiers::CONTROL | KeyModifiers::ALT)
+        {
+            return None;
+        }
+
+        let KeyCode::Char(c) = key.code else {
+            return None;
+        };
+
+        // Any key completes or cancels the pending sequence
+        if let Some(pending) = self.pending(now) {
+            return match (pending, c) {
+                ('d', 'd') => Some(Message::DeleteLine),
+                ('g', 'f') => Some(Message::FollowLink),
+                _ => Some(Message::NormalKey(c)),
+            };
+        }
+
+        match c {
+            'h' => Some(Message::CursorLeft),
+            'j' => Some(Message::CursorDown),
+            'k' => Some(Message::CursorUp),
+            'l' => Some(Message::CursorRight),
+            'w' => Some(Message::CursorWordForward),
+            'b' => Some(Message::CursorWordBackward),
+            'u' => Some(Message::Undo),
+            'o' => Some(Message::InsertLineBelow),
+            'O' => Some(Message::InsertLineAbove),
+            '0' | '$' | 'g' | 'G' | 'i' | 'a' | 'x' | 'd' => Some(Message::NormalKey(c)),
+            _ => None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn key(c: char) -> KeyEvent {
+        KeyEvent::new(KeyCode::Char(c), KeyModifiers::empty())
+    }
+
+    #[test]
+    fn test_handle_key() {
+        let now = Instant::now();
+        let vim = VimState::default();
+
+        let tests = [
+            ('j', Some(Message::CursorDown)),
+            ('u', Some(Message::Undo)),
+            ('o', Some(Message::InsertLineBelow)),
+            ('O', Some(Message::InsertLineAbove)),
+            ('g', Some(Message::NormalKey('g'))),
+            ('z', None),
+        ];
+
+        tests
+            .into_iter()
+            .for_each(|(c, expected)| assert_eq!(vim.handle_key(&key(c), now), expected, "{c}"));
+
+        assert_eq!(
+            vim.handle_key(
+                &KeyEvent::new(KeyCode::Char('u'), KeyModifiers::CONTROL),
+                now
+            ),
+            None
+        );
+    }
+
+    #[test]
+    fn test_pending_key_timeout() {
+        let now = Instant::now();
+        let vim = VimState::default().set_pending('d', now);
+
+        assert_eq!(vim.pending(now), Some('d'));
+        assert_eq!(vim.handle_key(&key('d'), now), Some(Message::DeleteLine));
+        assert_eq!(
+            vim.handle_key(&key('z'), now),
+            Some(Message::NormalKey('z'))
+        );
+
+        let later = now + PENDING_KEY_TIMEOUT;
+        assert_eq!(vim.pending(later), None);
+        assert_eq!(
+            vim.handle_key(&key('d'), later),
+            Some(Message::NormalKey('d'))
+        );
+        assert_eq!(vim.clear().pending(now), None);
+
+        let vim = VimState::default().set_pending('g', now);
+        assert_eq!(vim.handle_key(&key('f'), now), Some(Message::FollowLink));
+    }
+}
@@ -6,12 +6,14 @@ mod state;
 
 use ratatui::{
     buffer::Buffer,
-    layout::{Alignment, Rect},
+    layout::{Alignment, Constraint, Layout, Rect},
     style::{Style, Stylize},
     text::{Line, Span},
-    widgets::{Block, BorderType, Borders, List, ListItem, Padding, StatefulWidget},
+    widgets::{Block, BorderType, Borders, List, ListItem, Padding, StatefulWidget, Widget},
 };
 
+use crate::config::Theme;
+
 /// Outline needs to produce a similar tree like structure as in the explorer module, which means
 /// that there is potential for generalizing a widget for displaying a 'tree'.
 ///
@@ -20,7 +22,15 @@ use ratatui::{
 ///
 /// These indices can be used to mark the location of the node for scrolling.
 #[derive(Default)]
-pub struct Outline;
+pub struct Outline {
+    theme: Theme,
+}
+
+impl Outline {
+    pub fn with_theme(self, theme: &Theme) -> Self {
+        Self { theme: *theme }
+    }
+}
 
 trait AsListItems {
     fn to_list_items(&self) -> Vec<ListItem<'_>>;
@@ -92,6 +102,11 @@ impl StatefulWidget for Outline {
             } else {
                 BorderType::Rounded
             })
+            .border_style(if state.active {
+                Style::new().fg(self.theme.border_active)
+            } else {
+                Style::new()
+            })
             .title(if state.is_open() {
                 " ▶ Outline "
             } else {
@@ -107,15 +122,35 @@ impl StatefulWidget for Outline {
             state.items.to_collapsed_items()
         };
 
-        List::new(items)
-            .block(if state.is_open() {
-                block
-            } else {
-                block.borders(Borders::RIGHT | Borders::TOP | Borders::BOTTOM)
-            })
-            .highlight_style(Style::default().reversed().dark_gray())
-            .highlight_symbol("")
-            .render(area, buf, &mut state.list_state);
+        let block = if state.is_open() {
+            block
+        } else {
+            block.borders(Borders::RIGHT | Borders::TOP | Borders::BOTTOM)
+        };
+
+        let inner_area = block.inner(area);
+        block.render(area, buf);
+
+        let list_area = match &state.search_query {
+            Some(query) if state.is_open() => {
+                let [query_area, list_area] =
+                    Layout::vertical([Constraint::Length(1), Constraint::Fill(1)])
+                        .areas(inner_area);
+
+                Line::from(format!("/ {query}"))
+                    .dim()
+                    .render(query_area, buf);
+
+                list_area
+            }
+            _ => inner_area,
+        };
+
+        let list = List::new(items)
+            .highlight_style(Style::default().reversed().fg(self.theme.selection))
+            .highlight_symbol("");
+
+        StatefulWidget::render(list, list_area, buf, &mut state.list_state);
     }
 }
 
@@ -278,14 +313,83 @@ mod tests {
             _ = terminal.clear();
             terminal
                 .draw(|frame| {
-                    Outline.render(
+                    Outline::default().render(
                         frame.area(),
                         frame.buffer_mut(),
-                        &mut OutlineState::new(&nodes, 0, true).expand_all(),
+                        &mut OutlineState::new(&nodes, &[], 0, true).expand_all(),
                     )
                 })
                 .unwrap();
             assert_snapshot!(name, terminal.backend());
         });
     }
+
+    #[test]
+    fn test_outline_search() {
+        let nodes = markdown_parser::from_str(indoc! {r#"
+            # Overview
+            ## Setup
+            ### Install
+            ### Make targets
+            ## Usage
+            ### Keymaps
+            #### Notes
+            ## Reference
+        "#});
+
+        let state = OutlineState::new(&nodes, &[], 0, true).search("kE");
+        assert!(state.is_searching());
+        assert_eq!(
+            state
+                .items
+                .flatten()
+                .iter()
+                .map(|item| match item {
+                    Item::Heading { content, .. } | Item::HeadingEntry { content, .. } => {
+                        content.as_str()
+                    }
+                })
+                .collect::<Vec<_>>(),
+            ["Overview", "Setup", "Make targets", "Usage", "Keymaps"]
+        );
+
+        let mut terminal = Terminal::new(TestBackend::new(30, 10)).unwrap();
+        terminal
+            .draw(|frame| {
+                Outline::default().render(frame.area(), frame.buffer_mut(), &mut state.clone())
+            })
+            .unwrap();
+        assert_snapshot!("search_h3_entries", terminal.backend());
+
+        let state = state.search_input('y').search_backspace().search_input('x');
+        assert!(state.items.is_empty());
+        assert_eq!(state.selected(), None);
+
+        let state = state.search_backspace().next(2).clear_search();
+        assert!(!state.is_searching());
+        assert_eq!(state.items, OutlineState::new(&nodes, &[], 0, true).items);
+        assert_eq!(state.selected().map(|item| item.get_range().start), Some(3));
+    }
+
+    #[test]
+    fn test_find_by_heading_text() {
+        let nodes = markdown_parser::from_str(indoc! {r#"
+            # Overview
+            ## Setup
+            ### Install
+            ## Usage
+            ### Install
+        "#});
+
+        let state = OutlineState::new(&nodes, &[], 0, true);
+        assert_eq!(state.find_by_heading_text("Overview"), Some(0));
+        assert_eq!(state.find_by_heading_text("Usage"), Some(3));
+        assert_eq!(state.find_by_heading_text("Install"), Some(2));
+        assert_eq!(state.find_by_heading_text("install"), None);
+
+        // Headings of collapsed entries are not visible
+        let state = state.collapse_all();
+        assert_eq!(state.find_by_heading_text("Overview"), Some(0));
+        assert_eq!(state.find_by_heading_text("Usage"), None);
+    }
 }
@@ -21,6 +21,24 @@ impl Item {
             Item::Heading { range, .. } | Item::HeadingEntry { range, .. } => range,
         }
     }
+
+    /// Returns the text of the heading.
+    pub fn content(&self) -> &str {
+        match self {
+            Item::Heading { content, .. } | Item::HeadingEntry { content, .. } => content,
+        }
+    }
+
+    /// Returns `true` if the item has children that can be expanded or collapsed.
+    pub fn is_expandable(&self) -> bool {
+        matches!(self, Item::HeadingEntry { .. })
+    }
+
+    /// Returns `true` if the item has children that are listed in the outline.
+    pub fn is_expanded(&self) -> bool {
+        matches!(self, Item::HeadingEntry { expanded: true, .. })
+    }
+
     fn contains_index(&self, index: usize) -> bool {
         self.get_range().contains(&index)
     }
@@ -14,6 +14,11 @@ pub struct OutlineState {
     pub(crate) open: bool,
     pub(crate) list_state: ListState,
     pub(crate) active: bool,
+    /// The search query while the search is active. Only the headings containing the query are
+    /// listed in `items`.
+    pub(crate) search_query: Option<String>,
+    /// The unfiltered outline tree, which is restored when the search is cleared.
+    unfiltered_items: Vec<Item>,
 }
 
 #[derive(Debug, Clone, PartialEq)]
@@ -128,7 +133,14 @@ impl HeadingsAsItems for Vec<Heading> {
 }
 
 impl OutlineState {
-    pub fn new(nodes: &[Node], index: usize, open: bool) -> Self {
+    /// Creates the outline of the nodes. The headings are expanded unless their section is one of
+    /// the collapsed ranges of the note editor, see [`OutlineState::set_folds`].
+    pub fn new(
+        nodes: &[Node],
+        collapsed_ranges: &[Range<usize>],
+        index: usize,
+        open: bool,
+    ) -> Self {
         let headings = nodes.to_headings();
         let max_heading_count = headings.len();
 
@@ -140,16 +152,138 @@ impl OutlineState {
             list_state: ListState::default(),
             ..Default::default()
         }
+        .set_folds(collapsed_ranges)
         .select_at(index)
-        .expand_all()
     }
 
-    pub fn set_nodes(mut self, nodes: &[Node]) -> Self {
+    /// Replaces the outline with the headings of the nodes, see [`OutlineState::new`]. An active
+    /// search is applied to the new outline.
+    pub fn set_nodes(mut self, nodes: &[Node], collapsed_ranges: &[Range<usize>]) -> Self {
         let headings = nodes.to_headings();
         let max_heading_count = headings.len();
         self.max_heading_count = max_heading_count;
         self.items = headings.to_items(nodes.len());
-        self.expand_all()
+
+        match self.search_query.take() {
+            Some(query) => self.set_folds(collapsed_ranges).search(&query),
+            None => self.set_folds(collapsed_ranges),
+        }
+    }
+
+    fn folded_items(items: &[Item], collapsed_ranges: &[Range<usize>]) -> Vec<Item> {
+        items
+            .iter()
+            .map(|item| match item {
+                Item::HeadingEntry {
+                    range,
+                    content,
+                    children,
+                    ..
+                } => Item::HeadingEntry {
+                    range: range.clone(),
+                    content: content.clone(),
+                    children: Self::folded_items(children, collapsed_ranges),
+                    // The folded section of a heading starts right after the heading
+                    expanded: !collapsed_ranges
+                        .iter()
+                        .any(|collapsed| collapsed.start == range.start + 1),
+                },
+                heading => heading.clone(),
+            })
+            .collect()
+    }
+
+    /// Collapses the headings whose section is one of the collapsed ranges of the note editor and
+    /// expands the other headings, so that the outline matches the folds of the note editor.
+    pub fn set_folds(mut self, collapsed_ranges: &[Range<usize>]) -> Self {
+        self.items = Self::folded_items(&self.items, collapsed_ranges);
+        self
+    }
+
+    fn search_items(items: &[Item], query: &str) -> Vec<Item> {
+        let matches = |content: &str| content.to_lowercase().contains(query);
+
+        items
+            .iter()
+            .filter_map(|item| match item {
+                Item::Heading { content, .. } => matches(content).then(|| item.clone()),
+                Item::HeadingEntry {
+                    range,
+                    content,
+                    children,
+                    ..
+                } => {
+                    let children = Self::search_items(children, query);
+
+                    match (children.is_empty(), matches(content)) {
+                        (true, false) => None,
+                        (true, true) => Some(Item::Heading {
+                            range: range.clone(),
+                            content: content.clone(),
+                        }),
+                        (false, _) => Some(Item::HeadingEntry {
+                            range: range.clone(),
+                            content: content.clone(),
+                            children,
+                            expanded: true,
+                        }),
+                    }
+                }
+            })
+            .collect()
+    }
+
+    /// Returns a state listing only the headings that contain the query, ignoring case.
+    ///
+    /// Headings that do not match are kept expanded when they have a matching child, so that the
+    /// tree structure is preserved. The search always applies to the unfiltered outline, so the
+    /// query can be changed without clearing the search first.
+    pub fn search(&self, query: &str) -> Self {
+        let unfiltered_items = match self.search_query {
+            Some(_) => self.unfiltered_items.clone(),
+            None => self.items.clone(),
+        };
+
+        let items = Self::search_items(&unfiltered_items, &query.to_lowercase());
+        let selected = (!items.is_empty()).then_some(0);
+
+        Self {
+            items,
+            unfiltered_items,
+            search_query: Some(query.to_string()),
+            list_state: self.list_state.clone().with_selected(selected),
+            ..self.clone()
+        }
+    }
+
+    pub fn is_searching(&self) -> bool {
+        self.search_query.is_some()
+    }
+
+    pub fn search_input(self, c: char) -> Self {
+        let query = format!("{}{c}", self.search_query.as_deref().unwrap_or_default());
+        self.search(&query)
+    }
+
+    pub fn search_backspace(self) -> Self {
+        let mut query = self.search_query.clone().unwrap_or_default();
+        query.pop();
+        self.search(&query)
+    }
+
+    /// Restores the unfiltered outline and keeps the selected heading selected.
+    pub fn clear_search(mut self) -> Self {
+        if self.search_query.take().is_none() {
+            return self;
+        }
+
+        let selected = self.selected().map(|item| item.get_range().start);
+        self.items = std::mem::take(&mut self.unfiltered_items);
+
+        match selected {
+            Some(index) => self.select_at(index),
+            None => self,
+        }
     }
 
     pub fn selected(&self) -> Option<Item> {
@@ -197,7 +331,7 @@ impl OutlineState {
                 };
 
                 Item::HeadingEntry {
-                    range: heading_range.clone(),
+                    range: heading_range,
                     content,
                     expanded,
                     children: children
@@ -236,6 +370,14 @@ impl OutlineState {
         self
     }
 
+    /// Returns the index of the first visible item whose heading text is the given text.
+    pub fn find_by_heading_text(&self, text: &str) -> Option<usize> {
+        self.items
+            .flatten()
+            .iter()
+            .position(|item| item.content() == text)
+    }
+
     fn expanded_to_all_items(items: &[Item], expanded: bool) -> Vec<Item> {
         items
             .iter()
@@ -270,6 +412,22 @@ impl OutlineState {
         self.open
     }
 
+    /// Returns the number of visible items.
+    pub fn item_count(&self) -> usize {
+        self.items.flatten().len()
+    }
+
+    /// Moves the cursor to the item at the given index of the visible items.
+    ///
+    /// Returns the state unchanged if the index is out of bounds.
+    pub fn select_index(mut self, index: usize) -> Self {
+        if index < self.item_count() {
+            self.list_state.select(Some(index));
+        }
+
+        self
+    }
+
     pub fn next(mut self, amount: usize) -> Self {
         let index = self
             .list_state
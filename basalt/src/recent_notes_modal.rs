@@ -0,0 +1,180 @@
+use std::path::PathBuf;
+
+use ratatui::{
+    buffer::Buffer,
+    layout::{Constraint, Flex, Layout, Rect},
+    style::{Color, Style, Stylize},
+    widgets::{
+        Block, BorderType, Clear, List, ListItem, ListState, StatefulWidget, StatefulWidgetRef,
+        Widget,
+    },
+};
+
+use crate::config::Theme;
+
+/// A recently opened note listed in the modal.
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct RecentNote {
+    /// The vault-relative path of the note without the `.md` extension.
+    pub name: String,
+    pub path: PathBuf,
+}
+
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct RecentNotesModalState {
+    pub items: Vec<RecentNote>,
+    pub visible: bool,
+    list_state: ListState,
+}
+
+impl RecentNotesModalState {
+    /// Shows the modal with the given notes, the most recent first. The previous note is
+    /// highlighted, since the first note is the one that is already open.
+    pub fn show(&self, items: Vec<RecentNote>) -> Self {
+        let selected = (!items.is_empty()).then(|| 1.min(items.len() - 1));
+
+        Self {
+            items,
+            visible: true,
+            list_state: ListState::default().with_selected(selected),
+        }
+    }
+
+    pub fn hide(&self) -> Self {
+        Self {
+            visible: false,
+            ..self.clone()
+        }
+    }
+
+    /// Returns the highlighted note.
+    pub fn selected(&self) -> Option<&RecentNote> {
+        self.list_state
+            .selected()
+            .and_then(|index| self.items.get(index))
+    }
+
+    pub fn next(&self) -> Self {
+        let index = self
+            .list_state
+            .selected()
+            .map(|index| (index + 1).min(self.items.len().saturating_sub(1)));
+
+        Self {
+            list_state: self.list_state.clone().with_selected(index),
+            ..self.clone()
+        }
+    }
+
+    pub fn previous(&self) -> Self {
+        let index = self
+            .list_state
+            .selected()
+            .map(|index| index.saturating_sub(1));
+
+        Self {
+            list_state: self.list_state.clone().with_selected(index),
+            ..self.clone()
+        }
+    }
+}
+
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct RecentNotesModal {
+    theme: Theme,
+}
+
+impl RecentNotesModal {
+    pub fn with_theme(self, theme: &Theme) -> Self {
+        Self { theme: *theme }
+    }
+
+    fn modal_area(area: Rect) -> Rect {
+        let vertical = Layout::vertical([Constraint::Percentage(50)]).flex(Flex::Center);
+        let horizontal = Layout::horizontal([Constraint::Length(60)]).flex(Flex::Center);
+        let [area] = vertical.areas(area);
+        let [area] = horizontal.areas(area);
+        area
+    }
+}
+
+impl StatefulWidget for RecentNotesModal {
+    type State = RecentNotesModalState;
+
+    fn render(self, area: Rect, buf: &mut Buffer, state: &mut Self::State) {
+        let area = RecentNotesModal::modal_area(area);
+        Widget::render(Clear, area, buf);
+
+        let items: Vec<ListItem> = state
+            .items
+            .iter()
+            .map(|item| ListItem::new(format!("  {}", item.name)))
+            .collect();
+
+        let block = Block::bordered()
+            .dark_gray()
+            .title(" Recent notes ")
+            .title_style(Style::default().italic().bold())
+            .border_type(BorderType::Rounded);
+
+        List::new(items)
+            .block(block)
+            .fg(Color::default())
+            .highlight_style(Style::new().reversed().fg(self.theme.selection))
+            .highlight_symbol(" ")
+            .render_ref(area, buf, &mut state.list_state);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use insta::assert_snapshot;
+    use ratatui::{backend::TestBackend, Terminal};
+
+    #[test]
+    fn test_recent_notes_modal() {
+        let items = ["Daily", "Projects/Basalt", "Ideas"]
+            .map(|name| RecentNote {
+                name: name.to_string(),
+                path: PathBuf::from(format!("{name}.md")),
+            })
+            .to_vec();
+
+        let state = RecentNotesModalState::default().show(items);
+        assert_eq!(
+            state.selected().map(|note| note.name.as_str()),
+            Some("Projects/Basalt")
+        );
+        assert_eq!(
+            state
+                .next()
+                .next()
+                .selected()
+                .map(|note| note.name.as_str()),
+            Some("Ideas")
+        );
+        assert_eq!(
+            state
+                .previous()
+                .previous()
+                .selected()
+                .map(|note| note.name.as_str()),
+            Some("Daily")
+        );
+
+        let mut state = state.next();
+        let mut terminal = Terminal::new(TestBackend::new(60, 10)).unwrap();
+        terminal
+            .draw(|frame| {
+                RecentNotesModal::default().render(frame.area(), frame.buffer_mut(), &mut state)
+            })
+            .unwrap();
+        assert_snapshot!(terminal.backend());
+
+        assert_eq!(
+            RecentNotesModalState::default().show(vec![]).selected(),
+            None
+        );
+    }
+}
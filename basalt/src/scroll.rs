@@ -0,0 +1,35 @@
+/// Calculates the vertical offset of list items in rows.
+///
+/// When the selected item is near the end of the list and there aren't enough items
+/// remaining to keep the selection vertically centered, we shift the offset to show
+/// as many trailing items as possible instead of centering the selection.
+///
+/// This prevents empty lines from appearing at the bottom of the list when the
+/// selection moves toward the end.
+///
+/// Without this check, you'd see output like:
+/// ╭────────╮
+/// │ 3 item │
+/// │>4 item │
+/// │ 5 item │
+/// │        │
+/// ╰────────╯
+///
+/// With this check, the list scrolls up to fill the remaining space:
+/// ╭────────╮
+/// │ 2 item │
+/// │ 3 item │
+/// │>4 item │
+/// │ 5 item │
+/// ╰────────╯
+///
+/// The goal is to avoid showing unnecessary blank rows and to maximize visible items.
+pub(crate) fn calculate_offset(row: usize, items_count: usize, window_height: usize) -> usize {
+    let half = window_height / 2;
+
+    if row + half > items_count.saturating_sub(1) {
+        items_count.saturating_sub(window_height)
+    } else {
+        row.saturating_sub(half)
+    }
+}
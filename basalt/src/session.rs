@@ -0,0 +1,186 @@
+use std::{
+    fs, io,
+    path::{Path, PathBuf},
+};
+
+use basalt_core::obsidian::Vault;
+use etcetera::{choose_base_strategy, BaseStrategy};
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, thiserror::Error)]
+pub enum SessionError {
+    // Standard IO error, from [`std::io::Error`].
+    #[error(transparent)]
+    Io(#[from] io::Error),
+    // Occurs when the home directory cannot be located, from [`etcetera::HomeDirError`].
+    #[error(transparent)]
+    HomeDir(#[from] etcetera::HomeDirError),
+    /// TOML deserialization error, from [`toml::de::Error`].
+    #[error(transparent)]
+    Toml(#[from] toml::de::Error),
+    /// TOML serialization error, from [`toml::ser::Error`].
+    #[error(transparent)]
+    TomlSerialize(#[from] toml::ser::Error),
+}
+
+/// The vault and note that were open when basalt was closed.
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub struct Session {
+    /// Name of the open vault.
+    pub vault: String,
+    /// Path of the open note, if a note was open.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub note: Option<PathBuf>,
+}
+
+impl Session {
+    /// Finds the vault of the session from the given vaults.
+    ///
+    /// Returns `None` if the session is stale, i.e. the vault no longer exists or the note was
+    /// deleted or moved.
+    pub fn resolve<'a>(&self, vaults: &[&'a Vault]) -> Option<&'a Vault> {
+        let vault = vaults
+            .iter()
+            .find(|vault| vault.name == self.vault && vault.path.is_dir())?;
+
+        match &self.note {
+            Some(note) if !note.is_file() => None,
+            _ => Some(vault),
+        }
+    }
+}
+
+/// Returns the path of the session file in the user's state directory:
+/// `$XDG_STATE_HOME/basalt/session.toml`.
+///
+/// Platforms without a state directory use the data directory instead.
+fn session_path() -> Result<PathBuf, SessionError> {
+    let strategy = choose_base_strategy()?;
+
+    Ok(strategy
+        .state_dir()
+        .unwrap_or_else(|| strategy.data_dir())
+        .join("basalt/session.toml"))
+}
+
+/// Reads the session from the file at the given path.
+///
+/// Returns `Ok(None)` if the file does not exist.
+pub fn load_from(path: &Path) -> Result<Option<Session>, SessionError> {
+    match fs::read_to_string(path) {
+        Ok(contents) => Ok(Some(toml::from_str(&contents)?)),
+        Err(error) if error.kind() == io::ErrorKind::NotFound => Ok(None),
+        Err(error) => Err(error.into()),
+    }
+}
+
+/// Writes the session to the file at the given path, creating the parent directories.
+pub fn save_to(path: &Path, session: &Session) -> Result<(), SessionError> {
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)?;
+    }
+
+    fs::write(path, toml::to_string(session)?)?;
+    Ok(())
+}
+
+/// Reads the session of the previous run.
+///
+/// Returns `Ok(None)` if there is no previous session.
+pub fn load() -> Result<Option<Session>, SessionError> {
+    load_from(&session_path()?)
+}
+
+/// Stores the session, so that it can be restored on the next run.
+pub fn save(session: &Session) -> Result<(), SessionError> {
+    save_to(&session_path()?, session)
+}
+
+#[cfg(test)]
+mod tests {
+    use indoc::indoc;
+    use tempfile::tempdir;
+
+    use super::*;
+
+    #[test]
+    fn test_session_round_trip() {
+        let tests = [
+            (
+                Session {
+                    vault: "Notes".into(),
+                    note: Some("/home/user/Notes/Daily.md".into()),
+                },
+                indoc! {r#"
+                    vault = "Notes"
+                    note = "/home/user/Notes/Daily.md"
+                "#},
+            ),
+            (
+                Session {
+                    vault: "Work vault".into(),
+                    note: None,
+                },
+                indoc! {r#"
+                    vault = "Work vault"
+                "#},
+            ),
+        ];
+
+        tests.into_iter().for_each(|(session, expected)| {
+            let serialized = toml::to_string(&session).unwrap();
+            assert_eq!(serialized, expected);
+            assert_eq!(toml::from_str::<Session>(&serialized).unwrap(), session);
+        });
+    }
+
+    #[test]
+    fn test_save_and_load() {
+        let dir = tempdir().unwrap();
+        let path = dir.path().join("basalt/session.toml");
+        assert!(load_from(&path).unwrap().is_none());
+
+        let session = Session {
+            vault: "Notes".into(),
+            note: Some(dir.path().join("Notes/Daily.md")),
+        };
+        save_to(&path, &session).unwrap();
+        assert_eq!(load_from(&path).unwrap(), Some(session));
+
+        fs::write(&path, "vault = ").unwrap();
+        assert!(matches!(load_from(&path), Err(SessionError::Toml(_))));
+    }
+
+    #[test]
+    fn test_resolve() {
+        let dir = tempdir().unwrap();
+        let note = dir.path().join("Daily.md");
+        fs::write(&note, "# Daily").unwrap();
+
+        let notes = Vault {
+            name: "Notes".into(),
+            path: dir.path().to_path_buf(),
+            ..Default::default()
+        };
+        let missing = Vault {
+            name: "Missing".into(),
+            path: dir.path().join("missing"),
+            ..Default::default()
+        };
+        let vaults = [&notes, &missing];
+
+        let session = |vault: &str, note: Option<&Path>| Session {
+            vault: vault.into(),
+            note: note.map(Path::to_path_buf),
+        };
+
+        assert_eq!(session("Notes", Some(&note)).resolve(&vaults), Some(&notes));
+        assert_eq!(session("Notes", None).resolve(&vaults), Some(&notes));
+        assert_eq!(
+            session("Notes", Some(&dir.path().join("Deleted.md"))).resolve(&vaults),
+            None
+        );
+        assert_eq!(session("Missing", None).resolve(&vaults), None);
+        assert_eq!(session("Unknown", None).resolve(&vaults), None);
+    }
+}
@@ -1,15 +1,18 @@
-use std::marker::PhantomData;
+use std::{marker::PhantomData, path::PathBuf};
 
-use basalt_core::obsidian::Vault;
+use basalt_core::obsidian::{Error, ObsidianConfig, Vault};
 use ratatui::{
     buffer::Buffer,
     layout::{Constraint, Flex, Layout, Rect},
     style::Stylize,
     text::Text,
-    widgets::{StatefulWidgetRef, Widget},
+    widgets::{Paragraph, StatefulWidgetRef, Widget, Wrap},
 };
 
-use crate::vault_selector::{VaultSelector, VaultSelectorState};
+use crate::{
+    config::Theme,
+    vault_selector::{VaultSelector, VaultSelectorState},
+};
 
 const TITLE: &str = "⋅𝕭𝖆𝖘𝖆𝖑𝖙⋅";
 
@@ -41,19 +44,89 @@ pub const LOGO: [&str; 25] = [
     "          ░░▒▒░            ",
 ];
 
+/// Shown on the splash screen when the Obsidian config lists no vaults.
+pub const NO_VAULTS_MESSAGE: &str = "No vaults found in the Obsidian config. Open a vault in \
+    Obsidian once, or set OBSIDIAN_CONFIG_DIR to the directory that contains obsidian.json.";
+
+/// Returns the message shown on the splash screen when the Obsidian config cannot be loaded.
+pub fn error_message(error: &Error) -> String {
+    match error {
+        Error::PathNotFound(message) => format!(
+            "No Obsidian configuration found: {message}. Open a vault in Obsidian once, or set \
+             OBSIDIAN_CONFIG_DIR to the directory that contains obsidian.json."
+        ),
+        Error::Json(error) => format!("The Obsidian config could not be parsed: {error}"),
+        error => format!("The Obsidian config could not be loaded: {error}"),
+    }
+}
+
 #[derive(Debug, Default, Clone, PartialEq)]
 pub struct SplashState<'a> {
     pub(crate) vault_selector_state: VaultSelectorState<'a>,
     pub(crate) version: &'a str,
+    /// Shown instead of the vaults when the Obsidian config could not be loaded or lists no
+    /// vaults.
+    pub(crate) error: Option<String>,
+    /// The vault and note of the previous session, offered to be resumed.
+    pub(crate) session: Option<(&'a Vault, Option<PathBuf>)>,
 }
 
 impl<'a> SplashState<'a> {
-    pub fn new(version: &'a str, items: Vec<&'a Vault>) -> Self {
-        let vault_selector_state = VaultSelectorState::new(items);
+    /// Creates the splash screen with the vaults of the config, most recently opened first, see
+    /// [`ObsidianConfig::vaults_by_recent`]. The vault from
+    /// [`ObsidianConfig::most_recently_opened`] is highlighted. Without vaults,
+    /// [`NO_VAULTS_MESSAGE`] is shown instead.
+    pub fn new(version: &'a str, config: &'a ObsidianConfig) -> Self {
+        let items = config.vaults_by_recent();
+        let most_recently_opened = config
+            .most_recently_opened()
+            .and_then(|recent| items.iter().position(|vault| *vault == recent))
+            .unwrap_or_default();
+
+        let error = items.is_empty().then(|| NO_VAULTS_MESSAGE.to_string());
+        let vault_selector_state = VaultSelectorState::new(items).highlight(most_recently_opened);
 
         SplashState {
             version,
             vault_selector_state,
+            error,
+            session: None,
+        }
+    }
+
+    /// Offers to resume the previous session, i.e. to reopen the vault and note.
+    pub fn with_session(self, vault: &'a Vault, note: Option<PathBuf>) -> Self {
+        Self {
+            session: Some((vault, note)),
+            ..self
+        }
+    }
+
+    /// Returns the hint shown at the bottom of the splash screen. When a previous session can
+    /// be resumed, the hint offers to resume it.
+    fn hint(&self) -> String {
+        match &self.session {
+            Some((vault, note)) => {
+                let note = note
+                    .as_ref()
+                    .and_then(|note| note.file_stem())
+                    .map(|name| format!(" › {}", name.to_string_lossy()))
+                    .unwrap_or_default();
+
+                format!(
+                    "Press (Ctrl+R) to resume {}{note}, (?) for help",
+                    vault.name
+                )
+            }
+            None => "Press (?) for help".to_string(),
+        }
+    }
+
+    /// Shows the error that occurred when loading the Obsidian config, see [`error_message`].
+    pub fn with_error(self, error: &Error) -> Self {
+        Self {
+            error: Some(error_message(error)),
+            ..self
         }
     }
 
@@ -89,13 +162,48 @@ impl<'a> SplashState<'a> {
             ..self
         }
     }
+
+    pub fn search_query(&self) -> &str {
+        self.vault_selector_state.search_query()
+    }
+
+    pub fn filter_input(self, c: char) -> Self {
+        Self {
+            vault_selector_state: self.vault_selector_state.filter_input(c),
+            ..self
+        }
+    }
+
+    pub fn filter_backspace(self) -> Self {
+        Self {
+            vault_selector_state: self.vault_selector_state.filter_backspace(),
+            ..self
+        }
+    }
+
+    pub fn clear_filter(self) -> Self {
+        Self {
+            vault_selector_state: self.vault_selector_state.clear_filter(),
+            ..self
+        }
+    }
 }
 
 #[derive(Default)]
 pub struct Splash<'a> {
+    theme: Theme,
     _lifetime: PhantomData<&'a ()>,
 }
 
+impl Splash<'_> {
+    pub fn with_theme(self, theme: &Theme) -> Self {
+        Self {
+            theme: *theme,
+            ..self
+        }
+    }
+}
+
 impl<'a> StatefulWidgetRef for Splash<'a> {
     type State = SplashState<'a>;
 
@@ -107,10 +215,13 @@ impl<'a> StatefulWidgetRef for Splash<'a> {
         ])
         .areas(area);
 
+        // Room for the borders and the filter query, up to 9 vaults before the list scrolls
+        let vault_list_height = (state.vault_selector_state.items.len() + 3).clamp(6, 12) as u16;
+
         let [_, top, bottom, _, help] = Layout::vertical([
             Constraint::Fill(1),
             Constraint::Length(28),
-            Constraint::Min(6),
+            Constraint::Min(vault_list_height),
             Constraint::Fill(1),
             Constraint::Length(1),
         ])
@@ -147,12 +258,250 @@ impl<'a> StatefulWidgetRef for Splash<'a> {
             .centered()
             .render(version, buf);
 
-        Text::from("Press (?) for help")
+        Text::from(state.hint())
             .italic()
             .dark_gray()
             .centered()
             .render(help, buf);
 
-        VaultSelector::default().render_ref(bottom, buf, &mut state.vault_selector_state);
+        match &state.error {
+            Some(error) => Paragraph::new(error.as_str())
+                .italic()
+                .centered()
+                .wrap(Wrap { trim: true })
+                .render(bottom, buf),
+            None => VaultSelector::default().with_theme(&self.theme).render_ref(
+                bottom,
+                buf,
+                &mut state.vault_selector_state,
+            ),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{env, fs, io};
+
+    use super::*;
+    use insta::assert_snapshot;
+    use ratatui::{backend::TestBackend, Terminal};
+
+    #[test]
+    fn test_unavailable_vault() {
+        const NOW: u64 = 1760000000000;
+
+        let config = ObsidianConfig::from(
+            [
+                ("Notes", ".", NOW),
+                ("Deleted", "basalt-deleted-vault", NOW - 1),
+            ]
+            .map(|(name, path, ts)| {
+                (
+                    name,
+                    Vault {
+                        name: name.to_string(),
+                        path: path.into(),
+                        ts,
+                        ..Default::default()
+                    },
+                )
+            }),
+        );
+
+        let mut state = SplashState::new("0.0.0", &config);
+        state.vault_selector_state.now = NOW;
+        assert_eq!(state.select().selected(), Some(0));
+        assert_eq!(state.clone().next().select().selected(), None);
+
+        let mut terminal = Terminal::new(TestBackend::new(80, 40)).unwrap();
+        terminal
+            .draw(|frame| {
+                Splash::default().render_ref(frame.area(), frame.buffer_mut(), &mut state.next())
+            })
+            .unwrap();
+        assert_snapshot!(terminal.backend());
+    }
+
+    #[test]
+    fn test_most_recently_opened() {
+        let config = |vaults: [(&'static str, u64, bool); 3]| {
+            ObsidianConfig::from(vaults.map(|(name, ts, open)| {
+                (
+                    name,
+                    Vault {
+                        name: name.to_string(),
+                        path: env::temp_dir(),
+                        ts,
+                        open,
+                    },
+                )
+            }))
+        };
+        let selected_name = |config: &ObsidianConfig| {
+            let state = SplashState::new("0.0.0", config);
+            state
+                .select()
+                .selected()
+                .and_then(|index| state.get_item(index))
+                .map(|vault| vault.name.clone())
+        };
+
+        let recent = config([
+            ("Archive", 1600000000000, false),
+            ("Notes", 1710000000000, false),
+            ("Work", 0, true),
+        ]);
+        assert_eq!(selected_name(&recent), Some("Notes".to_string()));
+
+        // Without timestamps, the vault marked open by Obsidian is highlighted
+        let open = config([
+            ("Archive", 0, false),
+            ("Notes", 0, false),
+            ("Work", 0, true),
+        ]);
+        assert_eq!(selected_name(&open), Some("Work".to_string()));
+
+        assert_eq!(selected_name(&ObsidianConfig::default()), None);
+    }
+
+    #[test]
+    fn test_hint() {
+        let vault = Vault {
+            name: "Notes".to_string(),
+            ..Default::default()
+        };
+        let config = ObsidianConfig::default();
+        let state = SplashState::new("0.0.0", &config);
+        assert_eq!(state.hint(), "Press (?) for help");
+
+        let state = state.with_session(&vault, None);
+        assert_eq!(state.hint(), "Press (Ctrl+R) to resume Notes, (?) for help");
+
+        let state = state.with_session(&vault, Some(PathBuf::from("Notes/Daily.md")));
+        assert_eq!(
+            state.hint(),
+            "Press (Ctrl+R) to resume Notes › Daily, (?) for help"
+        );
+    }
+
+    #[test]
+    fn test_error_message() {
+        let error = Error::PathNotFound(
+            "Obsidian config directory was not found from these locations: ~/.config/obsidian"
+                .to_string(),
+        );
+        assert_eq!(
+            error_message(&error),
+            "No Obsidian configuration found: Obsidian config directory was not found from these \
+             locations: ~/.config/obsidian. Open a vault in Obsidian once, or set \
+             OBSIDIAN_CONFIG_DIR to the directory that contains obsidian.json."
+        );
+        assert_eq!(
+            error_message(&Error::Io(io::Error::other("disk failure"))),
+            "The Obsidian config could not be loaded: I/O error: disk failure"
+        );
+
+        let config = ObsidianConfig::default();
+        let mut state = SplashState::new("0.0.0", &config).with_error(&error);
+        let mut terminal = Terminal::new(TestBackend::new(80, 40)).unwrap();
+        terminal
+            .draw(|frame| {
+                Splash::default().render_ref(frame.area(), frame.buffer_mut(), &mut state)
+            })
+            .unwrap();
+        assert_snapshot!(terminal.backend());
+    }
+
+    #[test]
+    fn test_vault_list() {
+        const NOW: u64 = 1760000000000;
+        const HOUR: u64 = 60 * 60 * 1000;
+        const DAY: u64 = 24 * HOUR;
+
+        let root = tempfile::tempdir().unwrap();
+        let vault = |name: &str, path: &str, ts: u64, open: bool| {
+            let path = root.path().join(path);
+            if name != "Old Notes" {
+                fs::create_dir_all(&path).unwrap();
+            }
+
+            Vault {
+                name: name.to_string(),
+                path,
+                ts,
+                open,
+            }
+        };
+
+        let vaults = [
+            vault(
+                "Notes",
+                "home/user/Documents/Obsidian/Personal Notes",
+                NOW - 3 * HOUR,
+                true,
+            ),
+            vault(
+                "Work",
+                "home/user/Documents/Company/Engineering/Work Vault",
+                NOW - DAY,
+                false,
+            ),
+            vault(
+                "Research Papers",
+                "mnt/shared/university/thesis/Research Papers",
+                NOW - 12 * DAY,
+                false,
+            ),
+            vault(
+                "Old Notes",
+                "home/user/Backups/2019/Old Obsidian Notes",
+                NOW - 400 * DAY,
+                false,
+            ),
+            vault("Sandbox", "home/user/Projects/basalt/dev/Sandbox", 0, false),
+        ];
+
+        let config = ObsidianConfig::from(vaults.map(|vault| (vault.name.clone(), vault)));
+        let mut state = SplashState::new("0.0.0", &config);
+        state.vault_selector_state.now = NOW;
+
+        let mut terminal = Terminal::new(TestBackend::new(60, 42)).unwrap();
+        terminal
+            .draw(|frame| {
+                Splash::default().render_ref(frame.area(), frame.buffer_mut(), &mut state)
+            })
+            .unwrap();
+        assert_snapshot!(terminal.backend());
+
+        let mut state = state.filter_input('n').filter_input('o');
+        assert_eq!(state.search_query(), "no");
+        assert_eq!(state.select().selected(), Some(0));
+
+        terminal
+            .draw(|frame| {
+                Splash::default().render_ref(frame.area(), frame.buffer_mut(), &mut state)
+            })
+            .unwrap();
+        assert_snapshot!(terminal.backend());
+
+        let state = state.filter_backspace().filter_input('x');
+        assert_eq!(state.select().selected(), None);
+        assert_eq!(state.clear_filter().search_query(), "");
+    }
+
+    #[test]
+    fn test_no_vaults() {
+        let config = ObsidianConfig::default();
+        let mut state = SplashState::new("0.0.0", &config);
+        assert_eq!(state.error.as_deref(), Some(NO_VAULTS_MESSAGE));
+
+        let mut terminal = Terminal::new(TestBackend::new(80, 40)).unwrap();
+        terminal
+            .draw(|frame| {
+                Splash::default().render_ref(frame.area(), frame.buffer_mut(), &mut state)
+            })
+            .unwrap();
+        assert_snapshot!(terminal.backend());
     }
 }
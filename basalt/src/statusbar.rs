@@ -3,74 +3,335 @@ use std::marker::PhantomData;
 use ratatui::{
     buffer::Buffer,
     layout::{Constraint, Flex, Layout, Rect},
-    style::{Color, Stylize},
+    style::Stylize,
     text::{Line, Span, Text},
     widgets::{StatefulWidgetRef, Widget},
 };
 
+use textwrap::core::display_width;
+
+use crate::config::Theme;
+
 #[derive(Default, Clone, PartialEq)]
 pub struct StatusBarState<'a> {
     active_component_name: &'a str,
     word_count: usize,
     char_count: usize,
+    /// The 0-indexed `(row, col)` of the cursor in the active note editor.
+    cursor: Option<(usize, usize)>,
+    /// Vault-relative path of the open note.
+    note_path: Option<String>,
+    /// Whether the open note has unsaved changes.
+    modified: bool,
+    /// Label of the note editor mode.
+    mode: Option<String>,
+    /// Estimated reading time of the open note in minutes.
+    reading_minutes: usize,
+    /// The keys of a key sequence that is waiting for the next key, e.g. `space`.
+    pending_keys: Option<String>,
 }
 
 impl<'a> StatusBarState<'a> {
-    pub fn new(active_component_name: &'a str, word_count: usize, char_count: usize) -> Self {
+    pub fn new(
+        active_component_name: &'a str,
+        word_count: usize,
+        char_count: usize,
+        cursor: Option<(usize, usize)>,
+    ) -> Self {
         Self {
             active_component_name,
             word_count,
             char_count,
+            cursor,
+            ..Default::default()
+        }
+    }
+
+    pub fn with_note_path(self, note_path: Option<String>) -> Self {
+        Self { note_path, ..self }
+    }
+
+    pub fn with_modified(self, modified: bool) -> Self {
+        Self { modified, ..self }
+    }
+
+    pub fn with_mode(self, mode: Option<String>) -> Self {
+        Self { mode, ..self }
+    }
+
+    pub fn with_reading_minutes(self, reading_minutes: usize) -> Self {
+        Self {
+            reading_minutes,
+            ..self
+        }
+    }
+
+    pub fn with_pending_keys(self, pending_keys: Option<String>) -> Self {
+        Self {
+            pending_keys,
+            ..self
         }
     }
+
+    /// Returns the cursor position formatted as a 1-indexed `line:column`.
+    pub fn cursor_position(&self) -> Option<String> {
+        self.cursor
+            .map(|(row, col)| format!("{}:{}", row + 1, col + 1))
+    }
+}
+
+/// The path width below which the mode is hidden to leave more room for the path.
+const MIN_PATH_WIDTH: usize = 12;
+
+/// Shortens the text to the given display width by replacing the middle with `…`, so that both
+/// the start and the end of a path stay visible.
+fn truncate_middle(text: &str, max_width: usize) -> String {
+    if display_width(text) <= max_width {
+        return text.to_string();
+    }
+
+    if max_width == 0 {
+        return String::new();
+    }
+
+    /// Takes chars from the iterator until they no longer fit within the width.
+    fn take_within(width: usize, chars: impl Iterator<Item = char>) -> Vec<char> {
+        let mut used_width = 0;
+        chars
+            .take_while(|c| {
+                used_width += display_width(c.encode_utf8(&mut [0; 4]));
+                used_width <= width
+            })
+            .collect()
+    }
+
+    let available_width = max_width - 1;
+    let end_width = available_width / 2;
+    let start_width = available_width - end_width;
+
+    let start = take_within(start_width, text.chars());
+    let mut end = take_within(end_width, text.chars().rev());
+    end.reverse();
+
+    start.into_iter().chain(['…']).chain(end).collect()
+}
+
+/// Formats the count with thousands separators, e.g. `1,204`.
+fn format_count(count: usize) -> String {
+    let digits = count.to_string();
+    digits
+        .chars()
+        .enumerate()
+        .flat_map(|(i, c)| {
+            let separator = (i > 0 && (digits.len() - i) % 3 == 0).then_some(',');
+            separator.into_iter().chain([c])
+        })
+        .collect()
 }
 
 #[derive(Default)]
 pub struct StatusBar<'a> {
+    theme: Theme,
     _lifetime: PhantomData<&'a ()>,
 }
 
+impl StatusBar<'_> {
+    pub fn with_theme(self, theme: &Theme) -> Self {
+        Self {
+            theme: *theme,
+            ..self
+        }
+    }
+}
+
 impl<'a> StatefulWidgetRef for StatusBar<'a> {
     type State = StatusBarState<'a>;
 
     fn render_ref(&self, area: Rect, buf: &mut Buffer, state: &mut Self::State) {
-        let [left, right] = Layout::horizontal([Constraint::Fill(1), Constraint::Length(28)])
-            .flex(Flex::SpaceBetween)
-            .areas(area);
+        let cursor_position = state.cursor_position();
+
+        // The pending keys take the place of the cursor position until the sequence has ended
+        let center_text = match &state.pending_keys {
+            Some(pending_keys) => Text::from(pending_keys.as_str()).bold(),
+            None => Text::from(cursor_position.unwrap_or_default()).dim(),
+        };
+
+        let counts = Line::from(format!(
+            " {} word{}   {} min read   {} char{} ",
+            format_count(state.word_count),
+            if state.word_count == 1 { "" } else { "s" },
+            state.reading_minutes,
+            format_count(state.char_count),
+            if state.char_count == 1 { "" } else { "s" }
+        ));
+
+        // The breadcrumb takes the space that is not used by the cursor position and the counts
+        let [left, center, right] = Layout::horizontal([
+            Constraint::Fill(1),
+            Constraint::Length(match center_text.width() {
+                0 => 0,
+                width => width as u16 + 2,
+            }),
+            Constraint::Length(counts.width() as u16),
+        ])
+        .flex(Flex::SpaceBetween)
+        .areas(area);
 
+        let accent = self.theme.statusbar_accent;
         let active_component = [
-            Span::from("").dark_gray(),
-            Span::from(" ").bg(Color::DarkGray),
+            Span::from("").fg(accent),
+            Span::from(" ").bg(accent),
             Span::from(state.active_component_name)
-                .dark_gray()
+                .fg(accent)
                 .reversed()
                 .bold(),
-            Span::from(" ").bg(Color::DarkGray),
-            Span::from("").dark_gray(),
+            Span::from(" ").bg(accent),
+            Span::from("").fg(accent),
         ]
         .to_vec();
 
-        Text::from(Line::from(active_component)).render(left, buf);
+        let active_component_width = active_component
+            .iter()
+            .map(|span| span.width())
+            .sum::<usize>();
 
-        let [word_count, char_count] =
-            Layout::horizontal([Constraint::Fill(1), Constraint::Fill(1)])
-                .flex(Flex::End)
-                .areas(right);
+        let breadcrumb = state
+            .note_path
+            .as_ref()
+            .map(|note_path| {
+                let separator = Span::from(" ▸ ").fg(accent);
+                let modified = state.modified.then(|| Span::from(" [+]").bold());
+                let mode = state
+                    .mode
+                    .as_ref()
+                    .map(|mode| Span::from(format!(" {mode}")).dim().italic());
 
-        Text::from(format!(
-            "{} word{}",
-            state.word_count,
-            if state.word_count == 1 { "" } else { "s" }
-        ))
-        .right_aligned()
-        .render(word_count, buf);
+                let path_width = |suffix: &[&Span]| {
+                    (left.width as usize).saturating_sub(
+                        active_component_width
+                            + separator.width()
+                            + suffix.iter().map(|span| span.width()).sum::<usize>(),
+                    )
+                };
 
-        Text::from(format!(
-            "{} char{}",
-            state.char_count,
-            if state.char_count == 1 { "" } else { "s" }
+                // The mode is left out before the path is truncated too short to be recognizable
+                let suffix = [modified.as_ref(), mode.as_ref()]
+                    .into_iter()
+                    .flatten()
+                    .collect::<Vec<_>>();
+                let suffix = if path_width(&suffix) < MIN_PATH_WIDTH.min(note_path.len()) {
+                    modified.iter().collect()
+                } else {
+                    suffix
+                };
+
+                [
+                    separator.clone(),
+                    Span::from(truncate_middle(note_path, path_width(&suffix))),
+                ]
+                .into_iter()
+                .chain(suffix.into_iter().cloned())
+                .collect::<Vec<_>>()
+            })
+            .unwrap_or_default();
+
+        Text::from(Line::from(
+            active_component
+                .into_iter()
+                .chain(breadcrumb)
+                .collect::<Vec<_>>(),
         ))
-        .right_aligned()
-        .render(char_count, buf);
+        .render(left, buf);
+
+        center_text.centered().render(center, buf);
+
+        counts.right_aligned().render(right, buf);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use insta::assert_snapshot;
+    use ratatui::{backend::TestBackend, Terminal};
+
+    #[test]
+    fn test_cursor_position() {
+        let tests = [
+            (None, None),
+            (Some((0, 0)), Some("1:1")),
+            (Some((9, 4)), Some("10:5")),
+        ];
+
+        tests.into_iter().for_each(|(cursor, expected)| {
+            assert_eq!(
+                StatusBarState::new("EDITOR", 0, 0, cursor).cursor_position(),
+                expected.map(String::from)
+            )
+        });
+    }
+
+    #[test]
+    fn test_truncate_middle() {
+        let tests = [
+            ("notes.md", 10, "notes.md"),
+            ("projects/alpha/notes.md", 23, "projects/alpha/notes.md"),
+            ("projects/alpha/notes.md", 11, "proje…es.md"),
+            ("projects/alpha/notes.md", 1, "…"),
+            ("projects/alpha/notes.md", 0, ""),
+        ];
+
+        tests
+            .into_iter()
+            .for_each(|(text, width, expected)| assert_eq!(truncate_middle(text, width), expected));
+    }
+
+    #[test]
+    fn test_format_count() {
+        let tests = [
+            (0, "0"),
+            (999, "999"),
+            (1204, "1,204"),
+            (1234567, "1,234,567"),
+        ];
+
+        tests
+            .into_iter()
+            .for_each(|(count, expected)| assert_eq!(format_count(count), expected));
+    }
+
+    #[test]
+    fn test_render_breadcrumb() {
+        let tests = [("breadcrumb_truncated", 60), ("breadcrumb_full", 120)];
+
+        tests.into_iter().for_each(|(name, width)| {
+            let mut state = StatusBarState::new("Explorer", 1204, 6530, None)
+                .with_reading_minutes(7)
+                .with_note_path(Some("projects/alpha/notes.md".to_string()))
+                .with_modified(true)
+                .with_mode(Some("VIEW".to_string()));
+
+            let mut terminal = Terminal::new(TestBackend::new(width, 1)).unwrap();
+            terminal
+                .draw(|frame| {
+                    StatusBar::default().render_ref(frame.area(), frame.buffer_mut(), &mut state)
+                })
+                .unwrap();
+            assert_snapshot!(name, terminal.backend());
+        });
+    }
+
+    #[test]
+    fn test_render_pending_keys() {
+        let mut state = StatusBarState::new("Explorer", 12, 60, Some((2, 4)))
+            .with_pending_keys(Some("space g".to_string()));
+
+        let mut terminal = Terminal::new(TestBackend::new(72, 1)).unwrap();
+        terminal
+            .draw(|frame| {
+                StatusBar::default().render_ref(frame.area(), frame.buffer_mut(), &mut state)
+            })
+            .unwrap();
+        assert_snapshot!(terminal.backend());
     }
 }
@@ -0,0 +1,146 @@
+use ratatui::{
+    buffer::Buffer,
+    layout::Rect,
+    style::{Style, Stylize},
+    text::{Line, Span},
+    widgets::Widget,
+};
+use textwrap::core::display_width;
+
+use crate::config::Theme;
+
+/// A tab of the tab bar.
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct Tab {
+    pub name: String,
+    /// Whether the note of the tab has unsaved changes.
+    pub modified: bool,
+}
+
+/// The open notes shown above the note editor, with the active tab highlighted.
+///
+/// Tabs that do not fit are dropped from the side that is further from the active tab, so the
+/// active tab is always visible.
+#[derive(Default)]
+pub struct TabBar {
+    tabs: Vec<Tab>,
+    active: usize,
+    theme: Theme,
+}
+
+impl TabBar {
+    pub fn new(tabs: Vec<Tab>, active: usize) -> Self {
+        Self {
+            tabs,
+            active,
+            ..Default::default()
+        }
+    }
+
+    pub fn with_theme(self, theme: &Theme) -> Self {
+        Self {
+            theme: *theme,
+            ..self
+        }
+    }
+}
+
+/// Returns the range of labels that fit within the width, starting from the active label and
+/// adding the closest label to either side while there is room.
+fn visible_range(labels: &[String], active: usize, width: usize) -> (usize, usize) {
+    // Each label is followed by a space
+    let label_width = |index: usize| display_width(&labels[index]) + 1;
+    let (mut start, mut end) = (active, active + 1);
+    let mut used_width = label_width(active);
+    let (mut left_full, mut right_full) = (false, false);
+
+    loop {
+        let right = (!right_full && end < labels.len()).then_some(end);
+        let left = (!left_full).then(|| start.checked_sub(1)).flatten();
+
+        let Some(index) = [right, left]
+            .into_iter()
+            .flatten()
+            .min_by_key(|index| index.abs_diff(active))
+        else {
+            return (start, end);
+        };
+
+        let fits = used_width + label_width(index) <= width;
+        match (index < start, fits) {
+            (true, true) => start = index,
+            (false, true) => end = index + 1,
+            (true, false) => left_full = true,
+            (false, false) => right_full = true,
+        }
+
+        if fits {
+            used_width += label_width(index);
+        }
+    }
+}
+
+impl Widget for TabBar {
+    fn render(self, area: Rect, buf: &mut Buffer) {
+        if self.tabs.is_empty() {
+            return;
+        }
+
+        let labels: Vec<String> = self
+            .tabs
+            .iter()
+            .map(|tab| match tab.modified {
+                true => format!(" {} [+] ", tab.name),
+                false => format!(" {} ", tab.name),
+            })
+            .collect();
+
+        let active = self.active.min(self.tabs.len() - 1);
+        let (start, end) = visible_range(&labels, active, area.width.into());
+
+        let spans = labels[start..end]
+            .iter()
+            .enumerate()
+            .flat_map(|(offset, label)| {
+                let span = match start + offset == active {
+                    true => Span::styled(
+                        label.as_str(),
+                        Style::new().reversed().fg(self.theme.selection),
+                    ),
+                    false => Span::from(label.as_str()).dark_gray(),
+                };
+
+                [span, Span::from(" ")]
+            });
+
+        Line::from_iter(spans).render(area, buf);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use insta::assert_snapshot;
+    use ratatui::{backend::TestBackend, Terminal};
+
+    #[test]
+    fn test_render_tab_bar() {
+        let tabs = ["Daily", "Ideas", "Reading List", "Projects", "Archive"].map(|name| Tab {
+            name: name.to_string(),
+            modified: name == "Ideas",
+        });
+
+        // Each row shows a different active tab
+        let mut terminal = Terminal::new(TestBackend::new(36, 3)).unwrap();
+        terminal
+            .draw(|frame| {
+                [0, 2, 4].into_iter().enumerate().for_each(|(row, active)| {
+                    let area = Rect::new(0, row as u16, frame.area().width, 1);
+                    TabBar::new(tabs.to_vec(), active).render(area, frame.buffer_mut())
+                })
+            })
+            .unwrap();
+
+        assert_snapshot!(terminal.backend());
+    }
+}
@@ -0,0 +1,372 @@
+use std::collections::{BTreeMap, BTreeSet};
+
+use basalt_core::obsidian::{Note, VaultEntry};
+use ratatui::{
+    buffer::Buffer,
+    layout::{Constraint, Flex, Layout, Rect},
+    style::{Color, Style, Stylize},
+    text::Line,
+    widgets::{
+        Block, BorderType, Clear, List, ListItem, ListState, StatefulWidget, StatefulWidgetRef,
+        Widget,
+    },
+};
+
+use crate::config::Theme;
+
+/// Returns the tag and its parent tags, the outermost first, e.g. `project` and `project/active`
+/// for `project/active`.
+fn ancestors(tag: &str) -> impl Iterator<Item = &str> {
+    tag.match_indices('/')
+        .map(|(index, _)| &tag[..index])
+        .chain([tag])
+}
+
+/// Reads the tags of all markdown notes in the entries. Notes that cannot be read are skipped.
+pub fn note_tags(entries: &[VaultEntry]) -> Vec<(Note, Vec<String>)> {
+    entries
+        .iter()
+        .flat_map(|entry| match entry {
+            VaultEntry::File(note) if note.kind().is_markdown() => note
+                .tags()
+                .map(|tags| (note.clone(), tags))
+                .into_iter()
+                .collect(),
+            VaultEntry::File(_) => vec![],
+            VaultEntry::Directory { entries, .. } => note_tags(entries),
+        })
+        .collect()
+}
+
+/// Counts the notes of each tag. Nested tags also count towards their parent tags, so a note
+/// tagged `project/active` is counted for both `project` and `project/active`. A note is counted
+/// once per tag.
+pub fn tag_counts(notes: &[(Note, Vec<String>)]) -> BTreeMap<String, usize> {
+    notes
+        .iter()
+        .flat_map(|(_, tags)| {
+            tags.iter()
+                .flat_map(|tag| ancestors(tag))
+                .collect::<BTreeSet<_>>()
+        })
+        .fold(BTreeMap::new(), |mut counts, tag| {
+            *counts.entry(tag.to_string()).or_default() += 1;
+            counts
+        })
+}
+
+/// A tag listed in the panel.
+#[derive(Debug, Clone, PartialEq)]
+pub struct TagItem {
+    /// The full tag, e.g. `project/active`.
+    pub tag: String,
+    /// The nesting depth of the tag, `0` for top-level tags.
+    pub depth: usize,
+    /// The number of notes with the tag or one of its nested tags.
+    pub count: usize,
+}
+
+impl TagItem {
+    /// Returns the last part of the tag, e.g. `active` for `project/active`.
+    pub fn name(&self) -> &str {
+        self.tag.rsplit('/').next().unwrap_or(&self.tag)
+    }
+}
+
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct TagPanelState {
+    /// The number of notes of each tag, see [`tag_counts`].
+    pub tags: BTreeMap<String, usize>,
+    /// The notes and their tags, used to list the notes with the selected tag.
+    notes: Vec<(Note, Vec<String>)>,
+    /// The selected tag and the notes with the tag, when the notes are listed instead of the tags.
+    results: Option<(String, Vec<Note>)>,
+    pub visible: bool,
+    list_state: ListState,
+}
+
+impl TagPanelState {
+    /// Shows the panel with the tags of the given notes.
+    pub fn show(&self, notes: Vec<(Note, Vec<String>)>) -> Self {
+        let tags = tag_counts(&notes);
+        let selected = (!tags.is_empty()).then_some(0);
+
+        Self {
+            tags,
+            notes,
+            results: None,
+            visible: true,
+            list_state: ListState::default().with_selected(selected),
+        }
+    }
+
+    pub fn hide(&self) -> Self {
+        Self {
+            visible: false,
+            ..self.clone()
+        }
+    }
+
+    /// Returns the tags in hierarchical order, nested tags directly after their parent tag.
+    pub fn items(&self) -> Vec<TagItem> {
+        let mut tags: Vec<_> = self.tags.iter().collect();
+        tags.sort_by(|(a, _), (b, _)| a.split('/').cmp(b.split('/')));
+
+        tags.into_iter()
+            .map(|(tag, count)| TagItem {
+                tag: tag.clone(),
+                depth: tag.matches('/').count(),
+                count: *count,
+            })
+            .collect()
+    }
+
+    /// Returns the highlighted tag, or `None` if the notes of a tag are listed.
+    pub fn selected_tag(&self) -> Option<TagItem> {
+        if self.results.is_some() {
+            return None;
+        }
+
+        self.list_state
+            .selected()
+            .and_then(|index| self.items().into_iter().nth(index))
+    }
+
+    /// Returns the highlighted note, or `None` if the tags are listed.
+    pub fn selected_note(&self) -> Option<&Note> {
+        let (_, notes) = self.results.as_ref()?;
+        self.list_state
+            .selected()
+            .and_then(|index| notes.get(index))
+    }
+
+    fn len(&self) -> usize {
+        match &self.results {
+            Some((_, notes)) => notes.len(),
+            None => self.tags.len(),
+        }
+    }
+
+    /// Lists the notes with the highlighted tag or one of its nested tags.
+    pub fn select(&self) -> Self {
+        let Some(item) = self.selected_tag() else {
+            return self.clone();
+        };
+
+        let notes: Vec<Note> = self
+            .notes
+            .iter()
+            .filter(|(_, tags)| tags.iter().any(|tag| ancestors(tag).any(|t| t == item.tag)))
+            .map(|(note, _)| note.clone())
+            .collect();
+
+        Self {
+            results: Some((item.tag, notes)),
+            list_state: ListState::default().with_selected(Some(0)),
+            ..self.clone()
+        }
+    }
+
+    /// Returns from the listed notes to the tags, highlighting the tag of the notes. Hides the
+    /// panel if the tags are listed.
+    pub fn back(&self) -> Self {
+        let Some((tag, _)) = &self.results else {
+            return self.hide();
+        };
+
+        let index = self.items().iter().position(|item| &item.tag == tag);
+
+        Self {
+            results: None,
+            list_state: ListState::default().with_selected(index),
+            ..self.clone()
+        }
+    }
+
+    pub fn next(&self) -> Self {
+        let index = self
+            .list_state
+            .selected()
+            .map(|index| (index + 1).min(self.len().saturating_sub(1)));
+
+        Self {
+            list_state: self.list_state.clone().with_selected(index),
+            ..self.clone()
+        }
+    }
+
+    pub fn previous(&self) -> Self {
+        let index = self
+            .list_state
+            .selected()
+            .map(|index| index.saturating_sub(1));
+
+        Self {
+            list_state: self.list_state.clone().with_selected(index),
+            ..self.clone()
+        }
+    }
+}
+
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct TagPanel {
+    theme: Theme,
+}
+
+impl TagPanel {
+    pub fn with_theme(self, theme: &Theme) -> Self {
+        Self { theme: *theme }
+    }
+
+    fn modal_area(area: Rect) -> Rect {
+        let vertical = Layout::vertical([Constraint::Percentage(50)]).flex(Flex::Center);
+        let horizontal = Layout::horizontal([Constraint::Length(60)]).flex(Flex::Center);
+        let [area] = vertical.areas(area);
+        let [area] = horizontal.areas(area);
+        area
+    }
+}
+
+impl StatefulWidget for TagPanel {
+    type State = TagPanelState;
+
+    fn render(self, area: Rect, buf: &mut Buffer, state: &mut Self::State) {
+        let area = TagPanel::modal_area(area);
+        Widget::render(Clear, area, buf);
+
+        let (title, items): (String, Vec<ListItem>) = match &state.results {
+            Some((tag, notes)) => (
+                format!(" #{tag} "),
+                notes
+                    .iter()
+                    .map(|note| ListItem::new(format!("  {}", note.name)))
+                    .collect(),
+            ),
+            None => (
+                " Tags ".into(),
+                state
+                    .items()
+                    .iter()
+                    .map(|item| {
+                        ListItem::new(Line::from(
+                            [
+                                format!("  {}#{} ", "  ".repeat(item.depth), item.name()).into(),
+                                item.count.to_string().dark_gray(),
+                            ]
+                            .to_vec(),
+                        ))
+                    })
+                    .collect(),
+            ),
+        };
+
+        let block = Block::bordered()
+            .dark_gray()
+            .title(title)
+            .title_style(Style::default().italic().bold())
+            .border_type(BorderType::Rounded);
+
+        List::new(items)
+            .block(block)
+            .fg(Color::default())
+            .highlight_style(Style::new().reversed().fg(self.theme.selection))
+            .highlight_symbol(" ")
+            .render_ref(area, buf, &mut state.list_state);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use insta::assert_snapshot;
+    use ratatui::{backend::TestBackend, Terminal};
+
+    fn notes() -> Vec<(Note, Vec<String>)> {
+        [
+            ("Daily", ["daily", "project/basalt"].as_slice()),
+            ("Plan", &["project/basalt", "project/basalt/tui", "idea"]),
+            ("Ideas", &["idea", "project-x"]),
+            ("Empty", &[]),
+        ]
+        .map(|(name, tags)| {
+            (
+                Note {
+                    name: name.into(),
+                    path: format!("{name}.md").into(),
+                },
+                tags.iter().map(|tag| tag.to_string()).collect(),
+            )
+        })
+        .to_vec()
+    }
+
+    #[test]
+    fn test_tag_counts() {
+        assert_eq!(tag_counts(&[]), BTreeMap::new());
+        assert_eq!(
+            tag_counts(&notes()),
+            BTreeMap::from(
+                [
+                    ("daily", 1),
+                    ("idea", 2),
+                    ("project", 2),
+                    ("project-x", 1),
+                    ("project/basalt", 2),
+                    ("project/basalt/tui", 1),
+                ]
+                .map(|(tag, count)| (tag.to_string(), count))
+            )
+        );
+    }
+
+    #[test]
+    fn test_tag_panel() {
+        let state = TagPanelState::default().show(notes());
+        assert_eq!(
+            state
+                .items()
+                .iter()
+                .map(|item| (item.name(), item.depth))
+                .collect::<Vec<_>>(),
+            [
+                ("daily", 0),
+                ("idea", 0),
+                ("project", 0),
+                ("basalt", 1),
+                ("tui", 2),
+                ("project-x", 0),
+            ]
+        );
+
+        let mut terminal = Terminal::new(TestBackend::new(60, 20)).unwrap();
+        terminal
+            .draw(|frame| {
+                TagPanel::default().render(frame.area(), frame.buffer_mut(), &mut state.next())
+            })
+            .unwrap();
+        assert_snapshot!(terminal.backend());
+
+        // The notes of nested tags are listed with the parent tag
+        let state = state.next().next().next().select();
+        assert_eq!(state.selected_tag(), None);
+        assert_eq!(
+            state.selected_note().map(|note| note.name.as_str()),
+            Some("Daily")
+        );
+        assert_eq!(
+            state
+                .next()
+                .next()
+                .selected_note()
+                .map(|note| note.name.as_str()),
+            Some("Plan")
+        );
+
+        let state = state.back();
+        assert_eq!(
+            state.selected_tag().map(|item| item.tag),
+            Some("project/basalt".into())
+        );
+        assert!(!state.back().visible);
+    }
+}
@@ -1,10 +1,27 @@
-/// A wrapper type representing the number of characters in a string. **All** characters are
-/// counted for.
+use basalt_core::markdown;
+use unicode_segmentation::UnicodeSegmentation;
+
+/// A wrapper type representing the number of characters in a string.
 ///
-/// Character count can be created from an `usize` directly or computed from a `&str`.
+/// Character count can be created from an `usize` directly or computed from a `&str`, in which
+/// case the characters of the Markdown text are counted as in [`markdown::counts`]. Converting
+/// from a `&str` counts Unicode scalar values, so e.g. a flag emoji counts as two characters. Use
+/// [`CharCount::graphemes`] to count the characters as the user perceives them.
 #[derive(Default, Clone, Debug, PartialEq)]
 pub struct CharCount(usize);
 
+impl CharCount {
+    /// Counts the extended grapheme clusters of the Markdown text, e.g. `🇫🇮` and `e\u{301}` are
+    /// a single character each.
+    pub fn graphemes(value: &str) -> Self {
+        markdown::prose(&markdown::from_str(value))
+            .iter()
+            .map(|node| node.content.graphemes(true).count())
+            .sum::<usize>()
+            .into()
+    }
+}
+
 impl From<usize> for CharCount {
     fn from(value: usize) -> Self {
         Self(value)
@@ -13,7 +30,7 @@ impl From<usize> for CharCount {
 
 impl From<&str> for CharCount {
     fn from(value: &str) -> Self {
-        value.chars().count().into()
+        markdown::counts(&markdown::from_str(value)).chars.into()
     }
 }
 
@@ -25,10 +42,8 @@ impl From<CharCount> for usize {
 
 /// A wrapper type representing the number of words in a string.
 ///
-/// Can be created from a `usize` directly or computed from a `&str` by counting the number of
-/// whitespace-separated words, after removing special markdown characters.
-///
-/// markdown characters: * _ ` < > ? ! [ ] ( ) = ~ # +
+/// Can be created from a `usize` directly or computed from a `&str`, in which case the words of
+/// the Markdown text are counted as in [`markdown::counts`].
 #[derive(Default, Clone, Debug, PartialEq)]
 pub struct WordCount(usize);
 
@@ -46,15 +61,7 @@ impl From<usize> for WordCount {
 
 impl From<&str> for WordCount {
     fn from(value: &str) -> Self {
-        let special_symbols = [
-            '*', '_', '`', '<', '>', '?', '!', '[', ']', '(', ')', '=', '~', '#', '+',
-        ];
-
-        value
-            .replace(special_symbols, "")
-            .split_whitespace()
-            .count()
-            .into()
+        markdown::counts(&markdown::from_str(value)).words.into()
     }
 }
 
@@ -78,7 +85,7 @@ mod tests {
                 ##### Heading 5
 
                 ###### Heading 6"#},
-                (WordCount(12), CharCount(91)),
+                (WordCount(12), CharCount(54)),
             ),
             (
                 indoc! { r#"## Tasks
@@ -88,7 +95,7 @@ mod tests {
                 - [x] Completed task
 
                 - [?] Completed task"#},
-                (WordCount(10), CharCount(64)),
+                (WordCount(6), CharCount(41)),
             ),
             (
                 indoc! {r#"## Quotes
@@ -98,7 +105,27 @@ mod tests {
                 > Human beings face ever more complex and urgent problems, and their effectiveness in dealing with these problems is a matter that is critical to the stability and continued progress of society.
                 >
                 >- Doug Engelbart, 1961"#},
-                (WordCount(47), CharCount(294)),
+                (WordCount(46), CharCount(275)),
+            ),
+            (
+                indoc! {r#"## Code
+
+                Only the prose is counted, not the code.
+
+                ```rust
+                fn main() {
+                    println!("Hello, world!");
+                }
+                ```"#},
+                (WordCount(9), CharCount(44)),
+            ),
+            (
+                indoc! {r#"---
+                tags: [notes]
+                ---
+
+                Read the [guide](https://example.com/guide) first."#},
+                (WordCount(4), CharCount(21)),
             ),
         ];
 
@@ -110,4 +137,51 @@ mod tests {
             )
         });
     }
+
+    #[test]
+    fn test_word_count_skips_frontmatter_and_code() {
+        let prose = "# Setup\n\nInstall the tools before running the script.";
+        let note = format!(
+            "---\ntags: [setup, tools]\naliases: [Install guide]\n---\n\n{prose}\n\n```sh\n{}```",
+            "cargo install --locked basalt-tui\n".repeat(50)
+        );
+
+        assert_eq!(WordCount::from(note.as_str()), WordCount::from(prose));
+        assert_eq!(WordCount::from(prose), WordCount(8));
+    }
+
+    #[test]
+    fn test_char_count_graphemes() {
+        let tests = [
+            ("Hello, world!", WordCount(2), CharCount(13), CharCount(13)),
+            ("Suomi 🇫🇮", WordCount(1), CharCount(8), CharCount(7)),
+            ("👩‍💻 writes", WordCount(1), CharCount(10), CharCount(8)),
+            (
+                "Cafe\u{301} au lait",
+                WordCount(3),
+                CharCount(13),
+                CharCount(12),
+            ),
+            (
+                "# 日本語のテキスト",
+                WordCount(1),
+                CharCount(8),
+                CharCount(8),
+            ),
+            (
+                "> 한국어 **본문**",
+                WordCount(2),
+                CharCount(6),
+                CharCount(6),
+            ),
+        ];
+
+        tests
+            .into_iter()
+            .for_each(|(input, words, chars, graphemes)| {
+                assert_eq!(WordCount::from(input), words, "With input {input}");
+                assert_eq!(CharCount::from(input), chars, "With input {input}");
+                assert_eq!(CharCount::graphemes(input), graphemes, "With input {input}");
+            });
+    }
 }
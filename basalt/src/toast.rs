@@ -0,0 +1,153 @@
+use std::time::Duration;
+
+use ratatui::{
+    buffer::Buffer,
+    layout::{Constraint, Flex, Layout, Rect},
+    style::{Color, Stylize},
+    text::Line,
+    widgets::{Clear, StatefulWidget, Widget},
+};
+
+/// How long a toast is shown before it is dismissed automatically.
+pub const DEFAULT_TOAST_DURATION: Duration = Duration::from_secs(3);
+
+#[derive(Debug, Default, Clone, Copy, PartialEq)]
+pub enum ToastKind {
+    #[default]
+    Info,
+    Warning,
+    Error,
+}
+
+impl ToastKind {
+    fn symbol(&self) -> &'static str {
+        match self {
+            ToastKind::Info => "ℹ",
+            ToastKind::Warning => "⚠",
+            ToastKind::Error => "✖",
+        }
+    }
+
+    fn color(&self) -> Color {
+        match self {
+            ToastKind::Info => Color::Blue,
+            ToastKind::Warning => Color::Yellow,
+            ToastKind::Error => Color::Red,
+        }
+    }
+}
+
+/// A short-lived notification shown to the user, e.g. when saving a note fails.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Toast {
+    pub message: String,
+    pub kind: ToastKind,
+    /// The number of ticks left until the toast expires. The app ticks in a fixed interval while
+    /// a toast is shown.
+    pub ticks: u32,
+}
+
+impl Toast {
+    /// Creates a toast that expires after the given number of ticks.
+    pub fn new(kind: ToastKind, message: &str, ticks: u32) -> Self {
+        Self {
+            message: message.to_string(),
+            kind,
+            ticks,
+        }
+    }
+
+    /// Counts down one tick.
+    pub fn tick(self) -> Self {
+        Self {
+            ticks: self.ticks.saturating_sub(1),
+            ..self
+        }
+    }
+
+    pub fn is_expired(&self) -> bool {
+        self.ticks == 0
+    }
+}
+
+/// Renders a toast as a single line pop-up at the bottom of the given area, leaving the last row
+/// for the status bar.
+#[derive(Default)]
+pub struct ToastPopup;
+
+impl StatefulWidget for ToastPopup {
+    type State = Toast;
+
+    fn render(self, area: Rect, buf: &mut Buffer, state: &mut Self::State) {
+        let line = Line::from(format!(" {} {} ", state.kind.symbol(), state.message))
+            .bg(state.kind.color())
+            .black();
+
+        let [_, row, _] = Layout::vertical([
+            Constraint::Fill(1),
+            Constraint::Length(1),
+            Constraint::Length(1),
+        ])
+        .areas(area);
+
+        let width = (line.width() as u16).min(area.width.saturating_sub(2));
+        let [popup_area] = Layout::horizontal([Constraint::Length(width)])
+            .flex(Flex::Center)
+            .areas(row);
+
+        Clear.render(popup_area, buf);
+        line.render(popup_area, buf);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use insta::assert_snapshot;
+    use ratatui::{backend::TestBackend, Terminal};
+
+    #[test]
+    fn test_render_toast() {
+        let tests = [
+            Toast::new(ToastKind::Info, "Note saved", 1),
+            Toast::new(ToastKind::Warning, "Key binding conflicts found", 1),
+            Toast::new(
+                ToastKind::Error,
+                "Failed to save note: permission denied",
+                1,
+            ),
+        ];
+
+        let mut terminal = Terminal::new(TestBackend::new(50, 5)).unwrap();
+
+        tests.into_iter().for_each(|mut toast| {
+            _ = terminal.clear();
+            terminal
+                .draw(|frame| ToastPopup.render(frame.area(), frame.buffer_mut(), &mut toast))
+                .unwrap();
+
+            let buffer = terminal.backend().buffer();
+            let cell = buffer
+                .content()
+                .iter()
+                .find(|cell| cell.symbol() == toast.kind.symbol())
+                .unwrap();
+            assert_eq!(cell.bg, toast.kind.color());
+
+            assert_snapshot!(terminal.backend());
+        });
+    }
+
+    #[test]
+    fn test_toast_expiry() {
+        let toast = Toast::new(ToastKind::Info, "Note saved", 2);
+        assert!(!toast.is_expired());
+
+        let toast = toast.tick();
+        assert!(!toast.is_expired());
+
+        let toast = toast.tick();
+        assert!(toast.is_expired());
+        assert!(toast.tick().is_expired());
+    }
+}
@@ -0,0 +1,162 @@
+use ratatui::{buffer::Buffer, layout::Rect, style::Color};
+
+/// How many frames a transition lasts by default.
+pub const DEFAULT_TRANSITION_FRAMES: u8 = 5;
+
+/// The first and last colors of the grayscale ramp in the 256 color palette.
+const GRAYSCALE_START: u8 = 232;
+const GRAYSCALE_END: u8 = 255;
+
+#[derive(Debug, Default, Clone, Copy, PartialEq)]
+pub enum TransitionKind {
+    /// Fades the screen in from a blank screen.
+    #[default]
+    FadeIn,
+}
+
+/// An animation between screens, which advances one frame on every tick.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Transition {
+    pub kind: TransitionKind,
+    pub frame: u8,
+    pub total_frames: u8,
+}
+
+impl Transition {
+    pub fn new(kind: TransitionKind) -> Self {
+        Self {
+            kind,
+            frame: 0,
+            total_frames: DEFAULT_TRANSITION_FRAMES,
+        }
+    }
+
+    pub fn next(self) -> Self {
+        Self {
+            frame: self.frame.saturating_add(1).min(self.total_frames),
+            ..self
+        }
+    }
+
+    pub fn is_finished(&self) -> bool {
+        self.frame >= self.total_frames
+    }
+
+    /// Returns the progress of the transition between `0.0` and `1.0`.
+    fn progress(&self) -> f32 {
+        if self.total_frames == 0 {
+            return 1.0;
+        }
+
+        f32::from(self.frame) / f32::from(self.total_frames)
+    }
+
+    /// Blends the screen rendered into the buffer with a blank screen according to the progress
+    /// of the transition.
+    pub fn apply(&self, area: Rect, buf: &mut Buffer) {
+        if self.is_finished() {
+            return;
+        }
+
+        let progress = self.progress();
+
+        match self.kind {
+            TransitionKind::FadeIn => area.positions().for_each(|position| {
+                if let Some(cell) = buf.cell_mut(position) {
+                    cell.fg = fade_foreground(cell.fg, progress);
+                    cell.bg = fade_background(cell.bg, progress);
+                }
+            }),
+        }
+    }
+}
+
+fn scale(value: u8, progress: f32) -> u8 {
+    (f32::from(value) * progress).round() as u8
+}
+
+/// Fades the foreground color from black. Only RGB colors can be scaled, so other colors are
+/// approximated with the grayscale ramp of the 256 color palette.
+fn fade_foreground(color: Color, progress: f32) -> Color {
+    match color {
+        Color::Rgb(r, g, b) => {
+            Color::Rgb(scale(r, progress), scale(g, progress), scale(b, progress))
+        }
+        _ => Color::Indexed(GRAYSCALE_START + scale(GRAYSCALE_END - GRAYSCALE_START, progress)),
+    }
+}
+
+/// Fades the background color from the terminal background. Colors that cannot be scaled are
+/// shown only once the transition has finished.
+fn fade_background(color: Color, progress: f32) -> Color {
+    match color {
+        Color::Rgb(r, g, b) => {
+            Color::Rgb(scale(r, progress), scale(g, progress), scale(b, progress))
+        }
+        _ => Color::Reset,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use ratatui::style::{Style, Stylize};
+
+    use super::*;
+
+    #[test]
+    fn test_transition_frames() {
+        let transition = Transition::new(TransitionKind::FadeIn);
+        assert!(!transition.is_finished());
+
+        let transition = (0..DEFAULT_TRANSITION_FRAMES).fold(transition, |transition, _| {
+            assert!(!transition.is_finished());
+            transition.next()
+        });
+
+        assert!(transition.is_finished());
+        assert_eq!(transition.next().frame, DEFAULT_TRANSITION_FRAMES);
+    }
+
+    #[test]
+    fn test_fade_in() {
+        let area = Rect::new(0, 0, 2, 1);
+        let mut buffer = Buffer::empty(area);
+        buffer.set_string(0, 0, "a", Style::new().blue().on_red());
+        buffer.set_string(1, 0, "b", Style::new().fg(Color::Rgb(200, 100, 50)));
+
+        let fade = |frame: u8| {
+            let mut buffer = buffer.clone();
+            Transition {
+                frame,
+                ..Transition::new(TransitionKind::FadeIn)
+            }
+            .apply(area, &mut buffer);
+            [
+                (buffer[(0, 0)].fg, buffer[(0, 0)].bg),
+                (buffer[(1, 0)].fg, buffer[(1, 0)].bg),
+            ]
+        };
+
+        assert_eq!(
+            fade(0),
+            [
+                (Color::Indexed(232), Color::Reset),
+                (Color::Rgb(0, 0, 0), Color::Reset)
+            ]
+        );
+        assert_eq!(
+            fade(3),
+            [
+                (Color::Indexed(246), Color::Reset),
+                (Color::Rgb(120, 60, 30), Color::Reset)
+            ]
+        );
+        assert_eq!(
+            fade(DEFAULT_TRANSITION_FRAMES),
+            [
+                (Color::Blue, Color::Red),
+                (Color::Rgb(200, 100, 50), Color::Reset)
+            ]
+        );
+    }
+}
@@ -1,36 +1,207 @@
-use std::marker::PhantomData;
+use std::{
+    marker::PhantomData,
+    time::{SystemTime, UNIX_EPOCH},
+};
 
 use basalt_core::obsidian::Vault;
 use ratatui::{
     buffer::Buffer,
-    layout::Rect,
+    layout::{Constraint, Layout, Rect},
     style::{Color, Style, Stylize},
-    widgets::{Block, BorderType, List, ListItem, ListState, StatefulWidgetRef},
+    text::{Line, Span},
+    widgets::{Block, BorderType, List, ListItem, ListState, StatefulWidgetRef, Widget},
 };
+use textwrap::core::display_width;
+
+use crate::config::Theme;
+
+/// Returns `true` if the directory of the vault exists. Vaults that were moved or deleted outside
+/// of Obsidian are still listed, but cannot be selected.
+fn is_available(vault: &Vault) -> bool {
+    vault.path.is_dir()
+}
+
+/// Returns the current time in milliseconds since the Unix epoch, the unit of [`Vault::ts`].
+fn now() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|duration| duration.as_millis() as u64)
+        .unwrap_or_default()
+}
+
+/// Formats the time between the timestamps in milliseconds, e.g. `3 days ago`. Returns [`None`]
+/// for vaults that have no timestamp.
+pub fn last_opened(ts: u64, now: u64) -> Option<String> {
+    if ts == 0 {
+        return None;
+    }
+
+    let plural = |count: u64, unit: &str| match count {
+        1 => format!("1 {unit} ago"),
+        count => format!("{count} {unit}s ago"),
+    };
+
+    let minutes = now.saturating_sub(ts) / 60_000;
+    let hours = minutes / 60;
+    let days = hours / 24;
+
+    Some(match days {
+        0 if hours > 0 => plural(hours, "hour"),
+        0 if minutes > 0 => plural(minutes, "minute"),
+        0 => "just now".to_string(),
+        1 => "yesterday".to_string(),
+        2..7 => plural(days, "day"),
+        7..30 => plural(days / 7, "week"),
+        30..365 => plural(days / 30, "month"),
+        _ => plural(days / 365, "year"),
+    })
+}
+
+/// Shortens the text to the given display width by replacing the start with `…`, so that the
+/// last components of a path stay visible.
+fn truncate_start(text: &str, width: usize) -> String {
+    if display_width(text) <= width {
+        return text.to_string();
+    }
+
+    let mut used_width = 1;
+    let mut end: Vec<char> = text
+        .chars()
+        .rev()
+        .take_while(|c| {
+            used_width += display_width(c.encode_utf8(&mut [0; 4]));
+            used_width <= width
+        })
+        .collect();
+    end.reverse();
+
+    ['…'].into_iter().chain(end).take(width).collect()
+}
+
+/// Shortens the text to the given display width by replacing the end with `…`.
+fn truncate_end(text: &str, width: usize) -> String {
+    if display_width(text) <= width {
+        return text.to_string();
+    }
+
+    let mut used_width = 1;
+    let start = text.chars().take_while(|c| {
+        used_width += display_width(c.encode_utf8(&mut [0; 4]));
+        used_width <= width
+    });
+
+    start.chain(['…']).take(width).collect()
+}
+
+/// Pads the text with spaces to the given display width.
+fn pad(text: String, width: usize) -> String {
+    let padding = width.saturating_sub(display_width(&text));
+    text + &" ".repeat(padding)
+}
 
 #[derive(Debug, Default, Clone, PartialEq)]
 pub struct VaultSelectorState<'a> {
     pub(crate) selected_item_index: Option<usize>,
     pub(crate) items: Vec<&'a Vault>,
+    /// Whether the vault at the same index of the items is available, see [`is_available`]. The
+    /// directories are checked once when the state is created instead of on every render.
+    available: Vec<bool>,
+    /// Only the vaults with a name containing the query are listed. The list state indexes into
+    /// the filtered vaults.
+    pub(crate) search_query: String,
+    /// The time the last opened times are relative to, in milliseconds since the Unix epoch.
+    pub(crate) now: u64,
     list_state: ListState,
 }
 
 impl<'a> VaultSelectorState<'a> {
     pub fn new(items: Vec<&'a Vault>) -> Self {
         VaultSelectorState {
+            available: items.iter().map(|vault| is_available(vault)).collect(),
             items,
             selected_item_index: None,
+            search_query: String::new(),
+            now: now(),
             list_state: ListState::default().with_selected(Some(0)),
         }
     }
 
+    /// Selects the highlighted vault. The selected index refers to the unfiltered items. An
+    /// unavailable vault is not selected, see [`is_available`].
     pub fn select(&self) -> Self {
         Self {
-            selected_item_index: self.list_state.selected(),
+            selected_item_index: self
+                .list_state
+                .selected()
+                .and_then(|index| self.filtered_indices().get(index).copied())
+                .filter(|index| self.available[*index]),
             ..self.clone()
         }
     }
 
+    /// Moves the highlight to the item at the given index without selecting it.
+    pub fn highlight(self, index: usize) -> Self {
+        Self {
+            list_state: self.list_state.with_selected(Some(index)),
+            ..self
+        }
+    }
+
+    pub fn search_query(&self) -> &str {
+        &self.search_query
+    }
+
+    /// Returns the indices of the items with a name containing the search query, ignoring case.
+    pub fn filtered_indices(&self) -> Vec<usize> {
+        let query = self.search_query.to_lowercase();
+
+        self.items
+            .iter()
+            .enumerate()
+            .filter(|(_, vault)| vault.name.to_lowercase().contains(&query))
+            .map(|(index, _)| index)
+            .collect()
+    }
+
+    /// Returns the items with a name containing the search query, ignoring case.
+    pub fn filtered_items(&self) -> Vec<&'a Vault> {
+        self.filtered_indices()
+            .into_iter()
+            .map(|index| self.items[index])
+            .collect()
+    }
+
+    fn with_search_query(self, search_query: String) -> Self {
+        let state = Self {
+            search_query,
+            ..self
+        };
+
+        // The highlight is moved to the first match, since the previous position may no longer
+        // be visible.
+        let selected = (!state.filtered_indices().is_empty()).then_some(0);
+
+        Self {
+            list_state: state.list_state.clone().with_selected(selected),
+            ..state
+        }
+    }
+
+    pub fn filter_input(self, c: char) -> Self {
+        let search_query = format!("{}{c}", self.search_query);
+        self.with_search_query(search_query)
+    }
+
+    pub fn filter_backspace(self) -> Self {
+        let mut search_query = self.search_query.clone();
+        search_query.pop();
+        self.with_search_query(search_query)
+    }
+
+    pub fn clear_filter(self) -> Self {
+        self.with_search_query(String::new())
+    }
+
     pub fn items(self) -> Vec<&'a Vault> {
         self.items
     }
@@ -44,10 +215,12 @@ impl<'a> VaultSelectorState<'a> {
     }
 
     pub fn next(mut self) -> Self {
+        let len = self.filtered_indices().len();
         let index = self
             .list_state
             .selected()
-            .map(|i| (i + 1).min(self.items.len() - 1));
+            .filter(|_| len > 0)
+            .map(|i| (i + 1).min(len - 1));
 
         self.list_state.select(index);
 
@@ -58,7 +231,9 @@ impl<'a> VaultSelectorState<'a> {
     }
 
     pub fn previous(mut self) -> Self {
-        self.list_state.select_previous();
+        if self.list_state.selected().is_some() {
+            self.list_state.select_previous();
+        }
 
         Self {
             list_state: self.list_state,
@@ -69,36 +244,180 @@ impl<'a> VaultSelectorState<'a> {
 
 #[derive(Default)]
 pub struct VaultSelector<'a> {
+    theme: Theme,
     _lifetime: PhantomData<&'a ()>,
 }
 
+impl VaultSelector<'_> {
+    pub fn with_theme(self, theme: &Theme) -> Self {
+        Self {
+            theme: *theme,
+            ..self
+        }
+    }
+}
+
 impl<'a> StatefulWidgetRef for VaultSelector<'a> {
     type State = VaultSelectorState<'a>;
 
     fn render_ref(&self, area: Rect, buf: &mut Buffer, state: &mut Self::State) {
-        let items: Vec<ListItem> = state
-            .items
+        let block = Block::bordered()
+            .dark_gray()
+            .title(" Vaults ")
+            .title_style(Style::default().italic().bold())
+            .border_type(BorderType::Rounded);
+
+        let inner_area = block.inner(area);
+        block.render(area, buf);
+
+        let list_area = if state.search_query.is_empty() {
+            inner_area
+        } else {
+            let [query_area, list_area] =
+                Layout::vertical([Constraint::Length(1), Constraint::Fill(1)]).areas(inner_area);
+
+            Line::from(format!(" / {}", state.search_query))
+                .fg(Color::default())
+                .dim()
+                .render(query_area, buf);
+
+            list_area
+        };
+
+        let vaults: Vec<(&Vault, bool)> = state
+            .filtered_indices()
+            .into_iter()
+            .map(|index| (state.items[index], state.available[index]))
+            .collect();
+
+        let labels: Vec<String> = vaults
+            .iter()
+            .map(|(vault, available)| match available {
+                true => last_opened(vault.ts, state.now).unwrap_or_default(),
+                false => "unavailable".to_string(),
+            })
+            .collect();
+
+        // The highlight symbol and the open vault marker take up the first three columns, and
+        // the last column is left empty to keep the labels off the border
+        let width = usize::from(list_area.width).saturating_sub(4);
+        let label_width = labels
             .iter()
-            .map(|item| {
-                if item.open {
-                    ListItem::new(format!("◆ {}", item.name))
-                } else {
-                    ListItem::new(format!("  {}", item.name))
+            .map(|label| display_width(label))
+            .max()
+            .unwrap_or_default();
+        let name_width = vaults
+            .iter()
+            .map(|(vault, _)| display_width(&vault.name))
+            .max()
+            .unwrap_or_default()
+            .min(width / 2);
+        let path_width = width.saturating_sub(name_width + label_width + 3);
+
+        let items: Vec<ListItem> = vaults
+            .into_iter()
+            .zip(labels)
+            .map(|((vault, available), label)| {
+                let marker = if vault.open { "◆ " } else { "  " };
+                let name = truncate_end(&vault.name, name_width);
+                let path = truncate_start(&vault.path.to_string_lossy(), path_width);
+
+                let line = Line::from(vec![
+                    Span::from(marker),
+                    Span::from(pad(name, name_width + 2)),
+                    Span::from(pad(path, path_width + 1)).dim(),
+                    Span::from(format!("{label:>label_width$}")).dim(),
+                ]);
+
+                match available {
+                    true => ListItem::new(line),
+                    false => ListItem::new(line).dark_gray(),
                 }
             })
             .collect();
 
         List::new(items)
-            .block(
-                Block::bordered()
-                    .dark_gray()
-                    .title(" Vaults ")
-                    .title_style(Style::default().italic().bold())
-                    .border_type(BorderType::Rounded),
-            )
             .fg(Color::default())
-            .highlight_style(Style::new().reversed().dark_gray())
+            .highlight_style(Style::new().reversed().fg(self.theme.selection))
             .highlight_symbol(" ")
-            .render_ref(area, buf, &mut state.list_state);
+            .render_ref(list_area, buf, &mut state.list_state);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::env;
+
+    use super::*;
+
+    #[test]
+    fn test_last_opened() {
+        const NOW: u64 = 1760000000000;
+        const MINUTE: u64 = 60 * 1000;
+        const DAY: u64 = 24 * 60 * MINUTE;
+
+        let tests = [
+            (0, None),
+            (NOW - 30 * 1000, Some("just now")),
+            (NOW - MINUTE, Some("1 minute ago")),
+            (NOW - 90 * MINUTE, Some("1 hour ago")),
+            (NOW - DAY, Some("yesterday")),
+            (NOW - 6 * DAY, Some("6 days ago")),
+            (NOW - 14 * DAY, Some("2 weeks ago")),
+            (NOW - 65 * DAY, Some("2 months ago")),
+            (NOW - 800 * DAY, Some("2 years ago")),
+            (NOW + DAY, Some("just now")),
+        ];
+
+        tests.into_iter().for_each(|(ts, expected)| {
+            assert_eq!(last_opened(ts, NOW).as_deref(), expected, "ts {ts}");
+        });
+    }
+
+    #[test]
+    fn test_truncate() {
+        assert_eq!(truncate_start("/home/user/Notes", 16), "/home/user/Notes");
+        assert_eq!(truncate_start("/home/user/Notes", 8), "…r/Notes");
+        assert_eq!(truncate_start("/home/user/ノート", 8), "…/ノート");
+        assert_eq!(truncate_start("/home/user/Notes", 0), "");
+        assert_eq!(truncate_end("Research Papers", 9), "Research…");
+        assert_eq!(truncate_end("Research Papers", 1), "…");
+    }
+
+    #[test]
+    fn test_filter_clamps_selection() {
+        let vaults = ["Notes", "Work", "Personal", "Lessons"].map(|name| Vault {
+            name: name.to_string(),
+            path: env::temp_dir(),
+            ..Default::default()
+        });
+
+        let state = VaultSelectorState::new(vaults.iter().collect())
+            .next()
+            .next()
+            .next()
+            .filter_input('o')
+            .filter_input('N');
+
+        assert_eq!(state.filtered_indices(), vec![2, 3]);
+        assert_eq!(
+            state.select().selected(),
+            Some(2),
+            "Highlight moves to the first match"
+        );
+        assert_eq!(
+            state.clone().next().next().next().select().selected(),
+            Some(3),
+            "Highlight stays on the last match"
+        );
+
+        let state = state.filter_input('x');
+        assert!(state.filtered_items().is_empty());
+        assert_eq!(state.clone().next().select().selected(), None);
+
+        let state = state.filter_backspace();
+        assert_eq!(state.search_query(), "oN");
+        assert_eq!(state.select().selected(), Some(2));
+        assert_eq!(state.clear_filter().filtered_indices().len(), 4);
     }
 }
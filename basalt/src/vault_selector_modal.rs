@@ -7,7 +7,10 @@ use ratatui::{
     widgets::{Clear, ScrollbarState, StatefulWidget, StatefulWidgetRef, Widget},
 };
 
-use crate::vault_selector::{VaultSelector, VaultSelectorState};
+use crate::{
+    config::Theme,
+    vault_selector::{VaultSelector, VaultSelectorState},
+};
 
 #[derive(Debug, Default, Clone, PartialEq)]
 pub struct VaultSelectorModalState<'a> {
@@ -27,6 +30,10 @@ impl<'a> VaultSelectorModalState<'a> {
         self.vault_selector_state.selected()
     }
 
+    pub fn search_query(&self) -> &str {
+        self.vault_selector_state.search_query()
+    }
+
     pub fn select(&self) -> Self {
         Self {
             vault_selector_state: self.vault_selector_state.clone().select(),
@@ -52,27 +59,61 @@ impl<'a> VaultSelectorModalState<'a> {
         }
     }
 
-    pub fn hide(&self) -> Self {
+    pub fn filter_input(&self, c: char) -> Self {
         Self {
-            visible: false,
+            vault_selector_state: self.vault_selector_state.clone().filter_input(c),
             ..self.clone()
         }
     }
 
-    pub fn toggle_visibility(&self) -> Self {
+    pub fn filter_backspace(&self) -> Self {
+        Self {
+            vault_selector_state: self.vault_selector_state.clone().filter_backspace(),
+            ..self.clone()
+        }
+    }
+
+    pub fn clear_filter(&self) -> Self {
         Self {
-            visible: !self.visible,
+            vault_selector_state: self.vault_selector_state.clone().clear_filter(),
             ..self.clone()
         }
     }
+
+    /// Hides the modal and clears the filter, so that the modal opens with all vaults listed.
+    pub fn hide(&self) -> Self {
+        Self {
+            visible: false,
+            ..self.clear_filter()
+        }
+    }
+
+    pub fn toggle_visibility(&self) -> Self {
+        if self.visible {
+            self.hide()
+        } else {
+            Self {
+                visible: true,
+                ..self.clone()
+            }
+        }
+    }
 }
 
 #[derive(Debug, Default, Clone, PartialEq)]
 pub struct VaultSelectorModal<'a> {
+    theme: Theme,
     _lifetime: PhantomData<&'a ()>,
 }
 
 impl VaultSelectorModal<'_> {
+    pub fn with_theme(self, theme: &Theme) -> Self {
+        Self {
+            theme: *theme,
+            ..self
+        }
+    }
+
     fn modal_area(self, area: Rect) -> Rect {
         let vertical = Layout::vertical([Constraint::Percentage(50)]).flex(Flex::Center);
         let horizontal = Layout::horizontal([Constraint::Length(60)]).flex(Flex::Center);
@@ -89,9 +130,14 @@ impl<'a> StatefulWidget for VaultSelectorModal<'a> {
     where
         Self: Sized,
     {
+        let theme = self.theme;
         let area = self.modal_area(area);
         Widget::render(Clear, area, buf);
-        VaultSelector::default().render_ref(area, buf, &mut state.vault_selector_state);
+        VaultSelector::default().with_theme(&theme).render_ref(
+            area,
+            buf,
+            &mut state.vault_selector_state,
+        );
     }
 }
 
@@ -164,3 +210,50 @@ impl<'a> ModalState<'a> {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use insta::assert_snapshot;
+    use ratatui::{backend::TestBackend, Terminal};
+
+    #[test]
+    fn test_filtered_vaults() {
+        let vaults = ["Notes", "Work", "Personal", "Lessons"].map(|name| Vault {
+            name: name.to_string(),
+            path: ".".into(),
+            ..Default::default()
+        });
+
+        let mut state = VaultSelectorModalState::new(vaults.iter().collect())
+            .toggle_visibility()
+            .filter_input('O')
+            .filter_input('n');
+
+        assert_eq!(state.search_query(), "On");
+        assert_eq!(
+            state.vault_selector_state.filtered_indices(),
+            vec![2, 3],
+            "Matches Personal and Lessons ignoring case"
+        );
+
+        let selected = state.next().next().select().selected();
+        assert_eq!(selected, Some(3));
+        assert_eq!(
+            state.clone().get_item(3).map(|vault| vault.name.as_str()),
+            Some("Lessons")
+        );
+
+        let mut terminal = Terminal::new(TestBackend::new(60, 12)).unwrap();
+        terminal
+            .draw(|frame| {
+                VaultSelectorModal::default().render(frame.area(), frame.buffer_mut(), &mut state)
+            })
+            .unwrap();
+        assert_snapshot!(terminal.backend());
+
+        let state = state.hide();
+        assert_eq!(state.search_query(), "");
+        assert_eq!(state.vault_selector_state.filtered_indices().len(), 4);
+    }
+}